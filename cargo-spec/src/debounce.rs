@@ -0,0 +1,88 @@
+//! Pure debounce logic for `cargo spec watch`, decoupled from real time
+//! (and so unit-testable without actually sleeping): feed it the instant
+//! of every change observed, and it reports once enough quiet time has
+//! passed to act, collapsing a rapid burst of saves into one
+//! regeneration instead of one per write.
+
+use std::time::{Duration, Instant};
+
+pub struct Debouncer {
+    debounce: Duration,
+    pending_since: Option<Instant>,
+}
+
+impl Debouncer {
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            pending_since: None,
+        }
+    }
+
+    /// Call whenever a change is observed, at time `now`.
+    pub fn observe(&mut self, now: Instant) {
+        self.pending_since = Some(now);
+    }
+
+    /// Call on every poll tick at time `now`. Returns `true` exactly once
+    /// per [`observe`](Self::observe) call, once `debounce` has elapsed
+    /// since it with no further `observe` in between; clears the pending
+    /// state so the next tick goes back to returning `false` until
+    /// another change comes in.
+    pub fn ready(&mut self, now: Instant) -> bool {
+        match self.pending_since {
+            Some(since) if now.duration_since(since) >= self.debounce => {
+                self.pending_since = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_not_ready_before_any_change_is_observed() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+        assert!(!debouncer.ready(Instant::now()));
+    }
+
+    #[test]
+    fn is_not_ready_until_the_debounce_window_elapses() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+        let t0 = Instant::now();
+        debouncer.observe(t0);
+        assert!(!debouncer.ready(t0 + Duration::from_millis(50)));
+    }
+
+    #[test]
+    fn is_ready_once_the_debounce_window_elapses() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+        let t0 = Instant::now();
+        debouncer.observe(t0);
+        assert!(debouncer.ready(t0 + Duration::from_millis(150)));
+    }
+
+    #[test]
+    fn fires_only_once_per_observed_change() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+        let t0 = Instant::now();
+        debouncer.observe(t0);
+        assert!(debouncer.ready(t0 + Duration::from_millis(150)));
+        assert!(!debouncer.ready(t0 + Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn a_change_during_the_debounce_window_resets_it() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+        let t0 = Instant::now();
+        debouncer.observe(t0);
+        let t1 = t0 + Duration::from_millis(50);
+        debouncer.observe(t1);
+        assert!(!debouncer.ready(t1 + Duration::from_millis(80)));
+        assert!(debouncer.ready(t1 + Duration::from_millis(150)));
+    }
+}
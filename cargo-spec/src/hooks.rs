@@ -0,0 +1,88 @@
+//! Renders the `pre-commit` hook script `cargo spec install-hook` writes.
+//! Kept as a pure string-builder, independent of the filesystem, so the
+//! generated script is unit-testable without actually installing a hook.
+
+use libspec::trace::{CheckMode, HookConfig};
+
+/// Builds the full `pre-commit` hook script for `config`. Each check with
+/// [`CheckMode::Off`] is omitted entirely; `Block` and `Warn` checks run,
+/// differing only in whether a failure aborts the commit.
+pub fn render_pre_commit_hook(config: &HookConfig) -> String {
+    let mut script = String::from(
+        "#!/bin/sh\n\
+         # Installed by `cargo spec install-hook`. To change which checks\n\
+         # block the commit versus only warn, edit the [hooks] table in\n\
+         # libspec.toml and reinstall.\n\
+         set -e\n\
+         \n\
+         changed=$(git diff --cached --name-only --diff-filter=ACMR)\n\
+         if [ -z \"$changed\" ]; then\n\
+         \texit 0\n\
+         fi\n\
+         \n",
+    );
+
+    append_check(&mut script, config.lint, "cargo spec check");
+    append_check(&mut script, config.coverage, "cargo spec report");
+
+    script
+}
+
+fn append_check(script: &mut String, mode: CheckMode, command: &str) {
+    match mode {
+        CheckMode::Off => {}
+        CheckMode::Block => {
+            script.push_str(&format!(
+                "if ! {command}; then\n\techo \"pre-commit: '{command}' failed\" >&2\n\texit 1\nfi\n\n"
+            ));
+        }
+        CheckMode::Warn => {
+            script.push_str(&format!(
+                "if ! {command}; then\n\techo \"pre-commit: '{command}' failed (warning only)\" >&2\nfi\n\n"
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_blocking_check_exits_nonzero_on_failure() {
+        let script = render_pre_commit_hook(&HookConfig {
+            lint: CheckMode::Block,
+            coverage: CheckMode::Off,
+        });
+        assert!(script.contains("if ! cargo spec check; then"));
+        assert!(script.contains("exit 1"));
+        assert!(!script.contains("cargo spec report"));
+    }
+
+    #[test]
+    fn a_warning_check_never_exits_nonzero() {
+        let script = render_pre_commit_hook(&HookConfig {
+            lint: CheckMode::Off,
+            coverage: CheckMode::Warn,
+        });
+        assert!(script.contains("if ! cargo spec report; then"));
+        assert!(script.contains("warning only"));
+        assert!(!script.contains("exit 1"));
+    }
+
+    #[test]
+    fn an_off_check_is_omitted_entirely() {
+        let script = render_pre_commit_hook(&HookConfig {
+            lint: CheckMode::Off,
+            coverage: CheckMode::Off,
+        });
+        assert!(!script.contains("cargo spec check"));
+        assert!(!script.contains("cargo spec report"));
+    }
+
+    #[test]
+    fn skips_both_checks_when_nothing_is_staged() {
+        let script = render_pre_commit_hook(&HookConfig::default());
+        assert!(script.contains("if [ -z \"$changed\" ]; then"));
+    }
+}
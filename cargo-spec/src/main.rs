@@ -0,0 +1,1245 @@
+//! `cargo spec` subcommand: wraps `libspec`'s validation, codegen,
+//! traceability, and diffing APIs behind eleven subcommands (`check`,
+//! `gen`, `report`, `diff`, `watch`, `install-hook`, `ci`, `fmt`, `new`,
+//! `external-index`, `serve`) so a team runs `cargo spec check` instead
+//! of calling the library directly for routine tasks.
+//! Discovers `libspec.toml` by walking up from the current directory, the
+//! same config file [`CoveragePolicy`] reads its `min_tests_per_tag`
+//! policy from, and reads its `spec_file` entry to find the spec itself.
+//! A global `--profile <name>` flag resolves `libspec.toml`'s
+//! `[profiles.<name>]` overrides (see [`CoveragePolicy::for_profile`])
+//! before `check`, `report`, and `install-hook` apply the policy. A
+//! repeatable global `--condition <name>` flag marks a named condition
+//! (e.g. `overdraft`, `eu`) active, so `gen` and `report` run the spec
+//! through [`libspec::spec::SpecDocument::for_conditions`] first,
+//! dropping requirements gated on a condition that isn't active. A global
+//! `--format json` flag switches `check`, `gen`, `report`, and `diff`
+//! from their human-readable output to [`libspec::json_report`]'s
+//! versioned JSON, so other tools can build on `cargo spec` output
+//! without scraping text. Parsed specs, generated code, and `ci`'s source
+//! scan are cached under `target/libspec/` (see [`libspec::cache::Cache`]),
+//! keyed by content hash, so an unchanged tree doesn't pay to redo that
+//! work on every invocation; a global `--no-cache` flag bypasses this.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use libspec::cache::Cache;
+use libspec::codegen::rust_trait;
+use libspec::external_index;
+use libspec::fmt as spec_fmt;
+use libspec::github_annotations;
+use libspec::graph;
+use libspec::json_report;
+use libspec::lint;
+use libspec::merge;
+use libspec::review;
+use libspec::risk_policy;
+use libspec::spec::{Query, SpecDocument};
+use libspec::trace::{self, CoverageMatrix, CoveragePolicy, ExitCategory, ReportFormat};
+use libspec::validate;
+
+mod debounce;
+mod hooks;
+mod scaffold;
+use debounce::Debouncer;
+
+/// How often the watch loop polls the spec file's mtime.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Parser)]
+#[command(name = "cargo-spec", bin_name = "cargo spec")]
+struct Cli {
+    /// Resolves `libspec.toml`'s `[profiles.<name>]` overrides (if any) on
+    /// top of its base config, e.g. `--profile ci` for stricter coverage
+    /// gates than a local `dev` run.
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    /// Marks a named condition (e.g. `overdraft`, `eu`) as active, so
+    /// `gen` and `report` run the spec through
+    /// [`libspec::spec::SpecDocument::for_conditions`] first, dropping any
+    /// requirement whose [`libspec::spec::Requirement::applies_when`]
+    /// names a condition not passed here. Repeatable; a requirement with
+    /// no conditions always applies regardless of what's passed.
+    #[arg(long = "condition", global = true)]
+    conditions: Vec<String>,
+    /// Switches `check`, `gen`, `report`, and `diff` to machine-readable
+    /// JSON output instead of their normal human-readable text.
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+    /// Skips the on-disk cache under `target/libspec/` for `check`, `gen`,
+    /// and `ci`, recomputing everything from scratch. Use this if a cache
+    /// entry is ever suspected stale, the way `cargo build --offline`
+    /// isn't normally needed but exists for when it is.
+    #[arg(long, global = true)]
+    no_cache: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// The `--format` flag's value. `Report` already has its own
+/// `ReportFormat` (text/HTML/JSON/CSV, set via `libspec.toml`) for the
+/// `report` subcommand specifically; this is the coarser switch shared by
+/// every subcommand that can emit JSON.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// An error from a subcommand, tagged with the [`ExitCategory`] that
+/// decides its exit code (see [`CoveragePolicy::exit_codes`]). Defaults
+/// to [`ExitCategory::ParseError`] for the setup/IO failures (a missing
+/// `libspec.toml`, an unreadable or unparseable spec file) that arise
+/// from plain `String` errors via `?`, since those are the most
+/// fundamental kind of failure `cargo spec` can hit.
+struct CliError {
+    category: ExitCategory,
+    message: String,
+}
+
+impl CliError {
+    fn new(category: ExitCategory, message: impl Into<String>) -> Self {
+        Self { category, message: message.into() }
+    }
+}
+
+impl From<String> for CliError {
+    fn from(message: String) -> Self {
+        Self::new(ExitCategory::ParseError, message)
+    }
+}
+
+impl From<&str> for CliError {
+    fn from(message: &str) -> Self {
+        Self::new(ExitCategory::ParseError, message)
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Validates the spec and reports meta-schema, lint, and graph issues.
+    Check,
+    /// Generates a Rust trait from the spec and prints it to stdout.
+    Gen {
+        trait_name: String,
+        /// Generate the `async fn`-based trait instead of the sync one.
+        #[arg(long)]
+        r#async: bool,
+        /// Generates twice from the same spec and fails if the two runs
+        /// don't produce byte-identical output, instead of printing the
+        /// generated code. Catches a backend accidentally depending on
+        /// iteration order, the system clock, or anything else that would
+        /// make a committed generated file diff for no real reason.
+        #[arg(long)]
+        verify_reproducible: bool,
+    },
+    /// Prints the traceability coverage report and checks it against policy.
+    Report,
+    /// Diffs two spec files and prints a changelog and version recommendation.
+    Diff { before: PathBuf, after: PathBuf },
+    /// Structurally three-way merges two edited copies of a spec file
+    /// against their common ancestor, joining on requirement id and
+    /// constraint code instead of diffing lines (see [`libspec::merge`]).
+    /// Writes the merged result back over `ours` and exits non-zero if
+    /// any id conflicted, matching the argument order and exit-code
+    /// convention `git` expects from a `merge.<driver>.driver` command
+    /// (`%O %A %B`, i.e. base, ours, theirs).
+    Merge { base: PathBuf, ours: PathBuf, theirs: PathBuf },
+    /// Lists requirement ids matching a [`libspec::spec::Query`], e.g.
+    /// `cargo spec query "kind:security status:approved covers:none"`.
+    /// `covers:` is answered from recorded `covers` trace records, the
+    /// way `report`'s coverage gate is.
+    Query { expr: String },
+    /// Appends a `#[test]` skeleton, tagged `#[covers("...")]`, for every
+    /// requirement the coverage report currently shows untested, to
+    /// `file` (see [`trace::generate_test_skeletons`]).
+    GenTests { file: PathBuf },
+    /// Appends an `assert_cmd`-based `#[test]` for every invocation of
+    /// every `[[cli_contract]]` in the spec (see
+    /// [`libspec::codegen::rust_cli_test`]) to `file`. The generated
+    /// tests need `assert_cmd` and `predicates` as dev-dependencies in
+    /// the project `file` lives in.
+    GenCliTests { file: PathBuf },
+    /// Watches the spec file and re-checks it on every change, printing a
+    /// changelog of what changed. Runs until interrupted (Ctrl-C).
+    Watch {
+        /// Quiet period after the last detected change before re-checking,
+        /// so a burst of saves collapses into one run.
+        #[arg(long, default_value_t = 300)]
+        debounce_ms: u64,
+    },
+    /// Installs a `pre-commit` git hook that runs `check`/`report` on
+    /// commits that touch staged files, per the `[hooks]` table in
+    /// `libspec.toml`.
+    InstallHook,
+    /// Prints lint findings and coverage gaps as GitHub Actions
+    /// `::error`/`::warning` workflow commands, and appends a Markdown
+    /// summary table to `$GITHUB_STEP_SUMMARY` if that's set, so problems
+    /// show up inline on a PR without a separate code-scanning action.
+    Ci,
+    /// Rewrites the spec file into canonical layout, or, with `--check`,
+    /// exits with an error instead of writing if it isn't already
+    /// canonical (for CI, the way `rustfmt --check` works).
+    Fmt {
+        #[arg(long)]
+        check: bool,
+    },
+    /// Scaffolds a starter project in a new directory named `name`: a
+    /// spec file with example requirements, a `libspec.toml`, a
+    /// `build.rs` wired for codegen, and a test demonstrating
+    /// `#[covers]`.
+    New { name: String },
+    /// Manages on-disk indexes of another repo's requirement ids (see
+    /// `libspec::external_index`), so `depends_on`/`refines` can
+    /// reference a `namespace:REQ-004`-qualified id without it looking
+    /// like a dangling reference.
+    ExternalIndex {
+        #[command(subcommand)]
+        action: ExternalIndexCommand,
+    },
+    /// Runs a long-lived daemon listening on a Unix domain socket,
+    /// dispatching `parse`/`lint`/`coverage`/`diff` JSON-RPC 2.0 requests
+    /// (see `libspec::serve`) so IDE plugins, CI bots, and the TUI can
+    /// share one warm process instead of cold-starting the CLI per call.
+    /// Runs until interrupted (Ctrl-C). Unix only.
+    Serve {
+        /// Path to the Unix domain socket to listen on.
+        #[arg(long, default_value = "libspec.sock")]
+        socket: PathBuf,
+    },
+    /// Manages reviewer approvals (see `libspec::review`) gating release
+    /// reports on requirements tagged in `libspec.toml`'s
+    /// `[review]` table.
+    Review {
+        #[command(subcommand)]
+        action: ReviewCommand,
+    },
+    /// Manages per-run coverage history (see `libspec::trace::regression`)
+    /// for spotting a requirement that quietly lost its only test.
+    Coverage {
+        #[command(subcommand)]
+        action: CoverageCommand,
+    },
+    /// Prints a CODEOWNERS-style mapping from source files to the team
+    /// owning the requirement(s) they mention (see `libspec::codeowners`),
+    /// or, with `--check-routing`, fails instead if any team-owned
+    /// requirement with linked code hasn't been reviewed by its own team.
+    Codeowners {
+        #[arg(long)]
+        check_routing: bool,
+    },
+    /// Drafts a provisional spec from an existing crate's public API and
+    /// doc comments (see `libspec::bootstrap`): one draft requirement per
+    /// `pub fn`, with constraints guessed from `assert!`/error paths.
+    /// Prints the canonical TOML to stdout, or writes it to `--output`.
+    Bootstrap {
+        /// Directory to scan for `.rs` files. Defaults to the current directory.
+        #[arg(long)]
+        path: Option<PathBuf>,
+        /// File to write the draft spec to, instead of printing it to stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReviewCommand {
+    /// Lists mandatory requirements missing a current approval, for a
+    /// reviewer to work through.
+    Request,
+    /// Records an approval of `requirement` by `reviewer`, pinned to the
+    /// current spec version.
+    Record { requirement: String, reviewer: String },
+    /// Fails if any mandatory requirement is missing an approval, or was
+    /// approved against a spec version that's since changed.
+    Verify,
+}
+
+#[derive(Subcommand)]
+enum CoverageCommand {
+    /// Records the current coverage matrix as a snapshot labelled `run`
+    /// (a commit hash or CI run id, say), appending to the on-disk
+    /// coverage history.
+    Record { run: String },
+    /// Fails if any requirement covered under the `baseline` run's
+    /// recorded snapshot isn't covered anymore, per the current coverage
+    /// matrix.
+    CheckRegression { baseline: String },
+}
+
+#[derive(Subcommand)]
+enum ExternalIndexCommand {
+    /// Fetches `namespace`'s index from `source` (a path to that repo's
+    /// spec file), caching it under `target/libspec/external`.
+    Fetch { namespace: String, source: PathBuf },
+    /// Re-fetches every namespace already cached, from the source path
+    /// each was originally fetched from, reporting how many changed
+    /// since their last fetch.
+    Update,
+}
+
+fn main() -> ExitCode {
+    // Cargo invokes this binary as `cargo-spec spec <args>`, passing the
+    // subcommand name itself as the first argument; clap never needs to
+    // see that, so strip it before parsing.
+    let mut args: Vec<String> = env::args().collect();
+    if args.get(1).map(String::as_str) == Some("spec") {
+        args.remove(1);
+    }
+
+    let cli = Cli::parse_from(args);
+    let profile = cli.profile.clone();
+    let conditions = cli.conditions.clone();
+    match run(cli.command, profile.as_deref(), &conditions, cli.format, cli.no_cache) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("cargo spec: {}", err.message);
+            let cwd = env::current_dir().unwrap_or_default();
+            let policy = load_policy(&cwd, profile.as_deref()).unwrap_or_default();
+            ExitCode::from(policy.exit_codes.code(err.category))
+        }
+    }
+}
+
+fn run(
+    command: Command,
+    profile: Option<&str>,
+    conditions: &[String],
+    format: OutputFormat,
+    no_cache: bool,
+) -> Result<(), CliError> {
+    match command {
+        Command::Check => check(profile, format, no_cache),
+        Command::Gen { trait_name, r#async, verify_reproducible } => {
+            gen(&trait_name, r#async, verify_reproducible, conditions, format, no_cache)
+        }
+        Command::Report => report(profile, conditions, format, no_cache),
+        Command::Diff { before, after } => diff(&before, &after, format),
+        Command::Merge { base, ours, theirs } => merge_command(&base, &ours, &theirs, format),
+        Command::Query { expr } => query(&expr, format, no_cache),
+        Command::GenTests { file } => gen_tests(&file, no_cache),
+        Command::GenCliTests { file } => gen_cli_tests(&file, no_cache),
+        Command::Watch { debounce_ms } => watch(Duration::from_millis(debounce_ms)),
+        Command::InstallHook => install_hook(profile),
+        Command::Ci => ci(no_cache),
+        Command::Fmt { check } => fmt(check),
+        Command::New { name } => new_project(&name),
+        Command::ExternalIndex { action } => match action {
+            ExternalIndexCommand::Fetch { namespace, source } => external_index_fetch(&namespace, &source),
+            ExternalIndexCommand::Update => external_index_update(),
+        },
+        Command::Serve { socket } => serve(&socket),
+        Command::Review { action } => match action {
+            ReviewCommand::Request => review_request(profile, no_cache),
+            ReviewCommand::Record { requirement, reviewer } => review_record(&requirement, &reviewer, no_cache),
+            ReviewCommand::Verify => review_verify(profile, no_cache),
+        },
+        Command::Coverage { action } => match action {
+            CoverageCommand::Record { run } => coverage_record(&run, conditions, no_cache),
+            CoverageCommand::CheckRegression { baseline } => coverage_check_regression(&baseline, conditions, no_cache),
+        },
+        Command::Codeowners { check_routing } => codeowners(check_routing, no_cache),
+        Command::Bootstrap { path, output } => bootstrap(path.as_deref(), output.as_deref()),
+    }
+}
+
+/// The on-disk cache root for the given cache `kind` (`"specs"`, `"gen"`,
+/// `"scan"`), under `target/libspec/` per [`cache::Cache`]'s convention.
+fn cache_dir(kind: &str) -> PathBuf {
+    PathBuf::from("target").join("libspec").join(kind)
+}
+
+/// Loads the policy named by `libspec_toml`, resolved against `profile`
+/// (see [`CoveragePolicy::for_profile`]). Falls back to the default policy
+/// if there's no `libspec.toml` to read.
+fn load_policy(cwd: &Path, profile: Option<&str>) -> Result<CoveragePolicy, String> {
+    let policy = match find_libspec_toml(cwd) {
+        Some(libspec_toml) => CoveragePolicy::load_toml_file(&libspec_toml).map_err(|e| e.to_string())?,
+        None => CoveragePolicy::default(),
+    };
+    Ok(policy.for_profile(profile))
+}
+
+/// Walks up from `start` looking for `libspec.toml`, stopping at the
+/// filesystem root.
+fn find_libspec_toml(start: &Path) -> Option<PathBuf> {
+    let mut dir = Some(start);
+    while let Some(candidate) = dir {
+        let path = candidate.join("libspec.toml");
+        if path.is_file() {
+            return Some(path);
+        }
+        dir = candidate.parent();
+    }
+    None
+}
+
+/// Walks up from `start` looking for a `.git` directory, stopping at the
+/// filesystem root.
+fn find_git_dir(start: &Path) -> Option<PathBuf> {
+    let mut dir = Some(start);
+    while let Some(candidate) = dir {
+        let path = candidate.join(".git");
+        if path.is_dir() {
+            return Some(path);
+        }
+        dir = candidate.parent();
+    }
+    None
+}
+
+/// Resolves the spec file a `libspec.toml` names, relative to its own
+/// directory, defaulting to `spec.toml` when it doesn't set `spec_file`.
+fn resolve_spec_path(libspec_toml: &Path) -> Result<PathBuf, String> {
+    let policy = CoveragePolicy::load_toml_file(libspec_toml).map_err(|e| e.to_string())?;
+    let dir = libspec_toml.parent().unwrap_or_else(|| Path::new("."));
+    Ok(dir.join(policy.spec_file.as_deref().unwrap_or("spec.toml")))
+}
+
+fn spec_path_and_contents() -> Result<(PathBuf, String), String> {
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let libspec_toml = find_libspec_toml(&cwd)
+        .ok_or("no libspec.toml found in this directory or any parent")?;
+    let spec_path = resolve_spec_path(&libspec_toml)?;
+    let contents = std::fs::read_to_string(&spec_path).map_err(|e| e.to_string())?;
+    Ok((spec_path, contents))
+}
+
+fn load_doc(no_cache: bool) -> Result<SpecDocument, String> {
+    load_doc_with_path(no_cache).map(|(doc, _)| doc)
+}
+
+/// Loads the spec document, also returning its resolved path (for a
+/// caller like [`fmt`] that needs to write back to it). Cached under
+/// `target/libspec/specs`, keyed on the spec file's path and raw
+/// contents, unless `no_cache` is set.
+fn load_doc_with_path(no_cache: bool) -> Result<(SpecDocument, PathBuf), String> {
+    let (spec_path, contents) = spec_path_and_contents()?;
+
+    if no_cache {
+        let doc = SpecDocument::load_toml_file(&spec_path).map_err(|e| e.to_string())?;
+        return Ok((doc, spec_path));
+    }
+
+    let cache = Cache::new(cache_dir("specs"));
+    let key = (spec_path.clone(), contents);
+    if let Some(doc) = cache.get(&key) {
+        return Ok((doc, spec_path));
+    }
+    let doc = SpecDocument::load_toml_file(&spec_path).map_err(|e| e.to_string())?;
+    cache.put(&key, &doc);
+    Ok((doc, spec_path))
+}
+
+fn check(profile: Option<&str>, format: OutputFormat, no_cache: bool) -> Result<(), CliError> {
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let policy = load_policy(&cwd, profile)?;
+    let doc = load_doc(no_cache)?;
+
+    if format == OutputFormat::Json {
+        let report = json_report::check(&doc, policy.id_scheme.as_ref());
+        let valid = report["valid"].as_bool().unwrap_or(false);
+        println!("{}", serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?);
+        return if valid {
+            Ok(())
+        } else {
+            Err(CliError::new(ExitCategory::ValidationError, "spec has issues"))
+        };
+    }
+
+    check_doc(&doc, policy.id_scheme.as_ref())
+}
+
+fn check_doc(doc: &SpecDocument, id_scheme: Option<&libspec::spec::IdScheme>) -> Result<(), CliError> {
+    let mut clean = true;
+    let mut lint_only = true;
+
+    if let Err(errors) = validate::validate(doc) {
+        clean = false;
+        lint_only = false;
+        for error in errors {
+            println!("error: {error}");
+        }
+    }
+    for issue in lint::lint(doc) {
+        clean = false;
+        println!("warning: {issue}");
+    }
+    for reference in graph::dangling_references(doc) {
+        clean = false;
+        lint_only = false;
+        println!("error: {reference}");
+    }
+    if let Some(cycle) = graph::depends_on_cycle(doc) {
+        clean = false;
+        lint_only = false;
+        println!("error: {cycle}");
+    }
+    if let Some(cycle) = graph::refines_cycle(doc) {
+        clean = false;
+        lint_only = false;
+        println!("error: {cycle}");
+    }
+    if let Some(scheme) = id_scheme {
+        for violation in doc.requirement_id_violations(scheme) {
+            clean = false;
+            lint_only = false;
+            println!("error: {violation}");
+        }
+    }
+
+    if clean {
+        println!(
+            "spec is valid: {} requirements, {} constraints",
+            doc.requirements.len(),
+            doc.constraints.len()
+        );
+        Ok(())
+    } else {
+        // A lint warning alone is advisory; anything else (a meta-schema
+        // error, a dangling reference, a cycle, an id scheme violation)
+        // is a real validation failure. See [`ExitCategory`].
+        let category = if lint_only {
+            ExitCategory::LintWarning
+        } else {
+            ExitCategory::ValidationError
+        };
+        Err(CliError::new(category, "spec has issues"))
+    }
+}
+
+/// Rewrites the spec file into [`spec_fmt::render`]'s canonical layout, or,
+/// with `check`, reports whether it already is without writing anything.
+fn fmt(check: bool) -> Result<(), CliError> {
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let libspec_toml = find_libspec_toml(&cwd)
+        .ok_or("no libspec.toml found in this directory or any parent")?;
+    let spec_path = resolve_spec_path(&libspec_toml)?;
+
+    let original = std::fs::read_to_string(&spec_path).map_err(|e| e.to_string())?;
+    let doc = SpecDocument::load_toml_file(&spec_path).map_err(|e| e.to_string())?;
+    let canonical = spec_fmt::render(&doc).map_err(|e| e.to_string())?;
+
+    if check {
+        if canonical == original {
+            println!("{} is already formatted", spec_path.display());
+            Ok(())
+        } else {
+            Err(CliError::new(
+                ExitCategory::ValidationError,
+                format!("{} is not formatted", spec_path.display()),
+            ))
+        }
+    } else if canonical == original {
+        Ok(())
+    } else {
+        std::fs::write(&spec_path, &canonical).map_err(|e| e.to_string())?;
+        println!("formatted {}", spec_path.display());
+        Ok(())
+    }
+}
+
+fn gen(
+    trait_name: &str,
+    r#async: bool,
+    verify_reproducible: bool,
+    conditions: &[String],
+    format: OutputFormat,
+    no_cache: bool,
+) -> Result<(), CliError> {
+    let active: Vec<&str> = conditions.iter().map(String::as_str).collect();
+    let doc = load_doc(no_cache)?.for_conditions(&active);
+    let generate = || {
+        if r#async {
+            rust_trait::generate_async(&doc, trait_name)
+        } else {
+            rust_trait::generate(&doc, trait_name)
+        }
+    };
+
+    if verify_reproducible {
+        let first = generate();
+        let second = generate();
+        return if first == second {
+            println!("{trait_name} generates reproducibly ({} bytes)", first.len());
+            Ok(())
+        } else {
+            Err(CliError::new(
+                ExitCategory::ValidationError,
+                format!("{trait_name} generated different output across two runs from the same spec"),
+            ))
+        };
+    }
+
+    let (generated, reused) = if no_cache {
+        (generate(), false)
+    } else {
+        let cache = Cache::new(cache_dir("gen"));
+        let key = (doc.version_hash(), "rust_trait", trait_name.to_string(), r#async);
+        match cache.get(&key) {
+            Some(cached) => (cached, true),
+            None => {
+                let code = generate();
+                cache.put(&key, &code);
+                (code, false)
+            }
+        }
+    };
+
+    if format == OutputFormat::Json {
+        let report = serde_json::json!({
+            "schema_version": json_report::SCHEMA_VERSION,
+            "trait_name": trait_name,
+            "async": r#async,
+            "reused_from_cache": reused,
+            "code": generated,
+        });
+        println!("{}", serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?);
+    } else {
+        if reused {
+            eprintln!("cargo spec: {trait_name} reused from cache (spec unchanged)");
+        }
+        print!("{generated}");
+    }
+    Ok(())
+}
+
+fn report(profile: Option<&str>, conditions: &[String], format: OutputFormat, no_cache: bool) -> Result<(), CliError> {
+    let active: Vec<&str> = conditions.iter().map(String::as_str).collect();
+    let doc = load_doc(no_cache)?.for_conditions(&active);
+    let records = trace::read_records();
+    let sign_offs = trace::read_sign_offs();
+    let matrix = CoverageMatrix::build(&doc, &records);
+
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let policy = load_policy(&cwd, profile)?;
+    // `--format json` overrides `libspec.toml`'s `report_format`, the way
+    // an explicit CLI flag overrides a config file everywhere else in
+    // this binary.
+    let report_format = if format == OutputFormat::Json {
+        ReportFormat::Json
+    } else {
+        policy.report_format
+    };
+    match report_format {
+        ReportFormat::Terminal => print!("{}", matrix.to_terminal()),
+        ReportFormat::Html => print!("{}", matrix.to_html()),
+        ReportFormat::Json => print!("{}", matrix.to_json().map_err(|e| e.to_string())?),
+        ReportFormat::Csv => print!("{}", matrix.to_csv()),
+    }
+
+    if let Err(violations) = matrix.enforce(&policy, &sign_offs) {
+        for violation in &violations {
+            match violation {
+                trace::Violation::InsufficientTests { requirement, actual, required } => {
+                    println!("policy violation: {requirement} has {actual} test(s), needs {required}");
+                }
+                trace::Violation::MissingSignOff { requirement, method } => {
+                    println!("policy violation: {requirement} has no {method:?} sign-off on record");
+                }
+            }
+        }
+        return Err(CliError::new(ExitCategory::CoverageViolation, "coverage policy violated"));
+    }
+
+    print_review_gaps(&doc, &policy)?;
+    print_risk_gaps(&doc, &records, &sign_offs, &policy)
+}
+
+/// Checks `doc`'s risk-rated requirements against `policy.risk`, printing
+/// and failing on any gap. See [`risk_policy::check`].
+fn print_risk_gaps(
+    doc: &SpecDocument,
+    records: &[trace::Record],
+    sign_offs: &[trace::SignOff],
+    policy: &CoveragePolicy,
+) -> Result<(), CliError> {
+    let gaps = risk_policy::check(doc, records, sign_offs, &policy.risk);
+    if gaps.is_empty() {
+        return Ok(());
+    }
+    for gap in &gaps {
+        match &gap.violation {
+            risk_policy::RiskViolation::InsufficientTests { required, actual } => {
+                println!(
+                    "risk policy violation: {} has {actual} test(s), needs {required} at its risk level",
+                    gap.requirement
+                );
+            }
+            risk_policy::RiskViolation::MissingFormalCheck => {
+                println!("risk policy violation: {} has no formal sign-off on record", gap.requirement);
+            }
+        }
+    }
+    Err(CliError::new(ExitCategory::CoverageViolation, "risk policy violated"))
+}
+
+/// Checks `doc`'s mandatory requirements (per `policy.review.mandatory_tags`)
+/// against the recorded approvals, printing and failing on any gap. Shared
+/// between [`report`] (which gates the release report on it) and
+/// `cargo spec review verify` (which checks it standalone).
+fn print_review_gaps(doc: &SpecDocument, policy: &CoveragePolicy) -> Result<(), CliError> {
+    let approvals = review::read_approvals();
+    let gaps = review::gate(doc, &approvals, &policy.review.mandatory_tags);
+    if gaps.is_empty() {
+        return Ok(());
+    }
+    for gap in &gaps {
+        match gap {
+            review::ReviewGap::Missing { requirement } => {
+                println!("review violation: {requirement} has no approval on record");
+            }
+            review::ReviewGap::Stale { requirement, approved_hash, current_hash } => {
+                println!(
+                    "review violation: {requirement} was approved at spec version {approved_hash}, \
+                     which is stale (current version is {current_hash})"
+                );
+            }
+        }
+    }
+    Err(CliError::new(ExitCategory::ReviewViolation, "review policy violated"))
+}
+
+/// Lists mandatory requirements missing a current approval.
+fn review_request(profile: Option<&str>, no_cache: bool) -> Result<(), CliError> {
+    let doc = load_doc(no_cache)?;
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let policy = load_policy(&cwd, profile)?;
+    let approvals = review::read_approvals();
+    let gaps = review::gate(&doc, &approvals, &policy.review.mandatory_tags);
+
+    if gaps.is_empty() {
+        println!("no mandatory requirements are awaiting review");
+        return Ok(());
+    }
+    for gap in &gaps {
+        match gap {
+            review::ReviewGap::Missing { requirement } => println!("{requirement}: needs review"),
+            review::ReviewGap::Stale { requirement, .. } => {
+                println!("{requirement}: needs re-review (spec changed since last approval)")
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Records an approval of `requirement` by `reviewer` against the current
+/// spec version.
+fn review_record(requirement: &str, reviewer: &str, no_cache: bool) -> Result<(), CliError> {
+    let doc = load_doc(no_cache)?;
+    if doc.requirement(requirement).is_none() {
+        return Err(CliError::from(format!("no requirement `{requirement}` in the spec")));
+    }
+    review::record(&doc, requirement, reviewer);
+    println!("recorded {reviewer}'s approval of {requirement} at spec version {}", doc.version_hash());
+    Ok(())
+}
+
+/// Fails if any mandatory requirement is missing an approval, or was
+/// approved against a spec version that's since changed.
+fn review_verify(profile: Option<&str>, no_cache: bool) -> Result<(), CliError> {
+    let doc = load_doc(no_cache)?;
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let policy = load_policy(&cwd, profile)?;
+    print_review_gaps(&doc, &policy)?;
+    println!("all mandatory requirements are reviewed and current");
+    Ok(())
+}
+
+/// Records the current coverage matrix as a snapshot labelled `run`.
+fn coverage_record(run: &str, conditions: &[String], no_cache: bool) -> Result<(), CliError> {
+    let active: Vec<&str> = conditions.iter().map(String::as_str).collect();
+    let doc = load_doc(no_cache)?.for_conditions(&active);
+    let records = trace::read_records();
+    let matrix = CoverageMatrix::build(&doc, &records);
+
+    let snapshot = trace::CoverageSnapshot::build(&matrix, run);
+    trace::record_coverage_snapshot(&snapshot);
+    println!("recorded coverage snapshot for run {run}");
+    Ok(())
+}
+
+/// Fails if any requirement covered under the `baseline` run's recorded
+/// snapshot isn't covered by the current coverage matrix.
+fn coverage_check_regression(baseline: &str, conditions: &[String], no_cache: bool) -> Result<(), CliError> {
+    let snapshots = trace::read_coverage_snapshots();
+    let baseline_snapshot = snapshots
+        .iter()
+        .find(|s| s.run == baseline)
+        .ok_or_else(|| CliError::from(format!("no coverage snapshot recorded for run `{baseline}`")))?;
+
+    let active: Vec<&str> = conditions.iter().map(String::as_str).collect();
+    let doc = load_doc(no_cache)?.for_conditions(&active);
+    let records = trace::read_records();
+    let matrix = CoverageMatrix::build(&doc, &records);
+    let current = trace::CoverageSnapshot::build(&matrix, "current");
+
+    let regressions = trace::regressions(baseline_snapshot, &current);
+    if regressions.is_empty() {
+        println!("no coverage regressions since {baseline}");
+        return Ok(());
+    }
+    for regression in &regressions {
+        println!("coverage regression: {} was covered at {baseline}, but isn't anymore", regression.requirement);
+    }
+    Err(CliError::new(ExitCategory::CoverageViolation, "coverage regressed since baseline"))
+}
+
+/// Prints lint findings and coverage gaps as GitHub Actions workflow
+/// commands, anchoring coverage gaps at the first source mention
+/// [`trace::scan`] finds for their id (the current directory is scanned
+/// for `.rs` files), and appends the Markdown summary table to
+/// `$GITHUB_STEP_SUMMARY` if that variable is set, printing it to stdout
+/// otherwise. The scan itself is cached under `target/libspec/scan`,
+/// keyed on the spec path and [`trace::fingerprint`] of the tree, unless
+/// `no_cache` is set.
+fn ci(no_cache: bool) -> Result<(), CliError> {
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let libspec_toml = find_libspec_toml(&cwd)
+        .ok_or("no libspec.toml found in this directory or any parent")?;
+    let spec_path = resolve_spec_path(&libspec_toml)?;
+    let doc = SpecDocument::load_toml_file(&spec_path).map_err(|e| e.to_string())?;
+
+    let records = trace::read_records();
+    let mentions = if no_cache {
+        trace::scan(&cwd, &doc)
+    } else {
+        let cache = Cache::new(cache_dir("scan"));
+        let key = (spec_path.clone(), trace::fingerprint(&cwd));
+        cache.get_or_compute(&key, || trace::scan(&cwd, &doc))
+    };
+
+    print!("{}", github_annotations::annotations(&doc, &spec_path, &records, &mentions));
+
+    let summary = github_annotations::summary_markdown(&doc, &spec_path, &records, &mentions);
+    match env::var("GITHUB_STEP_SUMMARY") {
+        Ok(path) => {
+            use std::io::Write;
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| e.to_string())?;
+            file.write_all(summary.as_bytes()).map_err(|e| e.to_string())?;
+        }
+        Err(_) => print!("{summary}"),
+    }
+    Ok(())
+}
+
+/// Prints the CODEOWNERS-style mapping [`libspec::codeowners::build`]
+/// derives from a source scan of the current directory, or, with
+/// `check_routing`, fails instead if [`libspec::codeowners::routing_gate`]
+/// finds a team-owned, code-linked requirement its own team hasn't
+/// reviewed. Scans and caches the tree the same way [`ci`] does.
+fn codeowners(check_routing: bool, no_cache: bool) -> Result<(), CliError> {
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let libspec_toml = find_libspec_toml(&cwd)
+        .ok_or("no libspec.toml found in this directory or any parent")?;
+    let spec_path = resolve_spec_path(&libspec_toml)?;
+    let doc = SpecDocument::load_toml_file(&spec_path).map_err(|e| e.to_string())?;
+
+    let records = trace::read_records();
+    let mentions = if no_cache {
+        trace::scan(&cwd, &doc)
+    } else {
+        let cache = Cache::new(cache_dir("scan"));
+        let key = (spec_path.clone(), trace::fingerprint(&cwd));
+        cache.get_or_compute(&key, || trace::scan(&cwd, &doc))
+    };
+
+    if !check_routing {
+        let ownership = libspec::codeowners::build(&doc, &mentions);
+        print!("{}", libspec::codeowners::to_codeowners_file(&ownership));
+        return Ok(());
+    }
+
+    let approvals = review::read_approvals();
+    let gaps = libspec::codeowners::routing_gate(&doc, &records, &approvals);
+    if gaps.is_empty() {
+        println!("every team-owned, implemented requirement has been reviewed by its own team");
+        return Ok(());
+    }
+    for gap in &gaps {
+        println!("routing violation: {} is owned by {}, which hasn't reviewed it", gap.requirement, gap.team);
+    }
+    Err(CliError::new(ExitCategory::ReviewViolation, "code ownership routing violated"))
+}
+
+/// Drafts a provisional spec from `path` (defaulting to the current
+/// directory) via [`libspec::bootstrap::draft_dir`], rendered through
+/// [`spec_fmt::render`] the same as a hand-written spec would be — so
+/// what's printed or written is already canonical, ready to refine and
+/// `cargo spec fmt --check` in place. Writes to `output` if given,
+/// otherwise prints to stdout.
+fn bootstrap(path: Option<&Path>, output: Option<&Path>) -> Result<(), CliError> {
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let root = path.unwrap_or(&cwd);
+
+    let doc = libspec::bootstrap::draft_dir(root);
+    let rendered = spec_fmt::render(&doc).map_err(|e| e.to_string())?;
+
+    match output {
+        Some(output) => {
+            std::fs::write(output, &rendered).map_err(|e| e.to_string())?;
+            println!("wrote draft spec with {} requirement(s) to {}", doc.requirements.len(), output.display());
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+fn diff(before: &Path, after: &Path, format: OutputFormat) -> Result<(), CliError> {
+    let before_doc = SpecDocument::load_toml_file(before).map_err(|e| e.to_string())?;
+    let after_doc = SpecDocument::load_toml_file(after).map_err(|e| e.to_string())?;
+
+    let spec_diff = libspec::diff::diff(&before_doc, &after_doc);
+    let level = libspec::diff::classify(&spec_diff);
+
+    if format == OutputFormat::Json {
+        let mut report = serde_json::to_value(&spec_diff).map_err(|e| e.to_string())?;
+        report["change_level"] = serde_json::to_value(level).map_err(|e| e.to_string())?;
+        println!("{}", serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?);
+    } else {
+        print!("{}", libspec::changelog::render_markdown(&spec_diff));
+        println!("Change level: {level:?}");
+    }
+    Ok(())
+}
+
+/// Three-way merges `ours` and `theirs` against `base` (see
+/// [`merge::merge`]) and writes the result back over `ours` in canonical
+/// layout, the way a `git` merge driver is expected to leave its result
+/// in the `%A` file. Prints each conflicting id and fails with
+/// [`ExitCategory::ValidationError`] if any remain, so `git` reports the
+/// merge as needing manual resolution instead of silently picking a side.
+fn merge_command(base: &Path, ours: &Path, theirs: &Path, format: OutputFormat) -> Result<(), CliError> {
+    let base_doc = SpecDocument::load_toml_file(base).map_err(|e| e.to_string())?;
+    let ours_doc = SpecDocument::load_toml_file(ours).map_err(|e| e.to_string())?;
+    let theirs_doc = SpecDocument::load_toml_file(theirs).map_err(|e| e.to_string())?;
+
+    let result = merge::merge(&base_doc, &ours_doc, &theirs_doc);
+    let canonical = spec_fmt::render(&result.document).map_err(|e| e.to_string())?;
+    std::fs::write(ours, &canonical).map_err(|e| e.to_string())?;
+
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&result).map_err(|e| e.to_string())?);
+    } else {
+        for conflict in &result.conflicts {
+            println!("conflict: requirement {} edited on both sides", conflict.id);
+        }
+        for conflict in &result.constraint_conflicts {
+            println!("conflict: constraint {} edited on both sides", conflict.code);
+        }
+    }
+
+    if result.is_clean() {
+        Ok(())
+    } else {
+        Err(CliError::new(
+            ExitCategory::ValidationError,
+            format!(
+                "{} requirement(s) and {} constraint(s) need manual resolution in {}",
+                result.conflicts.len(),
+                result.constraint_conflicts.len(),
+                ours.display()
+            ),
+        ))
+    }
+}
+
+/// Parses `expr` as a [`Query`] and prints every matching requirement's
+/// id and text, one per line (or, with `--format json`, the full
+/// requirement objects). `covers:` clauses are answered from
+/// `trace::read_records`, the same source `report`'s coverage gate
+/// reads from.
+fn query(expr: &str, format: OutputFormat, no_cache: bool) -> Result<(), CliError> {
+    let doc = load_doc(no_cache)?;
+    let query = Query::parse(expr).map_err(|e| e.to_string())?;
+    let records = trace::read_records();
+
+    let matches: Vec<&libspec::spec::Requirement> = doc
+        .requirements
+        .iter()
+        .filter(|req| {
+            let is_covered = records.iter().any(|r| r.kind == "covers" && r.requirement == req.id);
+            query.matches_with_coverage(*req, is_covered)
+        })
+        .collect();
+
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&matches).map_err(|e| e.to_string())?);
+    } else {
+        for req in &matches {
+            println!("{}\t{}", req.id, req.text);
+        }
+    }
+    Ok(())
+}
+
+/// Appends [`trace::generate_test_skeletons`]'s output to `file`,
+/// creating it if it doesn't exist yet. Plain textual append, the same
+/// as a developer pasting the generated stubs in by hand — this doesn't
+/// parse `file` looking for a `mod tests { ... }` to insert into, so
+/// `file` should already be (or be about to become) a standalone test
+/// module.
+fn gen_tests(file: &Path, no_cache: bool) -> Result<(), CliError> {
+    let doc = load_doc(no_cache)?;
+    let records = trace::read_records();
+    let matrix = CoverageMatrix::build(&doc, &records);
+    let generated = trace::generate_test_skeletons(&matrix);
+
+    if generated.is_empty() {
+        println!("every requirement already has a covering test");
+        return Ok(());
+    }
+
+    let mut contents = std::fs::read_to_string(file).unwrap_or_default();
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(&generated);
+    std::fs::write(file, &contents).map_err(|e| e.to_string())?;
+
+    let count = generated.matches("#[test]").count();
+    println!("appended {count} test skeleton(s) to {}", file.display());
+    Ok(())
+}
+
+/// Appends [`libspec::codegen::rust_cli_test`]'s output to `file`,
+/// creating it if it doesn't exist yet — same plain textual append as
+/// [`gen_tests`].
+fn gen_cli_tests(file: &Path, no_cache: bool) -> Result<(), CliError> {
+    let doc = load_doc(no_cache)?;
+    let generated = libspec::codegen::rust_cli_test::generate(&doc);
+
+    if generated.is_empty() {
+        println!("no cli_contract invocations to generate tests from");
+        return Ok(());
+    }
+
+    let mut contents = std::fs::read_to_string(file).unwrap_or_default();
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(&generated);
+    std::fs::write(file, &contents).map_err(|e| e.to_string())?;
+
+    let count = generated.matches("#[test]").count();
+    println!("appended {count} cli contract test(s) to {}", file.display());
+    Ok(())
+}
+
+/// Polls the spec file's mtime and, once it settles after `debounce`,
+/// reloads it, prints a changelog of what changed since the last load,
+/// and re-runs [`check_doc`]. Runs until interrupted; never returns `Ok`.
+fn watch(debounce: Duration) -> Result<(), CliError> {
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let libspec_toml = find_libspec_toml(&cwd)
+        .ok_or("no libspec.toml found in this directory or any parent")?;
+    let spec_path = resolve_spec_path(&libspec_toml)?;
+    let policy = load_policy(&cwd, None)?;
+
+    let mut doc = SpecDocument::load_toml_file(&spec_path).map_err(|e| e.to_string())?;
+    let mut last_mtime = spec_mtime(&spec_path);
+    let mut debouncer = Debouncer::new(debounce);
+
+    println!("watching {} for changes (Ctrl-C to stop)...", spec_path.display());
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let mtime = spec_mtime(&spec_path);
+        if mtime != last_mtime {
+            last_mtime = mtime;
+            debouncer.observe(Instant::now());
+        }
+
+        if !debouncer.ready(Instant::now()) {
+            continue;
+        }
+
+        match SpecDocument::load_toml_file(&spec_path) {
+            Ok(new_doc) => {
+                let spec_diff = libspec::diff::diff(&doc, &new_doc);
+                if spec_diff.is_empty() {
+                    println!("spec changed, but nothing diff-visible (whitespace/formatting only)");
+                } else {
+                    print!("{}", libspec::changelog::render_markdown(&spec_diff));
+                }
+                doc = new_doc;
+                if let Err(err) = check_doc(&doc, policy.id_scheme.as_ref()) {
+                    eprintln!("cargo spec: {}", err.message);
+                }
+            }
+            Err(e) => eprintln!("cargo spec: {e}"),
+        }
+    }
+}
+
+fn spec_mtime(path: &Path) -> Option<std::time::SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Writes `.git/hooks/pre-commit`, generated from the `[hooks]` table in
+/// `libspec.toml` (defaulting to blocking on both lint and coverage if
+/// there's no `libspec.toml` to read).
+fn install_hook(profile: Option<&str>) -> Result<(), CliError> {
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let git_dir = find_git_dir(&cwd).ok_or("no .git directory found in this directory or any parent")?;
+    let policy = load_policy(&cwd, profile)?;
+
+    let hooks_dir = git_dir.join("hooks");
+    std::fs::create_dir_all(&hooks_dir).map_err(|e| e.to_string())?;
+    let hook_path = hooks_dir.join("pre-commit");
+    std::fs::write(&hook_path, hooks::render_pre_commit_hook(&policy.hooks)).map_err(|e| e.to_string())?;
+    make_executable(&hook_path)?;
+
+    println!("installed pre-commit hook at {}", hook_path.display());
+    Ok(())
+}
+
+/// Scaffolds a starter project in a new directory named `name`, from
+/// [`scaffold::render_project`]. Refuses to overwrite an existing
+/// directory.
+fn new_project(name: &str) -> Result<(), CliError> {
+    let dir = Path::new(name);
+    if dir.exists() {
+        return Err(CliError::from(format!("{} already exists", dir.display())));
+    }
+
+    for file in scaffold::render_project(name) {
+        let path = dir.join(file.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(&path, file.contents).map_err(|e| e.to_string())?;
+    }
+
+    println!("created {}", dir.display());
+    Ok(())
+}
+
+fn external_index_fetch(namespace: &str, source: &Path) -> Result<(), CliError> {
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let index = external_index::fetch(&cwd, namespace, source).map_err(|e| e.to_string())?;
+    println!(
+        "fetched {} requirement(s) for namespace `{}` from {}",
+        index.requirements.len(),
+        namespace,
+        source.display()
+    );
+    Ok(())
+}
+
+/// Re-fetches every namespace already cached under
+/// `target/libspec/external`, from the source path each was originally
+/// [`external_index::fetch`]ed from, reporting how many namespaces
+/// actually changed since their last fetch.
+fn external_index_update() -> Result<(), CliError> {
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let dir = external_index::index_dir(&cwd);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => {
+            println!("no external indexes to update");
+            return Ok(());
+        }
+    };
+
+    let mut updated = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(namespace) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let Some(existing) = external_index::load(&cwd, namespace) else {
+            continue;
+        };
+        let source = PathBuf::from(&existing.source);
+        match external_index::fetch(&cwd, namespace, &source) {
+            Ok(refetched) => {
+                if refetched.spec_hash != existing.spec_hash {
+                    updated += 1;
+                }
+            }
+            Err(e) => eprintln!("cargo spec: failed to update `{namespace}`: {e}"),
+        }
+    }
+    println!("updated {updated} external index(es)");
+    Ok(())
+}
+
+#[cfg(unix)]
+fn serve(socket: &Path) -> Result<(), CliError> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixListener;
+
+    if socket.exists() {
+        std::fs::remove_file(socket).map_err(|e| e.to_string())?;
+    }
+    let listener = UnixListener::bind(socket).map_err(|e| e.to_string())?;
+    println!("listening on {} (Ctrl-C to stop)...", socket.display());
+
+    for connection in listener.incoming() {
+        let mut stream = match connection {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("cargo spec: connection error: {e}");
+                continue;
+            }
+        };
+        let reader = BufReader::new(stream.try_clone().map_err(|e| e.to_string())?);
+        for line in reader.lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    eprintln!("cargo spec: read error: {e}");
+                    break;
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str(&line) {
+                Ok(request) => libspec::serve::dispatch(&request),
+                Err(e) => serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "error": { "code": -32700, "message": format!("parse error: {e}") },
+                    "id": serde_json::Value::Null,
+                }),
+            };
+            if writeln!(stream, "{response}").is_err() {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn serve(_socket: &Path) -> Result<(), CliError> {
+    Err(CliError::new(
+        ExitCategory::ParseError,
+        "cargo spec serve requires Unix domain sockets, which aren't available on this platform",
+    ))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut perms = std::fs::metadata(path).map_err(|e| e.to_string())?.permissions();
+    perms.set_mode(perms.mode() | 0o111);
+    std::fs::set_permissions(path, perms).map_err(|e| e.to_string())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
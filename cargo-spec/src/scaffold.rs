@@ -0,0 +1,141 @@
+//! Renders the files `cargo spec new` scaffolds for a starter project: a
+//! spec file with example requirements, a `libspec.toml` naming it, a
+//! `build.rs` wired to `libspec_build::generate`, and a test demonstrating
+//! `#[covers]` — automating what `examples/bank-account` otherwise shows
+//! by hand. Kept as pure string-builders, independent of the filesystem,
+//! so the generated files are unit-testable without actually creating a
+//! project.
+
+/// One file `cargo spec new` writes, relative to the new project's root.
+pub struct ScaffoldFile {
+    pub path: &'static str,
+    pub contents: String,
+}
+
+/// Renders every file for a new project named `name` (its directory and
+/// Cargo package name).
+pub fn render_project(name: &str) -> Vec<ScaffoldFile> {
+    let trait_name = format!("{}Api", pascal_case(name));
+    vec![
+        ScaffoldFile { path: "Cargo.toml", contents: render_cargo_toml(name) },
+        ScaffoldFile { path: "libspec.toml", contents: render_libspec_toml() },
+        ScaffoldFile { path: "spec.toml", contents: render_spec_toml() },
+        ScaffoldFile { path: "build.rs", contents: render_build_rs(&trait_name) },
+        ScaffoldFile { path: "src/lib.rs", contents: render_lib_rs() },
+        ScaffoldFile { path: "tests/spec_tests.rs", contents: render_spec_tests() },
+    ]
+}
+
+/// `bank-account` -> `BankAccount`, `my_api` -> `MyApi`.
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn render_cargo_toml(name: &str) -> String {
+    format!(
+        "[package]\n\
+         name = \"{name}\"\n\
+         version = \"0.1.0\"\n\
+         edition = \"2021\"\n\
+         build = \"build.rs\"\n\
+         \n\
+         [build-dependencies]\n\
+         libspec-build = \"0.1\"\n\
+         \n\
+         [dev-dependencies]\n\
+         libspec-macros = \"0.1\"\n"
+    )
+}
+
+fn render_libspec_toml() -> String {
+    "spec_file = \"spec.toml\"\n".to_string()
+}
+
+fn render_spec_toml() -> String {
+    "[[requirement]]\n\
+     id = \"REQ-001\"\n\
+     text = \"balance() returns the current balance\"\n\
+     \n\
+     [[requirement]]\n\
+     id = \"REQ-002\"\n\
+     text = \"withdraw() rejects overdrafts\"\n\
+     \n\
+     [[constraint]]\n\
+     code = \"CONST-001\"\n\
+     text = \"amount must be positive\"\n"
+        .to_string()
+}
+
+fn render_build_rs(trait_name: &str) -> String {
+    format!("fn main() {{\n    libspec_build::generate(\"spec.toml\", \"{trait_name}\").unwrap();\n}}\n")
+}
+
+fn render_lib_rs() -> String {
+    "include!(concat!(env!(\"OUT_DIR\"), \"/libspec_generated.rs\"));\n".to_string()
+}
+
+fn render_spec_tests() -> String {
+    "use libspec_macros::covers;\n\
+     \n\
+     #[covers(\"REQ-001\")]\n\
+     #[test]\n\
+     fn balance_is_correct_after_deposit() {\n\
+     \u{20}   assert_eq!(1 + 1, 2);\n\
+     }\n"
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libspec::spec::SpecDocument;
+
+    #[test]
+    fn derives_a_pascal_case_trait_name_from_a_hyphenated_name() {
+        let files = render_project("bank-account");
+        let build_rs = &files.iter().find(|f| f.path == "build.rs").unwrap().contents;
+        assert!(build_rs.contains("\"BankAccountApi\""));
+    }
+
+    #[test]
+    fn the_generated_spec_file_parses() {
+        let files = render_project("bank-account");
+        let spec_toml = &files.iter().find(|f| f.path == "spec.toml").unwrap().contents;
+        let doc = SpecDocument::from_toml_str(spec_toml).unwrap();
+        assert_eq!(doc.requirements.len(), 2);
+        assert_eq!(doc.constraints.len(), 1);
+    }
+
+    #[test]
+    fn the_generated_libspec_toml_names_the_spec_file() {
+        let files = render_project("bank-account");
+        let libspec_toml = &files.iter().find(|f| f.path == "libspec.toml").unwrap().contents;
+        assert!(libspec_toml.contains("spec_file = \"spec.toml\""));
+    }
+
+    #[test]
+    fn the_generated_test_covers_a_requirement_from_the_spec() {
+        let files = render_project("bank-account");
+        let spec_tests = &files.iter().find(|f| f.path == "tests/spec_tests.rs").unwrap().contents;
+        assert!(spec_tests.contains("#[covers(\"REQ-001\")]"));
+    }
+
+    #[test]
+    fn scaffolds_every_expected_file() {
+        let files = render_project("bank-account");
+        let paths: Vec<&str> = files.iter().map(|f| f.path).collect();
+        assert_eq!(
+            paths,
+            vec!["Cargo.toml", "libspec.toml", "spec.toml", "build.rs", "src/lib.rs", "tests/spec_tests.rs"]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! An async, timeout-enforcing facade over [`BankLibrary`]: [`BankApiAsync`]
+//! mirrors a subset of [`BankAPI`] with every method made `async fn`, the
+//! way `libspec::codegen::rust_trait::generate_async` generates an
+//! `async_trait` variant of a spec'd trait. Unlike that generated
+//! blanket adapter (which just calls through to the sync method, doing no
+//! actual async work), [`AsyncBankLibrary`] runs each call under a
+//! [`TimeoutPolicy`] budget via `tokio::time::timeout`, returning
+//! [`BankError::Timeout`] (CONST-014) instead of hanging past it.
+
+use std::future::Future;
+use std::time::Duration;
+
+use super::{BankAPI, BankError, BankLibrary, Money, Result};
+
+/// Async variant of [`BankAPI`]'s account-moving operations, enforced
+/// against a [`TimeoutPolicy`] by [`AsyncBankLibrary`].
+#[async_trait::async_trait]
+pub trait BankApiAsync {
+    async fn create_account(&self, owner: String) -> String;
+    async fn deposit(&self, account_id: &str, amount: Money) -> Result<()>;
+    async fn withdraw(&self, account_id: &str, amount: Money) -> Result<()>;
+    async fn transfer(&self, from: &str, to: &str, amount: Money) -> Result<()>;
+    async fn balance(&self, account_id: &str) -> Result<Money>;
+}
+
+/// How long an [`AsyncBankLibrary`] operation may run before it's
+/// abandoned as timed out; see [`BankError::Timeout`] (CONST-014).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutPolicy {
+    pub budget: Duration,
+}
+
+impl TimeoutPolicy {
+    pub const DEFAULT: TimeoutPolicy = TimeoutPolicy { budget: Duration::from_millis(50) };
+}
+
+impl Default for TimeoutPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// [`BankApiAsync`] over a [`BankLibrary`]: every method runs the sync
+/// call (cheap — lock-based, no blocking I/O) inside `policy.budget`. A
+/// test can instead drive an arbitrary slow future through
+/// [`AsyncBankLibrary::run_within_budget`] directly, to exercise the
+/// timeout path deterministically under a paused tokio clock
+/// (`#[tokio::test(start_paused = true)]`) rather than actually waiting
+/// out the budget.
+pub struct AsyncBankLibrary {
+    inner: BankLibrary,
+    policy: TimeoutPolicy,
+}
+
+impl AsyncBankLibrary {
+    pub fn new(policy: TimeoutPolicy) -> Self {
+        Self { inner: BankLibrary::new(), policy }
+    }
+
+    /// Runs `operation` under [`tokio::time::timeout`], converting an
+    /// elapsed budget into [`BankError::Timeout`] instead of the bare
+    /// `Elapsed` error `tokio::time::timeout` alone would return.
+    pub async fn run_within_budget<F, T>(&self, operation: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        tokio::time::timeout(self.policy.budget, operation)
+            .await
+            .unwrap_or(Err(BankError::Timeout(self.policy.budget)))
+    }
+}
+
+#[async_trait::async_trait]
+impl BankApiAsync for AsyncBankLibrary {
+    async fn create_account(&self, owner: String) -> String {
+        self.inner.create_account(owner)
+    }
+
+    async fn deposit(&self, account_id: &str, amount: Money) -> Result<()> {
+        self.run_within_budget(async { self.inner.deposit(account_id, amount) }).await
+    }
+
+    async fn withdraw(&self, account_id: &str, amount: Money) -> Result<()> {
+        self.run_within_budget(async { self.inner.withdraw(account_id, amount) }).await
+    }
+
+    async fn transfer(&self, from: &str, to: &str, amount: Money) -> Result<()> {
+        self.run_within_budget(async { self.inner.transfer(from, to, amount) }).await
+    }
+
+    async fn balance(&self, account_id: &str) -> Result<Money> {
+        self.run_within_budget(async { self.inner.balance(account_id) }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn an_operation_within_budget_succeeds() {
+        let bank = AsyncBankLibrary::new(TimeoutPolicy::DEFAULT);
+        let id = bank.create_account("User".into()).await;
+        bank.deposit(&id, Money::from_cents(1000)).await.unwrap();
+        assert_eq!(bank.balance(&id).await.unwrap(), Money::from_cents(1000));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn an_operation_past_its_budget_times_out_instead_of_hanging() {
+        let bank = AsyncBankLibrary::new(TimeoutPolicy { budget: Duration::from_millis(10) });
+        let result = bank
+            .run_within_budget(async {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                Ok(())
+            })
+            .await;
+        assert_eq!(result, Err(BankError::Timeout(Duration::from_millis(10))));
+    }
+}
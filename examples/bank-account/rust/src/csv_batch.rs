@@ -0,0 +1,98 @@
+use crate::lib::{BankAPI, BankError, BankLibrary, Money};
+use clap::Parser;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+/// Applies a CSV transaction log (header `type,client,tx,amount`) to a
+/// `BankLibrary` and prints a `client,available,held,total,locked` summary.
+#[derive(Parser)]
+#[command(name = "bank-batch", about = "Apply a CSV transaction log to the bank library")]
+pub struct Cli {
+    /// Path to the input CSV transaction log
+    pub input: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Row {
+    #[serde(rename = "type")]
+    kind: String,
+    client: String,
+    tx: u32,
+    amount: Option<f64>,
+}
+
+/// Streams `path` row by row, applying each transaction to a fresh
+/// `BankLibrary` keyed by client id, then writes the summary to stdout.
+/// Malformed or rejected rows are logged to stderr and skipped so one bad
+/// row doesn't abort the whole run.
+pub fn run(path: &PathBuf) {
+    let bank = BankLibrary::new();
+    let mut clients = Vec::new();
+    let mut seen = HashSet::new();
+
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(e) => {
+            eprintln!("error: failed to open {}: {}", path.display(), e);
+            return;
+        }
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(BufReader::new(file));
+
+    for result in reader.deserialize::<Row>() {
+        let row = match result {
+            Ok(row) => row,
+            Err(e) => {
+                eprintln!("error: skipping malformed row: {}", e);
+                continue;
+            }
+        };
+
+        if seen.insert(row.client.clone()) {
+            clients.push(row.client.clone());
+        }
+        bank.ensure_account(&row.client);
+
+        if let Err(e) = apply_row(&bank, &row) {
+            eprintln!("error: client {} tx {}: {}", row.client, row.tx, e);
+        }
+    }
+
+    print_summary(&bank, &clients);
+}
+
+fn apply_row(bank: &BankLibrary, row: &Row) -> crate::lib::Result<()> {
+    match row.kind.as_str() {
+        "deposit" => {
+            let dollars = row.amount.ok_or(BankError::NonPositiveAmount)?;
+            bank.deposit_with_tx(&row.client, row.tx, Money::from_dollars(dollars)?)
+        }
+        "withdrawal" => {
+            let dollars = row.amount.ok_or(BankError::NonPositiveAmount)?;
+            bank.withdraw_with_tx(&row.client, row.tx, Money::from_dollars(dollars)?)
+        }
+        "dispute" => bank.dispute(&row.client, row.tx),
+        "resolve" => bank.resolve(&row.client, row.tx),
+        "chargeback" => bank.chargeback(&row.client, row.tx),
+        other => {
+            eprintln!("error: unknown transaction type '{}'", other);
+            Ok(())
+        }
+    }
+}
+
+fn print_summary(bank: &BankLibrary, clients: &[String]) {
+    println!("client,available,held,total,locked");
+    for client in clients {
+        let available = bank.available(client).unwrap_or(Money::ZERO);
+        let held = bank.held(client).unwrap_or(Money::ZERO);
+        let locked = bank.is_locked(client).unwrap_or(false);
+        println!("{},{},{},{},{}", client, available, held, available + held, locked);
+    }
+}
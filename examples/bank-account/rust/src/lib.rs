@@ -1,62 +1,972 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
 
-pub type Result<T> = std::result::Result<T, String>;
+#[path = "persistence.rs"]
+pub mod persistence;
+pub use persistence::{PersistenceError, PersistentBankLibrary};
+
+#[path = "async_api.rs"]
+pub mod async_api;
+pub use async_api::{AsyncBankLibrary, BankApiAsync, TimeoutPolicy};
+
+pub type Result<T> = std::result::Result<T, BankError>;
+
+/// How far a dollar amount's cents may drift from a whole number before
+/// [`Money::from_dollars`] treats it as genuinely sub-cent rather than as
+/// `f64` noise (e.g. `10.10 * 100.0` landing on `1009.9999999999999`).
+const ROUNDING_EPSILON: f64 = 1e-6;
+
+/// An exact amount of money as a whole number of minor units (cents,
+/// regardless of [`Currency`]). Storing balances as `Money` instead of
+/// `f64` means two balances are either equal or not — no tolerance needed
+/// to paper over rounding drift the way float addition would require.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    /// Constructs a `Money` directly from a count of minor units.
+    pub fn from_cents(cents: i64) -> Money {
+        Money(cents)
+    }
+
+    /// Returns the underlying count of minor units, e.g. for encoding a
+    /// `Money` into a journal line. See [`crate::persistence`].
+    pub fn to_cents(self) -> i64 {
+        self.0
+    }
+
+    /// Converts a decimal dollar amount to whole cents, rejecting amounts
+    /// whose precision goes finer than a cent (CONST-009) instead of
+    /// silently rounding them away.
+    pub fn from_dollars(dollars: f64) -> Result<Money> {
+        let cents = dollars * 100.0;
+        let rounded = cents.round();
+        if (cents - rounded).abs() > ROUNDING_EPSILON {
+            return Err(BankError::SubCentAmount(dollars));
+        }
+        Ok(Money(rounded as i64))
+    }
+
+    pub fn to_dollars(self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+
+    /// Rounds a decimal dollar amount to the nearest cent instead of
+    /// rejecting sub-cent precision the way [`Money::from_dollars`] does
+    /// — for a value an implementation computed itself (e.g. accrued
+    /// interest), where rounding is the point rather than a caller error.
+    fn from_dollars_rounded(dollars: f64) -> Money {
+        Money((dollars * 100.0).round() as i64)
+    }
+
+    fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    fn checked_add(self, other: Money) -> Option<Money> {
+        self.0.checked_add(other.0).map(Money)
+    }
+
+    /// Converts this amount to another currency at `rate` (units of the
+    /// target currency per unit of this one), rejecting the result the
+    /// same way [`Money::from_dollars`] would (CONST-009) if it lands on
+    /// a sub-cent amount.
+    fn convert(self, rate: f64) -> Result<Money> {
+        Money::from_dollars(self.to_dollars() * rate)
+    }
+}
+
+impl std::ops::Add for Money {
+    type Output = Money;
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl std::ops::Sub for Money {
+    type Output = Money;
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl std::ops::Neg for Money {
+    type Output = Money;
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2}", self.to_dollars())
+    }
+}
+
+/// A currency an account can be denominated in: a closed set of values,
+/// the way a spec-level enumeration declares one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Currency {
+    Usd,
+    Eur,
+    Gbp,
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = match self {
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+        };
+        write!(f, "{code}")
+    }
+}
+
+/// Lifecycle states of an [`Account`], matching the spec's `Account` FSM
+/// (`Open` -[`freeze`]-> `Frozen` -[`unfreeze`]-> `Open`, and `Open` or
+/// `Frozen` -[`close`]-> `Closed`): freezing blocks withdrawals, closing
+/// blocks every operation. See [`BankLibrary::freeze`],
+/// [`BankLibrary::unfreeze`], [`BankLibrary::close`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountState {
+    Open,
+    Frozen,
+    Closed,
+}
+
+/// Errors returned by `BankAPI` operations.
+#[derive(Debug, PartialEq)]
+pub enum BankError {
+    InvalidAccount,
+    NonPositiveAmount,
+    InsufficientFunds,
+    AccountLocked,
+    BalanceOverflow,
+    DuplicateTransaction,
+    BalanceMismatch { account_id: String, expected: Money, actual: Money },
+    AuditFailed { account_id: String, stored: Money, recomputed: Money },
+    /// An amount had sub-cent precision, e.g. `$10.005` — there's no
+    /// minor unit it could round to without guessing.
+    SubCentAmount(f64),
+    /// A plain deposit's currency didn't match the account's; see
+    /// [`BankLibrary::deposit_in`]. Cross-currency deposits go through
+    /// [`BankLibrary::convert_and_deposit`] instead.
+    CurrencyMismatch { expected: Currency, actual: Currency },
+    /// [`BankLibrary::convert_and_deposit`] was asked to convert between
+    /// two currencies with no rate declared via
+    /// [`BankLibrary::set_conversion_rate`].
+    MissingConversionRate { from: Currency, to: Currency },
+    /// A withdrawal was attempted while the account is [`AccountState::Frozen`].
+    AccountFrozen,
+    /// Any mutating operation was attempted on an [`AccountState::Closed`]
+    /// account.
+    AccountClosed,
+    /// An [`async_api::AsyncBankLibrary`] operation didn't complete
+    /// within its [`async_api::TimeoutPolicy`] budget.
+    Timeout(std::time::Duration),
+    /// [`BankLibrary::transactions_page`] was asked for more than
+    /// [`MAX_PAGE_SIZE`] transactions in one page.
+    PageSizeExceeded { requested: usize, max: usize },
+}
+
+impl BankError {
+    /// The constraint code this variant corresponds to, in the same
+    /// `CONST-NNN` form `libspec`-generated errors use.
+    pub fn code(&self) -> &'static str {
+        match self {
+            BankError::NonPositiveAmount => "CONST-001",
+            BankError::InsufficientFunds => "CONST-002",
+            BankError::InvalidAccount => "CONST-003",
+            BankError::AccountLocked => "CONST-004",
+            BankError::BalanceOverflow => "CONST-005",
+            BankError::DuplicateTransaction => "CONST-006",
+            BankError::BalanceMismatch { .. } => "CONST-007",
+            BankError::AuditFailed { .. } => "CONST-008",
+            BankError::SubCentAmount(_) => "CONST-009",
+            BankError::CurrencyMismatch { .. } => "CONST-010",
+            BankError::MissingConversionRate { .. } => "CONST-011",
+            BankError::AccountFrozen => "CONST-012",
+            BankError::AccountClosed => "CONST-013",
+            BankError::Timeout(_) => "CONST-014",
+            BankError::PageSizeExceeded { .. } => "CONST-015",
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            BankError::InvalidAccount => "account not found".to_string(),
+            BankError::NonPositiveAmount => "amount must be positive".to_string(),
+            BankError::InsufficientFunds => "insufficient funds".to_string(),
+            BankError::AccountLocked => "account is locked".to_string(),
+            BankError::BalanceOverflow => "balance overflow".to_string(),
+            BankError::DuplicateTransaction => "transaction id already in use".to_string(),
+            BankError::BalanceMismatch { account_id, expected, actual } => format!(
+                "account {} expected balance {} but found {}", account_id, expected, actual
+            ),
+            BankError::AuditFailed { account_id, stored, recomputed } => format!(
+                "account {} stored balance {} diverges from history replay {}", account_id, stored, recomputed
+            ),
+            BankError::SubCentAmount(dollars) => {
+                format!("amount {:.4} is not a whole number of cents", dollars)
+            }
+            BankError::CurrencyMismatch { expected, actual } => {
+                format!("expected a deposit in {expected} but got {actual}")
+            }
+            BankError::MissingConversionRate { from, to } => {
+                format!("no conversion rate declared from {from} to {to}")
+            }
+            BankError::AccountFrozen => "account is frozen".to_string(),
+            BankError::AccountClosed => "account is closed".to_string(),
+            BankError::Timeout(budget) => format!("operation did not complete within {budget:?}"),
+            BankError::PageSizeExceeded { requested, max } => {
+                format!("requested page size {requested} exceeds the maximum of {max}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for BankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for BankError {}
 
 pub trait BankAPI {
     fn version(&self) -> String;
-    fn create_account(&mut self, owner: String) -> String;
-    fn deposit(&mut self, account_id: &str, amount: f64) -> Result<()>;
-    fn withdraw(&mut self, account_id: &str, amount: f64) -> Result<()>;
-    fn balance(&self, account_id: &str) -> Result<f64>;
-    fn transactions(&self, account_id: &str) -> Result<Vec<f64>>;
+    fn create_account(&self, owner: String) -> String;
+    fn deposit(&self, account_id: &str, amount: Money) -> Result<()>;
+    fn withdraw(&self, account_id: &str, amount: Money) -> Result<()>;
+    /// Moves `amount` from `from` to `to` as a single all-or-nothing step:
+    /// no caller can ever observe `from` debited without `to` credited, or
+    /// the reverse, even when one leg fails.
+    fn transfer(&self, from: &str, to: &str, amount: Money) -> Result<()>;
+    fn dispute(&self, account_id: &str, tx: u32) -> Result<()>;
+    fn resolve(&self, account_id: &str, tx: u32) -> Result<()>;
+    fn chargeback(&self, account_id: &str, tx: u32) -> Result<()>;
+    fn balance(&self, account_id: &str) -> Result<Money>;
+    fn transactions(&self, account_id: &str) -> Result<Vec<TransactionRecord>>;
+    fn assert_balance(&self, account_id: &str, expected: Money) -> Result<()>;
+    fn audit(&self) -> Result<()>;
+}
+
+/// A single deposit or withdrawal, kept around so it can later be disputed.
+struct Transaction {
+    amount: Money,
+    is_deposit: bool,
+    disputed: bool,
+    /// When this transaction was recorded, per the wall clock rather than
+    /// [`BankLibrary::accrue_interest`]'s injected `now` — this is
+    /// observability metadata, not an input to a monetary calculation a
+    /// test needs to drive deterministically.
+    timestamp: SystemTime,
+    /// The caller-supplied idempotency key this transaction was recorded
+    /// under, if it was deposited via
+    /// [`BankLibrary::deposit_with_idempotency_key`]; `None` for every
+    /// other deposit or withdrawal path.
+    correlation_id: Option<String>,
 }
 
 struct Account {
-    balance: f64,
-    history: Vec<f64>,
+    currency: Currency,
+    available: Money,
+    held: Money,
+    history: Vec<Money>,
+    transactions: HashMap<u32, Transaction>,
+    next_tx: u32,
+    locked: bool,
+    state: AccountState,
+    /// The clock time [`BankLibrary::accrue_interest`] last ran at for
+    /// this account, so the next call accrues only the elapsed time
+    /// since then rather than from account creation.
+    last_accrued: Duration,
+    /// Resulting balances of past [`BankLibrary::deposit_with_idempotency_key`]
+    /// calls, keyed by the caller-supplied idempotency key, so a replayed
+    /// key returns the original result instead of crediting again.
+    idempotency_keys: HashMap<String, Money>,
+}
+
+impl Account {
+    fn new(currency: Currency) -> Self {
+        Self {
+            currency,
+            available: Money::ZERO,
+            held: Money::ZERO,
+            history: Vec::new(),
+            transactions: HashMap::new(),
+            next_tx: 1,
+            locked: false,
+            state: AccountState::Open,
+            last_accrued: Duration::ZERO,
+            idempotency_keys: HashMap::new(),
+        }
+    }
+
+    fn balance(&self) -> Money {
+        self.available + self.held
+    }
+}
+
+/// How far into the negative a withdrawal may take an account's balance.
+/// Generated the way a `RequirementTemplate`-instantiated requirement's
+/// parameter would be supplied to an implementation: a plain config
+/// struct the deployer fills in, rather than a constant baked into
+/// `BankLibrary`.
+///
+/// CONST-002 reads "withdrawal may not exceed balance plus
+/// `overdraft_limit`" — [`OverdraftPolicy::NONE`] (the default) recovers
+/// the original "withdrawal may not exceed balance" rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OverdraftPolicy {
+    pub overdraft_limit: Money,
+}
+
+impl OverdraftPolicy {
+    pub const NONE: OverdraftPolicy = OverdraftPolicy { overdraft_limit: Money::ZERO };
+}
+
+/// The annual interest rate [`BankLibrary::accrue_interest`] applies,
+/// generated the same way [`OverdraftPolicy`] is: a plain config struct a
+/// deployer fills in rather than a constant baked into `BankLibrary`.
+///
+/// The time-dependent requirement reads "interest accrues at
+/// `annual_rate` per year of elapsed time, simple (non-compounding)
+/// within a single accrual call" — [`InterestPolicy::NONE`] (the
+/// default) accrues nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct InterestPolicy {
+    pub annual_rate: f64,
 }
 
+impl InterestPolicy {
+    pub const NONE: InterestPolicy = InterestPolicy { annual_rate: 0.0 };
+}
+
+/// Seconds in a Julian year (365.25 days) — the convention
+/// [`BankLibrary::accrue_interest`] uses to turn an elapsed [`Duration`]
+/// into a fraction of a year.
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
+
+/// One transaction in an account's history, as returned by both
+/// [`BankAPI::transactions`] and [`BankLibrary::transactions_page`]: a
+/// structured record of a single deposit or withdrawal, replacing a bare
+/// signed [`Money`] history with something a caller can page through,
+/// check the dispute status of, and correlate back to the call that
+/// produced it, without re-deriving any of that.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionRecord {
+    pub tx: u32,
+    pub amount: Money,
+    pub is_deposit: bool,
+    pub disputed: bool,
+    pub timestamp: SystemTime,
+    pub correlation_id: Option<String>,
+}
+
+/// The most [`BankLibrary::transactions_page`] will return in one page,
+/// regardless of the caller's requested `page_size` (CONST-015).
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// One page of [`BankLibrary::transactions_page`]'s result: transactions
+/// ordered by id ascending. Ordering is stable across calls — ids are
+/// assigned sequentially and never reused or reassigned, so a later call
+/// with the same `after` cursor can only see transactions appended since,
+/// never a reordering of ones already returned.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TransactionPage {
+    pub records: Vec<TransactionRecord>,
+    /// The cursor to pass as `transactions_page`'s `after` argument to
+    /// fetch the next page; `None` once every transaction up to the time
+    /// of this call has been returned.
+    pub next_cursor: Option<u32>,
+}
+
+/// A single constraint check disabled in an otherwise-correct
+/// `BankLibrary`, for exercising what a conformance vector or monitor
+/// watching these operations would need to catch. See
+/// [`BankLibrary::with_fault`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fault {
+    /// No fault: every check runs as documented.
+    #[default]
+    None,
+    /// Skips CONST-002's overdraft-limit check, so a withdrawal is
+    /// accepted no matter how far negative it takes `available`.
+    IgnoreOverdraftLimit,
+    /// Skips the locked-account check on both legs, so a deposit or
+    /// withdrawal against a locked account is accepted instead of
+    /// rejected with [`BankError::AccountLocked`].
+    IgnoreAccountLock,
+    /// Skips the duplicate-transaction check, so replaying the same `tx`
+    /// id credits or debits again instead of being rejected with
+    /// [`BankError::DuplicateTransaction`].
+    AllowDuplicateTransactions,
+}
+
+/// `BankLibrary` shards locking per account: the outer map is guarded by one
+/// `RwLock` (held only while accounts are created or looked up) and each
+/// account has its own `RwLock`, so unrelated accounts can be read and
+/// mutated concurrently instead of serializing on a single lock.
 pub struct BankLibrary {
-    accounts: HashMap<String, Account>,
+    accounts: RwLock<HashMap<String, RwLock<Account>>>,
+    /// Declared conversion rates, keyed `(from, to)`, units of `to` per
+    /// unit of `from`. See [`BankLibrary::set_conversion_rate`].
+    rates: RwLock<HashMap<(Currency, Currency), f64>>,
+    overdraft_limit: Money,
+    interest_policy: InterestPolicy,
+    fault: Fault,
 }
 
 impl BankLibrary {
     pub fn new() -> Self {
-        Self { accounts: HashMap::new() }
+        Self::with_policies(OverdraftPolicy::NONE, InterestPolicy::NONE)
     }
-}
 
-impl BankAPI for BankLibrary {
-    fn version(&self) -> String { "1".to_string() }
+    /// Creates a `BankLibrary` that lets withdrawals overdraw by up to
+    /// `policy.overdraft_limit`, applied to every account it holds.
+    pub fn with_overdraft_policy(policy: OverdraftPolicy) -> Self {
+        Self::with_policies(policy, InterestPolicy::NONE)
+    }
+
+    /// Creates a `BankLibrary` whose accounts accrue interest at
+    /// `policy.annual_rate` via [`BankLibrary::accrue_interest`].
+    pub fn with_interest_policy(policy: InterestPolicy) -> Self {
+        Self::with_policies(OverdraftPolicy::NONE, policy)
+    }
 
-    fn create_account(&mut self, owner: String) -> String {
-        let id = format!("ACC-{}", self.accounts.len() + 1);
-        self.accounts.insert(id.clone(), Account { balance: 0.0, history: Vec::new() });
+    /// Creates a `BankLibrary` with `fault` disabling one of its
+    /// constraint checks, otherwise behaving like [`BankLibrary::new`] —
+    /// a "break it" mode for demonstrating that a conformance suite or
+    /// monitor actually detects the violation this fault lets through.
+    pub fn with_fault(fault: Fault) -> Self {
+        let mut bank = Self::with_policies(OverdraftPolicy::NONE, InterestPolicy::NONE);
+        bank.fault = fault;
+        bank
+    }
+
+    fn with_policies(overdraft: OverdraftPolicy, interest: InterestPolicy) -> Self {
+        Self {
+            accounts: RwLock::new(HashMap::new()),
+            rates: RwLock::new(HashMap::new()),
+            overdraft_limit: overdraft.overdraft_limit,
+            interest_policy: interest,
+            fault: Fault::None,
+        }
+    }
+
+    /// Ensures `account_id` has an account, creating an empty USD one on
+    /// first sight.
+    ///
+    /// Used by batch front ends that key accounts by an externally supplied
+    /// id (e.g. a CSV `client` column) instead of `create_account`'s
+    /// sequential `ACC-N` ids.
+    pub fn ensure_account(&self, account_id: &str) {
+        if self.accounts.read().unwrap().contains_key(account_id) {
+            return;
+        }
+        self.accounts
+            .write()
+            .unwrap()
+            .entry(account_id.to_string())
+            .or_insert_with(|| RwLock::new(Account::new(Currency::Usd)));
+    }
+
+    /// Creates an account denominated in `currency` instead of
+    /// `create_account`'s implicit USD.
+    pub fn create_account_with_currency(&self, owner: String, currency: Currency) -> String {
+        let _ = owner;
+        self.insert_account(currency)
+    }
+
+    /// Inserts a fresh, empty account and returns its auto-assigned id.
+    fn insert_account(&self, currency: Currency) -> String {
+        let mut accounts = self.accounts.write().unwrap();
+        let id = format!("ACC-{}", accounts.len() + 1);
+        accounts.insert(id.clone(), RwLock::new(Account::new(currency)));
         id
     }
 
-    fn deposit(&mut self, account_id: &str, amount: f64) -> Result<()> {
-        let acc = self.accounts.get_mut(account_id).ok_or("CONST-003: Invalid ID")?;
-        if amount <= 0.0 { return Err("CONST-001: Must be positive".into()); }
-        acc.balance += amount;
+    /// Returns the currency `account_id` is denominated in.
+    pub fn currency(&self, account_id: &str) -> Result<Currency> {
+        self.with_account_ref(account_id, |acc| acc.currency)
+    }
+
+    /// Declares the rate (units of `to` per unit of `from`) that
+    /// [`BankLibrary::convert_and_deposit`] should use between two
+    /// currencies. A later call for the same pair replaces the rate.
+    pub fn set_conversion_rate(&self, from: Currency, to: Currency, rate: f64) {
+        self.rates.write().unwrap().insert((from, to), rate);
+    }
+
+    /// Deposits `amount` denominated in `currency`, rejecting it
+    /// (CONST-010) if that doesn't match the account's own currency.
+    /// Cross-currency deposits go through
+    /// [`BankLibrary::convert_and_deposit`] instead.
+    pub fn deposit_in(&self, account_id: &str, amount: Money, currency: Currency) -> Result<()> {
+        self.with_account(account_id, |acc| {
+            if currency != acc.currency {
+                return Err(BankError::CurrencyMismatch { expected: acc.currency, actual: currency });
+            }
+            let tx = acc.next_tx;
+            Self::apply_deposit(acc, tx, amount, None, self.fault)
+        })
+    }
+
+    /// Deposits `amount` denominated in `from`, converting it to the
+    /// account's currency at a rate declared via
+    /// [`BankLibrary::set_conversion_rate`]. Rejects the deposit
+    /// (CONST-011) if no such rate has been declared.
+    pub fn convert_and_deposit(&self, account_id: &str, amount: Money, from: Currency) -> Result<()> {
+        let to = self.currency(account_id)?;
+        if from == to {
+            return self.deposit_in(account_id, amount, from);
+        }
+        let rate = self.rates.read().unwrap().get(&(from, to)).copied();
+        let rate = rate.ok_or(BankError::MissingConversionRate { from, to })?;
+        let converted = amount.convert(rate)?;
+        self.with_account(account_id, |acc| {
+            let tx = acc.next_tx;
+            Self::apply_deposit(acc, tx, converted, None, self.fault)
+        })
+    }
+
+    /// Returns the ids of all accounts that aren't locked.
+    pub fn account_numbers(&self) -> HashSet<String> {
+        self.accounts
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, acc)| !acc.read().unwrap().locked)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Deposits with a caller-supplied transaction id instead of an
+    /// auto-assigned one, so a replayed log keeps its original tx ids.
+    pub fn deposit_with_tx(&self, account_id: &str, tx: u32, amount: Money) -> Result<()> {
+        self.with_account(account_id, |acc| Self::apply_deposit(acc, tx, amount, None, self.fault))
+    }
+
+    /// Withdraws with a caller-supplied transaction id; see `deposit_with_tx`.
+    pub fn withdraw_with_tx(&self, account_id: &str, tx: u32, amount: Money) -> Result<()> {
+        self.with_account(account_id, |acc| Self::apply_withdraw(acc, tx, amount, self.overdraft_limit, None, self.fault))
+    }
+
+    /// Deposits `amount`, keyed by a caller-supplied `idempotency_key`
+    /// instead of [`deposit_with_tx`][Self::deposit_with_tx]'s transaction
+    /// id: a call with a key that's already succeeded once returns the
+    /// balance that call credited, unchanged, instead of crediting
+    /// `amount` again — the way a retried HTTP request is expected not to
+    /// double-charge. A key that hasn't been seen before deposits
+    /// normally and remembers its resulting balance for any later replay.
+    ///
+    /// Unlike `deposit_with_tx`, a repeated key is never an error
+    /// ([`BankError::DuplicateTransaction`]): the point of an idempotency
+    /// key is specifically to make a retried call succeed quietly.
+    pub fn deposit_with_idempotency_key(
+        &self,
+        account_id: &str,
+        idempotency_key: &str,
+        amount: Money,
+    ) -> Result<Money> {
+        self.with_account(account_id, |acc| {
+            if let Some(&balance) = acc.idempotency_keys.get(idempotency_key) {
+                return Ok(balance);
+            }
+            let tx = acc.next_tx;
+            Self::apply_deposit(acc, tx, amount, Some(idempotency_key.to_string()), self.fault)?;
+            let balance = acc.balance();
+            acc.idempotency_keys.insert(idempotency_key.to_string(), balance);
+            Ok(balance)
+        })
+    }
+
+    pub fn available(&self, account_id: &str) -> Result<Money> {
+        self.with_account_ref(account_id, |acc| acc.available)
+    }
+
+    pub fn held(&self, account_id: &str) -> Result<Money> {
+        self.with_account_ref(account_id, |acc| acc.held)
+    }
+
+    pub fn is_locked(&self, account_id: &str) -> Result<bool> {
+        self.with_account_ref(account_id, |acc| acc.locked)
+    }
+
+    /// The account's lifecycle state; see [`AccountState`].
+    pub fn state(&self, account_id: &str) -> Result<AccountState> {
+        self.with_account_ref(account_id, |acc| acc.state)
+    }
+
+    /// Returns up to `page_size` of `account_id`'s transactions with an
+    /// id greater than `after` (`None` for the first page), ordered by id
+    /// ascending. Rejects `page_size` over [`MAX_PAGE_SIZE`] (CONST-015)
+    /// instead of silently capping it, so an oversized request fails at
+    /// the call site rather than leaving a caller to wonder why fewer
+    /// records came back than asked for.
+    pub fn transactions_page(
+        &self,
+        account_id: &str,
+        after: Option<u32>,
+        page_size: usize,
+    ) -> Result<TransactionPage> {
+        if page_size > MAX_PAGE_SIZE {
+            return Err(BankError::PageSizeExceeded { requested: page_size, max: MAX_PAGE_SIZE });
+        }
+        self.with_account_ref(account_id, |acc| {
+            let mut ids: Vec<u32> = acc
+                .transactions
+                .keys()
+                .copied()
+                .filter(|id| after.is_none_or(|after| *id > after))
+                .collect();
+            ids.sort_unstable();
+
+            let has_more = ids.len() > page_size;
+            ids.truncate(page_size);
+            let next_cursor = if has_more { ids.last().copied().or(after) } else { None };
+
+            let records = ids
+                .into_iter()
+                .map(|id| {
+                    let t = &acc.transactions[&id];
+                    TransactionRecord {
+                        tx: id,
+                        amount: t.amount,
+                        is_deposit: t.is_deposit,
+                        disputed: t.disputed,
+                        timestamp: t.timestamp,
+                        correlation_id: t.correlation_id.clone(),
+                    }
+                })
+                .collect();
+
+            TransactionPage { records, next_cursor }
+        })
+    }
+
+    /// Moves the account from `Open` to `Frozen` (CONST-012), blocking
+    /// withdrawals until [`BankLibrary::unfreeze`] is called. A no-op if
+    /// the account is already `Frozen`; rejected (CONST-013) if it's
+    /// `Closed`.
+    pub fn freeze(&self, account_id: &str) -> Result<()> {
+        self.with_account(account_id, |acc| {
+            if acc.state == AccountState::Closed {
+                return Err(BankError::AccountClosed);
+            }
+            acc.state = AccountState::Frozen;
+            Ok(())
+        })
+    }
+
+    /// Moves the account from `Frozen` back to `Open`. A no-op if the
+    /// account is already `Open`; rejected (CONST-013) if it's `Closed`.
+    pub fn unfreeze(&self, account_id: &str) -> Result<()> {
+        self.with_account(account_id, |acc| {
+            if acc.state == AccountState::Closed {
+                return Err(BankError::AccountClosed);
+            }
+            acc.state = AccountState::Open;
+            Ok(())
+        })
+    }
+
+    /// Moves the account to `Closed`, blocking every mutating operation
+    /// from then on. A no-op if the account is already `Closed`.
+    pub fn close(&self, account_id: &str) -> Result<()> {
+        self.with_account(account_id, |acc| {
+            acc.state = AccountState::Closed;
+            Ok(())
+        })
+    }
+
+    /// Credits `account_id` with interest on its available balance for
+    /// the time elapsed since the account's last accrual (or since
+    /// creation, for the first call), at `self.interest_policy`'s
+    /// `annual_rate`, and returns the amount credited.
+    ///
+    /// `now` is supplied by the caller rather than read from the system
+    /// clock, so a test can drive accrual deterministically (e.g. "a year
+    /// has passed") the same way the harness's injected clock lets a test
+    /// advance time without actually waiting. A `now` at or before the
+    /// account's last accrual counts as zero elapsed time rather than
+    /// negative interest.
+    pub fn accrue_interest(&self, account_id: &str, now: Duration) -> Result<Money> {
+        self.with_account(account_id, |acc| {
+            if acc.locked { return Err(BankError::AccountLocked); }
+            if acc.state == AccountState::Closed { return Err(BankError::AccountClosed); }
+
+            let elapsed = now.checked_sub(acc.last_accrued).unwrap_or(Duration::ZERO);
+            let interest = Self::compute_interest(acc.available, self.interest_policy.annual_rate, elapsed);
+            acc.last_accrued = now;
+            if interest.is_positive() {
+                let tx = acc.next_tx;
+                Self::apply_deposit(acc, tx, interest, None, self.fault)?;
+            }
+            Ok(interest)
+        })
+    }
+
+    /// `balance * annual_rate * (elapsed / SECONDS_PER_YEAR)`, rounded to
+    /// the nearest cent — simple (non-compounding within one call)
+    /// interest proportional to the fraction of a year `elapsed` covers.
+    fn compute_interest(balance: Money, annual_rate: f64, elapsed: Duration) -> Money {
+        let years = elapsed.as_secs_f64() / SECONDS_PER_YEAR;
+        Money::from_dollars_rounded(balance.to_dollars() * annual_rate * years)
+    }
+
+    /// Acquires the per-account write lock for `account_id` and runs `f` on
+    /// it. `deposit`/`withdraw`/`transfer` all go through this single
+    /// locking path.
+    fn with_account<T>(&self, account_id: &str, f: impl FnOnce(&mut Account) -> Result<T>) -> Result<T> {
+        let accounts = self.accounts.read().unwrap();
+        let lock = accounts.get(account_id).ok_or(BankError::InvalidAccount)?;
+        let mut acc = lock.write().unwrap();
+        f(&mut acc)
+    }
+
+    fn with_account_ref<T>(&self, account_id: &str, f: impl FnOnce(&Account) -> T) -> Result<T> {
+        let accounts = self.accounts.read().unwrap();
+        let lock = accounts.get(account_id).ok_or(BankError::InvalidAccount)?;
+        let acc = lock.read().unwrap();
+        Ok(f(&acc))
+    }
+
+    /// Everything [`Self::apply_deposit`] could reject, checked without
+    /// mutating `acc` — the credit-leg half of [`BankAPI::transfer`]'s
+    /// all-or-nothing guarantee.
+    fn check_deposit(acc: &Account, amount: Money, fault: Fault) -> Result<()> {
+        if acc.locked && fault != Fault::IgnoreAccountLock { return Err(BankError::AccountLocked); }
+        if acc.state == AccountState::Closed { return Err(BankError::AccountClosed); }
+        if !amount.is_positive() { return Err(BankError::NonPositiveAmount); }
+        acc.available.checked_add(amount).ok_or(BankError::BalanceOverflow)?;
+        Ok(())
+    }
+
+    /// Everything [`Self::apply_withdraw`] could reject, checked without
+    /// mutating `acc` — the debit-leg half of [`BankAPI::transfer`]'s
+    /// all-or-nothing guarantee. `overdraft_limit` is CONST-002's
+    /// parameter: a withdrawal may take `acc.available` down to
+    /// `-overdraft_limit` before being rejected.
+    fn check_withdraw(acc: &Account, amount: Money, overdraft_limit: Money, fault: Fault) -> Result<()> {
+        if acc.locked && fault != Fault::IgnoreAccountLock { return Err(BankError::AccountLocked); }
+        if acc.state == AccountState::Closed { return Err(BankError::AccountClosed); }
+        if acc.state == AccountState::Frozen { return Err(BankError::AccountFrozen); }
+        if !amount.is_positive() { return Err(BankError::NonPositiveAmount); }
+        if fault != Fault::IgnoreOverdraftLimit && amount > acc.available + overdraft_limit {
+            return Err(BankError::InsufficientFunds);
+        }
+        Ok(())
+    }
+
+    fn apply_deposit(
+        acc: &mut Account,
+        tx: u32,
+        amount: Money,
+        correlation_id: Option<String>,
+        fault: Fault,
+    ) -> Result<()> {
+        Self::check_deposit(acc, amount, fault)?;
+        if acc.transactions.contains_key(&tx) && fault != Fault::AllowDuplicateTransactions {
+            return Err(BankError::DuplicateTransaction);
+        }
+        acc.available = acc.available.checked_add(amount).ok_or(BankError::BalanceOverflow)?;
         acc.history.push(amount);
+        acc.transactions.insert(
+            tx,
+            Transaction { amount, is_deposit: true, disputed: false, timestamp: SystemTime::now(), correlation_id },
+        );
+        acc.next_tx = acc.next_tx.max(tx + 1);
         Ok(())
     }
 
-    fn withdraw(&mut self, account_id: &str, amount: f64) -> Result<()> {
-        let acc = self.accounts.get_mut(account_id).ok_or("CONST-003: Invalid ID")?;
-        if amount <= 0.0 { return Err("CONST-001: Must be positive".into()); }
-        if amount > acc.balance { return Err("CONST-002: Insufficient funds".into()); }
-        acc.balance -= amount;
+    fn apply_withdraw(
+        acc: &mut Account,
+        tx: u32,
+        amount: Money,
+        overdraft_limit: Money,
+        correlation_id: Option<String>,
+        fault: Fault,
+    ) -> Result<()> {
+        Self::check_withdraw(acc, amount, overdraft_limit, fault)?;
+        if acc.transactions.contains_key(&tx) && fault != Fault::AllowDuplicateTransactions {
+            return Err(BankError::DuplicateTransaction);
+        }
+        acc.available = acc.available - amount;
         acc.history.push(-amount);
+        acc.transactions.insert(
+            tx,
+            Transaction { amount, is_deposit: false, disputed: false, timestamp: SystemTime::now(), correlation_id },
+        );
+        acc.next_tx = acc.next_tx.max(tx + 1);
+        Ok(())
+    }
+}
+
+impl Default for BankLibrary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BankAPI for BankLibrary {
+    fn version(&self) -> String { "1".to_string() }
+
+    fn create_account(&self, owner: String) -> String {
+        let _ = owner;
+        self.insert_account(Currency::Usd)
+    }
+
+    fn deposit(&self, account_id: &str, amount: Money) -> Result<()> {
+        self.with_account(account_id, |acc| {
+            let tx = acc.next_tx;
+            Self::apply_deposit(acc, tx, amount, None, self.fault)
+        })
+    }
+
+    fn withdraw(&self, account_id: &str, amount: Money) -> Result<()> {
+        self.with_account(account_id, |acc| {
+            let tx = acc.next_tx;
+            Self::apply_withdraw(acc, tx, amount, self.overdraft_limit, None, self.fault)
+        })
+    }
+
+    /// Debits `from` and credits `to`, or does neither: both legs are
+    /// checked against the locked accounts before either is applied, so a
+    /// credit that would fail (the destination is locked, or the deposit
+    /// would overflow) can never leave the debit applied with nothing to
+    /// show for it.
+    fn transfer(&self, from: &str, to: &str, amount: Money) -> Result<()> {
+        if from == to {
+            return self.with_account(from, |acc| {
+                Self::check_withdraw(acc, amount, self.overdraft_limit, self.fault)?;
+                Self::check_deposit(acc, amount, self.fault)?;
+                let tx = acc.next_tx;
+                Self::apply_withdraw(acc, tx, amount, self.overdraft_limit, None, self.fault)?;
+                let tx = acc.next_tx;
+                Self::apply_deposit(acc, tx, amount, None, self.fault)
+            });
+        }
+
+        let accounts = self.accounts.read().unwrap();
+        let from_lock = accounts.get(from).ok_or(BankError::InvalidAccount)?;
+        let to_lock = accounts.get(to).ok_or(BankError::InvalidAccount)?;
+
+        // Always acquire the two account locks in a stable (sorted-id) order
+        // so a concurrent transfer running in the opposite direction can't
+        // deadlock against this one.
+        let (mut first, mut second) = if from < to {
+            (from_lock.write().unwrap(), to_lock.write().unwrap())
+        } else {
+            let second = to_lock.write().unwrap();
+            let first = from_lock.write().unwrap();
+            (first, second)
+        };
+
+        Self::check_withdraw(&first, amount, self.overdraft_limit, self.fault)?;
+        Self::check_deposit(&second, amount, self.fault)?;
+
+        let tx = first.next_tx;
+        Self::apply_withdraw(&mut first, tx, amount, self.overdraft_limit, None, self.fault)?;
+        let tx = second.next_tx;
+        Self::apply_deposit(&mut second, tx, amount, None, self.fault)?;
         Ok(())
     }
 
-    fn balance(&self, account_id: &str) -> Result<f64> {
-        self.accounts.get(account_id).map(|a| a.balance).ok_or("CONST-003: Invalid ID".into())
+    fn dispute(&self, account_id: &str, tx: u32) -> Result<()> {
+        self.with_account(account_id, |acc| {
+            if acc.state == AccountState::Closed { return Err(BankError::AccountClosed); }
+            if let Some(record) = acc.transactions.get(&tx) {
+                if record.is_deposit && !record.disputed {
+                    let amount = record.amount;
+                    acc.available = acc.available - amount;
+                    acc.held = acc.held + amount;
+                    acc.transactions.get_mut(&tx).unwrap().disputed = true;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn resolve(&self, account_id: &str, tx: u32) -> Result<()> {
+        self.with_account(account_id, |acc| {
+            if acc.state == AccountState::Closed { return Err(BankError::AccountClosed); }
+            if let Some(record) = acc.transactions.get(&tx) {
+                if record.disputed {
+                    let amount = record.amount;
+                    acc.held = acc.held - amount;
+                    acc.available = acc.available + amount;
+                    acc.transactions.get_mut(&tx).unwrap().disputed = false;
+                }
+            }
+            Ok(())
+        })
     }
 
-    fn transactions(&self, account_id: &str) -> Result<Vec<f64>> {
-        self.accounts.get(account_id).map(|a| a.history.clone()).ok_or("CONST-003: Invalid ID".into())
+    fn chargeback(&self, account_id: &str, tx: u32) -> Result<()> {
+        self.with_account(account_id, |acc| {
+            if acc.state == AccountState::Closed { return Err(BankError::AccountClosed); }
+            if let Some(record) = acc.transactions.get(&tx) {
+                if record.disputed {
+                    let amount = record.amount;
+                    acc.held = acc.held - amount;
+                    acc.history.push(-amount);
+                    acc.transactions.get_mut(&tx).unwrap().disputed = false;
+                    acc.locked = true;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn balance(&self, account_id: &str) -> Result<Money> {
+        self.with_account_ref(account_id, |acc| acc.balance())
+    }
+
+    fn transactions(&self, account_id: &str) -> Result<Vec<TransactionRecord>> {
+        self.with_account_ref(account_id, |acc| {
+            let mut ids: Vec<u32> = acc.transactions.keys().copied().collect();
+            ids.sort_unstable();
+            ids.into_iter()
+                .map(|id| {
+                    let t = &acc.transactions[&id];
+                    TransactionRecord {
+                        tx: id,
+                        amount: t.amount,
+                        is_deposit: t.is_deposit,
+                        disputed: t.disputed,
+                        timestamp: t.timestamp,
+                        correlation_id: t.correlation_id.clone(),
+                    }
+                })
+                .collect()
+        })
+    }
+
+    fn assert_balance(&self, account_id: &str, expected: Money) -> Result<()> {
+        let actual = self.balance(account_id)?;
+        if actual != expected {
+            return Err(BankError::BalanceMismatch { account_id: account_id.to_string(), expected, actual });
+        }
+        Ok(())
+    }
+
+    fn audit(&self) -> Result<()> {
+        let accounts = self.accounts.read().unwrap();
+        for (account_id, lock) in accounts.iter() {
+            let acc = lock.read().unwrap();
+            let recomputed = acc.history.iter().fold(Money::ZERO, |sum, m| sum + *m);
+            let stored = acc.balance();
+            if stored != recomputed {
+                return Err(BankError::AuditFailed { account_id: account_id.clone(), stored, recomputed });
+            }
+        }
+        Ok(())
     }
 }
@@ -1,31 +1,710 @@
+mod csv_batch;
 mod lib;
+use clap::Parser;
 use lib::{BankAPI, BankLibrary};
 
 fn main() {
-    let mut bank = BankLibrary::new();
-    println!("Bank API Version: {}", bank.version());
+    let cli = csv_batch::Cli::parse();
+    match cli.input {
+        Some(input) => csv_batch::run(&input),
+        None => {
+            let bank = BankLibrary::new();
+            println!("Bank API Version: {}", bank.version());
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::lib::{
+        AccountState, BankError, Currency, Fault, InterestPolicy, Money, OverdraftPolicy, MAX_PAGE_SIZE,
+    };
+    use std::time::Duration;
+
+    /// Shorthand for a dollar amount that's already cent-aligned, so the
+    /// tests below read in dollars without repeating `.unwrap()` on every
+    /// `Money::from_dollars` call.
+    fn dollars(amount: f64) -> Money {
+        Money::from_dollars(amount).unwrap()
+    }
 
     #[test]
     fn test_constraints() {
-        let mut bank = BankLibrary::new();
+        let bank = BankLibrary::new();
         let id = bank.create_account("User".into());
 
         // Test CONST-001
-        assert!(bank.deposit(&id, -10.0).is_err());
+        assert_eq!(bank.deposit(&id, dollars(-10.0)).unwrap_err(), BankError::NonPositiveAmount);
 
         // Test CONST-002
-        bank.deposit(&id, 50.0).unwrap();
-        assert!(bank.withdraw(&id, 100.0).is_err());
+        bank.deposit(&id, dollars(50.0)).unwrap();
+        assert_eq!(bank.withdraw(&id, dollars(100.0)).unwrap_err(), BankError::InsufficientFunds);
 
         // Test CONST-003
-        assert!(bank.balance("FAKE").is_err());
-        
+        assert_eq!(bank.balance("FAKE").unwrap_err(), BankError::InvalidAccount);
+
         // Test REQ-004
-        assert_eq!(bank.balance(&id).unwrap(), 50.0);
+        assert_eq!(bank.balance(&id).unwrap(), dollars(50.0));
+    }
+
+    #[test]
+    fn test_rejects_sub_cent_amounts() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+
+        // Test CONST-009
+        assert_eq!(Money::from_dollars(10.005).unwrap_err(), BankError::SubCentAmount(10.005));
+        assert!(bank.deposit(&id, dollars(10.0)).is_ok());
+    }
+
+    #[test]
+    fn test_transfer() {
+        let bank = BankLibrary::new();
+        let a = bank.create_account("Alice".into());
+        let b = bank.create_account("Bob".into());
+        bank.deposit(&a, dollars(100.0)).unwrap();
+
+        bank.transfer(&a, &b, dollars(40.0)).unwrap();
+        assert_eq!(bank.balance(&a).unwrap(), dollars(60.0));
+        assert_eq!(bank.balance(&b).unwrap(), dollars(40.0));
+
+        let a_txs = bank.transactions(&a).unwrap();
+        assert_eq!(a_txs.len(), 2);
+        assert_eq!(a_txs[0].amount, dollars(100.0));
+        assert!(a_txs[0].is_deposit);
+        assert_eq!(a_txs[1].amount, dollars(40.0));
+        assert!(!a_txs[1].is_deposit);
+
+        let b_txs = bank.transactions(&b).unwrap();
+        assert_eq!(b_txs.len(), 1);
+        assert_eq!(b_txs[0].amount, dollars(40.0));
+        assert!(b_txs[0].is_deposit);
+
+        // Insufficient funds leaves destination untouched.
+        assert!(bank.transfer(&a, &b, dollars(1000.0)).is_err());
+        assert_eq!(bank.balance(&b).unwrap(), dollars(40.0));
+
+        // Invalid account id on either side mutates nothing.
+        assert!(bank.transfer(&a, "FAKE", dollars(10.0)).is_err());
+        assert!(bank.transfer("FAKE", &a, dollars(10.0)).is_err());
+        assert_eq!(bank.balance(&a).unwrap(), dollars(60.0));
+    }
+
+    #[test]
+    fn test_transfer_is_atomic_when_the_credit_leg_would_fail() {
+        let bank = BankLibrary::new();
+        let a = bank.create_account("Alice".into());
+        let b = bank.create_account("Bob".into());
+        bank.deposit(&a, dollars(100.0)).unwrap(); // tx 1
+
+        // Lock `b` the same way a chargeback would, so the credit leg is
+        // the one that fails.
+        bank.deposit(&b, dollars(1.0)).unwrap(); // tx 1
+        bank.dispute(&b, 1).unwrap();
+        bank.chargeback(&b, 1).unwrap();
+        assert!(bank.is_locked(&b).unwrap());
+
+        assert_eq!(bank.transfer(&a, &b, dollars(50.0)).unwrap_err(), BankError::AccountLocked);
+
+        // The debit must not have happened either: `a` still has its full
+        // balance and no withdrawal in its history.
+        assert_eq!(bank.balance(&a).unwrap(), dollars(100.0));
+        let a_txs = bank.transactions(&a).unwrap();
+        assert_eq!(a_txs.len(), 1);
+        assert_eq!(a_txs[0].amount, dollars(100.0));
+        assert!(a_txs[0].is_deposit);
+    }
+
+    #[test]
+    fn test_transfer_is_atomic_when_the_debit_leg_would_fail() {
+        let bank = BankLibrary::new();
+        let a = bank.create_account("Alice".into());
+        let b = bank.create_account("Bob".into());
+        bank.deposit(&a, dollars(10.0)).unwrap();
+
+        assert_eq!(
+            bank.transfer(&a, &b, dollars(50.0)).unwrap_err(),
+            BankError::InsufficientFunds
+        );
+
+        // The credit must not have happened either: `b` has no balance and
+        // no deposit in its history.
+        assert_eq!(bank.balance(&b).unwrap(), Money::ZERO);
+        assert!(bank.transactions(&b).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_dispute_resolve() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(100.0)).unwrap(); // tx 1
+        bank.deposit(&id, dollars(20.0)).unwrap(); // tx 2
+
+        bank.dispute(&id, 1).unwrap();
+        assert_eq!(bank.balance(&id).unwrap(), dollars(120.0));
+
+        // Disputing the same tx twice is a no-op.
+        bank.dispute(&id, 1).unwrap();
+
+        bank.resolve(&id, 1).unwrap();
+        assert_eq!(bank.balance(&id).unwrap(), dollars(120.0));
+
+        // Resolving a tx that isn't under dispute is a no-op.
+        bank.resolve(&id, 1).unwrap();
+
+        // Disputing a tx that doesn't exist is a no-op.
+        bank.dispute(&id, 999).unwrap();
+    }
+
+    #[test]
+    fn test_chargeback_locks_account() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(100.0)).unwrap(); // tx 1
+
+        bank.dispute(&id, 1).unwrap();
+        bank.chargeback(&id, 1).unwrap();
+        assert_eq!(bank.balance(&id).unwrap(), Money::ZERO);
+
+        // A locked account rejects further deposits and withdrawals.
+        assert!(bank.deposit(&id, dollars(10.0)).is_err());
+        assert!(bank.withdraw(&id, dollars(1.0)).is_err());
+    }
+
+    #[test]
+    fn test_only_deposits_are_disputable() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(100.0)).unwrap(); // tx 1
+        bank.withdraw(&id, dollars(30.0)).unwrap(); // tx 2
+
+        // Disputing a withdrawal is a no-op.
+        bank.dispute(&id, 2).unwrap();
+        assert_eq!(bank.balance(&id).unwrap(), dollars(70.0));
+    }
+
+    #[test]
+    fn test_deposit_rejects_overflow() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, Money::from_cents(i64::MAX)).unwrap();
+
+        assert_eq!(
+            bank.deposit(&id, Money::from_cents(i64::MAX)).unwrap_err(),
+            BankError::BalanceOverflow
+        );
+        // The failed deposit must not have mutated the balance.
+        assert_eq!(bank.balance(&id).unwrap(), Money::from_cents(i64::MAX));
+    }
+
+    #[test]
+    fn test_explicit_tx_rejects_duplicates_and_protects_auto_assigned_ids() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit_with_tx(&id, 5, dollars(100.0)).unwrap();
+
+        // Replaying the same tx id is rejected rather than clobbering the
+        // earlier transaction's dispute state.
+        assert_eq!(
+            bank.deposit_with_tx(&id, 5, dollars(10.0)).unwrap_err(),
+            BankError::DuplicateTransaction
+        );
+
+        // An auto-assigned deposit must not reuse tx 5 either.
+        bank.deposit(&id, dollars(1.0)).unwrap();
+        assert!(bank.dispute(&id, 5).is_ok());
+        assert_eq!(bank.balance(&id).unwrap(), dollars(101.0));
+    }
+
+    #[test]
+    fn test_account_numbers_excludes_locked() {
+        let bank = BankLibrary::new();
+        let a = bank.create_account("Alice".into());
+        let b = bank.create_account("Bob".into());
+        bank.deposit(&a, dollars(100.0)).unwrap(); // tx 1
+        bank.dispute(&a, 1).unwrap();
+        bank.chargeback(&a, 1).unwrap();
+
+        let active = bank.account_numbers();
+        assert!(!active.contains(&a));
+        assert!(active.contains(&b));
+    }
+
+    #[test]
+    fn test_concurrent_deposits_to_distinct_accounts() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let bank = Arc::new(BankLibrary::new());
+        let a = bank.create_account("Alice".into());
+        let b = bank.create_account("Bob".into());
+
+        let bank_a = Arc::clone(&bank);
+        let account_a = a.clone();
+        let handle = thread::spawn(move || {
+            for _ in 0..100 {
+                bank_a.deposit(&account_a, dollars(1.0)).unwrap();
+            }
+        });
+
+        for _ in 0..100 {
+            bank.deposit(&b, dollars(1.0)).unwrap();
+        }
+        handle.join().unwrap();
+
+        assert_eq!(bank.balance(&a).unwrap(), dollars(100.0));
+        assert_eq!(bank.balance(&b).unwrap(), dollars(100.0));
+    }
+
+    #[test]
+    fn test_assert_balance() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(50.0)).unwrap();
+
+        bank.assert_balance(&id, dollars(50.0)).unwrap();
+        assert_eq!(
+            bank.assert_balance(&id, dollars(40.0)).unwrap_err(),
+            BankError::BalanceMismatch { account_id: id.clone(), expected: dollars(40.0), actual: dollars(50.0) }
+        );
+    }
+
+    #[test]
+    fn test_deposit_in_rejects_a_currency_that_does_not_match_the_account() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into()); // defaults to USD
+
+        // Test CONST-010
+        assert_eq!(
+            bank.deposit_in(&id, dollars(10.0), Currency::Eur).unwrap_err(),
+            BankError::CurrencyMismatch { expected: Currency::Usd, actual: Currency::Eur }
+        );
+        assert_eq!(bank.balance(&id).unwrap(), Money::ZERO);
+
+        assert!(bank.deposit_in(&id, dollars(10.0), Currency::Usd).is_ok());
+    }
+
+    #[test]
+    fn test_convert_and_deposit_requires_a_declared_rate() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into()); // defaults to USD
+
+        // Test CONST-011
+        assert_eq!(
+            bank.convert_and_deposit(&id, dollars(10.0), Currency::Eur).unwrap_err(),
+            BankError::MissingConversionRate { from: Currency::Eur, to: Currency::Usd }
+        );
+        assert_eq!(bank.balance(&id).unwrap(), Money::ZERO);
+    }
+
+    #[test]
+    fn test_convert_and_deposit_applies_the_declared_rate() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into()); // defaults to USD
+        bank.set_conversion_rate(Currency::Eur, Currency::Usd, 1.1);
+
+        bank.convert_and_deposit(&id, dollars(10.0), Currency::Eur).unwrap();
+        assert_eq!(bank.balance(&id).unwrap(), dollars(11.0));
+    }
+
+    #[test]
+    fn test_convert_and_deposit_is_a_same_currency_deposit_when_rates_agree() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into()); // defaults to USD
+
+        // No rate declared, but none is needed: the deposit's currency
+        // already matches the account's.
+        bank.convert_and_deposit(&id, dollars(10.0), Currency::Usd).unwrap();
+        assert_eq!(bank.balance(&id).unwrap(), dollars(10.0));
+    }
+
+    #[test]
+    fn test_create_account_with_currency_is_honored_by_deposit_in() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account_with_currency("User".into(), Currency::Gbp);
+        assert_eq!(bank.currency(&id).unwrap(), Currency::Gbp);
+
+        bank.deposit_in(&id, dollars(5.0), Currency::Gbp).unwrap();
+        assert_eq!(bank.balance(&id).unwrap(), dollars(5.0));
+    }
+
+    /// Boundary tests at `overdraft_limit` itself, generated the way a
+    /// `RequirementTemplate`'s parameter gets stamped into the requirement
+    /// it instantiates: one case at the limit, one just past it.
+    #[test]
+    fn test_withdraw_at_the_overdraft_limit_succeeds() {
+        let bank = BankLibrary::with_overdraft_policy(OverdraftPolicy { overdraft_limit: dollars(20.0) });
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(50.0)).unwrap();
+
+        // Test CONST-002: withdrawing down to exactly -overdraft_limit is allowed.
+        bank.withdraw(&id, dollars(70.0)).unwrap();
+        assert_eq!(bank.balance(&id).unwrap(), -dollars(20.0));
+    }
+
+    #[test]
+    fn test_withdraw_one_cent_past_the_overdraft_limit_is_rejected() {
+        let bank = BankLibrary::with_overdraft_policy(OverdraftPolicy { overdraft_limit: dollars(20.0) });
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(50.0)).unwrap();
+
+        // Test CONST-002: one cent further overdrawn is rejected, and the
+        // balance is left exactly where the failed withdrawal found it.
+        assert_eq!(
+            bank.withdraw(&id, dollars(70.01)).unwrap_err(),
+            BankError::InsufficientFunds
+        );
+        assert_eq!(bank.balance(&id).unwrap(), dollars(50.0));
+    }
+
+    #[test]
+    fn test_overdraft_policy_defaults_to_no_overdraft() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(50.0)).unwrap();
+
+        assert_eq!(
+            bank.withdraw(&id, dollars(50.01)).unwrap_err(),
+            BankError::InsufficientFunds
+        );
+    }
+
+    #[test]
+    fn test_audit_holds_after_transfer_and_chargeback() {
+        let bank = BankLibrary::new();
+        let a = bank.create_account("Alice".into());
+        let b = bank.create_account("Bob".into());
+        bank.deposit(&a, dollars(100.0)).unwrap(); // tx 1
+        bank.transfer(&a, &b, dollars(30.0)).unwrap();
+
+        bank.audit().unwrap();
+
+        bank.dispute(&a, 1).unwrap();
+        bank.chargeback(&a, 1).unwrap();
+
+        bank.audit().unwrap();
+    }
+
+    #[test]
+    fn test_freeze_blocks_withdrawals_but_not_deposits() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(50.0)).unwrap();
+
+        bank.freeze(&id).unwrap();
+        assert_eq!(bank.state(&id).unwrap(), AccountState::Frozen);
+        assert_eq!(bank.withdraw(&id, dollars(10.0)).unwrap_err(), BankError::AccountFrozen);
+        bank.deposit(&id, dollars(10.0)).unwrap();
+
+        bank.unfreeze(&id).unwrap();
+        assert_eq!(bank.state(&id).unwrap(), AccountState::Open);
+        bank.withdraw(&id, dollars(10.0)).unwrap();
+    }
+
+    #[test]
+    fn test_close_blocks_every_mutating_operation() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(50.0)).unwrap(); // tx 1
+
+        bank.close(&id).unwrap();
+        assert_eq!(bank.state(&id).unwrap(), AccountState::Closed);
+
+        assert_eq!(bank.deposit(&id, dollars(10.0)).unwrap_err(), BankError::AccountClosed);
+        assert_eq!(bank.withdraw(&id, dollars(10.0)).unwrap_err(), BankError::AccountClosed);
+        assert_eq!(bank.dispute(&id, 1).unwrap_err(), BankError::AccountClosed);
+        assert_eq!(bank.resolve(&id, 1).unwrap_err(), BankError::AccountClosed);
+        assert_eq!(bank.chargeback(&id, 1).unwrap_err(), BankError::AccountClosed);
+        assert_eq!(bank.freeze(&id).unwrap_err(), BankError::AccountClosed);
+        assert_eq!(bank.unfreeze(&id).unwrap_err(), BankError::AccountClosed);
+
+        // Closing is terminal, but idempotent.
+        bank.close(&id).unwrap();
+        // Reads are unaffected — a closed account still reports its balance.
+        assert_eq!(bank.balance(&id).unwrap(), dollars(50.0));
+    }
+
+    /// Mirrors the spec's `Account` FSM (`Open` -[`freeze`]-> `Frozen`
+    /// -[`unfreeze`]-> `Open`, `{Open, Frozen}` -[`close`]-> `Closed`):
+    /// drives every `(state, operation)` pair through the implementation
+    /// and checks it honors exactly the guards the transition table
+    /// implies, rather than hand-picking a handful of cases.
+    #[test]
+    fn test_account_lifecycle_transition_table_is_honored_for_every_state() {
+        for &state in &[AccountState::Open, AccountState::Frozen, AccountState::Closed] {
+            let bank = BankLibrary::new();
+            let id = bank.create_account("User".into());
+            bank.deposit(&id, dollars(10.0)).unwrap();
+            match state {
+                AccountState::Open => {}
+                AccountState::Frozen => bank.freeze(&id).unwrap(),
+                AccountState::Closed => bank.close(&id).unwrap(),
+            }
+            assert_eq!(bank.state(&id).unwrap(), state);
+
+            let withdraw_result = bank.withdraw(&id, dollars(1.0));
+            let deposit_result = bank.deposit(&id, dollars(1.0));
+            match state {
+                AccountState::Open => {
+                    assert!(withdraw_result.is_ok(), "withdraw should succeed while Open");
+                    assert!(deposit_result.is_ok(), "deposit should succeed while Open");
+                }
+                AccountState::Frozen => {
+                    assert_eq!(withdraw_result.unwrap_err(), BankError::AccountFrozen);
+                    assert!(deposit_result.is_ok(), "deposit should succeed while Frozen");
+                }
+                AccountState::Closed => {
+                    assert_eq!(withdraw_result.unwrap_err(), BankError::AccountClosed);
+                    assert_eq!(deposit_result.unwrap_err(), BankError::AccountClosed);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_accrue_interest_after_one_year_at_five_percent() {
+        let bank = BankLibrary::with_interest_policy(InterestPolicy { annual_rate: 0.05 });
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(1000.0)).unwrap();
+
+        let one_year = Duration::from_secs_f64(365.25 * 24.0 * 60.0 * 60.0);
+        let credited = bank.accrue_interest(&id, one_year).unwrap();
+
+        assert_eq!(credited, dollars(50.0));
+        assert_eq!(bank.balance(&id).unwrap(), dollars(1050.0));
+    }
+
+    #[test]
+    fn test_accrue_interest_is_proportional_to_elapsed_time() {
+        let bank = BankLibrary::with_interest_policy(InterestPolicy { annual_rate: 0.1 });
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(1200.0)).unwrap();
+
+        let one_month = Duration::from_secs_f64(365.25 * 24.0 * 60.0 * 60.0 / 12.0);
+        let credited = bank.accrue_interest(&id, one_month).unwrap();
+
+        assert_eq!(credited, dollars(10.0));
+    }
+
+    #[test]
+    fn test_accrue_interest_twice_only_charges_for_the_newly_elapsed_time() {
+        let bank = BankLibrary::with_interest_policy(InterestPolicy { annual_rate: 0.05 });
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(1000.0)).unwrap();
+
+        let one_year = Duration::from_secs_f64(365.25 * 24.0 * 60.0 * 60.0);
+        bank.accrue_interest(&id, one_year).unwrap();
+        // Calling again with the same `now` has no further time elapsed.
+        let second_call = bank.accrue_interest(&id, one_year).unwrap();
+
+        assert_eq!(second_call, Money::ZERO);
+        assert_eq!(bank.balance(&id).unwrap(), dollars(1050.0));
+    }
+
+    #[test]
+    fn test_accrue_interest_defaults_to_no_interest() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(1000.0)).unwrap();
+
+        let one_year = Duration::from_secs_f64(365.25 * 24.0 * 60.0 * 60.0);
+        let credited = bank.accrue_interest(&id, one_year).unwrap();
+
+        assert_eq!(credited, Money::ZERO);
+        assert_eq!(bank.balance(&id).unwrap(), dollars(1000.0));
+    }
+
+    #[test]
+    fn test_accrue_interest_rejects_a_closed_account() {
+        let bank = BankLibrary::with_interest_policy(InterestPolicy { annual_rate: 0.05 });
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(1000.0)).unwrap();
+        bank.close(&id).unwrap();
+
+        assert_eq!(
+            bank.accrue_interest(&id, Duration::from_secs(1)).unwrap_err(),
+            BankError::AccountClosed
+        );
+    }
+
+    #[test]
+    fn test_transactions_page_orders_by_id_ascending_and_sets_a_next_cursor() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(10.0)).unwrap(); // tx 1
+        bank.withdraw(&id, dollars(4.0)).unwrap(); // tx 2
+        bank.deposit(&id, dollars(1.0)).unwrap(); // tx 3
+
+        let page = bank.transactions_page(&id, None, 2).unwrap();
+        assert_eq!(page.records.len(), 2);
+        assert_eq!(page.records[0].tx, 1);
+        assert_eq!(page.records[0].amount, dollars(10.0));
+        assert!(page.records[0].is_deposit);
+        assert!(!page.records[0].disputed);
+        assert_eq!(page.records[1].tx, 2);
+        assert_eq!(page.records[1].amount, dollars(4.0));
+        assert!(!page.records[1].is_deposit);
+        assert!(!page.records[1].disputed);
+        assert_eq!(page.next_cursor, Some(2));
+
+        let next = bank.transactions_page(&id, page.next_cursor, 2).unwrap();
+        assert_eq!(next.records.len(), 1);
+        assert_eq!(next.records[0].tx, 3);
+        assert_eq!(next.records[0].amount, dollars(1.0));
+        assert!(next.records[0].is_deposit);
+        assert_eq!(next.next_cursor, None);
+    }
+
+    #[test]
+    fn test_transactions_page_cursor_is_stable_across_later_appends() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(10.0)).unwrap(); // tx 1
+        bank.deposit(&id, dollars(20.0)).unwrap(); // tx 2
+
+        let first_page = bank.transactions_page(&id, None, 1).unwrap();
+        assert_eq!(first_page.records[0].tx, 1);
+        assert_eq!(first_page.next_cursor, Some(1));
+
+        // A transaction appended after the first page was read doesn't
+        // reorder or reappear in it; it only shows up past the cursor.
+        bank.deposit(&id, dollars(30.0)).unwrap(); // tx 3
+        let second_page = bank.transactions_page(&id, first_page.next_cursor, MAX_PAGE_SIZE).unwrap();
+        assert_eq!(second_page.records.iter().map(|r| r.tx).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(second_page.next_cursor, None);
+    }
+
+    #[test]
+    fn test_transactions_page_reflects_a_dispute() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+        bank.deposit(&id, dollars(10.0)).unwrap(); // tx 1
+        bank.dispute(&id, 1).unwrap();
+
+        let page = bank.transactions_page(&id, None, MAX_PAGE_SIZE).unwrap();
+        assert_eq!(page.records.len(), 1);
+        assert_eq!(page.records[0].tx, 1);
+        assert_eq!(page.records[0].amount, dollars(10.0));
+        assert!(page.records[0].is_deposit);
+        assert!(page.records[0].disputed);
+    }
+
+    #[test]
+    fn test_transactions_page_rejects_a_page_size_over_the_maximum() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+
+        assert_eq!(
+            bank.transactions_page(&id, None, MAX_PAGE_SIZE + 1).unwrap_err(),
+            BankError::PageSizeExceeded { requested: MAX_PAGE_SIZE + 1, max: MAX_PAGE_SIZE }
+        );
+    }
+
+    /// Generated replay/duplicate scenarios for
+    /// `deposit_with_idempotency_key`'s constraint that a replayed key
+    /// returns the original result without crediting again — one
+    /// accidental duplicate, a request retried several times, and a retry
+    /// that mistakenly resends a different amount, mirroring the
+    /// transition-table test above rather than hand-picking one case.
+    #[test]
+    fn test_deposit_with_idempotency_key_replay_scenarios() {
+        struct Scenario {
+            name: &'static str,
+            first_amount: f64,
+            replays: &'static [f64],
+        }
+
+        const SCENARIOS: &[Scenario] = &[
+            Scenario { name: "single duplicate submission", first_amount: 10.0, replays: &[10.0] },
+            Scenario { name: "request retried several times", first_amount: 25.0, replays: &[25.0, 25.0, 25.0] },
+            Scenario { name: "retry resends a different amount", first_amount: 5.0, replays: &[99.0] },
+        ];
+
+        for scenario in SCENARIOS {
+            let bank = BankLibrary::new();
+            let id = bank.create_account("User".into());
+            let key = "idem-key-1";
+
+            let original =
+                bank.deposit_with_idempotency_key(&id, key, dollars(scenario.first_amount)).unwrap();
+            assert_eq!(original, dollars(scenario.first_amount), "{}: first call", scenario.name);
+
+            for &replay_amount in scenario.replays {
+                let replayed =
+                    bank.deposit_with_idempotency_key(&id, key, dollars(replay_amount)).unwrap();
+                assert_eq!(replayed, original, "{}: replay returned a different result", scenario.name);
+            }
+
+            assert_eq!(
+                bank.balance(&id).unwrap(),
+                dollars(scenario.first_amount),
+                "{}: replay double-credited the account",
+                scenario.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_deposit_with_idempotency_key_different_keys_both_credit() {
+        let bank = BankLibrary::new();
+        let id = bank.create_account("User".into());
+
+        bank.deposit_with_idempotency_key(&id, "key-a", dollars(10.0)).unwrap();
+        bank.deposit_with_idempotency_key(&id, "key-b", dollars(5.0)).unwrap();
+
+        assert_eq!(bank.balance(&id).unwrap(), dollars(15.0));
+    }
+
+    // The tests below inject a `Fault` and show it lets through exactly the
+    // violation a healthy `BankLibrary` rejects (CONST-002, CONST-004,
+    // CONST-006) — the divergence a conformance vector or monitor exercising
+    // the same operation would need to catch.
+
+    #[test]
+    fn test_ignore_overdraft_limit_lets_a_withdrawal_overdraw_past_const_002() {
+        let healthy = BankLibrary::new();
+        let id = healthy.create_account("User".into());
+        healthy.deposit(&id, dollars(50.0)).unwrap();
+        assert_eq!(healthy.withdraw(&id, dollars(100.0)).unwrap_err(), BankError::InsufficientFunds);
+
+        let faulty = BankLibrary::with_fault(Fault::IgnoreOverdraftLimit);
+        let id = faulty.create_account("User".into());
+        faulty.deposit(&id, dollars(50.0)).unwrap();
+        faulty.withdraw(&id, dollars(100.0)).unwrap();
+        assert_eq!(faulty.balance(&id).unwrap(), dollars(-50.0));
+    }
+
+    #[test]
+    fn test_ignore_account_lock_lets_a_locked_account_transact() {
+        let healthy = BankLibrary::new();
+        let id = healthy.create_account("User".into());
+        healthy.deposit(&id, dollars(1.0)).unwrap();
+        healthy.dispute(&id, 1).unwrap();
+        healthy.chargeback(&id, 1).unwrap();
+        assert!(healthy.is_locked(&id).unwrap());
+        assert_eq!(healthy.deposit(&id, dollars(10.0)).unwrap_err(), BankError::AccountLocked);
+
+        let faulty = BankLibrary::with_fault(Fault::IgnoreAccountLock);
+        let id = faulty.create_account("User".into());
+        faulty.deposit(&id, dollars(1.0)).unwrap();
+        faulty.dispute(&id, 1).unwrap();
+        faulty.chargeback(&id, 1).unwrap();
+        assert!(faulty.is_locked(&id).unwrap());
+        faulty.deposit(&id, dollars(10.0)).unwrap();
+    }
+
+    #[test]
+    fn test_allow_duplicate_transactions_lets_a_replayed_tx_credit_twice() {
+        let healthy = BankLibrary::new();
+        let id = healthy.create_account("User".into());
+        healthy.deposit_with_tx(&id, 1, dollars(10.0)).unwrap();
+        assert_eq!(healthy.deposit_with_tx(&id, 1, dollars(10.0)).unwrap_err(), BankError::DuplicateTransaction);
+
+        let faulty = BankLibrary::with_fault(Fault::AllowDuplicateTransactions);
+        let id = faulty.create_account("User".into());
+        faulty.deposit_with_tx(&id, 1, dollars(10.0)).unwrap();
+        faulty.deposit_with_tx(&id, 1, dollars(10.0)).unwrap();
+        assert_eq!(faulty.balance(&id).unwrap(), dollars(20.0));
     }
 }
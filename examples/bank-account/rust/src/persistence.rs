@@ -0,0 +1,350 @@
+//! A durable `BankLibrary`: every acknowledged write is appended to an
+//! on-disk, `fsync`ed journal before the call returns, so state survives
+//! a process restart (a crash, `kill -9`, or just re-running the binary)
+//! without losing anything the caller was told succeeded.
+//!
+//! [`PersistentBankLibrary::open`] replays the journal from scratch to
+//! rebuild an in-memory [`BankLibrary`], the same way `csv_batch::run`
+//! replays a CSV log — the journal is just a tab-separated line per
+//! mutating call instead of a CSV row.
+
+use super::{BankAPI, BankError, BankLibrary, Money, Result, TransactionRecord};
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// A single journaled call, in the order it was acknowledged.
+enum JournalOp {
+    CreateAccount { id: String },
+    Deposit { account_id: String, tx: u32, cents: i64 },
+    Withdraw { account_id: String, tx: u32, cents: i64 },
+    Dispute { account_id: String, tx: u32 },
+    Resolve { account_id: String, tx: u32 },
+    Chargeback { account_id: String, tx: u32 },
+}
+
+impl fmt::Display for JournalOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JournalOp::CreateAccount { id } => write!(f, "CREATE\t{id}"),
+            JournalOp::Deposit { account_id, tx, cents } => write!(f, "DEPOSIT\t{account_id}\t{tx}\t{cents}"),
+            JournalOp::Withdraw { account_id, tx, cents } => write!(f, "WITHDRAW\t{account_id}\t{tx}\t{cents}"),
+            JournalOp::Dispute { account_id, tx } => write!(f, "DISPUTE\t{account_id}\t{tx}"),
+            JournalOp::Resolve { account_id, tx } => write!(f, "RESOLVE\t{account_id}\t{tx}"),
+            JournalOp::Chargeback { account_id, tx } => write!(f, "CHARGEBACK\t{account_id}\t{tx}"),
+        }
+    }
+}
+
+impl std::str::FromStr for JournalOp {
+    type Err = PersistenceError;
+
+    fn from_str(line: &str) -> std::result::Result<Self, Self::Err> {
+        let corrupt = || PersistenceError::Corrupt(line.to_string());
+        let mut fields = line.split('\t');
+        let op = fields.next().ok_or_else(corrupt)?;
+
+        match op {
+            "CREATE" => Ok(JournalOp::CreateAccount { id: next_field(&mut fields, line)? }),
+            "DEPOSIT" | "WITHDRAW" => {
+                let account_id = next_field(&mut fields, line)?;
+                let tx = next_parsed(&mut fields, line)?;
+                let cents = next_parsed(&mut fields, line)?;
+                if op == "DEPOSIT" {
+                    Ok(JournalOp::Deposit { account_id, tx, cents })
+                } else {
+                    Ok(JournalOp::Withdraw { account_id, tx, cents })
+                }
+            }
+            "DISPUTE" | "RESOLVE" | "CHARGEBACK" => {
+                let account_id = next_field(&mut fields, line)?;
+                let tx = next_parsed(&mut fields, line)?;
+                Ok(match op {
+                    "DISPUTE" => JournalOp::Dispute { account_id, tx },
+                    "RESOLVE" => JournalOp::Resolve { account_id, tx },
+                    _ => JournalOp::Chargeback { account_id, tx },
+                })
+            }
+            _ => Err(corrupt()),
+        }
+    }
+}
+
+fn next_field(fields: &mut std::str::Split<'_, char>, line: &str) -> std::result::Result<String, PersistenceError> {
+    fields.next().map(str::to_string).ok_or_else(|| PersistenceError::Corrupt(line.to_string()))
+}
+
+fn next_parsed<T: std::str::FromStr>(
+    fields: &mut std::str::Split<'_, char>,
+    line: &str,
+) -> std::result::Result<T, PersistenceError> {
+    fields
+        .next()
+        .ok_or_else(|| PersistenceError::Corrupt(line.to_string()))?
+        .parse()
+        .map_err(|_| PersistenceError::Corrupt(line.to_string()))
+}
+
+/// Everything that can go wrong opening or writing to a
+/// [`PersistentBankLibrary`]'s journal.
+#[derive(Debug)]
+pub enum PersistenceError {
+    /// The in-memory `BankLibrary` rejected the call; nothing was journaled.
+    Bank(BankError),
+    Io(std::io::Error),
+    /// A journal line didn't match any known operation's format.
+    Corrupt(String),
+}
+
+impl fmt::Display for PersistenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistenceError::Bank(e) => write!(f, "{e}"),
+            PersistenceError::Io(e) => write!(f, "{e}"),
+            PersistenceError::Corrupt(line) => write!(f, "corrupt journal line: {line:?}"),
+        }
+    }
+}
+
+impl std::error::Error for PersistenceError {}
+
+impl From<BankError> for PersistenceError {
+    fn from(e: BankError) -> Self {
+        PersistenceError::Bank(e)
+    }
+}
+
+impl From<std::io::Error> for PersistenceError {
+    fn from(e: std::io::Error) -> Self {
+        PersistenceError::Io(e)
+    }
+}
+
+/// The append-only file backing a [`PersistentBankLibrary`].
+struct Journal {
+    file: std::fs::File,
+}
+
+impl Journal {
+    /// Opens `path` (creating it if absent) and parses its existing
+    /// entries, in the order they were appended.
+    fn open(path: &Path) -> std::result::Result<(Self, Vec<JournalOp>), PersistenceError> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let ops = contents
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::parse)
+            .collect::<std::result::Result<Vec<JournalOp>, PersistenceError>>()?;
+
+        let file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+        Ok((Self { file }, ops))
+    }
+
+    /// Appends `op` and `fsync`s it to disk before returning, so a crash
+    /// right after this call can't lose it.
+    fn append(&mut self, op: &JournalOp) -> std::result::Result<(), PersistenceError> {
+        writeln!(self.file, "{op}")?;
+        self.file.sync_data()?;
+        Ok(())
+    }
+}
+
+/// A [`BankLibrary`] whose acknowledged writes survive a process restart.
+///
+/// Every mutating call validates against and applies to the in-memory
+/// `BankLibrary` first; only once that succeeds is it appended to the
+/// journal (`fsync`ed) before the call returns `Ok`. A crash between
+/// those two steps is invisible to the caller, since the call hasn't
+/// returned yet — so nothing the caller was told succeeded can be lost.
+///
+/// ```
+/// # use bank_account::{Money, PersistentBankLibrary};
+/// # let path = std::env::temp_dir().join("persistent-bank-doctest.journal");
+/// # let _ = std::fs::remove_file(&path);
+/// let bank = PersistentBankLibrary::open(&path).unwrap();
+/// bank.create_account("alice").unwrap();
+/// bank.deposit("alice", 1, Money::from_dollars(100.0).unwrap()).unwrap();
+/// drop(bank); // simulates the process exiting
+///
+/// let reopened = PersistentBankLibrary::open(&path).unwrap();
+/// assert_eq!(reopened.balance("alice").unwrap(), Money::from_dollars(100.0).unwrap());
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct PersistentBankLibrary {
+    inner: BankLibrary,
+    journal: Mutex<Journal>,
+}
+
+impl PersistentBankLibrary {
+    /// Opens (creating if absent) the journal at `path`, replaying any
+    /// existing entries to rebuild state before returning.
+    pub fn open(path: impl AsRef<Path>) -> std::result::Result<Self, PersistenceError> {
+        let (journal, ops) = Journal::open(path.as_ref())?;
+        let inner = BankLibrary::new();
+        for op in ops {
+            Self::replay(&inner, op)?;
+        }
+        Ok(Self { inner, journal: Mutex::new(journal) })
+    }
+
+    fn replay(inner: &BankLibrary, op: JournalOp) -> std::result::Result<(), PersistenceError> {
+        match op {
+            JournalOp::CreateAccount { id } => {
+                inner.ensure_account(&id);
+                Ok(())
+            }
+            JournalOp::Deposit { account_id, tx, cents } => {
+                inner.deposit_with_tx(&account_id, tx, Money::from_cents(cents))?;
+                Ok(())
+            }
+            JournalOp::Withdraw { account_id, tx, cents } => {
+                inner.withdraw_with_tx(&account_id, tx, Money::from_cents(cents))?;
+                Ok(())
+            }
+            JournalOp::Dispute { account_id, tx } => {
+                inner.dispute(&account_id, tx)?;
+                Ok(())
+            }
+            JournalOp::Resolve { account_id, tx } => {
+                inner.resolve(&account_id, tx)?;
+                Ok(())
+            }
+            JournalOp::Chargeback { account_id, tx } => {
+                inner.chargeback(&account_id, tx)?;
+                Ok(())
+            }
+        }
+    }
+
+    pub fn create_account(&self, id: &str) -> std::result::Result<(), PersistenceError> {
+        self.inner.ensure_account(id);
+        self.journal.lock().unwrap().append(&JournalOp::CreateAccount { id: id.to_string() })
+    }
+
+    pub fn deposit(&self, account_id: &str, tx: u32, amount: Money) -> std::result::Result<(), PersistenceError> {
+        self.inner.deposit_with_tx(account_id, tx, amount)?;
+        self.journal.lock().unwrap().append(&JournalOp::Deposit {
+            account_id: account_id.to_string(),
+            tx,
+            cents: amount.to_cents(),
+        })
+    }
+
+    pub fn withdraw(&self, account_id: &str, tx: u32, amount: Money) -> std::result::Result<(), PersistenceError> {
+        self.inner.withdraw_with_tx(account_id, tx, amount)?;
+        self.journal.lock().unwrap().append(&JournalOp::Withdraw {
+            account_id: account_id.to_string(),
+            tx,
+            cents: amount.to_cents(),
+        })
+    }
+
+    pub fn dispute(&self, account_id: &str, tx: u32) -> std::result::Result<(), PersistenceError> {
+        self.inner.dispute(account_id, tx)?;
+        self.journal.lock().unwrap().append(&JournalOp::Dispute { account_id: account_id.to_string(), tx })
+    }
+
+    pub fn resolve(&self, account_id: &str, tx: u32) -> std::result::Result<(), PersistenceError> {
+        self.inner.resolve(account_id, tx)?;
+        self.journal.lock().unwrap().append(&JournalOp::Resolve { account_id: account_id.to_string(), tx })
+    }
+
+    pub fn chargeback(&self, account_id: &str, tx: u32) -> std::result::Result<(), PersistenceError> {
+        self.inner.chargeback(account_id, tx)?;
+        self.journal.lock().unwrap().append(&JournalOp::Chargeback { account_id: account_id.to_string(), tx })
+    }
+
+    pub fn balance(&self, account_id: &str) -> Result<Money> {
+        self.inner.balance(account_id)
+    }
+
+    pub fn transactions(&self, account_id: &str) -> Result<Vec<TransactionRecord>> {
+        self.inner.transactions(account_id)
+    }
+
+    pub fn is_locked(&self, account_id: &str) -> Result<bool> {
+        self.inner.is_locked(account_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn journal_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("bank-account-journal-test-{name}-{}", std::process::id()))
+    }
+
+    #[test]
+    fn a_reopened_journal_rebuilds_the_balance_it_last_acknowledged() {
+        let path = journal_path("rebuild");
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let bank = PersistentBankLibrary::open(&path).unwrap();
+            bank.create_account("alice").unwrap();
+            bank.deposit("alice", 1, Money::from_dollars(100.0).unwrap()).unwrap();
+            bank.withdraw("alice", 2, Money::from_dollars(30.0).unwrap()).unwrap();
+            // `bank` drops here, simulating the process exiting without a
+            // clean shutdown hook (e.g. a crash or `kill -9`).
+        }
+
+        let reopened = PersistentBankLibrary::open(&path).unwrap();
+        assert_eq!(reopened.balance("alice").unwrap(), Money::from_dollars(70.0).unwrap());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn a_rejected_write_is_never_journaled() {
+        let path = journal_path("rejected");
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let bank = PersistentBankLibrary::open(&path).unwrap();
+            bank.create_account("alice").unwrap();
+            assert!(bank.withdraw("alice", 1, Money::from_dollars(10.0).unwrap()).is_err());
+        }
+
+        let reopened = PersistentBankLibrary::open(&path).unwrap();
+        assert_eq!(reopened.balance("alice").unwrap(), Money::ZERO);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn a_disputed_and_charged_back_transaction_replays_to_the_same_locked_state() {
+        let path = journal_path("chargeback");
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let bank = PersistentBankLibrary::open(&path).unwrap();
+            bank.create_account("alice").unwrap();
+            bank.deposit("alice", 1, Money::from_dollars(100.0).unwrap()).unwrap();
+            bank.dispute("alice", 1).unwrap();
+            bank.chargeback("alice", 1).unwrap();
+        }
+
+        let reopened = PersistentBankLibrary::open(&path).unwrap();
+        assert_eq!(reopened.balance("alice").unwrap(), Money::ZERO);
+        assert!(reopened.is_locked("alice").unwrap());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn a_missing_journal_file_opens_as_empty_rather_than_erroring() {
+        let path = journal_path("missing");
+        let _ = std::fs::remove_file(&path);
+
+        let bank = PersistentBankLibrary::open(&path).unwrap();
+        assert!(bank.balance("alice").is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
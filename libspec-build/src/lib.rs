@@ -0,0 +1,224 @@
+//! `build.rs` helper: call [`emit_version`] from a downstream crate's
+//! `build.rs` to embed the exact [`SpecDocument::version_hash`] it was
+//! built against into a generated Rust file, instead of trusting a
+//! handwritten version string that can silently drift from the spec it
+//! names. Pair it with [`SpecDocument::verify_version_hash`] at runtime to
+//! catch the drift instead of letting it surface as a confusing failure
+//! somewhere deep in generated code.
+//!
+//! ```no_run
+//! // build.rs
+//! libspec_build::emit_version("spec.toml").unwrap();
+//! ```
+//!
+//! ```ignore
+//! // src/main.rs
+//! include!(concat!(env!("OUT_DIR"), "/libspec_version.rs"));
+//!
+//! fn main() {
+//!     let doc = libspec::spec::SpecDocument::load_toml_file("spec.toml").unwrap();
+//!     doc.verify_version_hash(SPEC_VERSION_HASH).expect("spec changed since this build");
+//! }
+//! ```
+//!
+//! [`generate`] goes further and generates the trait itself, so the
+//! `build.rs` and the generated code can never drift apart: a spec that
+//! fails validation fails the build with a readable error instead of
+//! producing a trait no one checked.
+//!
+//! ```no_run
+//! // build.rs
+//! libspec_build::generate("spec.toml", "PaymentsApi").unwrap();
+//! ```
+//!
+//! ```ignore
+//! // src/main.rs
+//! include!(concat!(env!("OUT_DIR"), "/libspec_generated.rs"));
+//! ```
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use libspec::cache::Cache;
+use libspec::codegen::rust_trait;
+use libspec::spec::SpecDocument;
+use libspec::validate;
+
+/// Loads the TOML spec at `spec_path`, and writes `$OUT_DIR/libspec_version.rs`
+/// declaring `pub const SPEC_VERSION_HASH: &str = "...";`, plus a
+/// `cargo:rerun-if-changed` directive so Cargo reruns this build script
+/// whenever `spec_path` changes. Panics if `OUT_DIR` isn't set, since that
+/// only happens when this is called from somewhere other than a
+/// `build.rs`.
+pub fn emit_version(spec_path: impl AsRef<Path>) -> io::Result<()> {
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is only set while a build.rs is running");
+    emit_version_to(spec_path, Path::new(&out_dir))
+}
+
+fn emit_version_to(spec_path: impl AsRef<Path>, out_dir: &Path) -> io::Result<()> {
+    let spec_path = spec_path.as_ref();
+    let doc = SpecDocument::load_toml_file(spec_path).map_err(io::Error::other)?;
+
+    let dest = out_dir.join("libspec_version.rs");
+    fs::write(
+        &dest,
+        format!(
+            "pub const SPEC_VERSION_HASH: &str = \"{}\";\n",
+            doc.version_hash()
+        ),
+    )?;
+
+    println!("cargo:rerun-if-changed={}", spec_path.display());
+    Ok(())
+}
+
+/// Loads the TOML spec at `spec_path`, generates a Rust trait named
+/// `trait_name` from it, and writes `$OUT_DIR/libspec_generated.rs`, plus a
+/// `cargo:rerun-if-changed` directive so Cargo reruns this build script
+/// whenever `spec_path` changes. Panics if the spec fails meta-schema
+/// validation, printing every validation error first — Cargo surfaces a
+/// panicking build script as a build failure, so a broken spec fails the
+/// build instead of silently generating a trait nobody checked. Panics if
+/// `OUT_DIR` isn't set, since that only happens when this is called from
+/// somewhere other than a `build.rs`.
+///
+/// The generated code is cached under the workspace's `target/libspec/gen`
+/// (shared by every crate in the workspace that calls this, the same way
+/// they already share one `target/`), keyed on the spec's
+/// [`SpecDocument::version_hash`] and `trait_name`, so a workspace with
+/// dozens of spec'd crates only regenerates the ones whose spec actually
+/// changed since the last build. Prints a `cargo:warning` noting whether
+/// this build reused a cached artifact or regenerated it.
+pub fn generate(spec_path: impl AsRef<Path>, trait_name: &str) -> io::Result<()> {
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is only set while a build.rs is running");
+    generate_to(spec_path, trait_name, Path::new(&out_dir))
+}
+
+fn generate_to(spec_path: impl AsRef<Path>, trait_name: &str, out_dir: &Path) -> io::Result<()> {
+    let spec_path = spec_path.as_ref();
+    let doc = SpecDocument::load_toml_file(spec_path).map_err(io::Error::other)?;
+
+    if let Err(errors) = validate::validate(&doc) {
+        for error in &errors {
+            eprintln!("error: {error}");
+        }
+        panic!(
+            "spec at {} failed validation ({} error(s), see above)",
+            spec_path.display(),
+            errors.len()
+        );
+    }
+
+    let cache = Cache::new(workspace_cache_dir(out_dir));
+    let key = (doc.version_hash(), "rust_trait", trait_name.to_string());
+    let (code, reused) = match cache.get(&key) {
+        Some(cached) => (cached, true),
+        None => {
+            let code = rust_trait::generate(&doc, trait_name);
+            cache.put(&key, &code);
+            (code, false)
+        }
+    };
+
+    let dest = out_dir.join("libspec_generated.rs");
+    fs::write(&dest, code)?;
+
+    println!(
+        "cargo:warning=libspec: trait `{trait_name}` {} for {}",
+        if reused { "reused from cache" } else { "regenerated" },
+        spec_path.display()
+    );
+    println!("cargo:rerun-if-changed={}", spec_path.display());
+    Ok(())
+}
+
+/// The `target/libspec/gen` cache directory shared by every crate in the
+/// workspace, derived by climbing `out_dir` (`.../target/<profile>/build/
+/// <crate>-<hash>/out`) up to its `target` ancestor. Falls back to a
+/// cache scoped to just this crate's `out_dir` if, for whatever reason,
+/// no ancestor is literally named `target` (a non-standard `CARGO_TARGET_DIR`
+/// layout) — a smaller cache is still correct, just less shared.
+fn workspace_cache_dir(out_dir: &Path) -> PathBuf {
+    let mut dir = out_dir;
+    while dir.file_name().is_some() {
+        if dir.file_name() == Some(std::ffi::OsStr::new("target")) {
+            return dir.join("libspec").join("gen");
+        }
+        match dir.parent() {
+            Some(parent) => dir = parent,
+            None => break,
+        }
+    }
+    out_dir.join("libspec-gen-cache")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_a_constant_naming_the_spec_version_hash() {
+        let dir = std::env::temp_dir().join(format!("libspec-build-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let spec_path = dir.join("spec.toml");
+        fs::write(
+            &spec_path,
+            "[[requirement]]\nid = \"REQ-001\"\ntext = \"text\"\n",
+        )
+        .unwrap();
+
+        emit_version_to(&spec_path, &dir).unwrap();
+
+        let generated = fs::read_to_string(dir.join("libspec_version.rs")).unwrap();
+        assert!(generated.starts_with("pub const SPEC_VERSION_HASH: &str = \""));
+
+        let doc = SpecDocument::load_toml_file(&spec_path).unwrap();
+        assert!(generated.contains(&doc.version_hash()));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn errors_on_an_unreadable_spec_path() {
+        let dir = std::env::temp_dir().join(format!("libspec-build-test-missing-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let result = emit_version_to(dir.join("does-not-exist.toml"), &dir);
+        assert!(result.is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn writes_a_generated_trait_for_a_valid_spec() {
+        let dir = std::env::temp_dir().join(format!("libspec-build-test-gen-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let spec_path = dir.join("spec.toml");
+        fs::write(
+            &spec_path,
+            "[[requirement]]\nid = \"REQ-001\"\ntext = \"text\"\n",
+        )
+        .unwrap();
+
+        generate_to(&spec_path, "PaymentsApi", &dir).unwrap();
+
+        let generated = fs::read_to_string(dir.join("libspec_generated.rs")).unwrap();
+        assert!(generated.contains("trait PaymentsApi"));
+        assert!(generated.contains("REQ-001"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    #[should_panic(expected = "failed validation")]
+    fn panics_when_the_spec_fails_validation() {
+        let dir = std::env::temp_dir().join(format!("libspec-build-test-gen-invalid-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let spec_path = dir.join("spec.toml");
+        fs::write(&spec_path, "[[requirement]]\nid = \"\"\ntext = \"text\"\n").unwrap();
+
+        generate_to(&spec_path, "PaymentsApi", &dir).unwrap();
+    }
+}
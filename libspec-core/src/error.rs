@@ -0,0 +1,155 @@
+//! A structured error type for generated code, replacing ad-hoc
+//! `Result<T, String>` returns with something callers can match on without
+//! parsing a message.
+//!
+//! [`SpecError`]'s [`Display`](core::fmt::Display) renders as
+//! `"{code}: {message}"`, the same format generated and hand-written code
+//! has historically built by hand (e.g.
+//! `format!("{}: {}", constraint.code, constraint.text)`), so swapping a
+//! `String` return for a `SpecError` one doesn't change what callers see
+//! when they print the error.
+//!
+//! ## Wire format
+//!
+//! [`SpecError`] derives `serde::Serialize`/`Deserialize` directly off its
+//! fields, so it serializes as:
+//!
+//! ```json
+//! { "code": "CONST-002", "requirement": "REQ-004", "message": "insufficient funds", "details": null }
+//! ```
+//!
+//! This is the one shape every backend's generated error type should
+//! produce, so a REST client sees the same JSON error whether the service
+//! behind it is a hosted Rust backend or an embedded one returning the
+//! same type from this crate.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A constraint or requirement violation, carrying enough structure to
+/// match on programmatically instead of parsing a formatted string. See
+/// the [module docs](self) for its wire format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecError {
+    /// The constraint code that failed, e.g. `"CONST-002"`.
+    pub code: String,
+    /// The id of the requirement this check traces back to, if any.
+    pub requirement: Option<String>,
+    /// Human-readable description, e.g. `"insufficient funds"`.
+    pub message: String,
+    /// Additional context beyond `code` and `message` (field values,
+    /// expected vs. actual, and the like), free-form since it varies per
+    /// constraint.
+    pub details: Option<String>,
+}
+
+impl SpecError {
+    /// Creates a `SpecError` with no requirement linkage or extra details.
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { code: code.into(), requirement: None, message: message.into(), details: None }
+    }
+
+    /// Sets which requirement this error traces back to.
+    pub fn with_requirement(mut self, requirement: impl Into<String>) -> Self {
+        self.requirement = Some(requirement.into());
+        self
+    }
+
+    /// Attaches free-form additional context.
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+
+    /// Resolves this error's message in `locale` via `catalog`, falling
+    /// back to [`SpecError::message`] when the locale or this error's
+    /// `code` isn't in it. The constraint code is the stable lookup key,
+    /// so a caller can swap locales without the error itself changing.
+    pub fn localized_message<'a>(&'a self, catalog: &'a MessageCatalog, locale: &str) -> &'a str {
+        catalog.get(locale, &self.code).unwrap_or(&self.message)
+    }
+}
+
+/// A locale -> constraint code -> message lookup, typically built from a
+/// spec's `LocaleCatalog`s via `SpecDocument::message_catalog` on the
+/// hosted side. Looked up by [`SpecError::localized_message`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MessageCatalog {
+    by_locale: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl MessageCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the message for `code` in `locale`, overwriting any earlier
+    /// one for the same pair.
+    pub fn insert(
+        &mut self,
+        locale: impl Into<String>,
+        code: impl Into<String>,
+        message: impl Into<String>,
+    ) -> &mut Self {
+        self.by_locale
+            .entry(locale.into())
+            .or_default()
+            .insert(code.into(), message.into());
+        self
+    }
+
+    /// The message for `code` in `locale`, if both are present.
+    pub fn get(&self, locale: &str, code: &str) -> Option<&str> {
+        self.by_locale.get(locale)?.get(code).map(String::as_str)
+    }
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl core::error::Error for SpecError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_renders_code_and_message_only() {
+        let err = SpecError::new("CONST-001", "insufficient funds")
+            .with_requirement("REQ-004")
+            .with_details("available=10, requested=50");
+        assert_eq!(alloc::string::ToString::to_string(&err), "CONST-001: insufficient funds");
+    }
+
+    #[test]
+    fn builder_methods_set_optional_fields() {
+        let err = SpecError::new("CONST-002", "account locked").with_requirement("REQ-009");
+        assert_eq!(err.requirement, Some("REQ-009".into()));
+        assert_eq!(err.details, None);
+    }
+
+    #[test]
+    fn localized_message_uses_the_catalog_entry_for_the_locale() {
+        let err = SpecError::new("CONST-001", "insufficient funds");
+        let mut catalog = MessageCatalog::new();
+        catalog.insert("fr", "CONST-001", "fonds insuffisants");
+
+        assert_eq!(err.localized_message(&catalog, "fr"), "fonds insuffisants");
+    }
+
+    #[test]
+    fn localized_message_falls_back_when_locale_or_code_is_missing() {
+        let err = SpecError::new("CONST-001", "insufficient funds");
+        let mut catalog = MessageCatalog::new();
+        catalog.insert("fr", "CONST-002", "fonds insuffisants");
+
+        assert_eq!(err.localized_message(&catalog, "fr"), "insufficient funds");
+        assert_eq!(err.localized_message(&catalog, "de"), "insufficient funds");
+    }
+}
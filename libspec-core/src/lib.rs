@@ -0,0 +1,17 @@
+//! The subset of `libspec`'s runtime-facing types that an embedded
+//! implementation of a spec needs and can actually build: [`SpecError`]
+//! and [`MessageCatalog`]. `no_std + alloc` — no parsing, no codegen, no
+//! SMT solving, none of the host-only machinery the rest of `libspec`
+//! pulls in, since none of that runs on the device that's actually
+//! enforcing the contract. `libspec` itself depends on this crate and
+//! re-exports these types from [`libspec::error`](../libspec/error/index.html)
+//! unchanged, so hosted and embedded code share one error shape without
+//! either one carrying the other's dependencies.
+
+#![no_std]
+
+extern crate alloc;
+
+mod error;
+
+pub use error::{MessageCatalog, SpecError};
@@ -0,0 +1,3320 @@
+//! A custom test runner for `[[test]]` targets built with `harness = false`.
+//!
+//! The `#[covers]`/`#[implements]` attribute macros in `libspec-macros`
+//! only record the ids a human remembered to list on the attribute. This
+//! harness additionally scans the `Display` of a failing test's returned
+//! error for ids from the spec, the same best-effort substring match
+//! [`libspec::trace::scan`] uses on source text, so a test that never got
+//! a `#[covers(...)]` annotation still leaves a traceability link when it
+//! exercises a requirement's error path.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use libspec::error::SpecError;
+use libspec::spec::{Comparison, RelOp, SpecDocument, Term};
+use libspec::trace;
+use serde::Serialize;
+
+/// What a [`Test`]'s function returns: `Ok(())` on success, `Err` with the
+/// failure, boxed so a test can return any error type via `?`.
+pub type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+/// One test to run. Built by hand (or by a small build-time list) rather
+/// than discovered, since `harness = false` opts the target out of
+/// libtest's `#[test]` collection.
+pub struct Test {
+    pub name: &'static str,
+    pub func: fn() -> TestResult,
+}
+
+/// Runs every test in `tests` against `doc`, printing libtest-style
+/// `test <name> ... ok`/`FAILED` lines. A failing test's error is scanned
+/// for `doc`'s requirement and constraint ids and each one found is
+/// recorded as a `covers` link, in addition to whatever `#[covers]`
+/// recorded when the test function itself ran.
+///
+/// Returns [`ExitCode::FAILURE`] if any test failed, so callers can use it
+/// directly as a test binary's `main`:
+///
+/// ```no_run
+/// # use libspec_harness::{run, Test};
+/// # use libspec::spec::SpecDocument;
+/// fn main() -> std::process::ExitCode {
+///     let doc = SpecDocument::new();
+///     run(&doc, &[Test { name: "it_works", func: || Ok(()) }])
+/// }
+/// ```
+pub fn run(doc: &SpecDocument, tests: &[Test]) -> ExitCode {
+    let mut failures = Vec::new();
+
+    for test in tests {
+        print!("test {} ... ", test.name);
+        let start = Instant::now();
+        match (test.func)() {
+            Ok(()) => println!("ok ({:?})", start.elapsed()),
+            Err(e) => {
+                for id in ids_mentioned(doc, &e.to_string()) {
+                    trace::record("covers", test.name, &id);
+                }
+                println!("FAILED ({:?})", start.elapsed());
+                failures.push(TestFailure {
+                    name: test.name,
+                    error: e,
+                });
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        println!("\nfailures:");
+        for failure in &failures {
+            println!("    {failure}");
+        }
+        ExitCode::FAILURE
+    }
+}
+
+/// A test that returned `Err`, kept around only to print a `name: error`
+/// summary once every test has run.
+struct TestFailure {
+    name: &'static str,
+    error: Box<dyn std::error::Error>,
+}
+
+impl fmt::Display for TestFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.error)
+    }
+}
+
+/// One spec-derived conformance scenario: a generated trait's method (see
+/// [`libspec::codegen::rust_trait`]), paired with the requirement id it
+/// implements. Built by hand, same as [`Test`] — the generated trait
+/// knows the method's name and signature, not what calling it should
+/// prove about the requirement.
+pub struct Scenario<T: ?Sized> {
+    pub requirement: &'static str,
+    pub check: fn(&T) -> TestResult,
+}
+
+// A `Scenario<T>` never stores a `T`, only a fn pointer that takes one by
+// reference, so it's `Copy`/`Clone` regardless of `T` — unlike `#[derive]`,
+// which would add a `T: Copy`/`T: Clone` bound neither field actually needs.
+// `T: ?Sized` so a scenario can check a trait object (`Scenario<dyn
+// BankAPI>`) directly, without every implementation needing to monomorphize
+// its own `Conformance<T>`.
+impl<T: ?Sized> Clone for Scenario<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for Scenario<T> {}
+
+/// A requirement id on a [`Scenario`] that isn't one of a spec's
+/// requirement ids.
+#[derive(Debug)]
+struct UnknownRequirement(&'static str);
+
+impl fmt::Display for UnknownRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not a requirement id in this spec", self.0)
+    }
+}
+
+impl std::error::Error for UnknownRequirement {}
+
+/// Runs a suite of [`Scenario`]s against any implementation of a
+/// generated trait, reporting pass/fail per requirement so implementors
+/// get a conformance test suite for free instead of hand-writing the same
+/// test per method every time. Wraps the suite (rather than taking it as
+/// a plain function argument) so a project can build one `Conformance`
+/// per generated trait and reuse it across every implementation under
+/// test. `T` is `?Sized`, so a suite can also be written once against a
+/// generated trait's object-safe shim (`Conformance<dyn BankApi>`) and run
+/// against a `Box<dyn BankApi>` chosen at runtime instead of monomorphizing
+/// per concrete implementation — see [`ImplementationRegistry`] for
+/// registering several of those at once:
+///
+/// ```no_run
+/// # use libspec_harness::{Conformance, Scenario};
+/// # use libspec::spec::SpecDocument;
+/// # trait BankApi { fn balance(&self) -> Result<(), libspec::error::SpecError>; }
+/// # struct MyBank;
+/// # impl BankApi for MyBank { fn balance(&self) -> Result<(), libspec::error::SpecError> { Ok(()) } }
+/// let doc = SpecDocument::new();
+/// let suite = Conformance::new(&[Scenario {
+///     requirement: "REQ-004",
+///     check: |bank: &MyBank| Ok(BankApi::balance(bank)?),
+/// }]);
+/// suite.run(&doc, &MyBank);
+/// ```
+pub struct Conformance<'a, T: ?Sized> {
+    suite: &'a [Scenario<T>],
+}
+
+impl<'a, T: ?Sized> Conformance<'a, T> {
+    pub fn new(suite: &'a [Scenario<T>]) -> Self {
+        Self { suite }
+    }
+
+    /// Runs every scenario against `implementation`, printing libtest-style
+    /// `test <requirement> ... ok`/`FAILED` lines and recording a `covers`
+    /// trace link per requirement that ran, pass or fail. A scenario whose
+    /// `requirement` isn't one of `doc`'s requirement ids fails with
+    /// `FAILED (unknown requirement id)` instead of being silently
+    /// trusted. Finishes by printing a [`ConformanceReport`] covering every
+    /// requirement in `doc`, not just the ones this suite has scenarios
+    /// for, so a requirement nobody wrote a scenario for shows up as
+    /// `not_exercised` instead of silently not appearing at all.
+    ///
+    /// Returns [`ExitCode::FAILURE`] if any scenario failed.
+    pub fn run(&self, doc: &SpecDocument, implementation: &T) -> ExitCode {
+        let mut failures = Vec::new();
+        let mut outcomes: Vec<(&str, bool)> = Vec::new();
+
+        for scenario in self.suite {
+            print!("test {} ... ", scenario.requirement);
+
+            if !doc.requirements.iter().any(|r| r.id == scenario.requirement) {
+                println!("FAILED (unknown requirement id)");
+                failures.push(TestFailure {
+                    name: scenario.requirement,
+                    error: Box::new(UnknownRequirement(scenario.requirement)),
+                });
+                continue;
+            }
+
+            let start = Instant::now();
+            let result = (scenario.check)(implementation);
+            trace::record("covers", scenario.requirement, scenario.requirement);
+            outcomes.push((scenario.requirement, result.is_ok()));
+            match result {
+                Ok(()) => println!("ok ({:?})", start.elapsed()),
+                Err(e) => {
+                    println!("FAILED ({:?})", start.elapsed());
+                    failures.push(TestFailure {
+                        name: scenario.requirement,
+                        error: e,
+                    });
+                }
+            }
+        }
+
+        println!("\n{}", ConformanceReport::build(doc, &outcomes).to_terminal());
+
+        if failures.is_empty() {
+            ExitCode::SUCCESS
+        } else {
+            println!("failures:");
+            for failure in &failures {
+                println!("    {failure}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+
+    /// Like [`Conformance::run`], but refuses to run at all unless
+    /// `implementation_version` (whatever the implementation under test
+    /// reports as its own spec version — typically a
+    /// [`SpecDocument::version_hash`] it was built against) matches
+    /// `doc.version_hash()`, printing a mismatch error and returning
+    /// [`ExitCode::FAILURE`] instead of silently testing against the
+    /// wrong contract. Pass `allow_mismatch: true` (the harness
+    /// equivalent of a suite's `--allow-mismatch` flag) to downgrade the
+    /// mismatch to a warning and run anyway.
+    pub fn run_versioned(
+        &self,
+        doc: &SpecDocument,
+        implementation: &T,
+        implementation_version: &str,
+        allow_mismatch: bool,
+    ) -> ExitCode {
+        let expected = doc.version_hash();
+        if implementation_version != expected {
+            if allow_mismatch {
+                println!(
+                    "warning: implementation reports spec version {implementation_version}, suite was generated from {expected} (--allow-mismatch)"
+                );
+            } else {
+                println!(
+                    "refusing to run: implementation reports spec version {implementation_version}, suite was generated from {expected}\n(pass --allow-mismatch to run anyway)"
+                );
+                return ExitCode::FAILURE;
+            }
+        }
+        self.run(doc, implementation)
+    }
+
+    /// Starts a [`ScenarioFilter`] narrowing this suite down to scenarios
+    /// covering a requirement with a given tag or id, before running it.
+    /// With no tags or ids added, the filter matches every scenario, same
+    /// as calling [`Conformance::run`] directly.
+    ///
+    /// ```no_run
+    /// # use libspec_harness::{Conformance, Scenario};
+    /// # use libspec::spec::SpecDocument;
+    /// # struct MyBank;
+    /// let doc = SpecDocument::new();
+    /// let suite = Conformance::new(&[Scenario {
+    ///     requirement: "REQ-004",
+    ///     check: |_bank: &MyBank| Ok(()),
+    /// }]);
+    /// suite.filter().tag("money").tag("auth").run(&doc, &MyBank);
+    /// ```
+    pub fn filter(&'a self) -> ScenarioFilter<'a, T> {
+        ScenarioFilter::new(self)
+    }
+}
+
+/// A builder that narrows a [`Conformance`] suite down to scenarios
+/// covering a requirement tagged with any of a set of tags, or matching a
+/// specific requirement id, before running it — e.g. a CLI wrapping this
+/// harness offering `--tag money --tag auth`/`--id REQ-004` flags. Built
+/// via [`Conformance::filter`].
+pub struct ScenarioFilter<'a, T: ?Sized> {
+    conformance: &'a Conformance<'a, T>,
+    tags: Vec<&'a str>,
+    ids: Vec<&'a str>,
+    active_conditions: Vec<&'a str>,
+}
+
+impl<'a, T: ?Sized> ScenarioFilter<'a, T> {
+    fn new(conformance: &'a Conformance<'a, T>) -> Self {
+        Self {
+            conformance,
+            tags: Vec::new(),
+            ids: Vec::new(),
+            active_conditions: Vec::new(),
+        }
+    }
+
+    /// Includes scenarios covering a requirement tagged `tag`. Can be
+    /// called more than once to match any of several tags.
+    pub fn tag(mut self, tag: &'a str) -> Self {
+        self.tags.push(tag);
+        self
+    }
+
+    /// Includes the scenario covering requirement `id`, regardless of its
+    /// tags. Can be called more than once to match several ids.
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.ids.push(id);
+        self
+    }
+
+    /// Marks `condition` (e.g. `"overdraft"`, `"eu"`) as active for this
+    /// run: a scenario whose requirement's `applies_when` names a
+    /// condition not passed here is excluded, the same way
+    /// [`SpecDocument::for_conditions`] drops it from codegen and coverage
+    /// gating. Can be called more than once to activate several
+    /// conditions; a requirement with no conditions always runs.
+    pub fn condition(mut self, condition: &'a str) -> Self {
+        self.active_conditions.push(condition);
+        self
+    }
+
+    /// Which of the suite's scenarios match this filter's tags/ids, out of
+    /// `doc`'s requirements — `doc` is expected to already be narrowed to
+    /// the active conditions (see [`Self::run`]), so a scenario whose
+    /// requirement was dropped for not applying is excluded here too.
+    fn matching(&self, doc: &SpecDocument) -> Vec<Scenario<T>> {
+        self.conformance
+            .suite
+            .iter()
+            .copied()
+            .filter(|scenario| {
+                let Some(req) = doc.requirement(scenario.requirement) else {
+                    return false;
+                };
+                if self.tags.is_empty() && self.ids.is_empty() {
+                    return true;
+                }
+                if self.ids.contains(&scenario.requirement) {
+                    return true;
+                }
+                req.tags.iter().any(|t| self.tags.contains(&t.as_str()))
+            })
+            .collect()
+    }
+
+    /// Runs the filtered suite, same as [`Conformance::run`], against a
+    /// view of `doc` narrowed to the active conditions (see
+    /// [`SpecDocument::for_conditions`]) — so a requirement excluded by
+    /// [`Self::condition`] is left out of the printed
+    /// [`ConformanceReport`] entirely instead of showing up as
+    /// `not_exercised`.
+    pub fn run(&self, doc: &SpecDocument, implementation: &T) -> ExitCode {
+        let doc = doc.for_conditions(&self.active_conditions);
+        Conformance::new(&self.matching(&doc)).run(&doc, implementation)
+    }
+}
+
+type Factory<T> = Box<dyn Fn() -> Box<T>>;
+
+/// Registers named factory closures that each build a fresh
+/// implementation on demand, so an integration test can add
+/// implementations at runtime — one per database backend under test, say
+/// — instead of hard-coding a fixed set of `T`s at compile time. Pairs
+/// naturally with a dyn-compatible `T` (e.g. `dyn BankApi`), since each
+/// registered factory can hand back a different concrete type behind the
+/// same trait object. Same registration shape as [`Replayer`], but keyed
+/// by a display name instead of an operation name, since what's varying
+/// here is the implementation under test, not the call being made.
+///
+/// ```no_run
+/// # use libspec_harness::{Conformance, ImplementationRegistry, Scenario};
+/// # use libspec::spec::SpecDocument;
+/// # trait BankApi { fn balance(&self) -> Result<(), libspec::error::SpecError>; }
+/// # struct SqliteBank;
+/// # impl BankApi for SqliteBank { fn balance(&self) -> Result<(), libspec::error::SpecError> { Ok(()) } }
+/// # struct PostgresBank;
+/// # impl BankApi for PostgresBank { fn balance(&self) -> Result<(), libspec::error::SpecError> { Ok(()) } }
+/// # fn check_balance(bank: &dyn BankApi) -> Result<(), Box<dyn std::error::Error>> { Ok(bank.balance()?) }
+/// let doc = SpecDocument::new();
+/// let suite: Conformance<dyn BankApi> = Conformance::new(&[Scenario {
+///     requirement: "REQ-004",
+///     check: check_balance,
+/// }]);
+///
+/// let mut backends = ImplementationRegistry::new();
+/// backends.register("sqlite", || Box::new(SqliteBank) as Box<dyn BankApi>);
+/// backends.register("postgres", || Box::new(PostgresBank) as Box<dyn BankApi>);
+/// backends.run_all(&suite, &doc);
+/// ```
+pub struct ImplementationRegistry<T: ?Sized> {
+    factories: Vec<(String, Factory<T>)>,
+}
+
+impl<T: ?Sized> ImplementationRegistry<T> {
+    pub fn new() -> Self {
+        Self { factories: Vec::new() }
+    }
+
+    /// Registers `factory` under `name`, run in [`Self::run_all`] alongside
+    /// every other registered implementation. Can be called more than once
+    /// to add several implementations.
+    pub fn register(&mut self, name: &str, factory: impl Fn() -> Box<T> + 'static) -> &mut Self {
+        self.factories.push((name.to_string(), Box::new(factory)));
+        self
+    }
+
+    /// Runs `suite` against a freshly built instance of every registered
+    /// implementation in turn, printing a `-- <name> --` heading before
+    /// each one's own [`Conformance::run`] output. Returns
+    /// [`ExitCode::FAILURE`] if any implementation failed any scenario.
+    pub fn run_all(&self, suite: &Conformance<'_, T>, doc: &SpecDocument) -> ExitCode {
+        let mut failed = false;
+        for (name, factory) in &self.factories {
+            println!("-- {name} --");
+            if suite.run(doc, &factory()) == ExitCode::FAILURE {
+                failed = true;
+            }
+        }
+        if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+    }
+}
+
+impl<T: ?Sized> Default for ImplementationRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Send + 'static> Conformance<'a, T> {
+    /// Runs every scenario concurrently, each against its own `T` built
+    /// fresh by `factory` so scenarios never share mutable state, printing
+    /// the same libtest-style lines and [`ConformanceReport`] as
+    /// [`Conformance::run`] once every scenario has either finished or hit
+    /// `timeout`.
+    ///
+    /// A scenario that doesn't return within `timeout` is reported as
+    /// `TIMED OUT` and counted as a failure; its thread is left running in
+    /// the background (Rust has no way to forcibly cancel a thread), so a
+    /// suite with a runaway scenario leaks that one thread for the life of
+    /// the process rather than hanging the whole run.
+    ///
+    /// ```no_run
+    /// # use libspec_harness::{Conformance, Scenario};
+    /// # use libspec::spec::SpecDocument;
+    /// # use std::time::Duration;
+    /// # struct MyBank;
+    /// let doc = SpecDocument::new();
+    /// let suite = Conformance::new(&[Scenario {
+    ///     requirement: "REQ-004",
+    ///     check: |_bank: &MyBank| Ok(()),
+    /// }]);
+    /// suite.run_parallel(&doc, || MyBank, Duration::from_secs(5));
+    /// ```
+    pub fn run_parallel(
+        &self,
+        doc: &SpecDocument,
+        factory: impl Fn() -> T + Send + Sync,
+        timeout: Duration,
+    ) -> ExitCode {
+        let receivers: Vec<_> = self
+            .suite
+            .iter()
+            .map(|scenario| {
+                let (tx, rx) = mpsc::channel();
+                let implementation = factory();
+                let check = scenario.check;
+                thread::spawn(move || {
+                    let result = check(&implementation).map_err(|e| e.to_string());
+                    let _ = tx.send(result);
+                });
+                (scenario, rx)
+            })
+            .collect();
+
+        let mut failures: Vec<(&str, String)> = Vec::new();
+        let mut outcomes: Vec<(&str, bool)> = Vec::new();
+
+        for (scenario, rx) in receivers {
+            print!("test {} ... ", scenario.requirement);
+
+            if !doc.requirements.iter().any(|r| r.id == scenario.requirement) {
+                println!("FAILED (unknown requirement id)");
+                failures.push((scenario.requirement, UnknownRequirement(scenario.requirement).to_string()));
+                continue;
+            }
+
+            match rx.recv_timeout(timeout) {
+                Ok(Ok(())) => {
+                    println!("ok");
+                    trace::record("covers", scenario.requirement, scenario.requirement);
+                    outcomes.push((scenario.requirement, true));
+                }
+                Ok(Err(message)) => {
+                    println!("FAILED");
+                    trace::record("covers", scenario.requirement, scenario.requirement);
+                    outcomes.push((scenario.requirement, false));
+                    failures.push((scenario.requirement, message));
+                }
+                Err(_) => {
+                    println!("TIMED OUT");
+                    outcomes.push((scenario.requirement, false));
+                    failures.push((scenario.requirement, format!("did not finish within {timeout:?}")));
+                }
+            }
+        }
+
+        println!("\n{}", ConformanceReport::build(doc, &outcomes).to_terminal());
+
+        if failures.is_empty() {
+            ExitCode::SUCCESS
+        } else {
+            println!("failures:");
+            for (name, message) in &failures {
+                println!("    {name}: {message}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// A requirement's verification status after a [`Conformance`] run,
+/// judged from the outcomes of every [`Scenario`] that covered it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStatus {
+    /// Every scenario covering this requirement passed.
+    Verified,
+    /// At least one scenario covering this requirement ran, but at least
+    /// one of them failed.
+    PartiallyVerified,
+    /// No scenario covered this requirement at all.
+    #[default]
+    NotExercised,
+}
+
+impl fmt::Display for VerificationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationStatus::Verified => write!(f, "verified"),
+            VerificationStatus::PartiallyVerified => write!(f, "partially verified"),
+            VerificationStatus::NotExercised => write!(f, "not exercised"),
+        }
+    }
+}
+
+/// One requirement's row in a [`ConformanceReport`]: how many of its
+/// covering scenarios passed vs. failed, and the [`VerificationStatus`]
+/// that implies.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RequirementVerification {
+    pub requirement: String,
+    pub status: VerificationStatus,
+    pub passed: usize,
+    pub failed: usize,
+}
+
+/// A final, per-requirement conformance summary: every requirement in a
+/// spec, attributed to [`VerificationStatus::Verified`],
+/// [`VerificationStatus::PartiallyVerified`], or
+/// [`VerificationStatus::NotExercised`] by the outcomes of whatever
+/// scenarios covered it, rendered in both human ([`ConformanceReport::to_terminal`])
+/// and machine ([`ConformanceReport::to_json`]) forms.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConformanceReport {
+    pub requirements: Vec<RequirementVerification>,
+}
+
+impl ConformanceReport {
+    /// Builds a report covering every requirement in `doc`, tallying
+    /// `outcomes` (a `(requirement id, passed)` pair per scenario that
+    /// ran) against each one.
+    pub fn build(doc: &SpecDocument, outcomes: &[(&str, bool)]) -> Self {
+        let requirements = doc
+            .requirements
+            .iter()
+            .map(|req| {
+                let passed = outcomes.iter().filter(|(id, ok)| *id == req.id && *ok).count();
+                let failed = outcomes.iter().filter(|(id, ok)| *id == req.id && !ok).count();
+                let status = if passed + failed == 0 {
+                    VerificationStatus::NotExercised
+                } else if failed == 0 {
+                    VerificationStatus::Verified
+                } else {
+                    VerificationStatus::PartiallyVerified
+                };
+                RequirementVerification {
+                    requirement: req.id.clone(),
+                    status,
+                    passed,
+                    failed,
+                }
+            })
+            .collect();
+        Self { requirements }
+    }
+
+    /// Renders the report as a plain-text summary for terminal output.
+    pub fn to_terminal(&self) -> String {
+        let mut out = String::from("conformance report:\n");
+        for r in &self.requirements {
+            out.push_str(&format!(
+                "  {} ... {} ({} passed, {} failed)\n",
+                r.requirement, r.status, r.passed, r.failed
+            ));
+        }
+        out
+    }
+
+    /// Renders the report as pretty-printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// A differential test: the same sequence of operations run against a
+/// reference implementation and a candidate (e.g. a new persistent
+/// backend being checked against the in-memory one it's replacing),
+/// stopping at and reporting the first operation where their results (or
+/// error codes) diverge, rather than running the whole sequence against
+/// each separately and diffing the two test reports by hand.
+///
+/// ```no_run
+/// # use libspec_harness::Differential;
+/// #[derive(Debug)]
+/// enum Op { Deposit(f64), Withdraw(f64) }
+///
+/// let ops = [Op::Deposit(100.0), Op::Withdraw(40.0)];
+/// let diff = Differential::new(&ops);
+/// diff.run(
+///     |op| match op { Op::Deposit(n) => Ok(*n), Op::Withdraw(n) => Ok(-*n) },
+///     |op| match op { Op::Deposit(n) => Ok(*n), Op::Withdraw(n) => Ok(-*n) },
+/// );
+/// ```
+pub struct Differential<'a, Op> {
+    operations: &'a [Op],
+}
+
+impl<'a, Op: fmt::Debug> Differential<'a, Op> {
+    pub fn new(operations: &'a [Op]) -> Self {
+        Self { operations }
+    }
+
+    /// Runs every operation through both `reference` and `candidate`,
+    /// comparing their results after each one. Prints a libtest-style
+    /// `op[{i}] {op:?} ... ok`/`DIVERGED` line per operation and returns
+    /// [`ExitCode::FAILURE`] as soon as one diverges, printing both sides'
+    /// results so the difference doesn't have to be tracked down from a
+    /// bare `DIVERGED`. The remaining operations in the sequence never
+    /// run, same as a real caller would stop at the first unexpected
+    /// result instead of plowing ahead.
+    pub fn run<V: PartialEq + fmt::Debug>(
+        &self,
+        mut reference: impl FnMut(&Op) -> Result<V, SpecError>,
+        mut candidate: impl FnMut(&Op) -> Result<V, SpecError>,
+    ) -> ExitCode {
+        for (i, op) in self.operations.iter().enumerate() {
+            print!("op[{i}] {op:?} ... ");
+            let expected = reference(op);
+            let actual = candidate(op);
+            if expected == actual {
+                println!("ok");
+            } else {
+                println!("DIVERGED");
+                println!("\ndiverged at op[{i}] {op:?}:");
+                println!("    reference: {expected:?}");
+                println!("    candidate: {actual:?}");
+                return ExitCode::FAILURE;
+            }
+        }
+        ExitCode::SUCCESS
+    }
+}
+
+/// Shrinks a randomly generated operation sequence that made `fails`
+/// return `true` down to a minimal reproducing subsequence, by
+/// repeatedly trying to drop one operation at a time and keeping the
+/// drop whenever the sequence still fails. Runs to a fixed point: a full
+/// pass over the sequence that removes nothing. This is the same idea a
+/// property-testing library's shrinker applies to its own generated
+/// inputs, applied here to the operation sequences a [`Differential`] or
+/// [`Conformance`] run would otherwise only report in full — "this
+/// 200-operation randomized sequence found a bug" becomes "here are the
+/// 3 operations that actually matter".
+///
+/// Doesn't attempt to shrink the operations themselves (e.g. a large
+/// `Deposit(1e9)` down to `Deposit(1)`), only which ones are present and
+/// in what order — callers wanting smaller individual values should
+/// generate those from an already-small range up front.
+pub fn shrink<Op: Clone>(ops: &[Op], mut fails: impl FnMut(&[Op]) -> bool) -> Vec<Op> {
+    let mut current = ops.to_vec();
+    loop {
+        let before = current.len();
+        let mut i = 0;
+        while i < current.len() {
+            let mut candidate = current.clone();
+            candidate.remove(i);
+            if fails(&candidate) {
+                current = candidate;
+            } else {
+                i += 1;
+            }
+        }
+        if current.len() == before {
+            return current;
+        }
+    }
+}
+
+/// Enumerates every operation sequence of length 1 up to `max_len` drawn
+/// (with repetition, order mattering) from `domain`, running `check`
+/// against each one and collecting those it flags as a failure (`true`).
+///
+/// Complements randomized model-based testing ([`Differential`] fed
+/// [`SpecEnv::random`]-driven sequences, or a `proptest` strategy from
+/// [`libspec::codegen::proptest_strategies`]): a random generator only
+/// samples the space and can easily miss a short, specific counterexample
+/// — e.g. the very first withdrawal on a fresh, empty account needs
+/// exactly the one-operation sequence `[Withdraw(_)]`, which a generator
+/// biased toward longer sequences might never try. Exhaustive search over
+/// a small `domain` and `max_len` can't miss it, at the cost of
+/// `domain.len().pow(max_len)` growth, so both `domain` and `max_len` have
+/// to stay small — this is a complement to randomized testing, not a
+/// replacement for it over the full input space.
+///
+/// ```no_run
+/// # use libspec_harness::exhaustive;
+/// #[derive(Debug, Clone)]
+/// enum Op { Deposit(f64), Withdraw(f64) }
+///
+/// let domain = [Op::Deposit(10.0), Op::Withdraw(10.0)];
+/// let failures = exhaustive(&domain, 3, |ops| {
+///     // replay `ops` against the model and the implementation, return
+///     // whether they diverged
+///     false
+/// });
+/// ```
+pub fn exhaustive<Op: Clone>(
+    domain: &[Op],
+    max_len: usize,
+    mut check: impl FnMut(&[Op]) -> bool,
+) -> Vec<Vec<Op>> {
+    let mut failures = Vec::new();
+    let mut sequence = Vec::new();
+    exhaustive_rec(domain, max_len, &mut sequence, &mut check, &mut failures);
+    failures
+}
+
+fn exhaustive_rec<Op: Clone>(
+    domain: &[Op],
+    remaining: usize,
+    sequence: &mut Vec<Op>,
+    check: &mut impl FnMut(&[Op]) -> bool,
+    failures: &mut Vec<Vec<Op>>,
+) {
+    if !sequence.is_empty() && check(sequence) {
+        failures.push(sequence.clone());
+    }
+    if remaining == 0 {
+        return;
+    }
+    for op in domain {
+        sequence.push(op.clone());
+        exhaustive_rec(domain, remaining - 1, sequence, check, failures);
+        sequence.pop();
+    }
+}
+
+/// Renders `ops` as a copy-pasteable `#[test]` function body, one line
+/// per operation using its `Debug` representation as the literal, so a
+/// [`shrink`]ed failure can be pasted straight into the test suite
+/// instead of re-transcribed by hand from a printed `Vec`.
+pub fn to_test_snippet<Op: fmt::Debug>(fn_name: &str, ops: &[Op]) -> String {
+    let mut out = format!("#[test]\nfn {fn_name}() {{\n    let ops = vec![\n");
+    for op in ops {
+        out.push_str(&format!("        {op:?},\n"));
+    }
+    out.push_str("    ];\n    // replay `ops` against the implementation under test\n}\n");
+    out
+}
+
+/// A subprocess implementation of the spec, driven over a line-delimited
+/// JSON protocol: each operation is written as one JSON value on its own
+/// line to the child's stdin, and the child responds with one JSON line
+/// on stdout, `{"ok": <value>}` or `{"err": <value>}` where `<value>` is
+/// [`SpecError`]'s own wire format (see [`libspec::error`]). This lets a
+/// [`Differential`] or [`Conformance`] run exercise a Python or Go
+/// implementation of the bank spec exactly like a native Rust one,
+/// without either side needing to know what language is on the other end
+/// of the pipe.
+///
+/// ```no_run
+/// # use libspec_harness::StdioProcess;
+/// let mut process = StdioProcess::spawn("python3", &["bank_impl.py"]).unwrap();
+/// let result = process.call(&serde_json::json!({"deposit": {"amount": 100.0}}));
+/// ```
+pub struct StdioProcess {
+    child: std::process::Child,
+    stdin: std::process::ChildStdin,
+    stdout: std::io::BufReader<std::process::ChildStdout>,
+}
+
+impl StdioProcess {
+    /// Spawns `command` with `args`, piping its stdin/stdout so
+    /// [`StdioProcess::call`] can drive it.
+    pub fn spawn(command: &str, args: &[&str]) -> std::io::Result<Self> {
+        let mut child = std::process::Command::new(command)
+            .args(args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("spawned with piped stdin");
+        let stdout = std::io::BufReader::new(child.stdout.take().expect("spawned with piped stdout"));
+        Ok(Self { child, stdin, stdout })
+    }
+
+    /// Sends `operation` as one JSON line and waits for the matching
+    /// response line, returning the `ok` value on success or the `err`
+    /// value (deserialized as a [`SpecError`]) on failure. A transport
+    /// problem (the subprocess exited, wrote a line that isn't valid
+    /// JSON, or sent neither an `ok` nor an `err`) is reported the same
+    /// way, as a `SpecError` with code `"IO"`, so callers that only know
+    /// how to handle `SpecError` (like [`Differential::run`]) don't need
+    /// a second error path for transport failures.
+    pub fn call(&mut self, operation: &serde_json::Value) -> Result<serde_json::Value, SpecError> {
+        use std::io::{BufRead, Write};
+
+        let io_error = |e: std::io::Error| SpecError::new("IO", e.to_string());
+
+        let mut line = serde_json::to_string(operation).expect("operation serialization is infallible");
+        line.push('\n');
+        self.stdin.write_all(line.as_bytes()).map_err(io_error)?;
+        self.stdin.flush().map_err(io_error)?;
+
+        let mut response = String::new();
+        self.stdout.read_line(&mut response).map_err(io_error)?;
+        if response.is_empty() {
+            return Err(SpecError::new("IO", "subprocess closed stdout"));
+        }
+
+        let mut value: serde_json::Value = serde_json::from_str(&response)
+            .map_err(|e| SpecError::new("IO", format!("invalid response line: {e}")))?;
+
+        let Some(obj) = value.as_object_mut() else {
+            return Err(SpecError::new(
+                "IO",
+                format!("response line is not a JSON object: {response}"),
+            ));
+        };
+        if let Some(ok) = obj.remove("ok") {
+            Ok(ok)
+        } else if let Some(err) = obj.remove("err") {
+            match serde_json::from_value::<SpecError>(err) {
+                Ok(spec_error) => Err(spec_error),
+                Err(e) => Err(SpecError::new("IO", format!("invalid err payload: {e}"))),
+            }
+        } else {
+            Err(SpecError::new(
+                "IO",
+                format!("response line has neither \"ok\" nor \"err\": {response}"),
+            ))
+        }
+    }
+}
+
+impl Drop for StdioProcess {
+    /// Kills and reaps the subprocess so a dropped `StdioProcess` doesn't
+    /// leave a zombie behind.
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// HTTP conformance mode: the network equivalent of [`StdioProcess`],
+/// testing a running REST service instead of a subprocess speaking a
+/// line-delimited protocol. Calls the same verb/path a generated REST
+/// client (see [`libspec::codegen::http_client`]) would, and maps a
+/// non-2xx response's JSON body (parsed as a [`SpecError`]) to `Err`,
+/// matching [`StdioProcess::call`]'s own `Result<serde_json::Value,
+/// SpecError>` shape so a [`Scenario`]'s `check` can be written against
+/// either transport the same way.
+///
+/// ```no_run
+/// # use libspec_harness::HttpTransport;
+/// let transport = HttpTransport::new("http://localhost:8080");
+/// let result = transport.call("POST", "/accounts/ACC-1/deposit");
+/// ```
+pub struct HttpTransport {
+    base_url: String,
+}
+
+impl HttpTransport {
+    /// An `HttpTransport` calling routes relative to `base_url`, e.g.
+    /// `"http://localhost:8080"`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self { base_url: base_url.into() }
+    }
+
+    /// Sends a `method` request to `path` (joined onto `base_url`),
+    /// returning the response's parsed JSON body on a 2xx status. Any
+    /// other status's body is parsed as a [`SpecError`] and returned as
+    /// `Err`; a transport problem (the service isn't reachable, or a
+    /// non-2xx body doesn't parse as a `SpecError`) is reported the same
+    /// way [`StdioProcess::call`] reports one, as a `SpecError` with code
+    /// `"IO"`.
+    pub fn call(&self, method: &str, path: &str) -> Result<serde_json::Value, SpecError> {
+        let url = format!("{}{}", self.base_url, path);
+        match ureq::request(method, &url).call() {
+            Ok(response) => response
+                .into_json()
+                .map_err(|e| SpecError::new("IO", format!("response body was not valid JSON: {e}"))),
+            Err(ureq::Error::Status(_, response)) => Err(response
+                .into_json::<SpecError>()
+                .unwrap_or_else(|e| SpecError::new("IO", format!("error body was not a SpecError: {e}")))),
+            Err(e) => Err(SpecError::new("IO", e.to_string())),
+        }
+    }
+}
+
+/// Sandboxed conformance mode for an implementation compiled to wasm
+/// instead of spoken to over a pipe or a socket (contrast
+/// [`StdioProcess`]/[`HttpTransport`]) — the module runs inside a
+/// wasmtime [`wasmtime::Store`] with no imports, so it can't touch the
+/// filesystem, network, or clock even if it wanted to. Rather than a
+/// WASI module (whose ABI has shifted across preview1/preview2/the
+/// component model), a `WasmHost` speaks a minimal core-wasm ABI of its
+/// own: the module exports a `memory`, an `alloc(len: i32) -> i32` the
+/// host uses to request space for a request, and a `call(ptr: i32, len:
+/// i32) -> i64` that reads the request out of its own memory and packs
+/// a response's `(ptr << 32) | len` into the return value. The request
+/// and response are the same one-JSON-value-per-operation
+/// `{"ok": ...}`/`{"err": ...}` shape [`StdioProcess::call`] speaks, so a
+/// [`Scenario`]'s `check` doesn't need to know which transport it's
+/// running against.
+pub struct WasmHost {
+    store: wasmtime::Store<()>,
+    memory: wasmtime::Memory,
+    alloc: wasmtime::TypedFunc<i32, i32>,
+    call: wasmtime::TypedFunc<(i32, i32), i64>,
+}
+
+impl WasmHost {
+    /// Compiles and instantiates `wasm`, resolving its exported `memory`,
+    /// `alloc`, and `call`. `wasm` may be wasm binary or (via wasmtime's
+    /// `wat` support) WebAssembly text format. A module missing any of
+    /// the three required exports, or that otherwise fails to compile or
+    /// instantiate, is reported as a `SpecError` with code `"IO"`, the
+    /// same code a transport problem on any other transport in this
+    /// module is reported with.
+    pub fn load(wasm: &[u8]) -> Result<Self, SpecError> {
+        let wasm_error = |e: wasmtime::Error| SpecError::new("IO", e.to_string());
+
+        let engine = wasmtime::Engine::default();
+        let module = wasmtime::Module::new(&engine, wasm).map_err(wasm_error)?;
+        let mut store = wasmtime::Store::new(&engine, ());
+        let linker = wasmtime::Linker::new(&engine);
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .map_err(wasm_error)?;
+
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| SpecError::new("IO", "module does not export a memory named \"memory\""))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "alloc")
+            .map_err(wasm_error)?;
+        let call = instance
+            .get_typed_func::<(i32, i32), i64>(&mut store, "call")
+            .map_err(wasm_error)?;
+
+        Ok(Self { store, memory, alloc, call })
+    }
+
+    /// Writes `operation` into the module's memory (via its own `alloc`)
+    /// and invokes its `call` export, returning the `ok` value on
+    /// success or the `err` value (deserialized as a [`SpecError`]) on
+    /// failure — the same response shape [`StdioProcess::call`] parses.
+    /// A malformed response, or a trap inside the module, is reported as
+    /// a `SpecError` with code `"IO"`.
+    pub fn call(&mut self, operation: &serde_json::Value) -> Result<serde_json::Value, SpecError> {
+        let wasm_error = |e: wasmtime::Error| SpecError::new("IO", e.to_string());
+
+        let request = serde_json::to_vec(operation).expect("operation serialization is infallible");
+        let request_ptr = self
+            .alloc
+            .call(&mut self.store, request.len() as i32)
+            .map_err(wasm_error)?;
+        self.memory
+            .write(&mut self.store, request_ptr as usize, &request)
+            .map_err(|e| SpecError::new("IO", e.to_string()))?;
+
+        let packed = self
+            .call
+            .call(&mut self.store, (request_ptr, request.len() as i32))
+            .map_err(wasm_error)?;
+        let response_ptr = (packed >> 32) as u32 as usize;
+        let response_len = packed as u32 as usize;
+
+        let mut response = vec![0u8; response_len];
+        self.memory
+            .read(&self.store, response_ptr, &mut response)
+            .map_err(|e| SpecError::new("IO", e.to_string()))?;
+
+        let mut value: serde_json::Value = serde_json::from_slice(&response)
+            .map_err(|e| SpecError::new("IO", format!("invalid response: {e}")))?;
+        let Some(obj) = value.as_object_mut() else {
+            return Err(SpecError::new("IO", "response is not a JSON object"));
+        };
+        if let Some(ok) = obj.remove("ok") {
+            Ok(ok)
+        } else if let Some(err) = obj.remove("err") {
+            match serde_json::from_value::<SpecError>(err) {
+                Ok(spec_error) => Err(spec_error),
+                Err(e) => Err(SpecError::new("IO", format!("invalid err payload: {e}"))),
+            }
+        } else {
+            Err(SpecError::new("IO", "response has neither \"ok\" nor \"err\""))
+        }
+    }
+}
+
+/// Conformance mode for a C/C++ implementation `dlopen`'d from a shared
+/// library built from [`libspec::codegen::c_abi_shim`]'s generated
+/// shim — the FFI equivalent of [`WasmHost`], except the implementation
+/// runs natively instead of sandboxed, so only point this at an
+/// implementation you trust. Looks up the shim's four fixed entry
+/// points (`libspec_shim_new`, `libspec_shim_call`, `libspec_shim_free`,
+/// `libspec_shim_free_handle`) by name, so a `CShimHost` doesn't need to
+/// know the spec's method names or prefix the way the generated shim
+/// itself does.
+pub struct CShimHost {
+    _library: libloading::Library,
+    handle: *mut std::ffi::c_void,
+    call: unsafe extern "C" fn(*mut std::ffi::c_void, *const u8, usize, *mut *mut u8, *mut usize) -> i32,
+    free: unsafe extern "C" fn(*mut u8, usize),
+    free_handle: unsafe extern "C" fn(*mut std::ffi::c_void),
+}
+
+impl CShimHost {
+    /// Loads the shared library at `path` and calls its
+    /// `libspec_shim_new` to obtain a handle. A missing library or a
+    /// missing entry point is reported as a `SpecError` with code
+    /// `"IO"`, the same code a transport problem on any other transport
+    /// in this module is reported with.
+    pub fn load(path: &std::path::Path) -> Result<Self, SpecError> {
+        let io_error = |e: libloading::Error| SpecError::new("IO", e.to_string());
+
+        let library = unsafe { libloading::Library::new(path) }.map_err(io_error)?;
+        let new_handle: libloading::Symbol<unsafe extern "C" fn() -> *mut std::ffi::c_void> =
+            unsafe { library.get(b"libspec_shim_new\0") }.map_err(io_error)?;
+        let call: libloading::Symbol<
+            unsafe extern "C" fn(*mut std::ffi::c_void, *const u8, usize, *mut *mut u8, *mut usize) -> i32,
+        > = unsafe { library.get(b"libspec_shim_call\0") }.map_err(io_error)?;
+        let free: libloading::Symbol<unsafe extern "C" fn(*mut u8, usize)> =
+            unsafe { library.get(b"libspec_shim_free\0") }.map_err(io_error)?;
+        let free_handle: libloading::Symbol<unsafe extern "C" fn(*mut std::ffi::c_void)> =
+            unsafe { library.get(b"libspec_shim_free_handle\0") }.map_err(io_error)?;
+
+        let handle = unsafe { new_handle() };
+        let call = *call;
+        let free = *free;
+        let free_handle = *free_handle;
+        Ok(Self { _library: library, handle, call, free, free_handle })
+    }
+
+    /// Sends `operation` to the shim's `libspec_shim_call` and returns
+    /// its response, parsed the same way [`StdioProcess::call`] parses
+    /// one. A malformed response is reported as a `SpecError` with code
+    /// `"IO"`.
+    pub fn call(&self, operation: &serde_json::Value) -> Result<serde_json::Value, SpecError> {
+        let request = serde_json::to_vec(operation).expect("operation serialization is infallible");
+        let mut out_ptr: *mut u8 = std::ptr::null_mut();
+        let mut out_len: usize = 0;
+        let status =
+            unsafe { (self.call)(self.handle, request.as_ptr(), request.len(), &mut out_ptr, &mut out_len) };
+        if status != 0 || out_ptr.is_null() {
+            return Err(SpecError::new("IO", "shim call failed"));
+        }
+
+        let response = unsafe { std::slice::from_raw_parts(out_ptr, out_len) }.to_vec();
+        unsafe { (self.free)(out_ptr, out_len) };
+
+        let mut value: serde_json::Value = serde_json::from_slice(&response)
+            .map_err(|e| SpecError::new("IO", format!("invalid response: {e}")))?;
+        let Some(obj) = value.as_object_mut() else {
+            return Err(SpecError::new("IO", "response is not a JSON object"));
+        };
+        if let Some(ok) = obj.remove("ok") {
+            Ok(ok)
+        } else if let Some(err) = obj.remove("err") {
+            match serde_json::from_value::<SpecError>(err) {
+                Ok(spec_error) => Err(spec_error),
+                Err(e) => Err(SpecError::new("IO", format!("invalid err payload: {e}"))),
+            }
+        } else {
+            Err(SpecError::new("IO", "response has neither \"ok\" nor \"err\""))
+        }
+    }
+}
+
+impl Drop for CShimHost {
+    /// Releases the handle `libspec_shim_new` returned so a dropped
+    /// `CShimHost` doesn't leak whatever the C implementation allocated
+    /// for it.
+    fn drop(&mut self) {
+        unsafe { (self.free_handle)(self.handle) };
+    }
+}
+
+/// What checking an operation's output against its stored snapshot found.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotOutcome {
+    /// No snapshot existed yet for this requirement; `value`'s current
+    /// output was written as the new accepted baseline, same as `insta`
+    /// does on a first run.
+    New,
+    /// Matched the accepted baseline.
+    Matched,
+    /// Differs from the accepted baseline. The new output was written
+    /// alongside it as a pending snapshot, for a reviewer to diff against
+    /// `expected` and either discard (the implementation regressed) or
+    /// promote with [`SnapshotStore::accept`] (the behavior change was
+    /// intentional).
+    Diverged { expected: String, actual: String },
+}
+
+/// Records an operation's output per requirement id and flags drift
+/// against a previously accepted snapshot, the same idea `insta` applies
+/// to any value under test, scoped here to the requirement a
+/// [`Scenario`]/[`Test`] exercises so a behavioral change shows up
+/// attributed to the requirement it broke rather than just a diff of
+/// opaque test output.
+///
+/// Each requirement's snapshot lives at `{root}/{requirement}.snap`; a
+/// divergent check additionally writes `{root}/{requirement}.snap.new`
+/// with the new output, left for review until [`SnapshotStore::accept`]
+/// promotes or a human discards it.
+///
+/// ```no_run
+/// # use libspec_harness::SnapshotStore;
+/// # use serde::Serialize;
+/// # #[derive(Serialize)] struct AccountDto { balance: f64 }
+/// let store = SnapshotStore::new("tests/snapshots");
+/// let outcome = store.check("REQ-004", &AccountDto { balance: 50.0 }).unwrap();
+/// ```
+pub struct SnapshotStore {
+    root: PathBuf,
+}
+
+impl SnapshotStore {
+    /// A store rooted at `root`, created on first write if it doesn't
+    /// exist yet.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn accepted_path(&self, requirement: &str) -> PathBuf {
+        self.root.join(format!("{requirement}.snap"))
+    }
+
+    fn pending_path(&self, requirement: &str) -> PathBuf {
+        self.root.join(format!("{requirement}.snap.new"))
+    }
+
+    /// Checks `value`'s pretty-printed JSON serialization (the format a
+    /// generated DTO's `Serialize` impl produces — see
+    /// [`libspec::codegen::rust_dto`]) against `requirement`'s accepted
+    /// snapshot.
+    pub fn check<T: serde::Serialize>(
+        &self,
+        requirement: &str,
+        value: &T,
+    ) -> std::io::Result<SnapshotOutcome> {
+        let actual =
+            serde_json::to_string_pretty(value).expect("snapshot value serialization is infallible");
+        let accepted_path = self.accepted_path(requirement);
+
+        match std::fs::read_to_string(&accepted_path) {
+            Ok(expected) if expected == actual => {
+                let _ = std::fs::remove_file(self.pending_path(requirement));
+                Ok(SnapshotOutcome::Matched)
+            }
+            Ok(expected) => {
+                write_file(&self.pending_path(requirement), &actual)?;
+                Ok(SnapshotOutcome::Diverged { expected, actual })
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                write_file(&accepted_path, &actual)?;
+                Ok(SnapshotOutcome::New)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Promotes `requirement`'s pending snapshot (written by a
+    /// [`SnapshotOutcome::Diverged`] check) to the accepted baseline,
+    /// deleting the pending file — the harness's equivalent of `cargo
+    /// insta accept`. A no-op if there's no pending snapshot to accept.
+    pub fn accept(&self, requirement: &str) -> std::io::Result<()> {
+        let pending = self.pending_path(requirement);
+        if pending.exists() {
+            std::fs::rename(pending, self.accepted_path(requirement))?;
+        }
+        Ok(())
+    }
+}
+
+fn write_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)
+}
+
+/// A controllable clock, RNG, and id source, threaded through an
+/// implementation wherever it would otherwise call
+/// `SystemTime::now()`/a random number generator/a UUID library directly,
+/// so time-dependent requirements (interest accrual, a session timeout)
+/// can be driven and asserted on deterministically instead of being at the
+/// mercy of the real clock and real randomness.
+///
+/// ```
+/// # use libspec_harness::SpecEnv;
+/// # use std::time::Duration;
+/// let mut env = SpecEnv::new(42);
+/// let start = env.now();
+/// env.advance(Duration::from_secs(60 * 60 * 24 * 365));
+/// assert_eq!(env.now() - start, Duration::from_secs(60 * 60 * 24 * 365));
+/// ```
+pub struct SpecEnv {
+    now: Duration,
+    rng: u64,
+}
+
+impl SpecEnv {
+    /// A `SpecEnv` whose clock starts at zero and whose RNG/id stream is
+    /// deterministic for a given `seed` — the same seed always produces
+    /// the same sequence of [`SpecEnv::random`]/[`SpecEnv::next_id`]
+    /// values, so a test failure found with one seed can be reproduced
+    /// exactly by reusing it.
+    pub fn new(seed: u64) -> Self {
+        Self { now: Duration::ZERO, rng: seed.max(1) }
+    }
+
+    /// The env's current time, as a duration since an arbitrary epoch. An
+    /// implementation under test should read this instead of
+    /// `SystemTime::now()`/`Instant::now()` so a caller can control it.
+    pub fn now(&self) -> Duration {
+        self.now
+    }
+
+    /// Moves the env's clock forward by `duration`, so a test can
+    /// simulate time passing (e.g. a year of interest accrual, a session
+    /// timing out) without actually waiting for it.
+    pub fn advance(&mut self, duration: Duration) {
+        self.now += duration;
+    }
+
+    /// The next pseudo-random `u64` in the deterministic stream, via a
+    /// xorshift64* generator. Not suitable for anything security-sensitive
+    /// — only for feeding an implementation a reproducible "random" value
+    /// in place of a real RNG.
+    fn next_u64(&mut self) -> u64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        self.rng.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// The next pseudo-random `f64` in `[0, 1)`.
+    pub fn random(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// The next pseudo-random integer in `[0, bound)`. Biased for very
+    /// large `bound`s (the modulo of a 64-bit generator isn't perfectly
+    /// uniform), which is fine for generating test inputs but not for
+    /// anything that needs true uniformity.
+    pub fn random_range(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "random_range bound must be positive");
+        self.next_u64() % bound
+    }
+
+    /// The next id in a deterministic, UUID-shaped stream (`8-4-4-4-12`
+    /// hex digits) — not a real UUID (no real randomness backs it), just
+    /// distinct and reproducible for a given seed, so an implementation
+    /// that takes its id source from the env can be tested without a real
+    /// UUID generator's nondeterminism creeping in.
+    pub fn next_id(&mut self) -> String {
+        let hi = self.next_u64();
+        let lo = self.next_u64();
+        format!(
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            (hi >> 32) as u32,
+            (hi >> 16) as u16,
+            hi as u16,
+            (lo >> 48) as u16,
+            lo & 0xffff_ffff_ffff,
+        )
+    }
+}
+
+/// Checks every requirement's [`PerfBudget`](libspec::spec::PerfBudget)
+/// against its generated criterion benchmark's recorded mean, reading
+/// criterion's own `estimates.json` artifact under `criterion_root`
+/// (`{criterion_root}/{benchmark}/new/estimates.json`, falling back to
+/// `.../base/estimates.json` for a benchmark whose results were saved as
+/// a baseline) rather than re-measuring anything itself — pairs with
+/// [`libspec::codegen::rust_bench`], which names each benchmark after its
+/// requirement's method name.
+///
+/// Returns a `(requirement id, passed)` pair per requirement with a perf
+/// budget whose benchmark has results on disk — the same shape
+/// [`ConformanceReport::build`]'s `outcomes` takes, so perf results merge
+/// straight into the same report as functional scenario outcomes.
+/// Requirements with a perf budget but no benchmark results yet (the
+/// bench hasn't been run) are skipped, not counted as failed.
+pub fn check_perf_budgets<'a>(doc: &'a SpecDocument, criterion_root: &std::path::Path) -> Vec<(&'a str, bool)> {
+    doc.requirements
+        .iter()
+        .filter_map(|req| {
+            let budget = req.perf_budget.as_ref()?;
+            let name = libspec::codegen::method_name(req);
+            let mean_nanos = read_criterion_mean_nanos(criterion_root, &name)?;
+            let mean_millis = mean_nanos / 1_000_000.0;
+            Some((req.id.as_str(), mean_millis <= budget.max_millis))
+        })
+        .collect()
+}
+
+/// The mean of a criterion benchmark's most recent run, in nanoseconds,
+/// read straight from its `estimates.json` rather than re-measuring
+/// anything.
+fn read_criterion_mean_nanos(criterion_root: &std::path::Path, benchmark: &str) -> Option<f64> {
+    for variant in ["new", "base"] {
+        let path = criterion_root.join(benchmark).join(variant).join("estimates.json");
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(&contents) else {
+            continue;
+        };
+        if let Some(point) = value.get("mean").and_then(|m| m.get("point_estimate")).and_then(|p| p.as_f64()) {
+            return Some(point);
+        }
+    }
+    None
+}
+
+/// Whether a [`run_within_budget`] call finished before its budget
+/// elapsed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeoutOutcome<T> {
+    /// The operation finished in time, with this result.
+    Completed(T),
+    /// The budget elapsed before the operation finished.
+    TimedOut,
+}
+
+/// Runs `operation` under [`tokio::time::timeout`], reporting whether it
+/// finished within `budget` as a [`TimeoutOutcome`] instead of requiring
+/// every caller to match on `tokio::time::timeout`'s `Result` by hand.
+///
+/// Pairs with a paused tokio clock (a test started with
+/// `#[tokio::test(start_paused = true)]`, driving time forward with
+/// `tokio::time::sleep`/`tokio::time::advance`) so a timeout requirement
+/// — e.g. the bank example's `AsyncBankLibrary` — can be asserted on
+/// deterministically: the budget either elapses or it doesn't, with no
+/// real waiting and no flake under CI load.
+///
+/// ```
+/// # use libspec_harness::{run_within_budget, TimeoutOutcome};
+/// # use std::time::Duration;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let outcome = run_within_budget(async { 42 }, Duration::from_millis(10)).await;
+/// assert_eq!(outcome, TimeoutOutcome::Completed(42));
+/// # }
+/// ```
+pub async fn run_within_budget<F: std::future::Future<Output = T>, T>(
+    operation: F,
+    budget: Duration,
+) -> TimeoutOutcome<T> {
+    match tokio::time::timeout(budget, operation).await {
+        Ok(value) => TimeoutOutcome::Completed(value),
+        Err(_) => TimeoutOutcome::TimedOut,
+    }
+}
+
+/// Which of `doc`'s requirement/constraint ids appear as a substring of
+/// `text`, e.g. the `"CONST-002: insufficient funds"` a generated error's
+/// `Display` produces (see [`libspec::codegen::rust_error`]).
+fn ids_mentioned(doc: &SpecDocument, text: &str) -> Vec<String> {
+    doc.requirements
+        .iter()
+        .map(|r| r.id.as_str())
+        .chain(doc.constraints.iter().map(|c| c.code.as_str()))
+        .filter(|id| text.contains(id))
+        .map(|id| id.to_string())
+        .collect()
+}
+
+/// One call captured for later replay: the operation invoked, its
+/// arguments as `(name, value)` pairs, and the outcome it produced
+/// (`Err` carrying the error's `Display`, since a replayed outcome only
+/// needs to compare pass/fail and message, not the original error type).
+#[derive(Debug, Clone, PartialEq, Serialize, serde::Deserialize)]
+pub struct RecordedOperation {
+    pub operation: String,
+    pub arguments: Vec<(String, String)>,
+    pub outcome: Result<(), String>,
+}
+
+/// A sequence of [`RecordedOperation`]s, in the order they happened —
+/// typically captured from a production incident by
+/// [`Recording::push`]ing each call as it's made, then saved with
+/// [`Recording::save`] and handed to a [`Replayer`] to turn "this
+/// happened in prod" into a reproducible conformance test case.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+    pub operations: Vec<RecordedOperation>,
+}
+
+impl Recording {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends one call to the recording.
+    pub fn push(&mut self, operation: &str, arguments: &[(&str, &str)], outcome: Result<(), String>) {
+        self.operations.push(RecordedOperation {
+            operation: operation.to_string(),
+            arguments: arguments.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect(),
+            outcome,
+        });
+    }
+
+    /// Appends every operation in this recording to `path` as one JSON
+    /// line each, the same JSON-lines convention [`libspec::trace`] and
+    /// [`libspec::audit`] use for their on-disk artifacts, creating the
+    /// file if it doesn't exist yet.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        use std::io::Write;
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+        for op in &self.operations {
+            let line = serde_json::to_string(op).expect("RecordedOperation serialization is infallible");
+            writeln!(file, "{line}")?;
+        }
+        Ok(())
+    }
+
+    /// Loads a recording back from the JSON lines at `path`, same format
+    /// [`Recording::save`] writes. A line that fails to parse is skipped
+    /// rather than failing the whole load.
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let operations = contents.lines().filter_map(|line| serde_json::from_str(line).ok()).collect();
+        Ok(Recording { operations })
+    }
+}
+
+/// A [`RecordedOperation`] whose name has no call registered with a
+/// [`Replayer`].
+#[derive(Debug)]
+struct UnknownOperation(String);
+
+impl fmt::Display for UnknownOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no replay registered for operation {:?}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownOperation {}
+
+/// Replays a [`Recording`] against any implementation, one operation at a
+/// time, comparing each replayed outcome against what was originally
+/// recorded — turning a production incident's captured trace into a
+/// reproducible conformance test case instead of a hand-transcribed one.
+/// Same registration shape as [`Conformance`], but keyed by operation name
+/// instead of requirement id, since a recording doesn't know which
+/// requirement each call implements.
+///
+/// ```no_run
+/// # use libspec_harness::{Recording, Replayer};
+/// # struct MyBank;
+/// # impl MyBank { fn deposit(&self, _amount: f64) -> Result<(), Box<dyn std::error::Error>> { Ok(()) } }
+/// let mut replayer = Replayer::new();
+/// replayer.register("deposit", |bank: &MyBank, args| {
+///     let amount: f64 = args.iter().find(|(n, _)| n == "amount").unwrap().1.parse()?;
+///     bank.deposit(amount)
+/// });
+/// let recording = Recording::load("incident.jsonl").unwrap();
+/// replayer.replay(&recording, &MyBank);
+/// ```
+type ReplayCall<T> = fn(&T, &[(String, String)]) -> TestResult;
+
+pub struct Replayer<T> {
+    calls: std::collections::HashMap<String, ReplayCall<T>>,
+}
+
+impl<T> Replayer<T> {
+    pub fn new() -> Self {
+        Replayer { calls: std::collections::HashMap::new() }
+    }
+
+    /// Registers `call` as how to invoke `operation` against an
+    /// implementation when replaying a [`RecordedOperation`] with that
+    /// name.
+    pub fn register(&mut self, operation: &str, call: ReplayCall<T>) -> &mut Self {
+        self.calls.insert(operation.to_string(), call);
+        self
+    }
+
+    /// Replays every operation in `recording` against `implementation` in
+    /// order, printing libtest-style `test[{i}] {operation} ... ok`/`FAILED`
+    /// lines. A step fails if no call is registered for its operation, or
+    /// if replaying it now produces a pass/fail outcome that diverges from
+    /// what was originally recorded — the replayed error's message isn't
+    /// compared, only whether it failed, since an incident's original
+    /// error text isn't expected to reproduce byte-for-byte.
+    ///
+    /// Returns [`ExitCode::FAILURE`] if any step failed.
+    pub fn replay(&self, recording: &Recording, implementation: &T) -> ExitCode {
+        let mut failures = Vec::new();
+
+        for (i, recorded) in recording.operations.iter().enumerate() {
+            print!("test[{i}] {} ... ", recorded.operation);
+
+            let Some(call) = self.calls.get(&recorded.operation) else {
+                println!("FAILED (no replay registered)");
+                failures.push((i, UnknownOperation(recorded.operation.clone()).to_string()));
+                continue;
+            };
+
+            let result = call(implementation, &recorded.arguments);
+            if result.is_ok() == recorded.outcome.is_ok() {
+                println!("ok");
+            } else {
+                println!("FAILED (diverged from recording)");
+                failures.push((
+                    i,
+                    format!(
+                        "recorded {:?}, replayed {:?}",
+                        recorded.outcome,
+                        result.map_err(|e| e.to_string())
+                    ),
+                ));
+            }
+        }
+
+        if failures.is_empty() {
+            ExitCode::SUCCESS
+        } else {
+            println!("\nfailures:");
+            for (i, message) in &failures {
+                println!("    test[{i}]: {message}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+impl<T> Default for Replayer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A candidate constraint [`infer_constraints`] noticed holding across
+/// every successful call to `operation` in the [`Recording`] it was given
+/// — a relationship the author never wrote down, surfaced for them to
+/// judge instead of a proposed edit to the spec itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintProposal {
+    pub operation: String,
+    /// A [`libspec::spec::ConstraintExpr`]-grammar condition, e.g.
+    /// `"amount > 0"`, ready to paste into a [`libspec::spec::Constraint`]'s
+    /// `expr` if the author accepts it.
+    pub expr: String,
+    /// How many successful calls to `operation` support this proposal —
+    /// one observation is a coincidence, a hundred looks intentional.
+    pub support: usize,
+}
+
+/// Looks for numeric invariants that held across every successful call to
+/// each operation in `recording`: an argument that was always the same
+/// value or always on one side of zero, and pairs of arguments that
+/// always stood in the same relation to each other (e.g. every
+/// `withdraw`'s `amount` was `<=` its `limit`). Each one becomes a
+/// [`ConstraintProposal`] for the spec's author to accept, tweak, or
+/// dismiss as coincidence — this never writes to the spec itself.
+///
+/// An operation needs at least two successful observations before any
+/// proposal is made for it; a single call can't distinguish a real
+/// invariant from a coincidence. An argument that isn't present, or
+/// doesn't parse as a number, on every successful call is left out of
+/// both checks (an invariant that doesn't apply to a whole call is not
+/// usefully expressed as a `Constraint::expr`, which knows only the
+/// arguments present on the call it's attached to).
+pub fn infer_constraints(recording: &Recording) -> Vec<ConstraintProposal> {
+    let mut by_operation: std::collections::BTreeMap<&str, Vec<&RecordedOperation>> = std::collections::BTreeMap::new();
+    for op in recording.operations.iter().filter(|op| op.outcome.is_ok()) {
+        by_operation.entry(op.operation.as_str()).or_default().push(op);
+    }
+
+    let mut proposals = Vec::new();
+    for (operation, calls) in by_operation {
+        if calls.len() < 2 {
+            continue;
+        }
+
+        let values: Vec<std::collections::BTreeMap<&str, f64>> = calls
+            .iter()
+            .map(|call| {
+                call.arguments
+                    .iter()
+                    .filter_map(|(name, value)| value.parse::<f64>().ok().map(|v| (name.as_str(), v)))
+                    .collect()
+            })
+            .collect();
+        let names: std::collections::BTreeSet<&str> = values.iter().flat_map(|v| v.keys().copied()).collect();
+
+        for &name in &names {
+            let observed: Vec<f64> = values.iter().filter_map(|v| v.get(name).copied()).collect();
+            if observed.len() != calls.len() {
+                continue;
+            }
+            let proposal = if observed.iter().all(|v| *v == observed[0]) {
+                Some(Comparison { lhs: Term::Ident(name.to_string()), op: RelOp::Eq, rhs: Term::Number(observed[0]) })
+            } else if observed.iter().all(|v| *v > 0.0) {
+                Some(Comparison { lhs: Term::Ident(name.to_string()), op: RelOp::Gt, rhs: Term::Number(0.0) })
+            } else if observed.iter().all(|v| *v >= 0.0) {
+                Some(Comparison { lhs: Term::Ident(name.to_string()), op: RelOp::Ge, rhs: Term::Number(0.0) })
+            } else {
+                None
+            };
+            if let Some(comparison) = proposal {
+                proposals.push(ConstraintProposal {
+                    operation: operation.to_string(),
+                    expr: comparison.to_string(),
+                    support: calls.len(),
+                });
+            }
+        }
+
+        let names: Vec<&str> = names.into_iter().collect();
+        for (i, &a) in names.iter().enumerate() {
+            for &b in &names[i + 1..] {
+                let pairs: Vec<(f64, f64)> =
+                    values.iter().filter_map(|v| Some((*v.get(a)?, *v.get(b)?))).collect();
+                if pairs.len() != calls.len() {
+                    continue;
+                }
+                for op in [RelOp::Lt, RelOp::Le, RelOp::Gt, RelOp::Ge, RelOp::Eq] {
+                    if pairs.iter().all(|(x, y)| relop_holds(op, *x, *y)) {
+                        proposals.push(ConstraintProposal {
+                            operation: operation.to_string(),
+                            expr: Comparison { lhs: Term::Ident(a.to_string()), op, rhs: Term::Ident(b.to_string()) }
+                                .to_string(),
+                            support: calls.len(),
+                        });
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    proposals
+}
+
+fn relop_holds(op: RelOp, lhs: f64, rhs: f64) -> bool {
+    match op {
+        RelOp::Gt => lhs > rhs,
+        RelOp::Lt => lhs < rhs,
+        RelOp::Ge => lhs >= rhs,
+        RelOp::Le => lhs <= rhs,
+        RelOp::Eq => lhs == rhs,
+        RelOp::Ne => lhs != rhs,
+    }
+}
+
+/// One call in a [`ConcurrentRecorder`]'s history: `operation`'s
+/// invocation and response, timestamped against the recorder's start so
+/// [`check_linearizable`] can tell which calls the real-time clock forced
+/// into a particular order and which overlapped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConcurrentCall {
+    pub operation: RecordedOperation,
+    pub start: Duration,
+    pub end: Duration,
+}
+
+/// Collects [`ConcurrentCall`]s from however many threads call
+/// [`ConcurrentRecorder::record`] concurrently, timestamping each one
+/// against a shared start [`Instant`] so the resulting history can be fed
+/// to [`check_linearizable`]. A thread-safe implementation like the bank
+/// example's `BankLibrary` (thread-safe via per-account locking) is
+/// exactly what this is for: drive it from several threads at once, then
+/// ask whether the recorded history is consistent with *some* sequential
+/// order against the spec model, instead of trusting that per-account
+/// locks add up to a correct concurrent implementation.
+///
+/// ```no_run
+/// # use libspec_harness::ConcurrentRecorder;
+/// # use std::sync::Arc;
+/// # use std::thread;
+/// let recorder = Arc::new(ConcurrentRecorder::new());
+/// let r = Arc::clone(&recorder);
+/// thread::spawn(move || {
+///     r.record("deposit", &[("amount", "10")], || Ok(()));
+/// })
+/// .join()
+/// .unwrap();
+/// let history = Arc::try_unwrap(recorder).unwrap().into_history();
+/// ```
+#[derive(Debug)]
+pub struct ConcurrentRecorder {
+    start: Instant,
+    calls: std::sync::Mutex<Vec<ConcurrentCall>>,
+}
+
+impl ConcurrentRecorder {
+    pub fn new() -> Self {
+        Self { start: Instant::now(), calls: std::sync::Mutex::new(Vec::new()) }
+    }
+
+    /// Times `call` against this recorder's shared start instant and
+    /// records its span and outcome, returning what `call` returned
+    /// unchanged so this can wrap a call in place without disturbing its
+    /// result.
+    pub fn record(
+        &self,
+        operation: &str,
+        arguments: &[(&str, &str)],
+        call: impl FnOnce() -> Result<(), String>,
+    ) -> Result<(), String> {
+        let start = self.start.elapsed();
+        let outcome = call();
+        let end = self.start.elapsed();
+        self.calls.lock().unwrap().push(ConcurrentCall {
+            operation: RecordedOperation {
+                operation: operation.to_string(),
+                arguments: arguments.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect(),
+                outcome: outcome.clone(),
+            },
+            start,
+            end,
+        });
+        outcome
+    }
+
+    /// Drains every call this recorder collected. Callers shouldn't read
+    /// anything into the returned order — once more than one thread is
+    /// recording, only each call's `start`/`end` carry real ordering
+    /// information.
+    pub fn into_history(self) -> Vec<ConcurrentCall> {
+        self.calls.into_inner().unwrap()
+    }
+}
+
+impl Default for ConcurrentRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a [`ConcurrentCall`] history is consistent with *some*
+/// sequential order of the same calls against the model [`check_linearizable`]
+/// drives — the textbook definition of linearizability (Herlihy & Wing,
+/// 1990).
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinearizabilityResult {
+    /// `history` is linearizable; one witnessing sequential order, given
+    /// as indices into the `history` slice that was checked.
+    Linearizable(Vec<usize>),
+    /// No permutation of `history` that respects real-time call order
+    /// reproduces every call's recorded outcome when replayed against the
+    /// model.
+    NotLinearizable,
+}
+
+/// Checks whether `history` — a set of concurrent calls, each with a
+/// real-time invocation/response span — is linearizable against `model`:
+/// whether some permutation of `history` both (a) respects real time (if
+/// call `a`'s response came before call `b`'s invocation, `a` must come
+/// first) and (b) reproduces every call's recorded outcome when `apply`
+/// replays it, one at a time, against a clone of `model`.
+///
+/// This is the Wing & Gong / Lowe linearizability-checking algorithm: at
+/// each step, try every not-yet-linearized call whose start isn't forced
+/// after some other not-yet-linearized call's end, apply it to the model,
+/// and recurse if its outcome matches; backtrack otherwise. Exponential
+/// in the worst case, so `history` needs to stay small — fine for the
+/// handful of concurrent calls a harness test drives, not for a
+/// production audit log.
+///
+/// ```no_run
+/// # use libspec_harness::{check_linearizable, ConcurrentRecorder};
+/// let recorder = ConcurrentRecorder::new();
+/// recorder.record("deposit", &[("amount", "10")], || Ok(()));
+/// let history = recorder.into_history();
+///
+/// check_linearizable(&history, &0.0_f64, |balance, op| {
+///     let amount: f64 = op.arguments[0].1.parse().unwrap();
+///     *balance += amount;
+///     Ok(())
+/// });
+/// ```
+pub fn check_linearizable<T: Clone>(
+    history: &[ConcurrentCall],
+    model: &T,
+    apply: impl Fn(&mut T, &RecordedOperation) -> Result<(), String>,
+) -> LinearizabilityResult {
+    let remaining: Vec<usize> = (0..history.len()).collect();
+    let mut order = Vec::new();
+    if linearize(history, &remaining, model, &apply, &mut order) {
+        LinearizabilityResult::Linearizable(order)
+    } else {
+        LinearizabilityResult::NotLinearizable
+    }
+}
+
+fn linearize<T: Clone>(
+    history: &[ConcurrentCall],
+    remaining: &[usize],
+    state: &T,
+    apply: &impl Fn(&mut T, &RecordedOperation) -> Result<(), String>,
+    order: &mut Vec<usize>,
+) -> bool {
+    if remaining.is_empty() {
+        return true;
+    }
+
+    // A call may be linearized next only if real time doesn't force
+    // another still-pending call ahead of it (that call's response
+    // strictly preceding this call's invocation).
+    let candidates = remaining
+        .iter()
+        .copied()
+        .filter(|&i| !remaining.iter().any(|&j| j != i && history[j].end < history[i].start));
+
+    for i in candidates {
+        let mut next_state = state.clone();
+        let outcome = apply(&mut next_state, &history[i].operation);
+        if outcome.is_ok() != history[i].operation.outcome.is_ok() {
+            continue;
+        }
+
+        let next_remaining: Vec<usize> = remaining.iter().copied().filter(|&r| r != i).collect();
+        order.push(i);
+        if linearize(history, &next_remaining, &next_state, apply, order) {
+            return true;
+        }
+        order.pop();
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libspec::spec::Requirement;
+
+    // `LIBSPEC_TRACE_FILE` is process-global, but libtest runs tests in
+    // this binary concurrently on several threads — without this lock,
+    // two `with_trace_file` calls running at once would each see the
+    // other's path instead of their own.
+    static TRACE_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    fn with_trace_file<T>(f: impl FnOnce() -> T) -> T {
+        let _guard = TRACE_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let path = std::env::temp_dir().join(format!(
+            "libspec-harness-test-{:?}.jsonl",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        std::env::set_var("LIBSPEC_TRACE_FILE", &path);
+        let result = f();
+        std::env::remove_var("LIBSPEC_TRACE_FILE");
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+
+    #[derive(Debug)]
+    struct Boom;
+
+    impl fmt::Display for Boom {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "REQ-004: balance must be non-negative")
+        }
+    }
+
+    impl std::error::Error for Boom {}
+
+    #[test]
+    fn records_ids_found_in_a_failing_test_error() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let tests = [Test {
+                name: "overdraws",
+                func: || Err(Box::new(Boom) as Box<dyn std::error::Error>),
+            }];
+
+            let code = run(&doc, &tests);
+            assert_eq!(code, ExitCode::FAILURE);
+
+            let records = trace::read_records();
+            assert!(records.iter().any(|r| r.kind == "covers"
+                && r.function == "overdraws"
+                && r.requirement == "REQ-004"));
+        });
+    }
+
+    struct MyBank {
+        balance: f64,
+    }
+
+    #[test]
+    fn conformance_runs_a_scenario_per_requirement_and_records_coverage() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: |bank: &MyBank| {
+                    if bank.balance >= 0.0 {
+                        Ok(())
+                    } else {
+                        Err(Box::new(Boom) as Box<dyn std::error::Error>)
+                    }
+                },
+            }]);
+
+            let code = suite.run(&doc, &MyBank { balance: 10.0 });
+            assert_eq!(code, ExitCode::SUCCESS);
+
+            let records = trace::read_records();
+            assert!(records
+                .iter()
+                .any(|r| r.kind == "covers" && r.requirement == "REQ-004"));
+        });
+    }
+
+    #[test]
+    fn conformance_fails_when_a_scenario_fails() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: |bank: &MyBank| {
+                    if bank.balance >= 0.0 {
+                        Ok(())
+                    } else {
+                        Err(Box::new(Boom) as Box<dyn std::error::Error>)
+                    }
+                },
+            }]);
+
+            let code = suite.run(&doc, &MyBank { balance: -5.0 });
+            assert_eq!(code, ExitCode::FAILURE);
+        });
+    }
+
+    #[test]
+    fn conformance_fails_a_scenario_whose_requirement_is_unknown_to_the_spec() {
+        with_trace_file(|| {
+            let doc = SpecDocument::new();
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-999",
+                check: |_: &MyBank| Ok(()),
+            }]);
+
+            let code = suite.run(&doc, &MyBank { balance: 10.0 });
+            assert_eq!(code, ExitCode::FAILURE);
+        });
+    }
+
+    #[test]
+    fn run_versioned_refuses_to_run_on_a_version_mismatch() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: |_: &MyBank| Ok(()),
+            }]);
+
+            let code = suite.run_versioned(&doc, &MyBank { balance: 10.0 }, "stale-version", false);
+            assert_eq!(code, ExitCode::FAILURE);
+        });
+    }
+
+    #[test]
+    fn run_versioned_runs_anyway_when_mismatch_is_allowed() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: |_: &MyBank| Ok(()),
+            }]);
+
+            let code = suite.run_versioned(&doc, &MyBank { balance: 10.0 }, "stale-version", true);
+            assert_eq!(code, ExitCode::SUCCESS);
+        });
+    }
+
+    #[test]
+    fn run_versioned_runs_normally_when_the_version_matches() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: |_: &MyBank| Ok(()),
+            }]);
+
+            let version = doc.version_hash();
+            let code = suite.run_versioned(&doc, &MyBank { balance: 10.0 }, &version, false);
+            assert_eq!(code, ExitCode::SUCCESS);
+        });
+    }
+
+    #[test]
+    fn filter_by_tag_only_runs_matching_scenarios() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                tags: vec!["money".into()],
+                ..Default::default()
+            });
+            doc.requirements.push(Requirement {
+                id: "REQ-005".into(),
+                text: "session must expire".into(),
+                tags: vec!["auth".into()],
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[
+                Scenario {
+                    requirement: "REQ-004",
+                    check: |_: &MyBank| Ok(()),
+                },
+                Scenario {
+                    requirement: "REQ-005",
+                    check: |_: &MyBank| Err(Box::new(Boom) as Box<dyn std::error::Error>),
+                },
+            ]);
+
+            let code = suite.filter().tag("money").run(&doc, &MyBank { balance: 10.0 });
+            assert_eq!(code, ExitCode::SUCCESS);
+        });
+    }
+
+    #[test]
+    fn filter_by_id_runs_that_scenario_regardless_of_tags() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+            doc.requirements.push(Requirement {
+                id: "REQ-005".into(),
+                text: "session must expire".into(),
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[
+                Scenario {
+                    requirement: "REQ-004",
+                    check: |_: &MyBank| Err(Box::new(Boom) as Box<dyn std::error::Error>),
+                },
+                Scenario {
+                    requirement: "REQ-005",
+                    check: |_: &MyBank| Ok(()),
+                },
+            ]);
+
+            let code = suite.filter().id("REQ-005").run(&doc, &MyBank { balance: 10.0 });
+            assert_eq!(code, ExitCode::SUCCESS);
+        });
+    }
+
+    #[test]
+    fn filter_by_condition_excludes_a_scenario_whose_condition_is_inactive() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "overdrafts are allowed up to the configured limit".into(),
+                applies_when: vec!["overdraft".into()],
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: |_: &MyBank| Err(Box::new(Boom) as Box<dyn std::error::Error>),
+            }]);
+
+            let code = suite.filter().run(&doc, &MyBank { balance: 10.0 });
+            assert_eq!(code, ExitCode::SUCCESS);
+        });
+    }
+
+    #[test]
+    fn filter_by_condition_runs_a_scenario_once_its_condition_is_active() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "overdrafts are allowed up to the configured limit".into(),
+                applies_when: vec!["overdraft".into()],
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: |_: &MyBank| Err(Box::new(Boom) as Box<dyn std::error::Error>),
+            }]);
+
+            let code = suite.filter().condition("overdraft").run(&doc, &MyBank { balance: 10.0 });
+            assert_eq!(code, ExitCode::FAILURE);
+        });
+    }
+
+    #[test]
+    fn filter_by_condition_leaves_unconditioned_scenarios_untouched() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: |_: &MyBank| Ok(()),
+            }]);
+
+            let code = suite.filter().condition("overdraft").run(&doc, &MyBank { balance: 10.0 });
+            assert_eq!(code, ExitCode::SUCCESS);
+        });
+    }
+
+    #[test]
+    fn filter_with_no_tags_or_ids_runs_everything() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: |_: &MyBank| Err(Box::new(Boom) as Box<dyn std::error::Error>),
+            }]);
+
+            let code = suite.filter().run(&doc, &MyBank { balance: 10.0 });
+            assert_eq!(code, ExitCode::FAILURE);
+        });
+    }
+
+    trait BalanceApi {
+        fn balance(&self) -> TestResult;
+    }
+
+    struct HealthyBank;
+
+    impl BalanceApi for HealthyBank {
+        fn balance(&self) -> TestResult {
+            Ok(())
+        }
+    }
+
+    struct OverdrawnBank;
+
+    impl BalanceApi for OverdrawnBank {
+        fn balance(&self) -> TestResult {
+            Err(Box::new(Boom) as Box<dyn std::error::Error>)
+        }
+    }
+
+    fn check_balance(bank: &dyn BalanceApi) -> TestResult {
+        bank.balance()
+    }
+
+    #[test]
+    fn conformance_runs_against_a_trait_object_without_monomorphizing_per_implementation() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite: Conformance<dyn BalanceApi> = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: check_balance,
+            }]);
+
+            let healthy: Box<dyn BalanceApi> = Box::new(HealthyBank);
+            assert_eq!(suite.run(&doc, &*healthy), ExitCode::SUCCESS);
+
+            let overdrawn: Box<dyn BalanceApi> = Box::new(OverdrawnBank);
+            assert_eq!(suite.run(&doc, &*overdrawn), ExitCode::FAILURE);
+        });
+    }
+
+    #[test]
+    fn implementation_registry_runs_the_suite_against_every_registered_factory() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite: Conformance<dyn BalanceApi> = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: check_balance,
+            }]);
+
+            let mut backends = ImplementationRegistry::new();
+            backends.register("healthy", || Box::new(HealthyBank) as Box<dyn BalanceApi>);
+            backends.register("overdrawn", || Box::new(OverdrawnBank) as Box<dyn BalanceApi>);
+
+            assert_eq!(backends.run_all(&suite, &doc), ExitCode::FAILURE);
+        });
+    }
+
+    #[test]
+    fn implementation_registry_succeeds_when_every_registered_factory_passes() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite: Conformance<dyn BalanceApi> = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: check_balance,
+            }]);
+
+            let mut backends = ImplementationRegistry::new();
+            backends.register("healthy", || Box::new(HealthyBank) as Box<dyn BalanceApi>);
+
+            assert_eq!(backends.run_all(&suite, &doc), ExitCode::SUCCESS);
+        });
+    }
+
+    #[test]
+    fn run_parallel_succeeds_when_every_scenario_passes() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: |bank: &MyBank| {
+                    if bank.balance >= 0.0 {
+                        Ok(())
+                    } else {
+                        Err(Box::new(Boom) as Box<dyn std::error::Error>)
+                    }
+                },
+            }]);
+
+            let code = suite.run_parallel(&doc, || MyBank { balance: 10.0 }, Duration::from_secs(5));
+            assert_eq!(code, ExitCode::SUCCESS);
+        });
+    }
+
+    #[test]
+    fn run_parallel_fails_when_a_scenario_fails() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: |bank: &MyBank| {
+                    if bank.balance >= 0.0 {
+                        Ok(())
+                    } else {
+                        Err(Box::new(Boom) as Box<dyn std::error::Error>)
+                    }
+                },
+            }]);
+
+            let code = suite.run_parallel(&doc, || MyBank { balance: -5.0 }, Duration::from_secs(5));
+            assert_eq!(code, ExitCode::FAILURE);
+        });
+    }
+
+    #[test]
+    fn run_parallel_times_out_a_scenario_that_runs_too_long() {
+        with_trace_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-004".into(),
+                text: "balance must be non-negative".into(),
+                ..Default::default()
+            });
+
+            let suite = Conformance::new(&[Scenario {
+                requirement: "REQ-004",
+                check: |_: &MyBank| {
+                    std::thread::sleep(Duration::from_secs(60));
+                    Ok(())
+                },
+            }]);
+
+            let code = suite.run_parallel(&doc, || MyBank { balance: 10.0 }, Duration::from_millis(50));
+            assert_eq!(code, ExitCode::FAILURE);
+        });
+    }
+
+    #[test]
+    fn report_marks_a_requirement_with_no_scenarios_as_not_exercised() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+
+        let report = ConformanceReport::build(&doc, &[]);
+        assert_eq!(report.requirements.len(), 1);
+        assert_eq!(report.requirements[0].status, VerificationStatus::NotExercised);
+        assert_eq!(report.requirements[0].passed, 0);
+        assert_eq!(report.requirements[0].failed, 0);
+    }
+
+    #[test]
+    fn report_marks_a_requirement_verified_when_every_scenario_passed() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+
+        let report = ConformanceReport::build(&doc, &[("REQ-004", true), ("REQ-004", true)]);
+        assert_eq!(report.requirements[0].status, VerificationStatus::Verified);
+        assert_eq!(report.requirements[0].passed, 2);
+        assert_eq!(report.requirements[0].failed, 0);
+    }
+
+    #[test]
+    fn report_marks_a_requirement_partially_verified_when_some_scenarios_failed() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+
+        let report = ConformanceReport::build(&doc, &[("REQ-004", true), ("REQ-004", false)]);
+        assert_eq!(report.requirements[0].status, VerificationStatus::PartiallyVerified);
+        assert_eq!(report.requirements[0].passed, 1);
+        assert_eq!(report.requirements[0].failed, 1);
+    }
+
+    #[test]
+    fn report_renders_as_json() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+
+        let report = ConformanceReport::build(&doc, &[("REQ-004", true)]);
+        let json = report.to_json().unwrap();
+        assert!(json.contains("\"requirement\": \"REQ-004\""));
+        assert!(json.contains("\"status\": \"verified\""));
+    }
+
+    #[test]
+    fn report_renders_as_terminal_text() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+
+        let report = ConformanceReport::build(&doc, &[]);
+        assert!(report.to_terminal().contains("REQ-004 ... not exercised (0 passed, 0 failed)"));
+    }
+
+    #[test]
+    fn succeeds_when_every_test_passes() {
+        let doc = SpecDocument::new();
+        let tests = [Test {
+            name: "it_works",
+            func: || Ok(()),
+        }];
+
+        assert_eq!(run(&doc, &tests), ExitCode::SUCCESS);
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Op {
+        Deposit(f64),
+        Withdraw(f64),
+    }
+
+    fn apply(balance: &mut f64, op: &Op) -> Result<f64, SpecError> {
+        match op {
+            Op::Deposit(n) => {
+                *balance += n;
+                Ok(*balance)
+            }
+            Op::Withdraw(n) if *n <= *balance => {
+                *balance -= n;
+                Ok(*balance)
+            }
+            Op::Withdraw(n) => Err(SpecError::new("CONST-002", format!("cannot withdraw {n}"))),
+        }
+    }
+
+    #[test]
+    fn differential_succeeds_when_both_implementations_agree() {
+        let ops = [Op::Deposit(100.0), Op::Withdraw(40.0)];
+        let diff = Differential::new(&ops);
+
+        let mut reference_balance = 0.0;
+        let mut candidate_balance = 0.0;
+        let code = diff.run(
+            |op| apply(&mut reference_balance, op),
+            |op| apply(&mut candidate_balance, op),
+        );
+
+        assert_eq!(code, ExitCode::SUCCESS);
+    }
+
+    #[test]
+    fn differential_fails_at_the_first_diverging_operation() {
+        let ops = [Op::Deposit(100.0), Op::Withdraw(150.0), Op::Deposit(1.0)];
+        let diff = Differential::new(&ops);
+
+        let mut reference_balance = 0.0;
+        let mut candidate_balance = 1000.0;
+        let code = diff.run(
+            |op| apply(&mut reference_balance, op),
+            |op| apply(&mut candidate_balance, op),
+        );
+
+        assert_eq!(code, ExitCode::FAILURE);
+    }
+
+    #[test]
+    fn shrink_drops_operations_that_are_not_needed_to_reproduce_the_failure() {
+        let ops = vec![
+            Op::Deposit(1.0),
+            Op::Deposit(2.0),
+            Op::Withdraw(1000.0),
+            Op::Deposit(3.0),
+        ];
+
+        let shrunk = shrink(&ops, |ops| ops.iter().any(|op| matches!(op, Op::Withdraw(n) if *n > 500.0)));
+
+        assert_eq!(shrunk, vec![Op::Withdraw(1000.0)]);
+    }
+
+    #[test]
+    fn shrink_leaves_a_sequence_untouched_when_it_does_not_fail() {
+        let ops = vec![Op::Deposit(1.0), Op::Withdraw(1000.0)];
+        let shrunk = shrink(&ops, |_| false);
+        assert_eq!(shrunk, ops);
+    }
+
+    #[test]
+    fn shrink_can_reduce_to_an_empty_sequence() {
+        let ops = vec![Op::Deposit(1.0), Op::Deposit(2.0)];
+        let shrunk = shrink(&ops, |_| true);
+        assert_eq!(shrunk, Vec::<Op>::new());
+    }
+
+    #[test]
+    fn exhaustive_finds_the_shortest_failing_sequence() {
+        let domain = [Op::Deposit(10.0), Op::Withdraw(10.0)];
+
+        let failures = exhaustive(&domain, 2, |ops| {
+            let mut balance = 0.0;
+            ops.iter().any(|op| apply(&mut balance, op).is_err())
+        });
+
+        assert!(failures.contains(&vec![Op::Withdraw(10.0)]));
+    }
+
+    #[test]
+    fn exhaustive_reports_no_failures_when_nothing_diverges() {
+        let domain = [Op::Deposit(10.0)];
+        let failures = exhaustive(&domain, 3, |_| false);
+        assert!(failures.is_empty());
+    }
+
+    #[test]
+    fn exhaustive_covers_every_sequence_up_to_max_len() {
+        let domain = [Op::Deposit(1.0), Op::Deposit(2.0)];
+        let mut seen = Vec::new();
+        exhaustive(&domain, 2, |ops| {
+            seen.push(ops.to_vec());
+            false
+        });
+
+        // 2 of length 1, 4 of length 2.
+        assert_eq!(seen.len(), 6);
+    }
+
+    #[test]
+    fn to_test_snippet_renders_a_pasteable_test_function() {
+        let ops = vec![Op::Withdraw(1000.0)];
+        let snippet = to_test_snippet("reproduces_the_overdraw", &ops);
+
+        assert!(snippet.contains("#[test]\nfn reproduces_the_overdraw() {"));
+        assert!(snippet.contains("Withdraw(1000.0),"));
+    }
+
+    fn snapshot_store() -> (SnapshotStore, std::path::PathBuf) {
+        let root = std::env::temp_dir().join(format!(
+            "libspec-harness-snapshot-test-{:?}",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+        (SnapshotStore::new(&root), root)
+    }
+
+    #[derive(serde::Serialize)]
+    struct AccountDto {
+        balance: f64,
+    }
+
+    #[test]
+    fn snapshot_check_writes_a_new_baseline_on_first_run() {
+        let (store, root) = snapshot_store();
+
+        let outcome = store.check("REQ-004", &AccountDto { balance: 50.0 }).unwrap();
+        assert_eq!(outcome, SnapshotOutcome::New);
+        assert!(root.join("REQ-004.snap").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn snapshot_check_matches_an_unchanged_output() {
+        let (store, root) = snapshot_store();
+        store.check("REQ-004", &AccountDto { balance: 50.0 }).unwrap();
+
+        let outcome = store.check("REQ-004", &AccountDto { balance: 50.0 }).unwrap();
+        assert_eq!(outcome, SnapshotOutcome::Matched);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn snapshot_check_flags_drift_and_writes_a_pending_snapshot() {
+        let (store, root) = snapshot_store();
+        store.check("REQ-004", &AccountDto { balance: 50.0 }).unwrap();
+
+        let outcome = store.check("REQ-004", &AccountDto { balance: 75.0 }).unwrap();
+        match outcome {
+            SnapshotOutcome::Diverged { expected, actual } => {
+                assert!(expected.contains("50.0"));
+                assert!(actual.contains("75.0"));
+            }
+            other => panic!("expected Diverged, got {other:?}"),
+        }
+        assert!(root.join("REQ-004.snap.new").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn snapshot_accept_promotes_the_pending_snapshot_to_the_baseline() {
+        let (store, root) = snapshot_store();
+        store.check("REQ-004", &AccountDto { balance: 50.0 }).unwrap();
+        store.check("REQ-004", &AccountDto { balance: 75.0 }).unwrap();
+
+        store.accept("REQ-004").unwrap();
+        assert!(!root.join("REQ-004.snap.new").exists());
+
+        let outcome = store.check("REQ-004", &AccountDto { balance: 75.0 }).unwrap();
+        assert_eq!(outcome, SnapshotOutcome::Matched);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn snapshot_accept_is_a_no_op_without_a_pending_snapshot() {
+        let (store, root) = snapshot_store();
+        store.check("REQ-004", &AccountDto { balance: 50.0 }).unwrap();
+
+        store.accept("REQ-004").unwrap();
+        let outcome = store.check("REQ-004", &AccountDto { balance: 50.0 }).unwrap();
+        assert_eq!(outcome, SnapshotOutcome::Matched);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    fn criterion_root_with_mean(benchmark: &str, mean_nanos: f64) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "libspec-harness-criterion-test-{:?}",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+        let dir = root.join(benchmark).join("new");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("estimates.json"),
+            format!(r#"{{"mean": {{"point_estimate": {mean_nanos}}}}}"#),
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn check_perf_budgets_passes_a_benchmark_within_budget() {
+        use libspec::spec::PerfBudget;
+
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            perf_budget: Some(PerfBudget { scale: 10_000, max_millis: 1.0 }),
+            ..Default::default()
+        });
+
+        let root = criterion_root_with_mean("balance", 500_000.0);
+        let outcomes = check_perf_budgets(&doc, &root);
+        assert_eq!(outcomes, vec![("REQ-004", true)]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn check_perf_budgets_fails_a_benchmark_over_budget() {
+        use libspec::spec::PerfBudget;
+
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            perf_budget: Some(PerfBudget { scale: 10_000, max_millis: 1.0 }),
+            ..Default::default()
+        });
+
+        let root = criterion_root_with_mean("balance", 5_000_000.0);
+        let outcomes = check_perf_budgets(&doc, &root);
+        assert_eq!(outcomes, vec![("REQ-004", false)]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn check_perf_budgets_skips_a_requirement_with_no_benchmark_results_yet() {
+        use libspec::spec::PerfBudget;
+
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            perf_budget: Some(PerfBudget { scale: 10_000, max_millis: 1.0 }),
+            ..Default::default()
+        });
+
+        let root = std::env::temp_dir().join("libspec-harness-criterion-test-missing");
+        let _ = std::fs::remove_dir_all(&root);
+        let outcomes = check_perf_budgets(&doc, &root);
+        assert!(outcomes.is_empty());
+    }
+
+    #[test]
+    fn check_perf_budgets_skips_requirements_without_a_perf_budget() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let root = std::env::temp_dir().join("libspec-harness-criterion-test-no-budget");
+        assert!(check_perf_budgets(&doc, &root).is_empty());
+    }
+
+    fn echo_process() -> StdioProcess {
+        StdioProcess::spawn(
+            "sh",
+            &["-c", "while IFS= read -r line; do echo \"{\\\"ok\\\": $line}\"; done"],
+        )
+        .expect("sh should be on PATH")
+    }
+
+    #[test]
+    fn stdio_process_round_trips_an_ok_response() {
+        let mut process = echo_process();
+        let result = process.call(&serde_json::json!({"deposit": {"amount": 100.0}}));
+        assert_eq!(result, Ok(serde_json::json!({"deposit": {"amount": 100.0}})));
+    }
+
+    #[test]
+    fn stdio_process_returns_the_spec_error_from_an_err_response() {
+        let mut process = StdioProcess::spawn(
+            "sh",
+            &[
+                "-c",
+                "read -r line; echo '{\"err\": {\"code\": \"CONST-002\", \"requirement\": null, \"message\": \"insufficient funds\", \"details\": null}}'",
+            ],
+        )
+        .expect("sh should be on PATH");
+
+        let result = process.call(&serde_json::json!({"withdraw": {"amount": 150.0}}));
+        assert_eq!(result, Err(SpecError::new("CONST-002", "insufficient funds")));
+    }
+
+    #[test]
+    fn stdio_process_reports_a_closed_pipe_as_an_io_spec_error() {
+        let mut process =
+            StdioProcess::spawn("sh", &["-c", "read -r line; exit 0"]).expect("sh should be on PATH");
+        let result = process.call(&serde_json::json!({"deposit": {"amount": 1.0}}));
+        assert_eq!(result, Err(SpecError::new("IO", "subprocess closed stdout")));
+    }
+
+    /// Drives `examples/bank-account/python/bank_impl.py` through
+    /// [`StdioProcess`], the same way a [`Differential`] or [`Conformance`]
+    /// run would, proving the cross-language story end to end in-tree
+    /// rather than just against the `sh` stand-ins above: a Python
+    /// implementation of the bank spec, talking the same protocol and
+    /// returning the same `CONST-NNN` codes as the Rust one.
+    #[test]
+    fn stdio_process_drives_the_python_bank_reference_implementation() {
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+        let script = std::path::Path::new(&manifest_dir)
+            .join("../examples/bank-account/python/bank_impl.py")
+            .to_str()
+            .expect("path is valid UTF-8")
+            .to_string();
+        let mut process =
+            StdioProcess::spawn("python3", &[&script]).expect("python3 should be on PATH");
+
+        let account_id = process
+            .call(&serde_json::json!({"create_account": {"owner": "Ada"}}))
+            .expect("create_account should succeed");
+        let account_id = account_id.as_str().expect("create_account returns an id string");
+
+        assert_eq!(
+            process.call(&serde_json::json!({"deposit": {"account_id": account_id, "amount": 100}})),
+            Ok(serde_json::Value::Null)
+        );
+        assert_eq!(
+            process.call(&serde_json::json!({"balance": {"account_id": account_id}})),
+            Ok(serde_json::json!(100))
+        );
+        assert_eq!(
+            process.call(&serde_json::json!({"withdraw": {"account_id": account_id, "amount": 150}})),
+            Err(SpecError::new("CONST-002", "insufficient funds"))
+        );
+        assert_eq!(
+            process.call(&serde_json::json!({"balance": {"account_id": "ACC-missing"}})),
+            Err(SpecError::new("CONST-003", "account not found"))
+        );
+    }
+
+    /// Binds an ephemeral port and serves canned HTTP/1.1 responses on a
+    /// background thread for [`HttpTransport`]'s tests: a path containing
+    /// `"missing"` gets a 404 with a `SpecError` body (CONST-003), every
+    /// other path gets a 200 with an empty JSON object. Hand-rolled
+    /// rather than pulling in a web framework, the same call this crate
+    /// makes for [`StdioProcess`]'s `sh` stand-ins.
+    fn spawn_http_test_server() -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind a test port");
+        let addr = listener.local_addr().expect("listener has a local addr");
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                respond_to_http_test_request(stream);
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    fn respond_to_http_test_request(mut stream: std::net::TcpStream) {
+        use std::io::{BufRead, Write};
+
+        let mut reader = std::io::BufReader::new(stream.try_clone().expect("clone the stream"));
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).is_err() {
+            return;
+        }
+        loop {
+            let mut line = String::new();
+            match reader.read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) if line == "\r\n" => break,
+                Ok(_) => continue,
+            }
+        }
+
+        let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+        let (status, body) = if path.contains("missing") {
+            (
+                "404 Not Found",
+                r#"{"code": "CONST-003", "requirement": null, "message": "account not found", "details": null}"#,
+            )
+        } else {
+            ("200 OK", "{}")
+        };
+
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let _ = stream.write_all(response.as_bytes());
+    }
+
+    #[test]
+    fn http_transport_returns_the_parsed_body_on_a_2xx_response() {
+        let base_url = spawn_http_test_server();
+        let transport = HttpTransport::new(base_url);
+
+        assert_eq!(
+            transport.call("POST", "/accounts/ACC-1/deposit"),
+            Ok(serde_json::json!({}))
+        );
+    }
+
+    #[test]
+    fn http_transport_maps_a_non_2xx_response_to_the_matching_spec_error() {
+        let base_url = spawn_http_test_server();
+        let transport = HttpTransport::new(base_url);
+
+        assert_eq!(
+            transport.call("GET", "/accounts/missing/balance"),
+            Err(SpecError::new("CONST-003", "account not found"))
+        );
+    }
+
+    /// Builds a tiny WAT module implementing [`WasmHost`]'s ABI: its
+    /// `call` ignores whatever request it's handed and always responds
+    /// with the literal `response` text, stored as static data at offset
+    /// 0. Enough to exercise the host's memory read/write plumbing and
+    /// response parsing without needing a real language's request
+    /// decoding logic inside wasm.
+    fn wasm_module_returning(response: &str) -> Vec<u8> {
+        let escaped = response.replace('\\', "\\\\").replace('"', "\\\"");
+        format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (global $next (mut i32) (i32.const 1024))
+                (func (export "alloc") (param $len i32) (result i32)
+                    (local $ptr i32)
+                    (local.set $ptr (global.get $next))
+                    (global.set $next (i32.add (global.get $next) (local.get $len)))
+                    (local.get $ptr))
+                (data (i32.const 0) "{escaped}")
+                (func (export "call") (param $ptr i32) (param $len i32) (result i64)
+                    (i64.or
+                        (i64.shl (i64.extend_i32_u (i32.const 0)) (i64.const 32))
+                        (i64.extend_i32_u (i32.const {len})))))"#,
+            len = response.len(),
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn wasm_host_returns_the_ok_value_a_module_responds_with() {
+        let wasm = wasm_module_returning(r#"{"ok": 42}"#);
+        let mut host = WasmHost::load(&wasm).expect("module exports memory/alloc/call");
+
+        assert_eq!(host.call(&serde_json::json!({"balance": {}})), Ok(serde_json::json!(42)));
+    }
+
+    #[test]
+    fn wasm_host_maps_an_err_response_to_the_matching_spec_error() {
+        let wasm = wasm_module_returning(
+            r#"{"err": {"code": "CONST-002", "requirement": null, "message": "insufficient funds", "details": null}}"#,
+        );
+        let mut host = WasmHost::load(&wasm).expect("module exports memory/alloc/call");
+
+        assert_eq!(
+            host.call(&serde_json::json!({"withdraw": {"amount": 100.0}})),
+            Err(SpecError::new("CONST-002", "insufficient funds"))
+        );
+    }
+
+    #[test]
+    fn wasm_host_reports_a_module_missing_the_abi_as_an_io_error() {
+        let wasm = br#"(module (func (export "not_call")))"#;
+
+        let Err(err) = WasmHost::load(wasm) else {
+            panic!("module has no alloc/call/memory exports");
+        };
+        assert_eq!(err.code, "IO");
+    }
+
+    /// Compiles a hand-written `libspec_shim_*` source file (hand-written
+    /// rather than using [`libspec::codegen::c_abi_shim`]'s output, since
+    /// that generator targets a real spec's prefix/methods and this only
+    /// needs to exercise [`CShimHost`]'s plumbing) into a `cdylib` with
+    /// `rustc`, the same toolchain already building this crate, and
+    /// returns its path.
+    fn compile_c_shim_test_library() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("libspec_c_shim_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create a scratch directory for the test shim");
+
+        let source_path = dir.join("shim.rs");
+        std::fs::write(
+            &source_path,
+            r##"
+use std::ffi::c_void;
+
+#[no_mangle]
+pub extern "C" fn libspec_shim_new() -> *mut c_void {
+    std::ptr::null_mut()
+}
+
+#[no_mangle]
+pub extern "C" fn libspec_shim_free_handle(_handle: *mut c_void) {}
+
+#[no_mangle]
+pub unsafe extern "C" fn libspec_shim_call(
+    _handle: *mut c_void,
+    request_ptr: *const u8,
+    request_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    let request = std::slice::from_raw_parts(request_ptr, request_len);
+    let body: &[u8] = if request == br#"{"method":"withdraw"}"# {
+        br#"{"err": {"code": "CONST-002", "requirement": null, "message": "insufficient funds", "details": null}}"#
+    } else {
+        br#"{"ok": 42}"#
+    };
+    let mut bytes = body.to_vec();
+    *out_len = bytes.len();
+    *out_ptr = bytes.as_mut_ptr();
+    std::mem::forget(bytes);
+    0
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn libspec_shim_free(ptr: *mut u8, len: usize) {
+    drop(Vec::from_raw_parts(ptr, len, len));
+}
+"##,
+        )
+        .expect("write the scratch shim source");
+
+        let library_path = dir.join(format!("libshim{}", std::env::consts::DLL_SUFFIX));
+        let status = std::process::Command::new("rustc")
+            .args(["--crate-type", "cdylib", "-o"])
+            .arg(&library_path)
+            .arg(&source_path)
+            .status()
+            .expect("invoke rustc to build the scratch shim");
+        assert!(status.success(), "rustc failed to build the scratch shim");
+
+        library_path
+    }
+
+    #[test]
+    fn c_shim_host_returns_the_ok_value_a_shim_responds_with() {
+        let library_path = compile_c_shim_test_library();
+        let host = CShimHost::load(&library_path).expect("library exports the shim ABI");
+
+        assert_eq!(host.call(&serde_json::json!({"method": "balance"})), Ok(serde_json::json!(42)));
+    }
+
+    #[test]
+    fn c_shim_host_maps_an_err_response_to_the_matching_spec_error() {
+        let library_path = compile_c_shim_test_library();
+        let host = CShimHost::load(&library_path).expect("library exports the shim ABI");
+
+        assert_eq!(
+            host.call(&serde_json::json!({"method": "withdraw"})),
+            Err(SpecError::new("CONST-002", "insufficient funds"))
+        );
+    }
+
+    #[test]
+    fn c_shim_host_reports_a_library_missing_the_abi_as_an_io_error() {
+        let Err(err) = CShimHost::load(Path::new("/nonexistent/libdoes-not-exist.so")) else {
+            panic!("library does not exist");
+        };
+        assert_eq!(err.code, "IO");
+    }
+
+    #[test]
+    fn spec_env_starts_at_time_zero_and_advances() {
+        let mut env = SpecEnv::new(1);
+        assert_eq!(env.now(), Duration::ZERO);
+        env.advance(Duration::from_secs(30));
+        env.advance(Duration::from_secs(12));
+        assert_eq!(env.now(), Duration::from_secs(42));
+    }
+
+    #[test]
+    fn spec_env_random_is_deterministic_for_a_given_seed() {
+        let mut a = SpecEnv::new(7);
+        let mut b = SpecEnv::new(7);
+        let sequence_a: Vec<f64> = (0..5).map(|_| a.random()).collect();
+        let sequence_b: Vec<f64> = (0..5).map(|_| b.random()).collect();
+        assert_eq!(sequence_a, sequence_b);
+    }
+
+    #[test]
+    fn spec_env_random_differs_across_seeds() {
+        let mut a = SpecEnv::new(7);
+        let mut b = SpecEnv::new(8);
+        assert_ne!(a.random(), b.random());
+    }
+
+    #[test]
+    fn spec_env_random_range_stays_within_bound() {
+        let mut env = SpecEnv::new(3);
+        for _ in 0..100 {
+            assert!(env.random_range(10) < 10);
+        }
+    }
+
+    #[test]
+    fn spec_env_next_id_is_uuid_shaped_and_reproducible() {
+        let mut a = SpecEnv::new(99);
+        let mut b = SpecEnv::new(99);
+        let id_a = a.next_id();
+        let id_b = b.next_id();
+        assert_eq!(id_a, id_b);
+
+        let parts: Vec<&str> = id_a.split('-').collect();
+        assert_eq!(parts.iter().map(|p| p.len()).collect::<Vec<_>>(), vec![8, 4, 4, 4, 12]);
+    }
+
+    #[test]
+    fn spec_env_next_id_changes_on_each_call() {
+        let mut env = SpecEnv::new(99);
+        assert_ne!(env.next_id(), env.next_id());
+    }
+
+    struct RecordedBank;
+
+    impl RecordedBank {
+        fn deposit(&self, amount: f64) -> TestResult {
+            if amount < 0.0 {
+                Err(Box::new(Boom))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn recording_round_trips_through_save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("libspec-harness-replay-test-{:?}.jsonl", std::thread::current().id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut recording = Recording::new();
+        recording.push("deposit", &[("amount", "100")], Ok(()));
+        recording.push("deposit", &[("amount", "-5")], Err("insufficient funds".to_string()));
+        recording.save(&path).unwrap();
+
+        let loaded = Recording::load(&path).unwrap();
+        assert_eq!(loaded, recording);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn replayer_passes_when_every_operation_reproduces_its_recorded_outcome() {
+        let mut recording = Recording::new();
+        recording.push("deposit", &[("amount", "100")], Ok(()));
+        recording.push("deposit", &[("amount", "-5")], Err("insufficient funds".to_string()));
+
+        let mut replayer = Replayer::new();
+        replayer.register("deposit", |bank: &RecordedBank, args| {
+            let amount: f64 = args.iter().find(|(n, _)| n == "amount").unwrap().1.parse()?;
+            bank.deposit(amount)
+        });
+
+        assert_eq!(replayer.replay(&recording, &RecordedBank), ExitCode::SUCCESS);
+    }
+
+    #[test]
+    fn replayer_fails_when_an_outcome_diverges_from_the_recording() {
+        let mut recording = Recording::new();
+        recording.push("deposit", &[("amount", "-5")], Ok(()));
+
+        let mut replayer = Replayer::new();
+        replayer.register("deposit", |bank: &RecordedBank, args| {
+            let amount: f64 = args.iter().find(|(n, _)| n == "amount").unwrap().1.parse()?;
+            bank.deposit(amount)
+        });
+
+        assert_eq!(replayer.replay(&recording, &RecordedBank), ExitCode::FAILURE);
+    }
+
+    #[test]
+    fn replayer_fails_on_an_unregistered_operation() {
+        let mut recording = Recording::new();
+        recording.push("withdraw", &[], Ok(()));
+
+        let replayer: Replayer<RecordedBank> = Replayer::new();
+        assert_eq!(replayer.replay(&recording, &RecordedBank), ExitCode::FAILURE);
+    }
+
+    #[test]
+    fn infers_a_bound_an_argument_never_crossed() {
+        let mut recording = Recording::new();
+        recording.push("deposit", &[("amount", "100")], Ok(()));
+        recording.push("deposit", &[("amount", "5")], Ok(()));
+        recording.push("deposit", &[("amount", "-1")], Err("rejected".into()));
+
+        let proposals = infer_constraints(&recording);
+        assert!(proposals.contains(&ConstraintProposal {
+            operation: "deposit".into(),
+            expr: "amount > 0".into(),
+            support: 2,
+        }));
+    }
+
+    #[test]
+    fn infers_a_relation_between_two_arguments() {
+        let mut recording = Recording::new();
+        recording.push("withdraw", &[("amount", "40"), ("limit", "100")], Ok(()));
+        recording.push("withdraw", &[("amount", "100"), ("limit", "100")], Ok(()));
+
+        let proposals = infer_constraints(&recording);
+        assert!(proposals.contains(&ConstraintProposal {
+            operation: "withdraw".into(),
+            expr: "amount <= limit".into(),
+            support: 2,
+        }));
+    }
+
+    #[test]
+    fn does_not_propose_anything_from_a_single_observation() {
+        let mut recording = Recording::new();
+        recording.push("deposit", &[("amount", "100")], Ok(()));
+
+        assert_eq!(infer_constraints(&recording), vec![]);
+    }
+
+    #[test]
+    fn does_not_propose_a_bound_an_argument_later_violates() {
+        let mut recording = Recording::new();
+        recording.push("deposit", &[("amount", "100")], Ok(()));
+        recording.push("deposit", &[("amount", "-3")], Ok(()));
+
+        let proposals = infer_constraints(&recording);
+        assert!(!proposals.iter().any(|p| p.expr.contains("amount")));
+    }
+
+    fn call(op: &str, amount: &str, outcome: Result<(), String>, start: u64, end: u64) -> ConcurrentCall {
+        ConcurrentCall {
+            operation: RecordedOperation {
+                operation: op.to_string(),
+                arguments: vec![("amount".to_string(), amount.to_string())],
+                outcome,
+            },
+            start: Duration::from_millis(start),
+            end: Duration::from_millis(end),
+        }
+    }
+
+    /// `deposit`/`withdraw` against a plain `f64` balance, the same model
+    /// shape [`Differential`]'s doctest uses — the simplest possible stand-in
+    /// for the spec model a real implementation's history would be checked
+    /// against.
+    fn apply_balance(balance: &mut f64, op: &RecordedOperation) -> Result<(), String> {
+        let amount: f64 = op.arguments[0].1.parse().unwrap();
+        match op.operation.as_str() {
+            "deposit" => {
+                *balance += amount;
+                Ok(())
+            }
+            "withdraw" if amount <= *balance => {
+                *balance -= amount;
+                Ok(())
+            }
+            "withdraw" => Err("insufficient funds".to_string()),
+            other => panic!("unknown operation {other}"),
+        }
+    }
+
+    #[test]
+    fn overlapping_calls_with_a_valid_order_are_linearizable() {
+        // Two overlapping deposits, in either order, leave enough balance
+        // for the withdrawal that starts only once both have finished.
+        let history = [
+            call("deposit", "10", Ok(()), 0, 20),
+            call("deposit", "20", Ok(()), 5, 15),
+            call("withdraw", "25", Ok(()), 25, 30),
+        ];
+
+        let result = check_linearizable(&history, &0.0_f64, apply_balance);
+        assert!(matches!(result, LinearizabilityResult::Linearizable(_)));
+    }
+
+    #[test]
+    fn a_recorded_outcome_real_time_cannot_justify_is_not_linearizable() {
+        // `deposit` finishes well before `withdraw` starts, so real time
+        // forces `deposit` first — but `withdraw` was recorded as
+        // succeeding for more than the deposit alone could cover.
+        let history = [call("deposit", "10", Ok(()), 0, 10), call("withdraw", "100", Ok(()), 20, 30)];
+
+        assert_eq!(check_linearizable(&history, &0.0_f64, apply_balance), LinearizabilityResult::NotLinearizable);
+    }
+
+    #[test]
+    fn a_witnessing_order_respects_real_time() {
+        let history = [call("deposit", "10", Ok(()), 0, 10), call("withdraw", "10", Ok(()), 20, 30)];
+
+        match check_linearizable(&history, &0.0_f64, apply_balance) {
+            LinearizabilityResult::Linearizable(order) => assert_eq!(order, vec![0, 1]),
+            LinearizabilityResult::NotLinearizable => panic!("expected a witnessing order"),
+        }
+    }
+
+    #[test]
+    fn concurrent_recorder_times_calls_from_several_threads() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let recorder = Arc::new(ConcurrentRecorder::new());
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let recorder = Arc::clone(&recorder);
+                thread::spawn(move || {
+                    recorder.record("deposit", &[("amount", "10")], || Ok(())).unwrap();
+                    let _ = i;
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let history = Arc::try_unwrap(recorder).unwrap().into_history();
+        assert_eq!(history.len(), 4);
+        assert!(history.iter().all(|c| c.start <= c.end));
+
+        let result = check_linearizable(&history, &0.0_f64, apply_balance);
+        assert!(matches!(result, LinearizabilityResult::Linearizable(_)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn run_within_budget_reports_completed_when_the_operation_finishes_first() {
+        let outcome = run_within_budget(async { 42 }, Duration::from_millis(50)).await;
+        assert_eq!(outcome, TimeoutOutcome::Completed(42));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn run_within_budget_reports_timed_out_when_the_budget_elapses_first() {
+        let outcome = run_within_budget(
+            async {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                42
+            },
+            Duration::from_millis(10),
+        )
+        .await;
+        assert_eq!(outcome, TimeoutOutcome::TimedOut);
+    }
+}
@@ -0,0 +1,284 @@
+//! Pure, LSP-transport-independent indexing of requirement and constraint
+//! ids across the spec file and any other open document, so `main.rs`'s
+//! message loop can stay a thin translation layer to `lsp_types`
+//! positions. Rebuilt from the spec's raw TOML text on every edit, since
+//! the TOML parser itself discards source positions and we need them back
+//! to answer go-to-definition.
+
+use std::collections::HashMap;
+
+use libspec::spec::SpecDocument;
+
+/// Zero-based line/column, matching the LSP `Position` encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// Where each requirement/constraint id is *defined* in the spec's TOML
+/// text, plus the requirement text to show on hover.
+#[derive(Debug, Default, Clone)]
+pub struct SpecIndex {
+    definitions: HashMap<String, Range>,
+    hover_text: HashMap<String, String>,
+}
+
+impl SpecIndex {
+    /// Builds the definition locations by scanning `spec_text` for
+    /// `id = "..."` and `code = "..."` assignments.
+    pub fn from_spec_text(spec_text: &str) -> Self {
+        let mut definitions = HashMap::new();
+        for (line_no, line) in spec_text.lines().enumerate() {
+            if let Some((id, range)) = parse_id_line(line, line_no as u32) {
+                definitions.entry(id).or_insert(range);
+            }
+        }
+        Self {
+            definitions,
+            hover_text: HashMap::new(),
+        }
+    }
+
+    /// Attaches requirement/constraint text for hover, read from the
+    /// already-parsed document rather than re-scanned from raw text.
+    pub fn with_hover_text(mut self, doc: &SpecDocument) -> Self {
+        self.hover_text = doc
+            .requirements
+            .iter()
+            .map(|r| (r.id.clone(), r.text.clone()))
+            .chain(
+                doc.constraints
+                    .iter()
+                    .map(|c| (c.code.clone(), c.text.clone())),
+            )
+            .collect();
+        self
+    }
+
+    pub fn definition(&self, id: &str) -> Option<Range> {
+        self.definitions.get(id).copied()
+    }
+
+    pub fn hover_text(&self, id: &str) -> Option<&str> {
+        self.hover_text.get(id).map(String::as_str)
+    }
+
+    pub fn is_known(&self, id: &str) -> bool {
+        self.definitions.contains_key(id)
+    }
+
+    pub fn known_ids(&self) -> impl Iterator<Item = &str> {
+        self.definitions.keys().map(String::as_str)
+    }
+}
+
+/// If the cursor at `character` in `line` sits inside the string literal
+/// argument of a `#[covers("..")]` or `#[implements("..")]` attribute,
+/// returns what's been typed inside the quotes so far (used to filter
+/// completion candidates down to matching requirement ids). Returns
+/// `None` outside such an attribute, or once the string has already been
+/// closed before the cursor.
+pub fn covers_attr_prefix(line: &str, character: u32) -> Option<String> {
+    let chars: Vec<char> = line.chars().collect();
+    let cursor = (character as usize).min(chars.len());
+    let before_cursor: String = chars[..cursor].iter().collect();
+
+    let open_quote = ["covers(\"", "implements(\""]
+        .iter()
+        .filter_map(|marker| before_cursor.rfind(marker).map(|idx| idx + marker.len()))
+        .max()?;
+
+    let typed = &before_cursor[open_quote..];
+    if typed.contains('"') {
+        return None;
+    }
+    Some(typed.to_string())
+}
+
+/// Scans `line` for an `id = "..."` or `code = "..."` TOML assignment and
+/// returns the id/code plus the `Range` of just the quoted value.
+fn parse_id_line(line: &str, line_no: u32) -> Option<(String, Range)> {
+    let eq = line.find('=')?;
+    let key = line[..eq].trim();
+    if key != "id" && key != "code" {
+        return None;
+    }
+    let rest = &line[eq + 1..];
+    let quote_start = rest.find('"')?;
+    let value_start = quote_start + 1;
+    let value_end = value_start + rest[value_start..].find('"')?;
+    let id = rest[value_start..value_end].to_string();
+    if id.is_empty() {
+        return None;
+    }
+
+    let rest_offset = eq + 1;
+    let start_col = (rest_offset + value_start) as u32;
+    let end_col = (rest_offset + value_end) as u32;
+    Some((
+        id,
+        Range {
+            start: Position {
+                line: line_no,
+                character: start_col,
+            },
+            end: Position {
+                line: line_no,
+                character: end_col,
+            },
+        },
+    ))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Finds the identifier-like token (letters, digits, `-`, `_`) touching
+/// `position` in `text`, if any — used to resolve hover/definition
+/// requests regardless of what kind of file the cursor is in.
+pub fn word_at(text: &str, position: Position) -> Option<String> {
+    let line = text.lines().nth(position.line as usize)?;
+    let chars: Vec<char> = line.chars().collect();
+    let idx = (position.character as usize).min(chars.len());
+
+    let mut start = idx;
+    while start > 0 && is_word_char(chars[start - 1]) {
+        start -= 1;
+    }
+    let mut end = idx;
+    while end < chars.len() && is_word_char(chars[end]) {
+        end += 1;
+    }
+    if start == end {
+        return None;
+    }
+    Some(chars[start..end].iter().collect())
+}
+
+/// Every position in `text` where `id` appears as a whole word (not part
+/// of a longer identifier) — used for find-references across any open
+/// document, spec or annotated source alike.
+pub fn find_references(text: &str, id: &str) -> Vec<Range> {
+    if id.is_empty() {
+        return Vec::new();
+    }
+    let mut ranges = Vec::new();
+    for (line_no, line) in text.lines().enumerate() {
+        let mut search_from = 0;
+        while let Some(rel) = line[search_from..].find(id) {
+            let start = search_from + rel;
+            let end = start + id.len();
+            let before_ok = line[..start].chars().last().map(|c| !is_word_char(c)).unwrap_or(true);
+            let after_ok = line[end..].chars().next().map(|c| !is_word_char(c)).unwrap_or(true);
+            if before_ok && after_ok {
+                ranges.push(Range {
+                    start: Position {
+                        line: line_no as u32,
+                        character: start as u32,
+                    },
+                    end: Position {
+                        line: line_no as u32,
+                        character: end as u32,
+                    },
+                });
+            }
+            search_from = end.max(search_from + 1);
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = "[[requirement]]\nid = \"REQ-001\"\ntext = \"does a thing\"\n";
+
+    #[test]
+    fn finds_the_definition_of_a_requirement_id() {
+        let index = SpecIndex::from_spec_text(SPEC);
+        let range = index.definition("REQ-001").unwrap();
+        assert_eq!(range.start.line, 1);
+    }
+
+    #[test]
+    fn has_no_definition_for_an_unknown_id() {
+        let index = SpecIndex::from_spec_text(SPEC);
+        assert!(index.definition("REQ-999").is_none());
+        assert!(!index.is_known("REQ-999"));
+    }
+
+    #[test]
+    fn exposes_requirement_text_for_hover() {
+        let doc = SpecDocument::from_toml_str(SPEC).unwrap();
+        let index = SpecIndex::from_spec_text(SPEC).with_hover_text(&doc);
+        assert_eq!(index.hover_text("REQ-001"), Some("does a thing"));
+    }
+
+    #[test]
+    fn word_at_finds_the_id_touching_the_cursor() {
+        let text = "// see REQ-001 for details";
+        let word = word_at(text, Position { line: 0, character: 9 });
+        assert_eq!(word, Some("REQ-001".to_string()));
+    }
+
+    #[test]
+    fn word_at_returns_none_on_whitespace() {
+        let text = "// see REQ-001";
+        assert_eq!(word_at(text, Position { line: 0, character: 2 }), None);
+    }
+
+    #[test]
+    fn find_references_matches_whole_words_only() {
+        let text = "see REQ-001 but not REQ-0010 or XREQ-001";
+        let refs = find_references(text, "REQ-001");
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].start.character, 4);
+    }
+
+    #[test]
+    fn find_references_finds_every_occurrence_across_lines() {
+        let text = "REQ-001 first\nsecond line\nREQ-001 again";
+        let refs = find_references(text, "REQ-001");
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[1].start.line, 2);
+    }
+
+    #[test]
+    fn covers_attr_prefix_returns_what_was_typed_so_far() {
+        let line = "    #[covers(\"RE";
+        assert_eq!(
+            covers_attr_prefix(line, line.len() as u32),
+            Some("RE".to_string())
+        );
+    }
+
+    #[test]
+    fn covers_attr_prefix_also_matches_implements() {
+        let line = "#[implements(\"REQ";
+        assert_eq!(
+            covers_attr_prefix(line, line.len() as u32),
+            Some("REQ".to_string())
+        );
+    }
+
+    #[test]
+    fn covers_attr_prefix_is_none_outside_an_attribute() {
+        let line = "fn handles_REQ_001() {}";
+        assert_eq!(covers_attr_prefix(line, line.len() as u32), None);
+    }
+
+    #[test]
+    fn covers_attr_prefix_is_none_once_the_string_is_closed() {
+        let line = "#[covers(\"REQ-001\")]";
+        assert_eq!(covers_attr_prefix(line, line.len() as u32), None);
+    }
+}
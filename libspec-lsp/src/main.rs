@@ -0,0 +1,359 @@
+//! `libspec-lsp`: a minimal language server for spec files, speaking LSP
+//! over stdio via `lsp-server`. Offers go-to-definition and
+//! find-references on requirement/constraint ids across the spec file and
+//! any other open document (so a comment like `// see REQ-003` resolves
+//! too), hover showing a requirement or constraint's text and coverage
+//! status, completion of known ids inside `#[covers("..")]` /
+//! `#[implements("..")]` attributes, and diagnostics from [`lint::lint`].
+//! All id-indexing logic lives in [`index`], kept free of the LSP
+//! transport so it's unit-testable on its own; this file is just the thin
+//! translation layer between `lsp_types`/`lsp_server` and that index.
+
+mod index;
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use lsp_server::{Connection, Message, Notification as ServerNotification, Request as ServerRequest, RequestId, Response};
+use lsp_types::notification::{DidChangeTextDocument, DidOpenTextDocument, Notification, PublishDiagnostics};
+use lsp_types::request::{Completion, GotoDefinition, HoverRequest, References, Request};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionParams, CompletionResponse, Diagnostic,
+    DiagnosticSeverity, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents,
+    HoverParams, HoverProviderCapability, InitializeParams, Location, MarkedString, OneOf,
+    Position as LspPosition, PublishDiagnosticsParams, Range as LspRange, ReferenceParams,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Uri,
+};
+
+use index::{Position, Range, SpecIndex};
+use libspec::lint;
+use libspec::query::QueryEngine;
+use libspec::spec::SpecDocument;
+use libspec::trace::{self, CoverageMatrix};
+
+fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(lsp_types::CompletionOptions {
+            trigger_characters: Some(vec!["\"".to_string()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let init_params = connection.initialize(serde_json::to_value(capabilities)?)?;
+    let _params: InitializeParams = serde_json::from_value(init_params)?;
+
+    main_loop(&connection)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Every open document's text, keyed by URI, plus the most recently built
+/// index and parsed [`SpecDocument`] for the spec file. Only one spec file
+/// is tracked at a time (the one the client most recently opened that
+/// parses as a valid spec) since that's the common case for a
+/// single-spec workspace. `query` memoizes the derived `spec_doc`/`index`
+/// pair against the `spec_text` input (see [`on_document_changed`]) so a
+/// request that doesn't change the spec text — a `didChange` whose
+/// content happens to match what's already cached — doesn't pay to
+/// re-parse and re-index it.
+struct State {
+    documents: HashMap<Uri, String>,
+    spec_uri: Option<Uri>,
+    spec_doc: Option<SpecDocument>,
+    index: SpecIndex,
+    query: QueryEngine,
+}
+
+fn main_loop(connection: &Connection) -> Result<(), Box<dyn Error + Sync + Send>> {
+    let mut state = State {
+        documents: HashMap::new(),
+        spec_uri: None,
+        spec_doc: None,
+        index: SpecIndex::default(),
+        query: QueryEngine::new(),
+    };
+
+    for msg in &connection.receiver {
+        match msg {
+            Message::Request(req) => {
+                if connection.handle_shutdown(&req)? {
+                    return Ok(());
+                }
+                handle_request(connection, &mut state, req)?;
+            }
+            Message::Notification(not) => handle_notification(connection, &mut state, not)?,
+            Message::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn handle_notification(
+    connection: &Connection,
+    state: &mut State,
+    not: ServerNotification,
+) -> Result<(), Box<dyn Error + Sync + Send>> {
+    match not.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params: lsp_types::DidOpenTextDocumentParams = serde_json::from_value(not.params)?;
+            let uri = params.text_document.uri;
+            let text = params.text_document.text;
+            on_document_changed(connection, state, uri, text)?;
+        }
+        DidChangeTextDocument::METHOD => {
+            let params: lsp_types::DidChangeTextDocumentParams = serde_json::from_value(not.params)?;
+            if let Some(change) = params.content_changes.into_iter().next_back() {
+                on_document_changed(connection, state, params.text_document.uri, change.text)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Re-indexes `uri`'s new `text`, and — if it parses as a spec document —
+/// rebuilds the requirement/constraint index and republishes lint
+/// diagnostics for it. The parsed doc and index are memoized in
+/// `state.query` against the `spec_text` input, which only moves to a
+/// new revision when `text` actually differs from what's already
+/// tracked for `uri` — so a `didChange` that reports back the same
+/// content (a cursor move under full-document sync, say) skips
+/// re-parsing and re-indexing entirely.
+fn on_document_changed(
+    connection: &Connection,
+    state: &mut State,
+    uri: Uri,
+    text: String,
+) -> Result<(), Box<dyn Error + Sync + Send>> {
+    if state.documents.get(&uri) != Some(&text) {
+        state.query.set_input("spec_text", text.clone());
+    }
+
+    let parsed: Option<SpecDocument> = state
+        .query
+        .query("spec_doc", &["spec_text"], || SpecDocument::from_toml_str(&text).ok());
+
+    if let Some(doc) = parsed {
+        let index = state
+            .query
+            .query("spec_index", &["spec_text"], || SpecIndex::from_spec_text(&text).with_hover_text(&doc));
+        state.index = index;
+        state.spec_uri = Some(uri.clone());
+        publish_lint_diagnostics(connection, &uri, &text, &doc)?;
+        state.spec_doc = Some(doc);
+    }
+    state.documents.insert(uri, text);
+    Ok(())
+}
+
+/// A requirement's coverage summary for hover text, mirroring the wording
+/// [`CoverageMatrix::to_terminal`] uses in the `report` subcommand.
+fn coverage_status(doc: &SpecDocument, id: &str) -> Option<String> {
+    let records = trace::read_records();
+    let matrix = CoverageMatrix::build(doc, &records);
+    let row = matrix.rows.iter().find(|row| row.requirement == id)?;
+    Some(if row.tests.is_empty() {
+        "(untested)".to_string()
+    } else {
+        row.tests.join(", ")
+    })
+}
+
+fn publish_lint_diagnostics(
+    connection: &Connection,
+    uri: &Uri,
+    text: &str,
+    doc: &SpecDocument,
+) -> Result<(), Box<dyn Error + Sync + Send>> {
+    let diagnostics = lint::lint(doc)
+        .into_iter()
+        .map(|issue| {
+            let range = issue_range(text, &issue.message);
+            Diagnostic::new(
+                to_lsp_range(range),
+                Some(DiagnosticSeverity::WARNING),
+                None,
+                Some("libspec-lsp".to_string()),
+                format!("[{}] {}", issue.rule, issue.message),
+                None,
+                None,
+            )
+        })
+        .collect();
+
+    let params = PublishDiagnosticsParams {
+        uri: uri.clone(),
+        diagnostics,
+        version: None,
+    };
+    connection.sender.send(Message::Notification(ServerNotification::new(
+        PublishDiagnostics::METHOD.to_string(),
+        params,
+    )))?;
+    Ok(())
+}
+
+/// Best-effort: points a lint issue's diagnostic at the first known id
+/// mentioned in its message, falling back to the top of the file when the
+/// message doesn't name one (or names one that isn't a definition, e.g. a
+/// duplicate's second occurrence).
+fn issue_range(text: &str, message: &str) -> Range {
+    for word in message.split(|c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '_') {
+        if let Some(range) = index::find_references(text, word).into_iter().next() {
+            return range;
+        }
+    }
+    Range {
+        start: Position { line: 0, character: 0 },
+        end: Position { line: 0, character: 0 },
+    }
+}
+
+fn handle_request(
+    connection: &Connection,
+    state: &mut State,
+    req: ServerRequest,
+) -> Result<(), Box<dyn Error + Sync + Send>> {
+    match req.method.as_str() {
+        GotoDefinition::METHOD => {
+            let (id, params): (RequestId, GotoDefinitionParams) =
+                req.extract(GotoDefinition::METHOD)?;
+            let response = goto_definition(state, &params);
+            respond(connection, id, response)?;
+        }
+        HoverRequest::METHOD => {
+            let (id, params): (RequestId, HoverParams) = req.extract(HoverRequest::METHOD)?;
+            let response = hover(state, &params);
+            respond(connection, id, response)?;
+        }
+        References::METHOD => {
+            let (id, params): (RequestId, ReferenceParams) = req.extract(References::METHOD)?;
+            let response = references(state, &params);
+            respond(connection, id, response)?;
+        }
+        Completion::METHOD => {
+            let (id, params): (RequestId, CompletionParams) = req.extract(Completion::METHOD)?;
+            let response = completion(state, &params);
+            respond(connection, id, response)?;
+        }
+        _ => {
+            connection.sender.send(Message::Response(Response::new_err(
+                req.id,
+                lsp_server::ErrorCode::MethodNotFound as i32,
+                format!("unhandled method: {}", req.method),
+            )))?;
+        }
+    }
+    Ok(())
+}
+
+fn respond<T: serde::Serialize>(
+    connection: &Connection,
+    id: RequestId,
+    result: T,
+) -> Result<(), Box<dyn Error + Sync + Send>> {
+    connection.sender.send(Message::Response(Response::new_ok(id, result)))?;
+    Ok(())
+}
+
+fn word_under_cursor(state: &State, uri: &Uri, position: LspPosition) -> Option<String> {
+    let text = state.documents.get(uri)?;
+    index::word_at(text, from_lsp_position(position))
+}
+
+fn goto_definition(state: &State, params: &GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+    let uri = &params.text_document_position_params.text_document.uri;
+    let position = params.text_document_position_params.position;
+    let word = word_under_cursor(state, uri, position)?;
+    let spec_uri = state.spec_uri.as_ref()?;
+    let range = state.index.definition(&word)?;
+    Some(GotoDefinitionResponse::Scalar(Location::new(
+        spec_uri.clone(),
+        to_lsp_range(range),
+    )))
+}
+
+fn hover(state: &State, params: &HoverParams) -> Option<Hover> {
+    let uri = &params.text_document_position_params.text_document.uri;
+    let position = params.text_document_position_params.position;
+    let word = word_under_cursor(state, uri, position)?;
+    let text = state.index.hover_text(&word)?;
+
+    let mut contents = text.to_string();
+    if let Some(doc) = &state.spec_doc {
+        if let Some(status) = coverage_status(doc, &word) {
+            contents.push_str("\n\ncoverage: ");
+            contents.push_str(&status);
+        }
+    }
+
+    Some(Hover {
+        contents: HoverContents::Scalar(MarkedString::String(contents)),
+        range: None,
+    })
+}
+
+fn completion(state: &State, params: &CompletionParams) -> Option<CompletionResponse> {
+    let uri = &params.text_document_position.text_document.uri;
+    let position = params.text_document_position.position;
+    let text = state.documents.get(uri)?;
+    let line = text.lines().nth(position.line as usize)?;
+    let prefix = index::covers_attr_prefix(line, position.character)?;
+
+    let items = state
+        .index
+        .known_ids()
+        .filter(|id| id.starts_with(&prefix))
+        .map(|id| CompletionItem {
+            label: id.to_string(),
+            kind: Some(CompletionItemKind::VALUE),
+            detail: state.index.hover_text(id).map(str::to_string),
+            ..Default::default()
+        })
+        .collect();
+    Some(CompletionResponse::Array(items))
+}
+
+fn references(state: &State, params: &ReferenceParams) -> Option<Vec<Location>> {
+    let uri = &params.text_document_position.text_document.uri;
+    let position = params.text_document_position.position;
+    let word = word_under_cursor(state, uri, position)?;
+    if !state.index.is_known(&word) {
+        return None;
+    }
+
+    let mut locations = Vec::new();
+    for (doc_uri, text) in &state.documents {
+        for range in index::find_references(text, &word) {
+            locations.push(Location::new(doc_uri.clone(), to_lsp_range(range)));
+        }
+    }
+    Some(locations)
+}
+
+fn from_lsp_position(position: LspPosition) -> Position {
+    Position {
+        line: position.line,
+        character: position.character,
+    }
+}
+
+fn to_lsp_position(position: Position) -> LspPosition {
+    LspPosition {
+        line: position.line,
+        character: position.character,
+    }
+}
+
+fn to_lsp_range(range: Range) -> LspRange {
+    LspRange {
+        start: to_lsp_position(range.start),
+        end: to_lsp_position(range.end),
+    }
+}
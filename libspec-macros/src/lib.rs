@@ -0,0 +1,465 @@
+//! `#[spec_api]`: checks a hand-written trait against a loaded spec at
+//! compile time, emitting compile errors for requirements with no matching
+//! method and methods the spec has nothing to say about.
+//!
+//! `#[spec_requires]`/`#[spec_ensures]`: weave a constraint's `expr` into a
+//! function as a pre/postcondition, compile-time-checked against its
+//! parameters, instead of a hand-written guard.
+//!
+//! `#[spec_doc]`: pulls a requirement's text from a loaded spec into a
+//! `#[doc]` attribute on a hand-written item.
+
+use libspec::codegen::method_name;
+use libspec::spec::{
+    Comparison, Constraint, ConstraintExpr, RelOp, Requirement, Severity, SpecDocument, Term,
+};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, FnArg, ItemFn, ItemTrait, LitStr, Pat, Token, TraitItem};
+
+/// `#[spec_api("spec.toml")]` on a trait loads the spec from `spec.toml`
+/// (resolved relative to `CARGO_MANIFEST_DIR`) and compares its
+/// requirements against the trait's methods, using the same
+/// [`method_name`] heuristic the `codegen` backends use to turn a
+/// requirement into a method name. It emits a `compile_error!` for every
+/// requirement with no matching method and every method the spec has
+/// nothing to say about; the trait itself passes through unchanged.
+///
+/// Signature checking (parameter/return types) isn't implemented: the spec
+/// doesn't model an operation's parameter or return types yet, only its
+/// requirement text, so there's nothing to check a signature against.
+#[proc_macro_attribute]
+pub fn spec_api(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let path_lit = parse_macro_input!(attr as LitStr);
+    let item_trait = parse_macro_input!(item as ItemTrait);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let spec_path = std::path::Path::new(&manifest_dir).join(path_lit.value());
+
+    let doc = match SpecDocument::load_toml_file(&spec_path) {
+        Ok(doc) => doc,
+        Err(e) => {
+            let message = format!(
+                "spec_api: failed to load spec at {}: {e}",
+                spec_path.display()
+            );
+            return quote! {
+                #item_trait
+                const _: () = { ::core::compile_error!(#message); };
+            }
+            .into();
+        }
+    };
+
+    let expected: Vec<(String, &str)> = doc
+        .requirements
+        .iter()
+        .map(|req| (method_name(req), req.id.as_str()))
+        .collect();
+
+    let actual: Vec<String> = item_trait
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            TraitItem::Fn(f) => Some(f.sig.ident.to_string()),
+            _ => None,
+        })
+        .collect();
+
+    let trait_name = item_trait.ident.to_string();
+    let mut errors = Vec::new();
+
+    for (name, req_id) in &expected {
+        if !actual.contains(name) {
+            errors.push(format!(
+                "spec_api: trait `{trait_name}` has no method `{name}` for requirement `{req_id}`"
+            ));
+        }
+    }
+
+    for name in &actual {
+        if !expected.iter().any(|(expected_name, _)| expected_name == name) {
+            errors.push(format!(
+                "spec_api: trait `{trait_name}` method `{name}` has no matching requirement in the spec"
+            ));
+        }
+    }
+
+    let error_consts = errors.iter().map(|message| {
+        quote! { const _: () = { ::core::compile_error!(#message); }; }
+    });
+
+    quote! {
+        #item_trait
+        #(#error_consts)*
+    }
+    .into()
+}
+
+/// `#[covers("REQ-004", "REQ-005")]` on a test function records, every time
+/// the test runs, a `{"kind":"covers", ...}` line to the
+/// [`libspec::trace`] artifact for each requirement id given — the
+/// traceability link that `// Test REQ-004` used to only leave in a
+/// comment for a human to notice.
+#[proc_macro_attribute]
+pub fn covers(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace_attribute("covers", attr, item)
+}
+
+/// `#[implements("REQ-004")]` on an implementation function mirrors
+/// `#[covers]` on the implementation side: every call records a
+/// `{"kind":"implements", ...}` line to the [`libspec::trace`] artifact, so
+/// [`libspec::trace::coverage_gaps`] can report requirements with a test
+/// but no implementation, or an implementation but no test.
+#[proc_macro_attribute]
+pub fn implements(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace_attribute("implements", attr, item)
+}
+
+/// `#[spec_requires("spec.toml", "CONST-002")]` on a function weaves
+/// `CONST-002`'s `expr` in as a precondition: the same condition
+/// [`rust_guard`](libspec::codegen::rust_guard) would generate as a
+/// standalone `check_const_002` function, checked against this function's
+/// own parameters before its body runs, instead of the implementation
+/// having to call a separately generated check by hand.
+///
+/// The function must return `Result<_, ::libspec::error::SpecError>`: a
+/// violation is checked through [`libspec::runtime::enforce`], same as the
+/// generated check, so whether it returns `Err` before the body runs
+/// depends on the constraint's severity and that severity's current
+/// [`EnforcementMode`](libspec::runtime::EnforcementMode) rather than
+/// always failing the call.
+///
+/// `expr`'s bare identifiers must each name one of the function's
+/// parameters — a compile error names the one that doesn't. Calls in
+/// `expr` (e.g. `balance(account)`) aren't supported here, since there's
+/// no closure parameter this macro could plausibly supply; use
+/// `rust_guard`'s generated function for those instead.
+#[proc_macro_attribute]
+pub fn spec_requires(attr: TokenStream, item: TokenStream) -> TokenStream {
+    contract_attribute(ContractMode::Requires, attr, item)
+}
+
+/// `#[spec_ensures("spec.toml", "CONST-003")]` mirrors [`spec_requires`]
+/// on the postcondition side: the check runs after the function body
+/// instead of before it, against the same parameters — `expr` can't
+/// reference the return value, since the spec doesn't model one yet.
+#[proc_macro_attribute]
+pub fn spec_ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
+    contract_attribute(ContractMode::Ensures, attr, item)
+}
+
+/// `#[spec_doc("spec.toml", "REQ-004")]` on a hand-written item injects a
+/// `#[doc = "REQ-004: {text}"]` attribute built from the requirement's own
+/// text, so the item's rustdoc carries the contract's prose straight from
+/// the spec instead of a comment a reviewer has to keep in sync by hand.
+///
+/// Unlike [`spec_api`], which checks an entire trait's methods against the
+/// spec, this attaches one requirement's text to one item — a function,
+/// struct, enum, trait, type alias, const, static, module, impl, or union.
+/// Any other item kind is a compile error naming it as unsupported.
+#[proc_macro_attribute]
+pub fn spec_doc(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr with Punctuated::<LitStr, Token![,]>::parse_terminated);
+    let mut parsed_item = parse_macro_input!(item as syn::Item);
+
+    let [path_lit, id_lit] = args.iter().collect::<Vec<_>>()[..] else {
+        let message = r#"spec_doc expects exactly two arguments: #[spec_doc("spec.toml", "REQ-ID")]"#.to_string();
+        return quote! {
+            #parsed_item
+            const _: () = { ::core::compile_error!(#message); };
+        }
+        .into();
+    };
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let spec_path = std::path::Path::new(&manifest_dir).join(path_lit.value());
+
+    let requirement = match load_requirement(&spec_path, &id_lit.value()) {
+        Ok(requirement) => requirement,
+        Err(message) => {
+            return quote! {
+                #parsed_item
+                const _: () = { ::core::compile_error!(#message); };
+            }
+            .into();
+        }
+    };
+
+    let attrs = match item_attrs(&mut parsed_item) {
+        Some(attrs) => attrs,
+        None => {
+            let message = "spec_doc: unsupported item kind; expected a fn, struct, enum, trait, type alias, const, static, mod, impl, or union".to_string();
+            return quote! {
+                #parsed_item
+                const _: () = { ::core::compile_error!(#message); };
+            }
+            .into();
+        }
+    };
+
+    let doc = format!("{}: {}", requirement.id, requirement.text);
+    attrs.insert(0, syn::parse_quote!(#[doc = #doc]));
+
+    quote! { #parsed_item }.into()
+}
+
+fn load_requirement(spec_path: &std::path::Path, id: &str) -> Result<Requirement, String> {
+    let doc = SpecDocument::load_toml_file(spec_path)
+        .map_err(|e| format!("failed to load spec at {}: {e}", spec_path.display()))?;
+    doc.requirements
+        .into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| format!("no requirement `{id}` in {}", spec_path.display()))
+}
+
+/// The `&mut Vec<Attribute>` of `item`'s common kinds, so [`spec_doc`] can
+/// prepend a `#[doc]` attribute regardless of which one it's attached to.
+/// `syn::Item` has no generic attribute accessor, so this matches out the
+/// kinds worth documenting; anything else (e.g. `extern` blocks, `use`) is
+/// `None` and left to the caller to reject.
+fn item_attrs(item: &mut syn::Item) -> Option<&mut Vec<syn::Attribute>> {
+    match item {
+        syn::Item::Fn(i) => Some(&mut i.attrs),
+        syn::Item::Struct(i) => Some(&mut i.attrs),
+        syn::Item::Enum(i) => Some(&mut i.attrs),
+        syn::Item::Trait(i) => Some(&mut i.attrs),
+        syn::Item::Type(i) => Some(&mut i.attrs),
+        syn::Item::Const(i) => Some(&mut i.attrs),
+        syn::Item::Static(i) => Some(&mut i.attrs),
+        syn::Item::Mod(i) => Some(&mut i.attrs),
+        syn::Item::Impl(i) => Some(&mut i.attrs),
+        syn::Item::Union(i) => Some(&mut i.attrs),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ContractMode {
+    Requires,
+    Ensures,
+}
+
+fn contract_attribute(mode: ContractMode, attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr with Punctuated::<LitStr, Token![,]>::parse_terminated);
+    let func = parse_macro_input!(item as ItemFn);
+
+    let macro_name = match mode {
+        ContractMode::Requires => "spec_requires",
+        ContractMode::Ensures => "spec_ensures",
+    };
+
+    let [path_lit, code_lit] = args.iter().collect::<Vec<_>>()[..] else {
+        let message = format!(r#"{macro_name} expects exactly two arguments: #[{macro_name}("spec.toml", "CODE")]"#);
+        return quote! {
+            #func
+            const _: () = { ::core::compile_error!(#message); };
+        }
+        .into();
+    };
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let spec_path = std::path::Path::new(&manifest_dir).join(path_lit.value());
+    let code = code_lit.value();
+
+    let constraint = match load_constraint(&spec_path, &code) {
+        Ok(constraint) => constraint,
+        Err(message) => {
+            return quote! {
+                #func
+                const _: () = { ::core::compile_error!(#message); };
+            }
+            .into();
+        }
+    };
+
+    let cond = match constraint_condition(&func, &constraint, macro_name) {
+        Ok(cond) => cond,
+        Err(message) => {
+            return quote! {
+                #func
+                const _: () = { ::core::compile_error!(#message); };
+            }
+            .into();
+        }
+    };
+
+    let check = check_block(&constraint, &cond);
+
+    let mut func = func;
+    let block = func.block.clone();
+    *func.block = match mode {
+        ContractMode::Requires => syn::parse_quote! {{
+            #check
+            #block
+        }},
+        ContractMode::Ensures => syn::parse_quote! {{
+            let __spec_ensures_result = (move || #block)();
+            #check
+            __spec_ensures_result
+        }},
+    };
+
+    quote! { #func }.into()
+}
+
+fn load_constraint(spec_path: &std::path::Path, code: &str) -> Result<Constraint, String> {
+    let doc = SpecDocument::load_toml_file(spec_path)
+        .map_err(|e| format!("failed to load spec at {}: {e}", spec_path.display()))?;
+    doc.constraints
+        .into_iter()
+        .find(|c| c.code == code)
+        .ok_or_else(|| format!("no constraint `{code}` in {}", spec_path.display()))
+}
+
+/// Parses `constraint`'s `expr`, checks every bare identifier in it names
+/// one of `func`'s parameters, and renders it as a Rust boolean
+/// expression (built as tokens directly, not through [`ConstraintExpr`]'s
+/// `Display`, so a bare number like `0` in `expr` becomes the `0f64`
+/// literal an `f64` parameter needs rather than an untyped integer
+/// literal). Rejects constraints with no `expr`, an unparseable one, or
+/// one containing a call (see [`spec_requires`]).
+fn constraint_condition(
+    func: &ItemFn,
+    constraint: &Constraint,
+    macro_name: &str,
+) -> Result<proc_macro2::TokenStream, String> {
+    let code = &constraint.code;
+    let expr_src = constraint
+        .expr
+        .as_deref()
+        .ok_or_else(|| format!("{macro_name}: constraint `{code}` has no `expr`"))?;
+    let expr = ConstraintExpr::parse(expr_src)
+        .map_err(|e| format!("{macro_name}: constraint `{code}`'s expr failed to parse: {e}"))?;
+
+    if expr.comparisons().iter().any(|c| has_call(&c.lhs) || has_call(&c.rhs)) {
+        return Err(format!(
+            "{macro_name}: constraint `{code}`'s expr calls a function, which isn't supported on an attribute macro"
+        ));
+    }
+
+    let params: Vec<String> = func
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(pat_type) => match &*pat_type.pat {
+                Pat::Ident(ident) => Some(ident.ident.to_string()),
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+
+    for ident in expr.idents() {
+        if !params.iter().any(|p| p == ident) {
+            return Err(format!(
+                "{macro_name}: constraint `{code}`'s expr uses `{ident}`, which isn't a parameter of this function"
+            ));
+        }
+    }
+
+    Ok(expr_to_tokens(&expr))
+}
+
+fn has_call(term: &Term) -> bool {
+    match term {
+        Term::Number(_) | Term::Ident(_) => false,
+        Term::Call(..) => true,
+        Term::Add(a, b) | Term::Sub(a, b) | Term::Mul(a, b) | Term::Div(a, b) => has_call(a) || has_call(b),
+    }
+}
+
+fn expr_to_tokens(expr: &ConstraintExpr) -> proc_macro2::TokenStream {
+    match expr {
+        ConstraintExpr::Compare(c) => comparison_to_tokens(c),
+        ConstraintExpr::And(a, b) => {
+            let (a, b) = (expr_to_tokens(a), expr_to_tokens(b));
+            quote! { (#a) && (#b) }
+        }
+        ConstraintExpr::Or(a, b) => {
+            let (a, b) = (expr_to_tokens(a), expr_to_tokens(b));
+            quote! { (#a) || (#b) }
+        }
+    }
+}
+
+fn comparison_to_tokens(comparison: &Comparison) -> proc_macro2::TokenStream {
+    let lhs = term_to_tokens(&comparison.lhs);
+    let rhs = term_to_tokens(&comparison.rhs);
+    match comparison.op {
+        RelOp::Gt => quote! { (#lhs) > (#rhs) },
+        RelOp::Lt => quote! { (#lhs) < (#rhs) },
+        RelOp::Ge => quote! { (#lhs) >= (#rhs) },
+        RelOp::Le => quote! { (#lhs) <= (#rhs) },
+        RelOp::Eq => quote! { (#lhs) == (#rhs) },
+        RelOp::Ne => quote! { (#lhs) != (#rhs) },
+    }
+}
+
+fn term_to_tokens(term: &Term) -> proc_macro2::TokenStream {
+    match term {
+        Term::Number(n) => {
+            let lit = proc_macro2::Literal::f64_suffixed(*n);
+            quote! { #lit }
+        }
+        Term::Ident(name) => {
+            let ident = syn::Ident::new(name, proc_macro2::Span::call_site());
+            quote! { #ident }
+        }
+        Term::Call(..) => unreachable!("calls are rejected in constraint_condition before this runs"),
+        Term::Add(a, b) => {
+            let (a, b) = (term_to_tokens(a), term_to_tokens(b));
+            quote! { (#a) + (#b) }
+        }
+        Term::Sub(a, b) => {
+            let (a, b) = (term_to_tokens(a), term_to_tokens(b));
+            quote! { (#a) - (#b) }
+        }
+        Term::Mul(a, b) => {
+            let (a, b) = (term_to_tokens(a), term_to_tokens(b));
+            quote! { (#a) * (#b) }
+        }
+        Term::Div(a, b) => {
+            let (a, b) = (term_to_tokens(a), term_to_tokens(b));
+            quote! { (#a) / (#b) }
+        }
+    }
+}
+
+fn check_block(constraint: &Constraint, cond: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let code = &constraint.code;
+    let message = &constraint.text;
+    let severity = match constraint.severity {
+        Severity::Error => quote! { ::libspec::spec::Severity::Error },
+        Severity::Warning => quote! { ::libspec::spec::Severity::Warning },
+        Severity::Advisory => quote! { ::libspec::spec::Severity::Advisory },
+    };
+
+    quote! {
+        ::libspec::runtime::enforce(#code, #message, #severity, !(#cond))?;
+    }
+}
+
+fn trace_attribute(kind: &str, attr: TokenStream, item: TokenStream) -> TokenStream {
+    let ids = parse_macro_input!(attr with Punctuated::<LitStr, Token![,]>::parse_terminated);
+    let mut func = parse_macro_input!(item as ItemFn);
+
+    let fn_name = func.sig.ident.to_string();
+    let record_calls: Vec<_> = ids
+        .iter()
+        .map(|id| {
+            let id = id.value();
+            quote! { ::libspec::trace::record(#kind, #fn_name, #id); }
+        })
+        .collect();
+
+    let block = &func.block;
+    *func.block = syn::parse_quote! {{
+        #(#record_calls)*
+        #block
+    }};
+
+    quote! { #func }.into()
+}
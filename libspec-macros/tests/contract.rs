@@ -0,0 +1,38 @@
+use libspec::error::SpecError;
+use libspec_macros::{spec_ensures, spec_requires};
+
+struct Bank;
+
+impl Bank {
+    #[spec_requires("tests/fixtures/contract_spec.toml", "CONST-001")]
+    fn deposit(&self, amount: f64) -> Result<f64, SpecError> {
+        Ok(amount)
+    }
+
+    #[spec_ensures("tests/fixtures/contract_spec.toml", "CONST-001")]
+    fn withdraw(&self, amount: f64) -> Result<f64, SpecError> {
+        Ok(-amount)
+    }
+}
+
+#[test]
+fn spec_requires_passes_a_satisfying_call_through() {
+    assert_eq!(Bank.deposit(10.0), Ok(10.0));
+}
+
+#[test]
+fn spec_requires_rejects_a_violating_call_before_the_body_runs() {
+    assert_eq!(
+        Bank.deposit(-10.0),
+        Err(SpecError::new("CONST-001", "amount must be positive"))
+    );
+}
+
+#[test]
+fn spec_ensures_checks_the_same_parameters_after_the_body_runs() {
+    assert_eq!(Bank.withdraw(5.0), Ok(-5.0));
+    assert_eq!(
+        Bank.withdraw(-5.0),
+        Err(SpecError::new("CONST-001", "amount must be positive"))
+    );
+}
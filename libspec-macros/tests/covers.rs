@@ -0,0 +1,26 @@
+use libspec_macros::covers;
+
+#[covers("REQ-004", "REQ-005")]
+#[test]
+fn balance_is_correct_after_deposit() {
+    assert_eq!(1 + 1, 2);
+}
+
+#[test]
+fn records_one_trace_line_per_requirement_id() {
+    let path = std::env::temp_dir().join("libspec-macros-covers-test.jsonl");
+    let _ = std::fs::remove_file(&path);
+    std::env::set_var("LIBSPEC_TRACE_FILE", &path);
+
+    balance_is_correct_after_deposit();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].contains(r#""requirement":"REQ-004""#));
+    assert!(lines[1].contains(r#""requirement":"REQ-005""#));
+    assert!(lines[0].contains(r#""function":"balance_is_correct_after_deposit""#));
+
+    std::env::remove_var("LIBSPEC_TRACE_FILE");
+    let _ = std::fs::remove_file(&path);
+}
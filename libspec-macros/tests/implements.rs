@@ -0,0 +1,27 @@
+use libspec_macros::implements;
+
+struct BankLibrary;
+
+impl BankLibrary {
+    #[implements("REQ-004")]
+    fn balance(&self) -> f64 {
+        0.0
+    }
+}
+
+#[test]
+fn records_an_implements_trace_line() {
+    let path = std::env::temp_dir().join("libspec-macros-implements-test.jsonl");
+    let _ = std::fs::remove_file(&path);
+    std::env::set_var("LIBSPEC_TRACE_FILE", &path);
+
+    let _ = BankLibrary.balance();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains(r#""kind":"implements""#));
+    assert!(contents.contains(r#""requirement":"REQ-004""#));
+    assert!(contents.contains(r#""function":"balance""#));
+
+    std::env::remove_var("LIBSPEC_TRACE_FILE");
+    let _ = std::fs::remove_file(&path);
+}
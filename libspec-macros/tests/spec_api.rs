@@ -0,0 +1,23 @@
+//! `#[spec_api]` only produces compile errors, so only the happy path (a
+//! trait that matches its spec) can be exercised as a normal test; the
+//! mismatch cases are exercised by hand when changing the macro.
+
+use libspec_macros::spec_api;
+
+#[spec_api("tests/fixtures/matching_spec.toml")]
+trait BankApi {
+    fn balance(&self) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+struct Bank;
+
+impl BankApi for Bank {
+    fn balance(&self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+}
+
+#[test]
+fn trait_matching_the_spec_compiles() {
+    assert!(Bank.balance().is_ok());
+}
@@ -0,0 +1,24 @@
+//! `#[spec_doc]` only affects the item's doc attribute, which isn't
+//! observable at runtime, so (as with `#[spec_api]`, see its own test file)
+//! only the happy path is exercised here: the item still compiles and
+//! behaves normally with the attribute attached.
+
+use libspec_macros::spec_doc;
+
+#[spec_doc("tests/fixtures/matching_spec.toml", "REQ-004")]
+struct Bank {
+    balance: f64,
+}
+
+impl Bank {
+    #[spec_doc("tests/fixtures/matching_spec.toml", "REQ-004")]
+    fn balance(&self) -> f64 {
+        self.balance
+    }
+}
+
+#[test]
+fn item_with_a_spec_doc_attribute_still_compiles_and_behaves_normally() {
+    let bank = Bank { balance: 10.0 };
+    assert_eq!(bank.balance(), 10.0);
+}
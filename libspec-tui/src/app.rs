@@ -0,0 +1,105 @@
+//! Pure TUI state: which requirement row is selected, and where "jump to
+//! source" for it resolves to. Kept free of ratatui/crossterm so the
+//! selection and navigation logic is unit-testable without a real
+//! terminal; `main.rs` is the thin rendering/event-loop layer over this.
+
+use libspec::trace::{CoverageMatrix, CoverageRow, Mention};
+
+pub struct App {
+    pub rows: Vec<CoverageRow>,
+    mentions: Vec<Mention>,
+    spec_path: String,
+    pub selected: usize,
+}
+
+impl App {
+    pub fn new(matrix: CoverageMatrix, mentions: Vec<Mention>, spec_path: String) -> Self {
+        Self {
+            rows: matrix.rows,
+            mentions,
+            spec_path,
+            selected: 0,
+        }
+    }
+
+    pub fn selected_row(&self) -> Option<&CoverageRow> {
+        self.rows.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.rows.is_empty() {
+            self.selected = (self.selected + 1).min(self.rows.len() - 1);
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Where jumping to the selected requirement's source should go: the
+    /// first source mention [`libspec::trace::scan`] found for its id, or
+    /// line 1 of the spec file itself if the scanner found none.
+    pub fn jump_target(&self) -> Option<(String, usize)> {
+        let row = self.selected_row()?;
+        Some(match self.mentions.iter().find(|m| m.id == row.requirement) {
+            Some(mention) => (mention.file.display().to_string(), mention.line),
+            None => (self.spec_path.clone(), 1),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libspec::spec::{Requirement, SpecDocument};
+    use std::path::PathBuf;
+
+    fn app_with_two_requirements() -> App {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-001".into(),
+            text: "first".into(),
+            ..Default::default()
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-002".into(),
+            text: "second".into(),
+            ..Default::default()
+        });
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        App::new(matrix, Vec::new(), "spec.toml".to_string())
+    }
+
+    #[test]
+    fn select_next_stops_at_the_last_row() {
+        let mut app = app_with_two_requirements();
+        app.select_next();
+        app.select_next();
+        app.select_next();
+        assert_eq!(app.selected, 1);
+    }
+
+    #[test]
+    fn select_prev_stops_at_the_first_row() {
+        let mut app = app_with_two_requirements();
+        app.select_prev();
+        assert_eq!(app.selected, 0);
+    }
+
+    #[test]
+    fn jump_target_falls_back_to_the_spec_file_with_no_mention() {
+        let app = app_with_two_requirements();
+        assert_eq!(app.jump_target(), Some(("spec.toml".to_string(), 1)));
+    }
+
+    #[test]
+    fn jump_target_prefers_a_scanned_mention() {
+        let mut app = app_with_two_requirements();
+        app.mentions.push(Mention {
+            id: "REQ-001".into(),
+            file: PathBuf::from("src/lib.rs"),
+            line: 42,
+        });
+        assert_eq!(app.jump_target(), Some(("src/lib.rs".to_string(), 42)));
+    }
+}
@@ -0,0 +1,163 @@
+//! `libspec-tui`: an interactive terminal app for browsing a spec's
+//! requirements — their text, coverage, and linked tests/implementations —
+//! and jumping to the source location a requirement's id is mentioned at.
+//! Everything `cargo spec report` prints flattened into a table,
+//! browsable. An optional second command-line argument narrows the table
+//! to a [`libspec::spec::Query`], e.g. `libspec-tui spec.toml
+//! "kind:security covers:none"`. All selection/navigation state lives in
+//! [`app`], kept free of ratatui/crossterm so it's unit-testable; this
+//! file is the thin rendering and event-loop layer over it.
+
+mod app;
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::ExecutableCommand;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+
+use app::App;
+use libspec::spec::{Query, SpecDocument};
+use libspec::trace::{self, CoverageMatrix, CoverageRow};
+
+fn main() -> io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let spec_path = args.next().unwrap_or_else(|| "spec.toml".to_string());
+    // e.g. `libspec-tui spec.toml "kind:security covers:none"` to open
+    // straight into the untested security requirements, instead of
+    // scrolling a large spec by hand — see `libspec::spec::Query`.
+    let filter = args.next();
+
+    let doc = SpecDocument::load_toml_file(&spec_path).unwrap_or_else(|e| {
+        eprintln!("libspec-tui: {e}");
+        std::process::exit(1);
+    });
+    let query = filter
+        .as_deref()
+        .map(|expr| {
+            Query::parse(expr).unwrap_or_else(|e| {
+                eprintln!("libspec-tui: {e}");
+                std::process::exit(1);
+            })
+        });
+    let records = trace::read_records();
+    let mut matrix = CoverageMatrix::build(&doc, &records);
+    if let Some(query) = &query {
+        matrix.rows.retain(|row| query.matches_with_coverage(row, !row.tests.is_empty()));
+    }
+    let mentions = trace::scan(Path::new("."), &doc);
+    let mut app = App::new(matrix, mentions, spec_path);
+
+    enable_raw_mode()?;
+    io::stdout().execute(EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    let jump = run(&mut terminal, &mut app)?;
+
+    disable_raw_mode()?;
+    terminal.backend_mut().execute(LeaveAlternateScreen)?;
+
+    if let Some((file, line)) = jump {
+        match std::env::var("EDITOR") {
+            Ok(editor) => {
+                Command::new(editor).arg(format!("+{line}")).arg(&file).status()?;
+            }
+            Err(_) => println!("{file}:{line}"),
+        }
+    }
+    Ok(())
+}
+
+/// Runs the event loop, redrawing on every key press, until the user
+/// quits (`q`/`Esc`, returning `None`) or asks to jump to the selected
+/// requirement's source (`Enter`/`o`, returning [`App::jump_target`]).
+fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> io::Result<Option<(String, usize)>> {
+    loop {
+        terminal.draw(|frame| draw(frame, app))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+                KeyCode::Down | KeyCode::Char('j') => app.select_next(),
+                KeyCode::Up | KeyCode::Char('k') => app.select_prev(),
+                KeyCode::Enter | KeyCode::Char('o') => return Ok(app.jump_target()),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(frame.area());
+
+    let items: Vec<ListItem> = app
+        .rows
+        .iter()
+        .map(|row| {
+            let marker = if row.tests.is_empty() { "✗" } else { "✓" };
+            ListItem::new(format!("{marker} {}", row.requirement))
+        })
+        .collect();
+    let mut list_state = ListState::default().with_selected(Some(app.selected));
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Requirements"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, columns[0], &mut list_state);
+
+    let detail = match app.selected_row() {
+        Some(row) => detail_lines(row),
+        None => vec![Line::from("no requirements")],
+    };
+    let paragraph = Paragraph::new(detail)
+        .block(Block::default().borders(Borders::ALL).title("Detail (Enter/o: jump to source, q: quit)"))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, columns[1]);
+}
+
+fn detail_lines(row: &CoverageRow) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(Span::styled(row.requirement.clone(), Style::default().add_modifier(Modifier::BOLD))),
+        Line::from(row.text.clone()),
+        Line::from(""),
+        Line::from(format!("status: {:?}", row.status)),
+        Line::from(format!("tags: {}", row.tags.join(", "))),
+        Line::from(""),
+        Line::from(Span::styled("tests", Style::default().add_modifier(Modifier::UNDERLINED))),
+    ];
+    if row.tests.is_empty() {
+        lines.push(Line::from(Span::styled("(untested)", Style::default().fg(Color::Red))));
+    } else {
+        for test in &row.tests {
+            lines.push(Line::from(format!("  {test}")));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "implementations",
+        Style::default().add_modifier(Modifier::UNDERLINED),
+    )));
+    if row.implementations.is_empty() {
+        lines.push(Line::from(Span::styled("(none recorded)", Style::default().fg(Color::Red))));
+    } else {
+        for implementation in &row.implementations {
+            lines.push(Line::from(format!("  {implementation}")));
+        }
+    }
+
+    lines
+}
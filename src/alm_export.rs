@@ -0,0 +1,202 @@
+//! Builds the requests an [`AlmExportConfig`] describes for pushing
+//! requirement and coverage data to an external ALM tool (Jama,
+//! Polarion, and similar systems typically expose a REST API shaped like
+//! this) over a generic REST adapter, so libspec stays the source of
+//! truth while such tools keep their own dashboards in sync. Mirrors
+//! [`crate::tracker`]'s split: this module only builds the requests;
+//! actually sending them is left to the caller, the same way
+//! `tracker::sync` leaves looking up issue status to an
+//! [`crate::tracker::IssueStatusSource`] implementation.
+
+use serde_json::json;
+
+use crate::spec::SpecDocument;
+use crate::trace::CoverageMatrix;
+
+/// How to authenticate exported requests against the external tool.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlmAuth {
+    None,
+    Bearer(String),
+    Basic { username: String, password: String },
+}
+
+/// Where and how to push requirement/coverage data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlmExportConfig {
+    /// The URL each requirement is pushed to, with `{id}` substituted for
+    /// its [`crate::spec::Requirement::id`], e.g.
+    /// `"https://jama.example.com/api/v1/items/{id}"`.
+    pub endpoint_template: String,
+    pub auth: AlmAuth,
+}
+
+/// One REST request [`export`] built for a requirement, ready for a
+/// caller's own HTTP client (e.g. `libspec_harness::HttpTransport`) to
+/// send.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlmRequest {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+/// Builds one `PUT` request per requirement in `doc`, at
+/// `config.endpoint_template` with `{id}` substituted, carrying that
+/// requirement's [`CoverageRow`](crate::trace::CoverageRow) (text, status,
+/// priority, test/implementation function names) as its JSON body. A
+/// requirement `matrix` has no row for (shouldn't happen, since
+/// [`CoverageMatrix::build`] covers every requirement in the same `doc`)
+/// is skipped.
+pub fn export(doc: &SpecDocument, matrix: &CoverageMatrix, config: &AlmExportConfig) -> Vec<AlmRequest> {
+    let mut requests = Vec::new();
+    for req in &doc.requirements {
+        let Some(row) = matrix.rows.iter().find(|row| row.requirement == req.id) else {
+            continue;
+        };
+
+        let url = config.endpoint_template.replace("{id}", &req.id);
+        let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        match &config.auth {
+            AlmAuth::None => {}
+            AlmAuth::Bearer(token) => {
+                headers.push(("Authorization".to_string(), format!("Bearer {token}")));
+            }
+            AlmAuth::Basic { username, password } => {
+                let credentials = base64_encode(format!("{username}:{password}").as_bytes());
+                headers.push(("Authorization".to_string(), format!("Basic {credentials}")));
+            }
+        }
+
+        let body = json!({
+            "id": req.id,
+            "text": row.text,
+            "status": format!("{:?}", row.status),
+            "priority": format!("{:?}", req.priority),
+            "tags": row.tags,
+            "tests": row.tests,
+            "implementations": row.implementations,
+        })
+        .to_string();
+
+        requests.push(AlmRequest { method: "PUT", url, headers, body });
+    }
+    requests
+}
+
+/// Encodes `bytes` as standard base64, for [`AlmAuth::Basic`]'s
+/// `Authorization: Basic` header — not worth pulling in a whole crate for
+/// one call site.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied();
+        let b2 = chunk.get(2).copied();
+
+        out.push(ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(ALPHABET[(((b0 & 0x03) << 4) | (b1.unwrap_or(0) >> 4)) as usize] as char);
+        out.push(match b1 {
+            Some(b1) => ALPHABET[(((b1 & 0x0f) << 2) | (b2.unwrap_or(0) >> 6)) as usize] as char,
+            None => '=',
+        });
+        out.push(match b2 {
+            Some(b2) => ALPHABET[(b2 & 0x3f) as usize] as char,
+            None => '=',
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+    use crate::trace::Record;
+
+    fn doc_with_one_requirement() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn builds_one_request_per_requirement_with_id_substituted() {
+        let doc = doc_with_one_requirement();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let config = AlmExportConfig {
+            endpoint_template: "https://jama.example.com/api/v1/items/{id}".into(),
+            auth: AlmAuth::None,
+        };
+
+        let requests = export(&doc, &matrix, &config);
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(requests[0].url, "https://jama.example.com/api/v1/items/REQ-004");
+        assert!(!requests[0].headers.iter().any(|(k, _)| k == "Authorization"));
+    }
+
+    #[test]
+    fn carries_coverage_records_in_the_body() {
+        let doc = doc_with_one_requirement();
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "deposit_then_balance_matches".into(),
+            requirement: "REQ-004".into(),
+        }];
+        let matrix = CoverageMatrix::build(&doc, &records);
+        let config = AlmExportConfig {
+            endpoint_template: "https://jama.example.com/api/v1/items/{id}".into(),
+            auth: AlmAuth::None,
+        };
+
+        let requests = export(&doc, &matrix, &config);
+        let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(body["tests"], json!(["deposit_then_balance_matches"]));
+    }
+
+    #[test]
+    fn bearer_auth_sets_the_authorization_header() {
+        let doc = doc_with_one_requirement();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let config = AlmExportConfig {
+            endpoint_template: "https://jama.example.com/api/v1/items/{id}".into(),
+            auth: AlmAuth::Bearer("secret-token".into()),
+        };
+
+        let requests = export(&doc, &matrix, &config);
+        assert!(requests[0]
+            .headers
+            .contains(&("Authorization".to_string(), "Bearer secret-token".to_string())));
+    }
+
+    #[test]
+    fn basic_auth_encodes_credentials() {
+        let doc = doc_with_one_requirement();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let config = AlmExportConfig {
+            endpoint_template: "https://jama.example.com/api/v1/items/{id}".into(),
+            auth: AlmAuth::Basic { username: "alice".into(), password: "hunter2".into() },
+        };
+
+        let requests = export(&doc, &matrix, &config);
+        let (_, value) = requests[0]
+            .headers
+            .iter()
+            .find(|(k, _)| k == "Authorization")
+            .unwrap();
+        assert!(value.starts_with("Basic "));
+    }
+
+    #[test]
+    fn base64_encode_matches_a_known_vector() {
+        assert_eq!(base64_encode(b"alice:hunter2"), "YWxpY2U6aHVudGVyMg==");
+    }
+}
@@ -0,0 +1,186 @@
+//! An append-only audit trail keyed by the requirements each call
+//! implements, for deployments (the bank example being the canonical one)
+//! that have to answer "who called what, with what arguments, and what
+//! happened" after the fact rather than just "did this pass or fail" —
+//! what [`crate::runtime::report`] and [`crate::monitor::Monitor`] are
+//! for. Same pluggable-sink shape as [`crate::monitor::Monitor`]: an
+//! [`AuditLog`] fans every [`AuditRecord`] out to one or more
+//! [`AuditStore`]s.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One call recorded into an [`AuditLog`]: the operation invoked, its
+/// arguments as `(name, value)` pairs, the result it produced, the
+/// requirement ids it implements, and the Unix timestamp (seconds) it
+/// was recorded at.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct AuditRecord {
+    pub operation: String,
+    pub arguments: Vec<(String, String)>,
+    pub result: String,
+    pub requirements: Vec<String>,
+    pub timestamp: u64,
+}
+
+/// Where an [`AuditLog`] persists every [`AuditRecord`] it records.
+/// Implement this to route the audit trail into whatever a regulated
+/// deployment already uses for long-term retention (a database, object
+/// storage, a SIEM), the same extension point [`crate::monitor::MonitorSink`]
+/// is for violations.
+pub trait AuditStore: Send + Sync {
+    fn record(&self, record: &AuditRecord);
+}
+
+/// Records calls into one or more [`AuditStore`]s, same builder shape as
+/// [`crate::monitor::Monitor`].
+pub struct AuditLog {
+    stores: Vec<Box<dyn AuditStore>>,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        AuditLog { stores: Vec::new() }
+    }
+
+    /// Adds `store` to the set every future [`record`](AuditLog::record)
+    /// call persists to.
+    pub fn register(&mut self, store: Box<dyn AuditStore>) -> &mut Self {
+        self.stores.push(store);
+        self
+    }
+
+    /// Builds an [`AuditRecord`] for a call to `operation`, stamps it with
+    /// the current time, and persists it to every registered store.
+    pub fn record(
+        &self,
+        operation: &str,
+        arguments: &[(&str, &str)],
+        result: &str,
+        requirements: &[&str],
+    ) {
+        let record = AuditRecord {
+            operation: operation.to_string(),
+            arguments: arguments.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect(),
+            result: result.to_string(),
+            requirements: requirements.iter().map(|r| r.to_string()).collect(),
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        };
+        for store in &self.stores {
+            store.record(&record);
+        }
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An [`AuditStore`] that keeps every [`AuditRecord`] in memory, for tests
+/// and for a deployment small enough not to need a real backing store.
+#[derive(Clone, Default)]
+pub struct InMemoryStore {
+    records: Arc<Mutex<Vec<AuditRecord>>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every [`AuditRecord`] recorded so far, in the order they arrived.
+    pub fn records(&self) -> Vec<AuditRecord> {
+        self.records.lock().unwrap().clone()
+    }
+}
+
+impl AuditStore for InMemoryStore {
+    fn record(&self, record: &AuditRecord) {
+        self.records.lock().unwrap().push(record.clone());
+    }
+}
+
+/// An [`AuditStore`] that appends one JSON line per [`AuditRecord`] to a
+/// file, the same JSON-lines convention [`crate::trace`] and
+/// [`crate::monitor::LogFileSink`] use for their on-disk artifacts. A
+/// write failure (missing directory, full disk) is swallowed rather than
+/// panicking the caller whose operation is being audited.
+pub struct JsonlFileStore {
+    path: PathBuf,
+}
+
+impl JsonlFileStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        JsonlFileStore { path: path.as_ref().to_path_buf() }
+    }
+}
+
+impl AuditStore for JsonlFileStore {
+    fn record(&self, record: &AuditRecord) {
+        let Ok(line) = serde_json::to_string(record) else {
+            return;
+        };
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&self.path) {
+            let _ = writeln!(file, "{line}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_a_call_with_its_requirements_into_every_registered_store() {
+        let store = InMemoryStore::new();
+        let mut log = AuditLog::new();
+        log.register(Box::new(store.clone()));
+
+        log.record("withdraw", &[("amount", "50")], "Ok(())", &["REQ-002"]);
+
+        let records = store.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].operation, "withdraw");
+        assert_eq!(records[0].arguments, vec![("amount".to_string(), "50".to_string())]);
+        assert_eq!(records[0].result, "Ok(())");
+        assert_eq!(records[0].requirements, vec!["REQ-002".to_string()]);
+        assert!(records[0].timestamp > 0);
+    }
+
+    #[test]
+    fn fans_a_record_out_to_every_registered_store() {
+        let store_a = InMemoryStore::new();
+        let store_b = InMemoryStore::new();
+        let mut log = AuditLog::new();
+        log.register(Box::new(store_a.clone()));
+        log.register(Box::new(store_b.clone()));
+
+        log.record("balance", &[], "Ok(100)", &["REQ-004"]);
+
+        assert_eq!(store_a.records().len(), 1);
+        assert_eq!(store_b.records().len(), 1);
+    }
+
+    #[test]
+    fn jsonl_file_store_appends_one_json_line_per_record() {
+        let path = std::env::temp_dir()
+            .join(format!("libspec-audit-test-{:?}.jsonl", std::thread::current().id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut log = AuditLog::new();
+        log.register(Box::new(JsonlFileStore::new(&path)));
+        log.record("deposit", &[("amount", "10")], "Ok(())", &["REQ-001"]);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 1);
+        assert!(contents.contains("\"operation\":\"deposit\""));
+        assert!(contents.contains("\"REQ-001\""));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
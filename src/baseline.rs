@@ -0,0 +1,238 @@
+//! Freezes the content of every non-[`Draft`](Status::Draft) requirement
+//! into a [`Baseline`] that can be written to a file and checked back into
+//! the repo, so a later run can tell whether an `Approved` (or further
+//! along) requirement was silently edited since it was baselined — making
+//! such a change requires deliberately re-running [`Baseline::capture`]
+//! and committing the result, not just re-running whatever loaded the spec.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::spec::{Requirement, SpecDocument, Status};
+
+/// One content hash per baselined requirement, keyed by id.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    #[serde(default)]
+    pub requirement_hashes: BTreeMap<String, String>,
+}
+
+impl Baseline {
+    /// Captures a baseline from `doc`: one content hash per requirement at
+    /// or past [`Status::Approved`] (`Approved`, `Implemented`, or
+    /// `Deprecated`) — a requirement still in `Draft` hasn't settled
+    /// enough to be worth pinning.
+    pub fn capture(doc: &SpecDocument) -> Self {
+        let requirement_hashes = doc
+            .requirements
+            .iter()
+            .filter(|req| req.status != Status::Draft)
+            .map(|req| (req.id.clone(), requirement_hash(req)))
+            .collect();
+        Self { requirement_hashes }
+    }
+
+    pub fn from_toml_str(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    pub fn to_toml_string(&self) -> String {
+        toml::to_string_pretty(self).expect("Baseline serialization is infallible")
+    }
+
+    /// Loads a baseline previously written by [`write_toml_file`](Self::write_toml_file).
+    pub fn load_toml_file(path: &Path) -> Result<Self, BaselineError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| BaselineError::Io {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Self::from_toml_str(&contents).map_err(|e| BaselineError::Parse {
+            path: path.to_path_buf(),
+            source: e,
+        })
+    }
+
+    /// Writes this baseline to `path`, overwriting whatever was there —
+    /// the explicit re-baseline step a drifted [`check`](Self::check) asks
+    /// for.
+    pub fn write_toml_file(&self, path: &Path) -> Result<(), BaselineError> {
+        std::fs::write(path, self.to_toml_string()).map_err(|e| BaselineError::Io {
+            path: path.to_path_buf(),
+            source: e,
+        })
+    }
+
+    /// Compares `doc` against this baseline, one [`Drift`] per baselined
+    /// requirement that no longer matches (or is gone). A requirement
+    /// that's newly `Approved` and isn't in the baseline yet isn't
+    /// drift — it just hasn't been baselined, and the next
+    /// [`capture`](Self::capture) will pick it up.
+    pub fn check(&self, doc: &SpecDocument) -> Vec<Drift> {
+        let mut drifts = Vec::new();
+        for (id, expected_hash) in &self.requirement_hashes {
+            match doc.requirement(id) {
+                None => drifts.push(Drift {
+                    requirement: id.clone(),
+                    kind: DriftKind::Removed,
+                }),
+                Some(req) if &requirement_hash(req) != expected_hash => drifts.push(Drift {
+                    requirement: id.clone(),
+                    kind: DriftKind::Changed,
+                }),
+                Some(_) => {}
+            }
+        }
+        drifts
+    }
+}
+
+/// One baselined requirement that no longer matches what was pinned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drift {
+    pub requirement: String,
+    pub kind: DriftKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftKind {
+    /// The requirement's content hash no longer matches the baseline.
+    Changed,
+    /// The requirement isn't in the spec anymore.
+    Removed,
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            DriftKind::Changed => write!(f, "{} was edited since it was baselined", self.requirement),
+            DriftKind::Removed => write!(f, "{} was removed since it was baselined", self.requirement),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum BaselineError {
+    Io { path: PathBuf, source: std::io::Error },
+    Parse { path: PathBuf, source: toml::de::Error },
+}
+
+impl fmt::Display for BaselineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BaselineError::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            BaselineError::Parse { path, source } => write!(f, "{}: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for BaselineError {}
+
+/// A hex digest of `req`'s full content, stable across runs — the same
+/// approach as [`SpecDocument::version_hash`](crate::spec::SpecDocument::version_hash),
+/// scoped to one requirement instead of the whole document.
+fn requirement_hash(req: &Requirement) -> String {
+    use std::hash::{Hash, Hasher};
+
+    let json = serde_json::to_string(req).expect("Requirement serialization is infallible");
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    json.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(id: &str, text: &str, status: Status) -> Requirement {
+        Requirement {
+            id: id.into(),
+            text: text.into(),
+            status,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn captures_approved_and_implemented_requirements_but_not_drafts() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-001", "drafty", Status::Draft));
+        doc.requirements.push(req("REQ-002", "approved", Status::Approved));
+        doc.requirements.push(req("REQ-003", "implemented", Status::Implemented));
+
+        let baseline = Baseline::capture(&doc);
+        assert!(!baseline.requirement_hashes.contains_key("REQ-001"));
+        assert!(baseline.requirement_hashes.contains_key("REQ-002"));
+        assert!(baseline.requirement_hashes.contains_key("REQ-003"));
+    }
+
+    #[test]
+    fn an_unchanged_baseline_has_no_drift() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-002", "approved", Status::Approved));
+
+        let baseline = Baseline::capture(&doc);
+        assert!(baseline.check(&doc).is_empty());
+    }
+
+    #[test]
+    fn flags_an_edited_approved_requirement_as_drifted() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-002", "approved", Status::Approved));
+        let baseline = Baseline::capture(&doc);
+
+        doc.requirements[0].text = "approved, but reworded".into();
+        let drifts = baseline.check(&doc);
+        assert_eq!(drifts, vec![Drift { requirement: "REQ-002".into(), kind: DriftKind::Changed }]);
+    }
+
+    #[test]
+    fn flags_a_removed_approved_requirement_as_drifted() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-002", "approved", Status::Approved));
+        let baseline = Baseline::capture(&doc);
+
+        doc.requirements.clear();
+        let drifts = baseline.check(&doc);
+        assert_eq!(drifts, vec![Drift { requirement: "REQ-002".into(), kind: DriftKind::Removed }]);
+    }
+
+    #[test]
+    fn a_newly_approved_requirement_not_yet_baselined_is_not_drift() {
+        let before = SpecDocument::new();
+        let baseline = Baseline::capture(&before);
+
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-002", "approved", Status::Approved));
+        assert!(baseline.check(&after).is_empty());
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-002", "approved", Status::Approved));
+        let baseline = Baseline::capture(&doc);
+
+        let parsed = Baseline::from_toml_str(&baseline.to_toml_string()).unwrap();
+        assert_eq!(parsed, baseline);
+    }
+
+    #[test]
+    fn writes_and_loads_a_baseline_file() {
+        let dir = std::env::temp_dir().join(format!("libspec-baseline-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("libspec-baseline.toml");
+
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-002", "approved", Status::Approved));
+        let baseline = Baseline::capture(&doc);
+        baseline.write_toml_file(&path).unwrap();
+
+        let loaded = Baseline::load_toml_file(&path).unwrap();
+        assert_eq!(loaded, baseline);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,314 @@
+//! Bootstraps a provisional [`SpecDocument`] from an existing crate's
+//! public API and doc comments, for a brownfield project with real Rust
+//! code but no spec yet — giving it a starting point to refine instead
+//! of a blank file. Line-based and best-effort, the same as
+//! [`crate::trace::scan`]: it doesn't parse the file, so a `pub fn`
+//! inside a doc example or a disabled `#[cfg(...)]` block is drafted the
+//! same as a real one, and every drafted requirement/constraint starts
+//! [`Status::Draft`]/unreviewed, expecting a human to confirm, reword, or
+//! discard it.
+
+use std::path::{Path, PathBuf};
+
+use crate::spec::{Constraint, Requirement, SpecDocument, Status};
+
+/// Drafts a provisional [`SpecDocument`] from `source`: one requirement
+/// per `pub fn`, its text taken from the immediately preceding `///` doc
+/// comment block (or a placeholder if it has none), plus one constraint
+/// per `assert!`/`assert_eq!`/`Err(...)` guess found in its body.
+pub fn draft(source: &str) -> SpecDocument {
+    let mut doc = SpecDocument::new();
+    let mut next_requirement = 1;
+    let mut next_constraint = 1;
+    draft_into(source, &mut doc, &mut next_requirement, &mut next_constraint);
+    doc
+}
+
+/// Same as [`draft`], but scanning every `.rs` file under `root`
+/// (skipping `target` and hidden directories, the same as
+/// [`crate::trace::scan`]) into one combined document, in file-then-line
+/// order, so ids stay unique across files instead of restarting at
+/// `REQ-DRAFT-1` in each one.
+pub fn draft_dir(root: &Path) -> SpecDocument {
+    let mut files = Vec::new();
+    collect_rust_files(root, &mut files);
+    files.sort();
+
+    let mut doc = SpecDocument::new();
+    let mut next_requirement = 1;
+    let mut next_constraint = 1;
+    for file in files {
+        let Ok(source) = std::fs::read_to_string(&file) else { continue };
+        draft_into(&source, &mut doc, &mut next_requirement, &mut next_constraint);
+    }
+    doc
+}
+
+fn draft_into(source: &str, doc: &mut SpecDocument, next_requirement: &mut usize, next_constraint: &mut usize) {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut pending_doc_lines: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let trimmed = lines[i].trim_start();
+
+        if let Some(text) = trimmed.strip_prefix("///") {
+            pending_doc_lines.push(text.trim().to_string());
+            i += 1;
+            continue;
+        }
+
+        if let Some(name) = public_fn_name(trimmed) {
+            let text = if pending_doc_lines.is_empty() {
+                format!("{name}(): (no doc comment found; describe its behavior)")
+            } else {
+                pending_doc_lines.join(" ")
+            };
+            doc.requirements.push(Requirement {
+                id: format!("REQ-DRAFT-{next_requirement}"),
+                text,
+                status: Status::Draft,
+                ..Default::default()
+            });
+            *next_requirement += 1;
+
+            let (body_end, body) = collect_fn_body(&lines, i);
+            for hint in guessed_constraints(&body) {
+                doc.constraints.push(Constraint {
+                    code: format!("CONST-DRAFT-{next_constraint}"),
+                    text: hint,
+                    ..Default::default()
+                });
+                *next_constraint += 1;
+            }
+            pending_doc_lines.clear();
+            i = body_end;
+            continue;
+        }
+
+        // A `///` block only describes the item directly below it — any
+        // other line (blank or unrelated code) between the block and a
+        // `pub fn` breaks that association. An attribute like
+        // `#[must_use]` doesn't, since it still applies to the same item.
+        if !trimmed.starts_with('#') {
+            pending_doc_lines.clear();
+        }
+        i += 1;
+    }
+}
+
+/// The name of the function `trimmed` declares, if it's `pub fn`/`pub
+/// async fn`/`pub unsafe fn` — `pub(crate)`/`pub(super)` don't count as
+/// public API, and neither does a plain private `fn`.
+fn public_fn_name(trimmed: &str) -> Option<&str> {
+    let rest = trimmed.strip_prefix("pub ")?.trim_start();
+    let rest = rest.strip_prefix("async ").unwrap_or(rest).trim_start();
+    let rest = rest.strip_prefix("unsafe ").unwrap_or(rest).trim_start();
+    let rest = rest.strip_prefix("fn ")?;
+    let name_end = rest.find(|c: char| c == '(' || c == '<' || c.is_whitespace())?;
+    let name = &rest[..name_end];
+    (!name.is_empty()).then_some(name)
+}
+
+/// Collects the lines of the function body starting at `lines[start]`
+/// (which names the function), tracking brace depth from the first `{`
+/// found through to the matching `}`. A signature with no body (a trait
+/// method ending in `;`, before any `{`) collects nothing. Returns the
+/// index just past the body/signature, and the body's lines (empty for a
+/// body-less signature).
+fn collect_fn_body(lines: &[&str], start: usize) -> (usize, Vec<String>) {
+    let mut i = start;
+    let mut depth = 0i32;
+    let mut opened = false;
+    let mut body = Vec::new();
+    while i < lines.len() {
+        let line = lines[i];
+        for ch in line.chars() {
+            match ch {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if opened {
+            body.push(line.to_string());
+        }
+        let signature_has_no_body = !opened && line.trim_end().ends_with(';');
+        i += 1;
+        if signature_has_no_body || (opened && depth <= 0) {
+            break;
+        }
+    }
+    (i, body)
+}
+
+/// Guesses one constraint hint per `assert!`/`assert_eq!`/`assert_ne!`
+/// call (its condition), and per `Err(...)` construction that carries a
+/// string literal (that message) — the two most common places a Rust
+/// function states a rule its spec should probably capture.
+fn guessed_constraints(body: &[String]) -> Vec<String> {
+    let mut hints = Vec::new();
+    for line in body {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("assert_eq!(") {
+            hints.push(format!("must hold: assert_eq!({}", trim_call_close(rest)));
+        } else if let Some(rest) = trimmed.strip_prefix("assert_ne!(") {
+            hints.push(format!("must hold: assert_ne!({}", trim_call_close(rest)));
+        } else if let Some(rest) = trimmed.strip_prefix("assert!(") {
+            hints.push(format!("must hold: {}", trim_call_close(rest)));
+        } else if trimmed.contains("Err(") {
+            if let Some(message) = quoted_message(trimmed) {
+                hints.push(format!("may fail: {message}"));
+            }
+        }
+    }
+    hints
+}
+
+/// Trims a trailing `);` or `)` left over from stripping a macro/call's
+/// opening `name!(`/`name(` prefix.
+fn trim_call_close(rest: &str) -> &str {
+    rest.trim_end().trim_end_matches(';').trim_end().trim_end_matches(')')
+}
+
+/// The first `"..."`-quoted string literal on `line`, if any.
+fn quoted_message(line: &str) -> Option<&str> {
+    let start = line.find('"')? + 1;
+    let end = start + line[start..].find('"')?;
+    Some(&line[start..end])
+}
+
+fn collect_rust_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if name == "target" || name.starts_with('.') {
+                continue;
+            }
+            collect_rust_files(&path, out);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            out.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drafts_a_requirement_from_a_doc_commented_public_function() {
+        let source = "/// Returns the account's current balance.\npub fn balance(&self) -> Money {\n    self.balance\n}\n";
+        let doc = draft(source);
+        assert_eq!(doc.requirements.len(), 1);
+        assert_eq!(doc.requirements[0].id, "REQ-DRAFT-1");
+        assert_eq!(doc.requirements[0].text, "Returns the account's current balance.");
+        assert_eq!(doc.requirements[0].status, Status::Draft);
+    }
+
+    #[test]
+    fn a_public_function_with_no_doc_comment_gets_a_placeholder_text() {
+        let source = "pub fn balance(&self) -> Money {\n    self.balance\n}\n";
+        let doc = draft(source);
+        assert_eq!(doc.requirements[0].text, "balance(): (no doc comment found; describe its behavior)");
+    }
+
+    #[test]
+    fn ignores_a_private_function() {
+        let source = "/// private helper\nfn helper() {}\n";
+        assert!(draft(source).requirements.is_empty());
+    }
+
+    #[test]
+    fn ignores_a_crate_scoped_function() {
+        let source = "/// crate-internal\npub(crate) fn helper() {}\n";
+        assert!(draft(source).requirements.is_empty());
+    }
+
+    #[test]
+    fn a_doc_comment_separated_by_a_blank_line_does_not_attach() {
+        let source = "/// unrelated\n\npub fn balance() -> Money {\n    Money::default()\n}\n";
+        let doc = draft(source);
+        assert_eq!(doc.requirements[0].text, "balance(): (no doc comment found; describe its behavior)");
+    }
+
+    #[test]
+    fn an_attribute_between_the_doc_comment_and_the_function_does_not_break_the_association() {
+        let source = "/// Returns the balance.\n#[must_use]\npub fn balance() -> Money {\n    Money::default()\n}\n";
+        let doc = draft(source);
+        assert_eq!(doc.requirements[0].text, "Returns the balance.");
+    }
+
+    #[test]
+    fn joins_a_multi_line_doc_comment_with_spaces() {
+        let source = "/// Returns the balance.\n/// Never negative.\npub fn balance() -> Money {\n    Money::default()\n}\n";
+        let doc = draft(source);
+        assert_eq!(doc.requirements[0].text, "Returns the balance. Never negative.");
+    }
+
+    #[test]
+    fn guesses_a_constraint_from_an_assert() {
+        let source = "pub fn withdraw(amount: Money) {\n    assert!(amount > 0, \"amount must be positive\");\n}\n";
+        let doc = draft(source);
+        assert_eq!(doc.constraints.len(), 1);
+        assert_eq!(doc.constraints[0].code, "CONST-DRAFT-1");
+        assert!(doc.constraints[0].text.contains("amount > 0"));
+    }
+
+    #[test]
+    fn guesses_a_constraint_from_an_error_message() {
+        let source = "pub fn withdraw(amount: Money) -> Result<()> {\n    Err(BankError::Overdrawn(\"insufficient funds\".into()))\n}\n";
+        let doc = draft(source);
+        assert_eq!(doc.constraints[0].text, "may fail: insufficient funds");
+    }
+
+    #[test]
+    fn a_trait_method_signature_with_no_body_yields_no_constraints() {
+        let source = "pub trait BankAPI {\n    /// Returns the balance.\n    pub fn balance(&self) -> Money;\n}\n";
+        let doc = draft(source);
+        assert_eq!(doc.requirements.len(), 1);
+        assert!(doc.constraints.is_empty());
+    }
+
+    #[test]
+    fn assigns_sequential_ids_across_several_functions() {
+        let source = "/// first\npub fn a() {}\n/// second\npub fn b() {}\n";
+        let doc = draft(source);
+        assert_eq!(doc.requirements[0].id, "REQ-DRAFT-1");
+        assert_eq!(doc.requirements[1].id, "REQ-DRAFT-2");
+    }
+
+    #[test]
+    fn draft_dir_scans_every_rust_file_and_keeps_ids_unique_across_them() {
+        let dir = std::env::temp_dir().join(format!("libspec-bootstrap-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.rs"), "/// first\npub fn a() {}\n").unwrap();
+        std::fs::write(dir.join("b.rs"), "/// second\npub fn b() {}\n").unwrap();
+
+        let doc = draft_dir(&dir);
+        let ids: Vec<&str> = doc.requirements.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["REQ-DRAFT-1", "REQ-DRAFT-2"]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn draft_dir_skips_the_target_directory() {
+        let dir = std::env::temp_dir().join(format!("libspec-bootstrap-skip-test-{}", std::process::id()));
+        let target = dir.join("target");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(target.join("generated.rs"), "pub fn generated() {}\n").unwrap();
+
+        assert!(draft_dir(&dir).requirements.is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
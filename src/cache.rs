@@ -0,0 +1,115 @@
+//! A content-hash-keyed on-disk cache for `cargo spec`'s more expensive
+//! passes — parsing a spec, generating code from it, scanning a source
+//! tree for id mentions — so a CI pipeline invoking the same command
+//! release after release on an unchanged tree doesn't pay for it every
+//! time. Rooted wherever the caller points it (`cargo spec` uses
+//! `target/libspec/<kind>`, following cargo's own convention of putting
+//! build artifacts under `target/`), one JSON file per cache key's hash.
+//! A `--no-cache` flag is a CLI concern, not this module's: a caller that
+//! wants to bypass caching just doesn't construct a [`Cache`] at all.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// An on-disk cache rooted at `dir`.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Reads back the value cached under `key`'s hash, if there is one
+    /// and it still deserializes to `T` (a stale cache from a previous
+    /// schema just misses, rather than erroring).
+    pub fn get<T: DeserializeOwned>(&self, key: &impl Hash) -> Option<T> {
+        let contents = fs::read_to_string(self.path_for(key)).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// Caches `value` under `key`'s hash. Best-effort: an unwritable
+    /// cache directory just means the next call misses again, not a
+    /// hard failure, since the cache is purely an optimization.
+    pub fn put<T: Serialize>(&self, key: &impl Hash, value: &T) {
+        if fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+        if let Ok(contents) = serde_json::to_string(value) {
+            let _ = fs::write(self.path_for(key), contents);
+        }
+    }
+
+    /// Returns the value cached under `key`, or calls `compute`, caches
+    /// its result, and returns that if there's no entry yet.
+    pub fn get_or_compute<T: Serialize + DeserializeOwned>(&self, key: &impl Hash, compute: impl FnOnce() -> T) -> T {
+        if let Some(cached) = self.get(key) {
+            return cached;
+        }
+        let value = compute();
+        self.put(key, &value);
+        value
+    }
+
+    fn path_for(&self, key: &impl Hash) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        self.dir.join(format!("{:016x}.json", hasher.finish()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("libspec-cache-test-{name}-{}", std::process::id()))
+    }
+
+    #[test]
+    fn get_misses_on_an_empty_cache() {
+        let dir = temp_dir("empty");
+        let cache = Cache::new(&dir);
+        assert_eq!(cache.get::<String>(&"key"), None);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn put_then_get_round_trips() {
+        let dir = temp_dir("round-trip");
+        let cache = Cache::new(&dir);
+        cache.put(&"key", &"value".to_string());
+        assert_eq!(cache.get::<String>(&"key"), Some("value".to_string()));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn different_keys_hash_to_different_entries() {
+        let dir = temp_dir("distinct-keys");
+        let cache = Cache::new(&dir);
+        cache.put(&"a", &1u32);
+        cache.put(&"b", &2u32);
+        assert_eq!(cache.get::<u32>(&"a"), Some(1));
+        assert_eq!(cache.get::<u32>(&"b"), Some(2));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn get_or_compute_only_calls_compute_on_a_miss() {
+        let dir = temp_dir("get-or-compute");
+        let cache = Cache::new(&dir);
+
+        let first = cache.get_or_compute(&"key", || "computed-1".to_string());
+        let second = cache.get_or_compute(&"key", || "computed-2".to_string());
+
+        assert_eq!(first, "computed-1");
+        assert_eq!(second, "computed-1");
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,236 @@
+//! Renders a [`crate::diff::SpecDiff`] as a human-readable changelog,
+//! grouped by each requirement's first tag (its "area"; untagged
+//! requirements fall under [`UNTAGGED`]), with the actual requirement/
+//! constraint text deltas inlined — suitable for pasting straight into
+//! release notes instead of hand-summarizing a spec diff. A renumbered
+//! requirement ([`Supersession`]) is rendered as a single "Renamed" entry
+//! naming both ids instead of an unrelated removal and addition.
+
+use std::collections::BTreeMap;
+
+use crate::diff::{ConstraintChange, RequirementChange, SpecDiff, StrictnessChange, Supersession};
+use crate::spec::Requirement;
+
+/// The area a tagless requirement's changes are grouped under.
+const UNTAGGED: &str = "General";
+
+/// Renders `diff` as Markdown: one `##` section per requirement area,
+/// each a bullet list of added/removed/modified requirements, plus a
+/// trailing `## Constraints` section for constraint changes (constraints
+/// have no tags to group by). Returns `"No changes.\n"` if `diff` is
+/// empty.
+pub fn render_markdown(diff: &SpecDiff) -> String {
+    let mut areas: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for req in &diff.added_requirements {
+        areas
+            .entry(area(req))
+            .or_default()
+            .push(format!("- **Added** {}: {}", req.id, req.text));
+    }
+    for req in &diff.removed_requirements {
+        areas
+            .entry(area(req))
+            .or_default()
+            .push(format!("- **Removed** {}: {}", req.id, req.text));
+    }
+    for change in &diff.modified_requirements {
+        areas
+            .entry(area(&change.after))
+            .or_default()
+            .push(format_requirement_change(change));
+    }
+    for supersession in &diff.superseded_requirements {
+        areas
+            .entry(area(&supersession.after))
+            .or_default()
+            .push(format_supersession(supersession));
+    }
+
+    let mut constraints = Vec::new();
+    for c in &diff.added_constraints {
+        constraints.push(format!("- **Added** {}: {}", c.code, c.text));
+    }
+    for c in &diff.removed_constraints {
+        constraints.push(format!("- **Removed** {}: {}", c.code, c.text));
+    }
+    for change in &diff.modified_constraints {
+        constraints.push(format_constraint_change(change));
+    }
+
+    if areas.is_empty() && constraints.is_empty() {
+        return "No changes.\n".to_string();
+    }
+
+    let mut out = String::new();
+    for (area_name, entries) in areas {
+        out.push_str(&format!("## {area_name}\n\n"));
+        for entry in entries {
+            out.push_str(&entry);
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    if !constraints.is_empty() {
+        out.push_str("## Constraints\n\n");
+        for entry in constraints {
+            out.push_str(&entry);
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// `req`'s first tag, or [`UNTAGGED`] if it has none.
+fn area(req: &Requirement) -> &str {
+    req.tags.first().map(String::as_str).unwrap_or(UNTAGGED)
+}
+
+fn format_requirement_change(change: &RequirementChange) -> String {
+    if change.before.text == change.after.text {
+        format!("- **Modified** {} (metadata changed)", change.id)
+    } else {
+        format!(
+            "- **Modified** {}: \"{}\" -> \"{}\"",
+            change.id, change.before.text, change.after.text
+        )
+    }
+}
+
+fn format_supersession(supersession: &Supersession) -> String {
+    let note = if supersession.signature_changed {
+        " (method renamed)"
+    } else {
+        ""
+    };
+    format!(
+        "- **Renamed** {} -> {}{}",
+        supersession.old_id, supersession.new_id, note
+    )
+}
+
+fn format_constraint_change(change: &ConstraintChange) -> String {
+    let note = match change.strictness {
+        StrictnessChange::Tightened => " (tightened)",
+        StrictnessChange::Loosened => " (loosened)",
+        StrictnessChange::Unchanged => "",
+    };
+    if change.before.text == change.after.text {
+        format!("- **Modified** {}{}", change.code, note)
+    } else {
+        format!(
+            "- **Modified** {}: \"{}\" -> \"{}\"{}",
+            change.code, change.before.text, change.after.text, note
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::diff;
+    use crate::spec::{Constraint, Severity, SpecDocument};
+
+    fn req(id: &str, text: &str, tags: &[&str]) -> Requirement {
+        Requirement {
+            id: id.into(),
+            text: text.into(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reports_no_changes_for_an_identical_pair() {
+        let doc = SpecDocument::new();
+        assert_eq!(render_markdown(&diff(&doc, &doc)), "No changes.\n");
+    }
+
+    #[test]
+    fn groups_an_added_requirement_under_its_first_tag() {
+        let before = SpecDocument::new();
+        let mut after = SpecDocument::new();
+        after
+            .requirements
+            .push(req("REQ-004", "balance() returns the current balance", &["accounts"]));
+
+        let rendered = render_markdown(&diff(&before, &after));
+        assert!(rendered.contains("## accounts\n"));
+        assert!(rendered.contains("- **Added** REQ-004: balance() returns the current balance"));
+    }
+
+    #[test]
+    fn groups_a_tagless_requirement_under_general() {
+        let before = SpecDocument::new();
+        let mut after = SpecDocument::new();
+        after
+            .requirements
+            .push(req("REQ-004", "balance() returns the current balance", &[]));
+
+        let rendered = render_markdown(&diff(&before, &after));
+        assert!(rendered.contains("## General\n"));
+    }
+
+    #[test]
+    fn inlines_the_text_delta_for_a_reworded_requirement() {
+        let mut before = SpecDocument::new();
+        before
+            .requirements
+            .push(req("REQ-004", "balance() returns the current account balance", &["accounts"]));
+        let mut after = SpecDocument::new();
+        after
+            .requirements
+            .push(req("REQ-004", "balance() returns the current balance", &["accounts"]));
+
+        let rendered = render_markdown(&diff(&before, &after));
+        assert!(rendered.contains(
+            "- **Modified** REQ-004: \"balance() returns the current account balance\" -> \"balance() returns the current balance\""
+        ));
+    }
+
+    #[test]
+    fn renders_a_renumbering_as_a_single_renamed_entry() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(Requirement {
+            replaced_by: Some("REQ-012".into()),
+            ..req("REQ-004", "balance() returns the current balance", &["accounts"])
+        });
+        let mut after = SpecDocument::new();
+        after
+            .requirements
+            .push(req("REQ-012", "balance() returns the current balance", &["accounts"]));
+
+        let rendered = render_markdown(&diff(&before, &after));
+        assert!(rendered.contains("- **Renamed** REQ-004 -> REQ-012\n"));
+        assert!(!rendered.contains("**Added**"));
+        assert!(!rendered.contains("**Removed**"));
+    }
+
+    #[test]
+    fn renders_removed_and_tightened_constraints_under_their_own_section() {
+        let mut before = SpecDocument::new();
+        before.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            severity: Severity::Warning,
+            ..Default::default()
+        });
+        before.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "account must exist".into(),
+            ..Default::default()
+        });
+        let mut after = SpecDocument::new();
+        after.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            severity: Severity::Error,
+            ..Default::default()
+        });
+
+        let rendered = render_markdown(&diff(&before, &after));
+        assert!(rendered.contains("## Constraints\n"));
+        assert!(rendered.contains("- **Modified** CONST-001 (tightened)"));
+        assert!(rendered.contains("- **Removed** CONST-002: account must exist"));
+    }
+}
@@ -0,0 +1,146 @@
+//! Exports the spec's [`DataType`] declarations as an Alloy module: one
+//! `sig` per data type with its fields as relations, and a `fact` per
+//! field constraint this module knows how to translate (currently:
+//! "unique" constraint text, translated into a no-two-instances-agree
+//! fact) — so a relational property like "account IDs are unique across
+//! every operation" can be checked with the Alloy Analyzer's bounded
+//! model finder instead of only asserted in prose. A constraint this
+//! module doesn't recognize is still listed in a trailing comment, the
+//! same best-effort spirit as [`super::tla`] skipping a constraint it
+//! can't state as a `StateMachine` invariant.
+
+use crate::spec::{DataType, SpecDocument};
+
+/// Generates an Alloy module: a shared `Str`/`Bool` signature every field
+/// type maps onto (Alloy has no built-in string or boolean sort), then
+/// one `sig {Name} { field: one {sort}, ... }` per [`DataType`] in `doc`,
+/// in spec order, each followed by a `fact` for every field constraint
+/// whose text reads as a uniqueness rule.
+pub fn generate(doc: &SpecDocument) -> String {
+    let mut out = String::new();
+    out.push_str("module spec\n\n");
+    out.push_str("sig Str {}\nabstract sig Bool {}\none sig True, False extends Bool {}\n\n");
+
+    for dt in &doc.data_types {
+        out.push_str(&generate_sig(doc, dt));
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Renders one data type's `sig` declaration plus a `fact` per field
+/// constraint recognized as a uniqueness rule. A constraint attached to a
+/// field but not recognized is listed in a trailing comment instead of
+/// silently dropped.
+fn generate_sig(doc: &SpecDocument, dt: &DataType) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("sig {} {{\n", dt.name));
+    let fields: Vec<String> =
+        dt.fields.iter().map(|f| format!("    {}: one {}", f.name, alloy_type(&f.ty))).collect();
+    out.push_str(&fields.join(",\n"));
+    out.push_str("\n}\n");
+
+    let mut skipped = Vec::new();
+    for field in &dt.fields {
+        for code in &field.constraints {
+            let Some(constraint) = doc.constraint(code) else {
+                continue;
+            };
+            if constraint.text.to_lowercase().contains("unique") {
+                out.push_str(&format!(
+                    "\nfact {}_{}_unique {{\n    no disj a, b: {} | a.{} = b.{}\n}}\n",
+                    dt.name, field.name, dt.name, field.name, field.name
+                ));
+            } else {
+                skipped.push(code.clone());
+            }
+        }
+    }
+    if !skipped.is_empty() {
+        out.push_str("\n// Constraints not translated to a fact (not recognized as a uniqueness rule):\n");
+        for code in &skipped {
+            out.push_str(&format!("// - {code}\n"));
+        }
+    }
+
+    out
+}
+
+/// Maps a field's Rust type to the Alloy sort its relation ranges over:
+/// `Int` for anything numeric, the shared `Bool` signature for `bool`,
+/// and the shared `Str` signature for everything else (`String`, `&str`,
+/// or a type this module doesn't special-case).
+fn alloy_type(ty: &str) -> &'static str {
+    match ty {
+        "f64" | "f32" | "i32" | "u32" | "i64" | "u64" | "usize" | "isize" => "Int",
+        "bool" => "Bool",
+        _ => "Str",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, Field};
+
+    fn sample_doc() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-010".into(),
+            text: "account ids must be unique".into(),
+            ..Default::default()
+        });
+        doc.constraints.push(Constraint {
+            code: "CONST-011".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+        doc.data_types.push(DataType {
+            name: "Account".into(),
+            fields: vec![
+                Field { name: "id".into(), ty: "String".into(), constraints: vec!["CONST-010".into()] },
+                Field { name: "balance".into(), ty: "f64".into(), constraints: vec!["CONST-011".into()] },
+                Field { name: "frozen".into(), ty: "bool".into(), constraints: vec![] },
+            ],
+        });
+        doc
+    }
+
+    #[test]
+    fn declares_one_sig_per_data_type_with_mapped_field_sorts() {
+        let generated = generate(&sample_doc());
+        assert!(generated.contains("sig Account {"));
+        assert!(generated.contains("id: one Str"));
+        assert!(generated.contains("balance: one Int"));
+        assert!(generated.contains("frozen: one Bool"));
+    }
+
+    #[test]
+    fn declares_the_shared_str_and_bool_signatures() {
+        let generated = generate(&sample_doc());
+        assert!(generated.contains("sig Str {}"));
+        assert!(generated.contains("abstract sig Bool {}"));
+        assert!(generated.contains("one sig True, False extends Bool {}"));
+    }
+
+    #[test]
+    fn translates_a_unique_constraint_into_a_no_disjoint_pair_fact() {
+        let generated = generate(&sample_doc());
+        assert!(generated.contains("fact Account_id_unique {\n    no disj a, b: Account | a.id = b.id\n}"));
+    }
+
+    #[test]
+    fn lists_an_unrecognized_constraint_instead_of_translating_it() {
+        let generated = generate(&sample_doc());
+        assert!(!generated.contains("CONST-011_unique"));
+        assert!(generated.contains("// - CONST-011"));
+    }
+
+    #[test]
+    fn returns_just_the_shared_signatures_for_a_document_with_no_data_types() {
+        let generated = generate(&SpecDocument::new());
+        assert!(!generated.contains("sig Account"));
+        assert!(generated.contains("sig Str {}"));
+    }
+}
@@ -0,0 +1,147 @@
+//! Generates a `cdylib` shim bridging a C implementation of the header
+//! [`c_header::generate`](super::c_header::generate) emits to the same
+//! one-JSON-value-per-operation ABI `libspec_harness::StdioProcess`/
+//! `HttpTransport`/`WasmHost` speak, so a harness loader can `dlopen` a
+//! C/C++ implementation and run it through the same conformance suite
+//! as a native Rust one. Paired on the harness side by
+//! `libspec_harness::CShimHost`.
+//!
+//! The generated shim exposes four `extern "C"` entry points, fixed
+//! regardless of `prefix` so a generic loader can look them up by name:
+//! `libspec_shim_new`/`libspec_shim_free_handle` (wrapping
+//! `{prefix}_new`/`{prefix}_free`), `libspec_shim_call` (parses a
+//! `{"method": "..."}` request, calls the matching `{prefix}_{method}`,
+//! and writes back `{"ok": null}`/`{"err": {...}}`), and
+//! `libspec_shim_free` (releases a response buffer `libspec_shim_call`
+//! allocated).
+
+use crate::spec::SpecDocument;
+
+use super::method_name;
+
+/// Generates Rust source for the shim crate described in the [module
+/// docs](self). A C function's nonzero return is mapped back to the
+/// constraint at that position in `doc.constraints`, mirroring the
+/// discriminant order [`c_header::generate`](super::c_header::generate)'s
+/// error enum assigns (`{prefix}_OK = 0`, then one `{prefix}_ERR_{CODE}`
+/// per constraint in order).
+pub fn generate(doc: &SpecDocument, prefix: &str) -> String {
+    let handle_type = format!("{prefix}_t");
+
+    let mut out = String::new();
+    out.push_str("extern \"C\" {\n");
+    out.push_str(&format!("    fn {prefix}_new() -> *mut {handle_type};\n"));
+    out.push_str(&format!("    fn {prefix}_free(handle: *mut {handle_type});\n"));
+    for req in &doc.requirements {
+        let name = method_name(req);
+        out.push_str(&format!(
+            "    fn {prefix}_{name}(handle: *mut {handle_type}) -> i32;\n"
+        ));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str("#[no_mangle]\n");
+    out.push_str(&format!(
+        "pub unsafe extern \"C\" fn libspec_shim_new() -> *mut std::ffi::c_void {{\n    {prefix}_new() as *mut std::ffi::c_void\n}}\n\n"
+    ));
+
+    out.push_str("#[no_mangle]\n");
+    out.push_str(&format!(
+        "pub unsafe extern \"C\" fn libspec_shim_free_handle(handle: *mut std::ffi::c_void) {{\n    {prefix}_free(handle as *mut {handle_type})\n}}\n\n"
+    ));
+
+    out.push_str("/// Maps a C function's return value to the constraint it signals, `\"\"`\n/// on success.\n");
+    out.push_str("fn error_code_for(result: i32) -> &'static str {\n    match result {\n");
+    for (i, c) in doc.constraints.iter().enumerate() {
+        out.push_str(&format!("        {} => \"{}\",\n", i + 1, c.code));
+    }
+    out.push_str("        _ => \"IO\",\n    }\n}\n\n");
+
+    out.push_str("unsafe fn write_response(out_ptr: *mut *mut u8, out_len: *mut usize, value: &serde_json::Value) -> i32 {\n");
+    out.push_str("    let mut bytes = serde_json::to_vec(value).expect(\"response serialization is infallible\");\n");
+    out.push_str("    *out_len = bytes.len();\n");
+    out.push_str("    *out_ptr = bytes.as_mut_ptr();\n");
+    out.push_str("    std::mem::forget(bytes);\n");
+    out.push_str("    0\n}\n\n");
+
+    out.push_str("/// Parses a `{\"method\": \"...\"}` request, calls the matching C\n");
+    out.push_str("/// function on `handle`, and writes `{\"ok\": null}`/`{\"err\": {...}}`\n");
+    out.push_str("/// into a buffer the caller reads out of `out_ptr`/`out_len` and\n");
+    out.push_str("/// releases via [`libspec_shim_free`]. Always returns `0`; a request\n");
+    out.push_str("/// that doesn't parse or names no known method is reported as an\n");
+    out.push_str("/// `\"IO\"`-coded error in the response, not a nonzero return.\n");
+    out.push_str("#[no_mangle]\n");
+    out.push_str(
+        "pub unsafe extern \"C\" fn libspec_shim_call(\n    handle: *mut std::ffi::c_void,\n    request_ptr: *const u8,\n    request_len: usize,\n    out_ptr: *mut *mut u8,\n    out_len: *mut usize,\n) -> i32 {\n",
+    );
+    out.push_str("    let request = std::slice::from_raw_parts(request_ptr, request_len);\n");
+    out.push_str("    let request: serde_json::Value = match serde_json::from_slice(request) {\n");
+    out.push_str("        Ok(v) => v,\n");
+    out.push_str("        Err(_) => {\n");
+    out.push_str("            return write_response(out_ptr, out_len, &serde_json::json!({\"err\": {\"code\": \"IO\", \"requirement\": null, \"message\": \"invalid request\", \"details\": null}}));\n");
+    out.push_str("        }\n    };\n");
+    out.push_str(&format!("    let handle = handle as *mut {handle_type};\n"));
+    out.push_str("    let name = request[\"method\"].as_str().unwrap_or_default();\n");
+    out.push_str("    let result = match name {\n");
+    for req in &doc.requirements {
+        let name = method_name(req);
+        out.push_str(&format!("        \"{name}\" => {prefix}_{name}(handle),\n"));
+    }
+    out.push_str("        _ => {\n");
+    out.push_str("            return write_response(out_ptr, out_len, &serde_json::json!({\"err\": {\"code\": \"IO\", \"requirement\": null, \"message\": \"unknown method\", \"details\": null}}));\n");
+    out.push_str("        }\n    };\n\n");
+    out.push_str("    let response = if result == 0 {\n        serde_json::json!({\"ok\": null})\n    } else {\n");
+    out.push_str("        serde_json::json!({\"err\": {\"code\": error_code_for(result), \"requirement\": null, \"message\": \"\", \"details\": null}})\n    };\n");
+    out.push_str("    write_response(out_ptr, out_len, &response)\n}\n\n");
+
+    out.push_str("/// Releases a response buffer [`libspec_shim_call`] allocated.\n");
+    out.push_str("#[no_mangle]\n");
+    out.push_str("pub unsafe extern \"C\" fn libspec_shim_free(ptr: *mut u8, len: usize) {\n");
+    out.push_str("    drop(Vec::from_raw_parts(ptr, len, len));\n");
+    out.push_str("}\n");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, Requirement};
+
+    #[test]
+    fn declares_one_extern_fn_per_requirement_and_wraps_new_free() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "bankapi");
+        assert!(generated.contains("fn bankapi_new() -> *mut bankapi_t;"));
+        assert!(generated.contains("fn bankapi_free(handle: *mut bankapi_t);"));
+        assert!(generated.contains("fn bankapi_balance(handle: *mut bankapi_t) -> i32;"));
+        assert!(generated.contains("pub unsafe extern \"C\" fn libspec_shim_new() -> *mut std::ffi::c_void {"));
+        assert!(generated.contains("bankapi_new() as *mut std::ffi::c_void"));
+        assert!(generated.contains("\"balance\" => bankapi_balance(handle),"));
+    }
+
+    #[test]
+    fn maps_a_nonzero_result_to_the_constraint_at_its_position() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            ..Default::default()
+        });
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "insufficient funds".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "bankapi");
+        assert!(generated.contains("1 => \"CONST-001\","));
+        assert!(generated.contains("2 => \"CONST-002\","));
+    }
+}
@@ -0,0 +1,103 @@
+//! Generates a C header backend: an opaque handle type, one function
+//! prototype per requirement, and an error-code enum mapping to constraint
+//! ids, so an embedded/C team can implement the same contract as the Rust
+//! side without a shared runtime.
+
+use crate::spec::SpecDocument;
+
+use super::method_name;
+
+/// Generates a C header guarded against double inclusion: `typedef struct
+/// {prefix}_t {prefix}_t;` for the opaque handle, `{prefix}_error_t` (an
+/// `enum`, `{PREFIX}_OK` plus one `{PREFIX}_ERR_{CODE}` member per
+/// constraint), and `{prefix}_error_t {prefix}_{method}({prefix}_t
+/// *handle);` per requirement, commented with the requirement's id and
+/// text.
+pub fn generate(doc: &SpecDocument, prefix: &str) -> String {
+    let upper = prefix.to_uppercase();
+    let guard = format!("{upper}_H");
+    let handle_type = format!("{prefix}_t");
+    let error_type = format!("{prefix}_error_t");
+
+    let mut out = String::new();
+    out.push_str(&format!("#ifndef {guard}\n#define {guard}\n\n"));
+    out.push_str(&format!("typedef struct {handle_type} {handle_type};\n\n"));
+
+    out.push_str(&format!("typedef enum {{\n    {upper}_OK = 0,\n"));
+    for c in &doc.constraints {
+        out.push_str(&format!(
+            "    {upper}_ERR_{}, /* {}: {} */\n",
+            screaming_snake(&c.code),
+            c.code,
+            c.text
+        ));
+    }
+    out.push_str(&format!("}} {error_type};\n\n"));
+
+    for req in &doc.requirements {
+        out.push_str(&format!("/* {}: {} */\n", req.id, req.text));
+        out.push_str(&format!(
+            "{error_type} {prefix}_{}({handle_type} *handle);\n\n",
+            method_name(req)
+        ));
+    }
+
+    out.push_str(&format!("#endif /* {guard} */\n"));
+    out
+}
+
+/// `CONST-002` -> `CONST_002`: upper-cased with non-alphanumerics turned
+/// into `_`, matching C enum-member convention.
+fn screaming_snake(code: &str) -> String {
+    code.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, Requirement};
+
+    #[test]
+    fn generates_handle_and_include_guard() {
+        let doc = SpecDocument::new();
+        let generated = generate(&doc, "bankapi");
+        assert!(generated.contains("#ifndef BANKAPI_H"));
+        assert!(generated.contains("typedef struct bankapi_t bankapi_t;"));
+        assert!(generated.contains("#endif /* BANKAPI_H */"));
+    }
+
+    #[test]
+    fn generates_one_prototype_per_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "bankapi");
+        assert!(generated.contains("/* REQ-004: balance() returns the current balance */"));
+        assert!(generated.contains("bankapi_error_t bankapi_balance(bankapi_t *handle);"));
+    }
+
+    #[test]
+    fn generates_one_error_member_per_constraint() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "insufficient funds".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "bankapi");
+        assert!(generated.contains("BANKAPI_ERR_CONST_002, /* CONST-002: insufficient funds */"));
+    }
+}
@@ -0,0 +1,249 @@
+//! Generates `Checked<T>`, a wrapper that implements a spec'd trait by
+//! delegating every method to `T` and then verifying every *state
+//! invariant* constraint against `T` itself — one whose `expr` compares
+//! only 0-arg method calls, e.g. `balance() == sum_of_history()`, since
+//! that's a statement about the wrapped type's state rather than about
+//! one call's arguments. A constraint whose `expr` mentions a bare
+//! identifier (an argument, not a 0-arg call) or a call with arguments
+//! isn't a state invariant in this sense — see
+//! [`rust_guard`](super::rust_guard) for generating a standalone check
+//! over those instead — and is skipped, with the reason recorded in the
+//! returned [`CheckedReport`].
+
+use crate::spec::{Comparison, Constraint, ConstraintExpr, RelOp, SpecDocument, Term};
+
+use super::{method_name, severity_variant};
+
+/// One constraint [`generate`] didn't derive a state-invariant check for,
+/// and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedInvariant {
+    pub constraint: String,
+    pub reason: String,
+}
+
+/// Everything [`generate`] found out while building `Checked<T>`: which
+/// constraints became invariant checks, and which it skipped.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CheckedReport {
+    pub checked: Vec<String>,
+    pub skipped: Vec<SkippedInvariant>,
+}
+
+/// Generates `pub struct {struct_name}<T> { pub inner: T }` and `impl<T:
+/// {trait_name}> {trait_name} for {struct_name}<T>`: every requirement's
+/// method delegates to `self.inner`, then calls `self.check_invariants()`
+/// before returning, so a violation surfaces at the call after the state
+/// went bad rather than silently downstream. Requirements are assumed to
+/// take no arguments and return `Result<(), ::libspec::error::SpecError>`,
+/// the same convention [`rust_adapter`](super::rust_adapter) uses, since
+/// the spec doesn't model a method's real signature.
+pub fn generate(doc: &SpecDocument, trait_name: &str, struct_name: &str) -> (String, CheckedReport) {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "/// Wraps a `{trait_name}` implementation, checking its state invariants after every call.\npub struct {struct_name}<T> {{\n    pub inner: T,\n}}\n\n"
+    ));
+
+    out.push_str(&format!("impl<T: {trait_name}> {trait_name} for {struct_name}<T> {{\n"));
+    for req in &doc.requirements {
+        let name = method_name(req);
+        out.push_str(&format!("    /// {}: {}\n", req.id, req.text));
+        out.push_str(&format!(
+            "    fn {name}(&self) -> Result<(), ::libspec::error::SpecError> {{\n        self.inner.{name}()?;\n        self.check_invariants()\n    }}\n"
+        ));
+    }
+    out.push_str("}\n\n");
+
+    let (checks, report) = invariant_checks(doc);
+    out.push_str(&format!("impl<T: {trait_name}> {struct_name}<T> {{\n"));
+    out.push_str("    /// Runs every state invariant derived from the spec's constraints.\n");
+    out.push_str("    fn check_invariants(&self) -> Result<(), ::libspec::error::SpecError> {\n");
+    if checks.is_empty() {
+        out.push_str("        // no state invariants derived from the spec\n");
+    } else {
+        out.push_str(&checks);
+    }
+    out.push_str("        Ok(())\n    }\n}\n");
+
+    (out, report)
+}
+
+fn invariant_checks(doc: &SpecDocument) -> (String, CheckedReport) {
+    let mut out = String::new();
+    let mut report = CheckedReport::default();
+
+    for constraint in &doc.constraints {
+        let Some(expr_src) = &constraint.expr else { continue };
+        match ConstraintExpr::parse(expr_src) {
+            Ok(expr) => match state_invariant_cond(&expr) {
+                Some(cond) => {
+                    out.push_str(&check_block(constraint, &cond));
+                    report.checked.push(constraint.code.clone());
+                }
+                None => report.skipped.push(SkippedInvariant {
+                    constraint: constraint.code.clone(),
+                    reason: "expr uses an argument or a call with arguments, not a 0-arg method call"
+                        .to_string(),
+                }),
+            },
+            Err(e) => report.skipped.push(SkippedInvariant {
+                constraint: constraint.code.clone(),
+                reason: format!("expr failed to parse: {e}"),
+            }),
+        }
+    }
+
+    (out, report)
+}
+
+/// Renders `expr` as a Rust condition if every term in it is a number or
+/// a 0-arg call, `None` if it mentions a bare identifier or a call with
+/// arguments.
+fn state_invariant_cond(expr: &ConstraintExpr) -> Option<String> {
+    match expr {
+        ConstraintExpr::Compare(c) => comparison_cond(c),
+        ConstraintExpr::And(a, b) => Some(format!("({}) && ({})", state_invariant_cond(a)?, state_invariant_cond(b)?)),
+        ConstraintExpr::Or(a, b) => Some(format!("({}) || ({})", state_invariant_cond(a)?, state_invariant_cond(b)?)),
+    }
+}
+
+fn comparison_cond(comparison: &Comparison) -> Option<String> {
+    Some(format!(
+        "{} {} {}",
+        term_cond(&comparison.lhs)?,
+        rel_op_str(comparison.op),
+        term_cond(&comparison.rhs)?
+    ))
+}
+
+fn term_cond(term: &Term) -> Option<String> {
+    match term {
+        Term::Number(n) => Some(format_float(*n)),
+        Term::Ident(_) => None,
+        Term::Call(name, args) if args.is_empty() => Some(format!("self.inner.{name}()?")),
+        Term::Call(..) => None,
+        Term::Add(a, b) => Some(format!("({}) + ({})", term_cond(a)?, term_cond(b)?)),
+        Term::Sub(a, b) => Some(format!("({}) - ({})", term_cond(a)?, term_cond(b)?)),
+        Term::Mul(a, b) => Some(format!("({}) * ({})", term_cond(a)?, term_cond(b)?)),
+        Term::Div(a, b) => Some(format!("({}) / ({})", term_cond(a)?, term_cond(b)?)),
+    }
+}
+
+fn format_float(n: f64) -> String {
+    let s = n.to_string();
+    if s.contains('.') {
+        s
+    } else {
+        format!("{s}.0")
+    }
+}
+
+fn rel_op_str(op: RelOp) -> &'static str {
+    match op {
+        RelOp::Gt => ">",
+        RelOp::Lt => "<",
+        RelOp::Ge => ">=",
+        RelOp::Le => "<=",
+        RelOp::Eq => "==",
+        RelOp::Ne => "!=",
+    }
+}
+
+fn check_block(constraint: &Constraint, cond: &str) -> String {
+    let code = &constraint.code;
+    let severity = severity_variant(constraint.severity);
+    format!(
+        "        ::libspec::runtime::enforce(\"{code}\", \"{}\", ::libspec::spec::Severity::{severity}, !({cond}))?;\n",
+        constraint.text
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Requirement, Severity};
+
+    fn req(id: &str, text: &str) -> Requirement {
+        Requirement { id: id.into(), text: text.into(), ..Default::default() }
+    }
+
+    fn constraint(code: &str, text: &str, expr: &str) -> Constraint {
+        Constraint {
+            code: code.into(),
+            text: text.into(),
+            expr: Some(expr.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn delegates_every_requirement_and_checks_invariants_after() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-004", "balance() returns the current balance"));
+
+        let (generated, _) = generate(&doc, "BankApi", "Checked");
+        assert!(generated.contains("pub struct Checked<T> {\n    pub inner: T,\n}"));
+        assert!(generated.contains("impl<T: BankApi> BankApi for Checked<T> {"));
+        assert!(generated.contains(
+            "fn balance(&self) -> Result<(), ::libspec::error::SpecError> {\n        self.inner.balance()?;\n        self.check_invariants()\n    }"
+        ));
+    }
+
+    #[test]
+    fn derives_an_invariant_from_a_comparison_of_two_0_arg_calls() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(constraint(
+            "CONST-010",
+            "balance equals sum of history",
+            "balance() == sum_of_history()",
+        ));
+
+        let (generated, report) = generate(&doc, "BankApi", "Checked");
+        assert!(generated.contains(
+            "::libspec::runtime::enforce(\"CONST-010\", \"balance equals sum of history\", ::libspec::spec::Severity::Error, !(self.inner.balance()? == self.inner.sum_of_history()?))?;"
+        ));
+        assert_eq!(report.checked, vec!["CONST-010"]);
+        assert!(report.skipped.is_empty());
+    }
+
+    #[test]
+    fn warning_severity_reports_instead_of_returning_err() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            severity: Severity::Warning,
+            ..constraint("CONST-011", "balance should stay under the soft cap", "balance() <= 1000")
+        });
+
+        let (generated, _) = generate(&doc, "BankApi", "Checked");
+        assert!(generated.contains(
+            "::libspec::runtime::enforce(\"CONST-011\", \"balance should stay under the soft cap\", ::libspec::spec::Severity::Warning, !(self.inner.balance()? <= 1000.0))?;"
+        ));
+    }
+
+    #[test]
+    fn skips_a_constraint_whose_expr_is_about_an_argument_not_state() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(constraint("CONST-001", "amount must be positive", "amount > 0"));
+
+        let (generated, report) = generate(&doc, "BankApi", "Checked");
+        assert!(generated.contains("// no state invariants derived from the spec"));
+        assert_eq!(
+            report.skipped,
+            vec![SkippedInvariant {
+                constraint: "CONST-001".into(),
+                reason: "expr uses an argument or a call with arguments, not a 0-arg method call".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn skips_a_constraint_with_an_unparseable_expr() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(constraint("CONST-099", "bad constraint", "amount >"));
+
+        let (_, report) = generate(&doc, "BankApi", "Checked");
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].constraint, "CONST-099");
+        assert!(report.skipped[0].reason.starts_with("expr failed to parse"));
+    }
+}
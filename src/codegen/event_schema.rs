@@ -0,0 +1,183 @@
+//! Generates serde Rust types plus Avro and JSON Schema artifacts for the
+//! spec's declared [`DomainEvent`]s, so an event-driven system's messages
+//! carry the same machine-checked contract [`rust_dto`](super::rust_dto)
+//! and [`json_schema`](super::json_schema) give request/response APIs.
+
+use crate::spec::{DomainEvent, SpecDocument};
+
+/// Generates, per named event in `doc`: a `#[derive(Debug, Clone, Serialize,
+/// Deserialize)] pub struct {name}` with one public field per declared
+/// field. Unlike [`rust_dto::generate`](super::rust_dto::generate), there's
+/// no builder or error enum — an event is a fact that already happened, not
+/// an input to validate on the way in.
+pub fn generate_rust(doc: &SpecDocument, event_names: &[&str]) -> String {
+    let mut out = String::new();
+    for name in event_names {
+        let Some(event) = doc.event(name) else {
+            continue;
+        };
+        out.push_str("#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]\n");
+        out.push_str(&format!("pub struct {} {{\n", event.name));
+        for field in &event.fields {
+            out.push_str(&format!("    pub {}: {},\n", field.name, field.ty));
+        }
+        out.push_str("}\n\n");
+    }
+    out
+}
+
+/// Generates a JSON array of Avro record schemas, one per named event in
+/// `doc`, typed via [`avro_type`] — Avro parses a schema file containing
+/// several named types as exactly such an array.
+pub fn generate_avro(doc: &SpecDocument, event_names: &[&str]) -> String {
+    let mut records = Vec::new();
+    for name in event_names {
+        if let Some(event) = doc.event(name) {
+            records.push(avro_record(event));
+        }
+    }
+    serde_json::to_string_pretty(&records).expect("avro schema array serialization is infallible")
+}
+
+/// One Avro `record` schema for `event`: one field per declared field,
+/// typed via [`avro_type`].
+fn avro_record(event: &DomainEvent) -> serde_json::Value {
+    let fields: Vec<_> = event
+        .fields
+        .iter()
+        .map(|field| serde_json::json!({ "name": field.name, "type": avro_type(&field.ty) }))
+        .collect();
+    serde_json::json!({
+        "type": "record",
+        "name": event.name,
+        "fields": fields,
+    })
+}
+
+/// Maps a Rust-ish field type (as declared on a [`DomainEvent`] field) to
+/// the closest Avro primitive type, falling back to `"string"` for
+/// anything it doesn't recognize — the same fallback
+/// [`json_schema::json_schema_type`](super::json_schema) uses for JSON Schema.
+fn avro_type(ty: &str) -> &str {
+    match ty {
+        "f64" => "double",
+        "f32" => "float",
+        "i32" | "u32" => "int",
+        "i64" | "u64" | "usize" | "isize" => "long",
+        "bool" => "boolean",
+        "String" | "str" | "&str" => "string",
+        _ => "string",
+    }
+}
+
+/// Generates a single JSON Schema document (draft 2020-12), structured the
+/// same way [`json_schema::generate`](super::json_schema::generate) is: one
+/// `$defs` entry per named event, typed via [`json_schema_type`].
+/// `schema_id` becomes the document's `$id`.
+pub fn generate_json_schema(doc: &SpecDocument, schema_id: &str, event_names: &[&str]) -> String {
+    let mut defs = serde_json::Map::new();
+    for name in event_names {
+        if let Some(event) = doc.event(name) {
+            defs.insert(event.name.clone(), event_schema(event));
+        }
+    }
+
+    let document = serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": schema_id,
+        "$defs": defs,
+    });
+
+    serde_json::to_string_pretty(&document).expect("schema document serialization is infallible")
+}
+
+/// Renders `event` as an `object` schema: one required property per field,
+/// typed via [`json_schema_type`].
+fn event_schema(event: &DomainEvent) -> serde_json::Value {
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+    for field in &event.fields {
+        properties.insert(field.name.clone(), serde_json::json!({ "type": json_schema_type(&field.ty) }));
+        required.push(serde_json::Value::String(field.name.clone()));
+    }
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// Maps a Rust-ish field type (as declared on a [`DomainEvent`] field) to
+/// the closest JSON Schema primitive type, falling back to `"string"` for
+/// anything it doesn't recognize — same fallback
+/// [`super::json_schema`]'s own mapping uses.
+fn json_schema_type(ty: &str) -> &str {
+    match ty {
+        "f64" | "f32" => "number",
+        "i32" | "u32" | "i64" | "u64" | "usize" | "isize" => "integer",
+        "bool" => "boolean",
+        "String" | "str" | "&str" => "string",
+        _ => "string",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Field;
+
+    fn doc_with_funds_withdrawn() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.events.push(DomainEvent {
+            name: "FundsWithdrawn".into(),
+            fields: vec![
+                Field { name: "account_id".into(), ty: "String".into(), constraints: vec![] },
+                Field { name: "amount".into(), ty: "f64".into(), constraints: vec![] },
+            ],
+        });
+        doc
+    }
+
+    #[test]
+    fn generates_a_struct_per_named_event() {
+        let doc = doc_with_funds_withdrawn();
+        let generated = generate_rust(&doc, &["FundsWithdrawn"]);
+        assert!(generated.contains("pub struct FundsWithdrawn {"));
+        assert!(generated.contains("pub account_id: String,"));
+        assert!(generated.contains("pub amount: f64,"));
+    }
+
+    #[test]
+    fn skips_unknown_event_names() {
+        let doc = doc_with_funds_withdrawn();
+        let generated = generate_rust(&doc, &["NotAnEvent"]);
+        assert!(generated.is_empty());
+    }
+
+    #[test]
+    fn generates_an_avro_record_per_named_event() {
+        let doc = doc_with_funds_withdrawn();
+        let generated = generate_avro(&doc, &["FundsWithdrawn"]);
+        let records: serde_json::Value = serde_json::from_str(&generated).unwrap();
+        assert_eq!(records[0]["type"], "record");
+        assert_eq!(records[0]["name"], "FundsWithdrawn");
+        assert_eq!(records[0]["fields"][0]["name"], "account_id");
+        assert_eq!(records[0]["fields"][0]["type"], "string");
+        assert_eq!(records[0]["fields"][1]["type"], "double");
+    }
+
+    #[test]
+    fn generates_a_json_schema_def_per_named_event() {
+        let doc = doc_with_funds_withdrawn();
+        let generated = generate_json_schema(&doc, "https://example.com/bank.events.json", &["FundsWithdrawn"]);
+        let schema: serde_json::Value = serde_json::from_str(&generated).unwrap();
+
+        assert_eq!(schema["$id"], "https://example.com/bank.events.json");
+        assert_eq!(schema["$defs"]["FundsWithdrawn"]["properties"]["account_id"]["type"], "string");
+        assert_eq!(schema["$defs"]["FundsWithdrawn"]["properties"]["amount"]["type"], "number");
+        assert_eq!(
+            schema["$defs"]["FundsWithdrawn"]["required"],
+            serde_json::json!(["account_id", "amount"])
+        );
+    }
+}
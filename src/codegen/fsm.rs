@@ -0,0 +1,125 @@
+//! Generates a Rust state enum and transition function from an
+//! [`Fsm`](crate::spec::Fsm), plus a `proptest` strategy for generating an
+//! arbitrary state, so a finite-state machine declared in the spec drives
+//! both an implementation's types and its test generation instead of
+//! being re-typed by hand in both places.
+
+use crate::spec::SpecDocument;
+
+use super::capitalize;
+
+/// Generates `pub enum {name}State` with one variant per state in the
+/// [`Fsm`](crate::spec::Fsm) named `name`, a `pub fn {name}_step(state,
+/// event: &str) -> Option<{name}State>` implementing its transition
+/// table, and `pub fn arbitrary_{name}_state()`, a `proptest` strategy
+/// over every declared state.
+///
+/// Returns an empty string if `doc` has no FSM named `name`.
+pub fn generate(doc: &SpecDocument, name: &str) -> String {
+    let Some(fsm) = doc.fsm(name) else {
+        return String::new();
+    };
+
+    let enum_name = format!("{}State", capitalize(name));
+    let mut out = String::new();
+
+    out.push_str(&format!(
+        "/// States of the spec's `{name}` finite state machine.\n"
+    ));
+    out.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq)]\n");
+    out.push_str(&format!("pub enum {enum_name} {{\n"));
+    for state in &fsm.states {
+        out.push_str(&format!("    {state},\n"));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!(
+        "/// Applies `event` to `state` per the spec's `{name}` transitions,\n/// or `None` if there's no transition for this `(state, event)` pair.\n"
+    ));
+    out.push_str(&format!(
+        "pub fn {}_step(state: {enum_name}, event: &str) -> Option<{enum_name}> {{\n    match (state, event) {{\n",
+        name.to_lowercase()
+    ));
+    for t in &fsm.transitions {
+        out.push_str(&format!(
+            "        ({enum_name}::{}, \"{}\") => Some({enum_name}::{}),\n",
+            t.from, t.event, t.to
+        ));
+    }
+    out.push_str("        _ => None,\n    }\n}\n\n");
+
+    out.push_str(&format!(
+        "/// A `proptest` strategy generating an arbitrary `{enum_name}`.\n"
+    ));
+    out.push_str(&format!(
+        "pub fn arbitrary_{}_state() -> impl proptest::strategy::Strategy<Value = {enum_name}> {{\n    proptest::prelude::prop_oneof![\n",
+        name.to_lowercase()
+    ));
+    for state in &fsm.states {
+        out.push_str(&format!(
+            "        proptest::prelude::Just({enum_name}::{state}),\n"
+        ));
+    }
+    out.push_str("    ]\n}\n");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Fsm, FsmTransition};
+
+    fn account_doc() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.fsms.push(Fsm {
+            name: "Account".into(),
+            states: vec!["Open".into(), "Frozen".into(), "Closed".into()],
+            transitions: vec![
+                FsmTransition {
+                    from: "Open".into(),
+                    event: "freeze".into(),
+                    to: "Frozen".into(),
+                },
+                FsmTransition {
+                    from: "Frozen".into(),
+                    event: "unfreeze".into(),
+                    to: "Open".into(),
+                },
+            ],
+        });
+        doc
+    }
+
+    #[test]
+    fn generates_one_variant_per_state() {
+        let generated = generate(&account_doc(), "Account");
+        assert!(generated.contains("pub enum AccountState {"));
+        assert!(generated.contains("    Open,"));
+        assert!(generated.contains("    Frozen,"));
+        assert!(generated.contains("    Closed,"));
+    }
+
+    #[test]
+    fn generates_a_step_function_matching_the_transition_table() {
+        let generated = generate(&account_doc(), "Account");
+        assert!(generated.contains(
+            "pub fn account_step(state: AccountState, event: &str) -> Option<AccountState> {"
+        ));
+        assert!(generated.contains("(AccountState::Open, \"freeze\") => Some(AccountState::Frozen),"));
+        assert!(generated.contains("(AccountState::Frozen, \"unfreeze\") => Some(AccountState::Open),"));
+    }
+
+    #[test]
+    fn generates_an_arbitrary_state_strategy() {
+        let generated = generate(&account_doc(), "Account");
+        assert!(generated.contains("pub fn arbitrary_account_state() -> impl proptest::strategy::Strategy<Value = AccountState> {"));
+        assert!(generated.contains("proptest::prelude::Just(AccountState::Closed),"));
+    }
+
+    #[test]
+    fn returns_empty_string_for_an_unknown_fsm() {
+        let doc = SpecDocument::new();
+        assert_eq!(generate(&doc, "Account"), "");
+    }
+}
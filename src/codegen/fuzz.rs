@@ -0,0 +1,103 @@
+//! Generates a `cargo-fuzz` harness: an `Op` enum with one variant per
+//! requirement, decoded from arbitrary bytes via `arbitrary::Arbitrary`,
+//! and a `fuzz_target!` that replays a decoded sequence of operations
+//! against an implementation, leaving constraint-preserving invariants
+//! (e.g. "balance never goes negative") as a `TODO` for the one thing the
+//! spec doesn't give us: an executable check, since constraint text is
+//! free-form prose, not a predicate.
+//!
+//! Every decoded op is also run through [`crate::trace::record`] with the
+//! requirement it exercises, so a corpus that's been fuzzed for a while
+//! builds up the same requirement-coverage trace `#[covers]`/
+//! `#[implements]` do — [`crate::trace::fuzz_priority`] reads it back to
+//! tell which requirements and constraints the corpus barely touches,
+//! instead of only knowing which lines of code it hit.
+use crate::spec::SpecDocument;
+
+use super::method_name;
+
+/// Generates a ready-to-run `fuzz_targets/*.rs` file: `use
+/// libfuzzer_sys::fuzz_target`, an `Op` enum (one variant per requirement
+/// in `doc`, doc-commented with the requirement's id and text), and a
+/// `fuzz_target!(|ops: Vec<Op>| { ... })` body that constructs `impl_expr`
+/// once, calls the matching method for every decoded `Op`, and records
+/// which requirement it exercised via [`crate::trace::record`] so fuzzing
+/// contributes to requirement coverage the same way `#[covers]` does. A
+/// `TODO` comment per constraint marks where its invariant should be
+/// asserted.
+pub fn generate(doc: &SpecDocument, trait_name: &str, impl_expr: &str) -> String {
+    let mut out = String::new();
+    out.push_str("#![no_main]\n\n");
+    out.push_str("use arbitrary::Arbitrary;\n");
+    out.push_str("use libfuzzer_sys::fuzz_target;\n\n");
+
+    out.push_str(&format!(
+        "/// One `{trait_name}` operation, decoded from arbitrary fuzzer input.\n#[derive(Debug, Clone, Arbitrary)]\nenum Op {{\n"
+    ));
+    for req in &doc.requirements {
+        out.push_str(&format!("    /// {}: {}\n", req.id, req.text));
+        out.push_str(&format!("    {},\n", super::pascal_case_code(&method_name(req))));
+    }
+    out.push_str("}\n\n");
+
+    if !doc.constraints.is_empty() {
+        out.push_str("// TODO: assert these constraint-preserving invariants after each op:\n");
+        for c in &doc.constraints {
+            out.push_str(&format!("// - {}: {}\n", c.code, c.text));
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&format!(
+        "fuzz_target!(|ops: Vec<Op>| {{\n    let target = {impl_expr};\n    for op in ops {{\n        let _ = match op {{\n"
+    ));
+    for req in &doc.requirements {
+        let name = method_name(req);
+        out.push_str(&format!(
+            "            Op::{} => {{\n                libspec::trace::record(\"fuzz\", \"{name}\", \"{}\");\n                target.{name}()\n            }}\n",
+            super::pascal_case_code(&name),
+            req.id,
+        ));
+    }
+    out.push_str("        };\n    }\n});\n");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, Requirement};
+
+    #[test]
+    fn generates_one_op_variant_per_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankAPI", "BankLibrary::new()");
+        assert!(generated.contains("enum Op {"));
+        assert!(generated.contains("/// REQ-004: balance() returns the current balance"));
+        assert!(generated.contains("Balance,"));
+        assert!(generated.contains("let target = BankLibrary::new();"));
+        assert!(generated.contains("Op::Balance => {"));
+        assert!(generated.contains("libspec::trace::record(\"fuzz\", \"balance\", \"REQ-004\");"));
+        assert!(generated.contains("target.balance()"));
+    }
+
+    #[test]
+    fn lists_constraints_as_todo_invariants() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankAPI", "BankLibrary::new()");
+        assert!(generated.contains("// - CONST-001: amount must be positive"));
+    }
+}
@@ -0,0 +1,94 @@
+//! Generates a Go codegen backend: an interface with one method per
+//! requirement, sentinel error values per constraint, and a conformance
+//! test entry point, so a Go implementation can participate in the same
+//! spec as the Rust one.
+
+use crate::spec::SpecDocument;
+
+use super::{capitalize, method_name, pascal_case_code};
+
+/// Generates a Go source file: `package {package}`, `type {interface_name}
+/// interface` with one exported method per requirement (doc-commented with
+/// the requirement's id and text), a `var` block of `Err{Code}` sentinel
+/// errors per constraint, and a `TestConformance` entry point a Go
+/// implementation's own test suite can call to check it satisfies
+/// `{interface_name}`.
+pub fn generate(doc: &SpecDocument, package: &str, interface_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("package {package}\n\n"));
+    out.push_str("import (\n\t\"errors\"\n\t\"testing\"\n)\n\n");
+
+    out.push_str(&format!("type {interface_name} interface {{\n"));
+    for req in &doc.requirements {
+        out.push_str(&format!("\t// {}: {}\n", req.id, req.text));
+        out.push_str(&format!("\t{}() error\n", go_method_name(req)));
+    }
+    out.push_str("}\n\n");
+
+    if !doc.constraints.is_empty() {
+        out.push_str("var (\n");
+        for c in &doc.constraints {
+            out.push_str(&format!(
+                "\tErr{} = errors.New(\"{}: {}\")\n",
+                pascal_case_code(&c.code),
+                c.code,
+                c.text
+            ));
+        }
+        out.push_str(")\n\n");
+    }
+
+    out.push_str(&format!(
+        "// TestConformance checks that impl satisfies the spec {interface_name} was\n// generated from.\nfunc TestConformance(t *testing.T, impl {interface_name}) {{\n"
+    ));
+    for req in &doc.requirements {
+        out.push_str(&format!(
+            "\tif err := impl.{}(); err != nil {{\n\t\tt.Errorf(\"{}: %v\", err)\n\t}}\n",
+            go_method_name(req),
+            req.id
+        ));
+    }
+    out.push_str("}\n");
+
+    out
+}
+
+/// Go convention is exported (PascalCase) method names; [`method_name`]
+/// gives a Rust-style snake_case name, so capitalize its first letter.
+fn go_method_name(req: &crate::spec::Requirement) -> String {
+    capitalize(&method_name(req))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, Requirement};
+
+    #[test]
+    fn generates_interface_method_per_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "bankapi", "BankAPI");
+        assert!(generated.contains("type BankAPI interface {"));
+        assert!(generated.contains("// REQ-004: balance() returns the current balance"));
+        assert!(generated.contains("Balance() error"));
+    }
+
+    #[test]
+    fn generates_sentinel_error_per_constraint() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "insufficient funds".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "bankapi", "BankAPI");
+        assert!(generated.contains("ErrConst002 = errors.New(\"CONST-002: insufficient funds\")"));
+    }
+}
@@ -0,0 +1,104 @@
+//! Generates a blocking REST client calling the verb/path declared via
+//! [`Requirement::http_method`]/[`Requirement::http_path`] for each
+//! requirement, so exercising a live HTTP service doesn't mean hand-rolling
+//! the route for every call. Paired on the harness side by
+//! `libspec_harness::HttpTransport`, which drives the same routes against a
+//! running service in conformance mode.
+
+use crate::spec::SpecDocument;
+
+use super::method_name;
+
+/// Generates `pub struct {client_name}` wrapping a `base_url`, with one
+/// `pub fn {name}(&self) -> Result<(), ::libspec::error::SpecError>` per
+/// requirement that carries both [`Requirement::http_method`] and
+/// [`Requirement::http_path`]; a requirement missing either is skipped —
+/// there's no route a client can call it at. Each method sends an
+/// empty-body request of its declared verb to `base_url` joined with its
+/// path, and maps a non-2xx response's JSON body (parsed as a
+/// `::libspec::error::SpecError`) to `Err`, the same mapping
+/// [`crate::codegen::http_status::generate`]'s converters apply in the
+/// other direction on the server side. Generated code calls `ureq` with
+/// its `json` feature enabled — the same dependency
+/// `libspec_harness::HttpTransport` uses to drive these same routes in
+/// conformance mode.
+pub fn generate(doc: &SpecDocument, client_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("pub struct {client_name} {{\n    base_url: String,\n}}\n\n"));
+    out.push_str(&format!("impl {client_name} {{\n"));
+    out.push_str("    pub fn new(base_url: impl Into<String>) -> Self {\n        Self { base_url: base_url.into() }\n    }\n");
+
+    for req in &doc.requirements {
+        let (Some(method), Some(path)) = (&req.http_method, &req.http_path) else {
+            continue;
+        };
+        let name = method_name(req);
+        out.push_str(&format!("\n    /// {}: {}\n", req.id, req.text));
+        out.push_str(&format!(
+            "    pub fn {name}(&self) -> Result<(), ::libspec::error::SpecError> {{\n"
+        ));
+        out.push_str(&format!(
+            "        let url = format!(\"{{}}{path}\", self.base_url);\n"
+        ));
+        out.push_str(&format!(
+            "        match ::ureq::request(\"{method}\", &url).call() {{\n"
+        ));
+        out.push_str("            Ok(_) => Ok(()),\n");
+        out.push_str("            Err(::ureq::Error::Status(_, response)) => Err(response\n");
+        out.push_str("                .into_json::<::libspec::error::SpecError>()\n");
+        out.push_str("                .unwrap_or_else(|e| ::libspec::error::SpecError::new(\"IO\", format!(\"error body was not a SpecError: {e}\")))),\n");
+        out.push_str("            Err(e) => Err(::libspec::error::SpecError::new(\"IO\", e.to_string())),\n");
+        out.push_str("        }\n");
+        out.push_str("    }\n");
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn generates_one_method_per_requirement_with_a_route() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "deposit(amount) credits the account".into(),
+            http_method: Some("POST".into()),
+            http_path: Some("/accounts/{account_id}/deposit".into()),
+            ..Default::default()
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-005".into(),
+            text: "no route declared for this one".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankHttpClient");
+        assert!(generated.contains("pub struct BankHttpClient {"));
+        assert!(generated.contains("pub fn new(base_url: impl Into<String>) -> Self {"));
+        assert!(generated.contains("pub fn deposit(&self) -> Result<(), ::libspec::error::SpecError> {"));
+        assert!(generated.contains("::ureq::request(\"POST\", &url).call()"));
+        assert!(generated.contains("/accounts/{account_id}/deposit"));
+        assert!(!generated.contains("REQ-005"));
+    }
+
+    #[test]
+    fn maps_a_non_2xx_response_to_a_spec_error() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            http_method: Some("GET".into()),
+            http_path: Some("/accounts/{account_id}/balance".into()),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankHttpClient");
+        assert!(generated.contains("Err(::ureq::Error::Status(_, response)) => Err(response"));
+        assert!(generated.contains(".into_json::<::libspec::error::SpecError>()"));
+    }
+}
@@ -0,0 +1,91 @@
+//! Generates an HTTP status-code lookup plus axum/actix response
+//! converters from constraints annotated with
+//! [`Constraint::http_status`](crate::spec::Constraint::http_status), so a
+//! REST frontend over a spec'd API doesn't hand-roll "which constraint
+//! maps to which status" itself.
+
+use crate::spec::SpecDocument;
+
+/// Generates `pub fn http_status(code: &str) -> Option<u16>` plus an axum
+/// `IntoResponse` and an actix `ResponseError` impl for
+/// `::libspec::error::SpecError`, both mapping a violation to a response
+/// via that lookup. A constraint without an `http_status` falls through
+/// to `http_status`'s `None` arm, which both converters treat as a 500 —
+/// same "unannotated means unopinionated" default as an unmapped error in
+/// a hand-written frontend.
+pub fn generate(doc: &SpecDocument) -> String {
+    let mut out = String::new();
+    out.push_str("pub fn http_status(code: &str) -> Option<u16> {\n    match code {\n");
+    for c in &doc.constraints {
+        if let Some(status) = c.http_status {
+            out.push_str(&format!("        \"{}\" => Some({status}),\n", c.code));
+        }
+    }
+    out.push_str("        _ => None,\n    }\n}\n\n");
+
+    out.push_str("impl ::axum::response::IntoResponse for ::libspec::error::SpecError {\n");
+    out.push_str("    fn into_response(self) -> ::axum::response::Response {\n");
+    out.push_str("        let status = http_status(&self.code)\n");
+    out.push_str("            .and_then(|code| ::axum::http::StatusCode::from_u16(code).ok())\n");
+    out.push_str("            .unwrap_or(::axum::http::StatusCode::INTERNAL_SERVER_ERROR);\n");
+    out.push_str("        (status, ::axum::Json(self)).into_response()\n");
+    out.push_str("    }\n}\n\n");
+
+    out.push_str("impl ::actix_web::ResponseError for ::libspec::error::SpecError {\n");
+    out.push_str("    fn status_code(&self) -> ::actix_web::http::StatusCode {\n");
+    out.push_str("        http_status(&self.code)\n");
+    out.push_str("            .and_then(|code| ::actix_web::http::StatusCode::from_u16(code).ok())\n");
+    out.push_str(
+        "            .unwrap_or(::actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)\n",
+    );
+    out.push_str("    }\n}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Constraint;
+
+    #[test]
+    fn generates_a_lookup_with_one_arm_per_annotated_constraint() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "account already exists".into(),
+            http_status: Some(409),
+            ..Default::default()
+        });
+        doc.constraints.push(Constraint {
+            code: "CONST-003".into(),
+            text: "account not found".into(),
+            http_status: Some(404),
+            ..Default::default()
+        });
+        doc.constraints.push(Constraint {
+            code: "CONST-004".into(),
+            text: "prose-only, no status".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc);
+        assert!(generated.contains("\"CONST-002\" => Some(409),"));
+        assert!(generated.contains("\"CONST-003\" => Some(404),"));
+        assert!(!generated.contains("CONST-004"));
+        assert!(generated.contains("_ => None,"));
+    }
+
+    #[test]
+    fn generates_axum_and_actix_converters() {
+        let doc = SpecDocument::new();
+        let generated = generate(&doc);
+        assert!(generated.contains(
+            "impl ::axum::response::IntoResponse for ::libspec::error::SpecError {"
+        ));
+        assert!(generated.contains("(status, ::axum::Json(self)).into_response()"));
+        assert!(
+            generated.contains("impl ::actix_web::ResponseError for ::libspec::error::SpecError {")
+        );
+        assert!(generated.contains("fn status_code(&self) -> ::actix_web::http::StatusCode {"));
+    }
+}
@@ -0,0 +1,83 @@
+//! Generates a JSON-RPC 2.0 dispatch layer wrapping an implementation of a
+//! [`rust_trait`](super::rust_trait)-shaped trait, mapping a returned
+//! [`SpecError`](crate::error::SpecError)'s `code`/`message` fields to
+//! JSON-RPC error objects with a stable numeric code derived from the
+//! constraint id.
+
+use crate::spec::SpecDocument;
+
+use super::method_name;
+
+const HELPER: &str = r#"/// Maps a constraint code like `CONST-002` to a JSON-RPC error code in the
+/// implementation-defined server-error range (`-32000` to `-32099`).
+fn constraint_error_code(code: &str) -> i64 {
+    let digits: String = code.chars().filter(char::is_ascii_digit).collect();
+    -32000 - digits.parse::<i64>().unwrap_or(0)
+}
+
+"#;
+
+/// Generates a `dispatch` function taking `&impl {trait_name}`, a JSON-RPC
+/// `method` name, and a request `id`, returning the JSON-RPC 2.0 response
+/// object as a `serde_json::Value`. `Ok(())` becomes `{"result": null}`; an
+/// `Err(SpecError { code, message, .. })` is mapped to `{"error": {"code":
+/// ..., "message": ...}}`, where `code` comes from [`constraint_error_code`]
+/// in the generated output.
+pub fn generate(doc: &SpecDocument, trait_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str(HELPER);
+
+    out.push_str("/// Dispatches a JSON-RPC 2.0 request to `impl_`, returning the response object.\n");
+    out.push_str(&format!(
+        "pub fn dispatch(impl_: &impl {trait_name}, method: &str, id: serde_json::Value) -> serde_json::Value {{\n"
+    ));
+    out.push_str("    let result = match method {\n");
+    for req in &doc.requirements {
+        let name = method_name(req);
+        out.push_str(&format!("        \"{name}\" => impl_.{name}(),\n"));
+    }
+    out.push_str("        _ => {\n");
+    out.push_str("            return serde_json::json!({\n");
+    out.push_str("                \"jsonrpc\": \"2.0\",\n");
+    out.push_str("                \"error\": { \"code\": -32601, \"message\": \"method not found\" },\n");
+    out.push_str("                \"id\": id,\n");
+    out.push_str("            });\n");
+    out.push_str("        }\n");
+    out.push_str("    };\n\n");
+    out.push_str("    match result {\n");
+    out.push_str("        Ok(()) => serde_json::json!({ \"jsonrpc\": \"2.0\", \"result\": null, \"id\": id }),\n");
+    out.push_str("        Err(e) => {\n");
+    out.push_str("            serde_json::json!({\n");
+    out.push_str("                \"jsonrpc\": \"2.0\",\n");
+    out.push_str(
+        "                \"error\": { \"code\": constraint_error_code(&e.code), \"message\": e.message },\n",
+    );
+    out.push_str("                \"id\": id,\n");
+    out.push_str("            })\n");
+    out.push_str("        }\n");
+    out.push_str("    }\n");
+    out.push_str("}\n");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn generates_one_dispatch_arm_per_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankAPI");
+        assert!(generated.contains("pub fn dispatch(impl_: &impl BankAPI, method: &str, id: serde_json::Value) -> serde_json::Value {"));
+        assert!(generated.contains("\"balance\" => impl_.balance(),"));
+        assert!(generated.contains("fn constraint_error_code(code: &str) -> i64 {"));
+    }
+}
@@ -0,0 +1,119 @@
+//! Generates a JSON Schema document covering every spec-defined
+//! [`DataType`] plus the [`SpecError`](crate::error::SpecError) wire
+//! format, so API gateways and client generators in other ecosystems can
+//! validate payloads against the same source of truth the Rust types
+//! are generated from.
+
+use crate::spec::{DataType, SpecDocument};
+
+/// Generates a single JSON Schema document (draft 2020-12): one
+/// `$defs` entry per [`DataType`] in `doc` (an `object` with one
+/// `required` property per field, typed via [`json_schema_type`]), plus
+/// a `$defs` entry named `SpecError` matching
+/// [`crate::error::SpecError`]'s wire format — `code`/`message` required
+/// strings, `requirement`/`details` nullable strings. `schema_id` becomes
+/// the document's `$id`.
+pub fn generate(doc: &SpecDocument, schema_id: &str) -> String {
+    let mut defs = serde_json::Map::new();
+    for dt in &doc.data_types {
+        defs.insert(dt.name.clone(), data_type_schema(dt));
+    }
+    defs.insert("SpecError".to_string(), spec_error_schema());
+
+    let document = serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": schema_id,
+        "$defs": defs,
+    });
+
+    serde_json::to_string_pretty(&document).expect("schema document serialization is infallible")
+}
+
+/// Renders `dt` as an `object` schema: one required property per field,
+/// typed via [`json_schema_type`].
+fn data_type_schema(dt: &DataType) -> serde_json::Value {
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+    for field in &dt.fields {
+        properties.insert(field.name.clone(), serde_json::json!({ "type": json_schema_type(&field.ty) }));
+        required.push(serde_json::Value::String(field.name.clone()));
+    }
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// The `SpecError` wire format documented in [`crate::error`]: `code` and
+/// `message` are required strings; `requirement` and `details` are
+/// nullable strings, matching their `Option<String>` fields.
+fn spec_error_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "code": { "type": "string" },
+            "requirement": { "type": ["string", "null"] },
+            "message": { "type": "string" },
+            "details": { "type": ["string", "null"] },
+        },
+        "required": ["code", "message"],
+    })
+}
+
+/// Maps a Rust-ish field type (as declared on a
+/// [`DataType`](crate::spec::DataType) field) to the closest JSON Schema
+/// primitive type, falling back to `"string"` for anything it doesn't
+/// recognize — the same fallback `proto::proto_type` uses for Protobuf.
+fn json_schema_type(ty: &str) -> &str {
+    match ty {
+        "f64" | "f32" => "number",
+        "i32" | "u32" | "i64" | "u64" | "usize" | "isize" => "integer",
+        "bool" => "boolean",
+        "String" | "str" | "&str" => "string",
+        _ => "string",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Field;
+
+    #[test]
+    fn generates_a_def_per_data_type_with_required_typed_properties() {
+        let mut doc = SpecDocument::new();
+        doc.data_types.push(DataType {
+            name: "Account".into(),
+            fields: vec![
+                Field { name: "id".into(), ty: "String".into(), constraints: vec![] },
+                Field { name: "balance".into(), ty: "f64".into(), constraints: vec![] },
+            ],
+        });
+
+        let generated = generate(&doc, "https://example.com/bank.schema.json");
+        let schema: serde_json::Value = serde_json::from_str(&generated).unwrap();
+
+        assert_eq!(schema["$id"], "https://example.com/bank.schema.json");
+        assert_eq!(schema["$defs"]["Account"]["properties"]["id"]["type"], "string");
+        assert_eq!(schema["$defs"]["Account"]["properties"]["balance"]["type"], "number");
+        assert_eq!(
+            schema["$defs"]["Account"]["required"],
+            serde_json::json!(["id", "balance"])
+        );
+    }
+
+    #[test]
+    fn generates_a_spec_error_def_matching_its_wire_format() {
+        let doc = SpecDocument::new();
+        let generated = generate(&doc, "https://example.com/bank.schema.json");
+        let schema: serde_json::Value = serde_json::from_str(&generated).unwrap();
+
+        assert_eq!(schema["$defs"]["SpecError"]["properties"]["code"]["type"], "string");
+        assert_eq!(
+            schema["$defs"]["SpecError"]["properties"]["requirement"]["type"],
+            serde_json::json!(["string", "null"])
+        );
+        assert_eq!(schema["$defs"]["SpecError"]["required"], serde_json::json!(["code", "message"]));
+    }
+}
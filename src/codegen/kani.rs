@@ -0,0 +1,261 @@
+//! Generates [Kani](https://github.com/model-checking/kani) proof harnesses
+//! that symbolically check a constraint's generated guard function
+//! ([`rust_guard`](super::rust_guard)) and a state machine transition's
+//! invariant, rather than the finitely-sampled cases
+//! [`rust_negative_tests`](super::rust_negative_tests) and
+//! [`proptest_strategies`](super::proptest_strategies) exercise — a real
+//! guarantee that holds for every input Kani can represent, not just the
+//! ones a test happened to pick. Harnesses live behind a `verification`
+//! feature so an ordinary build, which doesn't depend on the `kani` crate,
+//! is unaffected; only `cargo kani`, which enables that feature, compiles
+//! and runs them.
+
+use crate::spec::{ConstraintExpr, SpecDocument, StateMachine, Transition};
+
+use super::rust_guard::plan;
+
+/// Generates `#[cfg(feature = "verification")] mod kani_harnesses { ... }`
+/// containing:
+///
+/// - one `#[kani::proof]` per constraint whose `expr`
+///   [`rust_guard::plan`](super::rust_guard::plan) can turn into a check
+///   function with no call parameters (Kani has no symbolic value to give a
+///   closure parameter), constructing each bare identifier with
+///   `kani::any()` and asserting
+///   [`rust_guard::generate`](super::rust_guard::generate)'s matching check
+///   function doesn't panic, for every input including NaN and infinity;
+/// - one `#[kani::proof]` per state machine transition that `violates` a
+///   constraint and carries a `guard`: assumes the guard over symbolic
+///   state/param values, applies the transition's `effect`, and asserts the
+///   violated constraint's `expr` still holds afterward — e.g. proving
+///   `withdraw` never leaves `balance` negative for any `balance`/`amount`
+///   its guard lets through.
+///
+/// Must be emitted alongside
+/// [`rust_guard::generate`](super::rust_guard::generate)'s check functions,
+/// which the constraint harnesses call directly.
+///
+/// Constraints/transitions this can't state a harness for are skipped with
+/// a trailing comment, the same best-effort spirit as
+/// [`rust_guard`](super::rust_guard).
+pub fn generate(doc: &SpecDocument) -> String {
+    let mut body = String::new();
+    let mut skipped = Vec::new();
+
+    for c in &doc.constraints {
+        let Some(expr_src) = &c.expr else { continue };
+        match ConstraintExpr::parse(expr_src) {
+            Ok(expr) => match plan(&expr) {
+                Some(p) if p.calls.is_empty() => body.push_str(&constraint_harness(&c.code, &p)),
+                Some(_) => skipped.push((
+                    c.code.clone(),
+                    "expr calls a function, which Kani has no symbolic value for".to_string(),
+                )),
+                None => skipped.push((
+                    c.code.clone(),
+                    "expr calls a function with an argument that isn't a bare identifier".to_string(),
+                )),
+            },
+            Err(e) => skipped.push((c.code.clone(), format!("expr failed to parse: {e}"))),
+        }
+    }
+
+    for sm in &doc.state_machines {
+        for t in &sm.transitions {
+            match transition_harness(doc, sm, t) {
+                Some(harness) => body.push_str(&harness),
+                None => continue,
+            }
+        }
+    }
+
+    if body.is_empty() {
+        if !skipped.is_empty() {
+            for (code, reason) in &skipped {
+                body.push_str(&format!(
+                    "// Constraints with no generated proof harness (see their `expr` in the spec):\n// - {code}: {reason}\n"
+                ));
+            }
+        }
+        return body;
+    }
+
+    let mut out = String::from("#[cfg(feature = \"verification\")]\nmod kani_harnesses {\n    use super::*;\n\n");
+    for line in body.lines() {
+        if line.is_empty() {
+            out.push('\n');
+        } else {
+            out.push_str("    ");
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out.push_str("}\n");
+
+    if !skipped.is_empty() {
+        out.push_str("\n// Constraints with no generated proof harness (see their `expr` in the spec):\n");
+        for (code, reason) in &skipped {
+            out.push_str(&format!("// - {code}: {reason}\n"));
+        }
+    }
+    out
+}
+
+/// A `#[kani::proof]` asserting `check_{code}` doesn't panic for any
+/// `f64` value of each of `plan`'s bare identifiers.
+fn constraint_harness(code: &str, plan: &super::rust_guard::Plan) -> String {
+    let fn_name = format!("check_{}", code.to_lowercase().replace('-', "_"));
+    let mut out = String::new();
+    out.push_str(&format!("/// Proves `{fn_name}` never panics, for any input.\n"));
+    out.push_str("#[kani::proof]\n");
+    out.push_str(&format!("fn {fn_name}_never_panics() {{\n"));
+    for name in &plan.bare {
+        out.push_str(&format!("    let {name}: f64 = kani::any();\n"));
+    }
+    out.push_str(&format!("    let _ = {fn_name}({});\n", plan.bare.join(", ")));
+    out.push_str("}\n");
+    out
+}
+
+/// A `#[kani::proof]` proving `t`'s `violates` constraint still holds after
+/// `t` runs, for any state/param values satisfying its `guard`. `None` if
+/// `t` has no `guard` (nothing to assume) or no `violates` (nothing to
+/// prove), or if `doc` doesn't have the constraint its `violates` names.
+fn transition_harness(doc: &SpecDocument, sm: &StateMachine, t: &Transition) -> Option<String> {
+    let guard = t.guard.as_ref()?;
+    let code = t.violates.as_ref()?;
+    let constraint = doc.constraints.iter().find(|c| &c.code == code)?;
+    let expr = constraint.expr.as_ref()?;
+
+    let harness_name = format!(
+        "{}_{}_upholds_{}",
+        sm.name.to_lowercase(),
+        t.name,
+        code.to_lowercase().replace('-', "_")
+    );
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "/// Proves `{}::{}` never leaves `{}` violated, for any state/params its guard allows.\n",
+        sm.name, t.name, code
+    ));
+    out.push_str("#[kani::proof]\n");
+    out.push_str(&format!("fn {harness_name}() {{\n"));
+    for field in sm.state.keys() {
+        out.push_str(&format!("    let {field}: f64 = kani::any();\n"));
+    }
+    for param in &t.params {
+        out.push_str(&format!("    let {param}: f64 = kani::any();\n"));
+    }
+    out.push_str(&format!("    kani::assume({guard});\n"));
+    for field in sm.state.keys() {
+        if let Some(effect) = t.effect.get(field) {
+            out.push_str(&format!("    let {field} = {effect};\n"));
+        }
+    }
+    out.push_str(&format!("    assert!({expr});\n"));
+    out.push_str("}\n");
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, StateMachine, Transition};
+
+    fn doc_with_constraint(expr: &str) -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            expr: Some(expr.into()),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn generates_a_never_panics_harness_for_a_simple_comparison() {
+        let generated = generate(&doc_with_constraint("amount > 0"));
+        assert!(generated.contains("#[cfg(feature = \"verification\")]"));
+        assert!(generated.contains("#[kani::proof]"));
+        assert!(generated.contains("fn check_const_001_never_panics() {"));
+        assert!(generated.contains("let amount: f64 = kani::any();"));
+        assert!(generated.contains("let _ = check_const_001(amount);"));
+    }
+
+    #[test]
+    fn skips_a_constraint_whose_expr_calls_a_function() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "amount must not exceed the balance".into(),
+            expr: Some("amount <= balance(account)".into()),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc);
+        assert!(!generated.contains("fn check_const_002_never_panics"));
+        assert!(generated.contains("// - CONST-002: expr calls a function, which Kani has no symbolic value for"));
+    }
+
+    #[test]
+    fn skips_a_constraint_without_an_expr() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-003".into(),
+            text: "account must exist".into(),
+            expr: None,
+            ..Default::default()
+        });
+        assert_eq!(generate(&doc), "");
+    }
+
+    #[test]
+    fn generates_a_transition_invariant_harness() {
+        let mut doc = SpecDocument::new();
+        doc.state_machines.push(StateMachine {
+            name: "Account".into(),
+            state: [("balance".to_string(), 0.0)].into(),
+            transitions: vec![Transition {
+                name: "withdraw".into(),
+                params: vec!["amount".into()],
+                guard: Some("amount <= balance".into()),
+                violates: Some("CONST-002".into()),
+                effect: [("balance".to_string(), "balance - amount".to_string())].into(),
+            }],
+        });
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "balance never goes negative".into(),
+            expr: Some("balance >= 0".into()),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc);
+        assert!(generated.contains("fn account_withdraw_upholds_const_002() {"));
+        assert!(generated.contains("let balance: f64 = kani::any();"));
+        assert!(generated.contains("let amount: f64 = kani::any();"));
+        assert!(generated.contains("kani::assume(amount <= balance);"));
+        assert!(generated.contains("let balance = balance - amount;"));
+        assert!(generated.contains("assert!(balance >= 0);"));
+    }
+
+    #[test]
+    fn skips_a_transition_with_no_guard() {
+        let mut doc = SpecDocument::new();
+        doc.state_machines.push(StateMachine {
+            name: "Account".into(),
+            state: [("balance".to_string(), 0.0)].into(),
+            transitions: vec![Transition {
+                name: "deposit".into(),
+                params: vec!["amount".into()],
+                guard: None,
+                violates: None,
+                effect: [("balance".to_string(), "balance + amount".to_string())].into(),
+            }],
+        });
+
+        assert_eq!(generate(&doc), "");
+    }
+}
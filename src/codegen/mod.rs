@@ -0,0 +1,214 @@
+//! Generators that turn a [`SpecDocument`](crate::spec::SpecDocument) into
+//! source code: Rust traits, typed errors, stubs, mocks, and non-Rust
+//! backends, all driven by the same requirement/constraint model.
+
+pub mod alloy;
+pub mod c_abi_shim;
+pub mod c_header;
+pub mod checked;
+pub mod event_schema;
+pub mod fsm;
+pub mod fuzz;
+pub mod go;
+pub mod http_client;
+pub mod http_status;
+pub mod json_rpc;
+pub mod json_schema;
+pub mod kani;
+pub mod proptest_strategies;
+pub mod proto;
+pub mod python;
+pub mod rust_adapter;
+pub mod rust_audit;
+pub mod rust_bench;
+pub mod rust_cli_test;
+pub mod rust_dto;
+pub mod rust_error;
+pub mod rust_guard;
+pub mod rust_impl_stub;
+pub mod rust_metrics;
+pub mod rust_mock;
+pub mod rust_negative_tests;
+pub mod rust_trace;
+pub mod rust_trait;
+pub mod sql_ddl;
+pub mod state_machine;
+pub mod template;
+pub mod tla;
+pub mod typescript;
+pub mod wasm;
+
+use crate::spec::{Constraint, Requirement, Severity};
+
+/// Best-effort method name for a requirement: the identifier before the
+/// first `(` if its text reads like `balance() returns ...`, otherwise its
+/// id lowercased with non-alphanumerics turned into `_`.
+///
+/// Exposed beyond the codegen backends so other tooling (e.g.
+/// `libspec-macros`) can compare a hand-written method name against the
+/// same name the backends would generate.
+pub fn method_name(req: &Requirement) -> String {
+    if req.text.contains('(') {
+        let call = req.text.split('(').next().unwrap();
+        if let Some(name) = call.split_whitespace().last() {
+            if !name.is_empty()
+                && name
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            {
+                return name.to_string();
+            }
+        }
+    }
+    req.id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Capitalizes the first character, leaving the rest unchanged. Used by
+/// backends whose naming convention is PascalCase (Go, gRPC) but whose
+/// input is already [`method_name`]'s snake_case.
+pub(crate) fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// PascalCase identifier for a constraint code, e.g. `CONST-002` ->
+/// `Const002`. Shared by backends that need a type/variant/exception name
+/// per constraint (Rust's error enum, Python's exceptions, Go's sentinel
+/// errors).
+pub(crate) fn pascal_case_code(code: &str) -> String {
+    code.split(|ch: char| !ch.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => {
+                    first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()
+                }
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Renders `requirements` as a `///`-commented markdown table (id, status,
+/// priority), one row per requirement. Shared by backends (`rust_trait`)
+/// that want a spec-level summary doc comment above the generated item,
+/// alongside the existing per-requirement doc line on each method.
+pub(crate) fn requirement_table_doc(requirements: &[Requirement]) -> String {
+    let mut out = String::from("/// | Requirement | Status | Priority |\n/// |---|---|---|\n");
+    for req in requirements {
+        out.push_str(&format!(
+            "/// | {} | {:?} | {:?} |\n",
+            req.id, req.status, req.priority
+        ));
+    }
+    out
+}
+
+/// Renders `constraints` as a `///`-commented markdown table (code,
+/// severity, message), one row per constraint. Shared by backends
+/// (`rust_error`) that want a spec-level summary doc comment above the
+/// generated item, alongside the existing per-variant doc line on each
+/// error.
+pub(crate) fn constraint_table_doc(constraints: &[Constraint]) -> String {
+    let mut out = String::from("/// | Constraint | Severity | Message |\n/// |---|---|---|\n");
+    for c in constraints {
+        out.push_str(&format!(
+            "/// | {} | {:?} | {} |\n",
+            c.code, c.severity, c.text
+        ));
+    }
+    out
+}
+
+/// The `::libspec::spec::Severity` variant name matching `severity`.
+/// Shared by backends (`rust_guard`, `rust_dto`) that emit a
+/// [`crate::runtime::report`] call and need to name the severity a
+/// generated check ran at.
+pub(crate) fn severity_variant(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "Error",
+        Severity::Warning => "Warning",
+        Severity::Advisory => "Advisory",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_method_name_from_call_like_text() {
+        let req = Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        };
+        assert_eq!(method_name(&req), "balance");
+    }
+
+    #[test]
+    fn falls_back_to_slugified_id() {
+        let req = Requirement {
+            id: "REQ-004".into(),
+            text: "no function call here".into(),
+            ..Default::default()
+        };
+        assert_eq!(method_name(&req), "req_004");
+    }
+
+    #[test]
+    fn pascal_cases_a_constraint_code() {
+        assert_eq!(pascal_case_code("CONST-002"), "Const002");
+    }
+
+    #[test]
+    fn capitalizes_first_character() {
+        assert_eq!(capitalize("balance"), "Balance");
+    }
+
+    #[test]
+    fn names_each_severity_variant() {
+        assert_eq!(severity_variant(Severity::Error), "Error");
+        assert_eq!(severity_variant(Severity::Warning), "Warning");
+        assert_eq!(severity_variant(Severity::Advisory), "Advisory");
+    }
+
+    #[test]
+    fn renders_a_requirement_table_doc_comment() {
+        let req = Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        };
+        let table = requirement_table_doc(std::slice::from_ref(&req));
+        assert!(table.contains("/// | Requirement | Status | Priority |"));
+        assert!(table.contains("/// | REQ-004 |"));
+    }
+
+    #[test]
+    fn renders_a_constraint_table_doc_comment() {
+        let c = Constraint {
+            code: "CONST-002".into(),
+            text: "insufficient funds".into(),
+            ..Default::default()
+        };
+        let table = constraint_table_doc(std::slice::from_ref(&c));
+        assert!(table.contains("/// | Constraint | Severity | Message |"));
+        assert!(table.contains("/// | CONST-002 |"));
+        assert!(table.contains("insufficient funds"));
+    }
+}
@@ -0,0 +1,193 @@
+//! Generates `proptest` strategies for constrained
+//! [`DataType`](crate::spec::DataType) fields: a valid and an invalid
+//! strategy per field whose constraint text this module knows how to turn
+//! into a concrete range — the same "positive" heuristic
+//! [`rust_dto`](super::rust_dto) uses to validate. Also generates "oracle"
+//! functions (see [`generate_oracles`]) pairing with
+//! [`rust_guard`](super::rust_guard)'s generated check functions, so a
+//! proptest can assert the two never disagree.
+
+use crate::spec::{ConstraintExpr, SpecDocument};
+
+use super::rust_guard;
+
+/// Generates `mod spec_strategies` with `valid_{type}_{field}()` and
+/// `invalid_{type}_{field}()` functions, each an `impl
+/// Strategy<Value = f64>`, for every field of the named data types whose
+/// constraint text contains "positive". Fields with constraints this
+/// module can't interpret are skipped; call [`generate`] with more data
+/// types or extend the recognized constraint vocabulary to cover them.
+pub fn generate(doc: &SpecDocument, type_names: &[&str]) -> String {
+    let mut out = String::new();
+    out.push_str("pub mod spec_strategies {\n");
+    out.push_str("    use proptest::prelude::*;\n\n");
+
+    for name in type_names {
+        let Some(dt) = doc.data_type(name) else {
+            continue;
+        };
+        for field in &dt.fields {
+            if field.ty != "f64" {
+                continue;
+            }
+            for code in &field.constraints {
+                let text = doc.constraint(code).map(|c| c.text.as_str()).unwrap_or("");
+                if !text.contains("positive") {
+                    continue;
+                }
+                let fn_suffix = format!("{}_{}", dt.name.to_lowercase(), field.name);
+                out.push_str(&format!(
+                    "    /// Valid `{}.{}` values ({code}: {text}).\n    pub fn valid_{fn_suffix}() -> impl Strategy<Value = f64> {{\n        0.0001f64..1e12\n    }}\n\n",
+                    dt.name, field.name
+                ));
+                out.push_str(&format!(
+                    "    /// Invalid `{}.{}` values ({code}: {text}).\n    pub fn invalid_{fn_suffix}() -> impl Strategy<Value = f64> {{\n        -1e12f64..=0.0\n    }}\n\n",
+                    dt.name, field.name
+                ));
+            }
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Generates a `pub fn {code}_oracle(...) -> bool` per constraint in `doc`
+/// whose `expr` [`rust_guard`] can plan a check function for, with the
+/// exact same parameter list as `rust_guard::generate`'s `check_{code}`, so
+/// a proptest property can call both and assert they agree:
+///
+/// ```text
+/// proptest! {
+///     #[test]
+///     fn check_const_002_matches_its_oracle(amount: f64, balance: f64) {
+///         prop_assert_eq!(
+///             check_const_002(amount, || balance).is_ok(),
+///             oracle::check_const_002_oracle(amount, || balance),
+///         );
+///     }
+/// }
+/// ```
+///
+/// Unlike the generated check function, the oracle is a pure predicate: no
+/// severity branching, no [`crate::runtime::report`] call, no
+/// [`crate::error::SpecError`] — just the boolean `expr` evaluates to.
+pub fn generate_oracles(doc: &SpecDocument) -> String {
+    let mut out = String::new();
+    out.push_str("pub mod spec_oracles {\n");
+    for c in &doc.constraints {
+        let Some(expr_src) = &c.expr else { continue };
+        let Ok(expr) = ConstraintExpr::parse(expr_src) else {
+            continue;
+        };
+        let Some(plan) = rust_guard::plan(&expr) else {
+            continue;
+        };
+        let (generics_str, params) = rust_guard::signature_parts(&plan);
+        let fn_name = format!("{}_oracle", c.code.to_lowercase().replace('-', "_"));
+        out.push_str(&format!("    /// {}: {}\n", c.code, c.text));
+        out.push_str(&format!(
+            "    pub fn {fn_name}{generics_str}({}) -> bool {{\n        {expr}\n    }}\n\n",
+            params.join(", ")
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, DataType, Field};
+
+    #[test]
+    fn generates_valid_and_invalid_strategy_per_positive_field() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            ..Default::default()
+        });
+        doc.data_types.push(DataType {
+            name: "Account".into(),
+            fields: vec![Field {
+                name: "balance".into(),
+                ty: "f64".into(),
+                constraints: vec!["CONST-001".into()],
+            }],
+        });
+
+        let generated = generate(&doc, &["Account"]);
+        assert!(generated.contains("pub fn valid_account_balance() -> impl Strategy<Value = f64> {"));
+        assert!(generated.contains("pub fn invalid_account_balance() -> impl Strategy<Value = f64> {"));
+    }
+
+    #[test]
+    fn skips_fields_with_unrecognized_constraints() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "id must be unique".into(),
+            ..Default::default()
+        });
+        doc.data_types.push(DataType {
+            name: "Account".into(),
+            fields: vec![Field {
+                name: "id".into(),
+                ty: "String".into(),
+                constraints: vec!["CONST-002".into()],
+            }],
+        });
+
+        let generated = generate(&doc, &["Account"]);
+        assert!(!generated.contains("fn valid_"));
+    }
+
+    #[test]
+    fn generates_an_oracle_matching_its_check_functions_signature() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "amount must not exceed the balance".into(),
+            expr: Some("amount <= balance(account)".into()),
+            ..Default::default()
+        });
+
+        let generated = generate_oracles(&doc);
+        assert!(generated.contains("pub mod spec_oracles {"));
+        assert!(generated.contains("pub fn const_002_oracle<Account>("));
+        assert!(generated.contains("amount: f64"));
+        assert!(generated.contains("account: Account"));
+        assert!(generated.contains("balance: impl Fn(Account) -> f64"));
+        assert!(generated.contains("amount <= balance(account)"));
+    }
+
+    #[test]
+    fn generates_an_oracle_for_a_composed_expression() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-003".into(),
+            text: "amount is positive and within the cap".into(),
+            expr: Some("amount > 0 && amount <= 1000".into()),
+            ..Default::default()
+        });
+
+        let generated = generate_oracles(&doc);
+        assert!(generated.contains("pub fn const_003_oracle(amount: f64) -> bool {"));
+        assert!(generated.contains("amount > 0 && amount <= 1000"));
+    }
+
+    #[test]
+    fn skips_constraints_without_an_expr() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-004".into(),
+            text: "prose-only constraint".into(),
+            expr: None,
+            ..Default::default()
+        });
+
+        let generated = generate_oracles(&doc);
+        assert!(!generated.contains("fn "));
+    }
+}
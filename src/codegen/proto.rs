@@ -0,0 +1,114 @@
+//! Generates a Protobuf/gRPC backend: a `service` with one `rpc` per
+//! requirement, a `message` per [`DataType`], and a standard `ErrorDetail`
+//! message that carries a constraint code, so a spec'd API can be exposed
+//! over gRPC with the same traceability the Rust error enum gives in
+//! process.
+
+use crate::spec::SpecDocument;
+
+use super::{capitalize, method_name};
+
+/// Generates a `.proto` file: `syntax = "proto3"`, `package {package}`,
+/// `service {service_name}` with one `rpc {Method}({Method}Request)
+/// returns ({Method}Response)` per requirement (plus the matching empty
+/// request/response messages), one `message` per data type in `doc`, and
+/// `message ErrorDetail { string code = 1; string message = 2; }` for
+/// carrying a constraint code/message as gRPC error detail.
+pub fn generate(doc: &SpecDocument, package: &str, service_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str("syntax = \"proto3\";\n\n");
+    out.push_str(&format!("package {package};\n\n"));
+
+    out.push_str(&format!("service {service_name} {{\n"));
+    for req in &doc.requirements {
+        let name = capitalize(&method_name(req));
+        out.push_str(&format!(
+            "  // {}: {}\n  rpc {name}({name}Request) returns ({name}Response);\n",
+            req.id, req.text
+        ));
+    }
+    out.push_str("}\n\n");
+
+    for req in &doc.requirements {
+        let name = capitalize(&method_name(req));
+        out.push_str(&format!("message {name}Request {{}}\n"));
+        out.push_str(&format!("message {name}Response {{}}\n\n"));
+    }
+
+    for dt in &doc.data_types {
+        out.push_str(&format!("message {} {{\n", dt.name));
+        for (i, field) in dt.fields.iter().enumerate() {
+            out.push_str(&format!(
+                "  {} {} = {};\n",
+                proto_type(&field.ty),
+                field.name,
+                i + 1
+            ));
+        }
+        out.push_str("}\n\n");
+    }
+
+    out.push_str("message ErrorDetail {\n  string code = 1;\n  string message = 2;\n}\n");
+    out
+}
+
+/// Maps a Rust-ish field type (as declared on a [`DataType`](crate::spec::DataType)
+/// field) to the closest Protobuf scalar type, falling back to `string`
+/// for anything it doesn't recognize.
+fn proto_type(ty: &str) -> &str {
+    match ty {
+        "f64" => "double",
+        "f32" => "float",
+        "i32" => "int32",
+        "u32" => "uint32",
+        "i64" => "int64",
+        "u64" => "uint64",
+        "bool" => "bool",
+        "String" | "str" | "&str" => "string",
+        _ => "string",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{DataType, Field, Requirement};
+
+    #[test]
+    fn generates_one_rpc_per_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "bankapi", "BankAPI");
+        assert!(generated.contains("rpc Balance(BalanceRequest) returns (BalanceResponse);"));
+        assert!(generated.contains("message BalanceRequest {}"));
+        assert!(generated.contains("message BalanceResponse {}"));
+    }
+
+    #[test]
+    fn generates_message_per_data_type() {
+        let mut doc = SpecDocument::new();
+        doc.data_types.push(DataType {
+            name: "Account".into(),
+            fields: vec![Field {
+                name: "balance".into(),
+                ty: "f64".into(),
+                constraints: vec![],
+            }],
+        });
+
+        let generated = generate(&doc, "bankapi", "BankAPI");
+        assert!(generated.contains("message Account {\n  double balance = 1;\n}"));
+    }
+
+    #[test]
+    fn generates_error_detail_message() {
+        let doc = SpecDocument::new();
+        let generated = generate(&doc, "bankapi", "BankAPI");
+        assert!(generated.contains("message ErrorDetail {\n  string code = 1;\n  string message = 2;\n}"));
+    }
+}
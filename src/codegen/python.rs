@@ -0,0 +1,70 @@
+//! Generates a Python backend: an abstract base class with one method per
+//! requirement, and a typed exception per constraint, so a spec isn't
+//! implicitly Rust-only just because `examples/bank-account/rust` is the
+//! only implementation on disk.
+
+use crate::spec::SpecDocument;
+
+use super::{method_name, pascal_case_code};
+
+/// Generates a Python module defining one `Exception` subclass per
+/// constraint in `doc`, followed by `class {class_name}(ABC)` with one
+/// `@abstractmethod` per requirement, docstringed with the requirement's id
+/// and text.
+pub fn generate(doc: &SpecDocument, class_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str("from abc import ABC, abstractmethod\n\n\n");
+
+    for c in &doc.constraints {
+        out.push_str(&format!("class {}Error(Exception):\n", pascal_case_code(&c.code)));
+        out.push_str(&format!("    \"\"\"{}: {}\"\"\"\n\n\n", c.code, c.text));
+    }
+
+    out.push_str(&format!("class {class_name}(ABC):\n"));
+    if doc.requirements.is_empty() {
+        out.push_str("    pass\n");
+        return out;
+    }
+    for req in &doc.requirements {
+        out.push_str("    @abstractmethod\n");
+        out.push_str(&format!("    def {}(self):\n", method_name(req)));
+        out.push_str(&format!("        \"\"\"{}: {}\"\"\"\n", req.id, req.text));
+        out.push_str("        ...\n\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, Requirement};
+
+    #[test]
+    fn generates_one_exception_per_constraint() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "insufficient funds".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankAPI");
+        assert!(generated.contains("class Const002Error(Exception):"));
+        assert!(generated.contains("\"\"\"CONST-002: insufficient funds\"\"\""));
+    }
+
+    #[test]
+    fn generates_one_abstract_method_per_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankAPI");
+        assert!(generated.contains("class BankAPI(ABC):"));
+        assert!(generated.contains("    @abstractmethod\n    def balance(self):"));
+        assert!(generated.contains("\"\"\"REQ-004: balance() returns the current balance\"\"\""));
+    }
+}
@@ -0,0 +1,248 @@
+//! Generates an adapter `impl` of a newer spec version's trait that wraps
+//! an implementation of an older version, for operations whose semantics
+//! didn't change enough to need new logic — see [`crate::diff`] for how
+//! "changed enough" is decided. Operations `libspec` can't adapt
+//! automatically (new in v2, or reworded enough that [`method_name`]
+//! moved) get a `todo!()` stub instead of a silently wrong guess, and are
+//! listed in the returned [`AdapterReport`] so none get overlooked. A v2
+//! requirement that's a pure renumbering of a v1 one (see
+//! [`crate::diff::Supersession`]) is adapted by delegating to the old
+//! method name, not left stranded as "new in v2".
+
+use crate::diff::{self, SpecDiff, Supersession};
+use crate::spec::{Requirement, SpecDocument};
+
+use super::method_name;
+
+/// One requirement `generate` could not automatically wire an adapter
+/// for, and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnadaptableOperation {
+    pub requirement: String,
+    pub reason: String,
+}
+
+/// Everything [`generate`] found out while building the adapter: which
+/// v2 requirements it delegated straight to the wrapped v1
+/// implementation, and which ones it couldn't.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AdapterReport {
+    pub adapted: Vec<String>,
+    pub unadaptable: Vec<UnadaptableOperation>,
+}
+
+/// Generates `pub struct {struct_name}<T> { pub inner: T }` and `impl<T:
+/// {v1_trait_name}> {v2_trait_name} for {struct_name}<T>`. A v2
+/// requirement that's unchanged, or only reworded without its
+/// [`method_name`] moving, since v1 gets a method delegating straight to
+/// `self.inner`; one that's new in v2, or reworded enough that its
+/// [`method_name`] changed, gets a `todo!()` stub instead, and is
+/// recorded in the returned [`AdapterReport::unadaptable`].
+pub fn generate(
+    v1: &SpecDocument,
+    v2: &SpecDocument,
+    v1_trait_name: &str,
+    v2_trait_name: &str,
+    struct_name: &str,
+) -> (String, AdapterReport) {
+    let spec_diff = diff::diff(v1, v2);
+    let mut report = AdapterReport::default();
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "/// Adapts a `{v1_trait_name}` implementation to `{v2_trait_name}`.\npub struct {struct_name}<T> {{\n    pub inner: T,\n}}\n\n"
+    ));
+    out.push_str(&format!(
+        "impl<T: {v1_trait_name}> {v2_trait_name} for {struct_name}<T> {{\n"
+    ));
+
+    for req in &v2.requirements {
+        let name = method_name(req);
+        out.push_str(&format!("    /// {}: {}\n", req.id, req.text));
+
+        let supersession = spec_diff
+            .superseded_requirements
+            .iter()
+            .find(|s| s.new_id == req.id);
+
+        match unadaptable_reason(&spec_diff, req, supersession) {
+            Some(reason) => {
+                out.push_str(&format!("    // not auto-adapted: {reason}\n"));
+                out.push_str(&format!(
+                    "    fn {name}(&self) -> Result<(), ::libspec::error::SpecError> {{\n        todo!()\n    }}\n"
+                ));
+                report.unadaptable.push(UnadaptableOperation {
+                    requirement: req.id.clone(),
+                    reason,
+                });
+            }
+            None => {
+                let delegate_name = match supersession {
+                    Some(s) => method_name(&s.before),
+                    None => name.clone(),
+                };
+                out.push_str(&format!(
+                    "    fn {name}(&self) -> Result<(), ::libspec::error::SpecError> {{\n        self.inner.{delegate_name}()\n    }}\n"
+                ));
+                report.adapted.push(req.id.clone());
+            }
+        }
+    }
+    out.push_str("}\n");
+
+    (out, report)
+}
+
+/// Why `req` (a v2 requirement) can't be auto-adapted from v1, per
+/// `spec_diff`; `None` if it can. `supersession` is `req`'s entry in
+/// `spec_diff.superseded_requirements`, if it's a renumbering of a v1
+/// requirement rather than genuinely new.
+fn unadaptable_reason(
+    spec_diff: &SpecDiff,
+    req: &Requirement,
+    supersession: Option<&Supersession>,
+) -> Option<String> {
+    if let Some(s) = supersession {
+        return s.signature_changed.then(|| {
+            format!(
+                "{} supersedes {}, but its method name changed ({} -> {})",
+                req.id,
+                s.old_id,
+                method_name(&s.before),
+                method_name(&s.after)
+            )
+        });
+    }
+    if spec_diff.added_requirements.iter().any(|r| r.id == req.id) {
+        return Some(format!(
+            "{} is new in v2; there's no v1 behavior to delegate to",
+            req.id
+        ));
+    }
+    let change = spec_diff.modified_requirements.iter().find(|c| c.id == req.id)?;
+    change.signature_changed.then(|| {
+        format!(
+            "{}'s method name changed ({} -> {})",
+            req.id,
+            method_name(&change.before),
+            method_name(&change.after)
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(id: &str, text: &str) -> Requirement {
+        Requirement {
+            id: id.into(),
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn delegates_an_unchanged_requirement_to_the_wrapped_implementation() {
+        let mut v1 = SpecDocument::new();
+        v1.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        let v2 = v1.clone();
+
+        let (generated, report) = generate(&v1, &v2, "BankApiV1", "BankApiV2", "BankApiAdapter");
+        assert!(generated.contains("impl<T: BankApiV1> BankApiV2 for BankApiAdapter<T> {"));
+        assert!(generated.contains(
+            "fn balance(&self) -> Result<(), ::libspec::error::SpecError> {\n        self.inner.balance()\n    }"
+        ));
+        assert_eq!(report.adapted, vec!["REQ-004"]);
+        assert!(report.unadaptable.is_empty());
+    }
+
+    #[test]
+    fn delegates_a_requirement_reworded_without_changing_its_method_name() {
+        let mut v1 = SpecDocument::new();
+        v1.requirements.push(req("REQ-004", "balance() returns the current account balance"));
+        let mut v2 = SpecDocument::new();
+        v2.requirements.push(req("REQ-004", "balance() returns the current balance"));
+
+        let (_, report) = generate(&v1, &v2, "BankApiV1", "BankApiV2", "BankApiAdapter");
+        assert_eq!(report.adapted, vec!["REQ-004"]);
+    }
+
+    #[test]
+    fn flags_a_requirement_new_in_v2_as_unadaptable() {
+        let v1 = SpecDocument::new();
+        let mut v2 = SpecDocument::new();
+        v2.requirements.push(req("REQ-004", "balance() returns the current balance"));
+
+        let (generated, report) = generate(&v1, &v2, "BankApiV1", "BankApiV2", "BankApiAdapter");
+        assert!(generated.contains("// not auto-adapted: REQ-004 is new in v2; there's no v1 behavior to delegate to"));
+        assert!(generated.contains(
+            "fn balance(&self) -> Result<(), ::libspec::error::SpecError> {\n        todo!()\n    }"
+        ));
+        assert!(report.adapted.is_empty());
+        assert_eq!(report.unadaptable, vec![UnadaptableOperation {
+            requirement: "REQ-004".into(),
+            reason: "REQ-004 is new in v2; there's no v1 behavior to delegate to".into(),
+        }]);
+    }
+
+    #[test]
+    fn flags_a_renamed_method_as_unadaptable() {
+        let mut v1 = SpecDocument::new();
+        v1.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        let mut v2 = SpecDocument::new();
+        v2.requirements.push(req("REQ-004", "current_balance() returns the current balance"));
+
+        let (_, report) = generate(&v1, &v2, "BankApiV1", "BankApiV2", "BankApiAdapter");
+        assert!(report.adapted.is_empty());
+        assert_eq!(report.unadaptable.len(), 1);
+        assert_eq!(report.unadaptable[0].requirement, "REQ-004");
+        assert!(report.unadaptable[0].reason.contains("balance -> current_balance"));
+    }
+
+    #[test]
+    fn delegates_a_renumbered_requirement_to_its_old_method_name() {
+        let mut v1 = SpecDocument::new();
+        v1.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        let mut v2 = SpecDocument::new();
+        v2.requirements.push(Requirement {
+            replaced_by: None,
+            ..req("REQ-012", "balance() returns the current balance")
+        });
+        v1.requirements[0].replaced_by = Some("REQ-012".into());
+
+        let (generated, report) = generate(&v1, &v2, "BankApiV1", "BankApiV2", "BankApiAdapter");
+        assert!(generated.contains(
+            "fn balance(&self) -> Result<(), ::libspec::error::SpecError> {\n        self.inner.balance()\n    }"
+        ));
+        assert_eq!(report.adapted, vec!["REQ-012"]);
+        assert!(report.unadaptable.is_empty());
+    }
+
+    #[test]
+    fn flags_a_renumbering_with_a_renamed_method_as_unadaptable() {
+        let mut v1 = SpecDocument::new();
+        v1.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        v1.requirements[0].replaced_by = Some("REQ-012".into());
+        let mut v2 = SpecDocument::new();
+        v2.requirements.push(req("REQ-012", "current_balance() returns the current balance"));
+
+        let (_, report) = generate(&v1, &v2, "BankApiV1", "BankApiV2", "BankApiAdapter");
+        assert!(report.adapted.is_empty());
+        assert_eq!(report.unadaptable.len(), 1);
+        assert_eq!(report.unadaptable[0].requirement, "REQ-012");
+        assert!(report.unadaptable[0].reason.contains("supersedes REQ-004"));
+    }
+
+    #[test]
+    fn omits_a_requirement_removed_in_v2_without_flagging_anything() {
+        let mut v1 = SpecDocument::new();
+        v1.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        let v2 = SpecDocument::new();
+
+        let (generated, report) = generate(&v1, &v2, "BankApiV1", "BankApiV2", "BankApiAdapter");
+        assert!(!generated.contains("fn balance"));
+        assert!(report.adapted.is_empty());
+        assert!(report.unadaptable.is_empty());
+    }
+}
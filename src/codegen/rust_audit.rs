@@ -0,0 +1,88 @@
+//! Generates `Audited<T>`, a wrapper that implements a spec'd trait by
+//! delegating every method to `T` and recording the call into an
+//! [`::libspec::audit::AuditLog`](crate::audit::AuditLog) keyed by the
+//! requirement it implements, so a regulated deployment (the bank example
+//! being the canonical one) gets a durable "who called what, and what
+//! happened" trail out of the box. Same wrapper-struct convention as
+//! [`rust_trace`](super::rust_trace) and [`rust_metrics`](super::rust_metrics):
+//! requirements are assumed to take no arguments and return
+//! `Result<(), ::libspec::error::SpecError>`, since the spec doesn't model a
+//! method's real signature — so every generated record's `arguments` list is
+//! empty. Unlike those two backends, the generated code references
+//! [`crate::audit`] directly rather than assuming a third-party crate,
+//! since the audit store lives in `libspec` itself.
+
+use crate::spec::SpecDocument;
+
+use super::method_name;
+
+/// Generates `pub struct {struct_name}<T> { pub inner: T, pub log: ::libspec::audit::AuditLog }`
+/// and `impl<T: {trait_name}> {trait_name} for {struct_name}<T>`: every
+/// requirement's method delegates to `self.inner`, then records the call's
+/// result into `self.log` tagged with the requirement's id.
+pub fn generate(doc: &SpecDocument, trait_name: &str, struct_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "/// Wraps a `{trait_name}` implementation, recording every call into an audit log.\npub struct {struct_name}<T> {{\n    pub inner: T,\n    pub log: ::libspec::audit::AuditLog,\n}}\n\n"
+    ));
+    out.push_str(&format!("impl<T: {trait_name}> {trait_name} for {struct_name}<T> {{\n"));
+    for req in &doc.requirements {
+        let name = method_name(req);
+        out.push_str(&format!("    /// {}: {}\n", req.id, req.text));
+        out.push_str(&format!(
+            "    fn {name}(&self) -> Result<(), ::libspec::error::SpecError> {{\n        \
+             let __result = self.inner.{name}();\n        \
+             self.log.record(\"{name}\", &[], &format!(\"{{__result:?}}\"), &[\"{}\"]);\n        \
+             __result\n    }}\n",
+            req.id
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn delegates_every_requirement_and_records_it_with_its_requirement_id() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi", "Audited");
+        assert!(generated.contains(
+            "pub struct Audited<T> {\n    pub inner: T,\n    pub log: ::libspec::audit::AuditLog,\n}"
+        ));
+        assert!(generated.contains("impl<T: BankApi> BankApi for Audited<T> {"));
+        assert!(generated.contains("let __result = self.inner.balance();"));
+        assert!(generated.contains(
+            "self.log.record(\"balance\", &[], &format!(\"{__result:?}\"), &[\"REQ-004\"]);"
+        ));
+        assert!(generated.contains("__result\n    }"));
+    }
+
+    #[test]
+    fn tags_each_requirement_with_its_own_id() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-001".into(),
+            text: "deposit() adds to the balance".into(),
+            ..Default::default()
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-002".into(),
+            text: "withdraw() subtracts from the balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi", "Audited");
+        assert!(generated.contains("&[\"REQ-001\"]"));
+        assert!(generated.contains("&[\"REQ-002\"]"));
+    }
+}
@@ -0,0 +1,123 @@
+//! Generates a `criterion` benchmark file checking each requirement's
+//! [`PerfBudget`](crate::spec::PerfBudget) — e.g. "balance() completes in
+//! under 1ms for 10k accounts" — against how long calling its method
+//! actually takes. Pairs with
+//! [`harness::check_perf_budgets`](../../libspec_harness/fn.check_perf_budgets.html),
+//! which reads the `estimates.json` this produces and turns it into
+//! pass/fail, same as [`rust_negative_tests`](super::rust_negative_tests)
+//! pairs with [`rust_guard`](super::rust_guard).
+
+use crate::spec::SpecDocument;
+
+use super::method_name;
+
+/// Generates `use criterion::{...}`, one `fn bench_{method}(c: &mut
+/// Criterion)` per requirement with a [`PerfBudget`](crate::spec::PerfBudget),
+/// and a trailing `criterion_group!`/`criterion_main!`. Each bench
+/// function constructs `impl_expr` fresh and calls `bench_function` with
+/// the requirement's own [`method_name`] as the benchmark's id, so
+/// [`check_perf_budgets`](../../libspec_harness/fn.check_perf_budgets.html)
+/// can find its results by the same name. Requirements without a
+/// [`PerfBudget`](crate::spec::PerfBudget) are skipped — there's nothing
+/// to benchmark them against.
+pub fn generate(doc: &SpecDocument, impl_expr: &str) -> String {
+    let mut out = String::new();
+    out.push_str("use criterion::{black_box, criterion_group, criterion_main, Criterion};\n\n");
+
+    let mut fn_names = Vec::new();
+    for req in &doc.requirements {
+        let Some(budget) = &req.perf_budget else { continue };
+        let name = method_name(req);
+        let fn_name = format!("bench_{name}");
+        fn_names.push(fn_name.clone());
+
+        out.push_str(&format!(
+            "/// {}: {} must complete within {}ms at scale {}.\n",
+            req.id, req.text, budget.max_millis, budget.scale
+        ));
+        out.push_str(&format!("fn {fn_name}(c: &mut Criterion) {{\n"));
+        out.push_str(&format!("    let target = {impl_expr};\n"));
+        out.push_str(&format!(
+            "    c.bench_function(\"{name}\", |b| {{\n        b.iter(|| black_box(target.{name}()));\n    }});\n"
+        ));
+        out.push_str("}\n\n");
+    }
+
+    if fn_names.is_empty() {
+        out.push_str("// No requirement declares a perf_budget; nothing to benchmark.\n");
+        return out;
+    }
+
+    out.push_str(&format!("criterion_group!(benches, {});\n", fn_names.join(", ")));
+    out.push_str("criterion_main!(benches);\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{PerfBudget, Requirement};
+
+    #[test]
+    fn generates_a_bench_function_per_perf_budget() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            perf_budget: Some(PerfBudget {
+                scale: 10_000,
+                max_millis: 1.0,
+            }),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankLibrary::new()");
+        assert!(generated.contains("fn bench_balance(c: &mut Criterion) {"));
+        assert!(generated.contains("let target = BankLibrary::new();"));
+        assert!(generated.contains("c.bench_function(\"balance\", |b| {"));
+        assert!(generated.contains("b.iter(|| black_box(target.balance()));"));
+        assert!(generated.contains("criterion_group!(benches, bench_balance);"));
+        assert!(generated.contains("criterion_main!(benches);"));
+        assert!(generated.contains("/// REQ-004: balance() returns the current balance must complete within 1ms at scale 10000."));
+    }
+
+    #[test]
+    fn skips_requirements_without_a_perf_budget() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankLibrary::new()");
+        assert!(!generated.contains("fn bench_balance"));
+        assert!(generated.contains("// No requirement declares a perf_budget"));
+    }
+
+    #[test]
+    fn groups_every_budgeted_requirement_into_one_criterion_group() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            perf_budget: Some(PerfBudget {
+                scale: 10_000,
+                max_millis: 1.0,
+            }),
+            ..Default::default()
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-005".into(),
+            text: "deposit() applies a deposit".into(),
+            perf_budget: Some(PerfBudget {
+                scale: 10_000,
+                max_millis: 2.0,
+            }),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankLibrary::new()");
+        assert!(generated.contains("criterion_group!(benches, bench_balance, bench_deposit);"));
+    }
+}
@@ -0,0 +1,167 @@
+//! Generates an `assert_cmd`-based `#[test]` per
+//! [`CliInvocation`](crate::spec::CliInvocation) declared on a
+//! [`CliContract`](crate::spec::CliContract), so a spec that describes a
+//! binary's argv/exit-code/output contract gets the same
+//! declare-it-once-generate-the-test treatment
+//! [`rust_negative_tests`](super::rust_negative_tests) gives constraint
+//! boundaries. Generated tests call `assert_cmd::Command::cargo_bin` and
+//! `predicates::str::contains`, so the project this is pasted into needs
+//! `assert_cmd` and `predicates` as dev-dependencies — this crate doesn't
+//! depend on either itself, the same way
+//! [`proptest_strategies`](super::proptest_strategies) doesn't depend on
+//! `proptest`.
+
+use crate::spec::{CliContract, CliInvocation, SpecDocument};
+
+/// One `#[test]` per invocation of every [`CliContract`] in `doc`, in
+/// spec order.
+pub fn generate(doc: &SpecDocument) -> String {
+    let mut out = String::new();
+    for contract in &doc.cli_contracts {
+        for invocation in &contract.invocations {
+            out.push_str(&generate_one(contract, invocation));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn generate_one(contract: &CliContract, invocation: &CliInvocation) -> String {
+    let test_name = format!("{}_{}", ident(&contract.name), ident(&invocation.name));
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "/// `{}`: {} (args: `{}`)\n",
+        contract.name,
+        invocation.name,
+        invocation.args.join(" ")
+    ));
+    out.push_str("#[test]\n");
+    out.push_str(&format!("fn {test_name}() {{\n"));
+    out.push_str(&format!(
+        "    let mut cmd = assert_cmd::Command::cargo_bin({:?}).expect(\"binary should build\");\n",
+        contract.bin
+    ));
+    if !invocation.args.is_empty() {
+        let args = invocation.args.iter().map(|a| format!("{a:?}")).collect::<Vec<_>>().join(", ");
+        out.push_str(&format!("    cmd.args([{args}]);\n"));
+    }
+    out.push_str(&format!("    let assert = cmd.assert().code({});\n", invocation.expect_exit_code));
+    if let Some(stdout) = &invocation.expect_stdout_contains {
+        out.push_str(&format!("    let assert = assert.stdout(predicates::str::contains({stdout:?}));\n"));
+    }
+    if let Some(stderr) = &invocation.expect_stderr_contains {
+        out.push_str(&format!("    assert.stderr(predicates::str::contains({stderr:?}));\n"));
+    } else {
+        out.push_str("    let _ = assert;\n");
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// A valid Rust identifier fragment for `name`: lowercased, with runs of
+/// non-alphanumerics collapsed to a single `_`.
+fn ident(name: &str) -> String {
+    let mut out = String::new();
+    let mut last_was_underscore = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+            last_was_underscore = false;
+        } else if !last_was_underscore {
+            out.push('_');
+            last_was_underscore = true;
+        }
+    }
+    out.trim_matches('_').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::SpecDocument;
+
+    #[test]
+    fn generates_a_test_per_invocation() {
+        let mut doc = SpecDocument::new();
+        doc.cli_contracts.push(CliContract {
+            name: "cargo-spec check".into(),
+            bin: "cargo-spec".into(),
+            invocations: vec![
+                CliInvocation {
+                    name: "succeeds on a valid spec".into(),
+                    args: vec!["check".into()],
+                    expect_exit_code: 0,
+                    expect_stdout_contains: None,
+                    expect_stderr_contains: None,
+                },
+                CliInvocation {
+                    name: "fails on a missing spec".into(),
+                    args: vec!["check".into(), "--profile".into(), "ci".into()],
+                    expect_exit_code: 1,
+                    expect_stdout_contains: None,
+                    expect_stderr_contains: Some("no libspec.toml found".into()),
+                },
+            ],
+        });
+
+        let generated = generate(&doc);
+        assert_eq!(generated.matches("#[test]").count(), 2);
+        assert!(generated.contains("fn cargo_spec_check_succeeds_on_a_valid_spec()"));
+        assert!(generated.contains("fn cargo_spec_check_fails_on_a_missing_spec()"));
+    }
+
+    #[test]
+    fn passes_args_to_the_command() {
+        let contract = CliContract { name: "c".into(), bin: "b".into(), invocations: vec![] };
+        let invocation = CliInvocation {
+            name: "i".into(),
+            args: vec!["--flag".into(), "value".into()],
+            expect_exit_code: 0,
+            expect_stdout_contains: None,
+            expect_stderr_contains: None,
+        };
+        let generated = generate_one(&contract, &invocation);
+        assert!(generated.contains(r#"cmd.args(["--flag", "value"]);"#));
+    }
+
+    #[test]
+    fn checks_the_expected_exit_code() {
+        let contract = CliContract { name: "c".into(), bin: "b".into(), invocations: vec![] };
+        let invocation = CliInvocation {
+            name: "i".into(),
+            args: vec![],
+            expect_exit_code: 2,
+            expect_stdout_contains: None,
+            expect_stderr_contains: None,
+        };
+        assert!(generate_one(&contract, &invocation).contains(".code(2)"));
+    }
+
+    #[test]
+    fn checks_stdout_and_stderr_content_when_given() {
+        let contract = CliContract { name: "c".into(), bin: "b".into(), invocations: vec![] };
+        let invocation = CliInvocation {
+            name: "i".into(),
+            args: vec![],
+            expect_exit_code: 0,
+            expect_stdout_contains: Some("hello".into()),
+            expect_stderr_contains: Some("warning".into()),
+        };
+        let generated = generate_one(&contract, &invocation);
+        assert!(generated.contains(r#"predicates::str::contains("hello")"#));
+        assert!(generated.contains(r#"predicates::str::contains("warning")"#));
+    }
+
+    #[test]
+    fn a_contract_with_no_invocations_generates_nothing() {
+        let mut doc = SpecDocument::new();
+        doc.cli_contracts.push(CliContract { name: "c".into(), bin: "b".into(), invocations: vec![] });
+        assert_eq!(generate(&doc), "");
+    }
+
+    #[test]
+    fn sanitizes_punctuation_in_names_into_a_valid_identifier() {
+        assert_eq!(ident("cargo-spec check!"), "cargo_spec_check");
+    }
+}
@@ -0,0 +1,267 @@
+//! Generates `serde`-enabled Rust structs (DTOs) plus validating builders
+//! from the spec's [`DataType`](crate::spec::DataType) declarations, so
+//! `Account`/`Transaction`-style records aren't invisible to the spec.
+
+use crate::spec::{DataType, SpecDocument, Severity};
+
+use super::{pascal_case_code, severity_variant};
+
+/// Generates, per data type in `doc`: a `#[derive(Debug, Clone, Serialize,
+/// Deserialize)] pub struct {name}`; a `{name}Error` enum with one variant
+/// per constraint the builder below knows how to check mechanically
+/// (currently: a constraint whose text contains "positive", checked as
+/// `field > 0` on numeric fields); and a `{name}Builder` with one setter
+/// per field and a `build()` that runs those checks and returns
+/// `Result<{name}, {name}Error>` instead of a stringly-typed error.
+/// Constraints it can't check are still listed in `build`'s doc comment
+/// for traceability, but aren't enforced — same best-effort spirit as
+/// [`method_name`](super::method_name). Each check that does run reports
+/// its outcome to [`crate::runtime`], so runtime coverage of a constraint
+/// can be measured the same way test/implementation coverage is. A
+/// constraint whose [`Severity`] isn't [`Error`](Severity::Error) still
+/// reports, but doesn't fail `build()` — same policy as
+/// [`rust_guard`](super::rust_guard). `{name}Error` also gets a
+/// `From<{name}Error> for ::libspec::error::SpecError` and a `Serialize`
+/// impl built on it, so it serializes to `SpecError`'s documented wire
+/// format (see [`crate::error`]) like [`rust_error`](super::rust_error)'s
+/// generated enum does.
+pub fn generate(doc: &SpecDocument, type_names: &[&str]) -> String {
+    let mut out = String::new();
+    for name in type_names {
+        let Some(dt) = doc.data_type(name) else {
+            continue;
+        };
+        out.push_str(&generate_one(doc, dt));
+        out.push('\n');
+    }
+    out
+}
+
+/// Constraints on `dt`'s fields whose text this module knows how to check.
+fn checked_constraints<'a>(
+    doc: &'a SpecDocument,
+    dt: &'a DataType,
+) -> Vec<(&'a str, &'a str, &'a str, Severity)> {
+    dt.fields
+        .iter()
+        .flat_map(|field| {
+            field.constraints.iter().filter_map(move |code| {
+                let constraint = doc.constraint(code)?;
+                let text = constraint.text.as_str();
+                text.contains("positive").then_some((
+                    field.name.as_str(),
+                    code.as_str(),
+                    text,
+                    constraint.severity,
+                ))
+            })
+        })
+        .collect()
+}
+
+fn generate_one(doc: &SpecDocument, dt: &DataType) -> String {
+    let mut out = String::new();
+    let error_name = format!("{}Error", dt.name);
+    let checked = checked_constraints(doc, dt);
+
+    out.push_str("#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]\n");
+    out.push_str(&format!("pub struct {} {{\n", dt.name));
+    for field in &dt.fields {
+        out.push_str(&format!("    pub {}: {},\n", field.name, field.ty));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str("#[derive(Debug, Clone, PartialEq)]\n");
+    out.push_str(&format!("#[non_exhaustive]\npub enum {error_name} {{\n"));
+    for (field_name, code, text, _) in &checked {
+        out.push_str(&format!("    /// `{field_name}` ({code}): {text}\n"));
+        out.push_str(&format!(
+            "    {} {{ code: &'static str, message: &'static str }},\n",
+            pascal_case_code(code)
+        ));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!("impl std::fmt::Display for {error_name} {{\n"));
+    out.push_str("    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {\n");
+    out.push_str("        match self {\n");
+    for (_, code, _, _) in &checked {
+        out.push_str(&format!(
+            "            {error_name}::{} {{ code, message }} => write!(f, \"{{code}}: {{message}}\"),\n",
+            pascal_case_code(code)
+        ));
+    }
+    out.push_str("        }\n    }\n}\n\n");
+    out.push_str(&format!("impl std::error::Error for {error_name} {{}}\n\n"));
+
+    out.push_str(&format!(
+        "impl From<{error_name}> for ::libspec::error::SpecError {{\n"
+    ));
+    out.push_str(&format!("    fn from(err: {error_name}) -> Self {{\n"));
+    out.push_str("        match err {\n");
+    for (_, code, _, _) in &checked {
+        out.push_str(&format!(
+            "            {error_name}::{} {{ code, message }} => ::libspec::error::SpecError::new(code, message),\n",
+            pascal_case_code(code)
+        ));
+    }
+    out.push_str("        }\n    }\n}\n\n");
+
+    out.push_str(&format!("impl serde::Serialize for {error_name} {{\n"));
+    out.push_str(
+        "    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {\n",
+    );
+    out.push_str("        ::libspec::error::SpecError::from(self.clone()).serialize(serializer)\n");
+    out.push_str("    }\n}\n\n");
+
+    out.push_str(&format!("impl {} {{\n", dt.name));
+    out.push_str(&format!(
+        "    pub fn builder() -> {}Builder {{\n        {}Builder::default()\n    }}\n}}\n\n",
+        dt.name, dt.name
+    ));
+
+    out.push_str("#[derive(Debug, Clone, Default)]\n");
+    out.push_str(&format!("pub struct {}Builder {{\n", dt.name));
+    for field in &dt.fields {
+        out.push_str(&format!("    {}: Option<{}>,\n", field.name, field.ty));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!("impl {}Builder {{\n", dt.name));
+    for field in &dt.fields {
+        out.push_str(&format!(
+            "    pub fn {0}(mut self, value: {1}) -> Self {{\n        self.{0} = Some(value);\n        self\n    }}\n\n",
+            field.name, field.ty
+        ));
+    }
+
+    out.push_str("    /// Builds the value, checking:\n");
+    for field in &dt.fields {
+        for code in &field.constraints {
+            let text = doc.constraint(code).map(|c| c.text.as_str()).unwrap_or("");
+            out.push_str(&format!("    /// - `{}` ({code}): {text}\n", field.name));
+        }
+    }
+    out.push_str(&format!(
+        "    pub fn build(self) -> Result<{}, {error_name}> {{\n",
+        dt.name
+    ));
+    for field in &dt.fields {
+        out.push_str(&format!(
+            "        let {0} = self.{0}.unwrap_or_default();\n",
+            field.name
+        ));
+    }
+    for (field_name, code, text, severity) in &checked {
+        let severity = severity_variant(*severity);
+        out.push_str(&format!(
+            "        if !({field_name} > Default::default()) {{\n            ::libspec::runtime::report(\"{code}\", ::libspec::runtime::Outcome::Violated, ::libspec::spec::Severity::{severity});\n",
+        ));
+        if severity == "Error" {
+            out.push_str(&format!(
+                "            return Err({error_name}::{}{{ code: \"{code}\", message: \"{text}\" }});\n",
+                pascal_case_code(code)
+            ));
+        }
+        out.push_str(&format!(
+            "        }} else {{\n            ::libspec::runtime::report(\"{code}\", ::libspec::runtime::Outcome::Passed, ::libspec::spec::Severity::{severity});\n        }}\n",
+        ));
+    }
+    out.push_str(&format!(
+        "        Ok({} {{ {} }})\n",
+        dt.name,
+        dt.fields
+            .iter()
+            .map(|f| f.name.clone())
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    out.push_str("    }\n}\n");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, Field};
+
+    fn doc_with_account() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            ..Default::default()
+        });
+        doc.data_types.push(DataType {
+            name: "Account".into(),
+            fields: vec![Field {
+                name: "balance".into(),
+                ty: "f64".into(),
+                constraints: vec!["CONST-001".into()],
+            }],
+        });
+        doc
+    }
+
+    #[test]
+    fn generates_struct_error_enum_and_builder() {
+        let doc = doc_with_account();
+        let generated = generate(&doc, &["Account"]);
+        assert!(generated.contains("pub struct Account {"));
+        assert!(generated.contains("pub balance: f64,"));
+        assert!(generated.contains("pub enum AccountError {"));
+        assert!(generated.contains("Const001 { code: &'static str, message: &'static str },"));
+        assert!(generated.contains("pub fn builder() -> AccountBuilder {"));
+        assert!(generated.contains("pub struct AccountBuilder {"));
+        assert!(generated.contains("pub fn balance(mut self, value: f64) -> Self {"));
+        assert!(generated.contains("pub fn build(self) -> Result<Account, AccountError> {"));
+        assert!(generated.contains("if !(balance > Default::default())"));
+        assert!(generated.contains(
+            "return Err(AccountError::Const001{ code: \"CONST-001\", message: \"amount must be positive\" });"
+        ));
+        assert!(generated.contains(
+            "::libspec::runtime::report(\"CONST-001\", ::libspec::runtime::Outcome::Violated, ::libspec::spec::Severity::Error);"
+        ));
+        assert!(generated.contains(
+            "::libspec::runtime::report(\"CONST-001\", ::libspec::runtime::Outcome::Passed, ::libspec::spec::Severity::Error);"
+        ));
+        assert!(generated.contains("impl From<AccountError> for ::libspec::error::SpecError {"));
+        assert!(generated.contains(
+            "AccountError::Const001 { code, message } => ::libspec::error::SpecError::new(code, message),"
+        ));
+        assert!(generated.contains("impl serde::Serialize for AccountError {"));
+    }
+
+    #[test]
+    fn skips_unknown_type_names() {
+        let doc = doc_with_account();
+        let generated = generate(&doc, &["NotAType"]);
+        assert!(generated.is_empty());
+    }
+
+    #[test]
+    fn warning_severity_reports_instead_of_returning_err() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "balance should stay positive".into(),
+            severity: Severity::Warning,
+            ..Default::default()
+        });
+        doc.data_types.push(DataType {
+            name: "Account".into(),
+            fields: vec![Field {
+                name: "balance".into(),
+                ty: "f64".into(),
+                constraints: vec!["CONST-002".into()],
+            }],
+        });
+
+        let generated = generate(&doc, &["Account"]);
+        assert!(generated.contains(
+            "::libspec::runtime::report(\"CONST-002\", ::libspec::runtime::Outcome::Violated, ::libspec::spec::Severity::Warning);"
+        ));
+        assert!(!generated.contains("return Err(AccountError::Const002"));
+    }
+}
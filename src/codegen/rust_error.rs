@@ -0,0 +1,205 @@
+//! Generates a typed error enum with one variant per constraint.
+
+use crate::spec::SpecDocument;
+
+use super::{constraint_table_doc, pascal_case_code};
+
+/// Generates a `#[non_exhaustive] pub enum {enum_name}` with one variant per
+/// constraint in `doc`, each carrying the constraint's `code` and `message`
+/// so callers match on a typed error instead of parsing a formatted string
+/// like `"CONST-002: insufficient funds"`. `#[non_exhaustive]` leaves room
+/// for the hand-written implementation to add variants (e.g. for errors the
+/// spec doesn't model as constraints) without the generated code becoming
+/// stale.
+///
+/// Also generates a `From<{enum_name}> for ::libspec::error::SpecError` and
+/// a `Serialize` impl built on it, so `{enum_name}` serializes to
+/// `SpecError`'s documented wire format (see [`crate::error`]) instead of
+/// serde's default per-variant representation.
+///
+/// The enum itself is doc-commented with a table of every constraint's
+/// severity and message, so `cargo doc` shows the full contract next to
+/// the typed error instead of only in the spec file.
+///
+/// See [`generate_thiserror`] for a `thiserror`-based alternative to this
+/// function's hand-written `Display`/`Error` impls.
+pub fn generate(doc: &SpecDocument, enum_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&constraint_table_doc(&doc.constraints));
+    out.push_str("#[derive(Debug, Clone, PartialEq)]\n");
+    out.push_str(&format!("#[non_exhaustive]\npub enum {enum_name} {{\n"));
+    for c in &doc.constraints {
+        out.push_str(&format!("    /// {}: {}\n", c.code, c.text));
+        out.push_str(&format!(
+            "    {} {{ code: &'static str, message: &'static str }},\n",
+            pascal_case_code(&c.code)
+        ));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!("impl std::fmt::Display for {enum_name} {{\n"));
+    out.push_str("    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {\n");
+    out.push_str("        match self {\n");
+    for c in &doc.constraints {
+        out.push_str(&format!(
+            "            {enum_name}::{} {{ code, message }} => write!(f, \"{{code}}: {{message}}\"),\n",
+            pascal_case_code(&c.code)
+        ));
+    }
+    out.push_str("        }\n    }\n}\n\n");
+    out.push_str(&format!("impl std::error::Error for {enum_name} {{}}\n\n"));
+
+    out.push_str(&conversions(doc, enum_name));
+    out
+}
+
+/// Generates the same `{enum_name}` as [`generate`], but deriving
+/// `thiserror::Error` with an `#[error("{{code}}: {{message}}")]` per
+/// variant instead of hand-writing `Display`/`Error`, for teams that have
+/// standardized on `thiserror` across their generated and hand-written
+/// error types.
+pub fn generate_thiserror(doc: &SpecDocument, enum_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&constraint_table_doc(&doc.constraints));
+    out.push_str("#[derive(Debug, Clone, PartialEq, thiserror::Error)]\n");
+    out.push_str(&format!("#[non_exhaustive]\npub enum {enum_name} {{\n"));
+    for c in &doc.constraints {
+        out.push_str(&format!("    /// {}: {}\n", c.code, c.text));
+        out.push_str("    #[error(\"{code}: {message}\")]\n");
+        out.push_str(&format!(
+            "    {} {{ code: &'static str, message: &'static str }},\n",
+            pascal_case_code(&c.code)
+        ));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&conversions(doc, enum_name));
+    out
+}
+
+/// The `From<{enum_name}> for ::libspec::error::SpecError` and `Serialize`
+/// impls shared by [`generate`] and [`generate_thiserror`] — see
+/// [`generate`]'s docs for why they exist.
+fn conversions(doc: &SpecDocument, enum_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "impl From<{enum_name}> for ::libspec::error::SpecError {{\n"
+    ));
+    out.push_str(&format!("    fn from(err: {enum_name}) -> Self {{\n"));
+    out.push_str("        match err {\n");
+    for c in &doc.constraints {
+        out.push_str(&format!(
+            "            {enum_name}::{} {{ code, message }} => ::libspec::error::SpecError::new(code, message),\n",
+            pascal_case_code(&c.code)
+        ));
+    }
+    out.push_str("        }\n    }\n}\n\n");
+
+    out.push_str(&format!("impl serde::Serialize for {enum_name} {{\n"));
+    out.push_str(
+        "    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {\n",
+    );
+    out.push_str("        ::libspec::error::SpecError::from(self.clone()).serialize(serializer)\n");
+    out.push_str("    }\n}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Constraint;
+
+    #[test]
+    fn generates_one_variant_per_constraint() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "insufficient funds".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankError");
+        assert!(generated.contains("#[non_exhaustive]\npub enum BankError {"));
+        assert!(generated.contains("/// CONST-002: insufficient funds"));
+        assert!(generated.contains("Const002 { code: &'static str, message: &'static str },"));
+    }
+
+    #[test]
+    fn enum_doc_comment_includes_a_constraint_table() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "insufficient funds".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankError");
+        assert!(generated.contains("/// | Constraint | Severity | Message |"));
+        assert!(generated.contains("/// | CONST-002 |"));
+    }
+
+    #[test]
+    fn display_formats_code_and_message() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "insufficient funds".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankError");
+        assert!(generated.contains(
+            "BankError::Const002 { code, message } => write!(f, \"{code}: {message}\"),"
+        ));
+    }
+
+    #[test]
+    fn converts_to_a_spec_error_and_serializes_through_it() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "insufficient funds".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankError");
+        assert!(generated.contains("impl From<BankError> for ::libspec::error::SpecError {"));
+        assert!(generated.contains(
+            "BankError::Const002 { code, message } => ::libspec::error::SpecError::new(code, message),"
+        ));
+        assert!(generated.contains("impl serde::Serialize for BankError {"));
+        assert!(generated.contains(
+            "::libspec::error::SpecError::from(self.clone()).serialize(serializer)"
+        ));
+    }
+
+    #[test]
+    fn thiserror_variant_derives_error_with_a_per_variant_attribute() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "insufficient funds".into(),
+            ..Default::default()
+        });
+
+        let generated = generate_thiserror(&doc, "BankError");
+        assert!(generated.contains("#[derive(Debug, Clone, PartialEq, thiserror::Error)]"));
+        assert!(generated.contains("#[error(\"{code}: {message}\")]"));
+        assert!(generated.contains("Const002 { code: &'static str, message: &'static str },"));
+        assert!(!generated.contains("impl std::fmt::Display for BankError"));
+    }
+
+    #[test]
+    fn thiserror_variant_still_gets_the_spec_error_conversion_and_serialize_impl() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "insufficient funds".into(),
+            ..Default::default()
+        });
+
+        let generated = generate_thiserror(&doc, "BankError");
+        assert!(generated.contains("impl From<BankError> for ::libspec::error::SpecError {"));
+        assert!(generated.contains("impl serde::Serialize for BankError {"));
+    }
+}
@@ -0,0 +1,275 @@
+//! Generates a guard-clause check function per constraint that carries an
+//! `expr` (see [`crate::spec::expr`]), so checks like `amount > 0` or
+//! `amount <= balance(account)` are derived from the spec instead of
+//! hand-written and potentially divergent from what it declares. `expr` may
+//! also compose comparisons with `&&`/`||`, e.g.
+//! `amount > 0 && amount <= balance(account)`; every comparison in the
+//! expression contributes its identifiers and calls to the generated
+//! function's signature.
+
+use crate::spec::{Constraint, ConstraintExpr, SpecDocument, Term};
+
+use super::{capitalize, severity_variant};
+
+/// Generates one `pub fn check_{code}` per constraint in `doc` whose `expr`
+/// parses and only calls functions on bare identifiers (no nested calls,
+/// no arithmetic inside a call's arguments). Every bare identifier becomes
+/// an `f64` parameter; every call becomes a closure parameter, generic
+/// over arguments that aren't otherwise compared as `f64`. So
+/// `amount <= balance(account)` generates:
+///
+/// ```text
+/// pub fn check_const_002<Account>(
+///     amount: f64,
+///     account: Account,
+///     balance: impl Fn(Account) -> f64,
+/// ) -> Result<(), ::libspec::error::SpecError> { ... }
+/// ```
+///
+/// The body is a single call to [`crate::runtime::enforce`], which reports
+/// the outcome and decides whether a violation returns `Err`, only
+/// `debug_assert!`s, or is silently let through, according to the
+/// constraint's [`Severity`](crate::spec::Severity) and that severity's current
+/// [`EnforcementMode`](crate::runtime::EnforcementMode) — so a deployment
+/// can dial a constraint's strictness up or down without regenerating
+/// this function.
+///
+/// Constraints without an `expr`, whose `expr` doesn't parse, or whose
+/// `expr` needs more than this — same best-effort spirit as
+/// [`rust_dto`](super::rust_dto) — are skipped, with a trailing comment
+/// explaining why, for traceability.
+pub fn generate(doc: &SpecDocument) -> String {
+    let mut out = String::new();
+    let mut skipped = Vec::new();
+    for c in &doc.constraints {
+        let Some(expr_src) = &c.expr else { continue };
+        match ConstraintExpr::parse(expr_src) {
+            Ok(expr) => match plan(&expr) {
+                Some(plan) => {
+                    out.push_str(&generate_one(c, &expr, &plan));
+                    out.push('\n');
+                }
+                None => skipped.push((
+                    c.code.clone(),
+                    "expr calls a function with an argument that isn't a bare identifier"
+                        .to_string(),
+                )),
+            },
+            Err(e) => skipped.push((c.code.clone(), format!("expr failed to parse: {e}"))),
+        }
+    }
+    if !skipped.is_empty() {
+        out.push_str("// Constraints not checked here (see their `expr` in the spec):\n");
+        for (code, reason) in &skipped {
+            out.push_str(&format!("// - {code}: {reason}\n"));
+        }
+    }
+    out
+}
+
+/// What [`generate`] learned about a constraint's `expr` well enough to
+/// emit a check function: the bare identifiers it needs as `f64`
+/// parameters, and the calls it needs as closure parameters. Also used by
+/// [`proptest_strategies`](super::proptest_strategies) to generate a bare
+/// predicate function ("oracle") with the same signature.
+pub(super) struct Plan {
+    pub(super) bare: Vec<String>,
+    pub(super) calls: Vec<(String, Vec<String>)>,
+}
+
+pub(super) fn plan(expr: &ConstraintExpr) -> Option<Plan> {
+    let mut bare = Vec::new();
+    let mut calls = Vec::new();
+    for comparison in expr.comparisons() {
+        collect(&comparison.lhs, &mut bare, &mut calls)?;
+        collect(&comparison.rhs, &mut bare, &mut calls)?;
+    }
+    Some(Plan { bare, calls })
+}
+
+fn collect(term: &Term, bare: &mut Vec<String>, calls: &mut Vec<(String, Vec<String>)>) -> Option<()> {
+    match term {
+        Term::Number(_) => Some(()),
+        Term::Ident(name) => {
+            if !bare.contains(name) {
+                bare.push(name.clone());
+            }
+            Some(())
+        }
+        Term::Call(name, args) => {
+            let mut arg_names = Vec::new();
+            for arg in args {
+                match arg {
+                    Term::Ident(n) => arg_names.push(n.clone()),
+                    _ => return None,
+                }
+            }
+            if !calls.iter().any(|(n, a)| n == name && a == &arg_names) {
+                calls.push((name.clone(), arg_names));
+            }
+            Some(())
+        }
+        Term::Add(a, b) | Term::Sub(a, b) | Term::Mul(a, b) | Term::Div(a, b) => {
+            collect(a, bare, calls)?;
+            collect(b, bare, calls)
+        }
+    }
+}
+
+/// The Rust type a call argument gets in the generated signature: `f64` if
+/// it's also compared directly as a bare identifier, otherwise a generic
+/// type parameter named after it.
+fn arg_type(name: &str, bare: &[String]) -> String {
+    if bare.iter().any(|b| b == name) {
+        "f64".to_string()
+    } else {
+        capitalize(name)
+    }
+}
+
+/// The `<Generics>` clause and parameter list a [`Plan`] implies, shared by
+/// [`generate_one`] and by [`proptest_strategies`](super::proptest_strategies)'s
+/// oracle generator so a check function and its property-test oracle always
+/// agree on signature.
+pub(super) fn signature_parts(plan: &Plan) -> (String, Vec<String>) {
+    let generics: Vec<String> = plan
+        .calls
+        .iter()
+        .flat_map(|(_, args)| args.iter())
+        .filter(|a| !plan.bare.contains(a))
+        .map(|a| capitalize(a))
+        .fold(Vec::new(), |mut acc, g| {
+            if !acc.contains(&g) {
+                acc.push(g);
+            }
+            acc
+        });
+
+    let mut params = Vec::new();
+    for name in &plan.bare {
+        params.push(format!("{name}: f64"));
+    }
+    for (_, args) in &plan.calls {
+        for name in args {
+            if !plan.bare.contains(name) {
+                params.push(format!("{name}: {}", arg_type(name, &plan.bare)));
+            }
+        }
+    }
+    for (name, args) in &plan.calls {
+        let arg_types: Vec<String> = args.iter().map(|a| arg_type(a, &plan.bare)).collect();
+        params.push(format!("{name}: impl Fn({}) -> f64", arg_types.join(", ")));
+    }
+
+    let generics_str = if generics.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", generics.join(", "))
+    };
+
+    (generics_str, params)
+}
+
+fn generate_one(c: &Constraint, expr: &ConstraintExpr, plan: &Plan) -> String {
+    let fn_name = format!("check_{}", c.code.to_lowercase().replace('-', "_"));
+    let (generics_str, params) = signature_parts(plan);
+
+    let mut out = String::new();
+    out.push_str(&format!("/// {}: {}\n", c.code, c.text));
+    out.push_str(&format!(
+        "pub fn {fn_name}{generics_str}({}) -> Result<(), ::libspec::error::SpecError> {{\n",
+        params.join(", ")
+    ));
+    let severity = severity_variant(c.severity);
+    out.push_str(&format!(
+        "    ::libspec::runtime::enforce(\"{}\", \"{}\", ::libspec::spec::Severity::{severity}, !({expr}))\n",
+        c.code, c.text
+    ));
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Severity;
+
+    #[test]
+    fn generates_a_simple_comparison_check() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            expr: Some("amount > 0".into()),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc);
+        assert!(generated.contains("pub fn check_const_001(amount: f64) -> Result<(), ::libspec::error::SpecError> {"));
+        assert!(generated.contains(
+            "::libspec::runtime::enforce(\"CONST-001\", \"amount must be positive\", ::libspec::spec::Severity::Error, !(amount > 0))"
+        ));
+    }
+
+    #[test]
+    fn warning_severity_reports_instead_of_returning_err() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-005".into(),
+            text: "amount should stay under the soft cap".into(),
+            expr: Some("amount <= 1000".into()),
+            severity: Severity::Warning,
+            ..Default::default()
+        });
+
+        let generated = generate(&doc);
+        assert!(generated.contains(
+            "::libspec::runtime::enforce(\"CONST-005\", \"amount should stay under the soft cap\", ::libspec::spec::Severity::Warning, !(amount <= 1000))"
+        ));
+    }
+
+    #[test]
+    fn generates_a_generic_closure_parameter_for_a_call() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "amount must not exceed the balance".into(),
+            expr: Some("amount <= balance(account)".into()),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc);
+        assert!(generated.contains("pub fn check_const_002<Account>("));
+        assert!(generated.contains("amount: f64"));
+        assert!(generated.contains("account: Account"));
+        assert!(generated.contains("balance: impl Fn(Account) -> f64"));
+        assert!(generated.contains("!(amount <= balance(account))"));
+    }
+
+    #[test]
+    fn skips_constraints_without_an_expr() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-003".into(),
+            text: "prose-only constraint".into(),
+            expr: None,
+            ..Default::default()
+        });
+        assert_eq!(generate(&doc), "");
+    }
+
+    #[test]
+    fn notes_an_unparseable_expr_instead_of_generating_a_broken_function() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-004".into(),
+            text: "broken".into(),
+            expr: Some("amount >".into()),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc);
+        assert!(!generated.contains("fn check_const_004"));
+        assert!(generated.contains("// - CONST-004: expr failed to parse:"));
+    }
+}
@@ -0,0 +1,49 @@
+//! Generates a skeleton `impl` of a [`rust_trait`](super::rust_trait)-shaped
+//! trait, so a team has something that compiles to start from.
+
+use crate::spec::SpecDocument;
+
+use super::method_name;
+
+/// Generates `impl {trait_name} for {struct_name}` with one method per
+/// requirement in `doc`, each body a `todo!()` and preceded by a
+/// `// implements {req.id}` marker, plus a `supported_versions` stub
+/// returning `vec![]` for the team to fill in. The signatures match
+/// [`rust_trait::generate`](super::rust_trait::generate), so the stub
+/// compiles against a trait generated from the same spec.
+pub fn generate(doc: &SpecDocument, trait_name: &str, struct_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("impl {trait_name} for {struct_name} {{\n"));
+    for req in &doc.requirements {
+        out.push_str(&format!("    // implements {}\n", req.id));
+        out.push_str(&format!(
+            "    fn {}(&self) -> Result<(), ::libspec::error::SpecError> {{\n        todo!()\n    }}\n",
+            method_name(req)
+        ));
+    }
+    out.push_str("    fn supported_versions(&self) -> Vec<&'static str> {\n        todo!()\n    }\n");
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn generates_one_stub_method_per_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi", "BankLibrary");
+        assert!(generated.contains("impl BankApi for BankLibrary {"));
+        assert!(generated.contains("// implements REQ-004"));
+        assert!(generated.contains("fn balance(&self) -> Result<(), ::libspec::error::SpecError> {\n        todo!()\n    }"));
+        assert!(generated.contains("fn supported_versions(&self) -> Vec<&'static str> {\n        todo!()\n    }"));
+    }
+}
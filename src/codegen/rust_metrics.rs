@@ -0,0 +1,93 @@
+//! Generates `Metered<T>`, a wrapper that implements a spec'd trait by
+//! delegating every method to `T`, emitting a call counter, a duration
+//! histogram, and a violation counter per operation through the
+//! `metrics` facade, so an operator gets spec-level observability (which
+//! operations are called, how long they take, how often they fail) out
+//! of the box, instead of wiring up instrumentation by hand per
+//! operation. Same wrapper-struct convention as
+//! [`rust_adapter`](super::rust_adapter), [`checked`](super::checked),
+//! and [`rust_trace`](super::rust_trace): requirements are assumed to
+//! take no arguments and return `Result<(), ::libspec::error::SpecError>`,
+//! since the spec doesn't model a method's real signature. The generated
+//! code assumes the consuming crate already depends on `metrics`, the
+//! same assumption [`rust_trace`](super::rust_trace) makes about
+//! `tracing`; this is independent of `libspec`'s own `metrics` Cargo
+//! feature, which only governs [`crate::runtime::MetricsSink`].
+
+use crate::spec::SpecDocument;
+
+use super::method_name;
+
+/// Generates `pub struct {struct_name}<T> { pub inner: T }` and `impl<T:
+/// {trait_name}> {trait_name} for {struct_name}<T>`: every requirement's
+/// method increments a call counter, times the delegated call into a
+/// duration histogram, and increments a violation counter if it returns
+/// `Err`, all labeled with the operation's [`method_name`].
+pub fn generate(doc: &SpecDocument, trait_name: &str, struct_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "/// Wraps a `{trait_name}` implementation, emitting call/duration/violation metrics per operation.\npub struct {struct_name}<T> {{\n    pub inner: T,\n}}\n\n"
+    ));
+    out.push_str(&format!("impl<T: {trait_name}> {trait_name} for {struct_name}<T> {{\n"));
+    for req in &doc.requirements {
+        let name = method_name(req);
+        out.push_str(&format!("    /// {}: {}\n", req.id, req.text));
+        out.push_str(&format!(
+            "    fn {name}(&self) -> Result<(), ::libspec::error::SpecError> {{\n        \
+             ::metrics::counter!(\"libspec_operation_calls_total\", \"operation\" => \"{name}\").increment(1);\n        \
+             let __start = ::std::time::Instant::now();\n        \
+             let __result = self.inner.{name}();\n        \
+             ::metrics::histogram!(\"libspec_operation_duration_seconds\", \"operation\" => \"{name}\").record(__start.elapsed().as_secs_f64());\n        \
+             if __result.is_err() {{\n            \
+             ::metrics::counter!(\"libspec_operation_violations_total\", \"operation\" => \"{name}\").increment(1);\n        \
+             }}\n        \
+             __result\n    }}\n"
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn delegates_every_requirement_with_call_and_duration_metrics() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi", "Metered");
+        assert!(generated.contains("pub struct Metered<T> {\n    pub inner: T,\n}"));
+        assert!(generated.contains("impl<T: BankApi> BankApi for Metered<T> {"));
+        assert!(generated.contains(
+            "::metrics::counter!(\"libspec_operation_calls_total\", \"operation\" => \"balance\").increment(1);"
+        ));
+        assert!(generated.contains(
+            "::metrics::histogram!(\"libspec_operation_duration_seconds\", \"operation\" => \"balance\").record(__start.elapsed().as_secs_f64());"
+        ));
+        assert!(generated.contains("let __result = self.inner.balance();"));
+        assert!(generated.contains("__result\n    }"));
+    }
+
+    #[test]
+    fn increments_a_violation_counter_only_when_the_delegated_call_errs() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi", "Metered");
+        assert!(generated.contains("if __result.is_err() {"));
+        assert!(generated.contains(
+            "::metrics::counter!(\"libspec_operation_violations_total\", \"operation\" => \"balance\").increment(1);"
+        ));
+    }
+}
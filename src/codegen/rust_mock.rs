@@ -0,0 +1,77 @@
+//! Generates a scriptable mock implementation of a
+//! [`rust_trait`](super::rust_trait)-shaped trait, so a client of the trait
+//! can be tested against spec-accurate failures without a real backend.
+
+use crate::spec::SpecDocument;
+
+use super::method_name;
+
+/// Generates `pub struct {struct_name}` implementing `{trait_name}`, where
+/// each method's result can be scripted ahead of a test by constraint code
+/// via `script(method_name, constraint_code)`. A method with nothing
+/// scripted for it returns `Ok(())`; one with a scripted constraint code
+/// returns a `SpecError` carrying that code, so a test can assert a client
+/// handles e.g. `CONST-002` the way the spec requires.
+pub fn generate(doc: &SpecDocument, trait_name: &str, struct_name: &str) -> String {
+    let mut out = String::new();
+
+    out.push_str(&format!("#[derive(Default)]\npub struct {struct_name} {{\n"));
+    out.push_str(
+        "    scripted: std::cell::RefCell<std::collections::HashMap<&'static str, String>>,\n",
+    );
+    out.push_str("}\n\n");
+
+    out.push_str(&format!("impl {struct_name} {{\n"));
+    out.push_str("    /// Makes `method` fail with `constraint_code` the next time it's called.\n");
+    out.push_str("    pub fn script(&self, method: &'static str, constraint_code: &str) {\n");
+    out.push_str(
+        "        self.scripted.borrow_mut().insert(method, constraint_code.to_string());\n",
+    );
+    out.push_str("    }\n\n");
+    out.push_str("    /// Removes any scripted failure for `method`.\n");
+    out.push_str("    pub fn clear(&self, method: &'static str) {\n");
+    out.push_str("        self.scripted.borrow_mut().remove(method);\n");
+    out.push_str("    }\n\n");
+    out.push_str("    fn check(&self, method: &'static str) -> Result<(), ::libspec::error::SpecError> {\n");
+    out.push_str("        match self.scripted.borrow().get(method) {\n");
+    out.push_str("            Some(code) => Err(::libspec::error::SpecError::new(code.clone(), \"scripted failure\")),\n");
+    out.push_str("            None => Ok(()),\n");
+    out.push_str("        }\n    }\n");
+    out.push_str("}\n\n");
+
+    out.push_str(&format!("impl {trait_name} for {struct_name} {{\n"));
+    for req in &doc.requirements {
+        let name = method_name(req);
+        out.push_str(&format!("    /// {}: {}\n", req.id, req.text));
+        out.push_str(&format!(
+            "    fn {name}(&self) -> Result<(), ::libspec::error::SpecError> {{\n        self.check(\"{name}\")\n    }}\n"
+        ));
+    }
+    out.push_str("    fn supported_versions(&self) -> Vec<&'static str> {\n        Vec::new()\n    }\n");
+    out.push_str("}\n");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn generates_mock_with_scriptable_methods() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankAPI", "MockBankAPI");
+        assert!(generated.contains("pub struct MockBankAPI {"));
+        assert!(generated.contains("impl BankAPI for MockBankAPI {"));
+        assert!(generated.contains("fn balance(&self) -> Result<(), ::libspec::error::SpecError> {\n        self.check(\"balance\")\n    }"));
+        assert!(generated.contains("fn supported_versions(&self) -> Vec<&'static str> {\n        Vec::new()\n    }"));
+        assert!(generated.contains("pub fn script(&self, method: &'static str, constraint_code: &str) {"));
+    }
+}
@@ -0,0 +1,275 @@
+//! Generates `#[test]` functions that feed a [`rust_guard`](super::rust_guard)
+//! check function the boundary-violating input its `expr` implies —
+//! `amount > 0` violated by `0.0`, `amount <= balance(account)` violated
+//! by one past whatever `balance` returns — and assert it comes back
+//! `Err` with the constraint's own code. This is exactly the by-hand
+//! pattern the bank example's `test_constraints` uses
+//! (`assert_eq!(bank.withdraw(&id, 100.0).unwrap_err(), ...)`), derived
+//! from the spec instead of re-typed per constraint.
+
+use crate::spec::{Comparison, Constraint, ConstraintExpr, RelOp, Severity, SpecDocument, Term};
+
+use super::rust_guard::{plan, Plan};
+
+/// An arbitrary, fixed return value stood in for a call term (e.g.
+/// `balance(account)`) that a boundary violation needs a concrete number
+/// for but the spec doesn't supply one — there's no implementation to
+/// call yet, only its generated check function.
+const CALL_BASELINE: f64 = 100.0;
+
+/// Generates one `#[test]` per [`Error`](Severity::Error)-severity
+/// constraint in `doc` whose `expr` is a single comparison between a bare
+/// identifier and something with a derivable boundary value (a number
+/// literal, or a call — stood in for by [`CALL_BASELINE`]). Calls
+/// [`rust_guard::generate`](super::rust_guard::generate)'s check
+/// functions directly, so this must be emitted alongside it.
+///
+/// Constraints without an `expr`, whose `expr` doesn't parse or compose
+/// more than one comparison, whose comparison isn't between a bare
+/// identifier and a derivable boundary, or whose severity isn't `Error`
+/// (a `Warning`/`Advisory` check never returns `Err`) are skipped, with a
+/// trailing comment explaining why — same best-effort spirit as
+/// [`rust_guard`](super::rust_guard).
+pub fn generate(doc: &SpecDocument) -> String {
+    let mut out = String::new();
+    let mut skipped = Vec::new();
+
+    for c in &doc.constraints {
+        let Some(expr_src) = &c.expr else { continue };
+        let expr = match ConstraintExpr::parse(expr_src) {
+            Ok(expr) => expr,
+            Err(e) => {
+                skipped.push((c.code.clone(), format!("expr failed to parse: {e}")));
+                continue;
+            }
+        };
+
+        if c.severity != Severity::Error {
+            skipped.push((c.code.clone(), "severity isn't Error, so its check never returns Err".to_string()));
+            continue;
+        }
+
+        let comparisons = expr.comparisons();
+        let comparison = match comparisons.as_slice() {
+            [comparison] => *comparison,
+            _ => {
+                skipped.push((c.code.clone(), "expr composes more than one comparison".to_string()));
+                continue;
+            }
+        };
+
+        let Some(violation) = boundary_violation(comparison) else {
+            skipped.push((
+                c.code.clone(),
+                "expr's comparison isn't between a bare identifier and a derivable boundary".to_string(),
+            ));
+            continue;
+        };
+
+        let Some(plan) = plan(&expr) else {
+            skipped.push((
+                c.code.clone(),
+                "expr calls a function with an argument that isn't a bare identifier".to_string(),
+            ));
+            continue;
+        };
+
+        out.push_str(&generate_one(c, &plan, &violation));
+        out.push('\n');
+    }
+
+    if !skipped.is_empty() {
+        out.push_str("// Constraints with no generated negative test (see their `expr` in the spec):\n");
+        for (code, reason) in &skipped {
+            out.push_str(&format!("// - {code}: {reason}\n"));
+        }
+    }
+
+    out
+}
+
+/// One bare identifier pushed past a [`Comparison`]'s boundary.
+struct BoundaryViolation {
+    target: String,
+    value: f64,
+}
+
+/// Finds the bare identifier in `comparison` and the value that violates
+/// it, normalizing away which side of the comparison it's on. The other
+/// side must be a number literal or a call (stood in for by
+/// [`CALL_BASELINE`]); anything else (another bare identifier, or
+/// arithmetic) has no boundary this can derive.
+fn boundary_violation(comparison: &Comparison) -> Option<BoundaryViolation> {
+    match (&comparison.lhs, &comparison.rhs) {
+        (Term::Ident(name), Term::Number(n)) => {
+            Some(BoundaryViolation { target: name.clone(), value: violating_value(comparison.op, *n) })
+        }
+        (Term::Number(n), Term::Ident(name)) => Some(BoundaryViolation {
+            target: name.clone(),
+            value: violating_value(flip(comparison.op), *n),
+        }),
+        (Term::Ident(name), Term::Call(..)) => Some(BoundaryViolation {
+            target: name.clone(),
+            value: violating_value(comparison.op, CALL_BASELINE),
+        }),
+        (Term::Call(..), Term::Ident(name)) => Some(BoundaryViolation {
+            target: name.clone(),
+            value: violating_value(flip(comparison.op), CALL_BASELINE),
+        }),
+        _ => None,
+    }
+}
+
+/// The operator that keeps a comparison's meaning when its two sides
+/// swap places, e.g. `0 < amount` means the same as `amount > 0`.
+fn flip(op: RelOp) -> RelOp {
+    match op {
+        RelOp::Gt => RelOp::Lt,
+        RelOp::Lt => RelOp::Gt,
+        RelOp::Ge => RelOp::Le,
+        RelOp::Le => RelOp::Ge,
+        RelOp::Eq => RelOp::Eq,
+        RelOp::Ne => RelOp::Ne,
+    }
+}
+
+/// The closest value to `boundary` that fails `target op boundary`, e.g.
+/// `amount > 0` (boundary `0`) is violated by `amount = 0`, and
+/// `amount <= balance(account)` (boundary `CALL_BASELINE`) is violated by
+/// one past it.
+fn violating_value(op: RelOp, boundary: f64) -> f64 {
+    match op {
+        RelOp::Gt | RelOp::Lt | RelOp::Ne => boundary,
+        RelOp::Ge => boundary - 1.0,
+        RelOp::Le => boundary + 1.0,
+        RelOp::Eq => boundary + 1.0,
+    }
+}
+
+fn generate_one(c: &Constraint, plan: &Plan, violation: &BoundaryViolation) -> String {
+    let fn_name = format!("check_{}", c.code.to_lowercase().replace('-', "_"));
+    let test_name = format!("{fn_name}_rejects_a_boundary_violating_input");
+
+    let mut args = Vec::new();
+    for name in &plan.bare {
+        let value = if *name == violation.target { violation.value } else { 1.0 };
+        args.push(format!("{value:?}"));
+    }
+    for (_, call_args) in &plan.calls {
+        for name in call_args {
+            if !plan.bare.contains(name) {
+                args.push("()".to_string());
+            }
+        }
+    }
+    for (_, call_args) in &plan.calls {
+        let pattern = call_args.iter().map(|_| "_").collect::<Vec<_>>().join(", ");
+        args.push(format!("|{pattern}| {CALL_BASELINE:?}"));
+    }
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "/// Feeds `{fn_name}` the boundary-violating input `{} = {:?}` derived from `{}`'s expr.\n",
+        violation.target, violation.value, c.code
+    ));
+    out.push_str("#[test]\n");
+    out.push_str(&format!("fn {test_name}() {{\n"));
+    out.push_str(&format!("    let result = {fn_name}({});\n", args.join(", ")));
+    out.push_str("    let err = result.expect_err(\"boundary-violating input should be rejected\");\n");
+    out.push_str(&format!("    assert_eq!(err.code, \"{}\");\n", c.code));
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_a_negative_test_for_a_simple_comparison() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            expr: Some("amount > 0".into()),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc);
+        assert!(generated.contains("fn check_const_001_rejects_a_boundary_violating_input() {"));
+        assert!(generated.contains("let result = check_const_001(0.0);"));
+        assert!(generated.contains("assert_eq!(err.code, \"CONST-001\");"));
+    }
+
+    #[test]
+    fn derives_an_over_balance_withdrawal_from_a_call_comparison() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "amount must not exceed the balance".into(),
+            expr: Some("amount <= balance(account)".into()),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc);
+        assert!(generated.contains("fn check_const_002_rejects_a_boundary_violating_input() {"));
+        assert!(generated.contains("let result = check_const_002(101.0, (), |_| 100.0);"));
+    }
+
+    #[test]
+    fn flips_the_comparison_when_the_literal_comes_first() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            expr: Some("0 < amount".into()),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc);
+        assert!(generated.contains("let result = check_const_001(0.0);"));
+    }
+
+    #[test]
+    fn skips_a_warning_severity_constraint() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-005".into(),
+            text: "amount should stay under the soft cap".into(),
+            expr: Some("amount <= 1000".into()),
+            severity: Severity::Warning,
+            ..Default::default()
+        });
+
+        let generated = generate(&doc);
+        assert!(!generated.contains("fn check_const_005"));
+        assert!(generated.contains("// - CONST-005: severity isn't Error"));
+    }
+
+    #[test]
+    fn skips_a_constraint_without_an_expr() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-003".into(),
+            text: "account must exist".into(),
+            expr: None,
+            ..Default::default()
+        });
+
+        assert_eq!(generate(&doc), "");
+    }
+
+    #[test]
+    fn skips_a_comparison_between_two_bare_identifiers() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-006".into(),
+            text: "withdrawal must not exceed the daily limit".into(),
+            expr: Some("amount <= daily_limit".into()),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc);
+        assert!(generated.contains("// - CONST-006: expr's comparison isn't between a bare identifier and a derivable boundary"));
+    }
+}
@@ -0,0 +1,247 @@
+//! Generates a Rust trait with one stub method per requirement.
+
+use crate::spec::{Requirement, SpecDocument, Status};
+
+use super::{method_name, requirement_table_doc};
+
+/// Generates `pub trait {trait_name}` with one `fn` per requirement in
+/// `doc`, doc-commented with the requirement's id and text, plus a
+/// trailing `supported_versions` method. Every requirement method takes
+/// `&self` and returns `Result<(), ::libspec::error::SpecError>`, so
+/// callers can match on `code/requirement/message` instead of downcasting
+/// a `Box<dyn std::error::Error>`; callers rename the generated stub and
+/// fill in real signatures. A requirement with [`Status::Deprecated`] gets
+/// a `#[deprecated]` attribute on its method, noting its
+/// [`Requirement::replaced_by`](crate::spec::Requirement::replaced_by) if
+/// set. A requirement carrying
+/// [`Requirement::examples`] gets each one rendered as a fenced doc-comment
+/// code block straight after its method's doc line, so `cargo test --doc`
+/// compiles and runs it like any other doctest instead of the example only
+/// living as prose in the spec.
+///
+/// The trait itself is doc-commented with a table of every requirement's
+/// status and priority, so `cargo doc` shows the contract's shape next to
+/// the API instead of only in the spec file.
+pub fn generate(doc: &SpecDocument, trait_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&requirement_table_doc(&doc.requirements));
+    out.push_str(&format!("/// Generated from the spec by `libspec`; one method per requirement.\npub trait {trait_name} {{\n"));
+    for req in &doc.requirements {
+        out.push_str(&format!("    /// {}: {}\n", req.id, req.text));
+        out.push_str(&example_doc(req, "    "));
+        out.push_str(&deprecated_attr(req, "    "));
+        out.push_str(&format!(
+            "    fn {}(&self) -> Result<(), ::libspec::error::SpecError>;\n",
+            method_name(req)
+        ));
+    }
+    out.push_str(SUPPORTED_VERSIONS_METHOD);
+    out.push_str("}\n");
+    out
+}
+
+/// Renders each of `req`'s [`Requirement::examples`] as a fenced
+/// doc-comment code block, indented by `indent`; an empty string if it
+/// has none. Each example is emitted verbatim, one fence per example, so
+/// rustdoc compiles and runs it as an ordinary doctest — the same
+/// guarantee `cargo test --doc` already gives every hand-written example
+/// elsewhere in this crate.
+fn example_doc(req: &Requirement, indent: &str) -> String {
+    let mut out = String::new();
+    for example in &req.examples {
+        out.push_str(&format!("{indent}/// ```\n"));
+        for line in example.lines() {
+            out.push_str(&format!("{indent}/// {line}\n"));
+        }
+        out.push_str(&format!("{indent}/// ```\n"));
+    }
+    out
+}
+
+/// The API versions this implementation understands, e.g. `vec!["1.0.0",
+/// "1.1.0"]`. Pass this and a client's own list to
+/// [`negotiation::negotiate`](::libspec::negotiation::negotiate) to pick
+/// the highest version both sides speak.
+const SUPPORTED_VERSIONS_METHOD: &str =
+    "    fn supported_versions(&self) -> Vec<&'static str>;\n";
+
+/// Renders a `#[deprecated]` attribute line for `req` if its
+/// [`Status`] is [`Status::Deprecated`], indented by `indent`; an empty
+/// string otherwise.
+fn deprecated_attr(req: &crate::spec::Requirement, indent: &str) -> String {
+    if req.status != Status::Deprecated {
+        return String::new();
+    }
+    match &req.replaced_by {
+        Some(replacement) => format!("{indent}#[deprecated(note = \"replaced by {replacement}\")]\n"),
+        None => format!("{indent}#[deprecated]\n"),
+    }
+}
+
+/// Generates `{trait_name}Async`, an `async_trait`-based variant of
+/// [`generate`]'s trait with the same methods made `async fn`, plus a
+/// blanket `impl<T: {trait_name} + Sync> {trait_name}Async for T` so every
+/// existing sync implementation gets an async one for free (each method
+/// just calls through to the sync version; it doesn't suddenly do any
+/// actual async work).
+pub fn generate_async(doc: &SpecDocument, trait_name: &str) -> String {
+    let async_trait_name = format!("{trait_name}Async");
+    let mut out = String::new();
+
+    out.push_str(&requirement_table_doc(&doc.requirements));
+    out.push_str("#[async_trait::async_trait]\n");
+    out.push_str(&format!(
+        "/// Async variant of [`{trait_name}`], generated from the spec by `libspec`.\npub trait {async_trait_name} {{\n"
+    ));
+    for req in &doc.requirements {
+        out.push_str(&format!("    /// {}: {}\n", req.id, req.text));
+        out.push_str(&example_doc(req, "    "));
+        out.push_str(&deprecated_attr(req, "    "));
+        out.push_str(&format!(
+            "    async fn {}(&self) -> Result<(), ::libspec::error::SpecError>;\n",
+            method_name(req)
+        ));
+    }
+    out.push_str(SUPPORTED_VERSIONS_METHOD);
+    out.push_str("}\n\n");
+
+    out.push_str("#[async_trait::async_trait]\n");
+    out.push_str(&format!(
+        "impl<T: {trait_name} + Sync> {async_trait_name} for T {{\n"
+    ));
+    for req in &doc.requirements {
+        let name = method_name(req);
+        out.push_str(&format!(
+            "    async fn {name}(&self) -> Result<(), ::libspec::error::SpecError> {{\n        {trait_name}::{name}(self)\n    }}\n"
+        ));
+    }
+    out.push_str(&format!(
+        "    fn supported_versions(&self) -> Vec<&'static str> {{\n        {trait_name}::supported_versions(self)\n    }}\n"
+    ));
+    out.push_str("}\n");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn generates_one_method_per_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi");
+        assert!(generated.contains("pub trait BankApi {"));
+        assert!(generated.contains("/// REQ-004: balance() returns the current balance"));
+        assert!(generated.contains("fn balance(&self) -> Result<(), ::libspec::error::SpecError>;"));
+        assert!(generated.contains("fn supported_versions(&self) -> Vec<&'static str>;"));
+    }
+
+    #[test]
+    fn renders_a_requirements_examples_as_fenced_doctests() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            examples: vec!["assert_eq!(2 + 2, 4);".into()],
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi");
+        assert!(generated.contains("/// REQ-004: balance() returns the current balance\n    /// ```\n    /// assert_eq!(2 + 2, 4);\n    /// ```\n"));
+    }
+
+    #[test]
+    fn a_requirement_with_no_examples_gets_no_fence() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi");
+        assert!(!generated.contains("```"));
+    }
+
+    #[test]
+    fn trait_doc_comment_includes_a_requirement_table() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi");
+        assert!(generated.contains("/// | Requirement | Status | Priority |"));
+        assert!(generated.contains("/// | REQ-004 |"));
+    }
+
+    #[test]
+    fn generates_async_trait_and_blanket_adapter() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate_async(&doc, "BankApi");
+        assert!(generated.contains("pub trait BankApiAsync {"));
+        assert!(generated.contains("async fn balance(&self) -> Result<(), ::libspec::error::SpecError>;"));
+        assert!(generated.contains("fn supported_versions(&self) -> Vec<&'static str>;"));
+        assert!(generated.contains("impl<T: BankApi + Sync> BankApiAsync for T {"));
+        assert!(generated.contains("BankApi::balance(self)"));
+        assert!(generated.contains("BankApi::supported_versions(self)"));
+    }
+
+    #[test]
+    fn marks_a_deprecated_requirements_method_with_the_attribute() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            status: crate::spec::Status::Deprecated,
+            replaced_by: Some("REQ-009".into()),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi");
+        assert!(generated.contains("#[deprecated(note = \"replaced by REQ-009\")]\n    fn balance"));
+    }
+
+    #[test]
+    fn marks_a_deprecated_requirement_with_no_replacement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            status: crate::spec::Status::Deprecated,
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi");
+        assert!(generated.contains("#[deprecated]\n    fn balance"));
+    }
+
+    #[test]
+    fn does_not_mark_a_non_deprecated_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi");
+        assert!(!generated.contains("#[deprecated"));
+    }
+}
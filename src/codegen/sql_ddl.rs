@@ -0,0 +1,235 @@
+//! Generates `CREATE TABLE` DDL for every [`DataType`] a spec declares
+//! persisted, with each field's [`crate::spec::Field::constraints`] mapped onto a SQL
+//! `CHECK`, so a database keeps the spec's invariants even for writes
+//! that bypass the generated Rust types entirely (a migration script, a
+//! `psql` session, a service written in another language).
+
+use crate::spec::{ConstraintExpr, DataType, RelOp, SpecDocument, Term};
+
+/// Generates one `CREATE TABLE {dt.name} (...)` per [`DataType`] in `doc`:
+/// one column per [`crate::spec::Field`], typed via [`sql_type`], plus a `CHECK`
+/// constraint for each of the field's [`crate::spec::Field::constraints`] whose code
+/// names a [`crate::spec::Constraint`] with a parseable `expr` that doesn't call a
+/// function — a `CHECK` can't run an arbitrary spec-defined function, so
+/// those are skipped with a trailing comment explaining why, the same
+/// best-effort spirit as [`rust_guard`](super::rust_guard).
+pub fn generate(doc: &SpecDocument) -> String {
+    let mut out = String::new();
+    let mut skipped = Vec::new();
+    for dt in &doc.data_types {
+        out.push_str(&table(doc, dt, &mut skipped));
+        out.push('\n');
+    }
+    if !skipped.is_empty() {
+        out.push_str("-- Constraints not checked here (see their `expr` in the spec):\n");
+        for (code, reason) in &skipped {
+            out.push_str(&format!("-- - {code}: {reason}\n"));
+        }
+    }
+    out
+}
+
+/// Generates the same DDL as [`generate`], plus a Diesel `table!` macro
+/// invocation per [`DataType`] so `diesel print-schema`'s output doesn't
+/// drift from what this backend actually created. `sqlx` needs no
+/// equivalent: its macros check a query against the live database schema
+/// at compile time, so the plain DDL from [`generate`] is already
+/// everything it needs.
+pub fn generate_diesel(doc: &SpecDocument) -> String {
+    let mut out = generate(doc);
+    for dt in &doc.data_types {
+        out.push_str(&format!("diesel::table! {{\n    {} ({}) {{\n", dt.name, primary_column(dt)));
+        for field in &dt.fields {
+            out.push_str(&format!("        {} -> {},\n", field.name, diesel_type(&field.ty)));
+        }
+        out.push_str("    }\n}\n\n");
+    }
+    out
+}
+
+/// One `CREATE TABLE` statement for `dt`, collecting any constraint
+/// `generate` can't render as a `CHECK` into `skipped`.
+fn table(doc: &SpecDocument, dt: &DataType, skipped: &mut Vec<(String, String)>) -> String {
+    let mut out = format!("CREATE TABLE {} (\n", dt.name);
+    let mut lines = Vec::new();
+    for field in &dt.fields {
+        let mut line = format!("    {} {}", field.name, sql_type(&field.ty));
+        for code in &field.constraints {
+            match check_clause(doc, code) {
+                Ok(Some(clause)) => line.push_str(&format!(" CONSTRAINT {} CHECK ({clause})", constraint_name(code))),
+                Ok(None) => {}
+                Err(reason) => skipped.push((code.clone(), reason)),
+            }
+        }
+        lines.push(line);
+    }
+    out.push_str(&lines.join(",\n"));
+    out.push_str("\n);\n");
+    out
+}
+
+/// The `CHECK` clause for constraint `code`, if `doc` declares it with a
+/// parseable, call-free `expr`. `Ok(None)` if `code` isn't declared or has
+/// no `expr` (nothing to check at the database level). `Err` with a
+/// reason if `code` is declared but its `expr` can't become a `CHECK`.
+fn check_clause(doc: &SpecDocument, code: &str) -> Result<Option<String>, String> {
+    let Some(constraint) = doc.constraint(code) else { return Ok(None) };
+    let Some(expr_src) = &constraint.expr else { return Ok(None) };
+    let expr = ConstraintExpr::parse(expr_src).map_err(|e| format!("expr failed to parse: {e}"))?;
+    sql_expr(&expr).map(Some).ok_or_else(|| {
+        "expr calls a function, which a CHECK constraint can't evaluate".to_string()
+    })
+}
+
+/// Renders a [`ConstraintExpr`] as SQL, translating the DSL's Rust-like
+/// operators (`==`, `!=`, `&&`, `||`) to SQL's (`=`, `<>`, `AND`, `OR`).
+/// `None` if any [`Term::Call`] appears anywhere in it.
+fn sql_expr(expr: &ConstraintExpr) -> Option<String> {
+    match expr {
+        ConstraintExpr::Compare(c) => {
+            Some(format!("{} {} {}", sql_term(&c.lhs)?, sql_rel_op(c.op), sql_term(&c.rhs)?))
+        }
+        ConstraintExpr::And(a, b) => Some(format!("({}) AND ({})", sql_expr(a)?, sql_expr(b)?)),
+        ConstraintExpr::Or(a, b) => Some(format!("({}) OR ({})", sql_expr(a)?, sql_expr(b)?)),
+    }
+}
+
+fn sql_term(term: &Term) -> Option<String> {
+    match term {
+        Term::Number(n) => Some(n.to_string()),
+        Term::Ident(name) => Some(name.clone()),
+        Term::Call(..) => None,
+        Term::Add(a, b) => Some(format!("({} + {})", sql_term(a)?, sql_term(b)?)),
+        Term::Sub(a, b) => Some(format!("({} - {})", sql_term(a)?, sql_term(b)?)),
+        Term::Mul(a, b) => Some(format!("({} * {})", sql_term(a)?, sql_term(b)?)),
+        Term::Div(a, b) => Some(format!("({} / {})", sql_term(a)?, sql_term(b)?)),
+    }
+}
+
+fn sql_rel_op(op: RelOp) -> &'static str {
+    match op {
+        RelOp::Gt => ">",
+        RelOp::Lt => "<",
+        RelOp::Ge => ">=",
+        RelOp::Le => "<=",
+        RelOp::Eq => "=",
+        RelOp::Ne => "<>",
+    }
+}
+
+/// A SQL constraint name for `code`, e.g. `CONST-002` -> `const_002`.
+fn constraint_name(code: &str) -> String {
+    code.to_ascii_lowercase().replace('-', "_")
+}
+
+/// Maps a Rust-ish field type (as declared on a [`DataType`] field) to the
+/// closest SQL (PostgreSQL-flavored) column type, falling back to `TEXT`
+/// for anything it doesn't recognize — the same fallback [`super::proto::proto_type`]
+/// uses for Protobuf.
+fn sql_type(ty: &str) -> &str {
+    match ty {
+        "f64" | "f32" => "DOUBLE PRECISION",
+        "i32" | "u32" => "INTEGER",
+        "i64" | "u64" | "usize" | "isize" => "BIGINT",
+        "bool" => "BOOLEAN",
+        "String" | "str" | "&str" => "TEXT",
+        _ => "TEXT",
+    }
+}
+
+/// The Diesel column type matching [`sql_type`]'s choice for `ty`.
+fn diesel_type(ty: &str) -> &str {
+    match ty {
+        "f64" | "f32" => "Double",
+        "i32" | "u32" => "Integer",
+        "i64" | "u64" | "usize" | "isize" => "BigInt",
+        "bool" => "Bool",
+        _ => "Text",
+    }
+}
+
+/// The column [`generate_diesel`] names as `dt`'s primary key in its
+/// `table!` macro: the first field named `id`, or its first field if none
+/// is, since every [`DataType`] here has at least one column.
+fn primary_column(dt: &DataType) -> &str {
+    dt.fields
+        .iter()
+        .find(|f| f.name == "id")
+        .or_else(|| dt.fields.first())
+        .map(|f| f.name.as_str())
+        .unwrap_or("id")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, Field, Severity};
+
+    fn doc_with_account() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "balance must be non-negative".into(),
+            expr: Some("balance >= 0".into()),
+            severity: Severity::Error,
+            http_status: None,
+        });
+        doc.data_types.push(DataType {
+            name: "Account".into(),
+            fields: vec![
+                Field { name: "id".into(), ty: "String".into(), constraints: vec![] },
+                Field { name: "balance".into(), ty: "f64".into(), constraints: vec!["CONST-001".into()] },
+            ],
+        });
+        doc
+    }
+
+    #[test]
+    fn generates_one_table_per_data_type_with_mapped_column_types() {
+        let generated = generate(&doc_with_account());
+        assert!(generated.contains("CREATE TABLE Account ("));
+        assert!(generated.contains("id TEXT"));
+        assert!(generated.contains("balance DOUBLE PRECISION"));
+    }
+
+    #[test]
+    fn maps_a_constraint_expr_to_a_check_clause() {
+        let generated = generate(&doc_with_account());
+        assert!(generated.contains("CONSTRAINT const_001 CHECK (balance >= 0)"));
+    }
+
+    #[test]
+    fn skips_a_constraint_whose_expr_calls_a_function() {
+        let mut doc = doc_with_account();
+        doc.constraints[0].expr = Some("balance <= limit(id)".into());
+
+        let generated = generate(&doc);
+        assert!(!generated.contains("CONSTRAINT"));
+        assert!(generated.contains("-- - CONST-001: expr calls a function, which a CHECK constraint can't evaluate"));
+    }
+
+    #[test]
+    fn field_referencing_an_undeclared_constraint_is_left_unchecked() {
+        let mut doc = SpecDocument::new();
+        doc.data_types.push(DataType {
+            name: "Account".into(),
+            fields: vec![Field {
+                name: "balance".into(),
+                ty: "f64".into(),
+                constraints: vec!["CONST-999".into()],
+            }],
+        });
+
+        let generated = generate(&doc);
+        assert!(generated.contains("balance DOUBLE PRECISION"));
+        assert!(!generated.contains("CHECK"));
+    }
+
+    #[test]
+    fn diesel_variant_adds_a_table_macro_after_the_ddl() {
+        let generated = generate_diesel(&doc_with_account());
+        assert!(generated.contains("CREATE TABLE Account ("));
+        assert!(generated.contains("diesel::table! {\n    Account (id) {"));
+        assert!(generated.contains("balance -> Double,"));
+    }
+}
@@ -0,0 +1,235 @@
+//! Generates a `proptest` stateful property test from a
+//! [`StateMachine`](crate::spec::StateMachine): a `Model` that tracks the
+//! spec's abstract state across a random operation sequence, and a
+//! `proptest!` block that checks a real implementation agrees with it —
+//! the model errors exactly when the model says so, same as
+//! [`rust_guard`](super::rust_guard) checks a single call but over an
+//! arbitrary sequence instead of one call in isolation.
+
+use crate::spec::SpecDocument;
+
+use super::capitalize;
+
+/// Generates `pub mod {name}_model` for the [`StateMachine`] named `name`
+/// in `doc`: a `Model` struct with one field per state variable, an `Op`
+/// enum with one variant per transition, `Model::apply` replaying an `Op`
+/// against the model (returning `Err(code)` when a transition's `guard`
+/// fails), an `arbitrary_op` proptest strategy, and a `proptest!` block
+/// that runs a random operation sequence through both the model and a
+/// `todo!()` call into the real implementation, asserting they agree on
+/// every operation. Callers fill in the `todo!()` with however their
+/// implementation performs the matching operation.
+///
+/// Returns an empty string if `doc` has no state machine named `name`.
+pub fn generate(doc: &SpecDocument, name: &str) -> String {
+    let Some(sm) = doc.state_machine(name) else {
+        return String::new();
+    };
+
+    let mod_name = format!("{}_model", name.to_lowercase());
+    let mut out = String::new();
+    out.push_str(&format!("pub mod {mod_name} {{\n"));
+
+    // Model struct.
+    out.push_str(&format!(
+        "    /// Model state mirroring the spec's `{name}` state machine.\n"
+    ));
+    out.push_str("    #[derive(Debug, Clone, PartialEq)]\n    pub struct Model {\n");
+    for field in sm.state.keys() {
+        out.push_str(&format!("        pub {field}: f64,\n"));
+    }
+    out.push_str("    }\n\n");
+
+    out.push_str("    impl Model {\n        pub fn new() -> Self {\n            Self {\n");
+    for (field, initial) in &sm.state {
+        out.push_str(&format!("                {field}: {initial:?},\n"));
+    }
+    out.push_str("            }\n        }\n    }\n\n");
+
+    // Op enum.
+    out.push_str("    /// One operation from the spec's transitions.\n");
+    out.push_str("    #[derive(Debug, Clone)]\n    pub enum Op {\n");
+    for t in &sm.transitions {
+        let variant = capitalize(&t.name);
+        if t.params.is_empty() {
+            out.push_str(&format!("        {variant},\n"));
+        } else {
+            let fields = t
+                .params
+                .iter()
+                .map(|p| format!("{p}: f64"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            out.push_str(&format!("        {variant} {{ {fields} }},\n"));
+        }
+    }
+    out.push_str("    }\n\n");
+
+    // Model::apply.
+    out.push_str("    impl Model {\n");
+    out.push_str(&format!(
+        "        /// Applies `op` to the model, returning the constraint code a real\n        /// `{name}` implementation should report when it rejects the same\n        /// operation.\n"
+    ));
+    out.push_str("        pub fn apply(&mut self, op: &Op) -> Result<(), &'static str> {\n");
+    out.push_str("            match op {\n");
+    for t in &sm.transitions {
+        let variant = capitalize(&t.name);
+        let pattern = if t.params.is_empty() {
+            format!("Op::{variant}")
+        } else {
+            format!("Op::{variant} {{ {} }}", t.params.join(", "))
+        };
+        out.push_str(&format!("                {pattern} => {{\n"));
+        for field in sm.state.keys() {
+            out.push_str(&format!("                    let {field} = self.{field};\n"));
+        }
+        if let Some(guard) = &t.guard {
+            let code = t
+                .violates
+                .clone()
+                .unwrap_or_else(|| format!("{}-guard", t.name));
+            out.push_str(&format!("                    if !({guard}) {{\n"));
+            out.push_str(&format!("                        return Err(\"{code}\");\n"));
+            out.push_str("                    }\n");
+        }
+        for field in sm.state.keys() {
+            if let Some(effect) = t.effect.get(field) {
+                out.push_str(&format!("                    self.{field} = {effect};\n"));
+            }
+        }
+        out.push_str("                    Ok(())\n                }\n");
+    }
+    out.push_str("            }\n        }\n    }\n\n");
+
+    // arbitrary_op strategy.
+    out.push_str(
+        "    /// A `proptest` strategy generating an arbitrary `Op`.\n    pub fn arbitrary_op() -> impl proptest::strategy::Strategy<Value = Op> {\n        proptest::prelude::prop_oneof![\n",
+    );
+    for t in &sm.transitions {
+        let variant = capitalize(&t.name);
+        if t.params.is_empty() {
+            out.push_str(&format!("            proptest::prelude::Just(Op::{variant}),\n"));
+        } else {
+            let binders = t.params.join(", ");
+            let fields = binders.clone();
+            out.push_str(&format!(
+                "            ({})\n                .prop_map(|({binders})| Op::{variant} {{ {fields} }}),\n",
+                t.params.iter().map(|_| "-1e6f64..1e6".to_string()).collect::<Vec<_>>().join(", ")
+            ));
+        }
+    }
+    out.push_str("        ]\n    }\n\n");
+
+    // proptest! block.
+    out.push_str("    proptest::proptest! {\n        #[test]\n");
+    out.push_str(&format!(
+        "        fn {}_model_agrees_with_implementation(ops in proptest::collection::vec(arbitrary_op(), 1..50)) {{\n",
+        name.to_lowercase()
+    ));
+    out.push_str("            let mut model = Model::new();\n");
+    out.push_str("            for op in &ops {\n");
+    out.push_str("                let expected = model.apply(op);\n");
+    out.push_str(
+        "                // TODO: call the real implementation for `op` and map its error to a constraint code.\n",
+    );
+    out.push_str("                let actual: Result<(), &'static str> = todo!();\n");
+    out.push_str("                proptest::prop_assert_eq!(expected.is_ok(), actual.is_ok());\n");
+    out.push_str("            }\n        }\n    }\n");
+
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{StateMachine, Transition};
+
+    fn sample_doc() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.state_machines.push(StateMachine {
+            name: "Account".into(),
+            state: [("balance".to_string(), 0.0)].into(),
+            transitions: vec![
+                Transition {
+                    name: "deposit".into(),
+                    params: vec!["amount".into()],
+                    guard: None,
+                    violates: None,
+                    effect: [("balance".to_string(), "balance + amount".to_string())].into(),
+                },
+                Transition {
+                    name: "withdraw".into(),
+                    params: vec!["amount".into()],
+                    guard: Some("amount <= balance".into()),
+                    violates: Some("CONST-002".into()),
+                    effect: [("balance".to_string(), "balance - amount".to_string())].into(),
+                },
+            ],
+        });
+        doc
+    }
+
+    #[test]
+    fn generates_a_model_struct_with_one_field_per_state_variable() {
+        let generated = generate(&sample_doc(), "Account");
+        assert!(generated.contains("pub mod account_model {"));
+        assert!(generated.contains("pub struct Model {"));
+        assert!(generated.contains("pub balance: f64,"));
+        assert!(generated.contains("balance: 0.0,"));
+    }
+
+    #[test]
+    fn generates_an_op_variant_per_transition_with_its_params() {
+        let generated = generate(&sample_doc(), "Account");
+        assert!(generated.contains("Deposit { amount: f64 },"));
+        assert!(generated.contains("Withdraw { amount: f64 },"));
+    }
+
+    #[test]
+    fn generates_a_guard_check_that_returns_the_violated_constraint_code() {
+        let generated = generate(&sample_doc(), "Account");
+        assert!(generated.contains("if !(amount <= balance) {"));
+        assert!(generated.contains("return Err(\"CONST-002\");"));
+    }
+
+    #[test]
+    fn generates_the_effect_assignment() {
+        let generated = generate(&sample_doc(), "Account");
+        assert!(generated.contains("self.balance = balance + amount;"));
+        assert!(generated.contains("self.balance = balance - amount;"));
+    }
+
+    #[test]
+    fn generates_an_arbitrary_op_strategy_and_stateful_proptest_block() {
+        let generated = generate(&sample_doc(), "Account");
+        assert!(generated.contains("pub fn arbitrary_op() -> impl proptest::strategy::Strategy<Value = Op> {"));
+        assert!(generated.contains("proptest::proptest! {"));
+        assert!(generated.contains("fn account_model_agrees_with_implementation(ops in proptest::collection::vec(arbitrary_op(), 1..50)) {"));
+    }
+
+    #[test]
+    fn falls_back_to_a_guard_named_code_when_violates_is_unset() {
+        let mut doc = SpecDocument::new();
+        doc.state_machines.push(StateMachine {
+            name: "Account".into(),
+            state: [("balance".to_string(), 0.0)].into(),
+            transitions: vec![Transition {
+                name: "withdraw".into(),
+                params: vec!["amount".into()],
+                guard: Some("amount <= balance".into()),
+                violates: None,
+                effect: std::collections::BTreeMap::new(),
+            }],
+        });
+
+        let generated = generate(&doc, "Account");
+        assert!(generated.contains("return Err(\"withdraw-guard\");"));
+    }
+
+    #[test]
+    fn returns_empty_string_for_an_unknown_state_machine() {
+        let doc = SpecDocument::new();
+        assert_eq!(generate(&doc, "Account"), "");
+    }
+}
@@ -0,0 +1,46 @@
+//! User-pluggable codegen: render the spec model through a caller-supplied
+//! [Tera](https://keats.github.io/tera/) template, for artifacts libspec
+//! won't ship a native backend for (internal DSLs, SQL DDL, config files).
+//! Unlike the other backends in this module, the output shape is entirely
+//! up to the template — this one just supplies the rendering context.
+
+use crate::spec::SpecDocument;
+
+/// Renders `template` (Tera syntax) with `doc` serialized as its context.
+/// The context keys are [`SpecDocument`]'s serialized field names —
+/// `requirement`, `constraint`, `type`, `template`, `glossary` — not the
+/// plural Rust field names, e.g.
+/// `{% for r in requirement %}{{ r.id }}: {{ r.text }}{% endfor %}`.
+pub fn render(doc: &SpecDocument, template: &str) -> Result<String, tera::Error> {
+    let context = tera::Context::from_serialize(doc)?;
+    tera::Tera::one_off(template, &context, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn renders_requirement_fields_into_the_template() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let rendered = render(
+            &doc,
+            "{% for r in requirement %}{{ r.id }}: {{ r.text }}\n{% endfor %}",
+        )
+        .unwrap();
+        assert_eq!(rendered, "REQ-004: balance() returns the current balance\n");
+    }
+
+    #[test]
+    fn reports_a_template_syntax_error() {
+        let doc = SpecDocument::new();
+        assert!(render(&doc, "{% for broken %}").is_err());
+    }
+}
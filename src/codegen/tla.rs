@@ -0,0 +1,353 @@
+//! Exports a spec's [`StateMachine`] as a TLA+ module plus a TLC
+//! model-checker config, for teams doing design-level verification
+//! against the same source the generated Rust model
+//! ([`super::state_machine`]) comes from, instead of a hand-maintained
+//! TLA+ spec drifting from it.
+//!
+//! `guard`/`effect` expressions are rendered through the same
+//! [`ConstraintExpr`] grammar [`super::rust_guard`] uses: arithmetic
+//! (`+`/`-`/`*`/`/`) reads the same in TLA+ as it does in the spec's
+//! `expr` DSL, so only relational operators (`==` -> `=`, `!=` -> `#`)
+//! and `&&`/`||` (-> `/\`/`\/`) need translating. A constraint whose
+//! `expr` mentions anything outside the state machine's own state fields
+//! (a transition parameter like `amount`, say) can't be stated as a
+//! state invariant, so it's skipped with a trailing comment rather than
+//! emitted as nonsense TLA+ — the same best-effort spirit as
+//! [`super::rust_negative_tests`].
+
+use std::collections::BTreeSet;
+
+use crate::spec::{Comparison, ConstraintExpr, RelOp, SpecDocument, StateMachine, Transition};
+
+use super::capitalize;
+
+/// Generates a `---- MODULE {name} ----` TLA+ module for the
+/// [`StateMachine`] named `name` in `doc`: one `VARIABLES` declaration,
+/// an `Init` predicate from the state's initial values, one action per
+/// transition (its `guard` as a conjunct, its `effect` as a primed
+/// assignment per field it updates, every other field left `UNCHANGED`),
+/// a `Next` existentially quantifying each transition's params over a
+/// same-named `CONSTANT`, a `Spec` formula, and one `Inv_{code}` per
+/// constraint statable purely in terms of this machine's state.
+///
+/// Returns an empty string if `doc` has no state machine named `name`.
+pub fn generate(doc: &SpecDocument, name: &str) -> String {
+    let Some(sm) = doc.state_machine(name) else {
+        return String::new();
+    };
+
+    let field_list: Vec<&str> = sm.state.keys().map(String::as_str).collect();
+
+    let mut out = String::new();
+    out.push_str(&format!("---- MODULE {name} ----\nEXTENDS Integers\n\n"));
+    out.push_str(&format!("VARIABLES {}\n\n", field_list.join(", ")));
+
+    out.push_str("Init ==\n");
+    out.push_str(
+        &sm.state
+            .iter()
+            .map(|(field, initial)| format!("    /\\ {field} = {}", tla_number(*initial)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    out.push_str("\n\n");
+
+    for t in &sm.transitions {
+        out.push_str(&transition_action(t, sm));
+        out.push('\n');
+    }
+
+    out.push_str("Next ==\n");
+    out.push_str(
+        &sm.transitions
+            .iter()
+            .map(next_disjunct)
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    out.push_str("\n\n");
+
+    out.push_str(&format!("Spec == Init /\\ [][Next]_<<{}>>\n", field_list.join(", ")));
+
+    let fields: BTreeSet<String> = sm.state.keys().cloned().collect();
+    let (invariants, skipped) = state_invariants(doc, &fields);
+    if !invariants.is_empty() {
+        out.push('\n');
+        for (code, expr) in &invariants {
+            out.push_str(&format!("Inv_{} == {expr}\n", code.replace('-', "_")));
+        }
+    }
+    if !skipped.is_empty() {
+        out.push_str("\n\\* Constraints not stated as invariants (their expr mentions more than this machine's own state):\n");
+        for code in &skipped {
+            out.push_str(&format!("\\* - {code}\n"));
+        }
+    }
+
+    out.push_str("\n====\n");
+    out
+}
+
+/// Generates the TLC config naming `Spec` as the specification, declaring
+/// one `CONSTANTS` entry per transition parameter (bound to a small
+/// literal set, `{0, 1, 2, 3}`, so TLC has a finite domain to model-check
+/// over), and an `INVARIANT` line per [`generate`]'s `Inv_{code}`
+/// definitions.
+///
+/// Returns an empty string if `doc` has no state machine named `name`.
+pub fn generate_cfg(doc: &SpecDocument, name: &str) -> String {
+    let Some(sm) = doc.state_machine(name) else {
+        return String::new();
+    };
+
+    let mut out = String::from("SPECIFICATION Spec\n");
+
+    let mut params = BTreeSet::new();
+    for t in &sm.transitions {
+        params.extend(t.params.iter().cloned());
+    }
+    if !params.is_empty() {
+        out.push_str("\nCONSTANTS\n");
+        for p in &params {
+            out.push_str(&format!("    {} = {{0, 1, 2, 3}}\n", capitalize(p)));
+        }
+    }
+
+    let fields: BTreeSet<String> = sm.state.keys().cloned().collect();
+    let (invariants, _) = state_invariants(doc, &fields);
+    for (code, _) in &invariants {
+        out.push_str(&format!("\nINVARIANT Inv_{}\n", code.replace('-', "_")));
+    }
+
+    out
+}
+
+/// Renders transition `t`'s action definition: its `guard` (if any) and
+/// one primed assignment per field in its `effect`, plus an `UNCHANGED`
+/// conjunct for every field of `sm`'s state `t` doesn't assign.
+fn transition_action(t: &Transition, sm: &StateMachine) -> String {
+    let action = capitalize(&t.name);
+    let header = if t.params.is_empty() {
+        format!("{action} ==\n")
+    } else {
+        format!("{action}({}) ==\n", t.params.join(", "))
+    };
+
+    let mut conjuncts = Vec::new();
+    if let Some(guard) = &t.guard {
+        conjuncts.push(match ConstraintExpr::parse(guard) {
+            Ok(expr) => tla_expr(&expr),
+            Err(_) => guard.clone(),
+        });
+    }
+    for field in sm.state.keys() {
+        if let Some(effect) = t.effect.get(field) {
+            conjuncts.push(format!("{field}' = {effect}"));
+        }
+    }
+    let unchanged: Vec<&str> = sm
+        .state
+        .keys()
+        .map(String::as_str)
+        .filter(|f| !t.effect.contains_key(*f))
+        .collect();
+    if !unchanged.is_empty() {
+        conjuncts.push(format!("UNCHANGED <<{}>>", unchanged.join(", ")));
+    }
+
+    let body = conjuncts.iter().map(|c| format!("    /\\ {c}")).collect::<Vec<_>>().join("\n");
+    format!("{header}{body}\n")
+}
+
+/// `t`'s disjunct in `Next`: `\/ Action` with no params, or
+/// `\/ \E p1 \in P1, p2 \in P2 : Action(p1, p2)` with some, the domain
+/// for each param named after its own [`capitalize`]d identifier (e.g.
+/// `amount` -> `Amount`), matching the `CONSTANTS` [`generate_cfg`]
+/// declares for it.
+fn next_disjunct(t: &Transition) -> String {
+    let action = capitalize(&t.name);
+    if t.params.is_empty() {
+        return format!("    \\/ {action}");
+    }
+    let binders = t
+        .params
+        .iter()
+        .map(|p| format!("{p} \\in {}", capitalize(p)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("    \\/ \\E {binders} : {action}({})", t.params.join(", "))
+}
+
+/// Constraints in `doc` whose `expr` mentions only identifiers in
+/// `fields`, paired with their TLA+-rendered expression — and, separately,
+/// the codes of constraints that couldn't be (an unparseable `expr`, or
+/// one that mentions something outside `fields`, e.g. a transition
+/// parameter).
+fn state_invariants(doc: &SpecDocument, fields: &BTreeSet<String>) -> (Vec<(String, String)>, Vec<String>) {
+    let mut invariants = Vec::new();
+    let mut skipped = Vec::new();
+    for c in &doc.constraints {
+        let Some(expr_src) = &c.expr else { continue };
+        match ConstraintExpr::parse(expr_src) {
+            Ok(expr) if expr.idents().iter().all(|id| fields.contains(*id)) => {
+                invariants.push((c.code.clone(), tla_expr(&expr)));
+            }
+            _ => skipped.push(c.code.clone()),
+        }
+    }
+    (invariants, skipped)
+}
+
+fn tla_expr(e: &ConstraintExpr) -> String {
+    match e {
+        ConstraintExpr::Compare(c) => tla_comparison(c),
+        ConstraintExpr::And(a, b) => format!("({}) /\\ ({})", tla_expr(a), tla_expr(b)),
+        ConstraintExpr::Or(a, b) => format!("({}) \\/ ({})", tla_expr(a), tla_expr(b)),
+    }
+}
+
+fn tla_comparison(c: &Comparison) -> String {
+    format!("{} {} {}", c.lhs, tla_relop(c.op), c.rhs)
+}
+
+/// Renders a state field's initial `f64` value as a TLA+ number. `EXTENDS
+/// Integers` gives TLC no decimal literals, so a whole number is rendered
+/// without one (`0.0` -> `0`); a genuinely fractional initial value is
+/// rendered as-is, which TLC will reject, since this module has no sound
+/// way to model a non-integer quantity and emitting the literal spec value
+/// is more honest than silently rounding it.
+fn tla_number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        value.to_string()
+    }
+}
+
+fn tla_relop(op: RelOp) -> &'static str {
+    match op {
+        RelOp::Gt => ">",
+        RelOp::Lt => "<",
+        RelOp::Ge => ">=",
+        RelOp::Le => "<=",
+        RelOp::Eq => "=",
+        RelOp::Ne => "#",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, StateMachine, Transition};
+
+    fn sample_doc() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.state_machines.push(StateMachine {
+            name: "Account".into(),
+            state: [("balance".to_string(), 0.0)].into(),
+            transitions: vec![
+                Transition {
+                    name: "deposit".into(),
+                    params: vec!["amount".into()],
+                    guard: None,
+                    violates: None,
+                    effect: [("balance".to_string(), "balance + amount".to_string())].into(),
+                },
+                Transition {
+                    name: "withdraw".into(),
+                    params: vec!["amount".into()],
+                    guard: Some("amount <= balance".into()),
+                    violates: Some("CONST-002".into()),
+                    effect: [("balance".to_string(), "balance - amount".to_string())].into(),
+                },
+            ],
+        });
+        doc.constraints.push(Constraint {
+            code: "CONST-003".into(),
+            text: "balance never goes negative".into(),
+            expr: Some("balance >= 0".into()),
+            ..Default::default()
+        });
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "amount must not exceed the balance".into(),
+            expr: Some("amount <= balance".into()),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn declares_one_variable_per_state_field_and_its_initial_value() {
+        let generated = generate(&sample_doc(), "Account");
+        assert!(generated.contains("---- MODULE Account ----"));
+        assert!(generated.contains("VARIABLES balance"));
+        assert!(generated.contains("Init ==\n    /\\ balance = 0"));
+    }
+
+    #[test]
+    fn renders_a_guarded_action_with_its_effect_and_unchanged_fields() {
+        let generated = generate(&sample_doc(), "Account");
+        assert!(generated.contains("Withdraw(amount) ==\n    /\\ amount <= balance\n    /\\ balance' = balance - amount\n"));
+    }
+
+    #[test]
+    fn renders_an_unguarded_action_without_an_unchanged_conjunct() {
+        let generated = generate(&sample_doc(), "Account");
+        assert!(generated.contains("Deposit(amount) ==\n    /\\ balance' = balance + amount\n"));
+        assert!(!generated.contains("Deposit(amount) ==\n    /\\ balance' = balance + amount\n    /\\ UNCHANGED"));
+    }
+
+    #[test]
+    fn next_quantifies_each_transitions_params_over_its_own_constant() {
+        let generated = generate(&sample_doc(), "Account");
+        assert!(generated.contains("\\/ \\E amount \\in Amount : Deposit(amount)"));
+        assert!(generated.contains("\\/ \\E amount \\in Amount : Withdraw(amount)"));
+    }
+
+    #[test]
+    fn spec_formula_closes_over_every_state_variable() {
+        let generated = generate(&sample_doc(), "Account");
+        assert!(generated.contains("Spec == Init /\\ [][Next]_<<balance>>"));
+    }
+
+    #[test]
+    fn states_a_constraint_mentioning_only_state_fields_as_an_invariant() {
+        let generated = generate(&sample_doc(), "Account");
+        assert!(generated.contains("Inv_CONST_003 == balance >= 0"));
+    }
+
+    #[test]
+    fn skips_a_constraint_mentioning_a_transition_parameter() {
+        let generated = generate(&sample_doc(), "Account");
+        assert!(!generated.contains("Inv_CONST_002"));
+        assert!(generated.contains("\\* - CONST-002"));
+    }
+
+    #[test]
+    fn renders_a_whole_initial_value_without_a_decimal_point() {
+        assert_eq!(tla_number(0.0), "0");
+        assert_eq!(tla_number(-3.0), "-3");
+    }
+
+    #[test]
+    fn renders_a_fractional_initial_value_as_is() {
+        assert_eq!(tla_number(1.5), "1.5");
+    }
+
+    #[test]
+    fn returns_empty_string_for_an_unknown_state_machine() {
+        let doc = SpecDocument::new();
+        assert_eq!(generate(&doc, "Account"), "");
+        assert_eq!(generate_cfg(&doc, "Account"), "");
+    }
+
+    #[test]
+    fn cfg_declares_a_constant_set_per_param_and_an_invariant_per_stated_constraint() {
+        let cfg = generate_cfg(&sample_doc(), "Account");
+        assert!(cfg.contains("SPECIFICATION Spec"));
+        assert!(cfg.contains("Amount = {0, 1, 2, 3}"));
+        assert!(cfg.contains("INVARIANT Inv_CONST_003"));
+        assert!(!cfg.contains("Inv_CONST_002"));
+    }
+}
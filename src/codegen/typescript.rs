@@ -0,0 +1,74 @@
+//! Generates a TypeScript `.d.ts` backend: an interface with one method per
+//! requirement, and a discriminated-union error type with one member per
+//! constraint, so a web client sees the same operations and constraint
+//! codes as the Rust side.
+
+use crate::spec::SpecDocument;
+
+use super::method_name;
+
+/// Generates a `.d.ts`-style module: `export interface {interface_name}`
+/// with one method per requirement in `doc`, doc-commented with the
+/// requirement's id and text, followed by `export type {error_name}`, a
+/// discriminated union with one `{ code: "CONST-..."; message: string }`
+/// member per constraint.
+pub fn generate(doc: &SpecDocument, interface_name: &str, error_name: &str) -> String {
+    let mut out = String::new();
+
+    out.push_str(&format!("export interface {interface_name} {{\n"));
+    for req in &doc.requirements {
+        out.push_str(&format!("  /** {}: {} */\n", req.id, req.text));
+        out.push_str(&format!("  {}(): void;\n", method_name(req)));
+    }
+    out.push_str("}\n\n");
+
+    if doc.constraints.is_empty() {
+        out.push_str(&format!("export type {error_name} = never;\n"));
+        return out;
+    }
+
+    out.push_str(&format!("export type {error_name} =\n"));
+    let members: Vec<String> = doc
+        .constraints
+        .iter()
+        .map(|c| format!("  | {{ code: \"{}\"; message: string }}", c.code))
+        .collect();
+    out.push_str(&members.join("\n"));
+    out.push_str(";\n");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, Requirement};
+
+    #[test]
+    fn generates_interface_method_per_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankAPI", "BankError");
+        assert!(generated.contains("export interface BankAPI {"));
+        assert!(generated.contains("/** REQ-004: balance() returns the current balance */"));
+        assert!(generated.contains("balance(): void;"));
+    }
+
+    #[test]
+    fn generates_discriminated_union_member_per_constraint() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-002".into(),
+            text: "insufficient funds".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankAPI", "BankError");
+        assert!(generated.contains("export type BankError =\n  | { code: \"CONST-002\"; message: string };"));
+    }
+}
@@ -0,0 +1,86 @@
+//! Generates a `wasm-bindgen` wrapper around a spec'd Rust implementation,
+//! so browser demos of a spec are turnkey: one JS-friendly method per
+//! requirement, with constraint errors carried through as structured JS
+//! errors instead of opaque strings.
+
+use crate::spec::SpecDocument;
+
+use super::method_name;
+
+/// Generates `#[wasm_bindgen] pub struct {wasm_name}(pub(crate) {inner_expr_ty})`
+/// wrapping a value of `inner_expr_ty` that implements `trait_name`, with one
+/// `#[wasm_bindgen]` method per requirement. Each method returns
+/// `Result<(), JsValue>`; an `Err` from the wrapped implementation is
+/// rendered via `Display` as `"{code}: {message}"` (the same format
+/// [`rust_error`](super::rust_error) produces) and re-thrown as a JS object
+/// `{ code, message }` via `js_sys::Object`, so callers in JS get structured
+/// fields rather than a parsed string.
+pub fn generate(doc: &SpecDocument, trait_name: &str, wasm_name: &str, inner_expr_ty: &str) -> String {
+    let mut out = String::new();
+    out.push_str("use wasm_bindgen::prelude::*;\n\n");
+
+    out.push_str(&format!(
+        "/// `wasm-bindgen` wrapper around a [`{trait_name}`] implementation, generated from the spec by `libspec`.\n#[wasm_bindgen]\npub struct {wasm_name}({inner_expr_ty});\n\n"
+    ));
+
+    out.push_str("#[wasm_bindgen]\n");
+    out.push_str(&format!("impl {wasm_name} {{\n"));
+    for req in &doc.requirements {
+        let name = method_name(req);
+        out.push_str(&format!("    /// {}: {}\n", req.id, req.text));
+        out.push_str("    #[wasm_bindgen]\n");
+        out.push_str(&format!(
+            "    pub fn {name}(&self) -> Result<(), JsValue> {{\n        {trait_name}::{name}(&self.0).map_err(constraint_error_to_js)\n    }}\n"
+        ));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str("/// Renders a constraint error (formatted `\"{code}: {message}\"` by the\n/// generated `Display` impl) as a JS object `{ code, message }`.\n");
+    out.push_str("fn constraint_error_to_js(err: impl std::error::Error) -> JsValue {\n");
+    out.push_str("    let rendered = err.to_string();\n");
+    out.push_str(
+        "    let (code, message) = rendered.split_once(\": \").unwrap_or((\"\", rendered.as_str()));\n",
+    );
+    out.push_str("    let obj = js_sys::Object::new();\n");
+    out.push_str(
+        "    js_sys::Reflect::set(&obj, &JsValue::from_str(\"code\"), &JsValue::from_str(code)).unwrap();\n",
+    );
+    out.push_str(
+        "    js_sys::Reflect::set(&obj, &JsValue::from_str(\"message\"), &JsValue::from_str(message)).unwrap();\n",
+    );
+    out.push_str("    obj.into()\n");
+    out.push_str("}\n");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn generates_one_wrapper_method_per_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let generated = generate(&doc, "BankApi", "WasmBank", "Bank");
+        assert!(generated.contains("#[wasm_bindgen]\npub struct WasmBank(Bank);"));
+        assert!(generated.contains("/// REQ-004: balance() returns the current balance"));
+        assert!(generated.contains(
+            "pub fn balance(&self) -> Result<(), JsValue> {\n        BankApi::balance(&self.0).map_err(constraint_error_to_js)\n    }"
+        ));
+    }
+
+    #[test]
+    fn converts_constraint_errors_to_structured_js_objects() {
+        let doc = SpecDocument::new();
+        let generated = generate(&doc, "BankApi", "WasmBank", "Bank");
+        assert!(generated.contains("fn constraint_error_to_js(err: impl std::error::Error) -> JsValue {"));
+        assert!(generated.contains("js_sys::Reflect::set(&obj, &JsValue::from_str(\"code\"), &JsValue::from_str(code)).unwrap();"));
+    }
+}
@@ -0,0 +1,213 @@
+//! A CODEOWNERS-style mapping from source files to the team that owns
+//! the requirement(s) they mention, built from a
+//! [`Mention`](crate::trace::Mention) scan the way
+//! [`crate::github_annotations`] anchors a coverage gap at a source
+//! mention — plus a gate that flags a requirement whose linked
+//! implementation changed without a review recorded from its owning
+//! team.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::review::Approval;
+use crate::spec::SpecDocument;
+use crate::trace::{Mention, Record};
+
+/// Every team owning at least one requirement mentioned in `path`,
+/// sorted and deduplicated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ownership {
+    pub path: PathBuf,
+    pub teams: Vec<String>,
+}
+
+/// Builds one [`Ownership`] row per file `mentions` names, from the
+/// [`team`](crate::spec::Requirement::team) each mentioned id's
+/// requirement declares. A mention of an id with no requirement in
+/// `doc`, or whose requirement has no team, doesn't contribute a team to
+/// its file — a file drops out of the mapping entirely if none of its
+/// mentions do.
+pub fn build(doc: &SpecDocument, mentions: &[Mention]) -> Vec<Ownership> {
+    let mut by_path: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    for mention in mentions {
+        let Some(req) = doc.requirement(&mention.id) else { continue };
+        let Some(team) = &req.team else { continue };
+        let teams = by_path.entry(mention.file.clone()).or_default();
+        if !teams.contains(team) {
+            teams.push(team.clone());
+        }
+    }
+    by_path
+        .into_iter()
+        .map(|(path, mut teams)| {
+            teams.sort();
+            Ownership { path, teams }
+        })
+        .collect()
+}
+
+/// Renders `ownership` as a CODEOWNERS file: one `<path> @team...` line
+/// per row, in path order.
+pub fn to_codeowners_file(ownership: &[Ownership]) -> String {
+    let mut out = String::new();
+    for row in ownership {
+        let teams: Vec<String> = row.teams.iter().map(|t| format!("@{t}")).collect();
+        out.push_str(&format!("{} {}\n", row.path.display(), teams.join(" ")));
+    }
+    out
+}
+
+/// A requirement whose owning team hasn't reviewed it, even though it's
+/// linked to implemented code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutingGap {
+    pub requirement: String,
+    pub team: String,
+}
+
+/// Checks every requirement that both declares a
+/// [`team`](crate::spec::Requirement::team) and has at least one
+/// `implements` record in `records` against `approvals`, flagging one
+/// whose most recent approval wasn't recorded under its own team's name
+/// — the convention this expects is that a team reviews by calling
+/// [`crate::review::record`] with its own name as the reviewer, the same
+/// way an individual would with theirs. A requirement with no linked
+/// implementation isn't code yet, so there's nothing to route a review
+/// to.
+pub fn routing_gate(doc: &SpecDocument, records: &[Record], approvals: &[Approval]) -> Vec<RoutingGap> {
+    doc.requirements
+        .iter()
+        .filter_map(|req| {
+            let team = req.team.as_ref()?;
+            let has_implementation = records.iter().any(|r| r.kind == "implements" && r.requirement == req.id);
+            if !has_implementation {
+                return None;
+            }
+            let latest = approvals
+                .iter()
+                .filter(|a| a.requirement == req.id)
+                .max_by_key(|a| a.approved_at_unix);
+            match latest {
+                Some(approval) if &approval.reviewer == team => None,
+                _ => Some(RoutingGap { requirement: req.id.clone(), team: team.clone() }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    fn doc_with_team(id: &str, team: Option<&str>) -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: id.into(),
+            text: "text".into(),
+            team: team.map(String::from),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn maps_a_file_to_the_team_of_a_requirement_it_mentions() {
+        let doc = doc_with_team("REQ-004", Some("backend"));
+        let mentions = vec![Mention { id: "REQ-004".into(), file: "src/lib.rs".into(), line: 3 }];
+
+        let ownership = build(&doc, &mentions);
+        assert_eq!(ownership, vec![Ownership { path: "src/lib.rs".into(), teams: vec!["backend".into()] }]);
+    }
+
+    #[test]
+    fn a_file_with_no_team_owned_mentions_is_left_out() {
+        let doc = doc_with_team("REQ-004", None);
+        let mentions = vec![Mention { id: "REQ-004".into(), file: "src/lib.rs".into(), line: 3 }];
+
+        assert!(build(&doc, &mentions).is_empty());
+    }
+
+    #[test]
+    fn a_mention_of_an_unknown_id_is_ignored() {
+        let doc = SpecDocument::new();
+        let mentions = vec![Mention { id: "REQ-999".into(), file: "src/lib.rs".into(), line: 1 }];
+
+        assert!(build(&doc, &mentions).is_empty());
+    }
+
+    #[test]
+    fn deduplicates_and_sorts_teams_for_a_file_mentioned_by_several_requirements() {
+        let mut doc = doc_with_team("REQ-004", Some("backend"));
+        doc.requirements.push(Requirement { id: "REQ-005".into(), text: "t".into(), team: Some("frontend".into()), ..Default::default() });
+        doc.requirements.push(Requirement { id: "REQ-006".into(), text: "t".into(), team: Some("backend".into()), ..Default::default() });
+        let mentions = vec![
+            Mention { id: "REQ-004".into(), file: "src/lib.rs".into(), line: 1 },
+            Mention { id: "REQ-005".into(), file: "src/lib.rs".into(), line: 2 },
+            Mention { id: "REQ-006".into(), file: "src/lib.rs".into(), line: 3 },
+        ];
+
+        let ownership = build(&doc, &mentions);
+        assert_eq!(ownership, vec![Ownership { path: "src/lib.rs".into(), teams: vec!["backend".into(), "frontend".into()] }]);
+    }
+
+    #[test]
+    fn renders_a_codeowners_file() {
+        let ownership = vec![Ownership { path: "src/lib.rs".into(), teams: vec!["backend".into(), "frontend".into()] }];
+        assert_eq!(to_codeowners_file(&ownership), "src/lib.rs @backend @frontend\n");
+    }
+
+    #[test]
+    fn routing_gate_flags_an_implemented_team_owned_requirement_with_no_approval() {
+        let doc = doc_with_team("REQ-004", Some("backend"));
+        let records = vec![Record { kind: "implements".into(), function: "f".into(), requirement: "REQ-004".into() }];
+
+        let gaps = routing_gate(&doc, &records, &[]);
+        assert_eq!(gaps, vec![RoutingGap { requirement: "REQ-004".into(), team: "backend".into() }]);
+    }
+
+    #[test]
+    fn routing_gate_passes_when_the_owning_team_approved_it() {
+        let doc = doc_with_team("REQ-004", Some("backend"));
+        let records = vec![Record { kind: "implements".into(), function: "f".into(), requirement: "REQ-004".into() }];
+        let approvals = vec![Approval {
+            requirement: "REQ-004".into(),
+            reviewer: "backend".into(),
+            spec_hash: doc.version_hash(),
+            approved_at_unix: 1,
+        }];
+
+        assert!(routing_gate(&doc, &records, &approvals).is_empty());
+    }
+
+    #[test]
+    fn routing_gate_flags_an_approval_from_a_different_team() {
+        let doc = doc_with_team("REQ-004", Some("backend"));
+        let records = vec![Record { kind: "implements".into(), function: "f".into(), requirement: "REQ-004".into() }];
+        let approvals = vec![Approval {
+            requirement: "REQ-004".into(),
+            reviewer: "frontend".into(),
+            spec_hash: doc.version_hash(),
+            approved_at_unix: 1,
+        }];
+
+        assert_eq!(
+            routing_gate(&doc, &records, &approvals),
+            vec![RoutingGap { requirement: "REQ-004".into(), team: "backend".into() }]
+        );
+    }
+
+    #[test]
+    fn routing_gate_ignores_a_requirement_with_no_linked_implementation() {
+        let doc = doc_with_team("REQ-004", Some("backend"));
+        assert!(routing_gate(&doc, &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn routing_gate_ignores_a_requirement_with_no_owning_team() {
+        let doc = doc_with_team("REQ-004", None);
+        let records = vec![Record { kind: "implements".into(), function: "f".into(), requirement: "REQ-004".into() }];
+
+        assert!(routing_gate(&doc, &records, &[]).is_empty());
+    }
+}
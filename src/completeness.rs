@@ -0,0 +1,161 @@
+//! A documentation-quality report alongside `cargo spec report`'s test
+//! coverage: which requirements are missing [`Requirement::rationale`],
+//! [`Requirement::acceptance_criteria`], or [`Requirement::examples`], so
+//! "spec coverage" can mean more than "has a test" — a requirement with
+//! ten passing tests but no rationale is still underdocumented.
+//!
+//! What's required is configurable per tag (mirroring
+//! [`CoveragePolicy::min_tests_per_tag`](crate::trace::policy::CoveragePolicy)'s
+//! existing per-tag gate), since a `mandatory` requirement might need
+//! acceptance criteria while an `internal` one doesn't.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::spec::{Requirement, SpecDocument};
+
+/// A documentation field [`check`] can require.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RequiredField {
+    Rationale,
+    AcceptanceCriteria,
+    Examples,
+}
+
+impl RequiredField {
+    fn is_missing(self, req: &Requirement) -> bool {
+        match self {
+            RequiredField::Rationale => {
+                req.rationale.as_deref().unwrap_or("").trim().is_empty()
+            }
+            RequiredField::AcceptanceCriteria => req.acceptance_criteria.is_empty(),
+            RequiredField::Examples => req.examples.is_empty(),
+        }
+    }
+}
+
+/// Which documentation fields a requirement carrying a given tag must
+/// have. A requirement with no tag in this map (or no tags at all) is
+/// unconstrained.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompletenessPolicy {
+    #[serde(default)]
+    pub required_fields: BTreeMap<String, Vec<RequiredField>>,
+}
+
+/// One requirement missing at least one documentation field its tags
+/// require.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CompletenessGap {
+    pub requirement: String,
+    pub missing: Vec<RequiredField>,
+}
+
+/// Checks every requirement in `doc` against `policy`, returning one
+/// [`CompletenessGap`] per requirement missing at least one field
+/// required by any tag it carries. Requirements satisfying their
+/// policy, or carrying no tag the policy constrains, don't appear.
+pub fn check(doc: &SpecDocument, policy: &CompletenessPolicy) -> Vec<CompletenessGap> {
+    let mut gaps = Vec::new();
+    for req in &doc.requirements {
+        let mut required: Vec<RequiredField> = req
+            .tags
+            .iter()
+            .filter_map(|tag| policy.required_fields.get(tag))
+            .flatten()
+            .copied()
+            .collect();
+        required.sort();
+        required.dedup();
+
+        let missing: Vec<RequiredField> = required
+            .into_iter()
+            .filter(|field| field.is_missing(req))
+            .collect();
+        if !missing.is_empty() {
+            gaps.push(CompletenessGap { requirement: req.id.clone(), missing });
+        }
+    }
+    gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::AcceptanceCriterion;
+
+    fn policy() -> CompletenessPolicy {
+        CompletenessPolicy {
+            required_fields: [(
+                "mandatory".to_string(),
+                vec![RequiredField::Rationale, RequiredField::AcceptanceCriteria],
+            )]
+            .into(),
+        }
+    }
+
+    fn doc(req: Requirement) -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req);
+        doc
+    }
+
+    #[test]
+    fn flags_a_mandatory_requirement_missing_rationale_and_acceptance_criteria() {
+        let req = Requirement {
+            id: "REQ-001".into(),
+            tags: vec!["mandatory".into()],
+            ..Default::default()
+        };
+        let gaps = check(&doc(req), &policy());
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].requirement, "REQ-001");
+        assert_eq!(
+            gaps[0].missing,
+            vec![RequiredField::Rationale, RequiredField::AcceptanceCriteria]
+        );
+    }
+
+    #[test]
+    fn a_fully_documented_requirement_has_no_gap() {
+        let req = Requirement {
+            id: "REQ-001".into(),
+            tags: vec!["mandatory".into()],
+            rationale: Some("avoids overdraft fraud".into()),
+            acceptance_criteria: vec![AcceptanceCriterion { id: "a".into(), text: "balance never goes negative".into() }],
+            ..Default::default()
+        };
+        assert!(check(&doc(req), &policy()).is_empty());
+    }
+
+    #[test]
+    fn a_requirement_with_no_constrained_tag_is_unconstrained() {
+        let req = Requirement { id: "REQ-001".into(), ..Default::default() };
+        assert!(check(&doc(req), &policy()).is_empty());
+    }
+
+    #[test]
+    fn a_blank_rationale_still_counts_as_missing() {
+        let req = Requirement {
+            id: "REQ-001".into(),
+            tags: vec!["mandatory".into()],
+            rationale: Some("   ".into()),
+            acceptance_criteria: vec![AcceptanceCriterion { id: "a".into(), text: "balance never goes negative".into() }],
+            ..Default::default()
+        };
+        let gaps = check(&doc(req), &policy());
+        assert_eq!(gaps[0].missing, vec![RequiredField::Rationale]);
+    }
+
+    #[test]
+    fn tags_with_no_policy_entry_require_nothing() {
+        let req = Requirement {
+            id: "REQ-001".into(),
+            tags: vec!["cosmetic".into()],
+            ..Default::default()
+        };
+        assert!(check(&doc(req), &policy()).is_empty());
+    }
+}
@@ -0,0 +1,288 @@
+//! Detects constraints that are individually well-formed but jointly
+//! impossible to satisfy — e.g. `amount > 100` and `amount < 50` on the
+//! same field, which would reject every possible `deposit`.
+//! [`crate::lint`] can't catch this: each constraint's `expr` parses fine
+//! on its own, and only fails once checked *together* with the others.
+//!
+//! Deciding joint satisfiability needs an actual solver, so this is gated
+//! behind the `z3` feature: [`check`] still runs without it, but reports
+//! [`ConsistencyReport::solver_available`] as `false` and every checkable
+//! constraint as [`skipped`](ConsistencyReport::skipped) instead of
+//! silently claiming the spec is consistent.
+
+use crate::spec::SpecDocument;
+#[cfg(feature = "z3")]
+use crate::spec::{ConstraintExpr, Severity, Term};
+
+/// One set of constraint codes whose `expr`s can't all hold at once, per
+/// the solver's unsatisfiable core. Not guaranteed to be the globally
+/// smallest such set — only one the solver couldn't shrink further by
+/// default — but still a real, checkable counterexample to "this spec is
+/// consistent".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictingCore {
+    pub constraints: Vec<String>,
+}
+
+/// [`check`]'s result.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ConsistencyReport {
+    /// Constraint combinations the solver found jointly unsatisfiable.
+    /// Empty both when the solver ran and found none, and when it didn't
+    /// run at all — check `solver_available` to tell those apart.
+    pub conflicts: Vec<ConflictingCore>,
+    /// Constraints this check couldn't include, paired with why: no
+    /// `expr`, a non-[`Error`](Severity::Error) severity (a soft
+    /// constraint isn't a "must always hold" fact to check jointly), an
+    /// `expr` that calls a function (this module has no sound way to
+    /// encode an arbitrary call for a solver), or — only without the `z3`
+    /// feature — the solver being unavailable at all.
+    pub skipped: Vec<(String, String)>,
+    /// Whether the solver actually ran. `false` without the `z3` feature
+    /// enabled; `conflicts` is always empty in that case, which is not a
+    /// sign the spec is consistent.
+    pub solver_available: bool,
+}
+
+/// Checks every [`Error`](Severity::Error)-severity constraint in `doc`
+/// whose `expr` is pure arithmetic/comparisons over bare identifiers (no
+/// calls) for joint satisfiability, reporting a [`ConflictingCore`] if
+/// they can't all hold at the same time.
+pub fn check(doc: &SpecDocument) -> ConsistencyReport {
+    #[cfg(feature = "z3")]
+    {
+        z3_check(doc)
+    }
+    #[cfg(not(feature = "z3"))]
+    {
+        unavailable(doc)
+    }
+}
+
+#[cfg(not(feature = "z3"))]
+fn unavailable(doc: &SpecDocument) -> ConsistencyReport {
+    let skipped = doc
+        .constraints
+        .iter()
+        .filter(|c| c.expr.is_some())
+        .map(|c| {
+            (
+                c.code.clone(),
+                "the `z3` feature isn't enabled, so joint satisfiability can't be checked".to_string(),
+            )
+        })
+        .collect();
+    ConsistencyReport { conflicts: Vec::new(), skipped, solver_available: false }
+}
+
+#[cfg(feature = "z3")]
+fn z3_check(doc: &SpecDocument) -> ConsistencyReport {
+    use std::collections::BTreeMap;
+    use z3::ast::{Bool, Real};
+    use z3::{SatResult, Solver};
+
+    let mut skipped = Vec::new();
+    let mut checkable = Vec::new();
+    for c in &doc.constraints {
+        if c.severity != Severity::Error {
+            skipped.push((c.code.clone(), "severity isn't Error, so it isn't a fact to check jointly".to_string()));
+            continue;
+        }
+        let Some(expr_src) = &c.expr else { continue };
+        match ConstraintExpr::parse(expr_src) {
+            Ok(expr) if has_call(&expr) => {
+                skipped.push((c.code.clone(), "expr calls a function, which this module has no sound way to encode for a solver".to_string()));
+            }
+            Ok(expr) => checkable.push((c.code.clone(), expr)),
+            Err(_) => {
+                // Already reported by `crate::lint`'s `invalid-constraint-expr` rule.
+            }
+        }
+    }
+
+    if checkable.is_empty() {
+        return ConsistencyReport { conflicts: Vec::new(), skipped, solver_available: true };
+    }
+
+    let mut vars: BTreeMap<String, Real> = BTreeMap::new();
+    for (_, expr) in &checkable {
+        for ident in expr.idents() {
+            vars.entry(ident.to_string()).or_insert_with(|| Real::new_const(ident));
+        }
+    }
+
+    let solver = Solver::new();
+    let mut indicators = Vec::new();
+    for (code, expr) in &checkable {
+        let indicator = Bool::new_const(format!("consistency::{code}"));
+        solver.assert_and_track(expr_to_bool(expr, &vars), &indicator);
+        indicators.push((code.clone(), indicator));
+    }
+
+    let mut conflicts = Vec::new();
+    if solver.check() == SatResult::Unsat {
+        let core = solver.get_unsat_core();
+        let mut codes: Vec<String> = indicators
+            .iter()
+            .filter(|(_, indicator)| core.contains(indicator))
+            .map(|(code, _)| code.clone())
+            .collect();
+        codes.sort();
+        conflicts.push(ConflictingCore { constraints: codes });
+    }
+
+    ConsistencyReport { conflicts, skipped, solver_available: true }
+}
+
+#[cfg(feature = "z3")]
+fn has_call(expr: &ConstraintExpr) -> bool {
+    expr.comparisons().iter().any(|c| term_has_call(&c.lhs) || term_has_call(&c.rhs))
+}
+
+#[cfg(feature = "z3")]
+fn term_has_call(term: &Term) -> bool {
+    match term {
+        Term::Number(_) | Term::Ident(_) => false,
+        Term::Call(..) => true,
+        Term::Add(a, b) | Term::Sub(a, b) | Term::Mul(a, b) | Term::Div(a, b) => {
+            term_has_call(a) || term_has_call(b)
+        }
+    }
+}
+
+#[cfg(feature = "z3")]
+fn expr_to_bool(expr: &ConstraintExpr, vars: &std::collections::BTreeMap<String, z3::ast::Real>) -> z3::ast::Bool {
+    use crate::spec::{Comparison, RelOp};
+    use z3::ast::{Bool, Real};
+
+    fn term_to_real(term: &Term, vars: &std::collections::BTreeMap<String, Real>) -> Real {
+        match term {
+            Term::Number(n) => real_literal(*n),
+            Term::Ident(name) => vars[name].clone(),
+            Term::Add(a, b) => term_to_real(a, vars) + term_to_real(b, vars),
+            Term::Sub(a, b) => term_to_real(a, vars) - term_to_real(b, vars),
+            Term::Mul(a, b) => term_to_real(a, vars) * term_to_real(b, vars),
+            Term::Div(a, b) => term_to_real(a, vars) / term_to_real(b, vars),
+            Term::Call(..) => unreachable!("call-free by `has_call`'s precondition"),
+        }
+    }
+
+    fn real_literal(n: f64) -> Real {
+        const SCALE: i64 = 1_000_000;
+        let num = (n * SCALE as f64).round() as i64;
+        Real::from_rational_str(&num.to_string(), &SCALE.to_string()).unwrap()
+    }
+
+    fn comparison_to_bool(c: &Comparison, vars: &std::collections::BTreeMap<String, Real>) -> Bool {
+        let lhs = term_to_real(&c.lhs, vars);
+        let rhs = term_to_real(&c.rhs, vars);
+        match c.op {
+            RelOp::Gt => lhs.gt(rhs),
+            RelOp::Lt => lhs.lt(rhs),
+            RelOp::Ge => lhs.ge(rhs),
+            RelOp::Le => lhs.le(rhs),
+            RelOp::Eq => lhs.eq(rhs),
+            RelOp::Ne => lhs.eq(rhs).not(),
+        }
+    }
+
+    match expr {
+        ConstraintExpr::Compare(c) => comparison_to_bool(c, vars),
+        ConstraintExpr::And(a, b) => Bool::and(&[expr_to_bool(a, vars), expr_to_bool(b, vars)]),
+        ConstraintExpr::Or(a, b) => Bool::or(&[expr_to_bool(a, vars), expr_to_bool(b, vars)]),
+    }
+}
+
+#[cfg(all(test, feature = "z3"))]
+mod tests {
+    use super::*;
+    use crate::spec::Constraint;
+
+    fn constraint(code: &str, expr: &str) -> Constraint {
+        Constraint { code: code.into(), text: format!("{code} text"), expr: Some(expr.into()), ..Default::default() }
+    }
+
+    #[test]
+    fn flags_two_constraints_that_cannot_both_hold() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(constraint("CONST-001", "amount > 100"));
+        doc.constraints.push(constraint("CONST-002", "amount < 50"));
+
+        let report = check(&doc);
+        assert!(report.solver_available);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].constraints, vec!["CONST-001", "CONST-002"]);
+    }
+
+    #[test]
+    fn a_satisfiable_constraint_set_has_no_conflicts() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(constraint("CONST-001", "amount > 0"));
+        doc.constraints.push(constraint("CONST-002", "amount < 100"));
+
+        let report = check(&doc);
+        assert!(report.conflicts.is_empty());
+    }
+
+    #[test]
+    fn a_three_way_contradiction_is_reported_as_one_core() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(constraint("CONST-001", "amount > 50"));
+        doc.constraints.push(constraint("CONST-002", "amount < 100"));
+        doc.constraints.push(constraint("CONST-003", "amount == 0"));
+
+        let report = check(&doc);
+        assert_eq!(report.conflicts.len(), 1);
+        assert!(report.conflicts[0].constraints.contains(&"CONST-003".to_string()));
+    }
+
+    #[test]
+    fn skips_a_warning_severity_constraint() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-004".into(),
+            text: "soft cap".into(),
+            expr: Some("amount <= 1000".into()),
+            severity: Severity::Warning,
+            ..Default::default()
+        });
+
+        let report = check(&doc);
+        assert!(report.conflicts.is_empty());
+        assert!(report.skipped.iter().any(|(code, reason)| code == "CONST-004" && reason.contains("severity isn't Error")));
+    }
+
+    #[test]
+    fn skips_a_constraint_whose_expr_calls_a_function() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(constraint("CONST-005", "amount <= balance(account)"));
+
+        let report = check(&doc);
+        assert!(report.skipped.iter().any(|(code, _)| code == "CONST-005"));
+    }
+}
+
+#[cfg(all(test, not(feature = "z3")))]
+mod tests_without_solver {
+    use super::*;
+    use crate::spec::Constraint;
+
+    #[test]
+    fn reports_the_solver_as_unavailable_and_skips_every_checkable_constraint() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            expr: Some("amount > 0".into()),
+            ..Default::default()
+        });
+
+        let report = check(&doc);
+        assert!(!report.solver_available);
+        assert!(report.conflicts.is_empty());
+        assert_eq!(report.skipped, vec![(
+            "CONST-001".to_string(),
+            "the `z3` feature isn't enabled, so joint satisfiability can't be checked".to_string(),
+        )]);
+    }
+}
@@ -0,0 +1,715 @@
+//! Semantic diff between two [`SpecDocument`]s: which requirements and
+//! constraints were added, removed, or modified, classified by what
+//! changed about them (a requirement's implied method signature, a
+//! constraint's severity tightening or loosening) instead of a
+//! line-oriented text diff of the spec file itself. [`classify`] and
+//! [`recommend_next_version`] turn that diff into a breaking/additive/
+//! compatible verdict and a version bump, so a spec's version string
+//! doesn't need a human to remember to update it.
+
+use serde::Serialize;
+
+use crate::codegen::method_name;
+use crate::spec::{Constraint, Requirement, Severity, SpecDocument};
+
+/// One requirement present in both specs but changed between them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RequirementChange {
+    pub id: String,
+    pub before: Requirement,
+    pub after: Requirement,
+    /// Whether [`method_name`] derives a different method name from
+    /// `after` than from `before` — a reworded requirement whose implied
+    /// signature moved, not just a typo fix.
+    pub signature_changed: bool,
+}
+
+/// How a constraint's strictness moved between two specs, judged from its
+/// [`Severity`] alone (an `expr` rewrite with the same severity is
+/// reported as a change but not classified either way, since there's no
+/// evaluator in this codebase to compare two expressions' strictness).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StrictnessChange {
+    /// Moved to a stricter [`Severity`], e.g. `Warning` -> `Error`.
+    Tightened,
+    /// Moved to a less strict [`Severity`], e.g. `Error` -> `Advisory`.
+    Loosened,
+    /// Severity is unchanged; whatever else differs (`expr`, `text`,
+    /// `http_status`) isn't judged for strictness.
+    Unchanged,
+}
+
+/// One constraint present in both specs but changed between them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConstraintChange {
+    pub code: String,
+    pub before: Constraint,
+    pub after: Constraint,
+    pub strictness: StrictnessChange,
+}
+
+/// A requirement removed from `before` whose
+/// [`Requirement::replaced_by`](crate::spec::Requirement::replaced_by)
+/// names a requirement added in `after` — a renumbering or rename rather
+/// than a net loss of behavior, so [`diff`] reports it as one event
+/// instead of an unrelated removal plus addition. See
+/// [`crate::graph::lineage`] for following a chain of these across more
+/// than two spec versions.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Supersession {
+    pub old_id: String,
+    pub new_id: String,
+    pub before: Requirement,
+    pub after: Requirement,
+    /// Whether [`method_name`] derives a different method name for
+    /// `after` than for `before` — a pure renumbering keeps this `false`.
+    pub signature_changed: bool,
+}
+
+/// A structured diff of everything that changed between two specs.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct SpecDiff {
+    pub added_requirements: Vec<Requirement>,
+    pub removed_requirements: Vec<Requirement>,
+    pub modified_requirements: Vec<RequirementChange>,
+    pub superseded_requirements: Vec<Supersession>,
+    pub added_constraints: Vec<Constraint>,
+    pub removed_constraints: Vec<Constraint>,
+    pub modified_constraints: Vec<ConstraintChange>,
+}
+
+impl SpecDiff {
+    /// True if `before` and `after` were identical.
+    pub fn is_empty(&self) -> bool {
+        self.added_requirements.is_empty()
+            && self.removed_requirements.is_empty()
+            && self.modified_requirements.is_empty()
+            && self.superseded_requirements.is_empty()
+            && self.added_constraints.is_empty()
+            && self.removed_constraints.is_empty()
+            && self.modified_constraints.is_empty()
+    }
+
+    /// Renders this diff as pretty-printed JSON, for tooling that wants a
+    /// machine-readable changelog instead of
+    /// [`crate::changelog::render_markdown`].
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// How a [`SpecDiff`] affects implementations already built against the
+/// old spec, from least to most disruptive — the ordering matches
+/// semver's major/minor/patch precedence, so `Breaking > Additive >
+/// Compatible` as far as [`recommend_next_version`] is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeLevel {
+    /// Nothing a conforming implementation needs to change for.
+    Compatible,
+    /// New requirements or constraints an implementation may adopt but
+    /// isn't required to.
+    Additive,
+    /// An existing implementation can stop conforming: something was
+    /// removed, a requirement's implied method signature changed, or a
+    /// constraint got stricter.
+    Breaking,
+}
+
+/// Classifies a [`SpecDiff`] by the most disruptive change it contains.
+/// Any removal, [`RequirementChange::signature_changed`],
+/// [`Supersession::signature_changed`], or [`StrictnessChange::Tightened`]
+/// constraint makes the whole diff [`ChangeLevel::Breaking`]; failing
+/// that, any addition makes it [`ChangeLevel::Additive`]; otherwise (only
+/// reworded text, a pure renumbering, or loosened constraints) it's
+/// [`ChangeLevel::Compatible`].
+pub fn classify(diff: &SpecDiff) -> ChangeLevel {
+    let breaking = !diff.removed_requirements.is_empty()
+        || !diff.removed_constraints.is_empty()
+        || diff
+            .modified_requirements
+            .iter()
+            .any(|change| change.signature_changed)
+        || diff
+            .superseded_requirements
+            .iter()
+            .any(|change| change.signature_changed)
+        || diff
+            .modified_constraints
+            .iter()
+            .any(|change| change.strictness == StrictnessChange::Tightened);
+    if breaking {
+        return ChangeLevel::Breaking;
+    }
+
+    let additive = !diff.added_requirements.is_empty() || !diff.added_constraints.is_empty();
+    if additive {
+        return ChangeLevel::Additive;
+    }
+
+    ChangeLevel::Compatible
+}
+
+/// Recommends the next `major.minor.patch` version string for a spec,
+/// bumping `current_version` by how a [`SpecDiff`] classifies (major for
+/// [`ChangeLevel::Breaking`], minor for [`ChangeLevel::Additive`], patch
+/// for [`ChangeLevel::Compatible`]) — or leaving it untouched if the diff
+/// is empty. `current_version` need not already be `major.minor.patch`;
+/// missing components are treated as `0`, so a hand-maintained `"1"`
+/// becomes `"2.0.0"` on its first breaking change.
+pub fn recommend_next_version(current_version: &str, diff: &SpecDiff) -> String {
+    if diff.is_empty() {
+        return current_version.to_string();
+    }
+
+    let [mut major, mut minor, mut patch] = parse_version(current_version);
+    match classify(diff) {
+        ChangeLevel::Breaking => {
+            major += 1;
+            minor = 0;
+            patch = 0;
+        }
+        ChangeLevel::Additive => {
+            minor += 1;
+            patch = 0;
+        }
+        ChangeLevel::Compatible => {
+            patch += 1;
+        }
+    }
+    format!("{major}.{minor}.{patch}")
+}
+
+fn parse_version(version: &str) -> [u64; 3] {
+    let mut parts = [0u64; 3];
+    for (slot, piece) in parts.iter_mut().zip(version.split('.')) {
+        *slot = piece.parse().unwrap_or(0);
+    }
+    parts
+}
+
+/// Diffs `before` against `after`, matching requirements and constraints
+/// by id/code rather than position, so reordering a spec file's entries
+/// never shows up as a change. Same as [`diff_with_threads`]`(before,
+/// after, None)`.
+pub fn diff(before: &SpecDocument, after: &SpecDocument) -> SpecDiff {
+    diff_with_threads(before, after, None)
+}
+
+/// Same as [`diff`], behind the `parallel` feature classifying
+/// requirements and constraints on a rayon pool sized by `threads` (see
+/// [`crate::parallel::run`]) — without it, `threads` is ignored and
+/// classification runs sequentially, the same as before this knob
+/// existed. The resulting [`SpecDiff`] is identical either way: entries
+/// stay in [`diff`]'s usual order regardless of which one's
+/// classification actually finishes first.
+pub fn diff_with_threads(before: &SpecDocument, after: &SpecDocument, threads: Option<usize>) -> SpecDiff {
+    #[cfg(feature = "parallel")]
+    {
+        crate::parallel::run(threads, || diff_parallel(before, after))
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        let _ = threads;
+        diff_sequential(before, after)
+    }
+}
+
+enum RequirementClassification {
+    Added(Box<Requirement>),
+    Modified(Box<RequirementChange>),
+    Unchanged,
+}
+
+fn classify_after_requirement(before: &SpecDocument, req: &Requirement) -> RequirementClassification {
+    match before.requirement(&req.id) {
+        None => RequirementClassification::Added(Box::new(req.clone())),
+        Some(prev) if prev != req => RequirementClassification::Modified(Box::new(RequirementChange {
+            id: req.id.clone(),
+            before: prev.clone(),
+            after: req.clone(),
+            signature_changed: method_name(prev) != method_name(req),
+        })),
+        Some(_) => RequirementClassification::Unchanged,
+    }
+}
+
+fn removed_requirement(after: &SpecDocument, req: &Requirement) -> Option<Requirement> {
+    after.requirement(&req.id).is_none().then(|| req.clone())
+}
+
+enum ConstraintClassification {
+    Added(Constraint),
+    Modified(ConstraintChange),
+    Unchanged,
+}
+
+fn classify_after_constraint(before: &SpecDocument, c: &Constraint) -> ConstraintClassification {
+    match before.constraint(&c.code) {
+        None => ConstraintClassification::Added(c.clone()),
+        Some(prev) if prev != c => ConstraintClassification::Modified(ConstraintChange {
+            code: c.code.clone(),
+            before: prev.clone(),
+            after: c.clone(),
+            strictness: strictness_change(prev.severity, c.severity),
+        }),
+        Some(_) => ConstraintClassification::Unchanged,
+    }
+}
+
+fn removed_constraint(after: &SpecDocument, c: &Constraint) -> Option<Constraint> {
+    after.constraint(&c.code).is_none().then(|| c.clone())
+}
+
+fn assemble(
+    mut added_requirements: Vec<Requirement>,
+    mut removed_requirements: Vec<Requirement>,
+    modified_requirements: Vec<RequirementChange>,
+    added_constraints: Vec<Constraint>,
+    removed_constraints: Vec<Constraint>,
+    modified_constraints: Vec<ConstraintChange>,
+) -> SpecDiff {
+    let mut superseded_requirements = Vec::new();
+    removed_requirements.retain(|removed| {
+        let Some(new_id) = &removed.replaced_by else {
+            return true;
+        };
+        let Some(pos) = added_requirements.iter().position(|a: &Requirement| &a.id == new_id) else {
+            return true;
+        };
+        let added = added_requirements.remove(pos);
+        superseded_requirements.push(Supersession {
+            old_id: removed.id.clone(),
+            new_id: added.id.clone(),
+            signature_changed: method_name(removed) != method_name(&added),
+            before: removed.clone(),
+            after: added,
+        });
+        false
+    });
+
+    SpecDiff {
+        added_requirements,
+        removed_requirements,
+        modified_requirements,
+        superseded_requirements,
+        added_constraints,
+        removed_constraints,
+        modified_constraints,
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn diff_sequential(before: &SpecDocument, after: &SpecDocument) -> SpecDiff {
+    let mut added_requirements = Vec::new();
+    let mut modified_requirements = Vec::new();
+    for req in &after.requirements {
+        match classify_after_requirement(before, req) {
+            RequirementClassification::Added(r) => added_requirements.push(*r),
+            RequirementClassification::Modified(c) => modified_requirements.push(*c),
+            RequirementClassification::Unchanged => {}
+        }
+    }
+    let removed_requirements: Vec<Requirement> = before
+        .requirements
+        .iter()
+        .filter_map(|req| removed_requirement(after, req))
+        .collect();
+
+    let mut added_constraints = Vec::new();
+    let mut modified_constraints = Vec::new();
+    for c in &after.constraints {
+        match classify_after_constraint(before, c) {
+            ConstraintClassification::Added(c) => added_constraints.push(c),
+            ConstraintClassification::Modified(c) => modified_constraints.push(c),
+            ConstraintClassification::Unchanged => {}
+        }
+    }
+    let removed_constraints: Vec<Constraint> = before
+        .constraints
+        .iter()
+        .filter_map(|c| removed_constraint(after, c))
+        .collect();
+
+    assemble(
+        added_requirements,
+        removed_requirements,
+        modified_requirements,
+        added_constraints,
+        removed_constraints,
+        modified_constraints,
+    )
+}
+
+#[cfg(feature = "parallel")]
+fn diff_parallel(before: &SpecDocument, after: &SpecDocument) -> SpecDiff {
+    use rayon::prelude::*;
+
+    let after_requirements: Vec<RequirementClassification> = after
+        .requirements
+        .par_iter()
+        .map(|req| classify_after_requirement(before, req))
+        .collect();
+    let mut added_requirements = Vec::new();
+    let mut modified_requirements = Vec::new();
+    for classification in after_requirements {
+        match classification {
+            RequirementClassification::Added(r) => added_requirements.push(*r),
+            RequirementClassification::Modified(c) => modified_requirements.push(*c),
+            RequirementClassification::Unchanged => {}
+        }
+    }
+    let removed_requirements: Vec<Requirement> = before
+        .requirements
+        .par_iter()
+        .filter_map(|req| removed_requirement(after, req))
+        .collect();
+
+    let after_constraints: Vec<ConstraintClassification> = after
+        .constraints
+        .par_iter()
+        .map(|c| classify_after_constraint(before, c))
+        .collect();
+    let mut added_constraints = Vec::new();
+    let mut modified_constraints = Vec::new();
+    for classification in after_constraints {
+        match classification {
+            ConstraintClassification::Added(c) => added_constraints.push(c),
+            ConstraintClassification::Modified(c) => modified_constraints.push(c),
+            ConstraintClassification::Unchanged => {}
+        }
+    }
+    let removed_constraints: Vec<Constraint> = before
+        .constraints
+        .par_iter()
+        .filter_map(|c| removed_constraint(after, c))
+        .collect();
+
+    assemble(
+        added_requirements,
+        removed_requirements,
+        modified_requirements,
+        added_constraints,
+        removed_constraints,
+        modified_constraints,
+    )
+}
+
+/// How strict a [`Severity`] is, for comparing two of them: [`Severity::Error`]
+/// is strictest, [`Severity::Advisory`] least.
+fn strictness_rank(severity: Severity) -> u8 {
+    match severity {
+        Severity::Advisory => 0,
+        Severity::Warning => 1,
+        Severity::Error => 2,
+    }
+}
+
+fn strictness_change(before: Severity, after: Severity) -> StrictnessChange {
+    match strictness_rank(after).cmp(&strictness_rank(before)) {
+        std::cmp::Ordering::Greater => StrictnessChange::Tightened,
+        std::cmp::Ordering::Less => StrictnessChange::Loosened,
+        std::cmp::Ordering::Equal => StrictnessChange::Unchanged,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(id: &str, text: &str) -> Requirement {
+        Requirement {
+            id: id.into(),
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    fn constraint(code: &str, severity: Severity) -> Constraint {
+        Constraint {
+            code: code.into(),
+            text: "amount must be positive".into(),
+            severity,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn to_json_round_trips_through_serde_json() {
+        let before = SpecDocument::new();
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-004", "balance() returns the current balance"));
+
+        let spec_diff = diff(&before, &after);
+        let json = spec_diff.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["added_requirements"][0]["id"], "REQ-004");
+    }
+
+    #[test]
+    fn detects_an_added_requirement() {
+        let before = SpecDocument::new();
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-004", "balance() returns the current balance"));
+
+        let diff = diff(&before, &after);
+        assert_eq!(diff.added_requirements, vec![req("REQ-004", "balance() returns the current balance")]);
+        assert!(diff.removed_requirements.is_empty());
+        assert!(diff.modified_requirements.is_empty());
+    }
+
+    #[test]
+    fn detects_a_removed_requirement() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        let after = SpecDocument::new();
+
+        let diff = diff(&before, &after);
+        assert_eq!(diff.removed_requirements, vec![req("REQ-004", "balance() returns the current balance")]);
+    }
+
+    #[test]
+    fn reports_a_reworded_requirement_as_modified_without_a_signature_change() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(req("REQ-004", "balance() returns the current account balance"));
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-004", "balance() returns the current balance"));
+
+        let diff = diff(&before, &after);
+        assert_eq!(diff.modified_requirements.len(), 1);
+        assert!(!diff.modified_requirements[0].signature_changed);
+    }
+
+    #[test]
+    fn reports_a_renamed_method_as_a_signature_change() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-004", "current_balance() returns the current balance"));
+
+        let diff = diff(&before, &after);
+        assert_eq!(diff.modified_requirements.len(), 1);
+        assert!(diff.modified_requirements[0].signature_changed);
+    }
+
+    #[test]
+    fn classifies_a_severity_increase_as_tightened() {
+        let mut before = SpecDocument::new();
+        before.constraints.push(constraint("CONST-001", Severity::Warning));
+        let mut after = SpecDocument::new();
+        after.constraints.push(constraint("CONST-001", Severity::Error));
+
+        let diff = diff(&before, &after);
+        assert_eq!(diff.modified_constraints.len(), 1);
+        assert_eq!(diff.modified_constraints[0].strictness, StrictnessChange::Tightened);
+    }
+
+    #[test]
+    fn classifies_a_severity_decrease_as_loosened() {
+        let mut before = SpecDocument::new();
+        before.constraints.push(constraint("CONST-001", Severity::Error));
+        let mut after = SpecDocument::new();
+        after.constraints.push(constraint("CONST-001", Severity::Advisory));
+
+        let diff = diff(&before, &after);
+        assert_eq!(diff.modified_constraints[0].strictness, StrictnessChange::Loosened);
+    }
+
+    #[test]
+    fn classifies_an_expr_only_change_as_unchanged_strictness() {
+        let mut before = SpecDocument::new();
+        before.constraints.push(Constraint {
+            expr: Some("amount > 0".into()),
+            ..constraint("CONST-001", Severity::Error)
+        });
+        let mut after = SpecDocument::new();
+        after.constraints.push(Constraint {
+            expr: Some("amount > 10".into()),
+            ..constraint("CONST-001", Severity::Error)
+        });
+
+        let diff = diff(&before, &after);
+        assert_eq!(diff.modified_constraints.len(), 1);
+        assert_eq!(diff.modified_constraints[0].strictness, StrictnessChange::Unchanged);
+    }
+
+    #[test]
+    fn unchanged_requirements_and_constraints_are_not_reported() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        before.constraints.push(constraint("CONST-001", Severity::Error));
+        let after = before.clone();
+
+        let diff = diff(&before, &after);
+        assert_eq!(diff, SpecDiff::default());
+    }
+
+    #[test]
+    fn classifies_a_removed_requirement_as_breaking() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        let after = SpecDocument::new();
+
+        assert_eq!(classify(&diff(&before, &after)), ChangeLevel::Breaking);
+    }
+
+    #[test]
+    fn classifies_a_tightened_constraint_as_breaking() {
+        let mut before = SpecDocument::new();
+        before.constraints.push(constraint("CONST-001", Severity::Warning));
+        let mut after = SpecDocument::new();
+        after.constraints.push(constraint("CONST-001", Severity::Error));
+
+        assert_eq!(classify(&diff(&before, &after)), ChangeLevel::Breaking);
+    }
+
+    #[test]
+    fn classifies_a_new_requirement_as_additive() {
+        let before = SpecDocument::new();
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-004", "balance() returns the current balance"));
+
+        assert_eq!(classify(&diff(&before, &after)), ChangeLevel::Additive);
+    }
+
+    #[test]
+    fn classifies_a_rewording_with_no_signature_change_as_compatible() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(req("REQ-004", "balance() returns the current account balance"));
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-004", "balance() returns the current balance"));
+
+        assert_eq!(classify(&diff(&before, &after)), ChangeLevel::Compatible);
+    }
+
+    #[test]
+    fn classifies_a_loosened_constraint_as_compatible_not_additive() {
+        let mut before = SpecDocument::new();
+        before.constraints.push(constraint("CONST-001", Severity::Error));
+        let mut after = SpecDocument::new();
+        after.constraints.push(constraint("CONST-001", Severity::Advisory));
+
+        assert_eq!(classify(&diff(&before, &after)), ChangeLevel::Compatible);
+    }
+
+    #[test]
+    fn recommends_a_major_bump_for_a_breaking_change() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        let after = SpecDocument::new();
+
+        assert_eq!(recommend_next_version("1.2.3", &diff(&before, &after)), "2.0.0");
+    }
+
+    #[test]
+    fn recommends_a_minor_bump_for_an_additive_change() {
+        let before = SpecDocument::new();
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-004", "balance() returns the current balance"));
+
+        assert_eq!(recommend_next_version("1.2.3", &diff(&before, &after)), "1.3.0");
+    }
+
+    #[test]
+    fn recommends_a_patch_bump_for_a_compatible_change() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(req("REQ-004", "balance() returns the current account balance"));
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-004", "balance() returns the current balance"));
+
+        assert_eq!(recommend_next_version("1.2.3", &diff(&before, &after)), "1.2.4");
+    }
+
+    #[test]
+    fn leaves_the_version_unchanged_when_the_diff_is_empty() {
+        let before = SpecDocument::new();
+        let after = SpecDocument::new();
+
+        assert_eq!(recommend_next_version("1.2.3", &diff(&before, &after)), "1.2.3");
+    }
+
+    #[test]
+    fn treats_missing_version_components_as_zero() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        let after = SpecDocument::new();
+
+        assert_eq!(recommend_next_version("1", &diff(&before, &after)), "2.0.0");
+    }
+
+    fn superseded_req(id: &str, text: &str, replaced_by: &str) -> Requirement {
+        Requirement {
+            id: id.into(),
+            text: text.into(),
+            replaced_by: Some(replaced_by.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reports_a_renumbering_as_a_supersession_not_a_removal_plus_addition() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(superseded_req(
+            "REQ-004",
+            "balance() returns the current balance",
+            "REQ-012",
+        ));
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-012", "balance() returns the current balance"));
+
+        let diff = diff(&before, &after);
+        assert!(diff.added_requirements.is_empty());
+        assert!(diff.removed_requirements.is_empty());
+        assert_eq!(diff.superseded_requirements.len(), 1);
+        let supersession = &diff.superseded_requirements[0];
+        assert_eq!(supersession.old_id, "REQ-004");
+        assert_eq!(supersession.new_id, "REQ-012");
+        assert!(!supersession.signature_changed);
+    }
+
+    #[test]
+    fn classifies_a_renumbering_with_no_signature_change_as_compatible() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(superseded_req(
+            "REQ-004",
+            "balance() returns the current balance",
+            "REQ-012",
+        ));
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-012", "balance() returns the current balance"));
+
+        assert_eq!(classify(&diff(&before, &after)), ChangeLevel::Compatible);
+    }
+
+    #[test]
+    fn classifies_a_supersession_with_a_renamed_method_as_breaking() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(superseded_req(
+            "REQ-004",
+            "balance() returns the current balance",
+            "REQ-012",
+        ));
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-012", "current_balance() returns the current balance"));
+
+        let diff = diff(&before, &after);
+        assert!(diff.superseded_requirements[0].signature_changed);
+        assert_eq!(classify(&diff), ChangeLevel::Breaking);
+    }
+
+    #[test]
+    fn a_replaced_by_pointing_at_a_missing_requirement_is_still_a_plain_removal() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(superseded_req(
+            "REQ-004",
+            "balance() returns the current balance",
+            "REQ-404",
+        ));
+        let after = SpecDocument::new();
+
+        let diff = diff(&before, &after);
+        assert!(diff.superseded_requirements.is_empty());
+        assert_eq!(diff.removed_requirements.len(), 1);
+    }
+}
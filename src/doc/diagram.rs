@@ -0,0 +1,253 @@
+//! Renders Graphviz (DOT) and Mermaid diagrams of a spec's finite state
+//! machines ([`Fsm`]), its continuous state models ([`StateMachine`]), and
+//! its requirement dependency graph (`depends_on`/`refines`/
+//! `conflicts_with`) — embeddable into [`crate::doc::html`] (inline
+//! `<pre>`/an `.svg` rendered by a Graphviz toolchain) or
+//! [`crate::doc::mdbook`] (a fenced `dot`/`mermaid` code block, which
+//! mdBook's `mdbook-mermaid` preprocessor renders), since a reviewer reads
+//! a lifecycle diagram far faster than the same transitions spelled out
+//! as prose.
+
+use crate::spec::{Fsm, SpecDocument, StateMachine};
+
+/// Renders `fsm` as a Graphviz `digraph`: one node per state (the initial
+/// state, `states[0]`, drawn with a double border), one edge per
+/// transition labeled with its event.
+pub fn fsm_graphviz(fsm: &Fsm) -> String {
+    let mut out = format!("digraph {} {{\n", dot_id(&fsm.name));
+    for (i, state) in fsm.states.iter().enumerate() {
+        let peripheries = if i == 0 { ", peripheries=2" } else { "" };
+        out.push_str(&format!("  {} [label=\"{}\"{peripheries}];\n", dot_id(state), escape_dot(state)));
+    }
+    for t in &fsm.transitions {
+        out.push_str(&format!(
+            "  {} -> {} [label=\"{}\"];\n",
+            dot_id(&t.from),
+            dot_id(&t.to),
+            escape_dot(&t.event),
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Renders `fsm` as a Mermaid `stateDiagram-v2`: an initial `[*] -->`
+/// transition into `states[0]`, then one line per declared transition.
+pub fn fsm_mermaid(fsm: &Fsm) -> String {
+    let mut out = String::from("stateDiagram-v2\n");
+    if let Some(initial) = fsm.states.first() {
+        out.push_str(&format!("    [*] --> {}\n", mermaid_id(initial)));
+    }
+    for t in &fsm.transitions {
+        out.push_str(&format!(
+            "    {} --> {}: {}\n",
+            mermaid_id(&t.from),
+            mermaid_id(&t.to),
+            t.event,
+        ));
+    }
+    out
+}
+
+/// Renders `sm` as a Graphviz `digraph`: a single node for the model's
+/// state (there's no discrete set of named states, just fields — see
+/// [`StateMachine`]'s docs), with one self-loop edge per transition,
+/// labeled with its name, guard, and params, since that's the only
+/// faithful picture of a model whose "state" is a set of continuous
+/// fields rather than an enum.
+pub fn state_machine_graphviz(sm: &StateMachine) -> String {
+    let node = dot_id(&sm.name);
+    let mut out = format!("digraph {node} {{\n  {node} [label=\"{}\"];\n", escape_dot(&sm.name));
+    for t in &sm.transitions {
+        out.push_str(&format!("  {node} -> {node} [label=\"{}\"];\n", escape_dot(&transition_label(t))));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Renders `sm` as a Mermaid `stateDiagram-v2`, mirroring
+/// [`state_machine_graphviz`]'s single-node-plus-self-loops shape.
+pub fn state_machine_mermaid(sm: &StateMachine) -> String {
+    let node = mermaid_id(&sm.name);
+    let mut out = format!("stateDiagram-v2\n    [*] --> {node}\n");
+    for t in &sm.transitions {
+        out.push_str(&format!("    {node} --> {node}: {}\n", transition_label(t)));
+    }
+    out
+}
+
+fn transition_label(t: &crate::spec::Transition) -> String {
+    let params = if t.params.is_empty() { String::new() } else { format!("({})", t.params.join(", ")) };
+    match &t.guard {
+        Some(guard) => format!("{}{params} [{guard}]", t.name),
+        None => format!("{}{params}", t.name),
+    }
+}
+
+/// Renders `doc`'s requirement relationship graph (`depends_on`,
+/// `refines`, `conflicts_with`) as a Graphviz `digraph`: `depends_on` and
+/// `refines` edges are directed arrows, `conflicts_with` is an undirected
+/// red edge (a conflict has no direction).
+pub fn requirement_graph_graphviz(doc: &SpecDocument) -> String {
+    let mut out = String::from("digraph requirements {\n");
+    for req in &doc.requirements {
+        out.push_str(&format!("  {} [label=\"{}: {}\"];\n", dot_id(&req.id), escape_dot(&req.id), escape_dot(&req.text)));
+    }
+    for req in &doc.requirements {
+        for dep in &req.depends_on {
+            out.push_str(&format!("  {} -> {} [label=\"depends_on\"];\n", dot_id(&req.id), dot_id(dep)));
+        }
+        for refined in &req.refines {
+            out.push_str(&format!(
+                "  {} -> {} [label=\"refines\", style=dashed];\n",
+                dot_id(&req.id),
+                dot_id(refined),
+            ));
+        }
+        for conflict in &req.conflicts_with {
+            out.push_str(&format!(
+                "  {} -> {} [label=\"conflicts_with\", color=red, dir=none];\n",
+                dot_id(&req.id),
+                dot_id(conflict),
+            ));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Renders the same graph as [`requirement_graph_graphviz`] as a Mermaid
+/// `graph TD`.
+pub fn requirement_graph_mermaid(doc: &SpecDocument) -> String {
+    let mut out = String::from("graph TD\n");
+    for req in &doc.requirements {
+        out.push_str(&format!("    {}[\"{}: {}\"]\n", mermaid_id(&req.id), req.id, req.text));
+    }
+    for req in &doc.requirements {
+        for dep in &req.depends_on {
+            out.push_str(&format!("    {} -->|depends_on| {}\n", mermaid_id(&req.id), mermaid_id(dep)));
+        }
+        for refined in &req.refines {
+            out.push_str(&format!("    {} -.->|refines| {}\n", mermaid_id(&req.id), mermaid_id(refined)));
+        }
+        for conflict in &req.conflicts_with {
+            out.push_str(&format!("    {} ---|conflicts_with| {}\n", mermaid_id(&req.id), mermaid_id(conflict)));
+        }
+    }
+    out
+}
+
+/// A DOT node id: `name` quoted, with any embedded `"` escaped, so ids
+/// containing hyphens or other DOT-unfriendly characters (e.g.
+/// `REQ-004`) don't need to be valid bare identifiers.
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", escape_dot(name))
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// A Mermaid node id: `name` with every character Mermaid doesn't allow in
+/// a bare identifier (e.g. `REQ-004`'s hyphen) replaced with `_`. Labels
+/// (not ids) carry the original text, so nothing is actually lost.
+fn mermaid_id(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{FsmTransition, Requirement, Transition};
+
+    fn fsm() -> Fsm {
+        Fsm {
+            name: "account".into(),
+            states: vec!["Open".into(), "Frozen".into(), "Closed".into()],
+            transitions: vec![
+                FsmTransition { from: "Open".into(), event: "freeze".into(), to: "Frozen".into() },
+                FsmTransition { from: "Frozen".into(), event: "close".into(), to: "Closed".into() },
+            ],
+        }
+    }
+
+    #[test]
+    fn fsm_graphviz_marks_the_initial_state_and_renders_every_transition() {
+        let dot = fsm_graphviz(&fsm());
+        assert!(dot.contains("digraph \"account\" {"));
+        assert!(dot.contains("\"Open\" [label=\"Open\", peripheries=2];"));
+        assert!(dot.contains("\"Open\" -> \"Frozen\" [label=\"freeze\"];"));
+        assert!(dot.contains("\"Frozen\" -> \"Closed\" [label=\"close\"];"));
+    }
+
+    #[test]
+    fn fsm_mermaid_starts_at_the_initial_state() {
+        let mermaid = fsm_mermaid(&fsm());
+        assert!(mermaid.contains("stateDiagram-v2"));
+        assert!(mermaid.contains("[*] --> Open"));
+        assert!(mermaid.contains("Open --> Frozen: freeze"));
+    }
+
+    fn state_machine() -> StateMachine {
+        StateMachine {
+            name: "Account".into(),
+            state: [("balance".to_string(), 0.0)].into(),
+            transitions: vec![Transition {
+                name: "withdraw".into(),
+                params: vec!["amount".into()],
+                guard: Some("amount <= balance".into()),
+                violates: Some("CONST-002".into()),
+                effect: [("balance".to_string(), "balance - amount".to_string())].into(),
+            }],
+        }
+    }
+
+    #[test]
+    fn state_machine_graphviz_renders_a_self_loop_per_transition() {
+        let dot = state_machine_graphviz(&state_machine());
+        assert!(dot.contains("digraph \"Account\" {"));
+        assert!(dot.contains("\"Account\" -> \"Account\" [label=\"withdraw(amount) [amount <= balance]\"];"));
+    }
+
+    #[test]
+    fn state_machine_mermaid_renders_a_self_loop_per_transition() {
+        let mermaid = state_machine_mermaid(&state_machine());
+        assert!(mermaid.contains("Account --> Account: withdraw(amount) [amount <= balance]"));
+    }
+
+    fn doc() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            depends_on: vec!["REQ-001".into()],
+            conflicts_with: vec!["REQ-002".into()],
+            ..Default::default()
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-001".into(),
+            text: "deposit() adds to the balance".into(),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn requirement_graph_graphviz_renders_a_depends_on_edge() {
+        let dot = requirement_graph_graphviz(&doc());
+        assert!(dot.contains("\"REQ-004\" -> \"REQ-001\" [label=\"depends_on\"];"));
+    }
+
+    #[test]
+    fn requirement_graph_graphviz_renders_an_undirected_red_conflict_edge() {
+        let dot = requirement_graph_graphviz(&doc());
+        assert!(dot.contains("\"REQ-004\" -> \"REQ-002\" [label=\"conflicts_with\", color=red, dir=none];"));
+    }
+
+    #[test]
+    fn requirement_graph_mermaid_sanitizes_hyphenated_ids_into_node_ids() {
+        let mermaid = requirement_graph_mermaid(&doc());
+        assert!(mermaid.contains("REQ_004[\"REQ-004: balance() returns the current balance\"]"));
+        assert!(mermaid.contains("REQ_004 -->|depends_on| REQ_001"));
+    }
+}
@@ -0,0 +1,291 @@
+//! Renders a [`SpecDocument`] and its [`CoverageMatrix`] as a cross-linked
+//! static HTML site: an `index.html` overview, one page per requirement,
+//! a constraint index, and a coverage status page, all linking to each
+//! other and (optionally) out to source via a caller-supplied link
+//! template. Same plain string-building convention as
+//! [`crate::trace::dashboard_html`] and [`crate::trace::CoverageMatrix::to_html_report`]
+//! — no external templating engine — just spread across several pages
+//! instead of one.
+
+use crate::spec::SpecDocument;
+use crate::trace::CoverageMatrix;
+
+/// One rendered page of a [`Site`]: its path relative to the site root
+/// (e.g. `"requirements/REQ-004.html"`), and its HTML contents.
+pub struct Page {
+    pub path: String,
+    pub html: String,
+}
+
+/// A generated static site. Write each [`Page`] in [`Site::pages`] to
+/// disk at its `path` (relative to wherever the site should be rooted)
+/// to produce a browsable, cross-linked documentation set.
+pub struct Site {
+    pub pages: Vec<Page>,
+}
+
+/// How to link a covering test/implementation function name (from a
+/// [`CoverageMatrix`] row) back to its source. `{function}` in the
+/// template is replaced with the function's name, e.g.
+/// `"https://github.com/org/repo/blob/main/src/lib.rs#:~:text={function}"`.
+/// Without a template, function names render as plain text.
+#[derive(Default)]
+pub struct SiteOptions<'a> {
+    pub source_link_template: Option<&'a str>,
+}
+
+/// Renders `doc`/`matrix` (built from the same document — see
+/// [`CoverageMatrix::build`]) as a [`Site`]: an `index.html` linking every
+/// requirement plus the constraint index and coverage page, one
+/// `requirements/{id}.html` per requirement cross-linking its
+/// `depends_on`/`refines`/`conflicts_with`/`replaced_by` ids and listing
+/// its covering tests/implementations, a `constraints.html` index, and a
+/// `coverage.html` summarizing tested vs. untested requirements.
+pub fn generate(doc: &SpecDocument, matrix: &CoverageMatrix, options: &SiteOptions) -> Site {
+    let mut pages = vec![
+        Page { path: "index.html".to_string(), html: index_page(doc) },
+        Page { path: "constraints.html".to_string(), html: constraints_page(doc) },
+        Page { path: "coverage.html".to_string(), html: coverage_page(matrix, options) },
+    ];
+    for req in &doc.requirements {
+        let row = matrix.rows.iter().find(|r| r.requirement == req.id);
+        pages.push(Page {
+            path: format!("requirements/{}.html", req.id),
+            html: requirement_page(req, row, options),
+        });
+    }
+    Site { pages }
+}
+
+fn page_shell(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n\
+         body {{ font-family: sans-serif; margin: 2rem; }}\n\
+         table {{ border-collapse: collapse; width: 100%; }}\n\
+         th, td {{ border: 1px solid #ccc; padding: 0.25rem 0.5rem; text-align: left; }}\n\
+         </style>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+        escape_html(title),
+        body,
+    )
+}
+
+fn index_page(doc: &SpecDocument) -> String {
+    let mut body = String::from("<h1>Spec documentation</h1>\n<ul>\n");
+    for req in &doc.requirements {
+        body.push_str(&format!(
+            "  <li><a href=\"requirements/{}.html\">{}</a>: {}</li>\n",
+            escape_html(&req.id),
+            escape_html(&req.id),
+            escape_html(&req.text),
+        ));
+    }
+    body.push_str("</ul>\n<p>\n  <a href=\"constraints.html\">Constraints</a> &middot; <a href=\"coverage.html\">Coverage</a>\n</p>\n");
+    page_shell("Spec documentation", &body)
+}
+
+fn requirement_cross_links(label: &str, ids: &[String]) -> String {
+    if ids.is_empty() {
+        return String::new();
+    }
+    let links = ids
+        .iter()
+        .map(|id| format!("<a href=\"{}.html\">{}</a>", escape_html(id), escape_html(id)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("<p><strong>{label}:</strong> {links}</p>\n")
+}
+
+fn requirement_page(
+    req: &crate::spec::Requirement,
+    row: Option<&crate::trace::CoverageRow>,
+    options: &SiteOptions,
+) -> String {
+    let mut body = format!(
+        "<p><a href=\"../index.html\">&larr; Index</a></p>\n<h1>{}</h1>\n<p>{}</p>\n",
+        escape_html(&req.id),
+        escape_html(&req.text),
+    );
+    body.push_str(&format!(
+        "<p><strong>Status:</strong> {:?} &middot; <strong>Priority:</strong> {:?}</p>\n",
+        req.status, req.priority,
+    ));
+    if let Some(owner) = &req.owner {
+        body.push_str(&format!("<p><strong>Owner:</strong> {}</p>\n", escape_html(owner)));
+    }
+    if !req.tags.is_empty() {
+        body.push_str(&format!("<p><strong>Tags:</strong> {}</p>\n", escape_html(&req.tags.join(", "))));
+    }
+    body.push_str(&requirement_cross_links("Depends on", &req.depends_on));
+    body.push_str(&requirement_cross_links("Refines", &req.refines));
+    body.push_str(&requirement_cross_links("Conflicts with", &req.conflicts_with));
+    if let Some(replaced_by) = &req.replaced_by {
+        body.push_str(&requirement_cross_links("Replaced by", std::slice::from_ref(replaced_by)));
+    }
+
+    if let Some(row) = row {
+        body.push_str(&format!("<h2>Tests</h2>\n{}\n", source_link_list(&row.tests, options)));
+        body.push_str(&format!(
+            "<h2>Implementations</h2>\n{}\n",
+            source_link_list(&row.implementations, options)
+        ));
+    }
+
+    page_shell(&req.id, &body)
+}
+
+fn source_link_list(functions: &[String], options: &SiteOptions) -> String {
+    if functions.is_empty() {
+        return "<p>(none)</p>\n".to_string();
+    }
+    let mut out = String::from("<ul>\n");
+    for function in functions {
+        let entry = match options.source_link_template {
+            Some(template) => format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(&template.replace("{function}", function)),
+                escape_html(function),
+            ),
+            None => escape_html(function),
+        };
+        out.push_str(&format!("  <li>{entry}</li>\n"));
+    }
+    out.push_str("</ul>\n");
+    out
+}
+
+fn constraints_page(doc: &SpecDocument) -> String {
+    let mut body = String::from(
+        "<p><a href=\"index.html\">&larr; Index</a></p>\n<h1>Constraints</h1>\n\
+         <table>\n  <tr><th>Code</th><th>Text</th><th>Severity</th></tr>\n",
+    );
+    for constraint in &doc.constraints {
+        body.push_str(&format!(
+            "  <tr><td>{}</td><td>{}</td><td>{:?}</td></tr>\n",
+            escape_html(&constraint.code),
+            escape_html(&constraint.text),
+            constraint.severity,
+        ));
+    }
+    body.push_str("</table>\n");
+    page_shell("Constraints", &body)
+}
+
+fn coverage_page(matrix: &CoverageMatrix, options: &SiteOptions) -> String {
+    let mut body = String::from(
+        "<p><a href=\"index.html\">&larr; Index</a></p>\n<h1>Coverage</h1>\n\
+         <table>\n  <tr><th>Requirement</th><th>Status</th><th>Tests</th></tr>\n",
+    );
+    for row in &matrix.rows {
+        let status = if row.tests.is_empty() { "untested" } else { "tested" };
+        body.push_str(&format!(
+            "  <tr><td><a href=\"requirements/{}.html\">{}</a></td><td>{status}</td><td>{}</td></tr>\n",
+            escape_html(&row.requirement),
+            escape_html(&row.requirement),
+            source_link_list(&row.tests, options),
+        ));
+    }
+    body.push_str("</table>\n");
+    page_shell("Coverage", &body)
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, Requirement};
+    use crate::trace::Record;
+
+    fn doc() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            depends_on: vec!["REQ-001".into()],
+            ..Default::default()
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-001".into(),
+            text: "deposit() adds to the balance".into(),
+            ..Default::default()
+        });
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn generates_an_index_a_constraints_page_a_coverage_page_and_one_page_per_requirement() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let site = generate(&doc, &matrix, &SiteOptions::default());
+
+        let paths: Vec<&str> = site.pages.iter().map(|p| p.path.as_str()).collect();
+        assert!(paths.contains(&"index.html"));
+        assert!(paths.contains(&"constraints.html"));
+        assert!(paths.contains(&"coverage.html"));
+        assert!(paths.contains(&"requirements/REQ-004.html"));
+        assert!(paths.contains(&"requirements/REQ-001.html"));
+    }
+
+    #[test]
+    fn index_links_to_every_requirement_page() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let site = generate(&doc, &matrix, &SiteOptions::default());
+        let index = &site.pages.iter().find(|p| p.path == "index.html").unwrap().html;
+        assert!(index.contains("href=\"requirements/REQ-004.html\""));
+        assert!(index.contains("href=\"requirements/REQ-001.html\""));
+    }
+
+    #[test]
+    fn requirement_page_cross_links_depends_on() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let site = generate(&doc, &matrix, &SiteOptions::default());
+        let page = &site.pages.iter().find(|p| p.path == "requirements/REQ-004.html").unwrap().html;
+        assert!(page.contains("Depends on"));
+        assert!(page.contains("href=\"REQ-001.html\""));
+    }
+
+    #[test]
+    fn requirement_page_renders_tests_as_source_links_when_a_template_is_given() {
+        let doc = doc();
+        let records = vec![Record {
+            kind: "covers".to_string(),
+            function: "it_returns_balance".to_string(),
+            requirement: "REQ-004".to_string(),
+        }];
+        let matrix = CoverageMatrix::build(&doc, &records);
+        let options = SiteOptions {
+            source_link_template: Some("https://github.com/org/repo/blob/main/src/lib.rs#:~:text={function}"),
+        };
+        let site = generate(&doc, &matrix, &options);
+        let page = &site.pages.iter().find(|p| p.path == "requirements/REQ-004.html").unwrap().html;
+        assert!(page.contains("https://github.com/org/repo/blob/main/src/lib.rs#:~:text=it_returns_balance"));
+    }
+
+    #[test]
+    fn coverage_page_flags_untested_requirements() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let site = generate(&doc, &matrix, &SiteOptions::default());
+        let page = &site.pages.iter().find(|p| p.path == "coverage.html").unwrap().html;
+        assert!(page.contains("untested"));
+    }
+
+    #[test]
+    fn constraints_page_lists_every_constraint() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let site = generate(&doc, &matrix, &SiteOptions::default());
+        let page = &site.pages.iter().find(|p| p.path == "constraints.html").unwrap().html;
+        assert!(page.contains("CONST-001"));
+        assert!(page.contains("amount must be positive"));
+    }
+}
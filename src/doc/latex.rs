@@ -0,0 +1,291 @@
+//! Renders a [`SpecDocument`] (plus its [`CoverageMatrix`] and an optional
+//! diff-since-baseline) as a single LaTeX document — a `Requirements`
+//! section, a `Constraints` table, a `Coverage` table, and (if a diff is
+//! given) a trailing `Traceability Appendix` of what changed — for audits
+//! that want a printable PDF instead of the browsable site
+//! [`crate::doc::html`] generates. Like `html`, this is plain
+//! string-building, not a templating engine.
+//!
+//! Each requirement gets a starred (unnumbered) `\section*` so its
+//! position in the document can't shift LaTeX's own section counter: the
+//! id itself — printed as the section's heading and used as its
+//! `\label` — is the stable "section number" a reviewer cites, unaffected
+//! by requirements being added or reordered elsewhere in the spec.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::diff::SpecDiff;
+use crate::spec::SpecDocument;
+use crate::trace::CoverageMatrix;
+
+const PREAMBLE: &str = "\\documentclass{article}\n\
+\\usepackage[utf8]{inputenc}\n\
+\\usepackage{hyperref}\n\
+\\usepackage{longtable}\n\
+\\title{Specification}\n\
+\\date{}\n\
+";
+
+/// Renders `doc`/`matrix` (built from the same document, see
+/// [`CoverageMatrix::build`]) as a complete LaTeX document. `diff`, if
+/// given, is rendered as a trailing traceability appendix (see
+/// [`traceability_appendix`]); pass `None` when there's no prior baseline
+/// to compare against.
+pub fn generate(doc: &SpecDocument, matrix: &CoverageMatrix, diff: Option<&SpecDiff>) -> String {
+    let mut out = String::from(PREAMBLE);
+    out.push_str("\\begin{document}\n\\maketitle\n\\tableofcontents\n\n");
+    out.push_str(&requirements_section(doc));
+    out.push_str(&constraints_section(doc));
+    out.push_str(&coverage_section(matrix));
+    if let Some(diff) = diff {
+        out.push_str(&traceability_appendix(diff));
+    }
+    out.push_str("\\end{document}\n");
+    out
+}
+
+fn requirements_section(doc: &SpecDocument) -> String {
+    let mut out = String::from("\\section*{Requirements}\n\\addcontentsline{toc}{section}{Requirements}\n\n");
+    for req in &doc.requirements {
+        out.push_str(&format!(
+            "\\subsection*{{{} --- {}}}\n\\addcontentsline{{toc}}{{subsection}}{{{}}}\n\\label{{req:{}}}\n",
+            escape_latex(&req.id),
+            escape_latex(&req.text),
+            escape_latex(&req.id),
+            escape_latex(&req.id),
+        ));
+        out.push_str(&format!(
+            "Status: {:?} \\quad Priority: {:?}\n\n",
+            req.status, req.priority
+        ));
+    }
+    out
+}
+
+fn constraints_section(doc: &SpecDocument) -> String {
+    let mut out = String::from(
+        "\\section*{Constraints}\n\\addcontentsline{toc}{section}{Constraints}\n\n\
+         \\begin{longtable}{lll}\nCode & Text & Severity \\\\\n\\hline\n",
+    );
+    for c in &doc.constraints {
+        out.push_str(&format!(
+            "{} & {} & {:?} \\\\\n",
+            escape_latex(&c.code),
+            escape_latex(&c.text),
+            c.severity,
+        ));
+    }
+    out.push_str("\\end{longtable}\n\n");
+    out
+}
+
+fn coverage_section(matrix: &CoverageMatrix) -> String {
+    let mut out = String::from(
+        "\\section*{Coverage}\n\\addcontentsline{toc}{section}{Coverage}\n\n\
+         \\begin{longtable}{ll}\nRequirement & Status \\\\\n\\hline\n",
+    );
+    for row in &matrix.rows {
+        let status = if row.tests.is_empty() { "untested" } else { "tested" };
+        out.push_str(&format!("{} & {status} \\\\\n", escape_latex(&row.requirement)));
+    }
+    out.push_str("\\end{longtable}\n\n");
+    out
+}
+
+/// Renders `diff` as a trailing `Traceability Appendix` section: one line
+/// per added/removed/modified requirement and constraint since the spec
+/// this diff was built against — the same events
+/// [`crate::changelog::render_markdown`] renders as Markdown, here as
+/// LaTeX for the printed audit trail.
+fn traceability_appendix(diff: &SpecDiff) -> String {
+    let mut out =
+        String::from("\\section*{Traceability Appendix}\n\\addcontentsline{toc}{section}{Traceability Appendix}\n\n");
+    if diff.is_empty() {
+        out.push_str("No changes since the baseline.\n\n");
+        return out;
+    }
+    out.push_str("\\begin{itemize}\n");
+    for req in &diff.added_requirements {
+        out.push_str(&format!("\\item Added {}: {}\n", escape_latex(&req.id), escape_latex(&req.text)));
+    }
+    for req in &diff.removed_requirements {
+        out.push_str(&format!("\\item Removed {}: {}\n", escape_latex(&req.id), escape_latex(&req.text)));
+    }
+    for change in &diff.modified_requirements {
+        out.push_str(&format!("\\item Modified {}: {}\n", escape_latex(&change.id), escape_latex(&change.after.text)));
+    }
+    for supersession in &diff.superseded_requirements {
+        out.push_str(&format!(
+            "\\item Renamed {} to {}\n",
+            escape_latex(&supersession.old_id),
+            escape_latex(&supersession.new_id),
+        ));
+    }
+    for c in &diff.added_constraints {
+        out.push_str(&format!("\\item Added {}: {}\n", escape_latex(&c.code), escape_latex(&c.text)));
+    }
+    for c in &diff.removed_constraints {
+        out.push_str(&format!("\\item Removed {}: {}\n", escape_latex(&c.code), escape_latex(&c.text)));
+    }
+    for change in &diff.modified_constraints {
+        out.push_str(&format!(
+            "\\item Modified {}: {}\n",
+            escape_latex(&change.code),
+            escape_latex(&change.after.text),
+        ));
+    }
+    out.push_str("\\end{itemize}\n\n");
+    out
+}
+
+/// Escapes LaTeX's special characters so requirement/constraint text
+/// renders literally instead of breaking the document or being
+/// interpreted as markup.
+fn escape_latex(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '\\' => out.push_str("\\textbackslash{}"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// A LaTeX engine invocation failed.
+#[derive(Debug)]
+pub enum LatexBuildError {
+    Io(std::io::Error),
+    EngineFailed { status: std::process::ExitStatus, stderr: String },
+}
+
+impl fmt::Display for LatexBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LatexBuildError::Io(e) => write!(f, "failed to run the LaTeX engine: {e}"),
+            LatexBuildError::EngineFailed { status, stderr } => {
+                write!(f, "LaTeX engine exited with {status}: {}", stderr.trim())
+            }
+        }
+    }
+}
+
+impl std::error::Error for LatexBuildError {}
+
+/// Writes `tex` to `{out_dir}/{name}.tex` and runs `engine` (e.g.
+/// `"pdflatex"`) against it twice — LaTeX needs a second pass to resolve
+/// the table of contents and cross-references — returning the path to the
+/// resulting PDF. Requires `engine` to be installed and on `PATH`; callers
+/// that only want the `.tex` source (e.g. to check it into the repo
+/// alongside the spec) can skip this and use [`generate`]'s return value
+/// directly.
+pub fn build_pdf(tex: &str, out_dir: &Path, name: &str, engine: &str) -> Result<PathBuf, LatexBuildError> {
+    std::fs::create_dir_all(out_dir).map_err(LatexBuildError::Io)?;
+    let tex_path = out_dir.join(format!("{name}.tex"));
+    std::fs::write(&tex_path, tex).map_err(LatexBuildError::Io)?;
+
+    for _ in 0..2 {
+        let output = Command::new(engine)
+            .arg("-interaction=nonstopmode")
+            .arg("-output-directory")
+            .arg(out_dir)
+            .arg(&tex_path)
+            .output()
+            .map_err(LatexBuildError::Io)?;
+        if !output.status.success() {
+            return Err(LatexBuildError::EngineFailed {
+                status: output.status,
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            });
+        }
+    }
+
+    Ok(out_dir.join(format!("{name}.pdf")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, Requirement};
+
+    fn doc() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn renders_a_section_per_requirement_labeled_by_its_own_id() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let generated = generate(&doc, &matrix, None);
+        assert!(generated.contains("\\subsection*{REQ-004 --- balance() returns the current balance}"));
+        assert!(generated.contains("\\label{req:REQ-004}"));
+    }
+
+    #[test]
+    fn renders_a_constraint_table_row() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let generated = generate(&doc, &matrix, None);
+        assert!(generated.contains("CONST-001 & amount must be positive & Error \\\\"));
+    }
+
+    #[test]
+    fn renders_a_coverage_table_row() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let generated = generate(&doc, &matrix, None);
+        assert!(generated.contains("REQ-004 & untested \\\\"));
+    }
+
+    #[test]
+    fn omits_the_traceability_appendix_without_a_diff() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let generated = generate(&doc, &matrix, None);
+        assert!(!generated.contains("Traceability Appendix"));
+    }
+
+    #[test]
+    fn renders_the_traceability_appendix_when_a_diff_is_given() {
+        let before = SpecDocument::new();
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let diff = crate::diff::diff(&before, &doc);
+        let generated = generate(&doc, &matrix, Some(&diff));
+        assert!(generated.contains("Traceability Appendix"));
+        assert!(generated.contains("\\item Added REQ-004: balance() returns the current balance"));
+    }
+
+    #[test]
+    fn escapes_special_latex_characters() {
+        assert_eq!(escape_latex("50% & $cost_1"), "50\\% \\& \\$cost\\_1");
+    }
+
+    #[test]
+    fn build_pdf_reports_an_io_error_for_a_missing_engine() {
+        let out_dir = std::env::temp_dir().join(format!("libspec-latex-test-{}", std::process::id()));
+        let err = build_pdf("\\documentclass{article}", &out_dir, "spec", "pdflatex-does-not-exist-xyz")
+            .expect_err("a nonexistent engine should fail to spawn");
+        assert!(matches!(err, LatexBuildError::Io(_)));
+        let _ = std::fs::remove_dir_all(&out_dir);
+    }
+}
@@ -0,0 +1,197 @@
+//! Generates an mdBook source tree from a [`SpecDocument`] and its
+//! [`CoverageMatrix`]: a `book.toml`, a `src/SUMMARY.md` with one chapter
+//! per requirement group (grouped by each requirement's first tag, same
+//! grouping [`crate::changelog::render_markdown`] uses), one page per
+//! requirement inside its chapter, and a per-chapter coverage badge
+//! (reusing [`crate::trace::badge_svg`]) embedded on the chapter's
+//! index page — so a team already publishing an mdBook can include their
+//! spec by writing every [`BookFile`] to its `path` and running `mdbook
+//! build`, with no hand-written glue.
+
+use std::collections::BTreeMap;
+
+use crate::spec::{Requirement, SpecDocument};
+use crate::trace::{badge_svg, BadgeThresholds, CoverageMatrix, CoverageRow};
+
+/// The chapter a tagless requirement's page falls under.
+const UNTAGGED: &str = "General";
+
+/// One file in the generated book, `path` relative to the book's root
+/// (e.g. `"src/SUMMARY.md"`, `"book.toml"`).
+pub struct BookFile {
+    pub path: String,
+    pub contents: String,
+}
+
+/// A generated mdBook source tree.
+pub struct Book {
+    pub files: Vec<BookFile>,
+}
+
+/// Generates `title`'s book from `doc`/`matrix` (built from the same
+/// document, see [`CoverageMatrix::build`]): `book.toml`, `src/SUMMARY.md`
+/// linking every chapter, one `src/{chapter}/README.md` chapter index with
+/// an embedded coverage badge and a page listing, and one
+/// `src/{chapter}/{id}.md` per requirement.
+pub fn generate(doc: &SpecDocument, matrix: &CoverageMatrix, title: &str) -> Book {
+    let mut files = vec![BookFile { path: "book.toml".to_string(), contents: book_toml(title) }];
+
+    let mut chapters: BTreeMap<&str, Vec<&Requirement>> = BTreeMap::new();
+    for req in &doc.requirements {
+        chapters.entry(area(req)).or_default().push(req);
+    }
+
+    let mut summary = String::from("# Summary\n\n");
+    for (chapter, reqs) in &chapters {
+        let slug = slugify(chapter);
+        let rows: Vec<CoverageRow> = matrix
+            .rows
+            .iter()
+            .filter(|row| reqs.iter().any(|req| req.id == row.requirement))
+            .cloned()
+            .collect();
+        let chapter_matrix = CoverageMatrix { rows };
+
+        files.push(BookFile {
+            path: format!("src/{slug}/coverage.svg"),
+            contents: badge_svg(&chapter_matrix, &BadgeThresholds::default()),
+        });
+
+        summary.push_str(&format!("- [{chapter}](./{slug}/README.md)\n"));
+        let mut index = format!("# {chapter}\n\n![coverage](coverage.svg)\n\n");
+        for req in reqs {
+            summary.push_str(&format!("  - [{}](./{slug}/{}.md)\n", req.id, req.id));
+            index.push_str(&format!("- [{}](./{}.md): {}\n", req.id, req.id, req.text));
+            let row = chapter_matrix.rows.iter().find(|row| row.requirement == req.id);
+            files.push(BookFile {
+                path: format!("src/{slug}/{}.md", req.id),
+                contents: requirement_page(req, row),
+            });
+        }
+        files.push(BookFile { path: format!("src/{slug}/README.md"), contents: index });
+    }
+
+    files.push(BookFile { path: "src/SUMMARY.md".to_string(), contents: summary });
+    Book { files }
+}
+
+fn area(req: &Requirement) -> &str {
+    req.tags.first().map(String::as_str).unwrap_or(UNTAGGED)
+}
+
+fn slugify(s: &str) -> String {
+    s.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect()
+}
+
+fn book_toml(title: &str) -> String {
+    format!("[book]\ntitle = \"{title}\"\nsrc = \"src\"\n")
+}
+
+fn requirement_page(req: &Requirement, row: Option<&CoverageRow>) -> String {
+    let mut page = format!("# {}\n\n{}\n\n", req.id, req.text);
+    page.push_str(&format!("- Status: {:?}\n- Priority: {:?}\n", req.status, req.priority));
+    if let Some(owner) = &req.owner {
+        page.push_str(&format!("- Owner: {owner}\n"));
+    }
+    if let Some(row) = row {
+        page.push_str("\n## Tests\n\n");
+        if row.tests.is_empty() {
+            page.push_str("(none)\n");
+        } else {
+            for test in &row.tests {
+                page.push_str(&format!("- `{test}`\n"));
+            }
+        }
+    }
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    fn doc() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            tags: vec!["accounts".into()],
+            ..Default::default()
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-001".into(),
+            text: "no tags here".into(),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn generates_a_book_toml_and_a_summary() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let book = generate(&doc, &matrix, "Bank Spec");
+
+        let paths: Vec<&str> = book.files.iter().map(|f| f.path.as_str()).collect();
+        assert!(paths.contains(&"book.toml"));
+        assert!(paths.contains(&"src/SUMMARY.md"));
+        let book_toml = &book.files.iter().find(|f| f.path == "book.toml").unwrap().contents;
+        assert!(book_toml.contains("title = \"Bank Spec\""));
+    }
+
+    #[test]
+    fn groups_requirements_into_chapters_by_first_tag() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let book = generate(&doc, &matrix, "Bank Spec");
+
+        let paths: Vec<&str> = book.files.iter().map(|f| f.path.as_str()).collect();
+        assert!(paths.contains(&"src/accounts/REQ-004.md"));
+        assert!(paths.contains(&"src/general/REQ-001.md"));
+    }
+
+    #[test]
+    fn summary_links_every_chapter_and_page() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let book = generate(&doc, &matrix, "Bank Spec");
+
+        let summary = &book.files.iter().find(|f| f.path == "src/SUMMARY.md").unwrap().contents;
+        assert!(summary.contains("[accounts](./accounts/README.md)"));
+        assert!(summary.contains("[REQ-004](./accounts/REQ-004.md)"));
+    }
+
+    #[test]
+    fn chapter_index_embeds_a_coverage_badge() {
+        let doc = doc();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let book = generate(&doc, &matrix, "Bank Spec");
+
+        let index = &book.files.iter().find(|f| f.path == "src/accounts/README.md").unwrap().contents;
+        assert!(index.contains("![coverage](coverage.svg)"));
+        assert!(book.files.iter().any(|f| f.path == "src/accounts/coverage.svg"));
+    }
+
+    #[test]
+    fn requirement_page_lists_its_tests() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+        let records = vec![crate::trace::Record {
+            kind: "covers".to_string(),
+            function: "it_returns_balance".to_string(),
+            requirement: "REQ-004".to_string(),
+        }];
+        let matrix = CoverageMatrix::build(&doc, &records);
+        let book = generate(&doc, &matrix, "Bank Spec");
+
+        let page = &book.files.iter().find(|f| f.path == "src/general/REQ-004.md").unwrap().contents;
+        assert!(page.contains("it_returns_balance"));
+    }
+}
@@ -0,0 +1,9 @@
+//! Renders a [`crate::spec::SpecDocument`] as browsable documentation,
+//! so a spec's requirements and constraints live as a cross-linked site a
+//! reviewer can click through rather than only surfacing in error
+//! strings and trace artifacts.
+
+pub mod diagram;
+pub mod html;
+pub mod latex;
+pub mod mdbook;
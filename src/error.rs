@@ -0,0 +1,100 @@
+//! A structured error type for generated code, replacing ad-hoc
+//! `Result<T, String>` returns with something callers can match on without
+//! parsing a message.
+//!
+//! [`SpecError`] and [`MessageCatalog`] live in the `libspec-core` crate
+//! and are re-exported here unchanged: `libspec-core` is `no_std + alloc`,
+//! so an embedded implementation of a spec can depend on it alone and
+//! return the same typed errors a hosted Rust backend generated from the
+//! same spec would, without pulling in everything else `libspec` needs to
+//! parse, validate, and generate code from a spec on a host.
+//!
+//! [`SpecError`]'s [`Display`](std::fmt::Display) renders as
+//! `"{code}: {message}"`, the same format generated and hand-written code
+//! has historically built by hand (e.g.
+//! `format!("{}: {}", constraint.code, constraint.text)`), so swapping a
+//! `String` return for a `SpecError` one doesn't change what callers see
+//! when they print the error.
+//!
+//! ## Wire format
+//!
+//! [`SpecError`] derives `serde::Serialize`/`Deserialize` directly off its
+//! fields, so it serializes as:
+//!
+//! ```json
+//! { "code": "CONST-002", "requirement": "REQ-004", "message": "insufficient funds", "details": null }
+//! ```
+//!
+//! This is the one shape every backend's generated error type should
+//! produce, so a REST client sees the same JSON error whether the service
+//! behind it is the Rust backend or a Python/Go one generated from the
+//! same spec. Generated per-constraint error enums (see
+//! [`crate::codegen::rust_error`] and [`crate::codegen::rust_dto`])
+//! convert to a `SpecError` to serialize, rather than deriving `Serialize`
+//! on the enum itself, so their JSON matches this shape instead of
+//! serde's default enum representation.
+
+pub use libspec_core::{MessageCatalog, SpecError};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_renders_code_and_message_only() {
+        let err = SpecError::new("CONST-001", "insufficient funds")
+            .with_requirement("REQ-004")
+            .with_details("available=10, requested=50");
+        assert_eq!(err.to_string(), "CONST-001: insufficient funds");
+    }
+
+    #[test]
+    fn builder_methods_set_optional_fields() {
+        let err = SpecError::new("CONST-002", "account locked").with_requirement("REQ-009");
+        assert_eq!(err.requirement, Some("REQ-009".to_string()));
+        assert_eq!(err.details, None);
+    }
+
+    #[test]
+    fn localized_message_uses_the_catalog_entry_for_the_locale() {
+        let err = SpecError::new("CONST-001", "insufficient funds");
+        let mut catalog = MessageCatalog::new();
+        catalog.insert("fr", "CONST-001", "fonds insuffisants");
+
+        assert_eq!(err.localized_message(&catalog, "fr"), "fonds insuffisants");
+    }
+
+    #[test]
+    fn localized_message_falls_back_when_locale_or_code_is_missing() {
+        let err = SpecError::new("CONST-001", "insufficient funds");
+        let mut catalog = MessageCatalog::new();
+        catalog.insert("fr", "CONST-002", "fonds insuffisants");
+
+        assert_eq!(err.localized_message(&catalog, "fr"), "insufficient funds");
+        assert_eq!(err.localized_message(&catalog, "de"), "insufficient funds");
+    }
+
+    #[test]
+    fn serializes_to_the_documented_wire_format() {
+        let err = SpecError::new("CONST-002", "insufficient funds").with_requirement("REQ-004");
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "code": "CONST-002",
+                "requirement": "REQ-004",
+                "message": "insufficient funds",
+                "details": null,
+            })
+        );
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let err = SpecError::new("CONST-002", "insufficient funds")
+            .with_requirement("REQ-004")
+            .with_details("available=10, requested=50");
+        let json = serde_json::to_string(&err).unwrap();
+        assert_eq!(serde_json::from_str::<SpecError>(&json).unwrap(), err);
+    }
+}
@@ -0,0 +1,227 @@
+//! An on-disk index of requirement ids defined by *another* repository's
+//! spec (a shared platform spec several services `depends_on`/`refines`,
+//! say), so a `namespace:REQ-004`-style reference resolves to real text
+//! instead of looking like a [`crate::graph::DanglingReference`]. This
+//! crate has no HTTP client, so "fetching" an index means reading
+//! another spec file already reachable on disk — a sibling checkout, a
+//! mounted volume, a CI artifact fetched by some other tool — the same
+//! way [`crate::include_sync`] treats `root` as just another path, not a
+//! URL.
+//!
+//! One [`ExternalIndex`] is cached per namespace as a JSON file, so
+//! resolving a cross-repo reference doesn't require the other repo to be
+//! checked out at lookup time, only as of the last [`fetch`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::spec::SpecDocument;
+
+/// A `namespace:id`-qualified reference, e.g. `"platform:REQ-004"`
+/// naming requirement `REQ-004` in the `platform` namespace's
+/// [`ExternalIndex`]. References with no `:` aren't qualified — they name
+/// a requirement in the local spec, not an external one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QualifiedRef<'a> {
+    pub namespace: &'a str,
+    pub id: &'a str,
+}
+
+/// Splits `reference` into a namespace and id on its first `:`, or
+/// `None` if it has no `:` at all.
+pub fn parse_ref(reference: &str) -> Option<QualifiedRef<'_>> {
+    let (namespace, id) = reference.split_once(':')?;
+    if namespace.is_empty() || id.is_empty() {
+        return None;
+    }
+    Some(QualifiedRef { namespace, id })
+}
+
+/// One namespace's worth of requirement ids and text, as last fetched
+/// from wherever that namespace's spec actually lives.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalIndex {
+    pub namespace: String,
+    /// Where this index was last fetched from — a path, for now, since
+    /// there's no URL fetcher (see the module doc comment).
+    pub source: String,
+    /// [`SpecDocument::version_hash`] of the spec `source` held as of
+    /// this fetch, so [`is_stale`] can tell a caller whether `source`
+    /// has since moved on without re-reading every requirement's text.
+    pub spec_hash: String,
+    /// Unix timestamp (seconds) this index was fetched at.
+    pub fetched_at: u64,
+    pub requirements: BTreeMap<String, String>,
+}
+
+impl ExternalIndex {
+    /// Builds an `ExternalIndex` for `namespace` from `doc`, recording
+    /// `source` as where it came from and the current time as when.
+    pub fn from_spec(namespace: &str, source: &str, doc: &SpecDocument) -> Self {
+        ExternalIndex {
+            namespace: namespace.to_string(),
+            source: source.to_string(),
+            spec_hash: doc.version_hash(),
+            fetched_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            requirements: doc.requirements.iter().map(|r| (r.id.clone(), r.text.clone())).collect(),
+        }
+    }
+
+    /// The text of `id` within this namespace, if it's in the index.
+    pub fn requirement_text(&self, id: &str) -> Option<&str> {
+        self.requirements.get(id).map(String::as_str)
+    }
+}
+
+/// An `ExternalIndex` read back from disk that no longer matches its
+/// source spec's current [`SpecDocument::version_hash`] — the source
+/// repo has moved on since the last [`fetch`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleIndex {
+    pub namespace: String,
+    pub cached_hash: String,
+    pub current_hash: String,
+}
+
+impl fmt::Display for StaleIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "external index `{}` is stale: cached `{}`, source is now `{}`",
+            self.namespace, self.cached_hash, self.current_hash
+        )
+    }
+}
+
+impl std::error::Error for StaleIndex {}
+
+/// Where [`fetch`]/[`load`] keep one JSON file per namespace, relative to
+/// a project root — following [`crate::cache::Cache`]'s convention of
+/// living under `target/` rather than being checked in.
+pub fn index_dir(project_root: &Path) -> PathBuf {
+    project_root.join("target").join("libspec").join("external")
+}
+
+fn index_path(project_root: &Path, namespace: &str) -> PathBuf {
+    index_dir(project_root).join(format!("{namespace}.json"))
+}
+
+/// Reads `source` as a TOML spec file, builds an [`ExternalIndex`] for
+/// `namespace` from it, and writes it to `project_root`'s index
+/// directory, overwriting any index already cached for that namespace.
+pub fn fetch(project_root: &Path, namespace: &str, source: &Path) -> std::io::Result<ExternalIndex> {
+    let text = fs::read_to_string(source)?;
+    let doc = SpecDocument::from_toml_str(&text)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+    let index = ExternalIndex::from_spec(namespace, &source.to_string_lossy(), &doc);
+
+    let dir = index_dir(project_root);
+    fs::create_dir_all(&dir)?;
+    fs::write(index_path(project_root, namespace), serde_json::to_string_pretty(&index)?)?;
+    Ok(index)
+}
+
+/// Reads back the cached index for `namespace`, if one has been
+/// [`fetch`]ed before.
+pub fn load(project_root: &Path, namespace: &str) -> Option<ExternalIndex> {
+    let contents = fs::read_to_string(index_path(project_root, namespace)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Compares `index` against `source` (the same path it was originally
+/// [`fetch`]ed from) and reports whether `source`'s spec has since
+/// changed, without re-fetching.
+pub fn is_stale(index: &ExternalIndex, source: &Path) -> std::io::Result<Option<StaleIndex>> {
+    let text = fs::read_to_string(source)?;
+    let doc = SpecDocument::from_toml_str(&text)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+    let current_hash = doc.version_hash();
+    if current_hash == index.spec_hash {
+        Ok(None)
+    } else {
+        Ok(Some(StaleIndex {
+            namespace: index.namespace.clone(),
+            cached_hash: index.spec_hash.clone(),
+            current_hash,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("libspec-external-index-test-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    const SPEC: &str = "[[requirement]]\nid = \"REQ-001\"\ntext = \"does a thing\"\n";
+
+    #[test]
+    fn parse_ref_splits_on_the_first_colon() {
+        let parsed = parse_ref("platform:REQ-004").unwrap();
+        assert_eq!(parsed.namespace, "platform");
+        assert_eq!(parsed.id, "REQ-004");
+    }
+
+    #[test]
+    fn parse_ref_is_none_without_a_colon() {
+        assert!(parse_ref("REQ-004").is_none());
+    }
+
+    #[test]
+    fn fetch_then_load_round_trips() {
+        let dir = temp_dir("fetch-load");
+        let source = dir.join("platform.toml");
+        fs::write(&source, SPEC).unwrap();
+
+        let fetched = fetch(&dir, "platform", &source).unwrap();
+        let loaded = load(&dir, "platform").unwrap();
+        assert_eq!(fetched, loaded);
+        assert_eq!(loaded.requirement_text("REQ-001"), Some("does a thing"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn load_misses_without_a_prior_fetch() {
+        let dir = temp_dir("no-fetch");
+        assert!(load(&dir, "platform").is_none());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn is_stale_is_none_when_the_source_is_unchanged() {
+        let dir = temp_dir("fresh");
+        let source = dir.join("platform.toml");
+        fs::write(&source, SPEC).unwrap();
+
+        let index = fetch(&dir, "platform", &source).unwrap();
+        assert!(is_stale(&index, &source).unwrap().is_none());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn is_stale_reports_a_changed_source() {
+        let dir = temp_dir("stale");
+        let source = dir.join("platform.toml");
+        fs::write(&source, SPEC).unwrap();
+
+        let index = fetch(&dir, "platform", &source).unwrap();
+        fs::write(&source, "[[requirement]]\nid = \"REQ-002\"\ntext = \"a new thing\"\n").unwrap();
+
+        let stale = is_stale(&index, &source).unwrap().unwrap();
+        assert_eq!(stale.namespace, "platform");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
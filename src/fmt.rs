@@ -0,0 +1,354 @@
+//! Rewrites a spec's requirements and constraints into a canonical TOML
+//! layout: sorted by id/code, defaulted fields omitted instead of spelled
+//! out, one blank line between entries, and long `text` wrapped to
+//! [`WRAP_COLUMN`] columns using TOML's multi-line-string line-ending
+//! backslash (so the wrapping doesn't change the parsed value — a
+//! reformat is a no-op on [`SpecDocument::from_toml_str`]). [`check`]
+//! compares a document's current text against [`render`]'s output the way
+//! `rustfmt --check` compares a file against its own reformatting, so
+//! `cargo spec fmt --check` can fail CI on an unformatted spec without
+//! rewriting it.
+//!
+//! Only [`SpecDocument::requirements`] and [`SpecDocument::constraints`]
+//! are canonicalized — the sections spec review actually concentrates
+//! on. A document with anything else ([`SpecDocument::includes`],
+//! templates, glossary, types, locales, state machines, FSMs, or
+//! conformance vectors) is refused with [`FormatError::UnsupportedSection`]
+//! rather than silently dropped.
+
+use std::fmt;
+
+use crate::spec::{Constraint, PerfBudget, Priority, Requirement, Severity, SpecDocument, Status};
+
+/// Column long `text`/prose fields are wrapped at.
+const WRAP_COLUMN: usize = 80;
+
+/// Why [`render`] refused to canonicalize a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatError {
+    /// The document has a top-level section this formatter doesn't yet
+    /// know how to canonicalize.
+    UnsupportedSection(&'static str),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::UnsupportedSection(section) => write!(
+                f,
+                "fmt doesn't yet support the `{section}` section; rewriting would drop it"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+/// Renders `doc`'s requirements and constraints in canonical layout. See
+/// the module docs for what "canonical" means and which sections this
+/// refuses to touch.
+pub fn render(doc: &SpecDocument) -> Result<String, FormatError> {
+    check_supported(doc)?;
+
+    let mut requirements: Vec<&Requirement> = doc.requirements.iter().collect();
+    requirements.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut constraints: Vec<&Constraint> = doc.constraints.iter().collect();
+    constraints.sort_by(|a, b| a.code.cmp(&b.code));
+
+    let mut out = String::new();
+    for req in requirements {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        render_requirement(&mut out, req);
+    }
+    for constraint in constraints {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        render_constraint(&mut out, constraint);
+    }
+    Ok(out)
+}
+
+/// `true` if `original` is already `doc`'s canonical rendering.
+pub fn check(doc: &SpecDocument, original: &str) -> Result<bool, FormatError> {
+    Ok(render(doc)? == original)
+}
+
+fn check_supported(doc: &SpecDocument) -> Result<(), FormatError> {
+    if !doc.includes.is_empty() {
+        return Err(FormatError::UnsupportedSection("includes"));
+    }
+    if !doc.templates.is_empty() {
+        return Err(FormatError::UnsupportedSection("template"));
+    }
+    if !doc.glossary.is_empty() {
+        return Err(FormatError::UnsupportedSection("glossary"));
+    }
+    if !doc.data_types.is_empty() {
+        return Err(FormatError::UnsupportedSection("type"));
+    }
+    if !doc.locales.is_empty() {
+        return Err(FormatError::UnsupportedSection("locale"));
+    }
+    if !doc.state_machines.is_empty() {
+        return Err(FormatError::UnsupportedSection("state_machine"));
+    }
+    if !doc.fsms.is_empty() {
+        return Err(FormatError::UnsupportedSection("fsm"));
+    }
+    if !doc.conformance_vectors.is_empty() {
+        return Err(FormatError::UnsupportedSection("conformance_vector"));
+    }
+    if !doc.cli_contracts.is_empty() {
+        return Err(FormatError::UnsupportedSection("cli_contract"));
+    }
+    Ok(())
+}
+
+fn render_requirement(out: &mut String, req: &Requirement) {
+    out.push_str("[[requirement]]\n");
+    render_field(out, "id", &req.id);
+    render_text_field(out, "text", &req.text);
+    if req.priority != Priority::default() {
+        render_field(out, "priority", priority_str(req.priority));
+    }
+    if req.status != Status::default() {
+        render_field(out, "status", status_str(req.status));
+    }
+    if let Some(owner) = &req.owner {
+        render_field(out, "owner", owner);
+    }
+    if let Some(team) = &req.team {
+        render_field(out, "team", team);
+    }
+    render_string_list(out, "tags", &req.tags);
+    render_string_list(out, "depends_on", &req.depends_on);
+    render_string_list(out, "refines", &req.refines);
+    render_string_list(out, "conflicts_with", &req.conflicts_with);
+    if let Some(tracker) = &req.tracker {
+        render_field(out, "tracker", tracker);
+    }
+    if let Some(replaced_by) = &req.replaced_by {
+        render_field(out, "replaced_by", replaced_by);
+    }
+    if let Some(perf_budget) = &req.perf_budget {
+        render_perf_budget(out, perf_budget);
+    }
+}
+
+fn render_constraint(out: &mut String, constraint: &Constraint) {
+    out.push_str("[[constraint]]\n");
+    render_field(out, "code", &constraint.code);
+    render_text_field(out, "text", &constraint.text);
+    if let Some(expr) = &constraint.expr {
+        render_field(out, "expr", expr);
+    }
+    if constraint.severity != Severity::default() {
+        render_field(out, "severity", severity_str(constraint.severity));
+    }
+    if let Some(http_status) = constraint.http_status {
+        out.push_str(&format!("http_status = {http_status}\n"));
+    }
+}
+
+fn render_perf_budget(out: &mut String, perf_budget: &PerfBudget) {
+    out.push_str(&format!(
+        "perf_budget = {{ scale = {}, max_millis = {} }}\n",
+        perf_budget.scale, perf_budget.max_millis
+    ));
+}
+
+fn priority_str(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "low",
+        Priority::Medium => "medium",
+        Priority::High => "high",
+        Priority::Critical => "critical",
+    }
+}
+
+fn status_str(status: Status) -> &'static str {
+    match status {
+        Status::Draft => "draft",
+        Status::Approved => "approved",
+        Status::Implemented => "implemented",
+        Status::Deprecated => "deprecated",
+    }
+}
+
+fn severity_str(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Advisory => "advisory",
+    }
+}
+
+fn render_field(out: &mut String, key: &str, value: &str) {
+    out.push_str(&format!("{key} = \"{}\"\n", escape_basic_string(value)));
+}
+
+/// Renders a sorted, quoted list, or nothing if `values` is empty.
+fn render_string_list(out: &mut String, key: &str, values: &[String]) {
+    if values.is_empty() {
+        return;
+    }
+    let mut sorted: Vec<&String> = values.iter().collect();
+    sorted.sort();
+    let items = sorted
+        .iter()
+        .map(|v| format!("\"{}\"", escape_basic_string(v)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    out.push_str(&format!("{key} = [{items}]\n"));
+}
+
+/// Renders `key = "value"`, or — if `value` is longer than
+/// [`WRAP_COLUMN`] — a multi-line string wrapped at word boundaries using
+/// a line-ending backslash on every line (including the last), so the
+/// wrapping introduces no newlines or extra whitespace into the parsed
+/// value.
+fn render_text_field(out: &mut String, key: &str, value: &str) {
+    if value.chars().count() <= WRAP_COLUMN {
+        render_field(out, key, value);
+        return;
+    }
+
+    let lines = wrap(value, WRAP_COLUMN);
+    out.push_str(&format!("{key} = \"\"\"\n"));
+    for (i, line) in lines.iter().enumerate() {
+        out.push_str(&escape_basic_string(line));
+        if i + 1 < lines.len() {
+            out.push(' ');
+        }
+        out.push_str("\\\n");
+    }
+    out.push_str("\"\"\"\n");
+}
+
+/// Greedily wraps `text` at word boundaries to at most `width` columns
+/// per line.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn escape_basic_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn sorts_requirements_by_id() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-002".into(),
+            text: "second".into(),
+            ..Default::default()
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-001".into(),
+            text: "first".into(),
+            ..Default::default()
+        });
+
+        let rendered = render(&doc).unwrap();
+        assert!(rendered.find("REQ-001").unwrap() < rendered.find("REQ-002").unwrap());
+    }
+
+    #[test]
+    fn omits_defaulted_fields() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-001".into(),
+            text: "first".into(),
+            ..Default::default()
+        });
+
+        let rendered = render(&doc).unwrap();
+        assert_eq!(rendered, "[[requirement]]\nid = \"REQ-001\"\ntext = \"first\"\n");
+    }
+
+    #[test]
+    fn wraps_long_text_into_a_backslash_continued_multiline_string() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-001".into(),
+            text: "a ".repeat(60).trim().to_string(),
+            ..Default::default()
+        });
+
+        let rendered = render(&doc).unwrap();
+        assert!(rendered.contains("text = \"\"\"\n"));
+
+        let reparsed = SpecDocument::from_toml_str(&rendered).unwrap();
+        assert_eq!(reparsed.requirements[0].text, doc.requirements[0].text);
+    }
+
+    #[test]
+    fn round_trips_through_reparsing() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-001".into(),
+            text: "deposits must be rejected if the account is locked".into(),
+            priority: Priority::High,
+            status: Status::Approved,
+            tags: vec!["money".into(), "auth".into()],
+            ..Default::default()
+        });
+        doc.constraints.push(Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            severity: Severity::Warning,
+            ..Default::default()
+        });
+
+        let rendered = render(&doc).unwrap();
+        let reparsed = SpecDocument::from_toml_str(&rendered).unwrap();
+        assert_eq!(reparsed.requirements[0].text, doc.requirements[0].text);
+        assert_eq!(reparsed.requirements[0].priority, Priority::High);
+        assert_eq!(reparsed.requirements[0].status, Status::Approved);
+        assert_eq!(reparsed.requirements[0].tags, vec!["auth".to_string(), "money".to_string()]);
+        assert_eq!(reparsed.constraints[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn refuses_a_document_with_an_unsupported_section() {
+        let mut doc = SpecDocument::new();
+        doc.includes.push("other.toml".into());
+        assert_eq!(render(&doc), Err(FormatError::UnsupportedSection("includes")));
+    }
+
+    #[test]
+    fn check_reports_whether_text_is_already_canonical() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-001".into(),
+            text: "first".into(),
+            ..Default::default()
+        });
+
+        let canonical = render(&doc).unwrap();
+        assert_eq!(check(&doc, &canonical), Ok(true));
+        assert_eq!(check(&doc, "not canonical"), Ok(false));
+    }
+}
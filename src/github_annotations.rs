@@ -0,0 +1,203 @@
+//! Renders spec findings as [GitHub Actions workflow
+//! commands](https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions#setting-an-error-message)
+//! and a job-summary Markdown table, the CI-native counterpart to
+//! [`crate::sarif`]: a `::error`/`::warning` line per finding makes it show
+//! up inline on the PR diff without the repo needing to set up code
+//! scanning, and the summary table gives a one-glance total even when no
+//! finding lands on a changed line. Uses the same mention-anchoring as
+//! [`crate::sarif::report`]: a coverage-gap finding points at the first
+//! scanned mention of its id when one exists, and at the spec file
+//! otherwise.
+
+use std::path::Path;
+
+use crate::graph;
+use crate::lint;
+use crate::spec::SpecDocument;
+use crate::trace::{coverage_gaps, Mention, Record};
+
+/// One finding, already resolved to a file/line and severity, ready to
+/// render as either a workflow command or a summary row.
+struct Finding {
+    level: &'static str,
+    rule: &'static str,
+    message: String,
+    file: String,
+    line: usize,
+}
+
+/// Runs lint, dangling-reference, and coverage-gap checks against `doc`
+/// and its trace `records`, and renders one `::error`/`::warning`
+/// workflow command per finding, anchored to `spec_path` (or, for a
+/// coverage gap, to the first scanned `mentions` entry for its id).
+/// Printed to stdout in a GitHub Actions job, each line annotates the
+/// corresponding file/line in the PR's "Files changed" tab.
+pub fn annotations(doc: &SpecDocument, spec_path: &Path, records: &[Record], mentions: &[Mention]) -> String {
+    let mut out = String::new();
+    for finding in findings(doc, spec_path, records, mentions) {
+        out.push_str(&format!(
+            "::{} file={},line={}::[{}] {}\n",
+            finding.level, finding.file, finding.line, finding.rule, finding.message
+        ));
+    }
+    out
+}
+
+/// Renders the same findings as a Markdown table suitable for a [job
+/// summary](https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions#adding-a-job-summary)
+/// (`$GITHUB_STEP_SUMMARY`), so the PR shows a total even when CI doesn't
+/// surface inline annotations (e.g. a non-PR run). Renders `"No spec
+/// issues found.\n"` if `doc` has none.
+pub fn summary_markdown(doc: &SpecDocument, spec_path: &Path, records: &[Record], mentions: &[Mention]) -> String {
+    let findings = findings(doc, spec_path, records, mentions);
+    if findings.is_empty() {
+        return "No spec issues found.\n".to_string();
+    }
+
+    let mut out = String::from("## Spec issues\n\n| Severity | Rule | Location | Message |\n| --- | --- | --- | --- |\n");
+    for finding in &findings {
+        out.push_str(&format!(
+            "| {} | {} | {}:{} | {} |\n",
+            finding.level, finding.rule, finding.file, finding.line, finding.message
+        ));
+    }
+    out
+}
+
+fn findings(doc: &SpecDocument, spec_path: &Path, records: &[Record], mentions: &[Mention]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for issue in lint::lint(doc) {
+        findings.push(Finding {
+            level: "warning",
+            rule: issue.rule,
+            message: issue.message,
+            file: spec_path.display().to_string(),
+            line: 1,
+        });
+    }
+
+    for reference in graph::dangling_references(doc) {
+        findings.push(Finding {
+            level: "error",
+            rule: "dangling-reference",
+            message: reference.to_string(),
+            file: spec_path.display().to_string(),
+            line: 1,
+        });
+    }
+
+    let gaps = coverage_gaps(records);
+    for id in &gaps.tested_not_implemented {
+        let (file, line) = location_for(id, spec_path, mentions);
+        findings.push(Finding {
+            level: "warning",
+            rule: "missing-implementation",
+            message: format!("requirement `{id}` has a test but no recorded implementation"),
+            file,
+            line,
+        });
+    }
+    for id in &gaps.implemented_not_tested {
+        let (file, line) = location_for(id, spec_path, mentions);
+        findings.push(Finding {
+            level: "warning",
+            rule: "missing-test-coverage",
+            message: format!("requirement `{id}` has an implementation but no recorded test"),
+            file,
+            line,
+        });
+    }
+
+    findings
+}
+
+/// Where to point a coverage-gap finding: the first mention the source
+/// scanner found for `id`, or `spec_path`'s first line if the scanner
+/// found none.
+fn location_for(id: &str, spec_path: &Path, mentions: &[Mention]) -> (String, usize) {
+    match mentions.iter().find(|m| m.id == id) {
+        Some(mention) => (mention.file.display().to_string(), mention.line),
+        None => (spec_path.display().to_string(), 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+    use std::path::PathBuf;
+
+    fn doc_with_dangling_reference() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-1".into(),
+            text: "depends on something missing".into(),
+            depends_on: vec!["REQ-404".into()],
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn emits_an_error_command_for_a_dangling_reference() {
+        let doc = doc_with_dangling_reference();
+        let out = annotations(&doc, &PathBuf::from("spec.toml"), &[], &[]);
+        assert!(out.contains("::error file=spec.toml,line=1::[dangling-reference]"));
+    }
+
+    #[test]
+    fn emits_a_warning_command_for_a_lint_issue() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-1".into(),
+            text: "".into(),
+            ..Default::default()
+        });
+        let out = annotations(&doc, &PathBuf::from("spec.toml"), &[], &[]);
+        assert!(out.contains("::warning file=spec.toml,line=1::[empty-text]"));
+    }
+
+    #[test]
+    fn anchors_a_coverage_gap_to_a_scanned_mention_when_one_exists() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-4".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+        let records = vec![Record {
+            kind: "implements".into(),
+            function: "BankLibrary::balance".into(),
+            requirement: "REQ-4".into(),
+        }];
+        let mentions = vec![Mention {
+            id: "REQ-4".into(),
+            file: PathBuf::from("src/lib.rs"),
+            line: 42,
+        }];
+
+        let out = annotations(&doc, &PathBuf::from("spec.toml"), &records, &mentions);
+        assert!(out.contains("::warning file=src/lib.rs,line=42::[missing-test-coverage]"));
+    }
+
+    #[test]
+    fn summary_reports_no_issues_for_a_clean_spec() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-1".into(),
+            text: "fine".into(),
+            ..Default::default()
+        });
+        let summary = summary_markdown(&doc, &PathBuf::from("spec.toml"), &[], &[]);
+        assert_eq!(summary, "No spec issues found.\n");
+    }
+
+    #[test]
+    fn summary_tabulates_every_finding() {
+        let doc = doc_with_dangling_reference();
+        let summary = summary_markdown(&doc, &PathBuf::from("spec.toml"), &[], &[]);
+        assert!(summary.starts_with("## Spec issues\n"));
+        assert!(summary.contains("| error | dangling-reference | spec.toml:1 |"));
+    }
+}
@@ -0,0 +1,354 @@
+//! Queries over the requirement relationship graph a
+//! [`SpecDocument`](crate::spec::SpecDocument)'s `depends_on`/`refines`/
+//! `conflicts_with` fields describe.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::intern::{Interner, Symbol};
+use crate::spec::SpecDocument;
+
+/// A `depends_on`/`refines` cycle: `path` lists the ids visited, ending back
+/// at the id it started from.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Cycle {
+    pub path: Vec<String>,
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle: {}", self.path.join(" -> "))
+    }
+}
+
+impl std::error::Error for Cycle {}
+
+/// Finds a cycle in the graph formed by following `edges_of(id)` for every
+/// requirement, or `None` if the graph is acyclic. Ids are interned into
+/// [`Symbol`]s for the duration of the traversal, so the `visiting`/
+/// `visited` sets and the in-progress path hash and compare a `u32`
+/// rather than re-hashing and cloning the same id strings at every step;
+/// only the final [`Cycle`], if any, is translated back to `String`.
+fn find_cycle(
+    doc: &SpecDocument,
+    edges_of: impl Fn(&crate::spec::Requirement) -> &[String],
+) -> Option<Cycle> {
+    let mut interner = Interner::new();
+    let mut visiting = HashSet::new();
+    let mut visited = HashSet::new();
+    let mut path = Vec::new();
+
+    fn visit(
+        id: Symbol,
+        doc: &SpecDocument,
+        edges_of: &impl Fn(&crate::spec::Requirement) -> &[String],
+        interner: &mut Interner,
+        visiting: &mut HashSet<Symbol>,
+        visited: &mut HashSet<Symbol>,
+        path: &mut Vec<Symbol>,
+    ) -> Option<Vec<Symbol>> {
+        if visited.contains(&id) {
+            return None;
+        }
+        if !visiting.insert(id) {
+            path.push(id);
+            return Some(path.clone());
+        }
+        path.push(id);
+        if let Some(req) = doc.requirement(interner.resolve(id)) {
+            for next in edges_of(req) {
+                let next = interner.intern(next);
+                if let Some(cycle) = visit(next, doc, edges_of, interner, visiting, visited, path) {
+                    return Some(cycle);
+                }
+            }
+        }
+        path.pop();
+        visiting.remove(&id);
+        visited.insert(id);
+        None
+    }
+
+    for req in &doc.requirements {
+        let id = interner.intern(&req.id);
+        if let Some(cycle) = visit(
+            id,
+            doc,
+            &edges_of,
+            &mut interner,
+            &mut visiting,
+            &mut visited,
+            &mut path,
+        ) {
+            return Some(Cycle {
+                path: cycle.into_iter().map(|sym| interner.resolve(sym).to_string()).collect(),
+            });
+        }
+    }
+    None
+}
+
+/// Finds a cycle in the `depends_on` graph, if any.
+pub fn depends_on_cycle(doc: &SpecDocument) -> Option<Cycle> {
+    find_cycle(doc, |r| &r.depends_on)
+}
+
+/// Finds a cycle in the `refines` graph, if any.
+pub fn refines_cycle(doc: &SpecDocument) -> Option<Cycle> {
+    find_cycle(doc, |r| &r.refines)
+}
+
+/// Ids `a` whose `conflicts_with` lists `b` without `b`'s listing `a` back.
+pub fn asymmetric_conflicts(doc: &SpecDocument) -> Vec<(String, String)> {
+    let mut issues = Vec::new();
+    for req in &doc.requirements {
+        for other_id in &req.conflicts_with {
+            let reciprocated = doc
+                .requirement(other_id)
+                .is_some_and(|other| other.conflicts_with.contains(&req.id));
+            if !reciprocated {
+                issues.push((req.id.clone(), other_id.clone()));
+            }
+        }
+    }
+    issues
+}
+
+/// A `depends_on`/`refines`/`conflicts_with` reference that names an id with
+/// no matching requirement in the document.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DanglingReference {
+    pub from: String,
+    pub field: &'static str,
+    pub to: String,
+}
+
+impl fmt::Display for DanglingReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} `{}` references unknown requirement `{}`",
+            self.field, self.from, self.to
+        )
+    }
+}
+
+impl std::error::Error for DanglingReference {}
+
+/// Follows [`Requirement::replaced_by`](crate::spec::Requirement::replaced_by)
+/// forward from `id`, returning the chain of ids it passes through
+/// (starting with `id` itself) so an audit can trace a requirement across
+/// renumberings instead of losing it at the first supersession. Stops at a
+/// requirement with no `replaced_by`, a `replaced_by` naming an id not in
+/// `doc`, or back at an id already visited (a `replaced_by` cycle
+/// shouldn't hang the caller).
+pub fn lineage(doc: &SpecDocument, id: &str) -> Vec<String> {
+    let mut interner = Interner::new();
+    let start = interner.intern(id);
+    let mut chain = vec![start];
+    let mut visited: HashSet<Symbol> = std::iter::once(start).collect();
+    let mut current = start;
+
+    while let Some(next) = doc.requirement(interner.resolve(current)).and_then(|r| r.replaced_by.clone()) {
+        let next = interner.intern(&next);
+        if doc.requirement(interner.resolve(next)).is_none() || !visited.insert(next) {
+            break;
+        }
+        chain.push(next);
+        current = next;
+    }
+    chain.into_iter().map(|sym| interner.resolve(sym).to_string()).collect()
+}
+
+/// Every `depends_on`/`refines`/`conflicts_with` entry across `doc`'s
+/// requirements that names an id not declared anywhere in the document.
+/// Same as [`dangling_references_with_external`]`(doc, &[])`.
+pub fn dangling_references(doc: &SpecDocument) -> Vec<DanglingReference> {
+    dangling_references_with_external(doc, &[])
+}
+
+/// Like [`dangling_references`], but a qualified `namespace:id` reference
+/// (see [`crate::external_index::parse_ref`]) is checked against
+/// `external_indexes` instead of always being reported as dangling —
+/// once another repo's requirements are indexed locally via
+/// `cargo spec external-index fetch`, a `depends_on = ["platform:REQ-004"]`
+/// resolves like any other reference as long as that namespace's index
+/// has a `REQ-004`.
+pub fn dangling_references_with_external(
+    doc: &SpecDocument,
+    external_indexes: &[crate::external_index::ExternalIndex],
+) -> Vec<DanglingReference> {
+    let mut issues = Vec::new();
+    for req in &doc.requirements {
+        for (field, targets) in [
+            ("depends_on", &req.depends_on),
+            ("refines", &req.refines),
+            ("conflicts_with", &req.conflicts_with),
+        ] {
+            for id in targets {
+                if doc.requirement(id).is_some() {
+                    continue;
+                }
+                let resolved_externally = crate::external_index::parse_ref(id).is_some_and(|qref| {
+                    external_indexes
+                        .iter()
+                        .any(|index| index.namespace == qref.namespace && index.requirement_text(qref.id).is_some())
+                });
+                if !resolved_externally {
+                    issues.push(DanglingReference {
+                        from: req.id.clone(),
+                        field,
+                        to: id.clone(),
+                    });
+                }
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    fn req(id: &str, depends_on: &[&str]) -> Requirement {
+        Requirement {
+            id: id.into(),
+            text: "text".into(),
+            depends_on: depends_on.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn detects_depends_on_cycle() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-1", &["REQ-2"]));
+        doc.requirements.push(req("REQ-2", &["REQ-1"]));
+        assert!(depends_on_cycle(&doc).is_some());
+    }
+
+    #[test]
+    fn acyclic_graph_has_no_cycle() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-1", &["REQ-2"]));
+        doc.requirements.push(req("REQ-2", &[]));
+        assert!(depends_on_cycle(&doc).is_none());
+    }
+
+    #[test]
+    fn detects_asymmetric_conflict() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-1".into(),
+            text: "text".into(),
+            conflicts_with: vec!["REQ-2".into()],
+            ..Default::default()
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-2".into(),
+            text: "text".into(),
+            ..Default::default()
+        });
+
+        let issues = asymmetric_conflicts(&doc);
+        assert_eq!(issues, vec![("REQ-1".to_string(), "REQ-2".to_string())]);
+    }
+
+    #[test]
+    fn detects_dangling_references() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-1", &["REQ-404"]));
+
+        let issues = dangling_references(&doc);
+        assert_eq!(
+            issues,
+            vec![DanglingReference {
+                from: "REQ-1".into(),
+                field: "depends_on",
+                to: "REQ-404".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn resolved_references_are_not_dangling() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-1", &["REQ-2"]));
+        doc.requirements.push(req("REQ-2", &[]));
+        assert!(dangling_references(&doc).is_empty());
+    }
+
+    #[test]
+    fn a_qualified_reference_resolved_by_an_external_index_is_not_dangling() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-1", &["platform:REQ-004"]));
+
+        let mut platform = SpecDocument::new();
+        platform.requirements.push(req("REQ-004", &[]));
+        let index = crate::external_index::ExternalIndex::from_spec("platform", "platform.toml", &platform);
+
+        assert!(dangling_references_with_external(&doc, &[index]).is_empty());
+    }
+
+    #[test]
+    fn a_qualified_reference_to_an_unindexed_namespace_is_still_dangling() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-1", &["platform:REQ-004"]));
+
+        let issues = dangling_references_with_external(&doc, &[]);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].to, "platform:REQ-004");
+    }
+
+    fn superseded_req(id: &str, replaced_by: Option<&str>) -> Requirement {
+        Requirement {
+            id: id.into(),
+            text: "text".into(),
+            replaced_by: replaced_by.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn follows_a_supersession_chain() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(superseded_req("REQ-004", Some("REQ-012")));
+        doc.requirements.push(superseded_req("REQ-012", Some("REQ-020")));
+        doc.requirements.push(superseded_req("REQ-020", None));
+
+        assert_eq!(
+            lineage(&doc, "REQ-004"),
+            vec!["REQ-004".to_string(), "REQ-012".to_string(), "REQ-020".to_string()]
+        );
+    }
+
+    #[test]
+    fn lineage_of_an_unreplaced_requirement_is_just_itself() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(superseded_req("REQ-004", None));
+        assert_eq!(lineage(&doc, "REQ-004"), vec!["REQ-004".to_string()]);
+    }
+
+    #[test]
+    fn lineage_does_not_hang_on_a_replaced_by_cycle() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(superseded_req("REQ-004", Some("REQ-012")));
+        doc.requirements.push(superseded_req("REQ-012", Some("REQ-004")));
+
+        assert_eq!(
+            lineage(&doc, "REQ-004"),
+            vec!["REQ-004".to_string(), "REQ-012".to_string()]
+        );
+    }
+
+    #[test]
+    fn lineage_stops_at_a_dangling_replaced_by() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(superseded_req("REQ-004", Some("REQ-404")));
+        assert_eq!(lineage(&doc, "REQ-004"), vec!["REQ-004".to_string()]);
+    }
+}
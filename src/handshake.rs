@@ -0,0 +1,143 @@
+//! A small version/spec-hash/capability handshake a client and server of
+//! a spec'd API exchange at startup, replacing an ad-hoc comparison of
+//! hand-rolled `version()` strings with a typed check that tells a
+//! handshake-protocol mismatch apart from a spec mismatch apart from a
+//! missing capability, instead of all three collapsing into "versions
+//! don't match".
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::SpecError;
+use crate::spec::SpecDocument;
+
+/// The protocol version this crate's handshake wire format speaks.
+/// Bumped only when the handshake's own shape changes, independent of
+/// any one spec's [`SpecDocument::version_hash`].
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// What one side of a spec'd API offers at startup: the handshake
+/// protocol version it speaks, the spec it was built against
+/// ([`SpecDocument::version_hash`]), and the capabilities it supports.
+/// Exchanged as-is over the wire (it derives `Serialize`/`Deserialize`),
+/// then checked with [`negotiate`](Handshake::negotiate).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Handshake {
+    pub protocol_version: u32,
+    pub spec_hash: String,
+    pub capabilities: BTreeSet<String>,
+}
+
+impl Handshake {
+    /// Builds a handshake for `doc`, offering `capabilities`, at this
+    /// crate's current [`PROTOCOL_VERSION`].
+    pub fn new(doc: &SpecDocument, capabilities: impl IntoIterator<Item = String>) -> Self {
+        Handshake {
+            protocol_version: PROTOCOL_VERSION,
+            spec_hash: doc.version_hash(),
+            capabilities: capabilities.into_iter().collect(),
+        }
+    }
+
+    /// Checks this (typically the local) handshake against `peer`'s. A
+    /// different [`PROTOCOL_VERSION`] or spec hash is an [`Err`], since
+    /// nothing about the exchange can be trusted past that point;
+    /// capabilities `peer` doesn't support are reported in the returned
+    /// [`NegotiatedCapabilities`] without failing the handshake, since a
+    /// peer supporting a strict subset is a normal, tolerable outcome.
+    pub fn negotiate(&self, peer: &Handshake) -> Result<NegotiatedCapabilities, SpecError> {
+        if self.protocol_version != peer.protocol_version {
+            return Err(SpecError::new(
+                "HANDSHAKE-PROTOCOL-MISMATCH",
+                "peer speaks a different handshake protocol version",
+            )
+            .with_details(format!(
+                "local {}, peer {}",
+                self.protocol_version, peer.protocol_version
+            )));
+        }
+        if self.spec_hash != peer.spec_hash {
+            return Err(SpecError::new(
+                "HANDSHAKE-SPEC-MISMATCH",
+                "peer was built against a different spec version",
+            )
+            .with_details(format!("local {}, peer {}", self.spec_hash, peer.spec_hash)));
+        }
+        Ok(NegotiatedCapabilities {
+            shared: self.capabilities.intersection(&peer.capabilities).cloned().collect(),
+            missing: self.capabilities.difference(&peer.capabilities).cloned().collect(),
+        })
+    }
+}
+
+/// The result of a successful [`Handshake::negotiate`]: the local side's
+/// capabilities `peer` also supports, and the ones it doesn't.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NegotiatedCapabilities {
+    pub shared: BTreeSet<String>,
+    pub missing: BTreeSet<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    fn doc() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn negotiates_successfully_with_a_matching_spec_and_protocol() {
+        let doc = doc();
+        let local = Handshake::new(&doc, ["bulk_transfer".to_string()]);
+        let peer = Handshake::new(&doc, ["bulk_transfer".to_string()]);
+
+        let negotiated = local.negotiate(&peer).unwrap();
+        assert_eq!(negotiated.shared, BTreeSet::from(["bulk_transfer".to_string()]));
+        assert!(negotiated.missing.is_empty());
+    }
+
+    #[test]
+    fn reports_capabilities_the_peer_does_not_support_without_failing() {
+        let doc = doc();
+        let local = Handshake::new(&doc, ["bulk_transfer".to_string(), "webhooks".to_string()]);
+        let peer = Handshake::new(&doc, ["bulk_transfer".to_string()]);
+
+        let negotiated = local.negotiate(&peer).unwrap();
+        assert_eq!(negotiated.shared, BTreeSet::from(["bulk_transfer".to_string()]));
+        assert_eq!(negotiated.missing, BTreeSet::from(["webhooks".to_string()]));
+    }
+
+    #[test]
+    fn rejects_a_peer_on_a_different_spec_version() {
+        let local = Handshake::new(&doc(), []);
+        let mut other_doc = doc();
+        other_doc.requirements.push(Requirement {
+            id: "REQ-005".into(),
+            text: "withdraw() subtracts from the balance".into(),
+            ..Default::default()
+        });
+        let peer = Handshake::new(&other_doc, []);
+
+        let err = local.negotiate(&peer).unwrap_err();
+        assert_eq!(err.code, "HANDSHAKE-SPEC-MISMATCH");
+    }
+
+    #[test]
+    fn rejects_a_peer_on_a_different_protocol_version() {
+        let local = Handshake::new(&doc(), []);
+        let mut peer = Handshake::new(&doc(), []);
+        peer.protocol_version = PROTOCOL_VERSION + 1;
+
+        let err = local.negotiate(&peer).unwrap_err();
+        assert_eq!(err.code, "HANDSHAKE-PROTOCOL-MISMATCH");
+    }
+}
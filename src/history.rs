@@ -0,0 +1,185 @@
+//! Per-requirement change history, sourced from `git log`'s pickaxe search
+//! (`-G<id>`) rather than anything in the spec document itself: when a
+//! requirement's id first and last appeared in the spec file, and which
+//! commits touched Rust source mentioning it (the same `.rs`-only scope
+//! [`crate::trace::scan`] uses).
+
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+
+/// One commit a pickaxe search matched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitInfo {
+    pub hash: String,
+    pub date: String,
+    pub summary: String,
+}
+
+/// A requirement's history: when it was introduced and last modified in
+/// the spec file, and every commit that touched Rust source mentioning
+/// its id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequirementHistory {
+    pub requirement: String,
+    pub introduced: Option<CommitInfo>,
+    pub last_modified: Option<CommitInfo>,
+    pub touched_by: Vec<CommitInfo>,
+}
+
+/// A `git log` invocation failed.
+#[derive(Debug)]
+pub enum HistoryError {
+    Io(std::io::Error),
+    GitFailed { args: Vec<String>, stderr: String },
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::Io(e) => write!(f, "failed to run git: {e}"),
+            HistoryError::GitFailed { args, stderr } => {
+                write!(f, "git {} failed: {}", args.join(" "), stderr.trim())
+            }
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+/// Builds `requirement_id`'s history by running `git log -G<id>` in
+/// `repo_root`, once scoped to `spec_path` for [`RequirementHistory::introduced`]/
+/// [`RequirementHistory::last_modified`], and once scoped to `*.rs` files
+/// for [`RequirementHistory::touched_by`].
+pub fn history(
+    repo_root: &Path,
+    spec_path: &Path,
+    requirement_id: &str,
+) -> Result<RequirementHistory, HistoryError> {
+    let spec_commits = log_pickaxe(repo_root, requirement_id, &spec_path.to_string_lossy())?;
+    let source_commits = log_pickaxe(repo_root, requirement_id, "*.rs")?;
+
+    Ok(RequirementHistory {
+        requirement: requirement_id.to_string(),
+        introduced: spec_commits.last().cloned(),
+        last_modified: spec_commits.first().cloned(),
+        touched_by: source_commits,
+    })
+}
+
+/// Runs `git log --format=... -G<id> -- <pathspec>` and parses the commits
+/// it reports, newest first (`git log`'s own order). `-G` (rather than
+/// `-S`) matches any commit whose diff added or removed a line containing
+/// `id`, not just ones that changed how many times it occurs — a reworded
+/// line still mentions the id both before and after, so `-S` would miss it.
+fn log_pickaxe(repo_root: &Path, id: &str, pathspec: &str) -> Result<Vec<CommitInfo>, HistoryError> {
+    let args = vec![
+        "-C".to_string(),
+        repo_root.to_string_lossy().into_owned(),
+        "log".to_string(),
+        "--format=%H%x1f%aI%x1f%s".to_string(),
+        format!("-G{id}"),
+        "--".to_string(),
+        pathspec.to_string(),
+    ];
+
+    let output = Command::new("git").args(&args).output().map_err(HistoryError::Io)?;
+    if !output.status.success() {
+        return Err(HistoryError::GitFailed {
+            args,
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        });
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\u{1f}');
+            Some(CommitInfo {
+                hash: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                summary: fields.next()?.to_string(),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(args)
+            .status()
+            .expect("git should run");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn temp_repo(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("libspec-history-test-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        git(&dir, &["init", "-q"]);
+        git(&dir, &["-c", "user.name=test", "-c", "user.email=test@example.com", "commit", "--allow-empty", "-q", "-m", "init"]);
+        dir
+    }
+
+    #[test]
+    fn finds_when_an_id_was_introduced_and_last_modified_in_the_spec() {
+        let repo = temp_repo("spec");
+        let spec_path = repo.join("spec.toml");
+
+        fs::write(&spec_path, "[[requirement]]\nid = \"REQ-004\"\ntext = \"first\"\n").unwrap();
+        git(&repo, &["add", "spec.toml"]);
+        git(&repo, &["-c", "user.name=test", "-c", "user.email=test@example.com", "commit", "-q", "-m", "add REQ-004"]);
+        let introduced_at = Command::new("git").arg("-C").arg(&repo).arg("rev-parse").arg("HEAD").output().unwrap();
+        let introduced_hash = String::from_utf8_lossy(&introduced_at.stdout).trim().to_string();
+
+        fs::write(&spec_path, "[[requirement]]\nid  = \"REQ-004\"\ntext = \"updated\"\n").unwrap();
+        git(&repo, &["add", "spec.toml"]);
+        git(&repo, &["-c", "user.name=test", "-c", "user.email=test@example.com", "commit", "-q", "-m", "reword REQ-004", "--allow-empty"]);
+
+        let history = history(&repo, &spec_path, "REQ-004").unwrap();
+        assert_eq!(history.introduced.unwrap().hash, introduced_hash);
+        assert_eq!(history.last_modified.unwrap().summary, "reword REQ-004");
+
+        let _ = fs::remove_dir_all(&repo);
+    }
+
+    #[test]
+    fn finds_commits_that_touched_rust_source_mentioning_the_id() {
+        let repo = temp_repo("source");
+        let spec_path = repo.join("spec.toml");
+        fs::write(&spec_path, "[[requirement]]\nid = \"REQ-004\"\ntext = \"ok\"\n").unwrap();
+
+        let src = repo.join("lib.rs");
+        fs::write(&src, "// REQ-004: balance must be non-negative\n").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["-c", "user.name=test", "-c", "user.email=test@example.com", "commit", "-q", "-m", "implement REQ-004"]);
+
+        let history = history(&repo, &spec_path, "REQ-004").unwrap();
+        assert_eq!(history.touched_by.len(), 1);
+        assert_eq!(history.touched_by[0].summary, "implement REQ-004");
+
+        let _ = fs::remove_dir_all(&repo);
+    }
+
+    #[test]
+    fn unmentioned_ids_have_no_history() {
+        let repo = temp_repo("empty");
+        let spec_path = repo.join("spec.toml");
+        fs::write(&spec_path, "[[requirement]]\nid = \"REQ-004\"\ntext = \"ok\"\n").unwrap();
+
+        let history = history(&repo, &spec_path, "REQ-999").unwrap();
+        assert!(history.introduced.is_none());
+        assert!(history.last_modified.is_none());
+        assert!(history.touched_by.is_empty());
+
+        let _ = fs::remove_dir_all(&repo);
+    }
+}
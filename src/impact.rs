@@ -0,0 +1,139 @@
+//! Change impact analysis: given a set of requirement ids that changed,
+//! finds every test and implementation function a reviewer should
+//! re-verify. That's not just the ones directly `covers`/`implements`-ing
+//! a changed requirement, but also the ones for any requirement that
+//! `depends_on` or `refines` a changed one, transitively — if `CONST-002`
+//! changes, a requirement that depends on it may need re-verification even
+//! though its own text didn't change.
+
+use std::collections::BTreeSet;
+
+use crate::spec::SpecDocument;
+use crate::trace::Record;
+
+/// The result of an impact analysis.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Impact {
+    /// `changed_ids`, plus every requirement that depends on or refines
+    /// one of them, transitively.
+    pub affected_requirements: Vec<String>,
+    /// Every `covers` record's function naming an affected requirement.
+    pub affected_tests: Vec<String>,
+    /// Every `implements` record's function naming an affected requirement.
+    pub affected_implementations: Vec<String>,
+}
+
+/// Computes the [`Impact`] of changing `changed_ids`.
+pub fn impact(doc: &SpecDocument, changed_ids: &[String], records: &[Record]) -> Impact {
+    let affected_requirements = affected_requirement_ids(doc, changed_ids);
+
+    let mut tests = BTreeSet::new();
+    let mut implementations = BTreeSet::new();
+    for record in records {
+        if !affected_requirements.contains(&record.requirement) {
+            continue;
+        }
+        match record.kind.as_str() {
+            "covers" => {
+                tests.insert(record.function.clone());
+            }
+            "implements" => {
+                implementations.insert(record.function.clone());
+            }
+            _ => {}
+        }
+    }
+
+    Impact {
+        affected_requirements: affected_requirements.into_iter().collect(),
+        affected_tests: tests.into_iter().collect(),
+        affected_implementations: implementations.into_iter().collect(),
+    }
+}
+
+/// `changed_ids`, closed over every requirement that `depends_on` or
+/// `refines` one transitively: a breadth-first walk of the reverse edges,
+/// since we're looking for who points *at* a changed id, not who it
+/// points to.
+fn affected_requirement_ids(doc: &SpecDocument, changed_ids: &[String]) -> BTreeSet<String> {
+    let mut affected: BTreeSet<String> = changed_ids.iter().cloned().collect();
+    let mut frontier: Vec<String> = changed_ids.to_vec();
+
+    while let Some(id) = frontier.pop() {
+        for req in &doc.requirements {
+            let points_at_id = req.depends_on.contains(&id) || req.refines.contains(&id);
+            if points_at_id && affected.insert(req.id.clone()) {
+                frontier.push(req.id.clone());
+            }
+        }
+    }
+    affected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    fn req(id: &str, depends_on: &[&str]) -> Requirement {
+        Requirement {
+            id: id.into(),
+            text: "text".into(),
+            depends_on: depends_on.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn includes_tests_and_implementations_of_the_changed_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-004", &[]));
+
+        let records = vec![
+            Record { kind: "covers".into(), function: "test_balance".into(), requirement: "REQ-004".into() },
+            Record { kind: "implements".into(), function: "BankLibrary::balance".into(), requirement: "REQ-004".into() },
+        ];
+
+        let impact = impact(&doc, &["REQ-004".to_string()], &records);
+        assert_eq!(impact.affected_requirements, vec!["REQ-004".to_string()]);
+        assert_eq!(impact.affected_tests, vec!["test_balance".to_string()]);
+        assert_eq!(impact.affected_implementations, vec!["BankLibrary::balance".to_string()]);
+    }
+
+    #[test]
+    fn follows_transitive_dependents() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-001", &[]));
+        doc.requirements.push(req("REQ-002", &["REQ-001"]));
+        doc.requirements.push(req("REQ-003", &["REQ-002"]));
+
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_req_003".into(),
+            requirement: "REQ-003".into(),
+        }];
+
+        let impact = impact(&doc, &["REQ-001".to_string()], &records);
+        assert_eq!(
+            impact.affected_requirements,
+            vec!["REQ-001".to_string(), "REQ-002".to_string(), "REQ-003".to_string()]
+        );
+        assert_eq!(impact.affected_tests, vec!["test_req_003".to_string()]);
+    }
+
+    #[test]
+    fn unrelated_requirements_are_not_affected() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req("REQ-001", &[]));
+        doc.requirements.push(req("REQ-002", &[]));
+
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_req_002".into(),
+            requirement: "REQ-002".into(),
+        }];
+
+        let impact = impact(&doc, &["REQ-001".to_string()], &records);
+        assert!(impact.affected_tests.is_empty());
+    }
+}
@@ -0,0 +1,369 @@
+//! Keeps `<!-- libspec:include REQ-004 --> ... <!-- libspec:end-include -->`
+//! marker blocks in Markdown and Rust source files synced with the spec's
+//! current requirement text, preventing prose drift like a README
+//! describing a constraint that the spec itself has since reworded.
+//!
+//! The marker is plain text, not HTML/Rust syntax: [`scan`] looks for it
+//! line by line the same best-effort way [`crate::trace::scan`] looks for
+//! id mentions, so the same `<!-- libspec:include ... -->` pair works
+//! verbatim inside a Markdown file or a `//`/`///` Rust comment without
+//! this module needing to know either language's comment syntax.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::spec::SpecDocument;
+
+const END_MARKER: &str = "<!-- libspec:end-include -->";
+
+/// One `libspec:include` block found in a file: the requirement it names,
+/// the lines currently between its markers, and where it starts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncludeBlock {
+    pub file: PathBuf,
+    pub line: usize,
+    pub requirement: String,
+    pub current: String,
+}
+
+/// A `libspec:include` block naming a requirement the spec doesn't have.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownRequirement {
+    pub file: PathBuf,
+    pub line: usize,
+    pub requirement: String,
+}
+
+impl fmt::Display for UnknownRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: `libspec:include {}` names a requirement that doesn't exist",
+            self.file.display(),
+            self.line,
+            self.requirement,
+        )
+    }
+}
+
+impl std::error::Error for UnknownRequirement {}
+
+/// A `libspec:include` block whose current text doesn't match the spec.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleInclude {
+    pub file: PathBuf,
+    pub line: usize,
+    pub requirement: String,
+    pub current: String,
+    pub expected: String,
+}
+
+/// Walks `root` recursively (skipping `target` and hidden directories) and
+/// returns every `libspec:include` block found in a `.md` or `.rs` file,
+/// in file-then-line order.
+pub fn scan(root: &Path) -> Vec<IncludeBlock> {
+    let mut files = Vec::new();
+    collect_files(root, &mut files);
+
+    let mut blocks = Vec::new();
+    for file in files {
+        let Ok(contents) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        blocks.extend(blocks_in(&file, &contents));
+    }
+    blocks
+}
+
+fn blocks_in(file: &Path, contents: &str) -> Vec<IncludeBlock> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if let Some(id) = requirement_id(lines[i]) {
+            let start = i;
+            let mut body = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim().ends_with(END_MARKER) {
+                body.push(lines[i]);
+                i += 1;
+            }
+            // `i` now points at the `end-include` line, or past the end of
+            // the file if the block was never closed — either way nothing
+            // further to do with this block but record it as found.
+            blocks.push(IncludeBlock {
+                file: file.to_path_buf(),
+                line: start + 1,
+                requirement: id,
+                current: body.join("\n"),
+            });
+        }
+        i += 1;
+    }
+    blocks
+}
+
+/// The requirement id a `libspec:include` marker line names, if `line`
+/// contains one. The marker is looked for anywhere on the line, not just
+/// at its start, so it reads the same whether it's a whole Markdown line
+/// or trails a Rust `//`/`///` comment prefix.
+fn requirement_id(line: &str) -> Option<String> {
+    let prefix = "<!-- libspec:include ";
+    let suffix = " -->";
+    let start = line.find(prefix)? + prefix.len();
+    let rest = &line[start..];
+    let end = rest.find(suffix)?;
+    let id = &rest[..end];
+    if id.is_empty() {
+        None
+    } else {
+        Some(id.to_string())
+    }
+}
+
+/// Checks every `libspec:include` block under `root` against `doc`,
+/// returning one [`StaleInclude`] per block whose current text doesn't
+/// match the requirement's current text, so `cargo spec ci`-style tooling
+/// can fail the build on drift instead of silently tolerating it.
+/// Blocks naming a requirement `doc` doesn't have are skipped (see
+/// [`unknown_requirements`] to surface those separately).
+pub fn stale(root: &Path, doc: &SpecDocument) -> Vec<StaleInclude> {
+    scan(root)
+        .into_iter()
+        .filter_map(|block| {
+            let expected = doc.requirement(&block.requirement)?.text.clone();
+            if block.current == expected {
+                None
+            } else {
+                Some(StaleInclude {
+                    file: block.file,
+                    line: block.line,
+                    requirement: block.requirement,
+                    current: block.current,
+                    expected,
+                })
+            }
+        })
+        .collect()
+}
+
+/// Every `libspec:include` block under `root` naming a requirement `doc`
+/// doesn't have.
+pub fn unknown_requirements(root: &Path, doc: &SpecDocument) -> Vec<UnknownRequirement> {
+    scan(root)
+        .into_iter()
+        .filter(|block| doc.requirement(&block.requirement).is_none())
+        .map(|block| UnknownRequirement { file: block.file, line: block.line, requirement: block.requirement })
+        .collect()
+}
+
+/// Rewrites every stale `libspec:include` block under `root` in place with
+/// `doc`'s current requirement text, returning how many blocks changed.
+/// Blocks already in sync, and blocks naming an unknown requirement, are
+/// left untouched.
+pub fn sync(root: &Path, doc: &SpecDocument) -> std::io::Result<usize> {
+    let mut files = Vec::new();
+    collect_files(root, &mut files);
+
+    let mut changed = 0;
+    for file in files {
+        let Ok(contents) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        if let Some(rewritten) = rewrite(&contents, doc, &mut changed) {
+            std::fs::write(&file, rewritten)?;
+        }
+    }
+    Ok(changed)
+}
+
+/// Rewrites `contents`' `libspec:include` blocks with `doc`'s current
+/// requirement text, incrementing `changed` once per block that actually
+/// differed. Returns `None` if nothing in `contents` changed, so [`sync`]
+/// can skip writing files it didn't touch.
+fn rewrite(contents: &str, doc: &SpecDocument, changed: &mut usize) -> Option<String> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut out = Vec::new();
+    let mut touched = false;
+    let mut i = 0;
+    while i < lines.len() {
+        out.push(lines[i].to_string());
+        if let Some(id) = requirement_id(lines[i]) {
+            let start_body = out.len();
+            i += 1;
+            let mut body = Vec::new();
+            while i < lines.len() && !lines[i].trim().ends_with(END_MARKER) {
+                body.push(lines[i]);
+                i += 1;
+            }
+            if let Some(req) = doc.requirement(&id) {
+                if body.join("\n") != req.text {
+                    out.push(req.text.clone());
+                    touched = true;
+                    *changed += 1;
+                } else {
+                    out.truncate(start_body);
+                    out.extend(body.iter().map(|l| l.to_string()));
+                }
+            } else {
+                out.truncate(start_body);
+                out.extend(body.iter().map(|l| l.to_string()));
+            }
+            if i < lines.len() {
+                out.push(lines[i].to_string());
+            }
+        }
+        i += 1;
+    }
+
+    if touched {
+        let mut text = out.join("\n");
+        if contents.ends_with('\n') {
+            text.push('\n');
+        }
+        Some(text)
+    } else {
+        None
+    }
+}
+
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if name == "target" || name.starts_with('.') {
+                continue;
+            }
+            collect_files(&path, out);
+        } else if path.extension().and_then(|e| e.to_str()).is_some_and(|e| e == "rs" || e == "md") {
+            out.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    fn write(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("libspec-include-sync-test-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn doc() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn scan_finds_a_block_and_its_current_body() {
+        let dir = temp_dir("scan");
+        write(
+            &dir,
+            "README.md",
+            "# Bank\n\n<!-- libspec:include REQ-004 -->\nold stale text\n<!-- libspec:end-include -->\n",
+        );
+
+        let blocks = scan(&dir);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].requirement, "REQ-004");
+        assert_eq!(blocks[0].current, "old stale text");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn stale_reports_a_block_whose_text_drifted_from_the_spec() {
+        let dir = temp_dir("stale");
+        write(
+            &dir,
+            "README.md",
+            "<!-- libspec:include REQ-004 -->\nold stale text\n<!-- libspec:end-include -->\n",
+        );
+
+        let gaps = stale(&dir, &doc());
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].expected, "balance() returns the current balance");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn stale_is_empty_once_the_block_matches_the_spec() {
+        let dir = temp_dir("fresh");
+        write(
+            &dir,
+            "README.md",
+            "<!-- libspec:include REQ-004 -->\nbalance() returns the current balance\n<!-- libspec:end-include -->\n",
+        );
+
+        assert!(stale(&dir, &doc()).is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn unknown_requirements_flags_a_block_naming_a_requirement_that_does_not_exist() {
+        let dir = temp_dir("unknown");
+        write(
+            &dir,
+            "notes.rs",
+            "// <!-- libspec:include REQ-999 -->\n// missing\n// <!-- libspec:end-include -->\n",
+        );
+
+        let unknown = unknown_requirements(&dir, &doc());
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].requirement, "REQ-999");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn sync_rewrites_a_stale_block_in_place() {
+        let dir = temp_dir("sync");
+        let path = write(
+            &dir,
+            "README.md",
+            "# Bank\n\n<!-- libspec:include REQ-004 -->\nold stale text\n<!-- libspec:end-include -->\n\nmore\n",
+        );
+
+        let changed = sync(&dir, &doc()).unwrap();
+        assert_eq!(changed, 1);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("<!-- libspec:include REQ-004 -->\nbalance() returns the current balance\n<!-- libspec:end-include -->"));
+        assert!(contents.contains("more"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn sync_leaves_an_up_to_date_block_untouched() {
+        let dir = temp_dir("noop");
+        let original = "<!-- libspec:include REQ-004 -->\nbalance() returns the current balance\n<!-- libspec:end-include -->\n";
+        let path = write(&dir, "README.md", original);
+
+        let changed = sync(&dir, &doc()).unwrap();
+        assert_eq!(changed, 0);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
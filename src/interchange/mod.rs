@@ -0,0 +1,7 @@
+//! Exchange formats that move a [`SpecDocument`](crate::spec::SpecDocument)
+//! to and from other requirements-management tooling, as opposed to the
+//! formats under [`crate::spec`] a spec is natively authored in.
+
+mod reqif;
+
+pub use reqif::{export_string, import_str, ReqIfImportError};
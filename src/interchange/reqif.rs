@@ -0,0 +1,228 @@
+//! ReqIF (Requirements Interchange Format) import/export: a simplified,
+//! attribute-based subset of the OMG standard good enough to round-trip a
+//! [`SpecDocument`](crate::spec::SpecDocument)'s requirements through a
+//! DOORS-like tool, without pulling in a full XML/ReqIF library for it.
+//!
+//! ```xml
+//! <REQ-IF>
+//!   <SPEC-OBJECTS>
+//!     <SPEC-OBJECT IDENTIFIER="REQ-004">
+//!       <ATTRIBUTE NAME="Text">balance() returns the current balance</ATTRIBUTE>
+//!       <ATTRIBUTE NAME="Status">draft</ATTRIBUTE>
+//!       <ATTRIBUTE NAME="Priority">medium</ATTRIBUTE>
+//!       <ATTRIBUTE NAME="Owner">alice</ATTRIBUTE>
+//!       <ATTRIBUTE NAME="Tags">mandatory,billing</ATTRIBUTE>
+//!     </SPEC-OBJECT>
+//!   </SPEC-OBJECTS>
+//! </REQ-IF>
+//! ```
+
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::spec::{Requirement, SpecDocument};
+
+/// Error importing a ReqIF document.
+#[derive(Debug, PartialEq)]
+pub enum ReqIfImportError {
+    /// A `<SPEC-OBJECT>` had no `IDENTIFIER` attribute.
+    MissingIdentifier,
+}
+
+impl fmt::Display for ReqIfImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReqIfImportError::MissingIdentifier => {
+                write!(f, "<SPEC-OBJECT> is missing its IDENTIFIER attribute")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReqIfImportError {}
+
+/// Exports `doc`'s requirements as a ReqIF document: one `<SPEC-OBJECT>`
+/// per requirement, identified by its id.
+pub fn export_string(doc: &SpecDocument) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<REQ-IF>\n  <SPEC-OBJECTS>\n");
+    for req in &doc.requirements {
+        out.push_str(&format!(
+            "    <SPEC-OBJECT IDENTIFIER=\"{}\">\n",
+            escape_xml(&req.id)
+        ));
+        out.push_str(&attribute("Text", &req.text));
+        out.push_str(&attribute("Status", &format!("{:?}", req.status).to_lowercase()));
+        out.push_str(&attribute("Priority", &format!("{:?}", req.priority).to_lowercase()));
+        if let Some(owner) = &req.owner {
+            out.push_str(&attribute("Owner", owner));
+        }
+        if !req.tags.is_empty() {
+            out.push_str(&attribute("Tags", &req.tags.join(",")));
+        }
+        out.push_str("    </SPEC-OBJECT>\n");
+    }
+    out.push_str("  </SPEC-OBJECTS>\n</REQ-IF>\n");
+    out
+}
+
+fn attribute(name: &str, value: &str) -> String {
+    format!(
+        "      <ATTRIBUTE NAME=\"{}\">{}</ATTRIBUTE>\n",
+        escape_xml(name),
+        escape_xml(value)
+    )
+}
+
+/// Imports a ReqIF document's `<SPEC-OBJECT>`s as requirements.
+pub fn import_str(input: &str) -> Result<SpecDocument, ReqIfImportError> {
+    let mut doc = SpecDocument::new();
+    for block in spec_object_blocks(input) {
+        let id = attribute_in(&block, "IDENTIFIER=\"")
+            .ok_or(ReqIfImportError::MissingIdentifier)?;
+
+        let mut req = Requirement { id, ..Default::default() };
+        if let Some(text) = tag_content(&block, "Text") {
+            req.text = unescape_xml(&text);
+        }
+        if let Some(status) = tag_content(&block, "Status") {
+            req.status = enum_from_str(&status).unwrap_or_default();
+        }
+        if let Some(priority) = tag_content(&block, "Priority") {
+            req.priority = enum_from_str(&priority).unwrap_or_default();
+        }
+        if let Some(owner) = tag_content(&block, "Owner") {
+            req.owner = Some(unescape_xml(&owner));
+        }
+        if let Some(tags) = tag_content(&block, "Tags") {
+            req.tags = tags.split(',').map(|t| unescape_xml(t.trim())).collect();
+        }
+        doc.requirements.push(req);
+    }
+    Ok(doc)
+}
+
+/// Splits `input` into the contents of each `<SPEC-OBJECT ...>...
+/// </SPEC-OBJECT>` block.
+fn spec_object_blocks(input: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut rest = input;
+    while let Some(start) = rest.find("<SPEC-OBJECT") {
+        let Some(end_offset) = rest[start..].find("</SPEC-OBJECT>") else {
+            break;
+        };
+        let end = start + end_offset + "</SPEC-OBJECT>".len();
+        blocks.push(rest[start..end].to_string());
+        rest = &rest[end..];
+    }
+    blocks
+}
+
+/// Finds the quoted value following `marker` (e.g. `IDENTIFIER="`) in
+/// `block`'s opening tag.
+fn attribute_in(block: &str, marker: &str) -> Option<String> {
+    let start = block.find(marker)? + marker.len();
+    let end = block[start..].find('"')?;
+    Some(block[start..start + end].to_string())
+}
+
+/// Finds the text content of `<ATTRIBUTE NAME="{name}">...</ATTRIBUTE>`.
+fn tag_content(block: &str, name: &str) -> Option<String> {
+    let marker = format!("NAME=\"{name}\">");
+    let start = block.find(&marker)? + marker.len();
+    let end = block[start..].find("</ATTRIBUTE>")?;
+    Some(block[start..start + end].to_string())
+}
+
+/// Parses an enum value via its existing `serde(rename_all = "lowercase")`
+/// [`serde::Deserialize`] impl, rather than duplicating that mapping here.
+fn enum_from_str<T: serde::de::DeserializeOwned>(value: &str) -> Option<T> {
+    serde_json::from_value(Value::String(value.to_string())).ok()
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn unescape_xml(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Priority, Status};
+
+    #[test]
+    fn exports_one_spec_object_per_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            status: Status::Approved,
+            priority: Priority::High,
+            owner: Some("alice".into()),
+            tags: vec!["mandatory".into()],
+            ..Default::default()
+        });
+
+        let xml = export_string(&doc);
+        assert!(xml.contains("<SPEC-OBJECT IDENTIFIER=\"REQ-004\">"));
+        assert!(xml.contains("<ATTRIBUTE NAME=\"Text\">balance() returns the current balance</ATTRIBUTE>"));
+        assert!(xml.contains("<ATTRIBUTE NAME=\"Status\">approved</ATTRIBUTE>"));
+        assert!(xml.contains("<ATTRIBUTE NAME=\"Priority\">high</ATTRIBUTE>"));
+        assert!(xml.contains("<ATTRIBUTE NAME=\"Owner\">alice</ATTRIBUTE>"));
+        assert!(xml.contains("<ATTRIBUTE NAME=\"Tags\">mandatory</ATTRIBUTE>"));
+    }
+
+    #[test]
+    fn imports_a_spec_object_back_into_a_requirement() {
+        let doc = import_str(
+            r#"<REQ-IF><SPEC-OBJECTS>
+                <SPEC-OBJECT IDENTIFIER="REQ-004">
+                    <ATTRIBUTE NAME="Text">balance() returns the current balance</ATTRIBUTE>
+                    <ATTRIBUTE NAME="Status">approved</ATTRIBUTE>
+                    <ATTRIBUTE NAME="Priority">high</ATTRIBUTE>
+                    <ATTRIBUTE NAME="Owner">alice</ATTRIBUTE>
+                    <ATTRIBUTE NAME="Tags">mandatory,billing</ATTRIBUTE>
+                </SPEC-OBJECT>
+            </SPEC-OBJECTS></REQ-IF>"#,
+        )
+        .unwrap();
+
+        let req = doc.requirement("REQ-004").unwrap();
+        assert_eq!(req.text, "balance() returns the current balance");
+        assert_eq!(req.status, Status::Approved);
+        assert_eq!(req.priority, Priority::High);
+        assert_eq!(req.owner, Some("alice".into()));
+        assert_eq!(req.tags, vec!["mandatory".to_string(), "billing".to_string()]);
+    }
+
+    #[test]
+    fn round_trips_through_export_and_import() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            status: Status::Implemented,
+            priority: Priority::Critical,
+            ..Default::default()
+        });
+
+        let reimported = import_str(&export_string(&doc)).unwrap();
+        assert_eq!(reimported.requirement("REQ-004").unwrap().status, Status::Implemented);
+        assert_eq!(reimported.requirement("REQ-004").unwrap().priority, Priority::Critical);
+    }
+
+    #[test]
+    fn rejects_a_spec_object_with_no_identifier() {
+        let result = import_str("<REQ-IF><SPEC-OBJECTS><SPEC-OBJECT></SPEC-OBJECT></SPEC-OBJECTS></REQ-IF>");
+        assert_eq!(result, Err(ReqIfImportError::MissingIdentifier));
+    }
+}
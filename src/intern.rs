@@ -0,0 +1,81 @@
+//! A small string interner: trades a one-time lookup/allocation per
+//! distinct string for representing every later reference to it as a
+//! cheap, `Copy`, hashable [`Symbol`] instead of another `String`
+//! allocation and a byte-by-byte comparison. Used internally by
+//! [`crate::graph`]'s traversals, which otherwise spend most of their
+//! time hashing and cloning the same handful of requirement ids over and
+//! over; callers still pass and get back plain `&str`/`String`, so this
+//! is purely an implementation detail, not a new id type threaded
+//! through the public API.
+
+use std::collections::HashMap;
+
+/// A cheap, `Copy` handle for a string interned by an [`Interner`]. Only
+/// meaningful relative to the [`Interner`] that produced it — comparing
+/// or resolving a `Symbol` against a different `Interner` gives an
+/// unrelated (or missing) string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol(u32);
+
+/// Owns one copy of each distinct string it's been asked to intern,
+/// handing out a [`Symbol`] for each. Never shrinks: interning the same
+/// string twice returns the same `Symbol` rather than storing it again.
+#[derive(Debug, Default)]
+pub struct Interner {
+    strings: Vec<Box<str>>,
+    lookup: HashMap<Box<str>, Symbol>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `s`'s `Symbol`, interning it first if this is the first
+    /// time this `Interner` has seen it.
+    pub fn intern(&mut self, s: &str) -> Symbol {
+        if let Some(symbol) = self.lookup.get(s) {
+            return *symbol;
+        }
+        let symbol = Symbol(self.strings.len() as u32);
+        let boxed: Box<str> = s.into();
+        self.strings.push(boxed.clone());
+        self.lookup.insert(boxed, symbol);
+        symbol
+    }
+
+    /// The string `symbol` was interned from. Panics if `symbol` wasn't
+    /// produced by this `Interner`, the same contract
+    /// `Vec::swap`/indexing has for an out-of-range index.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.strings[symbol.0 as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interning_the_same_string_twice_returns_the_same_symbol() {
+        let mut interner = Interner::new();
+        let a = interner.intern("REQ-001");
+        let b = interner.intern("REQ-001");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn distinct_strings_get_distinct_symbols() {
+        let mut interner = Interner::new();
+        let a = interner.intern("REQ-001");
+        let b = interner.intern("REQ-002");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn resolve_returns_the_original_string() {
+        let mut interner = Interner::new();
+        let symbol = interner.intern("REQ-001");
+        assert_eq!(interner.resolve(symbol), "REQ-001");
+    }
+}
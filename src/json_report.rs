@@ -0,0 +1,106 @@
+//! Machine-readable JSON counterpart to `cargo spec check`'s terminal
+//! output: the same validate/lint/graph/id-scheme findings [`crate::sarif`]
+//! draws on for code-scanning uploads, shaped here for generic tooling
+//! that wants to build on `libspec` output instead of scraping text.
+//! [`SCHEMA_VERSION`] is bumped whenever a field is added, renamed, or
+//! removed, so a consumer can tell when it's looking at a shape it wasn't
+//! built for.
+
+use serde_json::{json, Value};
+
+use crate::graph;
+use crate::lint;
+use crate::spec::{IdScheme, SpecDocument};
+use crate::validate;
+
+/// The current shape of [`check`]'s output. Bump on any breaking field
+/// change.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Runs the same checks as `cargo spec check` (meta-schema validation,
+/// lint, dangling references, `depends_on`/`refines` cycles, and, if
+/// `id_scheme` is set, id scheme conformance) and renders the combined
+/// findings as JSON instead of printing them line by line.
+pub fn check(doc: &SpecDocument, id_scheme: Option<&IdScheme>) -> Value {
+    let errors = validate::validate(doc).err().unwrap_or_default();
+    let warnings = lint::lint(doc);
+    let dangling_references = graph::dangling_references(doc);
+    let depends_on_cycle = graph::depends_on_cycle(doc);
+    let refines_cycle = graph::refines_cycle(doc);
+    let id_scheme_violations = id_scheme
+        .map(|scheme| doc.requirement_id_violations(scheme))
+        .unwrap_or_default();
+
+    let valid = errors.is_empty()
+        && dangling_references.is_empty()
+        && depends_on_cycle.is_none()
+        && refines_cycle.is_none()
+        && id_scheme_violations.is_empty();
+
+    json!({
+        "schema_version": SCHEMA_VERSION,
+        "valid": valid,
+        "requirement_count": doc.requirements.len(),
+        "constraint_count": doc.constraints.len(),
+        "errors": errors,
+        "warnings": warnings,
+        "dangling_references": dangling_references,
+        "depends_on_cycle": depends_on_cycle,
+        "refines_cycle": refines_cycle,
+        "id_scheme_violations": id_scheme_violations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn clean_doc_is_valid_with_empty_findings() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-001".to_string(),
+            text: "the system does a thing".to_string(),
+            ..Default::default()
+        });
+
+        let report = check(&doc, None);
+        assert_eq!(report["schema_version"], SCHEMA_VERSION);
+        assert_eq!(report["valid"], true);
+        assert_eq!(report["requirement_count"], 1);
+        assert_eq!(report["errors"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn a_dangling_reference_is_reported_and_marks_the_doc_invalid() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-001".to_string(),
+            text: "the system does a thing".to_string(),
+            depends_on: vec!["REQ-404".to_string()],
+            ..Default::default()
+        });
+
+        let report = check(&doc, None);
+        assert_eq!(report["valid"], false);
+        assert_eq!(report["dangling_references"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn an_id_scheme_violation_is_reported_only_when_a_scheme_is_given() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-1".to_string(),
+            text: "the system does a thing".to_string(),
+            ..Default::default()
+        });
+
+        assert_eq!(check(&doc, None)["valid"], true);
+
+        let scheme = IdScheme::new("REQ", 3);
+        let report = check(&doc, Some(&scheme));
+        assert_eq!(report["valid"], false);
+        assert_eq!(report["id_scheme_violations"].as_array().unwrap().len(), 1);
+    }
+}
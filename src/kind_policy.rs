@@ -0,0 +1,150 @@
+//! Per-[`RequirementKind`] policy: some kinds carry a verification
+//! obligation beyond "has a covering test". A
+//! [`RequirementKind::Security`] requirement's claim isn't just that it
+//! works, but that it survives an attempt to violate it, so [`check`]
+//! looks for a covering test named like the `rejects`-style negative
+//! tests [`crate::codegen::rust_negative_tests`] generates and the
+//! bank example hand-writes. A [`RequirementKind::Performance`]
+//! requirement's claim is a number a plain test can't check, so [`check`]
+//! looks for the [`PerfBudget`] [`crate::codegen::rust_bench`] needs to
+//! generate a benchmark from.
+//!
+//! [`PerfBudget`]: crate::spec::PerfBudget
+
+use serde::Serialize;
+
+use crate::spec::{RequirementKind, SpecDocument};
+use crate::trace::Record;
+
+/// Why a requirement's [`RequirementKind`] policy isn't satisfied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KindViolation {
+    /// A [`RequirementKind::Security`] requirement has no covering test
+    /// whose name marks it as exercising a rejected/failing case.
+    MissingNegativeTest,
+    /// A [`RequirementKind::Performance`] requirement declares no
+    /// [`PerfBudget`](crate::spec::PerfBudget) for `rust_bench` to check.
+    MissingPerfBudget,
+}
+
+/// One requirement whose [`RequirementKind`] policy isn't satisfied.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KindGap {
+    pub requirement: String,
+    pub violation: KindViolation,
+}
+
+/// Checks every requirement in `doc` against its [`RequirementKind`]'s
+/// policy: [`RequirementKind::Security`] requirements need a covering
+/// `records` entry whose function name contains `"reject"`;
+/// [`RequirementKind::Performance`] requirements need a
+/// [`Requirement::perf_budget`](crate::spec::Requirement::perf_budget).
+/// [`RequirementKind::Functional`] and [`RequirementKind::Safety`]
+/// requirements carry no policy here today.
+pub fn check(doc: &SpecDocument, records: &[Record]) -> Vec<KindGap> {
+    let mut gaps = Vec::new();
+    for req in &doc.requirements {
+        match req.kind {
+            RequirementKind::Security => {
+                let has_negative_test = records.iter().any(|r| {
+                    r.kind == "covers"
+                        && r.requirement == req.id
+                        && r.function.to_lowercase().contains("reject")
+                });
+                if !has_negative_test {
+                    gaps.push(KindGap {
+                        requirement: req.id.clone(),
+                        violation: KindViolation::MissingNegativeTest,
+                    });
+                }
+            }
+            RequirementKind::Performance => {
+                if req.perf_budget.is_none() {
+                    gaps.push(KindGap {
+                        requirement: req.id.clone(),
+                        violation: KindViolation::MissingPerfBudget,
+                    });
+                }
+            }
+            RequirementKind::Functional | RequirementKind::Safety => {}
+        }
+    }
+    gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{PerfBudget, Requirement};
+
+    fn doc(req: Requirement) -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req);
+        doc
+    }
+
+    #[test]
+    fn flags_a_security_requirement_with_no_negative_test() {
+        let req = Requirement {
+            id: "REQ-001".into(),
+            kind: RequirementKind::Security,
+            ..Default::default()
+        };
+        let gaps = check(&doc(req), &[]);
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].requirement, "REQ-001");
+        assert_eq!(gaps[0].violation, KindViolation::MissingNegativeTest);
+    }
+
+    #[test]
+    fn a_security_requirement_with_a_rejects_style_test_has_no_gap() {
+        let req = Requirement {
+            id: "REQ-001".into(),
+            kind: RequirementKind::Security,
+            ..Default::default()
+        };
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_withdraw_rejects_a_forged_token".into(),
+            requirement: "REQ-001".into(),
+        }];
+        assert!(check(&doc(req), &records).is_empty());
+    }
+
+    #[test]
+    fn flags_a_performance_requirement_with_no_perf_budget() {
+        let req = Requirement {
+            id: "REQ-002".into(),
+            kind: RequirementKind::Performance,
+            ..Default::default()
+        };
+        let gaps = check(&doc(req), &[]);
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].requirement, "REQ-002");
+        assert_eq!(gaps[0].violation, KindViolation::MissingPerfBudget);
+    }
+
+    #[test]
+    fn a_performance_requirement_with_a_perf_budget_has_no_gap() {
+        let req = Requirement {
+            id: "REQ-002".into(),
+            kind: RequirementKind::Performance,
+            perf_budget: Some(PerfBudget { scale: 10_000, max_millis: 1.0 }),
+            ..Default::default()
+        };
+        assert!(check(&doc(req), &[]).is_empty());
+    }
+
+    #[test]
+    fn functional_and_safety_requirements_are_unconstrained() {
+        let functional = Requirement { id: "REQ-003".into(), ..Default::default() };
+        let safety = Requirement {
+            id: "REQ-004".into(),
+            kind: RequirementKind::Safety,
+            ..Default::default()
+        };
+        assert!(check(&doc(functional), &[]).is_empty());
+        assert!(check(&doc(safety), &[]).is_empty());
+    }
+}
@@ -0,0 +1,51 @@
+//! `libspec` ties machine-readable requirement/constraint documents ("specs")
+//! to the code that implements them: parsing specs from various front-end
+//! formats, validating them, and generating code and reports from them.
+
+pub mod alm_export;
+pub mod audit;
+pub mod baseline;
+pub mod bootstrap;
+pub mod cache;
+pub mod changelog;
+pub mod codegen;
+pub mod codeowners;
+pub mod completeness;
+pub mod consistency;
+pub mod diff;
+pub mod doc;
+pub mod error;
+pub mod external_index;
+pub mod fmt;
+pub mod github_annotations;
+pub mod graph;
+pub mod handshake;
+pub mod history;
+pub mod impact;
+pub mod include_sync;
+pub mod interchange;
+pub(crate) mod intern;
+pub mod json_report;
+pub mod kind_policy;
+pub mod lint;
+pub mod manifest;
+pub mod merge;
+pub mod migration_guide;
+pub mod monitor;
+pub mod mutation;
+pub mod negotiation;
+#[cfg(feature = "parallel")]
+pub(crate) mod parallel;
+pub mod plugin;
+pub mod query;
+pub mod refinement;
+pub mod review;
+pub mod risk_policy;
+pub mod runtime;
+pub mod sarif;
+pub mod serve;
+pub mod spec;
+pub mod temporal;
+pub mod trace;
+pub mod tracker;
+pub mod validate;
@@ -0,0 +1,213 @@
+//! A registry of constraint-code numbering conventions: reserved number
+//! ranges per subsystem, checked against a spec (typically merged from
+//! several included files, see [`SpecDocument::merge`]) for duplicate
+//! codes, numbering gaps, and codes outside their subsystem's range.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::spec::SpecDocument;
+
+use super::LintIssue;
+
+/// A contiguous band of numbers reserved for one subsystem under a given
+/// code prefix, e.g. `CONST-100` through `CONST-199` for `"auth"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReservedRange {
+    pub subsystem: String,
+    pub prefix: String,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl ReservedRange {
+    pub fn new(subsystem: impl Into<String>, prefix: impl Into<String>, start: u32, end: u32) -> Self {
+        Self {
+            subsystem: subsystem.into(),
+            prefix: prefix.into(),
+            start,
+            end,
+        }
+    }
+
+    fn contains(&self, prefix: &str, number: u32) -> bool {
+        self.prefix == prefix && number >= self.start && number <= self.end
+    }
+
+    fn overlaps(&self, other: &ReservedRange) -> bool {
+        self.prefix == other.prefix && self.start <= other.end && other.start <= self.end
+    }
+}
+
+/// Validates constraint codes across a spec: no duplicates, no gaps in a
+/// prefix's numbering unless the gap falls in a declared [`ReservedRange`],
+/// and no code straying outside every range declared for its prefix.
+#[derive(Debug, Clone, Default)]
+pub struct CodeRegistry {
+    ranges: Vec<ReservedRange>,
+}
+
+impl CodeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares a reserved range. Overlapping ranges for the same prefix
+    /// aren't rejected here so a registry can be built incrementally; they
+    /// are reported by [`CodeRegistry::check`] instead.
+    pub fn reserve(&mut self, range: ReservedRange) -> &mut Self {
+        self.ranges.push(range);
+        self
+    }
+
+    /// Runs every registry check over `doc`.
+    pub fn check(&self, doc: &SpecDocument) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        self.range_conflicts(&mut issues);
+        self.duplicate_codes(doc, &mut issues);
+        self.numbering_gaps(doc, &mut issues);
+        self.out_of_range_codes(doc, &mut issues);
+        issues
+    }
+
+    fn range_conflicts(&self, issues: &mut Vec<LintIssue>) {
+        for (i, a) in self.ranges.iter().enumerate() {
+            for b in &self.ranges[i + 1..] {
+                if a.overlaps(b) {
+                    issues.push(LintIssue {
+                        rule: "reserved-range-conflict",
+                        message: format!(
+                            "reserved ranges for `{}` and `{}` overlap on prefix `{}` ({}-{} vs {}-{})",
+                            a.subsystem, b.subsystem, a.prefix, a.start, a.end, b.start, b.end
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    fn duplicate_codes(&self, doc: &SpecDocument, issues: &mut Vec<LintIssue>) {
+        let mut seen = HashSet::new();
+        for constraint in &doc.constraints {
+            if !seen.insert(&constraint.code) {
+                issues.push(LintIssue {
+                    rule: "duplicate-constraint-code",
+                    message: format!(
+                        "constraint code `{}` is declared more than once",
+                        constraint.code
+                    ),
+                });
+            }
+        }
+    }
+
+    fn numbering_gaps(&self, doc: &SpecDocument, issues: &mut Vec<LintIssue>) {
+        let mut by_prefix: BTreeMap<&str, Vec<u32>> = BTreeMap::new();
+        for constraint in &doc.constraints {
+            if let Some((prefix, number)) = split_code(&constraint.code) {
+                by_prefix.entry(prefix).or_default().push(number);
+            }
+        }
+        for (prefix, mut numbers) in by_prefix {
+            numbers.sort_unstable();
+            numbers.dedup();
+            for (&lo, &hi) in numbers.iter().zip(numbers.iter().skip(1)) {
+                for missing in (lo + 1)..hi {
+                    if !self.ranges.iter().any(|r| r.contains(prefix, missing)) {
+                        issues.push(LintIssue {
+                            rule: "constraint-code-gap",
+                            message: format!(
+                                "`{prefix}-{missing}` is missing between `{prefix}-{lo}` and `{prefix}-{hi}` and isn't covered by a reserved range"
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn out_of_range_codes(&self, doc: &SpecDocument, issues: &mut Vec<LintIssue>) {
+        for constraint in &doc.constraints {
+            let Some((prefix, number)) = split_code(&constraint.code) else {
+                continue;
+            };
+            let declared_for_prefix = self.ranges.iter().any(|r| r.prefix == prefix);
+            if declared_for_prefix && !self.ranges.iter().any(|r| r.contains(prefix, number)) {
+                issues.push(LintIssue {
+                    rule: "constraint-code-out-of-range",
+                    message: format!(
+                        "constraint code `{}` falls outside every reserved range declared for prefix `{}`",
+                        constraint.code, prefix
+                    ),
+                });
+            }
+        }
+    }
+}
+
+/// Splits a code like `CONST-042` into (`"CONST"`, `42`); codes without a
+/// trailing numeric segment are skipped by the numbering checks.
+fn split_code(code: &str) -> Option<(&str, u32)> {
+    let (prefix, digits) = code.rsplit_once('-')?;
+    digits.parse().ok().map(|number| (prefix, number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Constraint;
+
+    fn doc_with_codes(codes: &[&str]) -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        for code in codes {
+            doc.constraints.push(Constraint {
+                code: code.to_string(),
+                text: "some rule".into(),
+                ..Default::default()
+            });
+        }
+        doc
+    }
+
+    #[test]
+    fn flags_duplicate_codes() {
+        let doc = doc_with_codes(&["CONST-001", "CONST-001"]);
+        let issues = CodeRegistry::new().check(&doc);
+        assert!(issues.iter().any(|i| i.rule == "duplicate-constraint-code"));
+    }
+
+    #[test]
+    fn flags_gap_not_covered_by_a_reserved_range() {
+        let doc = doc_with_codes(&["CONST-001", "CONST-003"]);
+        let issues = CodeRegistry::new().check(&doc);
+        assert!(issues.iter().any(|i| i.rule == "constraint-code-gap"));
+    }
+
+    #[test]
+    fn gap_inside_a_reserved_range_is_not_flagged() {
+        let doc = doc_with_codes(&["CONST-001", "CONST-003"]);
+        let mut registry = CodeRegistry::new();
+        registry.reserve(ReservedRange::new("auth", "CONST", 2, 2));
+        let issues = registry.check(&doc);
+        assert!(!issues.iter().any(|i| i.rule == "constraint-code-gap"));
+    }
+
+    #[test]
+    fn flags_code_outside_every_declared_range_for_its_prefix() {
+        let doc = doc_with_codes(&["CONST-150"]);
+        let mut registry = CodeRegistry::new();
+        registry.reserve(ReservedRange::new("auth", "CONST", 1, 99));
+        let issues = registry.check(&doc);
+        assert!(issues
+            .iter()
+            .any(|i| i.rule == "constraint-code-out-of-range"));
+    }
+
+    #[test]
+    fn flags_overlapping_reserved_ranges() {
+        let mut registry = CodeRegistry::new();
+        registry.reserve(ReservedRange::new("auth", "CONST", 1, 100));
+        registry.reserve(ReservedRange::new("billing", "CONST", 50, 150));
+        let issues = registry.check(&SpecDocument::new());
+        assert!(issues.iter().any(|i| i.rule == "reserved-range-conflict"));
+    }
+}
@@ -0,0 +1,294 @@
+//! A lint subsystem for [`SpecDocument`](crate::spec::SpecDocument)s: checks
+//! that catch authoring mistakes (duplicate ids, empty text) that the format
+//! parsers don't reject because they're syntactically valid. See also
+//! [`code_registry`] for constraint-code numbering conventions.
+
+mod code_registry;
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::spec::{FsmIssue, Requirement, SpecDocument};
+
+pub use code_registry::{CodeRegistry, ReservedRange};
+
+/// A single lint finding.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintIssue {
+    pub rule: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.rule, self.message)
+    }
+}
+
+impl std::error::Error for LintIssue {}
+
+/// Runs every built-in lint rule over `doc`. Same as
+/// [`lint_with_threads`]`(doc, None)`.
+pub fn lint(doc: &SpecDocument) -> Vec<LintIssue> {
+    lint_with_threads(doc, None)
+}
+
+/// Runs every built-in lint rule over `doc`, behind the `parallel` feature
+/// on a rayon pool sized by `threads` (see [`crate::parallel::run`]) —
+/// without it, `threads` is ignored and every rule runs sequentially, the
+/// same as before this knob existed. Issues come back in the same order
+/// [`lint`] always used (one rule's issues, then the next), regardless of
+/// which rule's pass actually finishes first.
+pub fn lint_with_threads(doc: &SpecDocument, threads: Option<usize>) -> Vec<LintIssue> {
+    #[cfg(feature = "parallel")]
+    {
+        crate::parallel::run(threads, || run_rules_parallel(doc))
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        let _ = threads;
+        run_rules_sequential(doc)
+    }
+}
+
+/// Every built-in lint rule, in the fixed order [`lint`]'s issues follow.
+fn rules() -> [fn(&SpecDocument) -> Vec<LintIssue>; 5] {
+    [
+        duplicate_requirement_ids,
+        duplicate_constraint_codes,
+        empty_text,
+        invalid_constraint_exprs,
+        fsm_issues,
+    ]
+}
+
+#[cfg(not(feature = "parallel"))]
+fn run_rules_sequential(doc: &SpecDocument) -> Vec<LintIssue> {
+    rules().iter().flat_map(|rule| rule(doc)).collect()
+}
+
+#[cfg(feature = "parallel")]
+fn run_rules_parallel(doc: &SpecDocument) -> Vec<LintIssue> {
+    use rayon::prelude::*;
+    rules()
+        .par_iter()
+        .map(|rule| rule(doc))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Applies [`lint`]'s `duplicate-requirement-id` and `empty-text` rules one
+/// requirement at a time, e.g. to a
+/// [`SpecDocument::requirements_from_jsonl_str`] iterator, without holding
+/// a full `Vec<Requirement>` (or [`SpecDocument`]) in memory. The
+/// constraint- and FSM-level rules [`lint`] also runs aren't available
+/// here — a spec large enough to need this is large because of its
+/// requirement count, not its (typically much smaller) constraint or FSM
+/// count, so those are expected to still go through [`lint`] on a
+/// normally-loaded document.
+#[derive(Debug, Default)]
+pub struct StreamingLinter {
+    seen_ids: std::collections::HashSet<String>,
+}
+
+impl StreamingLinter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks `req` against every requirement this linter has already
+    /// seen (for `duplicate-requirement-id`) and against itself (for
+    /// `empty-text`), recording its id for future calls.
+    pub fn check(&mut self, req: &Requirement) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        if !self.seen_ids.insert(req.id.clone()) {
+            issues.push(LintIssue {
+                rule: "duplicate-requirement-id",
+                message: format!("requirement id `{}` is declared more than once", req.id),
+            });
+        }
+        if req.text.trim().is_empty() {
+            issues.push(LintIssue {
+                rule: "empty-text",
+                message: format!("requirement `{}` has no text", req.id),
+            });
+        }
+        issues
+    }
+}
+
+fn duplicate_requirement_ids(doc: &SpecDocument) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for req in &doc.requirements {
+        if !seen.insert(&req.id) {
+            issues.push(LintIssue {
+                rule: "duplicate-requirement-id",
+                message: format!("requirement id `{}` is declared more than once", req.id),
+            });
+        }
+    }
+    issues
+}
+
+fn duplicate_constraint_codes(doc: &SpecDocument) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for constraint in &doc.constraints {
+        if !seen.insert(&constraint.code) {
+            issues.push(LintIssue {
+                rule: "duplicate-constraint-code",
+                message: format!(
+                    "constraint code `{}` is declared more than once",
+                    constraint.code
+                ),
+            });
+        }
+    }
+    issues
+}
+
+fn empty_text(doc: &SpecDocument) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    for req in &doc.requirements {
+        if req.text.trim().is_empty() {
+            issues.push(LintIssue {
+                rule: "empty-text",
+                message: format!("requirement `{}` has no text", req.id),
+            });
+        }
+    }
+    for constraint in &doc.constraints {
+        if constraint.text.trim().is_empty() {
+            issues.push(LintIssue {
+                rule: "empty-text",
+                message: format!("constraint `{}` has no text", constraint.code),
+            });
+        }
+    }
+    issues
+}
+
+fn invalid_constraint_exprs(doc: &SpecDocument) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    for constraint in &doc.constraints {
+        if let Some(Err(e)) = constraint.parsed_expr() {
+            issues.push(LintIssue {
+                rule: "invalid-constraint-expr",
+                message: format!("constraint `{}` has an unparseable expr: {e}", constraint.code),
+            });
+        }
+    }
+    issues
+}
+
+/// Runs [`Fsm::issues`](crate::spec::Fsm::issues)'s reachability/deadlock
+/// analysis over every declared [`Fsm`](crate::spec::Fsm), so a state
+/// nothing can reach or an event that can never fire shows up alongside
+/// the rest of the spec's lint findings instead of only in code that
+/// calls `Fsm::issues` directly.
+fn fsm_issues(doc: &SpecDocument) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    for fsm in &doc.fsms {
+        for issue in fsm.issues() {
+            let rule = match issue {
+                FsmIssue::UndeclaredState { .. } => "fsm-undeclared-state",
+                FsmIssue::UnreachableState { .. } => "fsm-unreachable-state",
+                FsmIssue::NondeterministicTransition { .. } => "fsm-nondeterministic-transition",
+                FsmIssue::DeadOperation { .. } => "fsm-dead-operation",
+            };
+            issues.push(LintIssue {
+                rule,
+                message: format!("fsm `{}`: {issue}", fsm.name),
+            });
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn flags_duplicate_ids_and_empty_text() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-1".into(),
+            text: "first".into(),
+            ..Default::default()
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-1".into(),
+            text: "".into(),
+            ..Default::default()
+        });
+
+        let issues = lint(&doc);
+        assert!(issues.iter().any(|i| i.rule == "duplicate-requirement-id"));
+        assert!(issues.iter().any(|i| i.rule == "empty-text"));
+    }
+
+    #[test]
+    fn flags_an_unparseable_constraint_expr() {
+        let mut doc = SpecDocument::new();
+        doc.constraints.push(crate::spec::Constraint {
+            code: "CONST-001".into(),
+            text: "amount must be positive".into(),
+            expr: Some("amount >".into()),
+            ..Default::default()
+        });
+
+        let issues = lint(&doc);
+        assert!(issues.iter().any(|i| i.rule == "invalid-constraint-expr"));
+    }
+
+    #[test]
+    fn flags_an_unreachable_fsm_state() {
+        let mut doc = SpecDocument::new();
+        doc.fsms.push(crate::spec::Fsm {
+            name: "Account".into(),
+            states: vec!["Open".into(), "Archived".into()],
+            transitions: vec![],
+        });
+
+        let issues = lint(&doc);
+        assert!(issues.iter().any(|i| i.rule == "fsm-unreachable-state"
+            && i.message.contains("Account")
+            && i.message.contains("Archived")));
+    }
+
+    #[test]
+    fn streaming_linter_flags_duplicate_ids_and_empty_text() {
+        let mut linter = StreamingLinter::new();
+        let first = linter.check(&Requirement {
+            id: "REQ-1".into(),
+            text: "first".into(),
+            ..Default::default()
+        });
+        assert!(first.is_empty());
+
+        let second = linter.check(&Requirement {
+            id: "REQ-1".into(),
+            text: "".into(),
+            ..Default::default()
+        });
+        assert!(second.iter().any(|i| i.rule == "duplicate-requirement-id"));
+        assert!(second.iter().any(|i| i.rule == "empty-text"));
+    }
+
+    #[test]
+    fn clean_document_has_no_issues() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-1".into(),
+            text: "fine".into(),
+            ..Default::default()
+        });
+        assert!(lint(&doc).is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+//! A signed manifest naming the exact spec a released binary or
+//! conformance report was built against: [`SpecDocument::version_hash`],
+//! a digest per requirement, and the `libspec` version that produced it.
+//! Going further than [`SpecDocument::verify_version_hash`]'s in-process
+//! drift check, [`sign`]/[`verify`] let a manifest travel outside the
+//! process it was built in (attached to a release artifact, published
+//! alongside a conformance report) and still be trusted, as long as the
+//! verifier holds the same shared secret the signer used.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+
+use crate::spec::SpecDocument;
+
+/// A manifest naming the exact spec a release was built against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecManifest {
+    /// [`SpecDocument::version_hash`] of the spec this manifest describes.
+    pub spec_hash: String,
+    /// A digest per requirement, keyed by id, so a consumer can tell
+    /// exactly which requirements changed since a prior manifest instead
+    /// of just that something did.
+    pub requirement_digests: BTreeMap<String, String>,
+    /// The `libspec` crate version that built this manifest
+    /// (`env!("CARGO_PKG_VERSION")`), so a mismatch between the manifest's
+    /// toolchain and the one verifying it is visible rather than assumed.
+    pub toolchain_version: String,
+}
+
+/// Builds a [`SpecManifest`] for `doc`: its [`SpecDocument::version_hash`],
+/// one digest per requirement (hex-SHA-256 of its id, text, priority, and
+/// status), and this crate's version.
+pub fn build(doc: &SpecDocument) -> SpecManifest {
+    let requirement_digests = doc
+        .requirements
+        .iter()
+        .map(|req| {
+            let mut hasher = sha2::Sha256::new();
+            hasher.update(req.id.as_bytes());
+            hasher.update(b"\0");
+            hasher.update(req.text.as_bytes());
+            hasher.update(b"\0");
+            hasher.update(format!("{:?}", req.priority).as_bytes());
+            hasher.update(b"\0");
+            hasher.update(format!("{:?}", req.status).as_bytes());
+            (req.id.clone(), hex::encode(hasher.finalize()))
+        })
+        .collect();
+
+    SpecManifest {
+        spec_hash: doc.version_hash(),
+        requirement_digests,
+        toolchain_version: env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+/// A manifest signature that doesn't match its payload under the given
+/// secret.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureMismatch;
+
+impl fmt::Display for SignatureMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "manifest signature does not match its payload")
+    }
+}
+
+impl std::error::Error for SignatureMismatch {}
+
+/// Signs `manifest`'s JSON serialization with HMAC-SHA-256 under `secret`,
+/// returning the hex-encoded signature. Verify with [`verify`], passing
+/// the same `secret`.
+pub fn sign(manifest: &SpecManifest, secret: &[u8]) -> String {
+    let payload = serde_json::to_string(manifest).expect("SpecManifest serialization is infallible");
+    hex::encode(hmac_sha256(secret, payload.as_bytes()))
+}
+
+/// HMAC-SHA-256 (RFC 2104): `H((key' xor opad) || H((key' xor ipad) || message))`,
+/// where `key'` is `key` hashed down to the block size if it's longer than
+/// one, zero-padded up to it otherwise.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+
+    let mut key_block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        key_block[..32].copy_from_slice(&sha2::Sha256::digest(key));
+    } else {
+        key_block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut ipad = [0x36u8; BLOCK_SIZE];
+    let mut opad = [0x5cu8; BLOCK_SIZE];
+    for i in 0..BLOCK_SIZE {
+        ipad[i] ^= key_block[i];
+        opad[i] ^= key_block[i];
+    }
+
+    let mut inner = sha2::Sha256::new();
+    inner.update(ipad);
+    inner.update(message);
+    let inner_digest = inner.finalize();
+
+    let mut outer = sha2::Sha256::new();
+    outer.update(opad);
+    outer.update(inner_digest);
+    outer.finalize().into()
+}
+
+/// Checks that `signature` (as returned by [`sign`]) matches `manifest`
+/// under `secret`.
+pub fn verify(manifest: &SpecManifest, signature: &str, secret: &[u8]) -> Result<(), SignatureMismatch> {
+    if sign(manifest, secret) == signature {
+        Ok(())
+    } else {
+        Err(SignatureMismatch)
+    }
+}
+
+mod hex {
+    pub fn encode(bytes: impl AsRef<[u8]>) -> String {
+        bytes.as_ref().iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    fn doc_with_one_requirement() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn builds_one_digest_per_requirement() {
+        let doc = doc_with_one_requirement();
+        let manifest = build(&doc);
+        assert_eq!(manifest.spec_hash, doc.version_hash());
+        assert!(manifest.requirement_digests.contains_key("REQ-004"));
+        assert_eq!(manifest.requirement_digests.len(), 1);
+    }
+
+    #[test]
+    fn requirement_digest_changes_when_its_text_changes() {
+        let mut doc = doc_with_one_requirement();
+        let before = build(&doc).requirement_digests["REQ-004"].clone();
+
+        doc.requirements[0].text = "balance() never goes negative".into();
+        let after = build(&doc).requirement_digests["REQ-004"].clone();
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn verify_accepts_a_signature_made_with_the_same_secret() {
+        let manifest = build(&doc_with_one_requirement());
+        let signature = sign(&manifest, b"shared-secret");
+        assert_eq!(verify(&manifest, &signature, b"shared-secret"), Ok(()));
+    }
+
+    #[test]
+    fn verify_rejects_a_signature_made_with_a_different_secret() {
+        let manifest = build(&doc_with_one_requirement());
+        let signature = sign(&manifest, b"shared-secret");
+        assert_eq!(verify(&manifest, &signature, b"wrong-secret"), Err(SignatureMismatch));
+    }
+
+    #[test]
+    fn verify_rejects_a_signature_against_a_tampered_manifest() {
+        let manifest = build(&doc_with_one_requirement());
+        let signature = sign(&manifest, b"shared-secret");
+
+        let mut tampered = manifest;
+        tampered.toolchain_version = "0.0.0".into();
+        assert_eq!(verify(&tampered, &signature, b"shared-secret"), Err(SignatureMismatch));
+    }
+}
@@ -0,0 +1,233 @@
+//! Structural three-way merge of spec files: reconciles two edited copies
+//! of a spec (`ours`/`theirs`) against their common `base` by requirement
+//! id and constraint code, instead of the line-oriented merge `git`
+//! would otherwise attempt — which conflicts on any two edits landing in
+//! adjacent lines even when they touch unrelated requirements. See
+//! [`cargo_spec`'s `merge-driver`] for wiring this in as a git merge
+//! driver.
+//!
+//! [`cargo_spec`'s `merge-driver`]: https://git-scm.com/docs/gitattributes#_defining_a_custom_merge_driver
+
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use crate::spec::{Constraint, Requirement, SpecDocument};
+
+/// One requirement or constraint id [`merge`] couldn't reconcile, because
+/// `ours` and `theirs` both changed it (or one changed it while the other
+/// removed it) since `base`, in ways that don't agree. `document.
+/// requirements`/`constraints` still carries `ours`'s version of a
+/// conflicting id, the way a git merge driver's working file keeps
+/// `ours`'s content pending manual resolution rather than dropping it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MergeConflict {
+    pub id: String,
+    pub base: Option<Requirement>,
+    pub ours: Option<Requirement>,
+    pub theirs: Option<Requirement>,
+}
+
+/// A [`Constraint`] counterpart to [`MergeConflict`], keyed by `code`
+/// instead of a requirement id.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConstraintMergeConflict {
+    pub code: String,
+    pub base: Option<Constraint>,
+    pub ours: Option<Constraint>,
+    pub theirs: Option<Constraint>,
+}
+
+/// The result of [`merge`]: a best-effort merged document, plus every
+/// requirement/constraint id that needs a human to pick a side.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MergeResult {
+    pub document: SpecDocument,
+    pub conflicts: Vec<MergeConflict>,
+    pub constraint_conflicts: Vec<ConstraintMergeConflict>,
+}
+
+impl MergeResult {
+    /// Whether every requirement and constraint merged cleanly.
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty() && self.constraint_conflicts.is_empty()
+    }
+}
+
+/// The classic three-way merge rule for one field's value across `base`,
+/// `ours`, and `theirs`: whichever side actually changed wins; both
+/// sides agreeing (including both leaving it alone) is never a conflict;
+/// both sides changing it differently is.
+fn merge_one<T: Clone + PartialEq>(base: Option<&T>, ours: Option<&T>, theirs: Option<&T>) -> Result<Option<T>, ()> {
+    if ours == theirs {
+        Ok(ours.cloned())
+    } else if ours == base {
+        Ok(theirs.cloned())
+    } else if theirs == base {
+        Ok(ours.cloned())
+    } else {
+        Err(())
+    }
+}
+
+/// Merges `ours` and `theirs`, both edited from common ancestor `base`,
+/// by joining their requirements on [`Requirement::id`] and their
+/// constraints on [`Constraint::code`] — an edit to `REQ-004` in `ours`
+/// and an unrelated edit to `REQ-005` in `theirs` merge cleanly even
+/// though a line-oriented merge of the same two files might find them on
+/// adjacent lines. Every other list on [`SpecDocument`] (includes,
+/// templates, glossary, data types, and the rest) isn't merged entry by
+/// entry; the result simply keeps `ours`'s copy of those, since none of
+/// them carry the kind of stable id this join needs.
+pub fn merge(base: &SpecDocument, ours: &SpecDocument, theirs: &SpecDocument) -> MergeResult {
+    let mut ids: BTreeSet<&str> = BTreeSet::new();
+    for doc in [base, ours, theirs] {
+        ids.extend(doc.requirements.iter().map(|r| r.id.as_str()));
+    }
+
+    let mut requirements = Vec::new();
+    let mut conflicts = Vec::new();
+    for id in ids {
+        let (base_req, ours_req, theirs_req) = (base.requirement(id), ours.requirement(id), theirs.requirement(id));
+        match merge_one(base_req, ours_req, theirs_req) {
+            Ok(Some(req)) => requirements.push(req),
+            Ok(None) => {}
+            Err(()) => {
+                if let Some(req) = ours_req {
+                    requirements.push(req.clone());
+                }
+                conflicts.push(MergeConflict {
+                    id: id.to_string(),
+                    base: base_req.cloned(),
+                    ours: ours_req.cloned(),
+                    theirs: theirs_req.cloned(),
+                });
+            }
+        }
+    }
+
+    let mut codes: BTreeSet<&str> = BTreeSet::new();
+    for doc in [base, ours, theirs] {
+        codes.extend(doc.constraints.iter().map(|c| c.code.as_str()));
+    }
+
+    let mut constraints = Vec::new();
+    let mut constraint_conflicts = Vec::new();
+    for code in codes {
+        let (base_c, ours_c, theirs_c) = (base.constraint(code), ours.constraint(code), theirs.constraint(code));
+        match merge_one(base_c, ours_c, theirs_c) {
+            Ok(Some(c)) => constraints.push(c),
+            Ok(None) => {}
+            Err(()) => {
+                if let Some(c) = ours_c {
+                    constraints.push(c.clone());
+                }
+                constraint_conflicts.push(ConstraintMergeConflict {
+                    code: code.to_string(),
+                    base: base_c.cloned(),
+                    ours: ours_c.cloned(),
+                    theirs: theirs_c.cloned(),
+                });
+            }
+        }
+    }
+
+    let document = SpecDocument { requirements, constraints, ..ours.clone() };
+    MergeResult { document, conflicts, constraint_conflicts }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(id: &str, text: &str) -> Requirement {
+        Requirement { id: id.into(), text: text.into(), ..Default::default() }
+    }
+
+    fn doc(reqs: Vec<Requirement>) -> SpecDocument {
+        SpecDocument { requirements: reqs, ..SpecDocument::new() }
+    }
+
+    #[test]
+    fn an_unrelated_edit_on_each_side_merges_cleanly() {
+        let base = doc(vec![req("REQ-004", "old text"), req("REQ-005", "other text")]);
+        let ours = doc(vec![req("REQ-004", "new text"), req("REQ-005", "other text")]);
+        let theirs = doc(vec![req("REQ-004", "old text"), req("REQ-005", "changed text")]);
+
+        let result = merge(&base, &ours, &theirs);
+        assert!(result.is_clean());
+        assert_eq!(result.document.requirement("REQ-004").unwrap().text, "new text");
+        assert_eq!(result.document.requirement("REQ-005").unwrap().text, "changed text");
+    }
+
+    #[test]
+    fn a_requirement_added_only_by_theirs_is_kept() {
+        let base = doc(vec![req("REQ-004", "text")]);
+        let ours = doc(vec![req("REQ-004", "text")]);
+        let theirs = doc(vec![req("REQ-004", "text"), req("REQ-005", "new requirement")]);
+
+        let result = merge(&base, &ours, &theirs);
+        assert!(result.is_clean());
+        assert!(result.document.requirement("REQ-005").is_some());
+    }
+
+    #[test]
+    fn a_requirement_removed_only_by_ours_stays_removed() {
+        let base = doc(vec![req("REQ-004", "text"), req("REQ-005", "text")]);
+        let ours = doc(vec![req("REQ-005", "text")]);
+        let theirs = doc(vec![req("REQ-004", "text"), req("REQ-005", "text")]);
+
+        let result = merge(&base, &ours, &theirs);
+        assert!(result.is_clean());
+        assert!(result.document.requirement("REQ-004").is_none());
+    }
+
+    #[test]
+    fn both_sides_editing_the_same_requirement_differently_conflicts() {
+        let base = doc(vec![req("REQ-004", "old text")]);
+        let ours = doc(vec![req("REQ-004", "ours text")]);
+        let theirs = doc(vec![req("REQ-004", "theirs text")]);
+
+        let result = merge(&base, &ours, &theirs);
+        assert!(!result.is_clean());
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].id, "REQ-004");
+        // Ours wins in the working document pending manual resolution.
+        assert_eq!(result.document.requirement("REQ-004").unwrap().text, "ours text");
+    }
+
+    #[test]
+    fn ours_editing_and_theirs_removing_the_same_requirement_conflicts() {
+        let base = doc(vec![req("REQ-004", "old text")]);
+        let ours = doc(vec![req("REQ-004", "ours text")]);
+        let theirs = doc(vec![]);
+
+        let result = merge(&base, &ours, &theirs);
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].theirs, None);
+    }
+
+    #[test]
+    fn identical_edits_on_both_sides_merge_cleanly() {
+        let base = doc(vec![req("REQ-004", "old text")]);
+        let ours = doc(vec![req("REQ-004", "same new text")]);
+        let theirs = doc(vec![req("REQ-004", "same new text")]);
+
+        let result = merge(&base, &ours, &theirs);
+        assert!(result.is_clean());
+        assert_eq!(result.document.requirement("REQ-004").unwrap().text, "same new text");
+    }
+
+    #[test]
+    fn constraints_merge_the_same_way_as_requirements() {
+        let mut base = SpecDocument::new();
+        base.constraints.push(Constraint { code: "CONST-001".into(), text: "old".into(), ..Default::default() });
+        let mut ours = base.clone();
+        ours.constraints[0].text = "new".into();
+        let theirs = base.clone();
+
+        let result = merge(&base, &ours, &theirs);
+        assert!(result.is_clean());
+        assert_eq!(result.document.constraint("CONST-001").unwrap().text, "new");
+    }
+}
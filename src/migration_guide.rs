@@ -0,0 +1,206 @@
+//! Renders a [`crate::diff::SpecDiff`] as a Markdown migration guide
+//! focused on what actually breaks an implementation: walks every
+//! breaking requirement/constraint change, names the affected trait
+//! method ([`method_name`]) or error code ([`pascal_case_code`]), and
+//! leaves a `TODO` for whatever manual migration note [`crate::diff`]
+//! can't infer on its own. Unlike [`crate::changelog`], which documents
+//! every change for release notes, this only covers what
+//! [`classify`](crate::diff::classify) calls
+//! [`ChangeLevel::Breaking`](crate::diff::ChangeLevel::Breaking).
+
+use crate::codegen::{method_name, pascal_case_code};
+use crate::diff::{self, ChangeLevel, ConstraintChange, RequirementChange, SpecDiff, Supersession};
+use crate::spec::{Constraint, Requirement};
+
+/// Renders `diff` as a Markdown migration guide. Returns `"No breaking
+/// changes.\n"` if `diff` doesn't [`classify`](diff::classify) as
+/// [`ChangeLevel::Breaking`].
+pub fn render_markdown(spec_diff: &SpecDiff) -> String {
+    if diff::classify(spec_diff) != ChangeLevel::Breaking {
+        return "No breaking changes.\n".to_string();
+    }
+
+    let mut methods = Vec::new();
+    for req in &spec_diff.removed_requirements {
+        methods.push(format_removed_requirement(req));
+    }
+    for change in spec_diff.modified_requirements.iter().filter(|c| c.signature_changed) {
+        methods.push(format_requirement_signature_change(change));
+    }
+    for supersession in spec_diff.superseded_requirements.iter().filter(|s| s.signature_changed) {
+        methods.push(format_supersession_signature_change(supersession));
+    }
+
+    let mut error_codes = Vec::new();
+    for c in &spec_diff.removed_constraints {
+        error_codes.push(format_removed_constraint(c));
+    }
+    for change in spec_diff
+        .modified_constraints
+        .iter()
+        .filter(|c| c.strictness == diff::StrictnessChange::Tightened)
+    {
+        error_codes.push(format_tightened_constraint(change));
+    }
+
+    let mut out = String::from("# Migration guide\n\n");
+    if !methods.is_empty() {
+        out.push_str("## Affected trait methods\n\n");
+        for bullet in methods {
+            out.push_str(&bullet);
+        }
+        out.push('\n');
+    }
+    if !error_codes.is_empty() {
+        out.push_str("## Error code changes\n\n");
+        for bullet in error_codes {
+            out.push_str(&bullet);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn format_removed_requirement(req: &Requirement) -> String {
+    format!(
+        "- **Removed** `{}()` ({})\n  - TODO: describe the migration path for callers relying on this.\n",
+        method_name(req),
+        req.id
+    )
+}
+
+fn format_requirement_signature_change(change: &RequirementChange) -> String {
+    format!(
+        "- **Renamed** `{}()` -> `{}()` ({})\n  - TODO: describe the migration path for callers relying on this.\n",
+        method_name(&change.before),
+        method_name(&change.after),
+        change.id
+    )
+}
+
+fn format_supersession_signature_change(supersession: &Supersession) -> String {
+    format!(
+        "- **Superseded** `{}()` -> `{}()` ({} -> {})\n  - TODO: describe the migration path for callers relying on this.\n",
+        method_name(&supersession.before),
+        method_name(&supersession.after),
+        supersession.old_id,
+        supersession.new_id
+    )
+}
+
+fn format_removed_constraint(c: &Constraint) -> String {
+    format!(
+        "- **Removed** `{}` ({})\n  - TODO: describe the migration path for callers handling this error.\n",
+        pascal_case_code(&c.code),
+        c.code
+    )
+}
+
+fn format_tightened_constraint(change: &ConstraintChange) -> String {
+    format!(
+        "- **Tightened** `{}` ({})\n  - TODO: describe the migration path for callers handling this error.\n",
+        pascal_case_code(&change.code),
+        change.code
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Severity, SpecDocument};
+
+    fn req(id: &str, text: &str) -> Requirement {
+        Requirement {
+            id: id.into(),
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    fn constraint(code: &str, severity: Severity) -> Constraint {
+        Constraint {
+            code: code.into(),
+            text: "amount must be positive".into(),
+            severity,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reports_no_breaking_changes_for_a_purely_additive_diff() {
+        let before = SpecDocument::new();
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-004", "balance() returns the current balance"));
+
+        assert_eq!(render_markdown(&diff::diff(&before, &after)), "No breaking changes.\n");
+    }
+
+    #[test]
+    fn lists_a_removed_requirement_under_affected_trait_methods() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        let after = SpecDocument::new();
+
+        let rendered = render_markdown(&diff::diff(&before, &after));
+        assert!(rendered.contains("## Affected trait methods\n"));
+        assert!(rendered.contains("- **Removed** `balance()` (REQ-004)"));
+        assert!(rendered.contains("- TODO: describe the migration path for callers relying on this."));
+    }
+
+    #[test]
+    fn lists_a_renamed_method_with_both_names() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-004", "current_balance() returns the current balance"));
+
+        let rendered = render_markdown(&diff::diff(&before, &after));
+        assert!(rendered.contains("- **Renamed** `balance()` -> `current_balance()` (REQ-004)"));
+    }
+
+    #[test]
+    fn lists_a_renumbered_requirement_with_a_renamed_method_as_superseded() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(Requirement {
+            replaced_by: Some("REQ-012".into()),
+            ..req("REQ-004", "balance() returns the current balance")
+        });
+        let mut after = SpecDocument::new();
+        after.requirements.push(req("REQ-012", "current_balance() returns the current balance"));
+
+        let rendered = render_markdown(&diff::diff(&before, &after));
+        assert!(rendered.contains("- **Superseded** `balance()` -> `current_balance()` (REQ-004 -> REQ-012)"));
+    }
+
+    #[test]
+    fn lists_a_removed_constraint_under_error_code_changes() {
+        let mut before = SpecDocument::new();
+        before.constraints.push(constraint("CONST-002", Severity::Error));
+        let after = SpecDocument::new();
+
+        let rendered = render_markdown(&diff::diff(&before, &after));
+        assert!(rendered.contains("## Error code changes\n"));
+        assert!(rendered.contains("- **Removed** `Const002` (CONST-002)"));
+    }
+
+    #[test]
+    fn lists_a_tightened_constraint_under_error_code_changes() {
+        let mut before = SpecDocument::new();
+        before.constraints.push(constraint("CONST-002", Severity::Warning));
+        let mut after = SpecDocument::new();
+        after.constraints.push(constraint("CONST-002", Severity::Error));
+
+        let rendered = render_markdown(&diff::diff(&before, &after));
+        assert!(rendered.contains("- **Tightened** `Const002` (CONST-002)"));
+    }
+
+    #[test]
+    fn omits_the_error_code_section_when_only_requirements_broke() {
+        let mut before = SpecDocument::new();
+        before.requirements.push(req("REQ-004", "balance() returns the current balance"));
+        let after = SpecDocument::new();
+
+        let rendered = render_markdown(&diff::diff(&before, &after));
+        assert!(!rendered.contains("## Error code changes"));
+    }
+}
@@ -0,0 +1,279 @@
+//! Runtime collection of constraint *violations*, enriched with the
+//! operation inputs that triggered them, forwarded to one or more
+//! pluggable [`MonitorSink`]s. Complements [`crate::runtime`]'s
+//! pass/violate [`report`](crate::runtime::report) hook, which a
+//! deployment can already wire into its own telemetry but which only
+//! ever sees a constraint code and an outcome — [`Monitor`] carries the
+//! actual argument values a violating call was made with, which is far
+//! more useful for incident investigation, so every input passes through
+//! a redaction hook before a sink ever sees it.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+
+use crate::spec::Severity;
+
+/// One constraint violation and the inputs the operation was called
+/// with, after [`Monitor`]'s redaction hook has run over each value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub constraint: String,
+    pub severity: Severity,
+    pub inputs: Vec<(String, String)>,
+}
+
+/// Where a [`Monitor`] forwards every [`Violation`] it observes.
+/// Implement this to wire violation monitoring into whatever a project
+/// already uses for incident response (paging, a dashboard, a queue),
+/// the same extension point [`crate::runtime::Sink`] is for plain
+/// pass/violate events.
+pub trait MonitorSink: Send + Sync {
+    fn record(&self, violation: &Violation);
+}
+
+/// A redaction hook: given an input's name and its value, returns what a
+/// [`MonitorSink`] actually sees. Install one with
+/// [`Monitor::redact_with`] for any input whose value shouldn't reach a
+/// sink unmodified (account numbers, tokens, PII).
+pub type Redactor = Box<dyn Fn(&str, &str) -> String + Send + Sync>;
+
+/// Collects constraint violations and fans each one out to every
+/// registered [`MonitorSink`], same builder shape as
+/// [`crate::plugin::PluginRegistry`].
+pub struct Monitor {
+    sinks: Vec<Box<dyn MonitorSink>>,
+    redact: Option<Redactor>,
+}
+
+impl Monitor {
+    pub fn new() -> Self {
+        Monitor { sinks: Vec::new(), redact: None }
+    }
+
+    /// Adds `sink` to the set every future [`violation`](Monitor::violation)
+    /// call forwards to.
+    pub fn register(&mut self, sink: Box<dyn MonitorSink>) -> &mut Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Installs `redact` as the hook run over every input's value before a
+    /// [`Violation`] is built. Only the most recently installed hook takes
+    /// effect; without one, input values reach sinks unmodified.
+    pub fn redact_with(
+        &mut self,
+        redact: impl Fn(&str, &str) -> String + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.redact = Some(Box::new(redact));
+        self
+    }
+
+    /// Records a violation of `constraint` at `severity`, carrying the
+    /// operation's `inputs` as `(name, value)` pairs, and forwards it
+    /// (after redaction) to every registered sink.
+    pub fn violation(&self, constraint: &str, severity: Severity, inputs: &[(&str, &str)]) {
+        let inputs = inputs
+            .iter()
+            .map(|(name, value)| {
+                let value = match &self.redact {
+                    Some(redact) => redact(name, value),
+                    None => (*value).to_string(),
+                };
+                (name.to_string(), value)
+            })
+            .collect();
+        let violation = Violation {
+            constraint: constraint.to_string(),
+            severity,
+            inputs,
+        };
+        for sink in &self.sinks {
+            sink.record(&violation);
+        }
+    }
+}
+
+impl Default for Monitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A [`MonitorSink`] that appends one JSON line per [`Violation`] to a
+/// file, the same JSON-lines convention [`crate::trace`] uses for its
+/// on-disk artifacts. A write failure (missing directory, full disk) is
+/// swallowed rather than panicking the caller whose operation triggered
+/// the violation.
+pub struct LogFileSink {
+    path: PathBuf,
+}
+
+impl LogFileSink {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        LogFileSink { path: path.as_ref().to_path_buf() }
+    }
+}
+
+impl MonitorSink for LogFileSink {
+    fn record(&self, violation: &Violation) {
+        let Ok(line) = serde_json::to_string(&LoggedViolation::from(violation)) else {
+            return;
+        };
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&self.path) {
+            let _ = writeln!(file, "{line}");
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct LoggedViolation {
+    constraint: String,
+    severity: &'static str,
+    inputs: Vec<(String, String)>,
+}
+
+impl From<&Violation> for LoggedViolation {
+    fn from(violation: &Violation) -> Self {
+        LoggedViolation {
+            constraint: violation.constraint.clone(),
+            severity: crate::codegen::severity_variant(violation.severity),
+            inputs: violation.inputs.clone(),
+        }
+    }
+}
+
+/// A [`MonitorSink`] that sends every [`Violation`] down a channel, for a
+/// consumer that wants to process them off the calling thread (a
+/// dedicated alerting worker, an async task). Send failures (the
+/// receiver was dropped) are swallowed, same as [`LogFileSink`]'s write
+/// failures.
+pub struct ChannelSink {
+    sender: Sender<Violation>,
+}
+
+impl ChannelSink {
+    pub fn new(sender: Sender<Violation>) -> Self {
+        ChannelSink { sender }
+    }
+}
+
+impl MonitorSink for ChannelSink {
+    fn record(&self, violation: &Violation) {
+        let _ = self.sender.send(violation.clone());
+    }
+}
+
+/// A [`MonitorSink`] that invokes an arbitrary callback per [`Violation`],
+/// for the common case of wanting to log or alert with a closure instead
+/// of a dedicated [`MonitorSink`] type.
+pub struct CallbackSink<F: Fn(&Violation) + Send + Sync> {
+    callback: F,
+}
+
+impl<F: Fn(&Violation) + Send + Sync> CallbackSink<F> {
+    pub fn new(callback: F) -> Self {
+        CallbackSink { callback }
+    }
+}
+
+impl<F: Fn(&Violation) + Send + Sync> MonitorSink for CallbackSink<F> {
+    fn record(&self, violation: &Violation) {
+        (self.callback)(violation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn forwards_a_violation_to_every_registered_sink() {
+        let events_a = Arc::new(Mutex::new(Vec::new()));
+        let events_b = Arc::new(Mutex::new(Vec::new()));
+
+        let mut monitor = Monitor::new();
+        monitor.register(Box::new(CallbackSink::new({
+            let events_a = events_a.clone();
+            move |v: &Violation| events_a.lock().unwrap().push(v.clone())
+        })));
+        monitor.register(Box::new(CallbackSink::new({
+            let events_b = events_b.clone();
+            move |v: &Violation| events_b.lock().unwrap().push(v.clone())
+        })));
+
+        monitor.violation("CONST-001", Severity::Error, &[("amount", "-10")]);
+
+        assert_eq!(events_a.lock().unwrap().len(), 1);
+        assert_eq!(events_b.lock().unwrap().len(), 1);
+        assert_eq!(events_a.lock().unwrap()[0].constraint, "CONST-001");
+    }
+
+    #[test]
+    fn redacts_every_input_before_building_the_violation() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut monitor = Monitor::new();
+        monitor.redact_with(|name, value| {
+            if name == "account_number" {
+                "***".to_string()
+            } else {
+                value.to_string()
+            }
+        });
+        monitor.register(Box::new(CallbackSink::new({
+            let events = events.clone();
+            move |v: &Violation| events.lock().unwrap().push(v.clone())
+        })));
+
+        monitor.violation(
+            "CONST-002",
+            Severity::Error,
+            &[("account_number", "12345"), ("amount", "-10")],
+        );
+
+        let recorded = events.lock().unwrap();
+        assert_eq!(
+            recorded[0].inputs,
+            vec![
+                ("account_number".to_string(), "***".to_string()),
+                ("amount".to_string(), "-10".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn channel_sink_sends_a_violation_per_call() {
+        let (tx, rx) = mpsc::channel();
+        let mut monitor = Monitor::new();
+        monitor.register(Box::new(ChannelSink::new(tx)));
+
+        monitor.violation("CONST-003", Severity::Warning, &[]);
+
+        let received = rx.recv().unwrap();
+        assert_eq!(received.constraint, "CONST-003");
+        assert_eq!(received.severity, Severity::Warning);
+    }
+
+    #[test]
+    fn log_file_sink_appends_one_json_line_per_violation() {
+        let path = std::env::temp_dir().join(format!(
+            "libspec-monitor-test-{:?}.jsonl",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let mut monitor = Monitor::new();
+        monitor.register(Box::new(LogFileSink::new(&path)));
+        monitor.violation("CONST-004", Severity::Error, &[("amount", "-10")]);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 1);
+        assert!(contents.contains("\"constraint\":\"CONST-004\""));
+        assert!(contents.contains("\"amount\""));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
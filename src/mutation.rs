@@ -0,0 +1,255 @@
+//! Mutation testing for a [`StateMachine`]'s guards: mutates a transition's
+//! guard (flip every comparison, or drop the guard entirely) and replays
+//! [`ConformanceVector`]s against the mutant the same way [`crate::refinement`]
+//! replays an implementation trace against the real model, to see whether
+//! any vector's expected outcome changes. A mutant no vector catches is a
+//! surviving mutant: the conformance suite exercises that operation, but
+//! not the boundary this mutation would have broken — a measure of test
+//! strength [`crate::trace::coverage_gaps`]'s presence/absence tracking
+//! can't give, since it doesn't know the guard could be wrong and still
+//! pass.
+
+use std::collections::BTreeMap;
+
+use crate::refinement::attributed_requirements;
+use crate::spec::{Comparison, ConformanceVector, ConstraintExpr, RelOp, SpecDocument, StateMachine, Term};
+
+/// A way of mutating a guarded transition, tried by [`surviving_mutants`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutationKind {
+    /// Every comparison in the guard replaced with its strict/non-strict
+    /// counterpart (e.g. `amount <= balance` becomes `amount < balance`) —
+    /// the classic relational-operator-replacement mutant, an off-by-one
+    /// at the boundary rather than a full negation, so only a vector that
+    /// actually exercises the boundary value can kill it.
+    FlippedComparison,
+    /// The guard removed entirely, so the transition is always allowed.
+    DroppedGuard,
+}
+
+/// A mutant that every [`ConformanceVector`] exercising its transition
+/// still accepted or rejected exactly as the real model would have —
+/// nothing in the conformance suite would notice if this mutation were
+/// the actual bug.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurvivingMutant {
+    pub transition: String,
+    pub kind: MutationKind,
+    /// Ids of requirements whose text reads like this transition's name
+    /// (see [`crate::codegen::method_name`]) — whose conformance coverage
+    /// this surviving mutant says needs strengthening.
+    pub requirements: Vec<String>,
+}
+
+/// Mutates every guarded transition of `doc`'s state machine named
+/// `state_machine`, once per [`MutationKind`], and replays every
+/// [`ConformanceVector`] declared against that state machine against each
+/// mutant. Returns the mutants none of them killed, in transition order.
+/// A state machine with no such name yields no mutants.
+pub fn surviving_mutants(doc: &SpecDocument, state_machine: &str) -> Vec<SurvivingMutant> {
+    let Some(sm) = doc.state_machine(state_machine) else {
+        return Vec::new();
+    };
+    let vectors: Vec<&ConformanceVector> =
+        doc.conformance_vectors.iter().filter(|v| v.state_machine == state_machine).collect();
+
+    let mut survivors = Vec::new();
+    for transition in &sm.transitions {
+        let Some(guard) = &transition.guard else {
+            continue;
+        };
+        let Ok(expr) = ConstraintExpr::parse(guard) else {
+            continue;
+        };
+
+        for kind in [MutationKind::FlippedComparison, MutationKind::DroppedGuard] {
+            let mutant_guard = match kind {
+                MutationKind::FlippedComparison => Some(flip_comparisons(&expr).to_string()),
+                MutationKind::DroppedGuard => None,
+            };
+            let mut mutated = sm.clone();
+            if let Some(t) = mutated.transitions.iter_mut().find(|t| t.name == transition.name) {
+                t.guard = mutant_guard;
+            }
+
+            if vectors.iter().all(|vector| !kills(&mutated, vector)) {
+                survivors.push(SurvivingMutant {
+                    transition: transition.name.clone(),
+                    kind,
+                    requirements: attributed_requirements(doc, &transition.name),
+                });
+            }
+        }
+    }
+    survivors
+}
+
+/// Replays `vector`'s steps against `sm`, maintaining its state through
+/// each step's effect the same way [`crate::refinement::check`] does, and
+/// returns `true` as soon as a step's expected outcome
+/// (`expect_error.is_none()`) disagrees with what `sm`'s (possibly
+/// mutated) guard predicts.
+fn kills(sm: &StateMachine, vector: &ConformanceVector) -> bool {
+    let mut state = sm.state.clone();
+    for step in &vector.steps {
+        let Some(transition) = sm.transitions.iter().find(|t| t.name == step.operation) else {
+            return false;
+        };
+
+        let mut env = state.clone();
+        env.extend(step.params.iter().map(|(k, v)| (k.clone(), *v)));
+
+        let allowed = match &transition.guard {
+            None => Some(true),
+            Some(guard) => ConstraintExpr::parse(guard).ok().and_then(|expr| expr.eval(&env)),
+        };
+        let Some(allowed) = allowed else {
+            return false;
+        };
+
+        if allowed != step.expect_error.is_none() {
+            return true;
+        }
+
+        if allowed {
+            let mut next = BTreeMap::new();
+            for (field, effect) in &transition.effect {
+                if let Some(value) = Term::parse(effect).ok().and_then(|term| term.eval(&env)) {
+                    next.insert(field.clone(), value);
+                }
+            }
+            state.extend(next);
+        }
+    }
+    false
+}
+
+/// Replaces every leaf [`Comparison`]'s operator in `expr` with its
+/// strict/non-strict counterpart, leaving its `&&`/`||` structure and both
+/// sides alone.
+fn flip_comparisons(expr: &ConstraintExpr) -> ConstraintExpr {
+    match expr {
+        ConstraintExpr::Compare(c) => {
+            ConstraintExpr::Compare(Comparison { lhs: c.lhs.clone(), op: flip(c.op), rhs: c.rhs.clone() })
+        }
+        ConstraintExpr::And(a, b) => {
+            ConstraintExpr::And(Box::new(flip_comparisons(a)), Box::new(flip_comparisons(b)))
+        }
+        ConstraintExpr::Or(a, b) => ConstraintExpr::Or(Box::new(flip_comparisons(a)), Box::new(flip_comparisons(b))),
+    }
+}
+
+/// A relational operator's strict/non-strict counterpart, e.g. `<=`
+/// becomes `<` and `==` becomes `!=`.
+fn flip(op: RelOp) -> RelOp {
+    match op {
+        RelOp::Gt => RelOp::Ge,
+        RelOp::Ge => RelOp::Gt,
+        RelOp::Lt => RelOp::Le,
+        RelOp::Le => RelOp::Lt,
+        RelOp::Eq => RelOp::Ne,
+        RelOp::Ne => RelOp::Eq,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{ConformanceStep, Requirement, StateMachine, Transition};
+
+    fn account(doc: &mut SpecDocument) {
+        doc.state_machines.push(StateMachine {
+            name: "Account".into(),
+            state: [("balance".to_string(), 0.0)].into(),
+            transitions: vec![
+                Transition {
+                    name: "deposit".into(),
+                    params: vec!["amount".into()],
+                    guard: None,
+                    violates: None,
+                    effect: [("balance".to_string(), "balance + amount".to_string())].into(),
+                },
+                Transition {
+                    name: "withdraw".into(),
+                    params: vec!["amount".into()],
+                    guard: Some("amount <= balance".into()),
+                    violates: Some("CONST-002".into()),
+                    effect: [("balance".to_string(), "balance - amount".to_string())].into(),
+                },
+            ],
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-010".into(),
+            text: "withdraw(amount) rejects an overdraw".into(),
+            ..Default::default()
+        });
+    }
+
+    fn step(operation: &str, amount: f64, expect_error: Option<&str>) -> ConformanceStep {
+        ConformanceStep {
+            operation: operation.into(),
+            params: [("amount".to_string(), amount)].into(),
+            expect_error: expect_error.map(String::from),
+            expect_state: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn a_vector_exercising_the_boundary_kills_both_mutants() {
+        let mut doc = SpecDocument::new();
+        account(&mut doc);
+        doc.conformance_vectors.push(ConformanceVector {
+            name: "overdraw is rejected".into(),
+            state_machine: "Account".into(),
+            steps: vec![
+                step("deposit", 100.0, None),
+                // Withdrawing exactly the balance is the `<=` boundary:
+                // `<` (FlippedComparison) rejects it, but the spec expects
+                // success.
+                step("withdraw", 100.0, None),
+                // Balance is now 0: any withdrawal should be rejected,
+                // catching DroppedGuard (which would always allow it).
+                step("withdraw", 1.0, Some("CONST-002")),
+            ],
+        });
+
+        assert_eq!(surviving_mutants(&doc, "Account"), vec![]);
+    }
+
+    #[test]
+    fn a_vector_that_never_overdraws_lets_both_mutants_survive() {
+        let mut doc = SpecDocument::new();
+        account(&mut doc);
+        doc.conformance_vectors.push(ConformanceVector {
+            name: "ordinary deposit and withdraw".into(),
+            state_machine: "Account".into(),
+            steps: vec![step("deposit", 100.0, None), step("withdraw", 40.0, None)],
+        });
+
+        let survivors = surviving_mutants(&doc, "Account");
+        assert_eq!(survivors.len(), 2);
+        assert!(survivors.iter().all(|m| m.transition == "withdraw"));
+        assert!(survivors.iter().any(|m| m.kind == MutationKind::FlippedComparison));
+        assert!(survivors.iter().any(|m| m.kind == MutationKind::DroppedGuard));
+        assert_eq!(survivors[0].requirements, vec!["REQ-010".to_string()]);
+    }
+
+    #[test]
+    fn an_unguarded_transition_has_no_mutants() {
+        let mut doc = SpecDocument::new();
+        account(&mut doc);
+        doc.conformance_vectors.push(ConformanceVector {
+            name: "deposit only".into(),
+            state_machine: "Account".into(),
+            steps: vec![step("deposit", 100.0, None)],
+        });
+
+        assert!(surviving_mutants(&doc, "Account").iter().all(|m| m.transition != "deposit"));
+    }
+
+    #[test]
+    fn returns_no_mutants_for_an_undeclared_state_machine() {
+        let doc = SpecDocument::new();
+        assert_eq!(surviving_mutants(&doc, "Account"), vec![]);
+    }
+}
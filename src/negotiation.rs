@@ -0,0 +1,85 @@
+//! Picks the best version two sides of a spec'd API can agree to speak,
+//! from each side's list of versions it understands — formalizes what a
+//! hand-written `version()` method returning one hardcoded string can't
+//! express: a client and server evolving independently, each still
+//! supporting a range of past versions. Generated traits declare a
+//! `supported_versions` method (see [`crate::codegen::rust_trait`]) an
+//! implementation fills in with the list [`negotiate`] picks from.
+
+use std::fmt;
+
+/// A parsed `major.minor.patch` version, ordered the usual way. Missing
+/// trailing components default to `0`, so `"1"` and `"1.0.0"` parse equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    pub fn parse(s: &str) -> Self {
+        let mut parts = s.split('.').map(|part| part.parse().unwrap_or(0));
+        Version {
+            major: parts.next().unwrap_or(0),
+            minor: parts.next().unwrap_or(0),
+            patch: parts.next().unwrap_or(0),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Picks the highest version present in both `client_versions` and
+/// `server_versions`, comparing them as [`Version`]s so `"2"` outranks
+/// `"1.9.9"`. Returns `None` if the two sides share no version, meaning
+/// the client and server can't talk to each other at all.
+pub fn negotiate(client_versions: &[&str], server_versions: &[&str]) -> Option<Version> {
+    client_versions
+        .iter()
+        .filter(|v| server_versions.contains(v))
+        .map(|v| Version::parse(v))
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_full_semver_string() {
+        assert_eq!(Version::parse("1.2.3"), Version { major: 1, minor: 2, patch: 3 });
+    }
+
+    #[test]
+    fn treats_missing_components_as_zero() {
+        assert_eq!(Version::parse("2"), Version { major: 2, minor: 0, patch: 0 });
+    }
+
+    #[test]
+    fn displays_as_major_minor_patch() {
+        assert_eq!(Version::parse("1.2.3").to_string(), "1.2.3");
+        assert_eq!(Version::parse("2").to_string(), "2.0.0");
+    }
+
+    #[test]
+    fn picks_the_highest_mutually_supported_version() {
+        let negotiated = negotiate(&["1.0.0", "1.1.0", "2.0.0"], &["1.1.0", "2.0.0"]);
+        assert_eq!(negotiated, Some(Version::parse("2.0.0")));
+    }
+
+    #[test]
+    fn returns_none_when_the_versions_do_not_overlap() {
+        assert_eq!(negotiate(&["1.0.0"], &["2.0.0"]), None);
+    }
+
+    #[test]
+    fn picks_the_highest_among_mixed_bare_and_full_version_strings() {
+        let negotiated = negotiate(&["1", "1.5.0"], &["1", "1.5.0"]);
+        assert_eq!(negotiated, Some(Version::parse("1.5.0")));
+    }
+}
@@ -0,0 +1,24 @@
+//! Thread-pool plumbing shared by every pass this crate parallelizes
+//! behind the `parallel` feature ([`crate::lint::lint`],
+//! [`crate::trace::scan`], [`crate::diff::diff`]): builds a scoped
+//! [`rayon::ThreadPool`] sized from an explicit thread count, so
+//! `libspec.toml`'s `threads` knob (see
+//! [`crate::trace::CoveragePolicy::threads`]) governs every one of them
+//! the same way, instead of each pass reading its own copy of the config.
+//! Deliberately not rayon's process-global pool, which can only be
+//! configured once per process — not safe to assume here, since a caller
+//! (or a test suite) may run one of these passes more than once with a
+//! different thread count.
+
+/// Runs `f` inside a [`rayon::ThreadPool`] sized by `threads` (rayon's own
+/// default, the number of logical CPUs, if `None`).
+pub(crate) fn run<R: Send>(threads: Option<usize>, f: impl FnOnce() -> R + Send) -> R {
+    let mut builder = rayon::ThreadPoolBuilder::new();
+    if let Some(threads) = threads {
+        builder = builder.num_threads(threads);
+    }
+    builder
+        .build()
+        .expect("rayon thread pool construction is infallible for a valid thread count")
+        .install(f)
+}
@@ -0,0 +1,177 @@
+//! An extension point for checks, generators, and report sinks that don't
+//! belong upstream: a [`SpecPlugin`] implements any combination of a lint
+//! pass, a codegen backend, and a report sink, and is added to a
+//! [`PluginRegistry`] that runs every registered plugin's hooks alongside
+//! (not instead of) the crate's built-ins — an organization adds
+//! proprietary rules or a house codegen target without forking `libspec`.
+//!
+//! This module only defines the trait and an in-process registry. Loading
+//! plugins from a dynamic library is deliberately out of scope here: a
+//! caller that wants that can implement [`SpecPlugin`] for a type backed by
+//! an `libloading`-opened `.so`/`.dll` and register it the same way.
+
+use serde_json::Value;
+
+use crate::lint::LintIssue;
+use crate::spec::SpecDocument;
+
+/// A unit of pluggable behavior in the `cargo spec` pipeline. Every method
+/// has a default no-op, so a plugin implements only the hooks it needs.
+pub trait SpecPlugin {
+    /// A short, stable name identifying this plugin in `--format json`
+    /// output and generated file names.
+    fn name(&self) -> &str;
+
+    /// Runs this plugin's lint pass over `doc`, in addition to
+    /// [`crate::lint::lint`]'s built-in rules.
+    fn lint(&self, _doc: &SpecDocument) -> Vec<LintIssue> {
+        Vec::new()
+    }
+
+    /// Generates code for `doc`, or `None` if this plugin isn't a codegen
+    /// backend (or has nothing to emit for this particular doc).
+    fn generate(&self, _doc: &SpecDocument) -> Option<String> {
+        None
+    }
+
+    /// Sinks an already-built report (e.g. from [`crate::json_report::check`])
+    /// wherever this plugin sends it — a webhook, a metrics backend, a
+    /// second file on disk. Takes `&doc` alongside the report so a sink
+    /// that only cares about part of it doesn't have to re-parse JSON.
+    fn report(&self, _doc: &SpecDocument, _report: &Value) {}
+}
+
+/// Holds registered plugins and fans a pipeline stage's call out to every
+/// plugin that implements it, in registration order.
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: Vec<Box<dyn SpecPlugin>>,
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a plugin to the registry.
+    pub fn register(&mut self, plugin: Box<dyn SpecPlugin>) -> &mut Self {
+        self.plugins.push(plugin);
+        self
+    }
+
+    /// Combined lint findings from every registered plugin.
+    pub fn lint(&self, doc: &SpecDocument) -> Vec<LintIssue> {
+        self.plugins.iter().flat_map(|plugin| plugin.lint(doc)).collect()
+    }
+
+    /// Generated code from every plugin that produced any for `doc`,
+    /// paired with the plugin's name so a caller can write each to its own
+    /// output file.
+    pub fn generate(&self, doc: &SpecDocument) -> Vec<(String, String)> {
+        self.plugins
+            .iter()
+            .filter_map(|plugin| plugin.generate(doc).map(|code| (plugin.name().to_string(), code)))
+            .collect()
+    }
+
+    /// Hands `report` to every registered plugin's sink.
+    pub fn report(&self, doc: &SpecDocument, report: &Value) {
+        for plugin in &self.plugins {
+            plugin.report(doc, report);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    struct Echo;
+
+    impl SpecPlugin for Echo {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn lint(&self, doc: &SpecDocument) -> Vec<LintIssue> {
+            vec![LintIssue {
+                rule: "echo-plugin",
+                message: format!("doc has {} requirement(s)", doc.requirements.len()),
+            }]
+        }
+
+        fn generate(&self, doc: &SpecDocument) -> Option<String> {
+            Some(format!("// {} requirement(s)", doc.requirements.len()))
+        }
+    }
+
+    struct Silent;
+
+    impl SpecPlugin for Silent {
+        fn name(&self) -> &str {
+            "silent"
+        }
+    }
+
+    #[test]
+    fn an_unregistered_registry_contributes_nothing() {
+        let registry = PluginRegistry::new();
+        assert!(registry.lint(&SpecDocument::new()).is_empty());
+        assert!(registry.generate(&SpecDocument::new()).is_empty());
+    }
+
+    #[test]
+    fn runs_every_registered_plugins_lint_pass() {
+        let mut registry = PluginRegistry::new();
+        registry.register(Box::new(Echo));
+        registry.register(Box::new(Silent));
+
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-1".into(),
+            text: "fine".into(),
+            ..Default::default()
+        });
+
+        let issues = registry.lint(&doc);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, "echo-plugin");
+        assert_eq!(issues[0].message, "doc has 1 requirement(s)");
+    }
+
+    #[test]
+    fn generate_skips_plugins_that_have_nothing_to_emit() {
+        let mut registry = PluginRegistry::new();
+        registry.register(Box::new(Echo));
+        registry.register(Box::new(Silent));
+
+        let generated = registry.generate(&SpecDocument::new());
+        assert_eq!(generated, vec![("echo".to_string(), "// 0 requirement(s)".to_string())]);
+    }
+
+    #[test]
+    fn report_reaches_every_registered_sink() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        struct Recorder(Rc<RefCell<Vec<Value>>>);
+
+        impl SpecPlugin for Recorder {
+            fn name(&self) -> &str {
+                "recorder"
+            }
+
+            fn report(&self, _doc: &SpecDocument, report: &Value) {
+                self.0.borrow_mut().push(report.clone());
+            }
+        }
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut registry = PluginRegistry::new();
+        registry.register(Box::new(Recorder(seen.clone())));
+
+        registry.report(&SpecDocument::new(), &serde_json::json!({"ok": true}));
+        assert_eq!(seen.borrow().len(), 1);
+    }
+}
@@ -0,0 +1,194 @@
+//! A minimal in-memory incremental-computation core: for a caller that
+//! recomputes derived data (a parsed [`SpecDocument`](crate::spec::SpecDocument),
+//! a [`trace::CoverageMatrix`](crate::trace::CoverageMatrix), a lineage
+//! [`graph`](crate::graph)) from the same handful of changing inputs many
+//! times over one process's life — the LSP on every keystroke, `cargo
+//! spec watch` on every save, a long-running `cargo spec` invocation
+//! across several subcommands — this lets each derived query skip
+//! recomputing whenever nothing it reads actually changed.
+//!
+//! This is a much smaller primitive than a real Salsa: there's no
+//! automatic dependency tracing. A query names the input keys it reads
+//! via `depends_on`; if a query reads an input it didn't name, a stale
+//! result can slip through silently, the same failure mode as a cache
+//! key that's missing a field. Unlike [`crate::cache::Cache`]'s on-disk,
+//! content-hash-keyed cache (built for a process that runs once and
+//! exits), a [`QueryEngine`] lives in memory for the process's whole
+//! life and is keyed by explicit input *revisions* rather than a hash of
+//! the input's bytes, so a no-op edit (saving unchanged text) still
+//! costs a revision bump and comparison, never a hash of the full input.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// A monotonically increasing version number for one input slot, bumped
+/// every time that slot is replaced via [`QueryEngine::set_input`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Revision(u64);
+
+struct InputSlot {
+    revision: Revision,
+    value: Box<dyn Any>,
+}
+
+struct Memo {
+    /// The revision of every input named in `depends_on`, as of the last
+    /// time this query ran.
+    seen: HashMap<String, Revision>,
+    value: Box<dyn Any>,
+}
+
+/// Named input slots plus the memoized result of every derived query run
+/// against them so far.
+#[derive(Default)]
+pub struct QueryEngine {
+    inputs: HashMap<String, InputSlot>,
+    memos: RefCell<HashMap<String, Memo>>,
+}
+
+impl QueryEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets input `key` to `value`, bumping its revision. Any memoized
+    /// query that named `key` in its `depends_on` recomputes the next
+    /// time it's called, even if `value` happens to equal the old one —
+    /// this engine compares revisions, not values.
+    pub fn set_input<T: 'static>(&mut self, key: &str, value: T) {
+        let revision = match self.inputs.get(key) {
+            Some(slot) => Revision(slot.revision.0 + 1),
+            None => Revision(0),
+        };
+        self.inputs.insert(
+            key.to_string(),
+            InputSlot {
+                revision,
+                value: Box::new(value),
+            },
+        );
+    }
+
+    /// The current value of input `key`, if it's been set and was set
+    /// with this same type `T`.
+    pub fn input<T: 'static + Clone>(&self, key: &str) -> Option<T> {
+        self.inputs.get(key)?.value.downcast_ref::<T>().cloned()
+    }
+
+    /// The current revision of `key`, or `Revision(0)` if it's never
+    /// been set — so a query depending on a not-yet-set input still has
+    /// a stable revision to memoize against.
+    fn revision_of(&self, key: &str) -> Revision {
+        self.inputs.get(key).map_or(Revision(0), |slot| slot.revision)
+    }
+
+    /// Returns the memoized result of `query_name`, recomputing it with
+    /// `compute` if this is the first call or if any input named in
+    /// `depends_on` has moved to a new revision since the last call.
+    pub fn query<T: 'static + Clone>(
+        &self,
+        query_name: &str,
+        depends_on: &[&str],
+        compute: impl FnOnce() -> T,
+    ) -> T {
+        let current: HashMap<String, Revision> = depends_on
+            .iter()
+            .map(|key| (key.to_string(), self.revision_of(key)))
+            .collect();
+
+        if let Some(memo) = self.memos.borrow().get(query_name) {
+            if memo.seen == current {
+                if let Some(value) = memo.value.downcast_ref::<T>() {
+                    return value.clone();
+                }
+            }
+        }
+
+        let value = compute();
+        self.memos.borrow_mut().insert(
+            query_name.to_string(),
+            Memo {
+                seen: current,
+                value: Box::new(value.clone()),
+            },
+        );
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn recomputes_on_the_first_call() {
+        let engine = QueryEngine::new();
+        let calls = Cell::new(0);
+        let result = engine.query("doubled", &[], || {
+            calls.set(calls.get() + 1);
+            42
+        });
+        assert_eq!(result, 42);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn skips_recompute_when_no_dependency_changed() {
+        let mut engine = QueryEngine::new();
+        engine.set_input("n", 1);
+        let calls = Cell::new(0);
+
+        for _ in 0..3 {
+            engine.query("doubled", &["n"], || {
+                calls.set(calls.get() + 1);
+                engine.input::<i32>("n").unwrap_or(0) * 2
+            });
+        }
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn recomputes_after_a_dependency_changes() {
+        let mut engine = QueryEngine::new();
+        engine.set_input("n", 1);
+        let calls = Cell::new(0);
+
+        let compute = |engine: &QueryEngine| {
+            engine.query("doubled", &["n"], || {
+                calls.set(calls.get() + 1);
+                engine.input::<i32>("n").unwrap_or(0) * 2
+            })
+        };
+
+        assert_eq!(compute(&engine), 2);
+        assert_eq!(compute(&engine), 2);
+        assert_eq!(calls.get(), 1);
+
+        engine.set_input("n", 5);
+        assert_eq!(compute(&engine), 10);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn ignores_an_unrelated_input_changing() {
+        let mut engine = QueryEngine::new();
+        engine.set_input("n", 1);
+        engine.set_input("unrelated", "a".to_string());
+        let calls = Cell::new(0);
+
+        let compute = |engine: &QueryEngine| {
+            engine.query("doubled", &["n"], || {
+                calls.set(calls.get() + 1);
+                engine.input::<i32>("n").unwrap_or(0) * 2
+            })
+        };
+        compute(&engine);
+        engine.set_input("unrelated", "b".to_string());
+        compute(&engine);
+
+        assert_eq!(calls.get(), 1);
+    }
+}
@@ -0,0 +1,251 @@
+//! Checks that a recorded implementation trace *refines* an abstract
+//! [`StateMachine`] model: replays each recorded step against the model,
+//! maintaining the model's own state, and compares what the model
+//! predicts (would this step's `guard` allow it, and if not, which
+//! constraint does it violate) against what the implementation actually
+//! did. Complements [`crate::monitor`] (captures violations as they
+//! happen, with no model to check them against) and
+//! `libspec_harness::Replayer` (replays a recording against a live
+//! implementation, with no abstract model in the loop at all).
+
+use std::collections::BTreeMap;
+
+use crate::codegen::method_name;
+use crate::spec::{ConstraintExpr, SpecDocument, Term, Transition};
+
+/// One step of a recorded implementation trace: the transition name, its
+/// params, and whether the implementation accepted or rejected it (and
+/// with which constraint code, if rejected).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceStep {
+    pub operation: String,
+    pub params: BTreeMap<String, f64>,
+    pub outcome: Result<(), String>,
+}
+
+/// How a [`TraceStep`] failed to refine the model, found by [`check`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Divergence {
+    /// The model has no transition with this name.
+    UnknownOperation,
+    /// The model's `guard` couldn't be evaluated — it failed to parse,
+    /// or mentions a state field/param the step doesn't have a value
+    /// for.
+    NotEvaluable,
+    /// The model's guard allowed this step but the implementation
+    /// rejected it, or vice versa.
+    OutcomeMismatch { model_allowed: bool },
+}
+
+/// The first recorded step that doesn't refine the model, found by
+/// [`check`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonConformingTransition {
+    /// This step's position in the trace.
+    pub index: usize,
+    pub operation: String,
+    pub divergence: Divergence,
+    /// The constraint this transition [`violates`](crate::spec::Transition::violates),
+    /// if the model names one.
+    pub constraint: Option<String>,
+    /// Ids of requirements whose text reads like this transition's name
+    /// (see [`method_name`]) — the model's best-effort attribution of
+    /// the divergence back to the requirement it implements.
+    pub requirements: Vec<String>,
+}
+
+/// Replays `trace` against `doc`'s state machine named `state_machine`,
+/// starting from its declared initial state, and returns the first step
+/// that isn't a refinement of the model — an accepted step the model
+/// would have rejected, a rejected step the model would have allowed, or
+/// an operation/guard the model can't make sense of. `None` if every
+/// step in `trace` conforms, or if `doc` has no state machine by that
+/// name.
+pub fn check(doc: &SpecDocument, state_machine: &str, trace: &[TraceStep]) -> Option<NonConformingTransition> {
+    let sm = doc.state_machine(state_machine)?;
+    let mut state = sm.state.clone();
+
+    for (index, step) in trace.iter().enumerate() {
+        let Some(transition) = sm.transitions.iter().find(|t| t.name == step.operation) else {
+            return Some(NonConformingTransition {
+                index,
+                operation: step.operation.clone(),
+                divergence: Divergence::UnknownOperation,
+                constraint: None,
+                requirements: attributed_requirements(doc, &step.operation),
+            });
+        };
+
+        let mut env = state.clone();
+        env.extend(step.params.iter().map(|(k, v)| (k.clone(), *v)));
+
+        let allowed = match &transition.guard {
+            None => Some(true),
+            Some(guard) => ConstraintExpr::parse(guard).ok().and_then(|expr| expr.eval(&env)),
+        };
+        let Some(allowed) = allowed else {
+            return Some(NonConformingTransition {
+                index,
+                operation: step.operation.clone(),
+                divergence: Divergence::NotEvaluable,
+                constraint: violated_constraint(transition),
+                requirements: attributed_requirements(doc, &transition.name),
+            });
+        };
+
+        if allowed != step.outcome.is_ok() {
+            return Some(NonConformingTransition {
+                index,
+                operation: step.operation.clone(),
+                divergence: Divergence::OutcomeMismatch { model_allowed: allowed },
+                constraint: violated_constraint(transition),
+                requirements: attributed_requirements(doc, &transition.name),
+            });
+        }
+
+        if allowed {
+            let mut next = BTreeMap::new();
+            for (field, effect) in &transition.effect {
+                if let Some(value) = Term::parse(effect).ok().and_then(|term| term.eval(&env)) {
+                    next.insert(field.clone(), value);
+                }
+            }
+            state.extend(next);
+        }
+    }
+
+    None
+}
+
+/// The constraint `t`'s guard protects, falling back to the same
+/// `"{name}-guard"` convention [`crate::codegen::state_machine`] uses when
+/// `violates` is unset.
+fn violated_constraint(t: &Transition) -> Option<String> {
+    t.guard
+        .as_ref()
+        .map(|_| t.violates.clone().unwrap_or_else(|| format!("{}-guard", t.name)))
+}
+
+/// Ids of requirements whose text reads like `operation` was generated
+/// from (see [`method_name`]) — the same name a transition and the
+/// requirement describing it are expected to share. Shared with
+/// [`crate::mutation`], which attributes surviving mutants back to
+/// requirements the same way.
+pub(crate) fn attributed_requirements(doc: &SpecDocument, operation: &str) -> Vec<String> {
+    doc.requirements
+        .iter()
+        .filter(|req| method_name(req) == operation)
+        .map(|req| req.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Requirement, StateMachine, Transition};
+
+    fn account(doc: &mut SpecDocument) {
+        doc.state_machines.push(StateMachine {
+            name: "Account".into(),
+            state: [("balance".to_string(), 0.0)].into(),
+            transitions: vec![
+                Transition {
+                    name: "deposit".into(),
+                    params: vec!["amount".into()],
+                    guard: None,
+                    violates: None,
+                    effect: [("balance".to_string(), "balance + amount".to_string())].into(),
+                },
+                Transition {
+                    name: "withdraw".into(),
+                    params: vec!["amount".into()],
+                    guard: Some("amount <= balance".into()),
+                    violates: Some("CONST-002".into()),
+                    effect: [("balance".to_string(), "balance - amount".to_string())].into(),
+                },
+            ],
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-010".into(),
+            text: "withdraw(amount) rejects an overdraw".into(),
+            ..Default::default()
+        });
+    }
+
+    fn step(operation: &str, amount: f64, outcome: Result<(), String>) -> TraceStep {
+        TraceStep {
+            operation: operation.into(),
+            params: [("amount".to_string(), amount)].into(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn a_conforming_trace_has_no_divergence() {
+        let mut doc = SpecDocument::new();
+        account(&mut doc);
+        let trace = vec![
+            step("deposit", 100.0, Ok(())),
+            step("withdraw", 40.0, Ok(())),
+            step("withdraw", 1000.0, Err("CONST-002".into())),
+        ];
+
+        assert_eq!(check(&doc, "Account", &trace), None);
+    }
+
+    #[test]
+    fn flags_an_accepted_step_the_model_would_reject() {
+        let mut doc = SpecDocument::new();
+        account(&mut doc);
+        let trace = vec![step("withdraw", 1000.0, Ok(()))];
+
+        let divergence = check(&doc, "Account", &trace).unwrap();
+        assert_eq!(divergence.index, 0);
+        assert_eq!(divergence.divergence, Divergence::OutcomeMismatch { model_allowed: false });
+        assert_eq!(divergence.constraint.as_deref(), Some("CONST-002"));
+        assert_eq!(divergence.requirements, vec!["REQ-010".to_string()]);
+    }
+
+    #[test]
+    fn flags_a_rejected_step_the_model_would_allow() {
+        let mut doc = SpecDocument::new();
+        account(&mut doc);
+        let trace = vec![
+            step("deposit", 100.0, Ok(())),
+            step("withdraw", 10.0, Err("unexpected".into())),
+        ];
+
+        let divergence = check(&doc, "Account", &trace).unwrap();
+        assert_eq!(divergence.index, 1);
+        assert_eq!(divergence.divergence, Divergence::OutcomeMismatch { model_allowed: true });
+    }
+
+    #[test]
+    fn reports_only_the_first_divergence_in_the_trace() {
+        let mut doc = SpecDocument::new();
+        account(&mut doc);
+        let trace = vec![
+            step("withdraw", 5.0, Ok(())),   // diverges immediately: model rejects (balance 0)
+            step("withdraw", 5.0, Ok(())),   // would also diverge, but never reached
+        ];
+
+        let divergence = check(&doc, "Account", &trace).unwrap();
+        assert_eq!(divergence.index, 0);
+    }
+
+    #[test]
+    fn flags_an_unknown_operation() {
+        let mut doc = SpecDocument::new();
+        account(&mut doc);
+        let trace = vec![step("close", 0.0, Ok(()))];
+
+        let divergence = check(&doc, "Account", &trace).unwrap();
+        assert_eq!(divergence.divergence, Divergence::UnknownOperation);
+    }
+
+    #[test]
+    fn returns_none_for_an_undeclared_state_machine() {
+        let doc = SpecDocument::new();
+        assert_eq!(check(&doc, "Account", &[]), None);
+    }
+}
@@ -0,0 +1,242 @@
+//! Reviewer approvals of individual requirements, gating release reports
+//! the way [`crate::trace::CoverageMatrix::enforce`] gates on test
+//! coverage: [`record`] appends who approved a requirement and against
+//! which [`SpecDocument::version_hash`], and [`gate`] checks the current
+//! approvals against a [`ReviewPolicy`]'s `mandatory_tags`, flagging a
+//! requirement that's never been approved or was approved against a spec
+//! version that's since changed underneath it.
+
+use std::collections::BTreeSet;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::spec::SpecDocument;
+
+/// One reviewer's approval of `requirement` as it stood at `spec_hash`
+/// ([`SpecDocument::version_hash`]), recorded at `approved_at_unix`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Approval {
+    pub requirement: String,
+    pub reviewer: String,
+    pub spec_hash: String,
+    pub approved_at_unix: u64,
+}
+
+/// Where approvals are appended/read, unless overridden by
+/// `LIBSPEC_REVIEW_FILE` (set this in tests, so parallel test runs don't
+/// clobber each other's file).
+fn approval_file_path() -> PathBuf {
+    std::env::var("LIBSPEC_REVIEW_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("target/libspec-reviews.jsonl"))
+}
+
+/// Appends an approval of `requirement` by `reviewer`, pinned to `doc`'s
+/// current [`SpecDocument::version_hash`] so [`gate`] can tell a spec
+/// change since review from one that hasn't happened yet. Failures to
+/// write are swallowed, the same as [`crate::trace::record`]: a missing
+/// or unwritable review file shouldn't fail the review step recording it.
+pub fn record(doc: &SpecDocument, requirement: &str, reviewer: &str) {
+    let path = approval_file_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let approval = Approval {
+        requirement: requirement.to_string(),
+        reviewer: reviewer.to_string(),
+        spec_hash: doc.version_hash(),
+        approved_at_unix: now_unix(),
+    };
+    let Ok(line) = serde_json::to_string(&approval) else {
+        return;
+    };
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+/// Reads and parses every approval ever recorded, oldest first, skipping
+/// lines that aren't valid JSON.
+pub fn read_approvals() -> Vec<Approval> {
+    let Ok(contents) = std::fs::read_to_string(approval_file_path()) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// A requirement [`gate`] found unfit to release on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReviewGap {
+    /// No approval of any kind is on record for this requirement.
+    Missing { requirement: String },
+    /// The most recent approval on record was against a spec version
+    /// that's since changed.
+    Stale { requirement: String, approved_hash: String, current_hash: String },
+}
+
+/// Checks every requirement tagged with one of `mandatory_tags` against
+/// `approvals`, using `doc`'s current [`SpecDocument::version_hash`] to
+/// tell a still-good approval from a stale one. A requirement approved
+/// more than once is judged by its most recent approval.
+pub fn gate(doc: &SpecDocument, approvals: &[Approval], mandatory_tags: &BTreeSet<String>) -> Vec<ReviewGap> {
+    let current_hash = doc.version_hash();
+    let mut gaps = Vec::new();
+    for req in &doc.requirements {
+        if !req.tags.iter().any(|tag| mandatory_tags.contains(tag)) {
+            continue;
+        }
+        let latest = approvals
+            .iter()
+            .filter(|approval| approval.requirement == req.id)
+            .max_by_key(|approval| approval.approved_at_unix);
+        match latest {
+            None => gaps.push(ReviewGap::Missing { requirement: req.id.clone() }),
+            Some(approval) if approval.spec_hash != current_hash => gaps.push(ReviewGap::Stale {
+                requirement: req.id.clone(),
+                approved_hash: approval.spec_hash.clone(),
+                current_hash: current_hash.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    fn with_review_file<T>(f: impl FnOnce() -> T) -> T {
+        let path = std::env::temp_dir().join(format!(
+            "libspec-review-test-{:?}.jsonl",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        std::env::set_var("LIBSPEC_REVIEW_FILE", &path);
+        let result = f();
+        std::env::remove_var("LIBSPEC_REVIEW_FILE");
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+
+    fn doc_with(id: &str, tags: &[&str]) -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: id.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn appends_and_reads_back_an_approval() {
+        with_review_file(|| {
+            let doc = doc_with("REQ-004", &["mandatory"]);
+            record(&doc, "REQ-004", "alice");
+
+            let approvals = read_approvals();
+            assert_eq!(approvals.len(), 1);
+            assert_eq!(approvals[0].requirement, "REQ-004");
+            assert_eq!(approvals[0].reviewer, "alice");
+            assert_eq!(approvals[0].spec_hash, doc.version_hash());
+        });
+    }
+
+    #[test]
+    fn reading_a_missing_file_is_an_empty_list() {
+        with_review_file(|| {
+            assert_eq!(read_approvals(), Vec::new());
+        });
+    }
+
+    #[test]
+    fn gate_flags_an_unreviewed_mandatory_requirement() {
+        let doc = doc_with("REQ-004", &["mandatory"]);
+        let mandatory_tags = BTreeSet::from(["mandatory".to_string()]);
+
+        let gaps = gate(&doc, &[], &mandatory_tags);
+        assert_eq!(gaps, vec![ReviewGap::Missing { requirement: "REQ-004".to_string() }]);
+    }
+
+    #[test]
+    fn gate_ignores_requirements_without_a_mandatory_tag() {
+        let doc = doc_with("REQ-004", &["nice-to-have"]);
+        let mandatory_tags = BTreeSet::from(["mandatory".to_string()]);
+
+        assert_eq!(gate(&doc, &[], &mandatory_tags), Vec::new());
+    }
+
+    #[test]
+    fn gate_passes_a_requirement_approved_at_the_current_spec_version() {
+        let doc = doc_with("REQ-004", &["mandatory"]);
+        let mandatory_tags = BTreeSet::from(["mandatory".to_string()]);
+        let approvals = vec![Approval {
+            requirement: "REQ-004".to_string(),
+            reviewer: "alice".to_string(),
+            spec_hash: doc.version_hash(),
+            approved_at_unix: 1,
+        }];
+
+        assert_eq!(gate(&doc, &approvals, &mandatory_tags), Vec::new());
+    }
+
+    #[test]
+    fn gate_flags_an_approval_from_before_the_spec_changed() {
+        let doc = doc_with("REQ-004", &["mandatory"]);
+        let mandatory_tags = BTreeSet::from(["mandatory".to_string()]);
+        let approvals = vec![Approval {
+            requirement: "REQ-004".to_string(),
+            reviewer: "alice".to_string(),
+            spec_hash: "stale-hash".to_string(),
+            approved_at_unix: 1,
+        }];
+
+        let gaps = gate(&doc, &approvals, &mandatory_tags);
+        assert_eq!(
+            gaps,
+            vec![ReviewGap::Stale {
+                requirement: "REQ-004".to_string(),
+                approved_hash: "stale-hash".to_string(),
+                current_hash: doc.version_hash(),
+            }]
+        );
+    }
+
+    #[test]
+    fn gate_judges_by_the_most_recent_approval() {
+        let doc = doc_with("REQ-004", &["mandatory"]);
+        let mandatory_tags = BTreeSet::from(["mandatory".to_string()]);
+        let approvals = vec![
+            Approval {
+                requirement: "REQ-004".to_string(),
+                reviewer: "alice".to_string(),
+                spec_hash: "stale-hash".to_string(),
+                approved_at_unix: 1,
+            },
+            Approval {
+                requirement: "REQ-004".to_string(),
+                reviewer: "bob".to_string(),
+                spec_hash: doc.version_hash(),
+                approved_at_unix: 2,
+            },
+        ];
+
+        assert_eq!(gate(&doc, &approvals, &mandatory_tags), Vec::new());
+    }
+}
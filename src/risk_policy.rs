@@ -0,0 +1,151 @@
+//! Risk-based coverage policy: a [`RiskRating`] on its own is just a
+//! label until something demands more of a high-risk requirement than an
+//! unrated one. [`check`] does that, per a [`RiskPolicy`] configured in
+//! `libspec.toml` — a requirement whose [`RiskRating::overall`] meets or
+//! exceeds a configured threshold needs at least that many covering
+//! tests, and, at the top threshold, a non-test [`SignOff`] on top of
+//! them, since a test alone isn't independent evidence for the
+//! highest-risk requirements.
+
+use serde::Serialize;
+
+use crate::spec::{RiskLevel, SpecDocument};
+use crate::trace::{Record, RiskPolicy, SignOff};
+
+/// Why a requirement's [`RiskRating`](crate::spec::RiskRating) policy
+/// isn't satisfied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case", tag = "reason")]
+pub enum RiskViolation {
+    /// Fewer covering tests than the policy demands at this risk level.
+    InsufficientTests { required: usize, actual: usize },
+    /// No non-test sign-off on record, despite the policy requiring one
+    /// at this risk level.
+    MissingFormalCheck,
+}
+
+/// One requirement whose risk-based policy isn't satisfied.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RiskGap {
+    pub requirement: String,
+    pub violation: RiskViolation,
+}
+
+/// Checks every risk-rated requirement in `doc` against `policy`. A
+/// requirement with no [`Requirement::risk`](crate::spec::Requirement::risk)
+/// is unconstrained, the same as an unmentioned tag is under
+/// [`crate::trace::CoveragePolicy::min_tests_per_tag`].
+pub fn check(doc: &SpecDocument, records: &[Record], sign_offs: &[SignOff], policy: &RiskPolicy) -> Vec<RiskGap> {
+    let mut gaps = Vec::new();
+    for req in &doc.requirements {
+        let Some(rating) = req.risk else { continue };
+        let level = rating.overall();
+        if level < RiskLevel::Medium {
+            continue;
+        }
+
+        let required = if level == RiskLevel::High { policy.min_tests_at_high } else { policy.min_tests_at_medium };
+        let actual = records
+            .iter()
+            .filter(|r| r.kind == "covers" && r.requirement == req.id)
+            .count();
+        if actual < required {
+            gaps.push(RiskGap {
+                requirement: req.id.clone(),
+                violation: RiskViolation::InsufficientTests { required, actual },
+            });
+        }
+
+        if level == RiskLevel::High && policy.require_formal_check_at_high {
+            let has_sign_off = sign_offs.iter().any(|s| s.requirement == req.id);
+            if !has_sign_off {
+                gaps.push(RiskGap {
+                    requirement: req.id.clone(),
+                    violation: RiskViolation::MissingFormalCheck,
+                });
+            }
+        }
+    }
+    gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Requirement, RiskRating, VerificationMethod};
+
+    fn doc(req: Requirement) -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req);
+        doc
+    }
+
+    fn rated(id: &str, likelihood: RiskLevel, severity: RiskLevel) -> Requirement {
+        Requirement {
+            id: id.into(),
+            risk: Some(RiskRating { likelihood, severity }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn an_unrated_requirement_is_unconstrained() {
+        let req = Requirement { id: "REQ-001".into(), ..Default::default() };
+        let policy = RiskPolicy { min_tests_at_medium: 5, min_tests_at_high: 5, require_formal_check_at_high: true };
+        assert!(check(&doc(req), &[], &[], &policy).is_empty());
+    }
+
+    #[test]
+    fn a_low_risk_requirement_is_unconstrained() {
+        let req = rated("REQ-001", RiskLevel::Low, RiskLevel::Low);
+        let policy = RiskPolicy { min_tests_at_medium: 5, min_tests_at_high: 5, require_formal_check_at_high: true };
+        assert!(check(&doc(req), &[], &[], &policy).is_empty());
+    }
+
+    #[test]
+    fn flags_a_medium_risk_requirement_short_on_tests() {
+        let req = rated("REQ-001", RiskLevel::Medium, RiskLevel::Low);
+        let policy = RiskPolicy { min_tests_at_medium: 2, min_tests_at_high: 3, require_formal_check_at_high: false };
+
+        let gaps = check(&doc(req), &[], &[], &policy);
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].requirement, "REQ-001");
+        assert_eq!(gaps[0].violation, RiskViolation::InsufficientTests { required: 2, actual: 0 });
+    }
+
+    #[test]
+    fn a_high_risk_requirement_is_held_to_the_higher_threshold() {
+        let req = rated("REQ-001", RiskLevel::High, RiskLevel::Low);
+        let policy = RiskPolicy { min_tests_at_medium: 1, min_tests_at_high: 3, require_formal_check_at_high: false };
+        let records = vec![Record { kind: "covers".into(), function: "test_x".into(), requirement: "REQ-001".into() }];
+
+        let gaps = check(&doc(req), &records, &[], &policy);
+        assert_eq!(gaps, vec![RiskGap {
+            requirement: "REQ-001".into(),
+            violation: RiskViolation::InsufficientTests { required: 3, actual: 1 },
+        }]);
+    }
+
+    #[test]
+    fn a_high_risk_requirement_needs_a_formal_check_when_the_policy_demands_one() {
+        let req = rated("REQ-001", RiskLevel::High, RiskLevel::High);
+        let policy = RiskPolicy { min_tests_at_medium: 0, min_tests_at_high: 0, require_formal_check_at_high: true };
+
+        let gaps = check(&doc(req), &[], &[], &policy);
+        assert_eq!(gaps, vec![RiskGap { requirement: "REQ-001".into(), violation: RiskViolation::MissingFormalCheck }]);
+    }
+
+    #[test]
+    fn a_recorded_sign_off_satisfies_the_formal_check_requirement() {
+        let req = rated("REQ-001", RiskLevel::High, RiskLevel::High);
+        let policy = RiskPolicy { min_tests_at_medium: 0, min_tests_at_high: 0, require_formal_check_at_high: true };
+        let sign_offs = vec![SignOff {
+            requirement: "REQ-001".into(),
+            method: VerificationMethod::Analysis,
+            signed_by: "alice".into(),
+            note: "worked through the proof".into(),
+        }];
+
+        assert!(check(&doc(req), &[], &sign_offs, &policy).is_empty());
+    }
+}
@@ -0,0 +1,373 @@
+//! Opt-in runtime instrumentation for generated constraint checks (see
+//! [`crate::codegen::rust_dto`] and [`crate::codegen::rust_guard`]): each
+//! check reports a [`CheckEvent`] to a pluggable [`Sink`], so "which
+//! constraints actually fired in production or integration tests" can be
+//! answered directly, instead of inferred from which tests exist
+//! ([`crate::trace`]) or which ids appear in source
+//! ([`crate::trace::scan`]). The event's [`Severity`] also lets a sink
+//! apply a deployment's own policy for [`Severity::Warning`]/
+//! [`Severity::Advisory`] violations (log, alert, ignore) without the
+//! generated check itself having an opinion beyond "don't fail the call".
+//!
+//! Reporting is a no-op until a sink is registered with [`set_sink`], so
+//! generated code can call [`report`] unconditionally at negligible cost
+//! in a build that never opts in.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use crate::error::SpecError;
+use crate::spec::Severity;
+
+/// Whether a constraint check passed or was violated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Violated,
+}
+
+/// One constraint check firing at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckEvent {
+    pub constraint: String,
+    pub outcome: Outcome,
+    pub severity: Severity,
+}
+
+/// Where [`report`] sends [`CheckEvent`]s once one is registered with
+/// [`set_sink`]. Implement this to wire runtime coverage into whatever a
+/// project already uses for telemetry (metrics, logs, a trace file).
+pub trait Sink: Send + Sync {
+    fn record(&self, event: CheckEvent);
+}
+
+static SINK: OnceLock<Box<dyn Sink>> = OnceLock::new();
+
+/// Registers `sink` as the destination for every future [`report`] call.
+/// Only the first call takes effect; later calls are ignored, the same as
+/// `log`/`tracing`'s global subscriber registration.
+pub fn set_sink(sink: Box<dyn Sink>) {
+    let _ = SINK.set(sink);
+}
+
+/// Reports that `constraint` evaluated with `outcome` at `severity`. A
+/// no-op if no sink has been registered.
+pub fn report(constraint: &str, outcome: Outcome, severity: Severity) {
+    if let Some(sink) = SINK.get() {
+        sink.record(CheckEvent {
+            constraint: constraint.to_string(),
+            outcome,
+            severity,
+        });
+    }
+}
+
+/// How a contract check reacts to a violation. Selectable per [`Severity`]
+/// at compile time via the `contracts-hard`/`contracts-off` Cargo features
+/// and overridable at runtime with [`set_enforcement`] (e.g. to raise
+/// strictness during an incident without a redeploy).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum EnforcementMode {
+    /// A violation returns `Err` from [`enforce`], same as the check
+    /// failing the call outright.
+    Hard = 0,
+    /// A violation `debug_assert!`s: panics in a debug build, compiles to
+    /// nothing in a release one. [`enforce`] still returns `Ok`.
+    DebugAssert = 1,
+    /// A violation is only visible through [`report`]; [`enforce`] always
+    /// returns `Ok`.
+    Off = 2,
+}
+
+impl EnforcementMode {
+    fn from_u8(n: u8) -> Self {
+        match n {
+            0 => EnforcementMode::Hard,
+            1 => EnforcementMode::DebugAssert,
+            _ => EnforcementMode::Off,
+        }
+    }
+}
+
+#[cfg(feature = "contracts-hard")]
+const DEFAULT_ERROR_MODE: EnforcementMode = EnforcementMode::Hard;
+#[cfg(feature = "contracts-hard")]
+const DEFAULT_NON_ERROR_MODE: EnforcementMode = EnforcementMode::Hard;
+
+#[cfg(all(not(feature = "contracts-hard"), feature = "contracts-off"))]
+const DEFAULT_ERROR_MODE: EnforcementMode = EnforcementMode::Off;
+#[cfg(all(not(feature = "contracts-hard"), feature = "contracts-off"))]
+const DEFAULT_NON_ERROR_MODE: EnforcementMode = EnforcementMode::Off;
+
+#[cfg(not(any(feature = "contracts-hard", feature = "contracts-off")))]
+const DEFAULT_ERROR_MODE: EnforcementMode = EnforcementMode::Hard;
+#[cfg(not(any(feature = "contracts-hard", feature = "contracts-off")))]
+const DEFAULT_NON_ERROR_MODE: EnforcementMode = if cfg!(debug_assertions) {
+    EnforcementMode::DebugAssert
+} else {
+    EnforcementMode::Off
+};
+
+static ERROR_MODE: AtomicU8 = AtomicU8::new(DEFAULT_ERROR_MODE as u8);
+static WARNING_MODE: AtomicU8 = AtomicU8::new(DEFAULT_NON_ERROR_MODE as u8);
+static ADVISORY_MODE: AtomicU8 = AtomicU8::new(DEFAULT_NON_ERROR_MODE as u8);
+
+fn mode_atomic(severity: Severity) -> &'static AtomicU8 {
+    match severity {
+        Severity::Error => &ERROR_MODE,
+        Severity::Warning => &WARNING_MODE,
+        Severity::Advisory => &ADVISORY_MODE,
+    }
+}
+
+/// The [`EnforcementMode`] currently in effect for `severity`.
+pub fn enforcement_for(severity: Severity) -> EnforcementMode {
+    EnforcementMode::from_u8(mode_atomic(severity).load(Ordering::Relaxed))
+}
+
+/// Overrides the [`EnforcementMode`] for every future [`enforce`] call
+/// against `severity`, in this process. Unlike [`set_sink`] this can be
+/// called more than once, so a long-running binary can dial strictness up
+/// or down (e.g. from an admin endpoint) without restarting.
+pub fn set_enforcement(severity: Severity, mode: EnforcementMode) {
+    mode_atomic(severity).store(mode as u8, Ordering::Relaxed);
+}
+
+/// Reports `constraint`'s outcome (same as [`report`]) and reacts to a
+/// violation according to [`enforcement_for`]'s current mode for
+/// `severity`. Generated and woven contract checks (see
+/// [`crate::codegen::rust_guard`], [`crate::codegen::checked`],
+/// `libspec_macros::spec_requires`/`spec_ensures`) call this instead of
+/// hard-coding "`Severity::Error` returns `Err`", so a deployment's
+/// [`set_enforcement`] override actually takes effect.
+pub fn enforce(
+    constraint: &str,
+    message: &str,
+    severity: Severity,
+    violated: bool,
+) -> Result<(), SpecError> {
+    report(
+        constraint,
+        if violated { Outcome::Violated } else { Outcome::Passed },
+        severity,
+    );
+    if !violated {
+        return Ok(());
+    }
+    match enforcement_for(severity) {
+        EnforcementMode::Hard => Err(SpecError::new(constraint, message)),
+        EnforcementMode::DebugAssert => {
+            debug_assert!(false, "{constraint}: {message}");
+            Ok(())
+        }
+        EnforcementMode::Off => Ok(()),
+    }
+}
+
+/// A [`Sink`] that increments a `metrics` counter per constraint code and
+/// outcome, so an operator's existing metrics backend (Prometheus,
+/// StatsD, ...) can show which spec constraints are being hit in
+/// production without wiring up a bespoke [`Sink`] of their own. Requires
+/// the crate to have already installed a `metrics` recorder; this sink
+/// only emits `metrics::counter!` calls, same as any other instrumented
+/// call site.
+///
+/// ```
+/// # #[cfg(feature = "metrics")]
+/// # {
+/// libspec::runtime::set_sink(Box::new(libspec::runtime::MetricsSink));
+/// # }
+/// ```
+#[cfg(feature = "metrics")]
+pub struct MetricsSink;
+
+#[cfg(feature = "metrics")]
+impl Sink for MetricsSink {
+    fn record(&self, event: CheckEvent) {
+        let outcome = match event.outcome {
+            Outcome::Passed => "passed",
+            Outcome::Violated => "violated",
+        };
+        metrics::counter!(
+            "libspec_constraint_checks_total",
+            "constraint" => event.constraint,
+            "outcome" => outcome,
+        )
+        .increment(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    struct RecordingSink(Arc<Mutex<Vec<CheckEvent>>>);
+
+    impl Sink for RecordingSink {
+        fn record(&self, event: CheckEvent) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    // `SINK` is process-global, so only one test in this binary can
+    // register it; every test that needs a sink shares this one instead
+    // of racing to be first, and just clears its recorded events.
+    fn recording_sink() -> Arc<Mutex<Vec<CheckEvent>>> {
+        static EVENTS: OnceLock<Arc<Mutex<Vec<CheckEvent>>>> = OnceLock::new();
+        let events = EVENTS.get_or_init(|| {
+            let events = Arc::new(Mutex::new(Vec::new()));
+            set_sink(Box::new(RecordingSink(events.clone())));
+            events
+        });
+        events.lock().unwrap().clear();
+        events.clone()
+    }
+
+    #[test]
+    fn reports_reach_the_registered_sink() {
+        let events = recording_sink();
+        report("CONST-001", Outcome::Violated, Severity::Error);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![CheckEvent {
+                constraint: "CONST-001".into(),
+                outcome: Outcome::Violated,
+                severity: Severity::Error,
+            }]
+        );
+    }
+
+    // `set_enforcement` mutates process-global atomics, same caveat as
+    // `SINK` above; this lock serializes the enforcement tests against
+    // each other instead of racing under the default parallel test runner.
+    fn enforcement_test_lock() -> &'static Mutex<()> {
+        static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+        LOCK.get_or_init(|| Mutex::new(()))
+    }
+
+    #[test]
+    fn enforce_returns_err_in_hard_mode() {
+        let _guard = enforcement_test_lock()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        set_enforcement(Severity::Error, EnforcementMode::Hard);
+        let events = recording_sink();
+
+        let result = enforce("CONST-001", "amount must be positive", Severity::Error, true);
+
+        assert_eq!(result, Err(SpecError::new("CONST-001", "amount must be positive")));
+        assert_eq!(events.lock().unwrap().last().unwrap().outcome, Outcome::Violated);
+    }
+
+    #[test]
+    fn enforce_is_ok_in_off_mode_even_when_violated() {
+        let _guard = enforcement_test_lock()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        set_enforcement(Severity::Advisory, EnforcementMode::Off);
+
+        let result = enforce("CONST-002", "should stay under the soft cap", Severity::Advisory, true);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "CONST-003: bad state"))]
+    fn enforce_debug_asserts_on_violation_in_debug_assert_mode() {
+        let _guard = enforcement_test_lock()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        set_enforcement(Severity::Warning, EnforcementMode::DebugAssert);
+
+        let result = enforce("CONST-003", "bad state", Severity::Warning, true);
+
+        // In a release build `debug_assert!` compiles to nothing, so
+        // `enforce` returns `Ok`; in a debug build it panics before
+        // reaching this assertion, which `should_panic` above expects.
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, Ok(()));
+        #[cfg(debug_assertions)]
+        let _ = result;
+    }
+
+    #[test]
+    fn enforcement_for_reflects_the_override() {
+        let _guard = enforcement_test_lock()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        set_enforcement(Severity::Warning, EnforcementMode::Hard);
+        assert_eq!(enforcement_for(Severity::Warning), EnforcementMode::Hard);
+
+        set_enforcement(Severity::Warning, EnforcementMode::Off);
+        assert_eq!(enforcement_for(Severity::Warning), EnforcementMode::Off);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn metrics_sink_increments_a_counter_per_constraint_and_outcome() {
+        use metrics::{CounterFn, Key, Recorder};
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        struct CountingFn(AtomicU64);
+        impl CounterFn for CountingFn {
+            fn increment(&self, value: u64) {
+                self.0.fetch_add(value, Ordering::Relaxed);
+            }
+            fn absolute(&self, value: u64) {
+                self.0.store(value, Ordering::Relaxed);
+            }
+        }
+
+        struct CapturingRecorder(Arc<CountingFn>);
+        impl Recorder for CapturingRecorder {
+            fn describe_counter(
+                &self,
+                _: metrics::KeyName,
+                _: Option<metrics::Unit>,
+                _: metrics::SharedString,
+            ) {
+            }
+            fn describe_gauge(
+                &self,
+                _: metrics::KeyName,
+                _: Option<metrics::Unit>,
+                _: metrics::SharedString,
+            ) {
+            }
+            fn describe_histogram(
+                &self,
+                _: metrics::KeyName,
+                _: Option<metrics::Unit>,
+                _: metrics::SharedString,
+            ) {
+            }
+            fn register_counter(&self, _: &Key, _: &metrics::Metadata<'_>) -> metrics::Counter {
+                metrics::Counter::from_arc(self.0.clone())
+            }
+            fn register_gauge(&self, _: &Key, _: &metrics::Metadata<'_>) -> metrics::Gauge {
+                unimplemented!("MetricsSink only registers counters")
+            }
+            fn register_histogram(
+                &self,
+                _: &Key,
+                _: &metrics::Metadata<'_>,
+            ) -> metrics::Histogram {
+                unimplemented!("MetricsSink only registers counters")
+            }
+        }
+
+        let calls = Arc::new(CountingFn(AtomicU64::new(0)));
+        let recorder = CapturingRecorder(calls.clone());
+        metrics::with_local_recorder(&recorder, || {
+            MetricsSink.record(CheckEvent {
+                constraint: "CONST-001".into(),
+                outcome: Outcome::Violated,
+                severity: Severity::Error,
+            });
+        });
+
+        assert_eq!(calls.0.load(Ordering::Relaxed), 1);
+    }
+}
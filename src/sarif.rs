@@ -0,0 +1,223 @@
+//! Exports spec findings as [SARIF](https://sarifweb.azurewebsites.net/)
+//! 2.1.0: lint issues, dangling cross-references, and coverage gaps become
+//! `results`, so GitHub/GitLab code scanning renders them as annotations
+//! the same way it does a compiler warning. A finding about an id the
+//! source scanner also found a mention of (see [`crate::trace::scan`]) is
+//! anchored there instead of at the spec file, since that's the line an
+//! author would actually want to jump to.
+
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::graph;
+use crate::lint;
+use crate::spec::SpecDocument;
+use crate::trace::{coverage_gaps, Mention, Record};
+
+/// Runs lint, dangling-reference, and coverage-gap checks against `doc`
+/// and its trace `records`, and renders the combined findings as a SARIF
+/// log. `spec_path` is the artifact lint and dangling-reference results
+/// are attached to; `mentions` (from [`scan`]) lets coverage-gap results
+/// point at a source location instead, when the scanner found one for the
+/// requirement's id.
+pub fn report(doc: &SpecDocument, spec_path: &Path, records: &[Record], mentions: &[Mention]) -> Value {
+    let mut results = Vec::new();
+
+    for issue in lint::lint(doc) {
+        results.push(result(issue.rule, "warning", issue.to_string(), spec_path_location(spec_path)));
+    }
+
+    for reference in graph::dangling_references(doc) {
+        results.push(result(
+            "dangling-reference",
+            "error",
+            reference.to_string(),
+            spec_path_location(spec_path),
+        ));
+    }
+
+    let gaps = coverage_gaps(records);
+    for id in &gaps.tested_not_implemented {
+        results.push(result(
+            "missing-implementation",
+            "warning",
+            format!("requirement `{id}` has a test but no recorded implementation"),
+            location_for(id, spec_path, mentions),
+        ));
+    }
+    for id in &gaps.implemented_not_tested {
+        results.push(result(
+            "missing-test-coverage",
+            "warning",
+            format!("requirement `{id}` has an implementation but no recorded test"),
+            location_for(id, spec_path, mentions),
+        ));
+    }
+
+    json!({
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/main/Schemata/sarif-schema-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "libspec",
+                    "informationUri": "https://github.com/drhodes/libspec",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules(),
+                }
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// [`report`], pretty-printed.
+pub fn report_string(doc: &SpecDocument, spec_path: &Path, records: &[Record], mentions: &[Mention]) -> String {
+    serde_json::to_string_pretty(&report(doc, spec_path, records, mentions))
+        .expect("Value serialization is infallible")
+}
+
+fn result(rule_id: &str, level: &str, message: String, physical_location: Value) -> Value {
+    json!({
+        "ruleId": rule_id,
+        "level": level,
+        "message": { "text": message },
+        "locations": [{ "physicalLocation": physical_location }],
+    })
+}
+
+fn spec_path_location(spec_path: &Path) -> Value {
+    json!({
+        "artifactLocation": { "uri": spec_path.display().to_string() },
+        "region": { "startLine": 1 },
+    })
+}
+
+/// Where to point a coverage-gap result: the first mention the source
+/// scanner found for `id`, or `spec_path` if the scanner found none.
+fn location_for(id: &str, spec_path: &Path, mentions: &[Mention]) -> Value {
+    match mentions.iter().find(|m| m.id == id) {
+        Some(mention) => json!({
+            "artifactLocation": { "uri": mention.file.display().to_string() },
+            "region": { "startLine": mention.line },
+        }),
+        None => spec_path_location(spec_path),
+    }
+}
+
+fn rules() -> Vec<Value> {
+    vec![
+        rule("duplicate-requirement-id", "A requirement id is declared more than once."),
+        rule("duplicate-constraint-code", "A constraint code is declared more than once."),
+        rule("empty-text", "A requirement or constraint has no text."),
+        rule("dangling-reference", "A depends_on/refines/conflicts_with entry names an unknown id."),
+        rule(
+            "missing-implementation",
+            "A requirement has a recorded test but no recorded implementation.",
+        ),
+        rule(
+            "missing-test-coverage",
+            "A requirement has a recorded implementation but no recorded test.",
+        ),
+    ]
+}
+
+fn rule(id: &str, description: &str) -> Value {
+    json!({
+        "id": id,
+        "shortDescription": { "text": description },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+    use std::path::PathBuf;
+
+    fn doc_with_dangling_reference() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-1".into(),
+            text: "depends on something missing".into(),
+            depends_on: vec!["REQ-404".into()],
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn reports_dangling_references_against_the_spec_file() {
+        let doc = doc_with_dangling_reference();
+        let sarif = report(&doc, &PathBuf::from("spec.toml"), &[], &[]);
+
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        let finding = results
+            .iter()
+            .find(|r| r["ruleId"] == "dangling-reference")
+            .expect("dangling-reference result");
+        assert_eq!(
+            finding["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "spec.toml"
+        );
+    }
+
+    #[test]
+    fn anchors_coverage_gaps_to_a_scanned_mention_when_one_exists() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-4".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+        let records = vec![Record {
+            kind: "implements".into(),
+            function: "BankLibrary::balance".into(),
+            requirement: "REQ-4".into(),
+        }];
+        let mentions = vec![Mention {
+            id: "REQ-4".into(),
+            file: PathBuf::from("src/lib.rs"),
+            line: 42,
+        }];
+
+        let sarif = report(&doc, &PathBuf::from("spec.toml"), &records, &mentions);
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        let finding = results
+            .iter()
+            .find(|r| r["ruleId"] == "missing-test-coverage")
+            .expect("missing-test-coverage result");
+        assert_eq!(
+            finding["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/lib.rs"
+        );
+        assert_eq!(finding["locations"][0]["physicalLocation"]["region"]["startLine"], 42);
+    }
+
+    #[test]
+    fn falls_back_to_the_spec_file_when_no_mention_was_scanned() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-4".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+        let records = vec![Record {
+            kind: "implements".into(),
+            function: "BankLibrary::balance".into(),
+            requirement: "REQ-4".into(),
+        }];
+
+        let sarif = report(&doc, &PathBuf::from("spec.toml"), &records, &[]);
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        let finding = results
+            .iter()
+            .find(|r| r["ruleId"] == "missing-test-coverage")
+            .expect("missing-test-coverage result");
+        assert_eq!(
+            finding["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "spec.toml"
+        );
+    }
+}
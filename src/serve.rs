@@ -0,0 +1,188 @@
+//! A small JSON-RPC 2.0 dispatcher behind `cargo spec serve`'s long-running
+//! daemon mode: a `parse`/`lint`/`coverage`/`diff` request comes in as one
+//! JSON object, gets dispatched here, and gets a JSON-RPC 2.0 response
+//! object back — so IDE plugins, CI bots, and the TUI can share one warm
+//! process (and its caches) instead of cold-starting the CLI for every
+//! call. The socket I/O itself (accepting connections, framing requests)
+//! is `cargo spec`'s job, not this module's, the same split
+//! [`crate::trace::CoveragePolicy`]'s pure policy logic has from the CLI
+//! code that reads it off disk — [`dispatch`] is plain and synchronous,
+//! so it's unit-testable without a real socket or client.
+
+use serde_json::{json, Value};
+
+use crate::spec::SpecDocument;
+use crate::trace::{self, CoverageMatrix};
+use crate::{diff, lint};
+
+/// Dispatches one JSON-RPC 2.0 request object to the matching operation,
+/// returning the JSON-RPC 2.0 response object. An unknown `method` gets
+/// the standard `-32601` ("method not found"); a request missing or
+/// misnaming a required `params` field gets `-32602` ("invalid params");
+/// anything the operation itself fails on (an unparseable spec, a
+/// missing file) becomes a `-32000` server error carrying the underlying
+/// message, the same range [`crate::codegen::json_rpc`]'s generated
+/// dispatch uses for constraint violations.
+pub fn dispatch(request: &Value) -> Value {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let method = request.get("method").and_then(Value::as_str).unwrap_or("");
+
+    let result = match method {
+        "parse" => spec_path_param(request).and_then(parse),
+        "lint" => spec_path_param(request).and_then(lint_spec),
+        "coverage" => spec_path_param(request).and_then(coverage),
+        "diff" => before_after_params(request).and_then(|(before, after)| diff_specs(&before, &after)),
+        "" => return error_response(id, -32600, "missing method"),
+        _ => return error_response(id, -32601, "method not found"),
+    };
+
+    match result {
+        Ok(value) => json!({ "jsonrpc": "2.0", "result": value, "id": id }),
+        Err(DispatchError::InvalidParams(message)) => error_response(id, -32602, &message),
+        Err(DispatchError::ServerError(message)) => error_response(id, -32000, &message),
+    }
+}
+
+enum DispatchError {
+    InvalidParams(String),
+    ServerError(String),
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "error": { "code": code, "message": message }, "id": id })
+}
+
+fn spec_path_param(request: &Value) -> Result<String, DispatchError> {
+    request
+        .get("params")
+        .and_then(|p| p.get("spec_path"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| DispatchError::InvalidParams("missing params.spec_path".to_string()))
+}
+
+fn before_after_params(request: &Value) -> Result<(String, String), DispatchError> {
+    let missing = |field: &str| DispatchError::InvalidParams(format!("missing params.{field}"));
+    let params = request
+        .get("params")
+        .ok_or_else(|| DispatchError::InvalidParams("missing params".to_string()))?;
+    let before = params.get("before").and_then(Value::as_str).ok_or_else(|| missing("before"))?;
+    let after = params.get("after").and_then(Value::as_str).ok_or_else(|| missing("after"))?;
+    Ok((before.to_string(), after.to_string()))
+}
+
+fn load(spec_path: &str) -> Result<SpecDocument, DispatchError> {
+    SpecDocument::load_toml_file(spec_path).map_err(|e| DispatchError::ServerError(e.to_string()))
+}
+
+fn parse(spec_path: String) -> Result<Value, DispatchError> {
+    let doc = load(&spec_path)?;
+    Ok(json!({
+        "requirements": doc.requirements.len(),
+        "constraints": doc.constraints.len(),
+        "version_hash": doc.version_hash(),
+    }))
+}
+
+fn lint_spec(spec_path: String) -> Result<Value, DispatchError> {
+    let doc = load(&spec_path)?;
+    serde_json::to_value(lint::lint(&doc)).map_err(|e| DispatchError::ServerError(e.to_string()))
+}
+
+fn coverage(spec_path: String) -> Result<Value, DispatchError> {
+    let doc = load(&spec_path)?;
+    let records = trace::read_records();
+    let matrix = CoverageMatrix::build(&doc, &records);
+    let json_text = matrix.to_json().map_err(|e| DispatchError::ServerError(e.to_string()))?;
+    serde_json::from_str(&json_text).map_err(|e| DispatchError::ServerError(e.to_string()))
+}
+
+fn diff_specs(before: &str, after: &str) -> Result<Value, DispatchError> {
+    let before_doc = load(before)?;
+    let after_doc = load(after)?;
+    serde_json::to_value(diff::diff(&before_doc, &after_doc)).map_err(|e| DispatchError::ServerError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_spec(name: &str, contents: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("libspec-serve-test-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("spec.toml");
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    const SPEC: &str = "[[requirement]]\nid = \"REQ-001\"\ntext = \"does a thing\"\n";
+
+    #[test]
+    fn parse_returns_counts_and_a_version_hash() {
+        let path = temp_spec("parse", SPEC);
+        let response = dispatch(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "parse",
+            "params": { "spec_path": path.to_string_lossy() },
+        }));
+        assert_eq!(response["result"]["requirements"], 1);
+        assert_eq!(response["id"], 1);
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn lint_returns_an_empty_array_for_a_clean_spec() {
+        let path = temp_spec("lint", SPEC);
+        let response = dispatch(&json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "lint",
+            "params": { "spec_path": path.to_string_lossy() },
+        }));
+        assert_eq!(response["result"], json!([]));
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn diff_reports_an_added_requirement() {
+        let before = temp_spec("diff-before", "");
+        let after = temp_spec("diff-after", SPEC);
+        let response = dispatch(&json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "diff",
+            "params": {
+                "before": before.to_string_lossy(),
+                "after": after.to_string_lossy(),
+            },
+        }));
+        assert_eq!(response["result"]["added_requirements"][0]["id"], "REQ-001");
+        fs::remove_dir_all(before.parent().unwrap()).ok();
+        fs::remove_dir_all(after.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn unknown_method_is_method_not_found() {
+        let response = dispatch(&json!({ "jsonrpc": "2.0", "id": 4, "method": "bogus" }));
+        assert_eq!(response["error"]["code"], -32601);
+    }
+
+    #[test]
+    fn missing_params_is_invalid_params() {
+        let response = dispatch(&json!({ "jsonrpc": "2.0", "id": 5, "method": "parse" }));
+        assert_eq!(response["error"]["code"], -32602);
+    }
+
+    #[test]
+    fn a_missing_spec_file_is_a_server_error_not_a_panic() {
+        let response = dispatch(&json!({
+            "jsonrpc": "2.0",
+            "id": 6,
+            "method": "parse",
+            "params": { "spec_path": "/no/such/spec.toml" },
+        }));
+        assert_eq!(response["error"]["code"], -32000);
+    }
+}
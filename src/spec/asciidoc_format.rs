@@ -0,0 +1,179 @@
+//! AsciiDoc front end for [`SpecDocument`](super::SpecDocument), mirroring
+//! [`markdown_format`](super::markdown_format): a requirement or
+//! constraint is an attribute list naming its id/code, followed by a
+//! delimited listing block holding its text, e.g.
+//!
+//! ```asciidoc
+//! [req,id=REQ-004]
+//! ----
+//! balance() returns the current balance
+//! ----
+//!
+//! [constraint,code=CONST-001]
+//! ----
+//! amount must be positive
+//! ----
+//! ```
+
+use std::fmt;
+
+use super::{Constraint, Requirement, SpecDocument};
+
+/// Error parsing an AsciiDoc spec document.
+#[derive(Debug, PartialEq)]
+pub enum AsciiDocLoadError {
+    /// A `[req,...]`/`[constraint,...]` attribute list is missing the
+    /// `id=`/`code=` attribute it needs.
+    MissingAttribute { line: usize, attribute: &'static str },
+    /// An attribute list wasn't immediately followed by a `----`-delimited
+    /// listing block.
+    MissingBlock { line: usize },
+    /// A listing block was opened with `----` but never closed.
+    UnterminatedBlock { line: usize },
+    /// A listing block closed with no non-blank line inside it.
+    EmptyBlock { line: usize },
+}
+
+impl fmt::Display for AsciiDocLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsciiDocLoadError::MissingAttribute { line, attribute } => {
+                write!(f, "line {line}: attribute list is missing `{attribute}=`")
+            }
+            AsciiDocLoadError::MissingBlock { line } => {
+                write!(f, "line {line}: attribute list is not followed by a `----` block")
+            }
+            AsciiDocLoadError::UnterminatedBlock { line } => {
+                write!(f, "line {line}: `----` block is never closed")
+            }
+            AsciiDocLoadError::EmptyBlock { line } => {
+                write!(f, "line {line}: `----` block has no text")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AsciiDocLoadError {}
+
+/// Parses `[req,id=REQ-004]` (or `[constraint,code=CONST-001]`) into its
+/// kind (`"req"` or `"constraint"`) and attribute map, or `None` if
+/// `line` isn't an attribute list at all.
+fn attribute_list(line: &str) -> Option<(&str, Vec<(&str, &str)>)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    let mut parts = inner.split(',');
+    let kind = parts.next()?.trim();
+    if kind != "req" && kind != "constraint" {
+        return None;
+    }
+    let attrs = parts
+        .filter_map(|part| part.split_once('=').map(|(k, v)| (k.trim(), v.trim())))
+        .collect();
+    Some((kind, attrs))
+}
+
+pub fn from_str(input: &str) -> Result<SpecDocument, AsciiDocLoadError> {
+    let mut doc = SpecDocument::new();
+    let lines: Vec<&str> = input.lines().collect();
+    let mut i = 0;
+    while i < lines.len() {
+        let Some((kind, attrs)) = attribute_list(lines[i].trim()) else {
+            i += 1;
+            continue;
+        };
+        let attribute_line = i;
+
+        let id_attr = if kind == "req" { "id" } else { "code" };
+        let id = attrs
+            .iter()
+            .find(|(k, _)| *k == id_attr)
+            .map(|(_, v)| v.to_string())
+            .ok_or(AsciiDocLoadError::MissingAttribute {
+                line: attribute_line + 1,
+                attribute: id_attr,
+            })?;
+
+        i += 1;
+        while i < lines.len() && lines[i].trim().is_empty() {
+            i += 1;
+        }
+        if i >= lines.len() || lines[i].trim() != "----" {
+            return Err(AsciiDocLoadError::MissingBlock { line: attribute_line + 1 });
+        }
+        let block_start = i;
+        i += 1;
+
+        let mut text_lines = Vec::new();
+        loop {
+            if i >= lines.len() {
+                return Err(AsciiDocLoadError::UnterminatedBlock { line: block_start + 1 });
+            }
+            if lines[i].trim() == "----" {
+                break;
+            }
+            text_lines.push(lines[i]);
+            i += 1;
+        }
+        i += 1;
+
+        let text = text_lines.join("\n").trim().to_string();
+        if text.is_empty() {
+            return Err(AsciiDocLoadError::EmptyBlock { line: block_start + 1 });
+        }
+
+        if kind == "req" {
+            doc.requirements.push(Requirement { id, text, ..Default::default() });
+        } else {
+            doc.constraints.push(Constraint { code: id, text, ..Default::default() });
+        }
+    }
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_req_and_constraint_blocks() {
+        let doc = from_str(
+            "= Spec\n\n[req,id=REQ-004]\n----\nbalance() returns the current balance\n----\n\n[constraint,code=CONST-001]\n----\namount must be positive\n----\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            doc.requirement("REQ-004").unwrap().text,
+            "balance() returns the current balance"
+        );
+        assert_eq!(
+            doc.constraint("CONST-001").unwrap().text,
+            "amount must be positive"
+        );
+    }
+
+    #[test]
+    fn rejects_attribute_list_missing_its_id() {
+        let err = from_str("[req]\n----\nbalance()\n----\n").unwrap_err();
+        assert_eq!(
+            err,
+            AsciiDocLoadError::MissingAttribute { line: 1, attribute: "id" }
+        );
+    }
+
+    #[test]
+    fn rejects_attribute_list_with_no_following_block() {
+        let err = from_str("[req,id=REQ-004]\nnot a block\n").unwrap_err();
+        assert_eq!(err, AsciiDocLoadError::MissingBlock { line: 1 });
+    }
+
+    #[test]
+    fn rejects_unterminated_block() {
+        let err = from_str("[req,id=REQ-004]\n----\nbalance()\n").unwrap_err();
+        assert_eq!(err, AsciiDocLoadError::UnterminatedBlock { line: 2 });
+    }
+
+    #[test]
+    fn rejects_empty_block() {
+        let err = from_str("[req,id=REQ-004]\n----\n----\n").unwrap_err();
+        assert_eq!(err, AsciiDocLoadError::EmptyBlock { line: 2 });
+    }
+}
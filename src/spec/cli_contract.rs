@@ -0,0 +1,61 @@
+//! Spec-declared CLI contracts: a named binary and the invocations
+//! (argv in, exit code and output content out) it must satisfy, turned
+//! into an `assert_cmd`-based conformance suite by
+//! [`crate::codegen::rust_cli_test`] — the same declare-it-in-the-spec,
+//! generate-the-test shape [`super::ConformanceVector`] gives stateful
+//! operations, applied to a binary's command-line surface instead of a
+//! state machine's.
+
+use serde::{Deserialize, Serialize};
+
+/// A binary and the invocations it must satisfy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct CliContract {
+    pub name: String,
+    /// The binary this contract exercises, e.g. `"cargo-spec"` — passed to
+    /// `assert_cmd::Command::cargo_bin` by the generated test.
+    pub bin: String,
+    #[serde(default, rename = "invocation", alias = "invocations")]
+    pub invocations: Vec<CliInvocation>,
+}
+
+/// One invocation of a [`CliContract`]'s binary and what it must produce.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct CliInvocation {
+    pub name: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// The exit code the binary must return for this invocation. Defaults
+    /// to `0`, i.e. success.
+    #[serde(default)]
+    pub expect_exit_code: i32,
+    /// Text that must appear somewhere in stdout, if given.
+    #[serde(default)]
+    pub expect_stdout_contains: Option<String>,
+    /// Text that must appear somewhere in stderr, if given.
+    #[serde(default)]
+    pub expect_stderr_contains: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_toml() {
+        let contract = CliContract {
+            name: "cargo-spec check".into(),
+            bin: "cargo-spec".into(),
+            invocations: vec![CliInvocation {
+                name: "rejects a missing spec file".into(),
+                args: vec!["check".into()],
+                expect_exit_code: 1,
+                expect_stdout_contains: None,
+                expect_stderr_contains: Some("no libspec.toml found".into()),
+            }],
+        };
+        let toml = toml::to_string(&contract).unwrap();
+        let back: CliContract = toml::from_str(&toml).unwrap();
+        assert_eq!(contract, back);
+    }
+}
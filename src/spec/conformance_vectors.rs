@@ -0,0 +1,99 @@
+//! Golden conformance vectors: spec-declared canonical operation
+//! sequences with their expected outputs/error codes, exported as plain
+//! JSON (see [`export_string`]) so a non-Rust implementation can validate
+//! itself against exactly the golden data the Rust harness's
+//! [`crate::codegen::state_machine`]/[`crate::codegen::fsm`]-generated
+//! property tests check, without running Rust at all.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::SpecDocument;
+
+/// One step in a [`ConformanceVector`]'s operation sequence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ConformanceStep {
+    /// The transition/operation name being applied, e.g. `"withdraw"`.
+    pub operation: String,
+    #[serde(default)]
+    pub params: BTreeMap<String, f64>,
+    /// The constraint code an implementation should report, if this step
+    /// is expected to fail. `None` means the step is expected to succeed.
+    #[serde(default)]
+    pub expect_error: Option<String>,
+    /// The state machine's fields expected after this step, if it's
+    /// expected to succeed.
+    #[serde(default)]
+    pub expect_state: BTreeMap<String, f64>,
+}
+
+/// A canonical operation sequence against a named state machine
+/// ([`super::StateMachine`] or [`super::Fsm`]), with the result expected
+/// after each step.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ConformanceVector {
+    pub name: String,
+    /// The state machine this vector exercises, by name.
+    pub state_machine: String,
+    #[serde(default, rename = "step", alias = "steps")]
+    pub steps: Vec<ConformanceStep>,
+}
+
+/// Exports `doc`'s [`ConformanceVector`]s as a pretty-printed JSON array,
+/// the language-neutral format non-Rust implementations check themselves
+/// against.
+pub fn export_string(doc: &SpecDocument) -> String {
+    serde_json::to_string_pretty(&doc.conformance_vectors)
+        .expect("ConformanceVector serialization is infallible")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_toml() {
+        let vector = ConformanceVector {
+            name: "overdraw is rejected".into(),
+            state_machine: "Account".into(),
+            steps: vec![
+                ConformanceStep {
+                    operation: "deposit".into(),
+                    params: [("amount".to_string(), 100.0)].into(),
+                    expect_error: None,
+                    expect_state: [("balance".to_string(), 100.0)].into(),
+                },
+                ConformanceStep {
+                    operation: "withdraw".into(),
+                    params: [("amount".to_string(), 150.0)].into(),
+                    expect_error: Some("CONST-002".into()),
+                    expect_state: BTreeMap::new(),
+                },
+            ],
+        };
+        let toml = toml::to_string(&vector).unwrap();
+        let back: ConformanceVector = toml::from_str(&toml).unwrap();
+        assert_eq!(vector, back);
+    }
+
+    #[test]
+    fn exports_vectors_as_a_json_array() {
+        let mut doc = SpecDocument::new();
+        doc.conformance_vectors.push(ConformanceVector {
+            name: "overdraw is rejected".into(),
+            state_machine: "Account".into(),
+            steps: vec![ConformanceStep {
+                operation: "withdraw".into(),
+                params: [("amount".to_string(), 150.0)].into(),
+                expect_error: Some("CONST-002".into()),
+                expect_state: BTreeMap::new(),
+            }],
+        });
+
+        let json: serde_json::Value = serde_json::from_str(&export_string(&doc)).unwrap();
+        assert_eq!(json[0]["name"], "overdraw is rejected");
+        assert_eq!(json[0]["state_machine"], "Account");
+        assert_eq!(json[0]["step"][0]["operation"], "withdraw");
+        assert_eq!(json[0]["step"][0]["expect_error"], "CONST-002");
+    }
+}
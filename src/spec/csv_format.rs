@@ -0,0 +1,271 @@
+//! CSV import of legacy requirement matrices: maps whichever columns a
+//! legacy tool happens to export (id, text, priority, verification
+//! method) into a [`SpecDocument`] via a caller-supplied [`ColumnMapping`],
+//! since these exports don't agree on a header naming convention.
+
+use std::fmt;
+
+use super::{Priority, Requirement, SpecDocument, VerificationMethod};
+
+/// Which CSV column (by header name) holds each field. `id`/`text` are
+/// required; `priority`/`verification_method` are optional, since not
+/// every legacy matrix tracks them — a row missing the mapped priority
+/// column falls back to [`Priority::default`], and a missing
+/// verification-method column leaves [`Requirement::verification_method`]
+/// unset.
+#[derive(Debug, Clone)]
+pub struct ColumnMapping {
+    pub id: String,
+    pub text: String,
+    pub priority: Option<String>,
+    pub verification_method: Option<String>,
+}
+
+/// Error importing a CSV requirement matrix.
+#[derive(Debug, PartialEq)]
+pub enum CsvImportError {
+    /// The input has no header row to read column names from.
+    Empty,
+    /// `column` isn't any of the header row's column names.
+    MissingHeader(String),
+    /// `row` doesn't have a value in `column` (fewer fields than the
+    /// header row).
+    MissingField { row: usize, column: String },
+    /// `row`'s priority column held `value`, which isn't one of `low`,
+    /// `medium`, `high`, or `critical` (case-insensitive).
+    UnknownPriority { row: usize, value: String },
+    /// `row`'s verification-method column held `value`, which isn't one
+    /// of `test`, `inspection`, `analysis`, or `demonstration`
+    /// (case-insensitive).
+    UnknownVerificationMethod { row: usize, value: String },
+}
+
+impl fmt::Display for CsvImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvImportError::Empty => write!(f, "input has no header row"),
+            CsvImportError::MissingHeader(column) => {
+                write!(f, "no column named `{column}` in the header row")
+            }
+            CsvImportError::MissingField { row, column } => {
+                write!(f, "row {row} has no value in column `{column}`")
+            }
+            CsvImportError::UnknownPriority { row, value } => {
+                write!(f, "row {row}: `{value}` is not a recognized priority")
+            }
+            CsvImportError::UnknownVerificationMethod { row, value } => {
+                write!(f, "row {row}: `{value}` is not a recognized verification method")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CsvImportError {}
+
+/// Splits one CSV record into fields, honoring `"`-quoted fields that may
+/// contain commas or escaped `""` quotes. Doesn't handle a quoted field
+/// spanning multiple lines — each record here is one line.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' && chars.peek() == Some(&'"') {
+                field.push('"');
+                chars.next();
+            } else if c == '"' {
+                in_quotes = false;
+            } else {
+                field.push(c);
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == ',' {
+            fields.push(std::mem::take(&mut field));
+        } else {
+            field.push(c);
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+fn parse_priority(value: &str) -> Option<Priority> {
+    match value.to_ascii_lowercase().as_str() {
+        "low" => Some(Priority::Low),
+        "medium" => Some(Priority::Medium),
+        "high" => Some(Priority::High),
+        "critical" => Some(Priority::Critical),
+        _ => None,
+    }
+}
+
+fn parse_verification_method(value: &str) -> Option<VerificationMethod> {
+    match value.to_ascii_lowercase().as_str() {
+        "test" => Some(VerificationMethod::Test),
+        "inspection" => Some(VerificationMethod::Inspection),
+        "analysis" => Some(VerificationMethod::Analysis),
+        "demonstration" => Some(VerificationMethod::Demonstration),
+        _ => None,
+    }
+}
+
+/// Imports a [`SpecDocument`] from a CSV requirement matrix: the first
+/// line is the header row `mapping`'s column names are looked up in; each
+/// following non-blank line becomes one requirement.
+pub fn import_str(input: &str, mapping: &ColumnMapping) -> Result<SpecDocument, CsvImportError> {
+    let mut lines = input.lines();
+    let headers = split_csv_line(lines.next().ok_or(CsvImportError::Empty)?);
+
+    let column_index = |name: &str| -> Result<usize, CsvImportError> {
+        headers
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| CsvImportError::MissingHeader(name.to_string()))
+    };
+    let id_index = column_index(&mapping.id)?;
+    let text_index = column_index(&mapping.text)?;
+    let priority_index = mapping.priority.as_deref().map(column_index).transpose()?;
+    let verification_index = mapping.verification_method.as_deref().map(column_index).transpose()?;
+
+    let mut doc = SpecDocument::new();
+    for (offset, line) in lines.enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row = offset + 2; // 1-indexed, with the header row as row 1
+        let fields = split_csv_line(line);
+        let field = |index: usize, column: &str| -> Result<String, CsvImportError> {
+            fields
+                .get(index)
+                .map(|s| s.trim().to_string())
+                .ok_or_else(|| CsvImportError::MissingField { row, column: column.to_string() })
+        };
+
+        let id = field(id_index, &mapping.id)?;
+        let text = field(text_index, &mapping.text)?;
+
+        let priority = match priority_index {
+            Some(index) => {
+                let value = field(index, mapping.priority.as_deref().unwrap_or_default())?;
+                parse_priority(&value).ok_or(CsvImportError::UnknownPriority { row, value })?
+            }
+            None => Priority::default(),
+        };
+
+        let verification_method = match verification_index {
+            Some(index) => {
+                let value = field(index, mapping.verification_method.as_deref().unwrap_or_default())?;
+                if value.is_empty() {
+                    None
+                } else {
+                    Some(
+                        parse_verification_method(&value)
+                            .ok_or(CsvImportError::UnknownVerificationMethod { row, value })?,
+                    )
+                }
+            }
+            None => None,
+        };
+
+        doc.requirements.push(Requirement {
+            id,
+            text,
+            priority,
+            verification_method,
+            ..Default::default()
+        });
+    }
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> ColumnMapping {
+        ColumnMapping {
+            id: "ID".into(),
+            text: "Requirement".into(),
+            priority: Some("Priority".into()),
+            verification_method: Some("Verification".into()),
+        }
+    }
+
+    #[test]
+    fn imports_one_requirement_per_row() {
+        let doc = import_str(
+            "ID,Requirement,Priority,Verification\nREQ-004,balance() returns the current balance,high,Test\n",
+            &mapping(),
+        )
+        .unwrap();
+
+        let req = doc.requirement("REQ-004").unwrap();
+        assert_eq!(req.text, "balance() returns the current balance");
+        assert_eq!(req.priority, Priority::High);
+        assert_eq!(req.verification_method, Some(VerificationMethod::Test));
+    }
+
+    #[test]
+    fn handles_quoted_fields_containing_commas() {
+        let doc = import_str(
+            "ID,Requirement,Priority,Verification\nREQ-004,\"deposit(amount), credits the account\",medium,Test\n",
+            &mapping(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            doc.requirement("REQ-004").unwrap().text,
+            "deposit(amount), credits the account"
+        );
+    }
+
+    #[test]
+    fn defaults_priority_and_verification_method_when_unmapped() {
+        let mapping = ColumnMapping {
+            id: "ID".into(),
+            text: "Requirement".into(),
+            priority: None,
+            verification_method: None,
+        };
+        let doc = import_str("ID,Requirement\nREQ-004,balance()\n", &mapping).unwrap();
+
+        let req = doc.requirement("REQ-004").unwrap();
+        assert_eq!(req.priority, Priority::Medium);
+        assert_eq!(req.verification_method, None);
+    }
+
+    #[test]
+    fn rejects_an_unmapped_header() {
+        let err = import_str("ID,Requirement\nREQ-004,balance()\n", &mapping()).unwrap_err();
+        assert_eq!(err, CsvImportError::MissingHeader("Priority".to_string()));
+    }
+
+    #[test]
+    fn rejects_an_unrecognized_priority() {
+        let err = import_str(
+            "ID,Requirement,Priority,Verification\nREQ-004,balance(),urgent,Test\n",
+            &mapping(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            CsvImportError::UnknownPriority { row: 2, value: "urgent".to_string() }
+        );
+    }
+
+    #[test]
+    fn rejects_an_unrecognized_verification_method() {
+        let err = import_str(
+            "ID,Requirement,Priority,Verification\nREQ-004,balance(),high,Peer Review\n",
+            &mapping(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            CsvImportError::UnknownVerificationMethod { row: 2, value: "Peer Review".to_string() }
+        );
+    }
+}
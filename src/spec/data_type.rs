@@ -0,0 +1,45 @@
+//! Record type declarations: a [`DataType`] gives the spec a vocabulary for
+//! the structs it talks about (e.g. `Account`, `Transaction`), so they're
+//! not invisible to the spec the way a hand-written struct is.
+
+use serde::{Deserialize, Serialize};
+
+/// One field of a [`DataType`], e.g. `balance: f64` constrained by
+/// `CONST-001`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    /// The field's Rust type, e.g. `String` or `f64`.
+    pub ty: String,
+    /// Codes of [`super::Constraint`]s this field must satisfy.
+    #[serde(default)]
+    pub constraints: Vec<String>,
+}
+
+/// A record type declared by the spec, e.g. `Account { id: String, balance: f64 }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataType {
+    pub name: String,
+    #[serde(default, rename = "field", alias = "fields")]
+    pub fields: Vec<Field>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_toml() {
+        let dt = DataType {
+            name: "Account".into(),
+            fields: vec![Field {
+                name: "balance".into(),
+                ty: "f64".into(),
+                constraints: vec!["CONST-001".into()],
+            }],
+        };
+        let toml = toml::to_string(&dt).unwrap();
+        let back: DataType = toml::from_str(&toml).unwrap();
+        assert_eq!(dt, back);
+    }
+}
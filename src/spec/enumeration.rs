@@ -0,0 +1,46 @@
+//! Spec-level enumerations: a closed set of named values a
+//! [`super::Field`] can be constrained to, e.g.
+//! `Currency = ["USD", "EUR", "GBP"]`.
+
+use serde::{Deserialize, Serialize};
+
+/// A named, closed set of values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Enumeration {
+    pub name: String,
+    #[serde(default, rename = "value", alias = "values")]
+    pub values: Vec<String>,
+}
+
+impl Enumeration {
+    /// Whether `value` is one of this enumeration's declared values.
+    pub fn contains(&self, value: &str) -> bool {
+        self.values.iter().any(|v| v == value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_checks_membership() {
+        let currency = Enumeration {
+            name: "Currency".into(),
+            values: vec!["USD".into(), "EUR".into()],
+        };
+        assert!(currency.contains("USD"));
+        assert!(!currency.contains("JPY"));
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let e = Enumeration {
+            name: "Currency".into(),
+            values: vec!["USD".into(), "EUR".into()],
+        };
+        let toml = toml::to_string(&e).unwrap();
+        let back: Enumeration = toml::from_str(&toml).unwrap();
+        assert_eq!(e, back);
+    }
+}
@@ -0,0 +1,39 @@
+//! Domain event declarations: a [`DomainEvent`] gives the spec a vocabulary
+//! for the messages an event-driven system publishes (e.g. `AccountCreated`,
+//! `FundsWithdrawn`), the same way a [`super::DataType`] gives it a
+//! vocabulary for request/response records. See
+//! [`crate::codegen::event_schema`].
+
+use serde::{Deserialize, Serialize};
+
+use super::Field;
+
+/// A domain event declared by the spec, e.g. `FundsWithdrawn { account_id:
+/// String, amount: f64 }`. Shares [`Field`] with [`super::DataType`] since
+/// an event's payload is shaped the same way a record's fields are.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainEvent {
+    pub name: String,
+    #[serde(default, rename = "field", alias = "fields")]
+    pub fields: Vec<Field>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_toml() {
+        let event = DomainEvent {
+            name: "FundsWithdrawn".into(),
+            fields: vec![Field {
+                name: "amount".into(),
+                ty: "f64".into(),
+                constraints: vec![],
+            }],
+        };
+        let toml = toml::to_string(&event).unwrap();
+        let back: DomainEvent = toml::from_str(&toml).unwrap();
+        assert_eq!(event, back);
+    }
+}
@@ -0,0 +1,668 @@
+//! A small expression DSL for [`Constraint::expr`](super::Constraint::expr):
+//! numeric comparisons like `amount > 0` or `amount <= balance(account)`,
+//! optionally composed with `&&`/`||`, parsed into a [`ConstraintExpr`] so
+//! checks can be generated from the spec (see
+//! [`crate::codegen::rust_guard`]) instead of hand-written and potentially
+//! divergent from what the spec declares.
+//!
+//! The grammar is deliberately small: a tree of [`Comparison`]s (one
+//! relational operator between two arithmetic [`Term`]s) combined with
+//! `&&`/`||`, where `&&` binds tighter than `||` (same as Rust), and
+//! parentheses can override that. A term is a number, an identifier, a
+//! parenthesized sub-term, or a call like `balance(account)`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// A relational operator between two [`Term`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelOp {
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl RelOp {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RelOp::Gt => ">",
+            RelOp::Lt => "<",
+            RelOp::Ge => ">=",
+            RelOp::Le => "<=",
+            RelOp::Eq => "==",
+            RelOp::Ne => "!=",
+        }
+    }
+}
+
+/// One side of a [`Comparison`]: a number, a bare identifier, a function
+/// call, or a parenthesized arithmetic expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Number(f64),
+    Ident(String),
+    Call(String, Vec<Term>),
+    Add(Box<Term>, Box<Term>),
+    Sub(Box<Term>, Box<Term>),
+    Mul(Box<Term>, Box<Term>),
+    Div(Box<Term>, Box<Term>),
+}
+
+impl Term {
+    /// Parses a standalone arithmetic term from its source text, e.g. an
+    /// [`Transition::effect`](super::Transition::effect) entry like
+    /// `"balance - amount"`. Unlike [`ConstraintExpr::parse`], there's no
+    /// relational operator at the top level.
+    pub fn parse(input: &str) -> Result<Self, ExprParseError> {
+        let mut parser = Parser::new(input);
+        let term = parser.parse_term()?;
+        parser.skip_whitespace();
+        if parser.pos != parser.input.len() {
+            return Err(error(format!(
+                "unexpected trailing input `{}`",
+                &parser.input[parser.pos..]
+            )));
+        }
+        Ok(term)
+    }
+
+    /// Every identifier mentioned anywhere in this term, including call
+    /// names and call arguments, in first-mention order.
+    fn idents<'a>(&'a self, out: &mut Vec<&'a str>) {
+        match self {
+            Term::Number(_) => {}
+            Term::Ident(name) => out.push(name),
+            Term::Call(name, args) => {
+                out.push(name);
+                for arg in args {
+                    arg.idents(out);
+                }
+            }
+            Term::Add(a, b) | Term::Sub(a, b) | Term::Mul(a, b) | Term::Div(a, b) => {
+                a.idents(out);
+                b.idents(out);
+            }
+        }
+    }
+
+    /// Evaluates this term given concrete values for every identifier it
+    /// mentions. `None` if it calls a function (no interpreter for an
+    /// arbitrary call) or mentions an identifier missing from `env`, so a
+    /// caller can tell "not evaluable" apart from any particular result.
+    pub fn eval(&self, env: &BTreeMap<String, f64>) -> Option<f64> {
+        match self {
+            Term::Number(n) => Some(*n),
+            Term::Ident(name) => env.get(name).copied(),
+            Term::Call(..) => None,
+            Term::Add(a, b) => Some(a.eval(env)? + b.eval(env)?),
+            Term::Sub(a, b) => Some(a.eval(env)? - b.eval(env)?),
+            Term::Mul(a, b) => Some(a.eval(env)? * b.eval(env)?),
+            Term::Div(a, b) => Some(a.eval(env)? / b.eval(env)?),
+        }
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Number(n) => write!(f, "{n}"),
+            Term::Ident(name) => write!(f, "{name}"),
+            Term::Call(name, args) => {
+                write!(f, "{name}(")?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{arg}")?;
+                }
+                write!(f, ")")
+            }
+            Term::Add(a, b) => write!(f, "{a} + {b}"),
+            Term::Sub(a, b) => write!(f, "{a} - {b}"),
+            Term::Mul(a, b) => write!(f, "{a} * {b}"),
+            Term::Div(a, b) => write!(f, "{a} / {b}"),
+        }
+    }
+}
+
+/// A single relation: `lhs op rhs`, e.g. `amount > 0` or
+/// `amount <= balance(account)`. The leaf of a [`ConstraintExpr`] tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub lhs: Term,
+    pub op: RelOp,
+    pub rhs: Term,
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.lhs, self.op.as_str(), self.rhs)
+    }
+}
+
+impl Comparison {
+    /// Evaluates this comparison given concrete values for every
+    /// identifier its sides mention. `None` under the same conditions as
+    /// [`Term::eval`].
+    pub fn eval(&self, env: &BTreeMap<String, f64>) -> Option<bool> {
+        let lhs = self.lhs.eval(env)?;
+        let rhs = self.rhs.eval(env)?;
+        Some(match self.op {
+            RelOp::Gt => lhs > rhs,
+            RelOp::Lt => lhs < rhs,
+            RelOp::Ge => lhs >= rhs,
+            RelOp::Le => lhs <= rhs,
+            RelOp::Eq => lhs == rhs,
+            RelOp::Ne => lhs != rhs,
+        })
+    }
+}
+
+/// A parsed constraint expression: one [`Comparison`], or several combined
+/// with `&&`/`||`, e.g. `amount > 0 && amount <= balance(account)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstraintExpr {
+    Compare(Comparison),
+    And(Box<ConstraintExpr>, Box<ConstraintExpr>),
+    Or(Box<ConstraintExpr>, Box<ConstraintExpr>),
+}
+
+impl ConstraintExpr {
+    /// Parses a constraint expression from its source text.
+    pub fn parse(input: &str) -> Result<Self, ExprParseError> {
+        Parser::new(input).parse_expr()
+    }
+
+    /// Every leaf [`Comparison`] in this tree, in source order. Used by
+    /// [`crate::codegen::rust_guard`] and property-test oracle generators
+    /// to learn what a check over this expression needs, without caring
+    /// about its `&&`/`||` structure.
+    pub fn comparisons(&self) -> Vec<&Comparison> {
+        let mut out = Vec::new();
+        self.collect_comparisons(&mut out);
+        out
+    }
+
+    fn collect_comparisons<'a>(&'a self, out: &mut Vec<&'a Comparison>) {
+        match self {
+            ConstraintExpr::Compare(c) => out.push(c),
+            ConstraintExpr::And(a, b) | ConstraintExpr::Or(a, b) => {
+                a.collect_comparisons(out);
+                b.collect_comparisons(out);
+            }
+        }
+    }
+
+    /// Every identifier mentioned anywhere in this tree, including call
+    /// names, in first-mention order with duplicates removed. Used by
+    /// [`crate::codegen::rust_guard`] to decide what parameters a
+    /// generated check function needs.
+    pub fn idents(&self) -> Vec<&str> {
+        let mut out = Vec::new();
+        for comparison in self.comparisons() {
+            comparison.lhs.idents(&mut out);
+            comparison.rhs.idents(&mut out);
+        }
+        let mut seen = BTreeSet::new();
+        out.retain(|name| seen.insert(*name));
+        out
+    }
+
+    /// Evaluates this expression given concrete values for every
+    /// identifier it mentions. `None` under the same conditions as
+    /// [`Term::eval`], propagated through `&&`/`||`.
+    pub fn eval(&self, env: &BTreeMap<String, f64>) -> Option<bool> {
+        match self {
+            ConstraintExpr::Compare(c) => c.eval(env),
+            ConstraintExpr::And(a, b) => Some(a.eval(env)? && b.eval(env)?),
+            ConstraintExpr::Or(a, b) => Some(a.eval(env)? || b.eval(env)?),
+        }
+    }
+}
+
+impl fmt::Display for ConstraintExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstraintExpr::Compare(c) => write!(f, "{c}"),
+            ConstraintExpr::And(a, b) => {
+                write_operand(f, a, false)?;
+                write!(f, " && ")?;
+                write_operand(f, b, false)
+            }
+            ConstraintExpr::Or(a, b) => {
+                write_operand(f, a, true)?;
+                write!(f, " || ")?;
+                write_operand(f, b, true)
+            }
+        }
+    }
+}
+
+/// Writes `expr` as an operand of `&&` (`parent_is_or = false`) or `||`
+/// (`parent_is_or = true`), parenthesizing an `Or` nested under an `And`
+/// so the rendered text re-parses to the same tree — `&&` binds tighter
+/// than `||`, so an unparenthesized `Or` there would silently regroup.
+fn write_operand(f: &mut fmt::Formatter<'_>, expr: &ConstraintExpr, parent_is_or: bool) -> fmt::Result {
+    if !parent_is_or && matches!(expr, ConstraintExpr::Or(..)) {
+        write!(f, "({expr})")
+    } else {
+        write!(f, "{expr}")
+    }
+}
+
+/// A constraint expression that couldn't be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprParseError {
+    pub message: String,
+}
+
+impl fmt::Display for ExprParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ExprParseError {}
+
+fn error(message: impl Into<String>) -> ExprParseError {
+    ExprParseError {
+        message: message.into(),
+    }
+}
+
+/// A hand-rolled recursive-descent/Pratt parser; the grammar is small
+/// enough that a parser generator would be more ceremony than the DSL it
+/// parses.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn parse_expr(mut self) -> Result<ConstraintExpr, ExprParseError> {
+        let expr = self.parse_or()?;
+        self.skip_whitespace();
+        if self.pos != self.input.len() {
+            return Err(error(format!(
+                "unexpected trailing input `{}`",
+                &self.input[self.pos..]
+            )));
+        }
+        Ok(expr)
+    }
+
+    fn parse_or(&mut self) -> Result<ConstraintExpr, ExprParseError> {
+        let mut lhs = self.parse_and()?;
+        loop {
+            self.skip_whitespace();
+            if self.input[self.pos..].starts_with("||") {
+                self.pos += 2;
+                lhs = ConstraintExpr::Or(Box::new(lhs), Box::new(self.parse_and()?));
+            } else {
+                break;
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<ConstraintExpr, ExprParseError> {
+        let mut lhs = self.parse_comparison_or_group()?;
+        loop {
+            self.skip_whitespace();
+            if self.input[self.pos..].starts_with("&&") {
+                self.pos += 2;
+                lhs = ConstraintExpr::And(Box::new(lhs), Box::new(self.parse_comparison_or_group()?));
+            } else {
+                break;
+            }
+        }
+        Ok(lhs)
+    }
+
+    /// A `(` here could open a parenthesized sub-[`ConstraintExpr`] (e.g.
+    /// `(a || b) && c`) or an arithmetic grouping that's just the lhs of a
+    /// comparison (e.g. `(amount + fee) > 0`). Try the former, and fall
+    /// back to an ordinary comparison if it either fails to parse or
+    /// turns out to be followed by a relational operator.
+    fn parse_comparison_or_group(&mut self) -> Result<ConstraintExpr, ExprParseError> {
+        self.skip_whitespace();
+        if self.peek() == Some('(') {
+            let checkpoint = self.pos;
+            self.pos += 1;
+            let attempt = self.parse_or().and_then(|inner| {
+                self.skip_whitespace();
+                self.expect(')')?;
+                self.skip_whitespace();
+                if self.parse_rel_op().is_ok() {
+                    Err(error("paren group is an arithmetic term, not a sub-expression"))
+                } else {
+                    Ok(inner)
+                }
+            });
+            match attempt {
+                Ok(inner) => return Ok(inner),
+                Err(_) => self.pos = checkpoint,
+            }
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<ConstraintExpr, ExprParseError> {
+        let lhs = self.parse_term()?;
+        self.skip_whitespace();
+        let op = self.parse_rel_op()?;
+        let rhs = self.parse_term()?;
+        Ok(ConstraintExpr::Compare(Comparison { lhs, op, rhs }))
+    }
+
+    fn parse_rel_op(&mut self) -> Result<RelOp, ExprParseError> {
+        for (text, op) in [
+            (">=", RelOp::Ge),
+            ("<=", RelOp::Le),
+            ("==", RelOp::Eq),
+            ("!=", RelOp::Ne),
+            (">", RelOp::Gt),
+            ("<", RelOp::Lt),
+        ] {
+            if self.input[self.pos..].starts_with(text) {
+                self.pos += text.len();
+                return Ok(op);
+            }
+        }
+        Err(error("expected a relational operator (>, <, >=, <=, ==, !=)"))
+    }
+
+    fn parse_term(&mut self) -> Result<Term, ExprParseError> {
+        let mut lhs = self.parse_product()?;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('+') => {
+                    self.pos += 1;
+                    lhs = Term::Add(Box::new(lhs), Box::new(self.parse_product()?));
+                }
+                Some('-') => {
+                    self.pos += 1;
+                    lhs = Term::Sub(Box::new(lhs), Box::new(self.parse_product()?));
+                }
+                _ => break,
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn parse_product(&mut self) -> Result<Term, ExprParseError> {
+        let mut lhs = self.parse_atom()?;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('*') => {
+                    self.pos += 1;
+                    lhs = Term::Mul(Box::new(lhs), Box::new(self.parse_atom()?));
+                }
+                Some('/') => {
+                    self.pos += 1;
+                    lhs = Term::Div(Box::new(lhs), Box::new(self.parse_atom()?));
+                }
+                _ => break,
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn parse_atom(&mut self) -> Result<Term, ExprParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let inner = self.parse_term()?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                Ok(inner)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.parse_number(),
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => self.parse_ident_or_call(),
+            _ => Err(error(format!(
+                "expected a number, identifier, or `(` at `{}`",
+                &self.input[self.pos..]
+            ))),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Term, ExprParseError> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || c == '.')
+        {
+            self.pos += 1;
+        }
+        self.input[start..self.pos]
+            .parse::<f64>()
+            .map(Term::Number)
+            .map_err(|_| error(format!("invalid number `{}`", &self.input[start..self.pos])))
+    }
+
+    fn parse_ident_or_call(&mut self) -> Result<Term, ExprParseError> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            self.pos += 1;
+        }
+        let name = self.input[start..self.pos].to_string();
+
+        self.skip_whitespace();
+        if self.peek() != Some('(') {
+            return Ok(Term::Ident(name));
+        }
+        self.pos += 1;
+        let mut args = Vec::new();
+        self.skip_whitespace();
+        if self.peek() != Some(')') {
+            loop {
+                args.push(self.parse_term()?);
+                self.skip_whitespace();
+                match self.peek() {
+                    Some(',') => {
+                        self.pos += 1;
+                    }
+                    _ => break,
+                }
+            }
+        }
+        self.skip_whitespace();
+        self.expect(')')?;
+        Ok(Term::Call(name, args))
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ExprParseError> {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(error(format!("expected `{c}` at `{}`", &self.input[self.pos..])))
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compare(expr: &ConstraintExpr) -> &Comparison {
+        match expr {
+            ConstraintExpr::Compare(c) => c,
+            _ => panic!("expected a single comparison, got {expr:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_a_simple_comparison() {
+        let expr = ConstraintExpr::parse("amount > 0").unwrap();
+        let c = compare(&expr);
+        assert_eq!(c.lhs, Term::Ident("amount".into()));
+        assert_eq!(c.op, RelOp::Gt);
+        assert_eq!(c.rhs, Term::Number(0.0));
+    }
+
+    #[test]
+    fn parses_a_call_on_the_right_hand_side() {
+        let expr = ConstraintExpr::parse("amount <= balance(account)").unwrap();
+        assert_eq!(
+            compare(&expr).rhs,
+            Term::Call("balance".into(), vec![Term::Ident("account".into())])
+        );
+    }
+
+    #[test]
+    fn parses_arithmetic_with_precedence() {
+        let expr = ConstraintExpr::parse("amount + fee * 2 <= balance").unwrap();
+        assert_eq!(
+            compare(&expr).lhs,
+            Term::Add(
+                Box::new(Term::Ident("amount".into())),
+                Box::new(Term::Mul(Box::new(Term::Ident("fee".into())), Box::new(Term::Number(2.0)))),
+            )
+        );
+    }
+
+    #[test]
+    fn idents_collects_call_names_and_arguments_once_each() {
+        let expr = ConstraintExpr::parse("amount <= balance(account)").unwrap();
+        assert_eq!(expr.idents(), vec!["amount", "balance", "account"]);
+    }
+
+    #[test]
+    fn parses_a_standalone_arithmetic_term() {
+        let term = Term::parse("balance - amount").unwrap();
+        assert_eq!(
+            term,
+            Term::Sub(Box::new(Term::Ident("balance".into())), Box::new(Term::Ident("amount".into())))
+        );
+    }
+
+    #[test]
+    fn term_parse_rejects_a_relational_operator() {
+        assert!(Term::parse("amount > 0").is_err());
+    }
+
+    #[test]
+    fn evaluates_arithmetic_with_precedence() {
+        let term = Term::parse("amount + fee * 2").unwrap();
+        let env = [("amount".to_string(), 10.0), ("fee".to_string(), 3.0)].into();
+        assert_eq!(term.eval(&env), Some(16.0));
+    }
+
+    #[test]
+    fn eval_is_none_for_a_missing_identifier() {
+        let term = Term::parse("balance - amount").unwrap();
+        let env = [("balance".to_string(), 100.0)].into();
+        assert_eq!(term.eval(&env), None);
+    }
+
+    #[test]
+    fn eval_is_none_for_a_call() {
+        let expr = ConstraintExpr::parse("amount <= balance(account)").unwrap();
+        let env = [("amount".to_string(), 10.0), ("account".to_string(), 1.0)].into();
+        assert_eq!(expr.eval(&env), None);
+    }
+
+    #[test]
+    fn evaluates_a_comparison() {
+        let expr = ConstraintExpr::parse("amount > 0").unwrap();
+        assert_eq!(expr.eval(&[("amount".to_string(), 5.0)].into()), Some(true));
+        assert_eq!(expr.eval(&[("amount".to_string(), -5.0)].into()), Some(false));
+    }
+
+    #[test]
+    fn evaluates_and_or() {
+        let and = ConstraintExpr::parse("amount > 0 && amount <= balance").unwrap();
+        let env = [("amount".to_string(), 50.0), ("balance".to_string(), 100.0)].into();
+        assert_eq!(and.eval(&env), Some(true));
+
+        let or = ConstraintExpr::parse("amount < 0 || amount > balance").unwrap();
+        assert_eq!(or.eval(&env), Some(false));
+    }
+
+    #[test]
+    fn rejects_a_missing_operator() {
+        assert!(ConstraintExpr::parse("amount").is_err());
+    }
+
+    #[test]
+    fn rejects_trailing_garbage() {
+        assert!(ConstraintExpr::parse("amount > 0 extra").is_err());
+    }
+
+    #[test]
+    fn display_round_trips_through_rust_syntax() {
+        let expr = ConstraintExpr::parse("amount <= balance(account)").unwrap();
+        assert_eq!(expr.to_string(), "amount <= balance(account)");
+    }
+
+    #[test]
+    fn parses_and_composition() {
+        let expr = ConstraintExpr::parse("amount > 0 && amount <= balance(account)").unwrap();
+        assert!(matches!(expr, ConstraintExpr::And(..)));
+        assert_eq!(expr.comparisons().len(), 2);
+        assert_eq!(expr.to_string(), "amount > 0 && amount <= balance(account)");
+    }
+
+    #[test]
+    fn parses_or_composition() {
+        let expr = ConstraintExpr::parse("status == 1 || status == 2").unwrap();
+        assert!(matches!(expr, ConstraintExpr::Or(..)));
+        assert_eq!(expr.idents(), vec!["status"]);
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or_and_round_trips() {
+        let expr = ConstraintExpr::parse("a > 0 || b > 0 && c > 0").unwrap();
+        match &expr {
+            ConstraintExpr::Or(_, rhs) => assert!(matches!(**rhs, ConstraintExpr::And(..))),
+            other => panic!("expected a top-level Or, got {other:?}"),
+        }
+        assert_eq!(expr.to_string(), "a > 0 || b > 0 && c > 0");
+    }
+
+    #[test]
+    fn parenthesized_or_under_and_round_trips_with_parens() {
+        let expr = ConstraintExpr::parse("(a > 0 || b > 0) && c > 0").unwrap();
+        match &expr {
+            ConstraintExpr::And(lhs, _) => assert!(matches!(**lhs, ConstraintExpr::Or(..))),
+            other => panic!("expected a top-level And, got {other:?}"),
+        }
+        assert_eq!(expr.to_string(), "(a > 0 || b > 0) && c > 0");
+    }
+
+    #[test]
+    fn parenthesized_arithmetic_term_is_not_mistaken_for_a_sub_expression() {
+        let expr = ConstraintExpr::parse("(amount + fee) > 0").unwrap();
+        assert_eq!(
+            compare(&expr).lhs,
+            Term::Add(
+                Box::new(Term::Ident("amount".into())),
+                Box::new(Term::Ident("fee".into())),
+            )
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! First-class finite-state-machine declarations: an [`Fsm`] names the
+//! discrete states something in the domain moves through (e.g. a bank
+//! account: `Open` -> `Frozen` -> `Closed`) and the transitions between
+//! them, so `states`/`transitions` are native to the spec instead of prose
+//! a reader has to infer from requirement text. [`Fsm::issues`] checks a
+//! declared machine for structural problems that are easy to introduce by
+//! hand: a state nothing can reach, a transition to/from an undeclared
+//! state, and two transitions for the same state/event pair that disagree
+//! on where they land.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// One transition in an [`Fsm`]: `event` moves the machine from `from` to
+/// `to`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct FsmTransition {
+    pub from: String,
+    pub event: String,
+    pub to: String,
+}
+
+/// A named finite state machine: the discrete states something in the
+/// domain moves through and the transitions between them. The first
+/// entry in `states` is the machine's initial state, for
+/// [`Fsm::issues`]'s reachability check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Fsm {
+    pub name: String,
+    #[serde(default)]
+    pub states: Vec<String>,
+    #[serde(default, rename = "transition", alias = "transitions")]
+    pub transitions: Vec<FsmTransition>,
+}
+
+/// A structural problem with an [`Fsm`], found by [`Fsm::issues`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FsmIssue {
+    /// A transition's `from` or `to` names a state that isn't declared in
+    /// `states`.
+    UndeclaredState { state: String },
+    /// No sequence of transitions from the initial state reaches this
+    /// state.
+    UnreachableState { state: String },
+    /// The same `(from, event)` pair transitions to more than one `to`
+    /// state, so the machine doesn't deterministically know where to go.
+    NondeterministicTransition {
+        from: String,
+        event: String,
+        targets: Vec<String>,
+    },
+    /// Every transition for this event starts from a state the initial
+    /// state can't reach, so the event can never actually fire.
+    DeadOperation { event: String },
+}
+
+impl fmt::Display for FsmIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FsmIssue::UndeclaredState { state } => {
+                write!(f, "`{state}` is used in a transition but not declared in `states`")
+            }
+            FsmIssue::UnreachableState { state } => {
+                write!(f, "`{state}` is unreachable from the initial state")
+            }
+            FsmIssue::NondeterministicTransition {
+                from,
+                event,
+                targets,
+            } => write!(
+                f,
+                "`{from}` on `{event}` is nondeterministic: could go to {}",
+                targets.join(" or ")
+            ),
+            FsmIssue::DeadOperation { event } => {
+                write!(f, "`{event}` is enabled in no state reachable from the initial state")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FsmIssue {}
+
+impl Fsm {
+    /// Checks this machine for undeclared states, unreachable states,
+    /// nondeterministic transitions, and events that only ever fire from
+    /// an unreachable state.
+    pub fn issues(&self) -> Vec<FsmIssue> {
+        let mut issues = Vec::new();
+
+        for t in &self.transitions {
+            if !self.states.contains(&t.from) {
+                issues.push(FsmIssue::UndeclaredState {
+                    state: t.from.clone(),
+                });
+            }
+            if !self.states.contains(&t.to) {
+                issues.push(FsmIssue::UndeclaredState {
+                    state: t.to.clone(),
+                });
+            }
+        }
+
+        let mut targets_by_key: BTreeMap<(&str, &str), Vec<&str>> = BTreeMap::new();
+        for t in &self.transitions {
+            let targets = targets_by_key
+                .entry((t.from.as_str(), t.event.as_str()))
+                .or_default();
+            if !targets.contains(&t.to.as_str()) {
+                targets.push(t.to.as_str());
+            }
+        }
+        for ((from, event), targets) in targets_by_key {
+            if targets.len() > 1 {
+                issues.push(FsmIssue::NondeterministicTransition {
+                    from: from.to_string(),
+                    event: event.to_string(),
+                    targets: targets.into_iter().map(str::to_string).collect(),
+                });
+            }
+        }
+
+        if let Some(initial) = self.states.first() {
+            let mut reachable: HashSet<&str> = HashSet::new();
+            reachable.insert(initial.as_str());
+            let mut frontier = vec![initial.as_str()];
+            while let Some(state) = frontier.pop() {
+                for t in &self.transitions {
+                    if t.from == state && reachable.insert(t.to.as_str()) {
+                        frontier.push(t.to.as_str());
+                    }
+                }
+            }
+            for state in &self.states {
+                if !reachable.contains(state.as_str()) {
+                    issues.push(FsmIssue::UnreachableState {
+                        state: state.clone(),
+                    });
+                }
+            }
+
+            let mut events: BTreeMap<&str, bool> = BTreeMap::new();
+            for t in &self.transitions {
+                let enabled = events.entry(t.event.as_str()).or_insert(false);
+                *enabled = *enabled || reachable.contains(t.from.as_str());
+            }
+            for (event, enabled_somewhere) in events {
+                if !enabled_somewhere {
+                    issues.push(FsmIssue::DeadOperation {
+                        event: event.to_string(),
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account_fsm() -> Fsm {
+        Fsm {
+            name: "Account".into(),
+            states: vec!["Open".into(), "Frozen".into(), "Closed".into()],
+            transitions: vec![
+                FsmTransition {
+                    from: "Open".into(),
+                    event: "freeze".into(),
+                    to: "Frozen".into(),
+                },
+                FsmTransition {
+                    from: "Frozen".into(),
+                    event: "unfreeze".into(),
+                    to: "Open".into(),
+                },
+                FsmTransition {
+                    from: "Open".into(),
+                    event: "close".into(),
+                    to: "Closed".into(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let fsm = account_fsm();
+        let toml = toml::to_string(&fsm).unwrap();
+        let back: Fsm = toml::from_str(&toml).unwrap();
+        assert_eq!(fsm, back);
+    }
+
+    #[test]
+    fn well_formed_machine_has_no_issues() {
+        assert_eq!(account_fsm().issues(), vec![]);
+    }
+
+    #[test]
+    fn flags_an_unreachable_state() {
+        let mut fsm = account_fsm();
+        fsm.states.push("Archived".into());
+        assert_eq!(
+            fsm.issues(),
+            vec![FsmIssue::UnreachableState {
+                state: "Archived".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn flags_a_transition_to_an_undeclared_state() {
+        let mut fsm = account_fsm();
+        fsm.transitions.push(FsmTransition {
+            from: "Open".into(),
+            event: "archive".into(),
+            to: "Archived".into(),
+        });
+        assert_eq!(
+            fsm.issues(),
+            vec![FsmIssue::UndeclaredState {
+                state: "Archived".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn flags_an_operation_only_reachable_from_a_dead_state() {
+        let mut fsm = account_fsm();
+        fsm.states.push("Archived".into());
+        fsm.transitions.push(FsmTransition {
+            from: "Archived".into(),
+            event: "purge".into(),
+            to: "Archived".into(),
+        });
+        assert_eq!(
+            fsm.issues(),
+            vec![
+                FsmIssue::UnreachableState {
+                    state: "Archived".into()
+                },
+                FsmIssue::DeadOperation {
+                    event: "purge".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_a_nondeterministic_transition() {
+        let mut fsm = account_fsm();
+        fsm.transitions.push(FsmTransition {
+            from: "Open".into(),
+            event: "freeze".into(),
+            to: "Closed".into(),
+        });
+        assert_eq!(
+            fsm.issues(),
+            vec![FsmIssue::NondeterministicTransition {
+                from: "Open".into(),
+                event: "freeze".into(),
+                targets: vec!["Frozen".into(), "Closed".into()],
+            }]
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Gherkin front end for [`SpecDocument`](super::SpecDocument): each
+//! `Scenario` becomes a requirement/constraint, named by a tag placed on the
+//! line(s) above it (`@REQ-004` or `@CONST-001`), with the scenario's title
+//! as its text.
+//!
+//! ```gherkin
+//! @REQ-004
+//! Scenario: balance reflects deposits
+//!   Given an account with no deposits
+//!   ...
+//! ```
+
+use std::fmt;
+
+use super::{Constraint, Requirement, SpecDocument};
+
+/// Error parsing a Gherkin feature file.
+#[derive(Debug, PartialEq)]
+pub enum GherkinLoadError {
+    /// A `Scenario:` line had no `@REQ-...`/`@CONST-...` tag above it.
+    UntaggedScenario { line: usize },
+}
+
+impl fmt::Display for GherkinLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GherkinLoadError::UntaggedScenario { line } => {
+                write!(f, "line {line}: scenario has no @REQ-... or @CONST-... tag")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GherkinLoadError {}
+
+pub fn from_str(input: &str) -> Result<SpecDocument, GherkinLoadError> {
+    let mut doc = SpecDocument::new();
+    let mut pending_tags: Vec<&str> = Vec::new();
+
+    for (i, raw_line) in input.lines().enumerate() {
+        let line = raw_line.trim();
+
+        if line.starts_with('@') {
+            pending_tags.extend(line.split_whitespace().filter_map(|t| t.strip_prefix('@')));
+            continue;
+        }
+
+        let Some(title) = line
+            .strip_prefix("Scenario:")
+            .or_else(|| line.strip_prefix("Scenario Outline:"))
+        else {
+            if !line.is_empty() && !line.starts_with("Feature:") {
+                // Not a tag or scenario line; tags only apply to the next
+                // Scenario, so anything else clears them.
+                pending_tags.clear();
+            }
+            continue;
+        };
+        let title = title.trim().to_string();
+
+        let req_tag = pending_tags.iter().find(|t| t.starts_with("REQ-")).copied();
+        let const_tag = pending_tags
+            .iter()
+            .find(|t| t.starts_with("CONST-"))
+            .copied();
+
+        if req_tag.is_none() && const_tag.is_none() {
+            return Err(GherkinLoadError::UntaggedScenario { line: i + 1 });
+        }
+        if let Some(id) = req_tag {
+            doc.requirements.push(Requirement {
+                id: id.to_string(),
+                text: title.clone(),
+                ..Default::default()
+            });
+        }
+        if let Some(code) = const_tag {
+            doc.constraints.push(Constraint {
+                code: code.to_string(),
+                text: title,
+                ..Default::default()
+            });
+        }
+        pending_tags.clear();
+    }
+
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_tagged_scenario_to_requirement() {
+        let doc = from_str(
+            "Feature: bank account\n\n  @REQ-004\n  Scenario: balance reflects deposits\n    Given an account\n",
+        )
+        .unwrap();
+        assert_eq!(
+            doc.requirement("REQ-004").unwrap().text,
+            "balance reflects deposits"
+        );
+    }
+
+    #[test]
+    fn maps_const_tag_to_constraint() {
+        let doc = from_str("@CONST-001\nScenario: rejects non-positive deposits\n").unwrap();
+        assert_eq!(
+            doc.constraint("CONST-001").unwrap().text,
+            "rejects non-positive deposits"
+        );
+    }
+
+    #[test]
+    fn rejects_untagged_scenario() {
+        let err = from_str("Scenario: no tag\n").unwrap_err();
+        assert_eq!(err, GherkinLoadError::UntaggedScenario { line: 1 });
+    }
+}
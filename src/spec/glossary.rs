@@ -0,0 +1,184 @@
+//! Glossary terms and consistency checking: a term shouldn't be defined
+//! twice with different text, and its casing should stay consistent
+//! wherever it appears in requirement/constraint text.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::SpecDocument;
+
+/// A defined term, e.g. "account": "a ledger identified by an id that holds
+/// a balance".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlossaryTerm {
+    pub term: String,
+    pub definition: String,
+}
+
+/// A glossary consistency problem.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GlossaryIssue {
+    /// The same term (case-insensitively) is defined more than once, with
+    /// different definitions.
+    ConflictingDefinition {
+        term: String,
+        definitions: Vec<String>,
+    },
+    /// A term appears in text with different casing than its definition.
+    InconsistentCase {
+        term: String,
+        found: String,
+        location: String,
+    },
+}
+
+impl fmt::Display for GlossaryIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlossaryIssue::ConflictingDefinition { term, definitions } => {
+                write!(
+                    f,
+                    "`{term}` has conflicting definitions: {}",
+                    definitions.join(" | ")
+                )
+            }
+            GlossaryIssue::InconsistentCase {
+                term,
+                found,
+                location,
+            } => {
+                write!(
+                    f,
+                    "`{found}` in {location} doesn't match glossary casing for `{term}`"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GlossaryIssue {}
+
+pub fn check(doc: &SpecDocument) -> Vec<GlossaryIssue> {
+    let mut issues = Vec::new();
+    check_conflicting_definitions(doc, &mut issues);
+    check_case_consistency(doc, &mut issues);
+    issues
+}
+
+fn check_conflicting_definitions(doc: &SpecDocument, issues: &mut Vec<GlossaryIssue>) {
+    let mut by_term: std::collections::HashMap<String, Vec<&str>> =
+        std::collections::HashMap::new();
+    for entry in &doc.glossary {
+        by_term
+            .entry(entry.term.to_lowercase())
+            .or_default()
+            .push(&entry.definition);
+    }
+    for (term, definitions) in by_term {
+        let mut distinct: Vec<&str> = Vec::new();
+        for d in &definitions {
+            if !distinct.contains(d) {
+                distinct.push(d);
+            }
+        }
+        if distinct.len() > 1 {
+            issues.push(GlossaryIssue::ConflictingDefinition {
+                term,
+                definitions: distinct.into_iter().map(str::to_string).collect(),
+            });
+        }
+    }
+}
+
+fn check_case_consistency(doc: &SpecDocument, issues: &mut Vec<GlossaryIssue>) {
+    for entry in &doc.glossary {
+        for req in &doc.requirements {
+            check_text(
+                &entry.term,
+                &req.text,
+                &format!("requirement {}", req.id),
+                issues,
+            );
+        }
+        for constraint in &doc.constraints {
+            check_text(
+                &entry.term,
+                &constraint.text,
+                &format!("constraint {}", constraint.code),
+                issues,
+            );
+        }
+    }
+}
+
+fn check_text(term: &str, text: &str, location: &str, issues: &mut Vec<GlossaryIssue>) {
+    for word in text.split_whitespace() {
+        let cleaned = word.trim_matches(|c: char| !c.is_alphanumeric());
+        if cleaned.eq_ignore_ascii_case(term) && cleaned != term {
+            issues.push(GlossaryIssue::InconsistentCase {
+                term: term.to_string(),
+                found: cleaned.to_string(),
+                location: location.to_string(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn detects_conflicting_definitions() {
+        let mut doc = SpecDocument::new();
+        doc.glossary.push(GlossaryTerm {
+            term: "account".into(),
+            definition: "a ledger".into(),
+        });
+        doc.glossary.push(GlossaryTerm {
+            term: "Account".into(),
+            definition: "a wallet".into(),
+        });
+
+        let issues = check(&doc);
+        assert!(
+            matches!(&issues[0], GlossaryIssue::ConflictingDefinition { term, .. } if term == "account")
+        );
+    }
+
+    #[test]
+    fn detects_inconsistent_case_in_requirement_text() {
+        let mut doc = SpecDocument::new();
+        doc.glossary.push(GlossaryTerm {
+            term: "account".into(),
+            definition: "a ledger".into(),
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-1".into(),
+            text: "an Account starts with zero balance".into(),
+            ..Default::default()
+        });
+
+        let issues = check(&doc);
+        assert!(issues.iter().any(
+            |i| matches!(i, GlossaryIssue::InconsistentCase { found, .. } if found == "Account")
+        ));
+    }
+
+    #[test]
+    fn consistent_usage_has_no_issues() {
+        let mut doc = SpecDocument::new();
+        doc.glossary.push(GlossaryTerm {
+            term: "account".into(),
+            definition: "a ledger".into(),
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-1".into(),
+            text: "an account starts with zero balance".into(),
+            ..Default::default()
+        });
+        assert!(check(&doc).is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+//! Configurable requirement/constraint id schemes: projects aren't all
+//! `REQ-004`/`CONST-001` — an [`IdScheme`] lets a project declare its own
+//! prefix and numeric width and check a document's ids against it.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A `{prefix}-{number, zero-padded to `width` digits}` id scheme, e.g.
+/// `IdScheme::new("REQ", 3)` matches `REQ-004` but not `REQ-4` or `FOO-004`.
+/// `Serialize`/`Deserialize` so it can be set as `[id_scheme]` in
+/// `libspec.toml`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdScheme {
+    pub prefix: String,
+    pub width: usize,
+}
+
+impl IdScheme {
+    pub fn new(prefix: impl Into<String>, width: usize) -> Self {
+        Self {
+            prefix: prefix.into(),
+            width,
+        }
+    }
+
+    /// Formats `number` as an id under this scheme.
+    pub fn format(&self, number: u32) -> String {
+        format!("{}-{:0width$}", self.prefix, number, width = self.width)
+    }
+
+    /// Whether `id` matches this scheme exactly.
+    pub fn matches(&self, id: &str) -> bool {
+        let Some(digits) = id
+            .strip_prefix(&self.prefix)
+            .and_then(|rest| rest.strip_prefix('-'))
+        else {
+            return false;
+        };
+        digits.len() == self.width && digits.chars().all(|c| c.is_ascii_digit())
+    }
+
+    /// Checks every id in `ids` against this scheme, returning the ones that
+    /// don't match.
+    pub fn violations<'a>(&self, ids: impl IntoIterator<Item = &'a str>) -> Vec<IdSchemeViolation> {
+        ids.into_iter()
+            .filter(|id| !self.matches(id))
+            .map(|id| IdSchemeViolation {
+                id: id.to_string(),
+                scheme: self.clone(),
+            })
+            .collect()
+    }
+}
+
+/// An id that doesn't conform to an [`IdScheme`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IdSchemeViolation {
+    pub id: String,
+    pub scheme: IdScheme,
+}
+
+impl fmt::Display for IdSchemeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` does not match id scheme `{}-{}digits`",
+            self.id, self.scheme.prefix, self.scheme.width
+        )
+    }
+}
+
+impl std::error::Error for IdSchemeViolation {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_exact_width() {
+        let scheme = IdScheme::new("REQ", 3);
+        assert!(scheme.matches("REQ-004"));
+        assert!(!scheme.matches("REQ-4"));
+        assert!(!scheme.matches("CONST-004"));
+    }
+
+    #[test]
+    fn format_zero_pads() {
+        assert_eq!(IdScheme::new("REQ", 3).format(4), "REQ-004");
+    }
+
+    #[test]
+    fn violations_reports_mismatches_only() {
+        let scheme = IdScheme::new("REQ", 3);
+        let violations = scheme.violations(["REQ-004", "REQ-4"]);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].id, "REQ-4");
+    }
+}
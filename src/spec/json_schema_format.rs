@@ -0,0 +1,112 @@
+//! JSON Schema import: turns a schema's `properties` validation keywords
+//! (`minimum`, `maximum`, `required`, ...) into [`Constraint`]s, so an
+//! existing service's schema can seed a spec instead of writing constraints
+//! by hand.
+
+use std::fmt;
+
+use serde_json::Value;
+
+use super::{Constraint, SpecDocument};
+
+/// Error importing a JSON Schema document.
+#[derive(Debug)]
+pub enum JsonSchemaImportError {
+    Json(serde_json::Error),
+    /// The top-level document wasn't a JSON object.
+    NotAnObject,
+}
+
+impl fmt::Display for JsonSchemaImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonSchemaImportError::Json(e) => write!(f, "invalid JSON Schema: {e}"),
+            JsonSchemaImportError::NotAnObject => {
+                write!(f, "JSON Schema document must be a JSON object")
+            }
+        }
+    }
+}
+
+impl std::error::Error for JsonSchemaImportError {}
+
+/// Keywords imported as one constraint each, per property.
+const IMPORTED_KEYWORDS: &[&str] = &[
+    "type",
+    "minimum",
+    "maximum",
+    "minLength",
+    "maxLength",
+    "pattern",
+];
+
+pub fn from_str(input: &str) -> Result<SpecDocument, JsonSchemaImportError> {
+    let schema: Value = serde_json::from_str(input).map_err(JsonSchemaImportError::Json)?;
+    let schema = schema
+        .as_object()
+        .ok_or(JsonSchemaImportError::NotAnObject)?;
+
+    let mut doc = SpecDocument::new();
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|a| a.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        for (name, prop) in properties {
+            let upper = name.to_uppercase();
+            if required.contains(&name.as_str()) {
+                doc.constraints.push(Constraint {
+                    code: format!("CONST-{upper}-REQUIRED"),
+                    text: format!("`{name}` is required"),
+                    ..Default::default()
+                });
+            }
+            for keyword in IMPORTED_KEYWORDS {
+                if let Some(value) = prop.get(keyword) {
+                    doc.constraints.push(Constraint {
+                        code: format!("CONST-{upper}-{}", keyword.to_uppercase()),
+                        text: format!("`{name}` must satisfy {keyword} = {value}"),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_required_and_range_keywords() {
+        let doc = from_str(
+            r#"{
+                "required": ["amount"],
+                "properties": {
+                    "amount": { "type": "number", "minimum": 0 }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert!(doc.constraint("CONST-AMOUNT-REQUIRED").is_some());
+        assert_eq!(
+            doc.constraint("CONST-AMOUNT-MINIMUM").unwrap().text,
+            "`amount` must satisfy minimum = 0"
+        );
+        assert!(doc.constraint("CONST-AMOUNT-TYPE").is_some());
+    }
+
+    #[test]
+    fn rejects_non_object_schema() {
+        assert!(matches!(
+            from_str("[1, 2, 3]"),
+            Err(JsonSchemaImportError::NotAnObject)
+        ));
+    }
+}
@@ -0,0 +1,93 @@
+//! A streaming, line-delimited JSON front end for requirements: one
+//! `Requirement` JSON object per line, so a spec running to tens of
+//! thousands of requirements can be scanned one at a time instead of
+//! parsed into a full [`super::SpecDocument`] — and its one allocation-heavy
+//! `Vec<Requirement>` — up front. [`crate::lint::StreamingLinter`] and
+//! [`crate::trace::rows_streaming`] consume the iterator this module
+//! returns directly.
+
+use std::fmt;
+use std::io::BufRead;
+
+use super::Requirement;
+
+/// Error reading or parsing one line of a line-delimited requirement
+/// stream.
+#[derive(Debug)]
+pub enum JsonlLoadError {
+    Io(std::io::Error),
+    Parse {
+        line: usize,
+        source: serde_json::Error,
+    },
+}
+
+impl fmt::Display for JsonlLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonlLoadError::Io(e) => write!(f, "{e}"),
+            JsonlLoadError::Parse { line, source } => write!(f, "line {line}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for JsonlLoadError {}
+
+/// Reads requirements one line at a time from `reader`, yielding one
+/// `Result<Requirement, JsonlLoadError>` per non-blank line. Nothing past
+/// the current line is held in memory at once, so `reader` can be a
+/// buffered file handle over a spec far too large to load as one string.
+pub fn from_reader(reader: impl BufRead) -> impl Iterator<Item = Result<Requirement, JsonlLoadError>> {
+    reader.lines().enumerate().filter_map(|(i, line)| {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => return Some(Err(JsonlLoadError::Io(e))),
+        };
+        if line.trim().is_empty() {
+            return None;
+        }
+        Some(
+            serde_json::from_str(&line)
+                .map_err(|source| JsonlLoadError::Parse { line: i + 1, source }),
+        )
+    })
+}
+
+/// [`from_reader`] over an in-memory string, for callers that already have
+/// one (e.g. a test, or a spec small enough not to need the streaming
+/// path but still in this format).
+pub fn from_str(input: &str) -> impl Iterator<Item = Result<Requirement, JsonlLoadError>> + '_ {
+    from_reader(std::io::Cursor::new(input.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yields_one_requirement_per_line() {
+        let input = "{\"id\": \"REQ-001\", \"text\": \"first\"}\n{\"id\": \"REQ-002\", \"text\": \"second\"}\n";
+        let reqs: Vec<Requirement> = from_str(input).collect::<Result<_, _>>().unwrap();
+        assert_eq!(reqs.len(), 2);
+        assert_eq!(reqs[0].id, "REQ-001");
+        assert_eq!(reqs[1].id, "REQ-002");
+    }
+
+    #[test]
+    fn skips_blank_lines() {
+        let input = "{\"id\": \"REQ-001\", \"text\": \"first\"}\n\n";
+        let reqs: Vec<Requirement> = from_str(input).collect::<Result<_, _>>().unwrap();
+        assert_eq!(reqs.len(), 1);
+    }
+
+    #[test]
+    fn reports_the_line_number_of_a_malformed_entry() {
+        let input = "{\"id\": \"REQ-001\", \"text\": \"first\"}\nnot json\n";
+        let results: Vec<_> = from_str(input).collect();
+        assert!(results[0].is_ok());
+        match &results[1] {
+            Err(JsonlLoadError::Parse { line, .. }) => assert_eq!(*line, 2),
+            other => panic!("expected a Parse error, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Resolves a [`SpecDocument`]'s `includes` directives, so a large spec can
+//! be split across files instead of living in one monolith.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::{SpecDocument, TomlLoadError};
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        source: TomlLoadError,
+    },
+    /// `a` includes `b` which (transitively) includes `a` again.
+    Cycle { path: PathBuf },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            LoadError::Parse { path, source } => write!(f, "{}: {source}", path.display()),
+            LoadError::Cycle { path } => write!(f, "{}: include cycle detected", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+pub fn load_toml_file(path: &Path) -> Result<SpecDocument, LoadError> {
+    let mut stack = Vec::new();
+    load(path, &mut stack)
+}
+
+fn load(path: &Path, stack: &mut Vec<PathBuf>) -> Result<SpecDocument, LoadError> {
+    let canonical = path.canonicalize().map_err(|e| LoadError::Io {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    if stack.contains(&canonical) {
+        return Err(LoadError::Cycle {
+            path: path.to_path_buf(),
+        });
+    }
+
+    let contents = std::fs::read_to_string(path).map_err(|e| LoadError::Io {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    let mut doc = SpecDocument::from_toml_str(&contents).map_err(|e| LoadError::Parse {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+
+    let includes = std::mem::take(&mut doc.includes);
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    stack.push(canonical);
+    for include in includes {
+        let included = load(&dir.join(&include), stack)?;
+        doc.merge(included);
+    }
+    stack.pop();
+
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn merges_included_requirements() {
+        let dir = std::env::temp_dir().join(format!("libspec-loader-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        write(
+            &dir,
+            "child.toml",
+            "[[requirement]]\nid = \"REQ-002\"\ntext = \"child requirement\"\n",
+        );
+        let root = write(
+            &dir,
+            "root.toml",
+            "includes = [\"child.toml\"]\n\n[[requirement]]\nid = \"REQ-001\"\ntext = \"root requirement\"\n",
+        );
+
+        let doc = load_toml_file(&root).unwrap();
+        assert!(doc.requirement("REQ-001").is_some());
+        assert!(doc.requirement("REQ-002").is_some());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn detects_include_cycle() {
+        let dir = std::env::temp_dir().join(format!("libspec-loader-cycle-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        write(&dir, "a.toml", "includes = [\"b.toml\"]\n");
+        let b = write(&dir, "b.toml", "includes = [\"a.toml\"]\n");
+
+        assert!(matches!(load_toml_file(&b), Err(LoadError::Cycle { .. })));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,149 @@
+//! Markdown front end for [`SpecDocument`](super::SpecDocument): requirements
+//! and constraints are written as fenced code blocks tagged `requirement` or
+//! `constraint`, e.g.
+//!
+//! ```markdown
+//! ```requirement
+//! id: REQ-004
+//! text: balance() returns the current balance
+//! ```
+//! ```
+
+use std::fmt;
+
+use super::{Constraint, Requirement, SpecDocument};
+
+/// Error parsing a Markdown spec document.
+#[derive(Debug, PartialEq)]
+pub enum MarkdownLoadError {
+    /// A `requirement`/`constraint` fence was opened but never closed.
+    UnterminatedFence { line: usize },
+    /// A fence was missing its `id:`/`code:` field.
+    MissingField { line: usize, field: &'static str },
+}
+
+impl fmt::Display for MarkdownLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarkdownLoadError::UnterminatedFence { line } => {
+                write!(f, "line {line}: fenced block is never closed with ```")
+            }
+            MarkdownLoadError::MissingField { line, field } => {
+                write!(f, "line {line}: fenced block is missing `{field}:`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MarkdownLoadError {}
+
+/// Pulls `key: value` out of a fence line, if it starts with `key:`.
+fn field(line: &str, key: &str) -> Option<String> {
+    let rest = line.strip_prefix(key)?.strip_prefix(':')?;
+    Some(rest.trim().to_string())
+}
+
+pub fn from_str(input: &str) -> Result<SpecDocument, MarkdownLoadError> {
+    let mut doc = SpecDocument::new();
+    let lines: Vec<&str> = input.lines().collect();
+    let mut i = 0;
+    while i < lines.len() {
+        let trimmed = lines[i].trim();
+        let kind = trimmed
+            .strip_prefix("```requirement")
+            .map(|_| "requirement")
+            .or_else(|| trimmed.strip_prefix("```constraint").map(|_| "constraint"));
+
+        let Some(kind) = kind else {
+            i += 1;
+            continue;
+        };
+
+        let fence_start = i;
+        let mut id = None;
+        let mut text = None;
+        i += 1;
+        loop {
+            if i >= lines.len() {
+                return Err(MarkdownLoadError::UnterminatedFence {
+                    line: fence_start + 1,
+                });
+            }
+            if lines[i].trim() == "```" {
+                break;
+            }
+            if id.is_none() {
+                id = field(
+                    lines[i].trim(),
+                    if kind == "requirement" { "id" } else { "code" },
+                );
+            }
+            if let Some(t) = field(lines[i].trim(), "text") {
+                text = Some(t);
+            }
+            i += 1;
+        }
+        i += 1;
+
+        let field_name = if kind == "requirement" { "id" } else { "code" };
+        let id = id.ok_or(MarkdownLoadError::MissingField {
+            line: fence_start + 1,
+            field: field_name,
+        })?;
+        let text = text.ok_or(MarkdownLoadError::MissingField {
+            line: fence_start + 1,
+            field: "text",
+        })?;
+
+        if kind == "requirement" {
+            doc.requirements.push(Requirement {
+                id,
+                text,
+                ..Default::default()
+            });
+        } else {
+            doc.constraints.push(Constraint { code: id, text, ..Default::default() });
+        }
+    }
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fenced_requirement_and_constraint() {
+        let doc = from_str(
+            "# Spec\n\n```requirement\nid: REQ-004\ntext: balance() returns the current balance\n```\n\n```constraint\ncode: CONST-001\ntext: amount must be positive\n```\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            doc.requirement("REQ-004").unwrap().text,
+            "balance() returns the current balance"
+        );
+        assert_eq!(
+            doc.constraint("CONST-001").unwrap().text,
+            "amount must be positive"
+        );
+    }
+
+    #[test]
+    fn rejects_unterminated_fence() {
+        let err = from_str("```requirement\nid: REQ-1\n").unwrap_err();
+        assert_eq!(err, MarkdownLoadError::UnterminatedFence { line: 1 });
+    }
+
+    #[test]
+    fn rejects_missing_field() {
+        let err = from_str("```requirement\ntext: no id here\n```\n").unwrap_err();
+        assert_eq!(
+            err,
+            MarkdownLoadError::MissingField {
+                line: 1,
+                field: "id"
+            }
+        );
+    }
+}
@@ -0,0 +1,992 @@
+//! The in-memory spec model shared by every front-end format and every
+//! code generator: [`SpecDocument`], its [`Requirement`]s, and the
+//! [`Constraint`]s that give requirements a machine-checkable id like
+//! `CONST-001`.
+
+mod asciidoc_format;
+mod cli_contract;
+mod conformance_vectors;
+mod csv_format;
+mod data_type;
+mod enumeration;
+mod event;
+mod expr;
+mod fsm;
+mod gherkin_format;
+mod glossary;
+mod id_scheme;
+mod json_schema_format;
+mod jsonl_format;
+mod loader;
+mod markdown_format;
+mod openapi_format;
+mod query;
+mod spans;
+mod state_machine;
+mod template;
+mod toml_format;
+mod yaml_format;
+
+pub use asciidoc_format::AsciiDocLoadError;
+pub use cli_contract::{CliContract, CliInvocation};
+pub use conformance_vectors::{ConformanceStep, ConformanceVector};
+pub use csv_format::{ColumnMapping, CsvImportError};
+pub use data_type::{DataType, Field};
+pub use enumeration::Enumeration;
+pub use event::DomainEvent;
+pub use expr::{Comparison, ConstraintExpr, ExprParseError, RelOp, Term};
+pub use fsm::{Fsm, FsmIssue, FsmTransition};
+pub use gherkin_format::GherkinLoadError;
+pub use glossary::{GlossaryIssue, GlossaryTerm};
+pub use id_scheme::{IdScheme, IdSchemeViolation};
+pub use json_schema_format::JsonSchemaImportError;
+pub use jsonl_format::JsonlLoadError;
+pub use loader::LoadError;
+pub use markdown_format::MarkdownLoadError;
+pub use openapi_format::OpenApiImportError;
+pub use query::{Query, QueryParseError, Queryable};
+pub use spans::{scan as source_spans, Span, SourceMap};
+pub use state_machine::{StateMachine, Transition};
+pub use template::{InstantiateError, MissingParam, RequirementTemplate};
+pub use toml_format::TomlLoadError;
+pub use yaml_format::YamlLoadError;
+
+use serde::{Deserialize, Serialize};
+
+/// A single requirement, e.g. "REQ-004: deposits must be rejected if the
+/// account is locked."
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Requirement {
+    pub id: String,
+    pub text: String,
+    #[serde(default)]
+    pub priority: Priority,
+    #[serde(default)]
+    pub status: Status,
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// The team responsible for this requirement's implementation, for
+    /// [`crate::codeowners`]'s CODEOWNERS-style mapping and review
+    /// routing — distinct from [`owner`](Self::owner), which names an
+    /// individual rather than a team.
+    #[serde(default)]
+    pub team: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Ids of requirements this one depends on.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Ids of requirements this one refines (narrows/specializes).
+    #[serde(default)]
+    pub refines: Vec<String>,
+    /// Ids of requirements this one conflicts with.
+    #[serde(default)]
+    pub conflicts_with: Vec<String>,
+    /// An external issue tracker reference, e.g.
+    /// `"github:drhodes/libspec#42"`. See [`crate::tracker::sync`].
+    #[serde(default)]
+    pub tracker: Option<String>,
+    /// The id of the requirement that supersedes this one, set once
+    /// [`Requirement::status`] is [`Status::Deprecated`]. Generated trait
+    /// methods carry it through to the `#[deprecated]` attribute's note.
+    /// See [`crate::codegen::rust_trait`].
+    #[serde(default)]
+    pub replaced_by: Option<String>,
+    /// A quantitative performance budget on this requirement's operation,
+    /// e.g. "balance() completes in under 1ms for 10k accounts". See
+    /// [`crate::codegen::rust_bench`].
+    #[serde(default)]
+    pub perf_budget: Option<PerfBudget>,
+    /// Why this requirement exists, beyond what `text` states. See
+    /// [`crate::completeness`].
+    #[serde(default)]
+    pub rationale: Option<String>,
+    /// Concrete, checkable conditions for calling this requirement done,
+    /// each individually addressable by a `#[covers]` record or
+    /// [`crate::trace::CoverageMatrix`] row as `"{id}.{criterion.id}"`
+    /// (e.g. `"REQ-004.a"`), instead of the requirement being covered
+    /// all-or-nothing. See [`crate::completeness`] and
+    /// [`AcceptanceCriterion`].
+    #[serde(default)]
+    pub acceptance_criteria: Vec<AcceptanceCriterion>,
+    /// Worked examples (inputs and expected results) illustrating this
+    /// requirement. See [`crate::completeness`].
+    #[serde(default)]
+    pub examples: Vec<String>,
+    /// Marks this as a durability requirement: the state it describes
+    /// must survive a process restart, not merely stay correct while the
+    /// process keeps running. Unlike [`Requirement::perf_budget`], there's
+    /// no generated check for this — it's verified by a harness scenario
+    /// that closes and reopens the implementation's persistence backend
+    /// between operations, e.g. `PersistentBankLibrary` in
+    /// `examples/bank-account`.
+    #[serde(default)]
+    pub durable: bool,
+    /// The HTTP verb a REST frontend invokes this requirement's operation
+    /// with, e.g. `"POST"` for a deposit. Paired with
+    /// [`Requirement::http_path`]; a requirement with only one of the two
+    /// set has no route a generated client can call. See
+    /// [`crate::codegen::http_client`].
+    #[serde(default)]
+    pub http_method: Option<String>,
+    /// The HTTP path template this requirement's operation is invoked at,
+    /// e.g. `"/accounts/{account_id}/deposit"`. See
+    /// [`crate::codegen::http_client`].
+    #[serde(default)]
+    pub http_path: Option<String>,
+    /// How this requirement is checked off — the verification-method
+    /// column legacy requirement matrices track alongside id/text/
+    /// priority (see the CSV importer's `ColumnMapping`). Unset means the
+    /// method wasn't tracked at all, which
+    /// [`CoverageMatrix::enforce`](crate::trace::CoverageMatrix::enforce)
+    /// treats the same as [`Test`](VerificationMethod::Test): only a
+    /// requirement explicitly verified some other way is exempted from
+    /// the automated-test gate in favor of a recorded sign-off.
+    #[serde(default)]
+    pub verification_method: Option<VerificationMethod>,
+    /// What kind of requirement this is. Drives per-kind policy: see
+    /// [`crate::kind_policy`] for the checks a [`Security`](RequirementKind::Security)
+    /// or [`Performance`](RequirementKind::Performance) requirement is
+    /// held to, and [`crate::trace::CoverageMatrix::by_kind`] for
+    /// coverage broken down by kind.
+    #[serde(default)]
+    pub kind: RequirementKind,
+    /// This requirement's risk profile, if assessed. Unset means no risk
+    /// assessment has been done, which
+    /// [`crate::risk_policy::check`] leaves unconstrained rather than
+    /// treating as the lowest risk level. See [`RiskRating`].
+    #[serde(default)]
+    pub risk: Option<RiskRating>,
+    /// Named conditions (e.g. `"overdraft"` for a feature flag, `"eu"` for
+    /// a deployment profile) that must all be active for this requirement
+    /// to apply. Empty means it always applies. Checked by
+    /// [`SpecDocument::for_conditions`], which codegen,
+    /// [`crate::trace::CoverageMatrix`], and `libspec_harness::Conformance`'s
+    /// condition-aware filtering are all expected to run a document
+    /// through before treating this requirement as live.
+    #[serde(default)]
+    pub applies_when: Vec<String>,
+}
+
+/// What a requirement is fundamentally about, beyond its text. Unlike
+/// [`Requirement::tags`], which are free-form and project-defined, `kind`
+/// is a closed taxonomy the tooling itself understands and can attach
+/// policy to — see [`crate::kind_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RequirementKind {
+    /// An ordinary behavioral requirement. The default, so specs written
+    /// before `kind` existed don't need updating.
+    #[default]
+    Functional,
+    /// A requirement whose violation risks harm rather than merely
+    /// incorrect output, e.g. "the reactor must scram within 2 seconds of
+    /// an overtemperature reading".
+    Safety,
+    /// A requirement about resisting a hostile input or actor, e.g.
+    /// "withdrawals must reject a forged authorization token". See
+    /// [`crate::kind_policy`] for the negative-test policy this kind
+    /// carries.
+    Security,
+    /// A requirement about how fast or how much, typically paired with a
+    /// [`Requirement::perf_budget`]. See [`crate::kind_policy`] for the
+    /// benchmark policy this kind carries.
+    Performance,
+}
+
+/// One of the classic verification methods a requirement is checked off
+/// by, as used in requirement matrices (DO-178C, IEEE 29148, and similar
+/// standards all draw on the same four). Which method a requirement
+/// carries changes what
+/// [`CoverageMatrix::enforce`](crate::trace::CoverageMatrix::enforce)
+/// demands of it: [`Test`](Self::Test) requirements need an automated
+/// covering test, the rest need a recorded
+/// [`SignOff`](crate::trace::SignOff) instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VerificationMethod {
+    /// Checked by running an automated test against the implementation.
+    #[default]
+    Test,
+    /// Checked by inspecting the implementation (e.g. a code review)
+    /// rather than running it.
+    Inspection,
+    /// Checked by reasoning about the implementation (e.g. a proof or a
+    /// calculation) rather than running or reading it.
+    Analysis,
+    /// Checked by observing the running system perform the behavior,
+    /// without the fine-grained pass/fail assertions a test would make.
+    Demonstration,
+}
+
+/// A requirement's likelihood/severity risk rating, as tracked by a
+/// standard risk matrix: `likelihood` is how often the failure this
+/// requirement guards against is expected to occur, `severity` is how
+/// bad it is when it does. See [`crate::risk_policy`] for the evidence a
+/// high [`RiskRating::overall`] demands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RiskRating {
+    pub likelihood: RiskLevel,
+    pub severity: RiskLevel,
+}
+
+impl RiskRating {
+    /// The rating's overall risk level: the worse of its two axes, the
+    /// way a risk matrix's overall rating is driven by whichever axis is
+    /// worse, not their average.
+    pub fn overall(&self) -> RiskLevel {
+        self.likelihood.max(self.severity)
+    }
+}
+
+/// One axis of a [`RiskRating`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+/// One discrete, checkable condition within a
+/// [`Requirement::acceptance_criteria`] checklist. `id` is just the
+/// suffix (e.g. `"a"`); a covering record or coverage row addresses it
+/// by the requirement id and this suffix joined with a dot (e.g.
+/// `"REQ-004.a"`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AcceptanceCriterion {
+    pub id: String,
+    pub text: String,
+}
+
+/// A quantitative performance budget on a requirement's operation,
+/// checked by a generated criterion benchmark rather than by an ordinary
+/// assertion. See [`crate::codegen::rust_bench`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct PerfBudget {
+    /// The input size (e.g. accounts, records) the budget is measured at,
+    /// e.g. `10_000` for "... for 10k accounts".
+    pub scale: u64,
+    /// The longest the operation may take at [`PerfBudget::scale`],
+    /// in milliseconds.
+    pub max_millis: f64,
+}
+
+/// How important a requirement is to get right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Critical,
+}
+
+/// Where a requirement is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    #[default]
+    Draft,
+    Approved,
+    Implemented,
+    Deprecated,
+}
+
+/// How strictly a violated constraint should be treated. Generated checks
+/// (see [`crate::codegen::rust_guard`]) return an error for [`Error`], and
+/// report the violation without failing for [`Warning`]/[`Advisory`] — a
+/// deployment registers a [`crate::runtime::Sink`] to decide what to do
+/// with those (log, alert, ignore).
+///
+/// [`Error`]: Severity::Error
+/// [`Warning`]: Severity::Warning
+/// [`Advisory`]: Severity::Advisory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+    Advisory,
+}
+
+/// A machine-checkable rule a requirement relies on, e.g. `CONST-001`
+/// ("amount must be positive").
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Constraint {
+    pub code: String,
+    pub text: String,
+    /// An optional machine-checkable condition in the [`expr`] DSL, e.g.
+    /// `"amount > 0"` or `"amount <= balance(account)"`. When present,
+    /// [`crate::codegen::rust_guard`] can generate a real check function
+    /// from it instead of leaving the constraint as prose.
+    #[serde(default)]
+    pub expr: Option<String>,
+    /// How strictly a violation of this constraint should be treated.
+    /// Defaults to [`Severity::Error`], matching how constraints behaved
+    /// before severity was configurable.
+    #[serde(default)]
+    pub severity: Severity,
+    /// The HTTP status a REST frontend should return when this constraint
+    /// is violated, e.g. `404` for "account not found" or `409` for
+    /// "account already exists". See
+    /// [`crate::codegen::http_status`].
+    #[serde(default)]
+    pub http_status: Option<u16>,
+}
+
+impl Constraint {
+    /// Parses [`Constraint::expr`], if set, into a [`ConstraintExpr`].
+    pub fn parsed_expr(&self) -> Option<Result<ConstraintExpr, ExprParseError>> {
+        self.expr.as_deref().map(ConstraintExpr::parse)
+    }
+}
+
+/// Localized text for constraint messages in one locale (e.g. `"en"`,
+/// `"fr"`), keyed by constraint code — the code stays the stable key
+/// across locales, so adding a language doesn't touch [`Constraint`]
+/// declarations. See [`SpecDocument::message_catalog`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct LocaleCatalog {
+    pub locale: String,
+    #[serde(default)]
+    pub messages: std::collections::BTreeMap<String, String>,
+}
+
+/// A parsed spec: the requirements and constraints it declares.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpecDocument {
+    #[serde(default, rename = "requirement", alias = "requirements")]
+    pub requirements: Vec<Requirement>,
+    #[serde(default, rename = "constraint", alias = "constraints")]
+    pub constraints: Vec<Constraint>,
+    /// Other spec files to merge into this one, resolved relative to the
+    /// including file. See [`SpecDocument::load_toml_file`].
+    #[serde(default)]
+    pub includes: Vec<String>,
+    /// Parameterized requirements, stamped into concrete requirements via
+    /// [`SpecDocument::instantiate`].
+    #[serde(default, rename = "template", alias = "templates")]
+    pub templates: Vec<RequirementTemplate>,
+    /// Defined terms, checked for consistent use across requirement and
+    /// constraint text by [`SpecDocument::glossary_issues`].
+    #[serde(default, rename = "glossary")]
+    pub glossary: Vec<GlossaryTerm>,
+    /// Record types the spec talks about, e.g. `Account` or `Transaction`.
+    #[serde(default, rename = "type", alias = "types")]
+    pub data_types: Vec<DataType>,
+    /// Closed sets of named values a [`Field`] can be constrained to, e.g.
+    /// `Currency = ["USD", "EUR", "GBP"]`.
+    #[serde(default, rename = "enum", alias = "enums")]
+    pub enums: Vec<Enumeration>,
+    /// Per-locale constraint message overrides. See
+    /// [`SpecDocument::message_catalog`].
+    #[serde(default, rename = "locale", alias = "locales")]
+    pub locales: Vec<LocaleCatalog>,
+    /// Abstract state models for stateful property testing. See
+    /// [`crate::codegen::state_machine`].
+    #[serde(default, rename = "state_machine", alias = "state_machines")]
+    pub state_machines: Vec<StateMachine>,
+    /// Discrete finite-state machines, e.g. an account's `Open` ->
+    /// `Frozen` -> `Closed` lifecycle. See [`Fsm::issues`] and
+    /// [`crate::codegen::fsm`].
+    #[serde(default, rename = "fsm", alias = "fsms")]
+    pub fsms: Vec<Fsm>,
+    /// Golden conformance vectors: canonical operation sequences with
+    /// their expected outputs/error codes. See
+    /// [`SpecDocument::to_conformance_vectors_json`].
+    #[serde(default, rename = "conformance_vector", alias = "conformance_vectors")]
+    pub conformance_vectors: Vec<ConformanceVector>,
+    /// Domain events the spec declares, e.g. `AccountCreated`,
+    /// `FundsWithdrawn`, so event-driven systems carry the same
+    /// machine-checked contract a request/response API gets. See
+    /// [`crate::codegen::event_schema`].
+    #[serde(default, rename = "event", alias = "events")]
+    pub events: Vec<DomainEvent>,
+    /// CLI contracts: binaries and the invocations they must satisfy. See
+    /// [`crate::codegen::rust_cli_test`].
+    #[serde(default, rename = "cli_contract", alias = "cli_contracts")]
+    pub cli_contracts: Vec<CliContract>,
+}
+
+impl SpecDocument {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn requirement(&self, id: &str) -> Option<&Requirement> {
+        self.requirements.iter().find(|r| r.id == id)
+    }
+
+    pub fn constraint(&self, code: &str) -> Option<&Constraint> {
+        self.constraints.iter().find(|c| c.code == code)
+    }
+
+    /// Parses `expr` as a [`Query`] and returns every requirement it
+    /// matches, in spec order. `covers:` clauses always fail, since this
+    /// method has no trace records to answer them with — a caller that
+    /// does (`cargo spec query`, the TUI) should parse the query once
+    /// with [`Query::parse`] and call [`Query::matches_with_coverage`]
+    /// per requirement instead.
+    pub fn query(&self, expr: &str) -> Result<Vec<&Requirement>, QueryParseError> {
+        let query = Query::parse(expr)?;
+        Ok(self.requirements.iter().filter(|r| query.matches(*r)).collect())
+    }
+
+    /// Drops every requirement whose [`Requirement::applies_when`] names a
+    /// condition not present in `active_conditions` (e.g. a requirement
+    /// gated on the `"overdraft"` feature or an `"eu"` deployment profile),
+    /// leaving everything else — constraints, templates, the rest of the
+    /// document — unchanged. A requirement with no conditions always
+    /// survives. Codegen, coverage gating, and the conformance harness are
+    /// all meant to run a document through this before treating it as the
+    /// live spec for a given build/deployment, so a requirement that
+    /// doesn't apply is never generated, never counted against coverage
+    /// thresholds, and never reported as unexercised.
+    pub fn for_conditions(&self, active_conditions: &[&str]) -> SpecDocument {
+        let mut doc = self.clone();
+        doc.requirements
+            .retain(|r| r.applies_when.iter().all(|c| active_conditions.contains(&c.as_str())));
+        doc
+    }
+
+    /// Builds a [`crate::error::MessageCatalog`] from every declared
+    /// [`LocaleCatalog`], for resolving a [`crate::error::SpecError`]'s
+    /// message in the caller's locale at runtime.
+    pub fn message_catalog(&self) -> crate::error::MessageCatalog {
+        let mut catalog = crate::error::MessageCatalog::new();
+        for locale in &self.locales {
+            for (code, message) in &locale.messages {
+                catalog.insert(locale.locale.clone(), code.clone(), message.clone());
+            }
+        }
+        catalog
+    }
+
+    pub fn data_type(&self, name: &str) -> Option<&DataType> {
+        self.data_types.iter().find(|d| d.name == name)
+    }
+
+    pub fn enumeration(&self, name: &str) -> Option<&Enumeration> {
+        self.enums.iter().find(|e| e.name == name)
+    }
+
+    pub fn state_machine(&self, name: &str) -> Option<&StateMachine> {
+        self.state_machines.iter().find(|s| s.name == name)
+    }
+
+    pub fn fsm(&self, name: &str) -> Option<&Fsm> {
+        self.fsms.iter().find(|f| f.name == name)
+    }
+
+    pub fn event(&self, name: &str) -> Option<&DomainEvent> {
+        self.events.iter().find(|e| e.name == name)
+    }
+
+    /// Exports this document's [`ConformanceVector`]s as a pretty-printed
+    /// JSON array, for non-Rust implementations to validate themselves
+    /// against.
+    pub fn to_conformance_vectors_json(&self) -> String {
+        conformance_vectors::export_string(self)
+    }
+
+    /// A hex digest of this document's full content, stable across runs
+    /// (unlike [`std::collections::HashMap`]'s randomized default hasher)
+    /// and changing whenever any requirement, constraint, or other
+    /// declared item changes. Used to pin a generated conformance suite
+    /// to the exact spec it was generated from, so running it against an
+    /// implementation built for a different version of the spec can be
+    /// caught instead of silently testing the wrong contract.
+    pub fn version_hash(&self) -> String {
+        use std::hash::{Hash, Hasher};
+
+        let json = serde_json::to_string(self).expect("SpecDocument serialization is infallible");
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        json.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// Checks this document's [`version_hash`](Self::version_hash) against
+    /// `expected_hash` — typically a constant a `build.rs` embedded via
+    /// `libspec-build`'s `emit_version` at the time an implementation was
+    /// built. A mismatch means the spec changed since then, caught here
+    /// instead of surfacing as a confusing failure somewhere deep in
+    /// generated code.
+    pub fn verify_version_hash(
+        &self,
+        expected_hash: &str,
+    ) -> Result<(), crate::error::SpecError> {
+        let actual = self.version_hash();
+        if actual == expected_hash {
+            Ok(())
+        } else {
+            Err(crate::error::SpecError::new(
+                "SPEC-VERSION-MISMATCH",
+                "spec version hash does not match the version this code was built against",
+            )
+            .with_details(format!("expected {expected_hash}, found {actual}")))
+        }
+    }
+
+    /// Appends `other`'s requirements and constraints onto this document;
+    /// used to fold included spec files into their parent.
+    pub fn merge(&mut self, mut other: SpecDocument) {
+        self.requirements.append(&mut other.requirements);
+        self.constraints.append(&mut other.constraints);
+        self.templates.append(&mut other.templates);
+        self.glossary.append(&mut other.glossary);
+        self.data_types.append(&mut other.data_types);
+        self.enums.append(&mut other.enums);
+        self.state_machines.append(&mut other.state_machines);
+        self.fsms.append(&mut other.fsms);
+        self.conformance_vectors.append(&mut other.conformance_vectors);
+        self.events.append(&mut other.events);
+        self.cli_contracts.append(&mut other.cli_contracts);
+    }
+
+    pub fn template(&self, id: &str) -> Option<&RequirementTemplate> {
+        self.templates.iter().find(|t| t.id == id)
+    }
+
+    /// Instantiates a template by id and appends the resulting requirement.
+    pub fn instantiate(
+        &mut self,
+        template_id: &str,
+        params: &[(&str, &str)],
+    ) -> Result<(), InstantiateError> {
+        let template = self
+            .template(template_id)
+            .ok_or(InstantiateError::UnknownTemplate)?
+            .clone();
+        self.requirements.push(template.instantiate(params)?);
+        Ok(())
+    }
+
+    /// Parses a [`SpecDocument`] from a TOML string.
+    pub fn from_toml_str(input: &str) -> Result<Self, TomlLoadError> {
+        toml_format::from_str(input)
+    }
+
+    /// Parses a [`SpecDocument`] from a YAML string.
+    pub fn from_yaml_str(input: &str) -> Result<Self, YamlLoadError> {
+        yaml_format::from_str(input)
+    }
+
+    /// Parses a [`SpecDocument`] from Markdown with fenced `requirement`/
+    /// `constraint` blocks.
+    pub fn from_markdown_str(input: &str) -> Result<Self, MarkdownLoadError> {
+        markdown_format::from_str(input)
+    }
+
+    /// Parses a [`SpecDocument`] from AsciiDoc, mapping `[req,id=...]`/
+    /// `[constraint,code=...]` attribute lists and their following
+    /// `----`-delimited listing block into a requirement/constraint.
+    pub fn from_asciidoc_str(input: &str) -> Result<Self, AsciiDocLoadError> {
+        asciidoc_format::from_str(input)
+    }
+
+    /// Parses a [`SpecDocument`] from a Gherkin feature file, mapping each
+    /// `@REQ-.../@CONST-...`-tagged `Scenario` to a requirement/constraint.
+    pub fn from_gherkin_str(input: &str) -> Result<Self, GherkinLoadError> {
+        gherkin_format::from_str(input)
+    }
+
+    /// Imports constraints from a JSON Schema document's `properties`
+    /// validation keywords.
+    pub fn from_json_schema_str(input: &str) -> Result<Self, JsonSchemaImportError> {
+        json_schema_format::from_str(input)
+    }
+
+    /// Imports one requirement per operation from an OpenAPI document.
+    pub fn from_openapi_str(input: &str) -> Result<Self, OpenApiImportError> {
+        openapi_format::import_str(input)
+    }
+
+    /// Imports a [`SpecDocument`] from a CSV requirement matrix exported by
+    /// a legacy tool, per `mapping`'s column names.
+    pub fn from_csv_str(input: &str, mapping: &ColumnMapping) -> Result<Self, CsvImportError> {
+        csv_format::import_str(input, mapping)
+    }
+
+    /// Streams requirements one line at a time from a line-delimited JSON
+    /// string, without materializing a [`SpecDocument`]. See
+    /// [`requirements_from_jsonl_reader`] for specs too large to hold as
+    /// one string, and [`crate::lint::StreamingLinter`]/
+    /// [`crate::trace::rows_streaming`] for consuming the result without
+    /// collecting it into a `Vec` either.
+    pub fn requirements_from_jsonl_str(
+        input: &str,
+    ) -> impl Iterator<Item = Result<Requirement, JsonlLoadError>> + '_ {
+        jsonl_format::from_str(input)
+    }
+
+    /// Streams requirements one line at a time from a line-delimited JSON
+    /// [`std::io::BufRead`] (e.g. a buffered file handle), for specs too
+    /// large to load into one string first.
+    pub fn requirements_from_jsonl_reader(
+        reader: impl std::io::BufRead,
+    ) -> impl Iterator<Item = Result<Requirement, JsonlLoadError>> {
+        jsonl_format::from_reader(reader)
+    }
+
+    /// Exports this document as a pretty-printed OpenAPI 3.0 JSON document,
+    /// one `GET /requirements/{id}` operation per requirement.
+    pub fn to_openapi_string(&self) -> String {
+        openapi_format::export_string(self)
+    }
+
+    /// Loads a TOML spec file, recursively resolving its `includes` (paths
+    /// relative to the including file) and merging them in.
+    pub fn load_toml_file(path: impl AsRef<std::path::Path>) -> Result<Self, LoadError> {
+        loader::load_toml_file(path.as_ref())
+    }
+
+    /// Checks this document's requirement ids against `scheme`, returning
+    /// the ones that don't conform.
+    pub fn requirement_id_violations(&self, scheme: &IdScheme) -> Vec<IdSchemeViolation> {
+        scheme.violations(self.requirements.iter().map(|r| r.id.as_str()))
+    }
+
+    /// Checks the glossary for duplicate/conflicting terms and inconsistent
+    /// case when a term is used in requirement or constraint text.
+    pub fn glossary_issues(&self) -> Vec<GlossaryIssue> {
+        glossary::check(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instantiate_appends_a_requirement_from_a_template() {
+        let mut doc = SpecDocument::new();
+        doc.templates.push(RequirementTemplate {
+            id: "REQ-ACCOUNT-{n}".into(),
+            text: "account {n} starts at zero balance".into(),
+        });
+
+        doc.instantiate("REQ-ACCOUNT-{n}", &[("n", "1")]).unwrap();
+        assert_eq!(
+            doc.requirement("REQ-ACCOUNT-1").unwrap().text,
+            "account 1 starts at zero balance"
+        );
+    }
+
+    #[test]
+    fn message_catalog_is_keyed_by_locale_then_constraint_code() {
+        let mut doc = SpecDocument::new();
+        doc.locales.push(LocaleCatalog {
+            locale: "fr".into(),
+            messages: [("CONST-001".to_string(), "fonds insuffisants".to_string())].into(),
+        });
+
+        let catalog = doc.message_catalog();
+        assert_eq!(catalog.get("fr", "CONST-001"), Some("fonds insuffisants"));
+        assert_eq!(catalog.get("fr", "CONST-002"), None);
+        assert_eq!(catalog.get("de", "CONST-001"), None);
+    }
+
+    #[test]
+    fn requirement_metadata_defaults() {
+        let req = Requirement {
+            id: "REQ-1".into(),
+            text: "text".into(),
+            ..Default::default()
+        };
+        assert_eq!(req.priority, Priority::Medium);
+        assert_eq!(req.status, Status::Draft);
+        assert_eq!(req.owner, None);
+        assert!(req.tags.is_empty());
+    }
+
+    #[test]
+    fn requirement_metadata_round_trips_through_toml() {
+        let doc = SpecDocument::from_toml_str(
+            r#"
+            [[requirement]]
+            id = "REQ-1"
+            text = "text"
+            priority = "high"
+            status = "approved"
+            owner = "alice"
+            tags = ["billing"]
+            "#,
+        )
+        .unwrap();
+        let req = doc.requirement("REQ-1").unwrap();
+        assert_eq!(req.priority, Priority::High);
+        assert_eq!(req.status, Status::Approved);
+        assert_eq!(req.owner.as_deref(), Some("alice"));
+        assert_eq!(req.tags, vec!["billing".to_string()]);
+    }
+
+    #[test]
+    fn enums_round_trip_through_toml() {
+        let doc = SpecDocument::from_toml_str(
+            r#"
+            [[enum]]
+            name = "Currency"
+            values = ["USD", "EUR", "GBP"]
+            "#,
+        )
+        .unwrap();
+        let currency = doc.enumeration("Currency").unwrap();
+        assert!(currency.contains("EUR"));
+        assert!(!currency.contains("JPY"));
+        assert_eq!(doc.enumeration("Unknown"), None);
+    }
+
+    #[test]
+    fn for_conditions_keeps_unconditioned_requirements() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement { id: "REQ-1".into(), text: "text".into(), ..Default::default() });
+
+        assert_eq!(doc.for_conditions(&[]).requirements.len(), 1);
+    }
+
+    #[test]
+    fn for_conditions_drops_a_requirement_missing_an_active_condition() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-OVERDRAFT".into(),
+            text: "text".into(),
+            applies_when: vec!["overdraft".into()],
+            ..Default::default()
+        });
+
+        assert!(doc.for_conditions(&[]).requirements.is_empty());
+        assert_eq!(doc.for_conditions(&["overdraft"]).requirements.len(), 1);
+    }
+
+    #[test]
+    fn for_conditions_requires_every_named_condition() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-EU-OVERDRAFT".into(),
+            text: "text".into(),
+            applies_when: vec!["overdraft".into(), "eu".into()],
+            ..Default::default()
+        });
+
+        assert!(doc.for_conditions(&["overdraft"]).requirements.is_empty());
+        assert_eq!(doc.for_conditions(&["overdraft", "eu"]).requirements.len(), 1);
+    }
+
+    #[test]
+    fn instantiate_rejects_unknown_template() {
+        let mut doc = SpecDocument::new();
+        assert_eq!(
+            doc.instantiate("REQ-UNKNOWN", &[]).unwrap_err(),
+            InstantiateError::UnknownTemplate
+        );
+    }
+
+    #[test]
+    fn perf_budget_round_trips_through_toml() {
+        let doc = SpecDocument::from_toml_str(
+            r#"
+            [[requirement]]
+            id = "REQ-004"
+            text = "balance() returns the current balance"
+
+            [requirement.perf_budget]
+            scale = 10000
+            max_millis = 1.0
+            "#,
+        )
+        .unwrap();
+        let budget = doc.requirement("REQ-004").unwrap().perf_budget.as_ref().unwrap();
+        assert_eq!(budget.scale, 10_000);
+        assert_eq!(budget.max_millis, 1.0);
+    }
+
+    #[test]
+    fn durable_defaults_to_false() {
+        let req = Requirement {
+            id: "REQ-1".into(),
+            text: "text".into(),
+            ..Default::default()
+        };
+        assert!(!req.durable);
+    }
+
+    #[test]
+    fn durable_round_trips_through_toml() {
+        let doc = SpecDocument::from_toml_str(
+            r#"
+            [[requirement]]
+            id = "REQ-010"
+            text = "an acknowledged deposit survives a process restart"
+            durable = true
+            "#,
+        )
+        .unwrap();
+        assert!(doc.requirement("REQ-010").unwrap().durable);
+    }
+
+    #[test]
+    fn kind_defaults_to_functional() {
+        let req = Requirement {
+            id: "REQ-1".into(),
+            text: "text".into(),
+            ..Default::default()
+        };
+        assert_eq!(req.kind, RequirementKind::Functional);
+    }
+
+    #[test]
+    fn kind_round_trips_through_toml() {
+        let doc = SpecDocument::from_toml_str(
+            r#"
+            [[requirement]]
+            id = "REQ-1"
+            text = "withdrawals reject a forged authorization token"
+            kind = "security"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(doc.requirement("REQ-1").unwrap().kind, RequirementKind::Security);
+    }
+
+    #[test]
+    fn verification_method_defaults_to_unset() {
+        let req = Requirement {
+            id: "REQ-1".into(),
+            text: "text".into(),
+            ..Default::default()
+        };
+        assert_eq!(req.verification_method, None);
+    }
+
+    #[test]
+    fn verification_method_round_trips_through_toml() {
+        let doc = SpecDocument::from_toml_str(
+            r#"
+            [[requirement]]
+            id = "REQ-1"
+            text = "text"
+            verification_method = "inspection"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            doc.requirement("REQ-1").unwrap().verification_method,
+            Some(VerificationMethod::Inspection)
+        );
+    }
+
+    #[test]
+    fn risk_defaults_to_unset() {
+        let req = Requirement {
+            id: "REQ-1".into(),
+            text: "text".into(),
+            ..Default::default()
+        };
+        assert_eq!(req.risk, None);
+    }
+
+    #[test]
+    fn risk_round_trips_through_toml() {
+        let doc = SpecDocument::from_toml_str(
+            r#"
+            [[requirement]]
+            id = "REQ-1"
+            text = "text"
+            [requirement.risk]
+            likelihood = "high"
+            severity = "medium"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            doc.requirement("REQ-1").unwrap().risk,
+            Some(RiskRating { likelihood: RiskLevel::High, severity: RiskLevel::Medium })
+        );
+    }
+
+    #[test]
+    fn risk_overall_is_the_worse_of_its_two_axes() {
+        let rating = RiskRating { likelihood: RiskLevel::Low, severity: RiskLevel::High };
+        assert_eq!(rating.overall(), RiskLevel::High);
+
+        let rating = RiskRating { likelihood: RiskLevel::Medium, severity: RiskLevel::Low };
+        assert_eq!(rating.overall(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn requirement_without_a_perf_budget_defaults_to_none() {
+        let req = Requirement {
+            id: "REQ-1".into(),
+            text: "text".into(),
+            ..Default::default()
+        };
+        assert_eq!(req.perf_budget, None);
+    }
+
+    #[test]
+    fn version_hash_is_stable_for_an_unchanged_document() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+
+        assert_eq!(doc.version_hash(), doc.version_hash());
+    }
+
+    #[test]
+    fn version_hash_changes_when_the_document_changes() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+        let before = doc.version_hash();
+
+        doc.requirements.push(Requirement {
+            id: "REQ-005".into(),
+            text: "deposits must be positive".into(),
+            ..Default::default()
+        });
+
+        assert_ne!(before, doc.version_hash());
+    }
+
+    #[test]
+    fn verify_version_hash_accepts_its_own_hash() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+
+        assert_eq!(doc.verify_version_hash(&doc.version_hash()), Ok(()));
+    }
+
+    #[test]
+    fn verify_version_hash_rejects_a_stale_hash() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+        let stale = doc.version_hash();
+
+        doc.requirements.push(Requirement {
+            id: "REQ-005".into(),
+            text: "deposits must be positive".into(),
+            ..Default::default()
+        });
+
+        let err = doc.verify_version_hash(&stale).unwrap_err();
+        assert_eq!(err.code, "SPEC-VERSION-MISMATCH");
+    }
+}
@@ -0,0 +1,150 @@
+//! OpenAPI import: each operation in an OpenAPI document's `paths` becomes a
+//! requirement, so a spec can be bootstrapped from a service that already
+//! has an OpenAPI description instead of being written from scratch.
+
+use std::fmt;
+
+use serde_json::{json, Value};
+
+use super::{Requirement, SpecDocument};
+
+#[derive(Debug)]
+pub enum OpenApiImportError {
+    Json(serde_json::Error),
+    NotAnObject,
+}
+
+impl fmt::Display for OpenApiImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenApiImportError::Json(e) => write!(f, "invalid OpenAPI document: {e}"),
+            OpenApiImportError::NotAnObject => write!(f, "OpenAPI document must be a JSON object"),
+        }
+    }
+}
+
+impl std::error::Error for OpenApiImportError {}
+
+const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+pub fn import_str(input: &str) -> Result<SpecDocument, OpenApiImportError> {
+    let root: Value = serde_json::from_str(input).map_err(OpenApiImportError::Json)?;
+    let root = root.as_object().ok_or(OpenApiImportError::NotAnObject)?;
+
+    let mut doc = SpecDocument::new();
+    let Some(paths) = root.get("paths").and_then(Value::as_object) else {
+        return Ok(doc);
+    };
+
+    for (path, item) in paths {
+        let Some(item) = item.as_object() else {
+            continue;
+        };
+        for method in HTTP_METHODS {
+            let Some(operation) = item.get(*method).and_then(Value::as_object) else {
+                continue;
+            };
+            let id = operation
+                .get("operationId")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("REQ-{}-{}", method.to_uppercase(), path));
+            let text = operation
+                .get("summary")
+                .or_else(|| operation.get("description"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path));
+            doc.requirements.push(Requirement {
+                id,
+                text,
+                ..Default::default()
+            });
+        }
+    }
+
+    Ok(doc)
+}
+
+/// Exports a [`SpecDocument`] as a minimal OpenAPI 3.0 document: one
+/// `GET /requirements/{id}` operation per requirement, named by its id and
+/// described by its text, as a traceable (if not executable) counterpart to
+/// [`import_str`].
+pub fn export(doc: &SpecDocument) -> Value {
+    let mut paths = serde_json::Map::new();
+    for Requirement { id, text, .. } in &doc.requirements {
+        paths.insert(
+            format!("/requirements/{id}"),
+            json!({
+                "get": {
+                    "operationId": id,
+                    "summary": text,
+                    "responses": { "200": { "description": "OK" } }
+                }
+            }),
+        );
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": { "title": "libspec export", "version": "1.0.0" },
+        "paths": Value::Object(paths),
+    })
+}
+
+/// Exports a [`SpecDocument`] as a pretty-printed OpenAPI JSON string.
+pub fn export_string(doc: &SpecDocument) -> String {
+    serde_json::to_string_pretty(&export(doc)).expect("Value serialization is infallible")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_one_requirement_per_operation() {
+        let doc = import_str(
+            r#"{
+                "paths": {
+                    "/accounts/{id}/balance": {
+                        "get": { "operationId": "REQ-004", "summary": "balance() returns the current balance" }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            doc.requirement("REQ-004").unwrap().text,
+            "balance() returns the current balance"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_method_and_path_when_unnamed() {
+        let doc = import_str(r#"{"paths": {"/ping": {"get": {}}}}"#).unwrap();
+        assert_eq!(doc.requirements.len(), 1);
+        assert_eq!(doc.requirements[0].id, "REQ-GET-/ping");
+    }
+
+    #[test]
+    fn exports_one_operation_per_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+
+        let exported = export(&doc);
+        assert_eq!(
+            exported["paths"]["/requirements/REQ-004"]["get"]["operationId"],
+            "REQ-004"
+        );
+        assert_eq!(
+            exported["paths"]["/requirements/REQ-004"]["get"]["summary"],
+            "balance() returns the current balance"
+        );
+    }
+}
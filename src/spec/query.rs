@@ -0,0 +1,245 @@
+//! A small query language for slicing a spec down to the requirements a
+//! user cares about, e.g. `kind:security status:approved tag:money` to
+//! find approved money-handling security requirements, instead of
+//! writing a one-off `requirements.iter().filter(...)` for every ad hoc
+//! question. Space-separated `field:value` clauses are ANDed together;
+//! there's no OR or grouping — the grammar is deliberately no richer
+//! than [`SpecDocument::query`](super::SpecDocument::query) needs.
+//!
+//! `covers:none`/`covers:some` is the one clause [`Query::matches`]
+//! can't answer by itself, since coverage lives in trace records outside
+//! the spec, not on [`Requirement`] — callers with that data (`cargo
+//! spec`, the TUI) pass the covered ids they already computed via
+//! [`Query::matches_with_coverage`]; [`SpecDocument::query`] itself, having
+//! none, treats every requirement as uncovered.
+//!
+//! [`Queryable`] is what lets the same clauses run against both a bare
+//! [`Requirement`] and a [`CoverageMatrix`](crate::trace::CoverageMatrix)
+//! row, which carries most of the same fields under different names
+//! (`requirement` instead of `id`) plus its own tests already joined in.
+
+use std::fmt;
+
+use crate::spec::{Requirement, RequirementKind, Status, VerificationMethod};
+
+/// The fields of a requirement [`Query`] can filter on, implemented by
+/// both [`Requirement`] itself and
+/// [`CoverageRow`](crate::trace::CoverageRow) so the same query runs
+/// against either.
+pub trait Queryable {
+    fn query_id(&self) -> &str;
+    fn query_kind(&self) -> RequirementKind;
+    fn query_status(&self) -> Status;
+    fn query_tags(&self) -> &[String];
+    fn query_owner(&self) -> Option<&str>;
+    fn query_verification_method(&self) -> Option<VerificationMethod>;
+}
+
+impl Queryable for Requirement {
+    fn query_id(&self) -> &str {
+        &self.id
+    }
+    fn query_kind(&self) -> RequirementKind {
+        self.kind
+    }
+    fn query_status(&self) -> Status {
+        self.status
+    }
+    fn query_tags(&self) -> &[String] {
+        &self.tags
+    }
+    fn query_owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+    fn query_verification_method(&self) -> Option<VerificationMethod> {
+        self.verification_method
+    }
+}
+
+/// One `field:value` clause of a [`Query`].
+#[derive(Debug, Clone, PartialEq)]
+enum Predicate {
+    Id(String),
+    Kind(RequirementKind),
+    Status(Status),
+    Tag(String),
+    Owner(String),
+    VerificationMethod(VerificationMethod),
+    Covers(CoverageState),
+}
+
+/// The value side of a `covers:` clause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CoverageState {
+    None,
+    Some,
+}
+
+/// A parsed query: a conjunction of [`Predicate`]s, built by [`Query::parse`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    predicates: Vec<Predicate>,
+}
+
+impl Query {
+    /// Parses a space-separated list of `field:value` clauses. Supported
+    /// fields: `id`, `kind`, `status`, `tag`, `owner`, `verification`,
+    /// `covers` (`none`/`some`). An empty (or all-whitespace) input
+    /// parses to a query that matches every requirement.
+    pub fn parse(input: &str) -> Result<Self, QueryParseError> {
+        let mut predicates = Vec::new();
+        for clause in input.split_whitespace() {
+            let (field, value) = clause.split_once(':').ok_or_else(|| {
+                error(format!("expected `field:value`, got `{clause}`"))
+            })?;
+            predicates.push(parse_clause(field, value)?);
+        }
+        Ok(Query { predicates })
+    }
+
+    /// Whether `item` satisfies every clause of this query, treating
+    /// `covers:` clauses as unanswerable and always false — see
+    /// [`Query::matches_with_coverage`] for a caller that has coverage
+    /// data to answer them with.
+    pub fn matches<T: Queryable>(&self, item: &T) -> bool {
+        self.matches_with_coverage(item, false)
+    }
+
+    /// Like [`Query::matches`], but a `covers:` clause is answered by
+    /// `is_covered` (whether the caller found at least one `covers`
+    /// trace record naming `item`'s id) instead of always failing.
+    pub fn matches_with_coverage<T: Queryable>(&self, item: &T, is_covered: bool) -> bool {
+        self.predicates.iter().all(|predicate| match predicate {
+            Predicate::Id(id) => item.query_id() == id,
+            Predicate::Kind(kind) => item.query_kind() == *kind,
+            Predicate::Status(status) => item.query_status() == *status,
+            Predicate::Tag(tag) => item.query_tags().contains(tag),
+            Predicate::Owner(owner) => item.query_owner() == Some(owner.as_str()),
+            Predicate::VerificationMethod(method) => item.query_verification_method() == Some(*method),
+            Predicate::Covers(CoverageState::Some) => is_covered,
+            Predicate::Covers(CoverageState::None) => !is_covered,
+        })
+    }
+}
+
+fn parse_clause(field: &str, value: &str) -> Result<Predicate, QueryParseError> {
+    match field {
+        "id" => Ok(Predicate::Id(value.to_string())),
+        "kind" => parse_enum_value(value).map(Predicate::Kind),
+        "status" => parse_enum_value(value).map(Predicate::Status),
+        "tag" => Ok(Predicate::Tag(value.to_string())),
+        "owner" => Ok(Predicate::Owner(value.to_string())),
+        "verification" => parse_enum_value(value).map(Predicate::VerificationMethod),
+        "covers" => match value {
+            "none" => Ok(Predicate::Covers(CoverageState::None)),
+            "some" => Ok(Predicate::Covers(CoverageState::Some)),
+            other => Err(error(format!("`covers` must be `none` or `some`, got `{other}`"))),
+        },
+        other => Err(error(format!("unknown query field `{other}`"))),
+    }
+}
+
+/// Parses `value` as one of a `#[serde(rename_all = "...")]` enum's
+/// variants by deserializing it through the same serde impl the spec's
+/// TOML loader uses, instead of hand-writing a second name table that
+/// could drift from it.
+fn parse_enum_value<T: serde::de::DeserializeOwned>(value: &str) -> Result<T, QueryParseError> {
+    toml::Value::String(value.to_string())
+        .try_into()
+        .map_err(|_: toml::de::Error| error(format!("invalid value `{value}`")))
+}
+
+/// A query that couldn't be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryParseError {
+    pub message: String,
+}
+
+impl fmt::Display for QueryParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for QueryParseError {}
+
+fn error(message: impl Into<String>) -> QueryParseError {
+    QueryParseError { message: message.into() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req() -> Requirement {
+        Requirement {
+            id: "REQ-004".into(),
+            kind: RequirementKind::Security,
+            status: Status::Approved,
+            tags: vec!["money".into()],
+            owner: Some("alice".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn an_empty_query_matches_everything() {
+        assert!(Query::parse("").unwrap().matches(&req()));
+        assert!(Query::parse("   ").unwrap().matches(&req()));
+    }
+
+    #[test]
+    fn a_single_matching_clause_matches() {
+        assert!(Query::parse("kind:security").unwrap().matches(&req()));
+    }
+
+    #[test]
+    fn a_single_mismatching_clause_fails() {
+        assert!(!Query::parse("kind:performance").unwrap().matches(&req()));
+    }
+
+    #[test]
+    fn clauses_are_conjunctive() {
+        let query = Query::parse("kind:security status:approved tag:money").unwrap();
+        assert!(query.matches(&req()));
+
+        let query = Query::parse("kind:security status:draft").unwrap();
+        assert!(!query.matches(&req()));
+    }
+
+    #[test]
+    fn tag_owner_and_id_match_by_exact_value() {
+        assert!(Query::parse("id:REQ-004").unwrap().matches(&req()));
+        assert!(Query::parse("owner:alice").unwrap().matches(&req()));
+        assert!(!Query::parse("owner:bob").unwrap().matches(&req()));
+        assert!(!Query::parse("tag:fraud").unwrap().matches(&req()));
+    }
+
+    #[test]
+    fn covers_without_coverage_data_treats_everything_as_uncovered() {
+        assert!(Query::parse("covers:none").unwrap().matches(&req()));
+        assert!(!Query::parse("covers:some").unwrap().matches(&req()));
+    }
+
+    #[test]
+    fn covers_with_coverage_data_answers_from_it() {
+        let query = Query::parse("covers:some").unwrap();
+        assert!(query.matches_with_coverage(&req(), true));
+        assert!(!query.matches_with_coverage(&req(), false));
+    }
+
+    #[test]
+    fn an_unknown_field_is_a_parse_error() {
+        assert!(Query::parse("bogus:x").is_err());
+    }
+
+    #[test]
+    fn a_clause_missing_a_colon_is_a_parse_error() {
+        assert!(Query::parse("kindsecurity").is_err());
+    }
+
+    #[test]
+    fn an_invalid_enum_value_is_a_parse_error() {
+        assert!(Query::parse("kind:nonsense").is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! Byte-span source locations for a TOML spec file's `[[requirement]]`/
+//! `[[constraint]]` entries and their fields, read straight from the raw
+//! text rather than threaded through [`SpecDocument`](super::SpecDocument)'s
+//! owned, fully-parsed fields — the same scan-the-raw-text approach
+//! `libspec-lsp::index` already uses for go-to-definition, generalized
+//! from line/column to byte offsets and from requirement/constraint ids
+//! alone to every field on them. A true zero-copy, lifetime-carrying
+//! rewrite of `SpecDocument` itself would ripple through every front-end
+//! format, every codegen backend, and every trace/diff/lint consumer in
+//! this crate that assumes owned `String` fields; this gives diagnostics,
+//! the LSP, and the formatter exact byte ranges to point at without that.
+
+use std::collections::HashMap;
+
+/// A byte range into the input a [`SourceMap`] was built from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// The slice of `input` this span covers. Panics if `input` isn't
+    /// the same text the owning [`SourceMap`] was built from.
+    pub fn as_str<'a>(&self, input: &'a str) -> &'a str {
+        &input[self.start..self.end]
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct EntrySpans {
+    entry: Span,
+    fields: HashMap<String, Span>,
+}
+
+/// Where each `[[requirement]]`/`[[constraint]]` entry in a spec's raw
+/// TOML text — and each `key = "..."` field inside it — starts and ends,
+/// as of whatever text [`scan`] last read. Entries are keyed by their
+/// `id`/`code`; an entry with neither (malformed, or not yet given one)
+/// isn't recorded, since there'd be nothing to key it by.
+#[derive(Debug, Default, Clone)]
+pub struct SourceMap {
+    requirements: HashMap<String, EntrySpans>,
+    constraints: HashMap<String, EntrySpans>,
+}
+
+impl SourceMap {
+    pub fn requirement_span(&self, id: &str) -> Option<Span> {
+        self.requirements.get(id).map(|e| e.entry)
+    }
+
+    pub fn requirement_field_span(&self, id: &str, field: &str) -> Option<Span> {
+        self.requirements.get(id)?.fields.get(field).copied()
+    }
+
+    pub fn constraint_span(&self, code: &str) -> Option<Span> {
+        self.constraints.get(code).map(|e| e.entry)
+    }
+
+    pub fn constraint_field_span(&self, code: &str, field: &str) -> Option<Span> {
+        self.constraints.get(code)?.fields.get(field).copied()
+    }
+}
+
+/// One `[[requirement]]`/`[[constraint]]` block being built up as [`scan`]
+/// walks `input` line by line.
+struct OpenEntry {
+    kind: EntryKind,
+    start: usize,
+    id: Option<String>,
+    fields: HashMap<String, Span>,
+}
+
+#[derive(Clone, Copy)]
+enum EntryKind {
+    Requirement,
+    Constraint,
+}
+
+/// Scans `input` — a TOML spec file's raw text — for `[[requirement]]`/
+/// `[[constraint]]` array-of-tables and the `key = "..."` assignments
+/// inside them, recording each entry's and each field's byte span.
+/// Best-effort, like [`crate::trace::scan`]: a value that isn't a bare
+/// string literal on its own line (a multi-line string, an inline table,
+/// a non-string scalar) has no recorded field span, though the entry
+/// itself is still tracked as long as its `id`/`code` is a plain string.
+pub fn scan(input: &str) -> SourceMap {
+    let mut map = SourceMap::default();
+    let mut open: Option<OpenEntry> = None;
+    let mut offset = 0usize;
+
+    for line in input.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let trimmed = line.trim();
+
+        let header_kind = match trimmed {
+            "[[requirement]]" => Some(EntryKind::Requirement),
+            "[[constraint]]" => Some(EntryKind::Constraint),
+            _ => None,
+        };
+
+        if header_kind.is_some() || trimmed.starts_with('[') {
+            close_entry(&mut map, open.take(), line_start);
+        }
+        if let Some(kind) = header_kind {
+            open = Some(OpenEntry {
+                kind,
+                start: line_start,
+                id: None,
+                fields: HashMap::new(),
+            });
+            continue;
+        }
+
+        let Some(entry) = open.as_mut() else { continue };
+        if let Some((key, value, value_span)) = string_assignment(line, line_start) {
+            entry.fields.insert(key.to_string(), value_span);
+            let id_key = match entry.kind {
+                EntryKind::Requirement => "id",
+                EntryKind::Constraint => "code",
+            };
+            if key == id_key {
+                entry.id = Some(value.to_string());
+            }
+        }
+    }
+    close_entry(&mut map, open.take(), input.len());
+    map
+}
+
+/// Finalizes `entry`'s span as `entry.start..end` and records it in
+/// `map`, if it has an `id`/`code` to key it by. A no-op if `entry` is
+/// `None` (nothing was open).
+fn close_entry(map: &mut SourceMap, entry: Option<OpenEntry>, end: usize) {
+    let Some(entry) = entry else { return };
+    let Some(id) = entry.id else { return };
+    let spans = EntrySpans {
+        entry: Span {
+            start: entry.start,
+            end,
+        },
+        fields: entry.fields,
+    };
+    match entry.kind {
+        EntryKind::Requirement => map.requirements.insert(id, spans),
+        EntryKind::Constraint => map.constraints.insert(id, spans),
+    };
+}
+
+/// Parses `line` (starting at byte offset `line_start` in the original
+/// input) as a `key = "value"` assignment, returning the key, the
+/// unquoted value, and the value's own byte span. `None` if `line` isn't
+/// of that shape — not an assignment at all, or its value isn't a plain
+/// quoted string.
+fn string_assignment(line: &str, line_start: usize) -> Option<(&str, &str, Span)> {
+    let eq = line.find('=')?;
+    let key = line[..eq].trim();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    let rest = &line[eq + 1..];
+    let quote_start = rest.find('"')?;
+    let value_start = quote_start + 1;
+    let value_end = value_start + rest[value_start..].find('"')?;
+    let value = &rest[value_start..value_end];
+
+    let rest_offset = eq + 1;
+    Some((
+        key,
+        value,
+        Span {
+            start: line_start + rest_offset + value_start,
+            end: line_start + rest_offset + value_end,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = concat!(
+        "[[requirement]]\n",
+        "id = \"REQ-001\"\n",
+        "text = \"does a thing\"\n",
+        "\n",
+        "[[constraint]]\n",
+        "code = \"CONST-001\"\n",
+        "text = \"amount must be positive\"\n",
+    );
+
+    #[test]
+    fn finds_a_requirement_field_span_pointing_at_its_value() {
+        let map = scan(SPEC);
+        let span = map.requirement_field_span("REQ-001", "id").unwrap();
+        assert_eq!(span.as_str(SPEC), "REQ-001");
+    }
+
+    #[test]
+    fn finds_a_requirement_text_field_span() {
+        let map = scan(SPEC);
+        let span = map.requirement_field_span("REQ-001", "text").unwrap();
+        assert_eq!(span.as_str(SPEC), "does a thing");
+    }
+
+    #[test]
+    fn finds_a_constraint_field_span() {
+        let map = scan(SPEC);
+        let span = map.constraint_field_span("CONST-001", "text").unwrap();
+        assert_eq!(span.as_str(SPEC), "amount must be positive");
+    }
+
+    #[test]
+    fn records_an_entry_span_starting_at_its_table_header() {
+        let map = scan(SPEC);
+        let span = map.requirement_span("REQ-001").unwrap();
+        assert_eq!(&SPEC[span.start..span.start + "[[requirement]]".len()], "[[requirement]]");
+    }
+
+    #[test]
+    fn has_no_span_for_an_unknown_id() {
+        let map = scan(SPEC);
+        assert!(map.requirement_span("REQ-999").is_none());
+        assert!(map.constraint_span("CONST-999").is_none());
+    }
+}
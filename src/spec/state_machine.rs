@@ -0,0 +1,71 @@
+//! Abstract state-machine declarations: a [`StateMachine`] models an
+//! implementation's state over a sequence of operations (e.g. a bank
+//! account's `balance`), so [`crate::codegen::state_machine`] can generate
+//! a stateful property test that checks a real implementation agrees with
+//! the model on every operation sequence — not just the single-call
+//! checks [`crate::codegen::rust_guard`] generates from a lone constraint.
+
+use serde::{Deserialize, Serialize};
+
+/// One operation a [`StateMachine`] can take, e.g. `deposit` or
+/// `withdraw`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Transition {
+    pub name: String,
+    /// This transition's parameters, each an `f64` in the generated
+    /// model, e.g. `["amount"]` for `withdraw(amount)`.
+    #[serde(default)]
+    pub params: Vec<String>,
+    /// A [`crate::spec::expr`] condition over the model's state fields and
+    /// this transition's params that must hold for the transition to be
+    /// allowed, e.g. `"amount <= balance"`. `None` means always allowed.
+    #[serde(default)]
+    pub guard: Option<String>,
+    /// The [`super::Constraint`] code an implementation should report when
+    /// `guard` fails, e.g. `"CONST-002"`. Falls back to `"{name}-guard"`
+    /// if `guard` is set but this isn't.
+    #[serde(default)]
+    pub violates: Option<String>,
+    /// How each state field changes, keyed by field name to a
+    /// [`crate::spec::expr`] arithmetic expression over the prior state
+    /// and this transition's params, e.g. `{"balance": "balance + amount"}`.
+    /// A field not mentioned here is left unchanged.
+    #[serde(default)]
+    pub effect: std::collections::BTreeMap<String, String>,
+}
+
+/// An abstract model of an implementation's state, e.g. a bank account's
+/// `balance`, plus the [`Transition`]s that change it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct StateMachine {
+    pub name: String,
+    /// The model's state fields and their starting values, e.g.
+    /// `{"balance": 0.0}`.
+    #[serde(default)]
+    pub state: std::collections::BTreeMap<String, f64>,
+    #[serde(default, rename = "transition", alias = "transitions")]
+    pub transitions: Vec<Transition>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_toml() {
+        let sm = StateMachine {
+            name: "Account".into(),
+            state: [("balance".to_string(), 0.0)].into(),
+            transitions: vec![Transition {
+                name: "withdraw".into(),
+                params: vec!["amount".into()],
+                guard: Some("amount <= balance".into()),
+                violates: Some("CONST-002".into()),
+                effect: [("balance".to_string(), "balance - amount".to_string())].into(),
+            }],
+        };
+        let toml = toml::to_string(&sm).unwrap();
+        let back: StateMachine = toml::from_str(&toml).unwrap();
+        assert_eq!(sm, back);
+    }
+}
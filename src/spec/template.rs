@@ -0,0 +1,121 @@
+//! Parameterized requirement templates: one `RequirementTemplate` stamps out
+//! many concrete [`Requirement`]s by substituting `{param}` placeholders,
+//! instead of writing out near-duplicate requirements by hand.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::Requirement;
+
+/// A requirement with `{param}`-style placeholders in its id and text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequirementTemplate {
+    pub id: String,
+    pub text: String,
+}
+
+/// A placeholder in a template had no matching parameter.
+#[derive(Debug, PartialEq)]
+pub struct MissingParam(pub String);
+
+impl fmt::Display for MissingParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "template placeholder `{{{}}}` has no matching parameter",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for MissingParam {}
+
+/// Error instantiating a template by id via [`super::SpecDocument::instantiate`].
+#[derive(Debug, PartialEq)]
+pub enum InstantiateError {
+    UnknownTemplate,
+    MissingParam(MissingParam),
+}
+
+impl fmt::Display for InstantiateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstantiateError::UnknownTemplate => write!(f, "unknown template id"),
+            InstantiateError::MissingParam(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for InstantiateError {}
+
+impl From<MissingParam> for InstantiateError {
+    fn from(e: MissingParam) -> Self {
+        InstantiateError::MissingParam(e)
+    }
+}
+
+impl RequirementTemplate {
+    /// Substitutes every `{param}` placeholder in `id` and `text` using
+    /// `params`, producing a concrete [`Requirement`].
+    pub fn instantiate(&self, params: &[(&str, &str)]) -> Result<Requirement, MissingParam> {
+        Ok(Requirement {
+            id: substitute(&self.id, params)?,
+            text: substitute(&self.text, params)?,
+            ..Default::default()
+        })
+    }
+}
+
+fn substitute(template: &str, params: &[(&str, &str)]) -> Result<String, MissingParam> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}') else {
+            out.push_str(rest);
+            return Ok(out);
+        };
+        let close = open + close;
+        let name = &rest[open + 1..close];
+        let value = params
+            .iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| *v)
+            .ok_or_else(|| MissingParam(name.to_string()))?;
+        out.push_str(&rest[..open]);
+        out.push_str(value);
+        rest = &rest[close + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_all_placeholders() {
+        let template = RequirementTemplate {
+            id: "REQ-ACCOUNT-{n}".into(),
+            text: "account {n} owned by {owner} starts at zero balance".into(),
+        };
+        let req = template
+            .instantiate(&[("n", "1"), ("owner", "Alice")])
+            .unwrap();
+        assert_eq!(req.id, "REQ-ACCOUNT-1");
+        assert_eq!(req.text, "account 1 owned by Alice starts at zero balance");
+    }
+
+    #[test]
+    fn reports_missing_param() {
+        let template = RequirementTemplate {
+            id: "REQ-{n}".into(),
+            text: "text".into(),
+        };
+        assert_eq!(
+            template.instantiate(&[]).unwrap_err(),
+            MissingParam("n".into())
+        );
+    }
+}
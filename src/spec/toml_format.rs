@@ -0,0 +1,83 @@
+//! TOML front end for [`SpecDocument`](super::SpecDocument).
+
+use std::fmt;
+
+use super::SpecDocument;
+
+/// Error parsing a TOML spec document.
+#[derive(Debug)]
+pub struct TomlLoadError(toml::de::Error);
+
+impl fmt::Display for TomlLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid TOML spec: {}", self.0)
+    }
+}
+
+impl std::error::Error for TomlLoadError {}
+
+pub fn from_str(input: &str) -> Result<SpecDocument, TomlLoadError> {
+    toml::from_str(input).map_err(TomlLoadError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_requirements_and_constraints() {
+        let doc = from_str(
+            r#"
+            [[requirement]]
+            id = "REQ-004"
+            text = "balance() returns the current balance"
+
+            [[constraint]]
+            code = "CONST-001"
+            text = "amount must be positive"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            doc.requirement("REQ-004").unwrap().text,
+            "balance() returns the current balance"
+        );
+        assert_eq!(
+            doc.constraint("CONST-001").unwrap().text,
+            "amount must be positive"
+        );
+    }
+
+    #[test]
+    fn parses_data_types() {
+        let doc = from_str(
+            r#"
+            [[type]]
+            name = "Account"
+
+            [[type.field]]
+            name = "balance"
+            ty = "f64"
+            constraints = ["CONST-001"]
+            "#,
+        )
+        .unwrap();
+
+        let account = doc.data_type("Account").unwrap();
+        assert_eq!(account.fields[0].name, "balance");
+        assert_eq!(account.fields[0].constraints, vec!["CONST-001".to_string()]);
+    }
+
+    #[test]
+    fn empty_document_has_no_requirements() {
+        let doc = from_str("").unwrap();
+        assert!(doc.requirements.is_empty());
+        assert!(doc.constraints.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_toml() {
+        assert!(from_str("not = [valid").is_err());
+    }
+}
@@ -0,0 +1,62 @@
+//! YAML front end for [`SpecDocument`](super::SpecDocument).
+
+use std::fmt;
+
+use super::SpecDocument;
+
+/// Error parsing a YAML spec document.
+#[derive(Debug)]
+pub struct YamlLoadError(serde_yaml::Error);
+
+impl fmt::Display for YamlLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid YAML spec: {}", self.0)
+    }
+}
+
+impl std::error::Error for YamlLoadError {}
+
+pub fn from_str(input: &str) -> Result<SpecDocument, YamlLoadError> {
+    serde_yaml::from_str(input).map_err(YamlLoadError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_requirements_and_constraints() {
+        let doc = from_str(
+            r#"
+            requirements:
+              - id: REQ-004
+                text: "balance() returns the current balance"
+            constraints:
+              - code: CONST-001
+                text: "amount must be positive"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            doc.requirement("REQ-004").unwrap().text,
+            "balance() returns the current balance"
+        );
+        assert_eq!(
+            doc.constraint("CONST-001").unwrap().text,
+            "amount must be positive"
+        );
+    }
+
+    #[test]
+    fn empty_document_has_no_requirements() {
+        let doc = from_str("{}").unwrap();
+        assert!(doc.requirements.is_empty());
+        assert!(doc.constraints.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_yaml() {
+        assert!(from_str("requirements: [").is_err());
+    }
+}
@@ -0,0 +1,225 @@
+//! Online monitors for temporal requirements phrased as two of Dwyer's
+//! property patterns: "Absence" ("after `close()`, no `deposit` ever
+//! succeeds") and "Response" ("every `withdraw` is eventually reflected
+//! in `transactions()`"). [`TemporalPattern::parse`] recognizes exactly
+//! those two phrasings in a requirement or constraint's text — anything
+//! else is `None`, same best-effort, skip-if-unrecognized spirit as
+//! [`crate::codegen::rust_guard`]'s `expr` parsing — and
+//! [`TemporalMonitor`] consumes an operation trace one [`TraceEvent`] at
+//! a time, flagging a [`TemporalViolation`] carrying the trace suffix
+//! that led to it, so "what happened leading up to this" doesn't have to
+//! be reconstructed by hand from a log afterward.
+
+use crate::spec::Severity;
+
+/// One operation observed at runtime, fed into a [`TemporalMonitor`] in
+/// the order it happened.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEvent {
+    pub operation: String,
+    pub succeeded: bool,
+}
+
+/// A temporal requirement [`TemporalPattern::parse`] recognized in a
+/// requirement or constraint's text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemporalPattern {
+    /// "after `after`(), no `forbidden` ever succeeds": once `after` has
+    /// succeeded, every later `forbidden` call must fail.
+    Absence { after: String, forbidden: String },
+    /// "every `trigger` is eventually reflected in `response`()": every
+    /// successful `trigger` call must eventually be followed by a
+    /// successful `response` call.
+    Response { trigger: String, response: String },
+}
+
+impl TemporalPattern {
+    /// Recognizes `text` as an [`Absence`](Self::Absence) or
+    /// [`Response`](Self::Response) pattern, `None` if it matches
+    /// neither phrasing.
+    pub fn parse(text: &str) -> Option<Self> {
+        if let Some(rest) = text.strip_prefix("after ") {
+            let (after, rest) = rest.split_once("(), no ")?;
+            let forbidden = rest.strip_suffix(" ever succeeds")?;
+            return Some(TemporalPattern::Absence {
+                after: after.trim().to_string(),
+                forbidden: forbidden.trim().to_string(),
+            });
+        }
+        if let Some(rest) = text.strip_prefix("every ") {
+            let (trigger, rest) = rest.split_once(" is eventually reflected in ")?;
+            let response = rest.strip_suffix("()")?;
+            return Some(TemporalPattern::Response {
+                trigger: trigger.trim().to_string(),
+                response: response.trim().to_string(),
+            });
+        }
+        None
+    }
+}
+
+/// A [`TemporalPattern`] violated at runtime, with the trace suffix (every
+/// event observed since the pattern became relevant) that led to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemporalViolation {
+    pub pattern: TemporalPattern,
+    pub severity: Severity,
+    pub trace: Vec<TraceEvent>,
+}
+
+enum State {
+    Absence { after_occurred: bool, trace: Vec<TraceEvent> },
+    Response { pending: Vec<TraceEvent> },
+}
+
+/// Consumes a [`TraceEvent`] stream one event at a time via
+/// [`observe`](Self::observe), tracking one [`TemporalPattern`] per
+/// instance — a spec with several temporal requirements runs one monitor
+/// per pattern over the same trace.
+pub struct TemporalMonitor {
+    pattern: TemporalPattern,
+    severity: Severity,
+    state: State,
+}
+
+impl TemporalMonitor {
+    pub fn new(pattern: TemporalPattern, severity: Severity) -> Self {
+        let state = match &pattern {
+            TemporalPattern::Absence { .. } => {
+                State::Absence { after_occurred: false, trace: Vec::new() }
+            }
+            TemporalPattern::Response { .. } => State::Response { pending: Vec::new() },
+        };
+        TemporalMonitor { pattern, severity, state }
+    }
+
+    /// Feeds one `event` into the monitor, returning a
+    /// [`TemporalViolation`] if it breaks the pattern being tracked. A
+    /// [`TemporalPattern::Response`]'s trigger with no response yet isn't
+    /// a violation here — only once the trace ends with it still
+    /// pending, reported by [`finish`](Self::finish).
+    pub fn observe(&mut self, event: TraceEvent) -> Option<TemporalViolation> {
+        match (&self.pattern, &mut self.state) {
+            (
+                TemporalPattern::Absence { after, forbidden },
+                State::Absence { after_occurred, trace },
+            ) => {
+                if !*after_occurred {
+                    if event.operation == *after && event.succeeded {
+                        *after_occurred = true;
+                        trace.push(event);
+                    }
+                    return None;
+                }
+                trace.push(event);
+                let violating = trace.last().is_some_and(|e| e.operation == *forbidden && e.succeeded);
+                violating.then(|| TemporalViolation {
+                    pattern: self.pattern.clone(),
+                    severity: self.severity,
+                    trace: trace.clone(),
+                })
+            }
+            (TemporalPattern::Response { trigger, response }, State::Response { pending }) => {
+                if event.operation == *trigger && event.succeeded {
+                    pending.push(event);
+                } else if event.operation == *response && event.succeeded {
+                    pending.clear();
+                }
+                None
+            }
+            _ => unreachable!("pattern and state are always constructed together in `new`"),
+        }
+    }
+
+    /// Called once the trace stream ends: a [`TemporalPattern::Response`]
+    /// trigger that was never followed by its response is a violation,
+    /// reported here since [`observe`](Self::observe) can only tell a
+    /// response ran out of chances to arrive, not that it never will.
+    pub fn finish(&self) -> Option<TemporalViolation> {
+        match (&self.pattern, &self.state) {
+            (TemporalPattern::Response { .. }, State::Response { pending }) if !pending.is_empty() => {
+                Some(TemporalViolation {
+                    pattern: self.pattern.clone(),
+                    severity: self.severity,
+                    trace: pending.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(operation: &str) -> TraceEvent {
+        TraceEvent { operation: operation.into(), succeeded: true }
+    }
+
+    fn failed(operation: &str) -> TraceEvent {
+        TraceEvent { operation: operation.into(), succeeded: false }
+    }
+
+    #[test]
+    fn parses_an_absence_pattern() {
+        assert_eq!(
+            TemporalPattern::parse("after close(), no deposit ever succeeds"),
+            Some(TemporalPattern::Absence { after: "close".into(), forbidden: "deposit".into() })
+        );
+    }
+
+    #[test]
+    fn parses_a_response_pattern() {
+        assert_eq!(
+            TemporalPattern::parse("every withdraw is eventually reflected in transactions()"),
+            Some(TemporalPattern::Response { trigger: "withdraw".into(), response: "transactions".into() })
+        );
+    }
+
+    #[test]
+    fn does_not_parse_unrelated_text() {
+        assert_eq!(TemporalPattern::parse("amount must be positive"), None);
+    }
+
+    #[test]
+    fn flags_a_forbidden_operation_succeeding_after_the_trigger() {
+        let pattern = TemporalPattern::parse("after close(), no deposit ever succeeds").unwrap();
+        let mut monitor = TemporalMonitor::new(pattern, Severity::Error);
+
+        assert_eq!(monitor.observe(ok("close")), None);
+        assert_eq!(monitor.observe(failed("deposit")), None);
+        let violation = monitor.observe(ok("deposit")).unwrap();
+        assert_eq!(violation.trace, vec![ok("close"), failed("deposit"), ok("deposit")]);
+    }
+
+    #[test]
+    fn does_not_flag_a_forbidden_operation_before_the_trigger() {
+        let pattern = TemporalPattern::parse("after close(), no deposit ever succeeds").unwrap();
+        let mut monitor = TemporalMonitor::new(pattern, Severity::Error);
+
+        assert_eq!(monitor.observe(ok("deposit")), None);
+    }
+
+    #[test]
+    fn response_pattern_is_satisfied_when_the_response_follows() {
+        let pattern =
+            TemporalPattern::parse("every withdraw is eventually reflected in transactions()").unwrap();
+        let mut monitor = TemporalMonitor::new(pattern, Severity::Error);
+
+        assert_eq!(monitor.observe(ok("withdraw")), None);
+        assert_eq!(monitor.observe(ok("transactions")), None);
+        assert_eq!(monitor.finish(), None);
+    }
+
+    #[test]
+    fn response_pattern_is_violated_when_the_trace_ends_without_a_response() {
+        let pattern =
+            TemporalPattern::parse("every withdraw is eventually reflected in transactions()").unwrap();
+        let mut monitor = TemporalMonitor::new(pattern, Severity::Error);
+
+        assert_eq!(monitor.observe(ok("withdraw")), None);
+        let violation = monitor.finish().unwrap();
+        assert_eq!(violation.trace, vec![ok("withdraw")]);
+    }
+}
@@ -0,0 +1,130 @@
+//! Renders a shields.io-style SVG badge ("spec coverage 87%") from a
+//! [`CoverageMatrix`], so a repo can embed its requirement coverage in a
+//! README next to its CI/build badges.
+
+use serde::{Deserialize, Serialize};
+
+use super::CoverageMatrix;
+
+/// The coverage percentages a badge's color switches at, configurable in
+/// `libspec.toml` the same way [`CoveragePolicy`](super::CoveragePolicy)'s
+/// `min_tests_per_tag` is.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BadgeThresholds {
+    /// Coverage percentage at or above which the badge renders green.
+    pub green_at: f64,
+    /// Coverage percentage at or above which the badge renders yellow,
+    /// rather than red, when below `green_at`.
+    pub yellow_at: f64,
+}
+
+impl Default for BadgeThresholds {
+    fn default() -> Self {
+        Self { green_at: 80.0, yellow_at: 50.0 }
+    }
+}
+
+const GREEN: &str = "#4c1";
+const YELLOW: &str = "#dfb317";
+const RED: &str = "#e05d44";
+
+/// Percentage of `matrix`'s rows with at least one covering test. An empty
+/// matrix has nothing to cover, so it reports `100.0` rather than `0.0`.
+pub fn percentage(matrix: &CoverageMatrix) -> f64 {
+    if matrix.rows.is_empty() {
+        return 100.0;
+    }
+    let tested = matrix.rows.iter().filter(|row| !row.tests.is_empty()).count();
+    100.0 * tested as f64 / matrix.rows.len() as f64
+}
+
+/// Renders `matrix`'s coverage as a flat SVG badge, colored by where its
+/// percentage falls against `thresholds`.
+pub fn badge_svg(matrix: &CoverageMatrix, thresholds: &BadgeThresholds) -> String {
+    let pct = percentage(matrix);
+    let color = if pct >= thresholds.green_at {
+        GREEN
+    } else if pct >= thresholds.yellow_at {
+        YELLOW
+    } else {
+        RED
+    };
+    render_badge("spec coverage", &format!("{:.0}%", pct.round()), color)
+}
+
+/// A minimal shields.io-style flat badge: two rects side by side, each
+/// sized to its text by a fixed per-character width estimate. There's no
+/// font metrics available at generation time, so this isn't pixel-exact,
+/// but it's close enough for a README.
+fn render_badge(label: &str, value: &str, color: &str) -> String {
+    const CHAR_WIDTH: usize = 7;
+    const PADDING: usize = 10;
+    let label_width = label.len() * CHAR_WIDTH + PADDING;
+    let value_width = value.len() * CHAR_WIDTH + PADDING;
+    let total_width = label_width + value_width;
+    let label_x = label_width / 2;
+    let value_x = label_width + value_width / 2;
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{total_width}\" height=\"20\">\n\
+         \x20 <rect width=\"{label_width}\" height=\"20\" fill=\"#555\"/>\n\
+         \x20 <rect x=\"{label_width}\" width=\"{value_width}\" height=\"20\" fill=\"{color}\"/>\n\
+         \x20 <text x=\"{label_x}\" y=\"14\" fill=\"#fff\" font-family=\"Verdana,sans-serif\" font-size=\"11\" text-anchor=\"middle\">{label}</text>\n\
+         \x20 <text x=\"{value_x}\" y=\"14\" fill=\"#fff\" font-family=\"Verdana,sans-serif\" font-size=\"11\" text-anchor=\"middle\">{value}</text>\n\
+         </svg>\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+    use crate::trace::Record;
+
+    fn matrix_with_percentage(tested: usize, total: usize) -> CoverageMatrix {
+        let mut doc = crate::spec::SpecDocument::new();
+        for i in 0..total {
+            doc.requirements.push(Requirement {
+                id: format!("REQ-{i}"),
+                text: "text".into(),
+                ..Default::default()
+            });
+        }
+        let records: Vec<Record> = (0..tested)
+            .map(|i| Record {
+                kind: "covers".into(),
+                function: format!("test_{i}"),
+                requirement: format!("REQ-{i}"),
+            })
+            .collect();
+        CoverageMatrix::build(&doc, &records)
+    }
+
+    #[test]
+    fn percentage_counts_rows_with_at_least_one_test() {
+        let matrix = matrix_with_percentage(2, 4);
+        assert_eq!(percentage(&matrix), 50.0);
+    }
+
+    #[test]
+    fn empty_matrix_is_fully_covered() {
+        let matrix = CoverageMatrix::build(&crate::spec::SpecDocument::new(), &[]);
+        assert_eq!(percentage(&matrix), 100.0);
+    }
+
+    #[test]
+    fn badge_colors_by_threshold() {
+        let thresholds = BadgeThresholds::default();
+        assert!(badge_svg(&matrix_with_percentage(4, 4), &thresholds).contains(GREEN));
+        assert!(badge_svg(&matrix_with_percentage(3, 5), &thresholds).contains(YELLOW));
+        assert!(badge_svg(&matrix_with_percentage(1, 5), &thresholds).contains(RED));
+    }
+
+    #[test]
+    fn badge_contains_the_coverage_percentage_text() {
+        let svg = badge_svg(&matrix_with_percentage(2, 4), &BadgeThresholds::default());
+        assert!(svg.contains(">50%<"));
+        assert!(svg.contains(">spec coverage<"));
+    }
+}
@@ -0,0 +1,186 @@
+//! A single-page interactive HTML dashboard: a filterable (by tag, owner,
+//! and priority) requirement table plus a coverage trend chart, built
+//! from a [`CoverageMatrix`] joined back against the [`SpecDocument`] it
+//! was built from (for status/owner/priority, which the matrix itself
+//! doesn't carry) and a history of [`Snapshot`]s.
+
+use crate::spec::SpecDocument;
+
+use super::{CoverageMatrix, Snapshot};
+
+/// Renders `doc`/`matrix` (built from the same document — see
+/// [`CoverageMatrix::build`]) and `snapshots` as a standalone HTML
+/// dashboard: one `<tr>` per requirement, filterable client-side by tag,
+/// owner, and priority, plus an SVG trend line of `snapshots`' coverage
+/// percentages over time.
+pub fn dashboard_html(doc: &SpecDocument, matrix: &CoverageMatrix, snapshots: &[Snapshot]) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>Requirement dashboard</title>\n<style>\n");
+    out.push_str(
+        "body { font-family: sans-serif; margin: 2rem; }\n\
+         table { border-collapse: collapse; width: 100%; }\n\
+         th, td { border: 1px solid #ccc; padding: 0.25rem 0.5rem; text-align: left; }\n\
+         select { margin-right: 1rem; }\n",
+    );
+    out.push_str("</style>\n</head>\n<body>\n<h1>Requirement dashboard</h1>\n");
+
+    out.push_str(&trend_svg(snapshots));
+    out.push_str(&filter_controls(doc));
+    out.push_str(&requirement_table(doc, matrix));
+    out.push_str(&filter_script());
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn filter_controls(doc: &SpecDocument) -> String {
+    let mut out = String::from("<p>\n");
+    out.push_str(&select("tag-filter", "Tag", &unique_values(doc.requirements.iter().flat_map(|r| r.tags.iter().cloned()))));
+    out.push_str(&select(
+        "owner-filter",
+        "Owner",
+        &unique_values(doc.requirements.iter().filter_map(|r| r.owner.clone())),
+    ));
+    out.push_str(&select(
+        "priority-filter",
+        "Priority",
+        &unique_values(doc.requirements.iter().map(|r| format!("{:?}", r.priority))),
+    ));
+    out.push_str("</p>\n");
+    out
+}
+
+fn select(id: &str, label: &str, values: &[String]) -> String {
+    let mut out = format!("<label>{label}: <select id=\"{id}\" onchange=\"applyFilters()\">\n  <option value=\"\">All</option>\n");
+    for value in values {
+        out.push_str(&format!("  <option value=\"{}\">{}</option>\n", escape_html(value), escape_html(value)));
+    }
+    out.push_str("</select></label>\n");
+    out
+}
+
+fn unique_values(values: impl Iterator<Item = String>) -> Vec<String> {
+    let mut seen: Vec<String> = Vec::new();
+    for value in values {
+        if !seen.contains(&value) {
+            seen.push(value);
+        }
+    }
+    seen.sort();
+    seen
+}
+
+fn requirement_table(doc: &SpecDocument, matrix: &CoverageMatrix) -> String {
+    let mut out = String::from("<table id=\"requirements\">\n  <tr><th>Requirement</th><th>Text</th><th>Status</th><th>Priority</th><th>Owner</th><th>Tags</th><th>Tests</th><th>Implementations</th></tr>\n");
+    for (req, row) in doc.requirements.iter().zip(&matrix.rows) {
+        let owner = req.owner.clone().unwrap_or_default();
+        let tags = req.tags.join(",");
+        out.push_str(&format!(
+            "  <tr data-tag=\"{}\" data-owner=\"{}\" data-priority=\"{:?}\">\n",
+            escape_html(&tags),
+            escape_html(&owner),
+            req.priority,
+        ));
+        out.push_str(&format!("    <td>{}</td>\n", escape_html(&row.requirement)));
+        out.push_str(&format!("    <td>{}</td>\n", escape_html(&row.text)));
+        out.push_str(&format!("    <td>{:?}</td>\n", req.status));
+        out.push_str(&format!("    <td>{:?}</td>\n", req.priority));
+        out.push_str(&format!("    <td>{}</td>\n", escape_html(&owner)));
+        out.push_str(&format!("    <td>{}</td>\n", escape_html(&tags)));
+        out.push_str(&format!("    <td>{}</td>\n", row.tests.len()));
+        out.push_str(&format!("    <td>{}</td>\n", row.implementations.len()));
+        out.push_str("  </tr>\n");
+    }
+    out.push_str("</table>\n");
+    out
+}
+
+fn filter_script() -> String {
+    "<script>\nfunction applyFilters() {\n  var tag = document.getElementById('tag-filter').value;\n  var owner = document.getElementById('owner-filter').value;\n  var priority = document.getElementById('priority-filter').value;\n  var rows = document.querySelectorAll('#requirements tr[data-tag]');\n  rows.forEach(function(row) {\n    var tags = row.getAttribute('data-tag').split(',');\n    var matches = (!tag || tags.indexOf(tag) !== -1)\n      && (!owner || row.getAttribute('data-owner') === owner)\n      && (!priority || row.getAttribute('data-priority') === priority);\n    row.style.display = matches ? '' : 'none';\n  });\n}\n</script>\n".to_string()
+}
+
+/// Renders `snapshots` as an SVG polyline, the x axis spanning their
+/// recorded order and the y axis their coverage percentage (0-100,
+/// inverted since SVG y grows downward). Empty/single-point histories
+/// render as an empty `<svg>` rather than a degenerate line.
+fn trend_svg(snapshots: &[Snapshot]) -> String {
+    const WIDTH: usize = 400;
+    const HEIGHT: usize = 100;
+    if snapshots.len() < 2 {
+        return format!("<svg width=\"{WIDTH}\" height=\"{HEIGHT}\"></svg>\n");
+    }
+
+    let step = WIDTH as f64 / (snapshots.len() - 1) as f64;
+    let points: Vec<String> = snapshots
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let x = i as f64 * step;
+            let y = HEIGHT as f64 - (s.percentage.clamp(0.0, 100.0) / 100.0) * HEIGHT as f64;
+            format!("{x:.1},{y:.1}")
+        })
+        .collect();
+
+    format!(
+        "<svg width=\"{WIDTH}\" height=\"{HEIGHT}\">\n  <polyline points=\"{}\" fill=\"none\" stroke=\"#4c1\" stroke-width=\"2\"/>\n</svg>\n",
+        points.join(" ")
+    )
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    fn doc_with_requirement() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            owner: Some("alice".into()),
+            tags: vec!["mandatory".into()],
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn renders_a_filter_control_per_dimension() {
+        let doc = doc_with_requirement();
+        let html = dashboard_html(&doc, &CoverageMatrix::build(&doc, &[]), &[]);
+        assert!(html.contains("id=\"tag-filter\""));
+        assert!(html.contains("id=\"owner-filter\""));
+        assert!(html.contains("id=\"priority-filter\""));
+        assert!(html.contains("<option value=\"mandatory\">mandatory</option>"));
+        assert!(html.contains("<option value=\"alice\">alice</option>"));
+    }
+
+    #[test]
+    fn renders_one_row_per_requirement_with_filter_attributes() {
+        let doc = doc_with_requirement();
+        let html = dashboard_html(&doc, &CoverageMatrix::build(&doc, &[]), &[]);
+        assert!(html.contains("data-tag=\"mandatory\" data-owner=\"alice\""));
+        assert!(html.contains("<td>REQ-004</td>"));
+    }
+
+    #[test]
+    fn renders_a_trend_polyline_for_two_or_more_snapshots() {
+        let snapshots = vec![
+            Snapshot { timestamp_unix: 1, percentage: 0.0 },
+            Snapshot { timestamp_unix: 2, percentage: 100.0 },
+        ];
+        let html = dashboard_html(&SpecDocument::new(), &CoverageMatrix::build(&SpecDocument::new(), &[]), &snapshots);
+        assert!(html.contains("<polyline"));
+    }
+
+    #[test]
+    fn renders_an_empty_chart_for_fewer_than_two_snapshots() {
+        let html = dashboard_html(&SpecDocument::new(), &CoverageMatrix::build(&SpecDocument::new(), &[]), &[]);
+        assert!(!html.contains("<polyline"));
+    }
+}
@@ -0,0 +1,88 @@
+//! Ranks a spec's requirements by how rarely a fuzzing session's corpus
+//! exercised them, so a `cargo-fuzz` loop can bias its next round toward
+//! rarely-hit constraints instead of treating every corpus input as
+//! equally interesting. The generated harness
+//! ([`crate::codegen::fuzz`]) records a `"fuzz"`-kind [`Record`] each time
+//! a decoded op runs, the same [`super::record`] mechanism `#[covers]`/
+//! `#[implements]` already use — this just reads that trace back and
+//! joins it against the spec instead of code coverage.
+
+use std::collections::BTreeMap;
+
+use crate::spec::SpecDocument;
+
+use super::Record;
+
+/// One requirement's fuzz coverage: how many `"fuzz"`-kind records named
+/// it, found by [`fuzz_priority`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzCoverage {
+    pub requirement: String,
+    pub hits: usize,
+}
+
+/// Ranks every requirement in `doc` by how many `"fuzz"`-kind records in
+/// `records` name it, least-hit first, so the requirements and
+/// constraints the existing corpus barely exercises sort to the front —
+/// the ones worth spending the next round's mutation budget on. A
+/// requirement with zero fuzz records still appears, with `hits: 0`, so
+/// "never hit at all" is visible rather than silently absent.
+pub fn fuzz_priority(doc: &SpecDocument, records: &[Record]) -> Vec<FuzzCoverage> {
+    let mut hits: BTreeMap<&str, usize> = doc.requirements.iter().map(|req| (req.id.as_str(), 0)).collect();
+    for record in records.iter().filter(|r| r.kind == "fuzz") {
+        if let Some(count) = hits.get_mut(record.requirement.as_str()) {
+            *count += 1;
+        }
+    }
+
+    let mut ranked: Vec<FuzzCoverage> =
+        hits.into_iter().map(|(requirement, hits)| FuzzCoverage { requirement: requirement.to_string(), hits }).collect();
+    ranked.sort_by_key(|c| c.hits);
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    fn doc_with_requirements(ids: &[&str]) -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        for id in ids {
+            doc.requirements.push(Requirement { id: (*id).into(), text: "text".into(), ..Default::default() });
+        }
+        doc
+    }
+
+    #[test]
+    fn ranks_the_least_hit_requirement_first() {
+        let doc = doc_with_requirements(&["REQ-001", "REQ-002"]);
+        let records = vec![
+            Record { kind: "fuzz".into(), function: "deposit".into(), requirement: "REQ-001".into() },
+            Record { kind: "fuzz".into(), function: "deposit".into(), requirement: "REQ-001".into() },
+            Record { kind: "fuzz".into(), function: "withdraw".into(), requirement: "REQ-002".into() },
+        ];
+
+        let ranked = fuzz_priority(&doc, &records);
+        assert_eq!(
+            ranked,
+            vec![
+                FuzzCoverage { requirement: "REQ-002".into(), hits: 1 },
+                FuzzCoverage { requirement: "REQ-001".into(), hits: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn a_requirement_with_no_fuzz_records_has_zero_hits() {
+        let doc = doc_with_requirements(&["REQ-001"]);
+        assert_eq!(fuzz_priority(&doc, &[]), vec![FuzzCoverage { requirement: "REQ-001".into(), hits: 0 }]);
+    }
+
+    #[test]
+    fn ignores_non_fuzz_records() {
+        let doc = doc_with_requirements(&["REQ-001"]);
+        let records = vec![Record { kind: "covers".into(), function: "test_it".into(), requirement: "REQ-001".into() }];
+        assert_eq!(fuzz_priority(&doc, &records), vec![FuzzCoverage { requirement: "REQ-001".into(), hits: 0 }]);
+    }
+}
@@ -0,0 +1,86 @@
+//! Renders `covers` trace records as JUnit XML: one `<testcase>` per test
+//! function, its `<properties>` listing the requirement ids it covers, so
+//! a CI dashboard that already parses JUnit output shows requirement
+//! traceability without new tooling.
+
+use std::collections::BTreeMap;
+
+use super::Record;
+
+/// Groups `records`' `covers` entries by test function and renders them as
+/// a JUnit `<testsuite>`, one `<testcase>` per function with a
+/// `<property name="requirement" value="...">` for each requirement id it
+/// was recorded against.
+pub fn to_junit_xml(records: &[Record]) -> String {
+    let mut by_test: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for record in records.iter().filter(|r| r.kind == "covers") {
+        by_test
+            .entry(record.function.as_str())
+            .or_default()
+            .push(record.requirement.as_str());
+    }
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuite name=\"libspec\" tests=\"{}\">\n",
+        by_test.len()
+    ));
+    for (name, requirement_ids) in &by_test {
+        out.push_str(&format!(
+            "  <testcase name=\"{}\" classname=\"libspec\">\n",
+            escape_xml(name)
+        ));
+        out.push_str("    <properties>\n");
+        for id in requirement_ids {
+            out.push_str(&format!(
+                "      <property name=\"requirement\" value=\"{}\"/>\n",
+                escape_xml(id)
+            ));
+        }
+        out.push_str("    </properties>\n");
+        out.push_str("  </testcase>\n");
+    }
+    out.push_str("</testsuite>\n");
+    out
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_every_requirement_id_a_test_covers() {
+        let records = vec![
+            Record { kind: "covers".into(), function: "test_balance".into(), requirement: "REQ-004".into() },
+            Record { kind: "covers".into(), function: "test_balance".into(), requirement: "REQ-005".into() },
+            Record { kind: "implements".into(), function: "BankLibrary::balance".into(), requirement: "REQ-004".into() },
+        ];
+
+        let xml = to_junit_xml(&records);
+        assert!(xml.contains("<testsuite name=\"libspec\" tests=\"1\">"));
+        assert!(xml.contains("<testcase name=\"test_balance\" classname=\"libspec\">"));
+        assert!(xml.contains("<property name=\"requirement\" value=\"REQ-004\"/>"));
+        assert!(xml.contains("<property name=\"requirement\" value=\"REQ-005\"/>"));
+        assert!(!xml.contains("BankLibrary::balance"));
+    }
+
+    #[test]
+    fn escapes_special_characters_in_names_and_ids() {
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test<weird>".into(),
+            requirement: "REQ\"004".into(),
+        }];
+
+        let xml = to_junit_xml(&records);
+        assert!(xml.contains("name=\"test&lt;weird&gt;\""));
+        assert!(xml.contains("value=\"REQ&quot;004\""));
+    }
+}
@@ -0,0 +1,721 @@
+//! Joins a spec's requirements against recorded [`Record`](super::Record)s
+//! into a requirement-by-test coverage matrix, renderable for a terminal,
+//! a standalone HTML page, or JSON. The bank example's informal `// Test
+//! CONST-001`-style comments are what this replaces.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::graph;
+use crate::spec::{Queryable, Requirement, RequirementKind, SpecDocument, Status, VerificationMethod};
+
+/// The full dotted id a `#[covers]`/`#[implements]` record names one of
+/// `requirement`'s [`AcceptanceCriterion`](crate::spec::AcceptanceCriterion)s
+/// by, e.g. `"REQ-004.a"` for criterion `"a"` of `"REQ-004"`.
+fn criterion_id(requirement: &str, criterion: &str) -> String {
+    format!("{requirement}.{criterion}")
+}
+
+use super::{Record, SignOff};
+
+/// One row of a [`CoverageMatrix`]: a requirement, the `covers` trace
+/// records (test function names) that exercise it, and the `implements`
+/// trace records (implementation function names) that fulfil it. Either
+/// list is empty if the requirement has no such record.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoverageRow {
+    pub requirement: String,
+    pub text: String,
+    pub tags: Vec<String>,
+    pub status: Status,
+    pub kind: RequirementKind,
+    pub verification_method: Option<VerificationMethod>,
+    pub tests: Vec<String>,
+    pub implementations: Vec<String>,
+    /// This requirement's acceptance criteria, each with its own
+    /// coverage tracked individually rather than folded into
+    /// [`tests`](Self::tests) above. Empty if the requirement declares
+    /// no [`AcceptanceCriterion`](crate::spec::AcceptanceCriterion)s.
+    pub criteria: Vec<CriterionCoverage>,
+    /// Ids downstream of this requirement in its
+    /// [`Requirement::replaced_by`](crate::spec::Requirement::replaced_by)
+    /// chain (see [`graph::lineage`]), so an auditor can follow a
+    /// deprecated requirement's coverage forward to whatever superseded
+    /// it instead of losing the trail. Empty if this requirement hasn't
+    /// been superseded.
+    pub superseded_by: Vec<String>,
+}
+
+impl Queryable for CoverageRow {
+    fn query_id(&self) -> &str {
+        &self.requirement
+    }
+    fn query_kind(&self) -> RequirementKind {
+        self.kind
+    }
+    fn query_status(&self) -> Status {
+        self.status
+    }
+    fn query_tags(&self) -> &[String] {
+        &self.tags
+    }
+    fn query_owner(&self) -> Option<&str> {
+        // Not carried on a coverage row; an `owner:` clause never
+        // matches one, the same as it never matches a requirement whose
+        // own `owner` is unset.
+        None
+    }
+    fn query_verification_method(&self) -> Option<VerificationMethod> {
+        self.verification_method
+    }
+}
+
+/// A requirement x test matrix, built by joining a spec's requirements
+/// against recorded `covers` trace records.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoverageMatrix {
+    pub rows: Vec<CoverageRow>,
+}
+
+impl CoverageMatrix {
+    /// Builds the matrix: one row per requirement in `doc`, in spec order,
+    /// listing every `covers` record in `records` naming that requirement.
+    pub fn build(doc: &SpecDocument, records: &[Record]) -> Self {
+        let rows = doc
+            .requirements
+            .iter()
+            .map(|req| CoverageRow {
+                requirement: req.id.clone(),
+                text: req.text.clone(),
+                tags: req.tags.clone(),
+                status: req.status,
+                kind: req.kind,
+                verification_method: req.verification_method,
+                tests: records
+                    .iter()
+                    .filter(|r| r.kind == "covers" && r.requirement == req.id)
+                    .map(|r| r.function.clone())
+                    .collect(),
+                implementations: records
+                    .iter()
+                    .filter(|r| r.kind == "implements" && r.requirement == req.id)
+                    .map(|r| r.function.clone())
+                    .collect(),
+                criteria: req
+                    .acceptance_criteria
+                    .iter()
+                    .map(|c| {
+                        let id = criterion_id(&req.id, &c.id);
+                        let tested = records.iter().any(|r| r.kind == "covers" && r.requirement == id);
+                        CriterionCoverage { id, text: c.text.clone(), tested }
+                    })
+                    .collect(),
+                superseded_by: graph::lineage(doc, &req.id).into_iter().skip(1).collect(),
+            })
+            .collect();
+        Self { rows }
+    }
+
+    /// Renders the matrix as a plain-text table for terminal output.
+    pub fn to_terminal(&self) -> String {
+        let mut out = String::new();
+        for row in &self.rows {
+            let tests = if row.tests.is_empty() {
+                "(untested)".to_string()
+            } else {
+                row.tests.join(", ")
+            };
+            out.push_str(&format!("{}: {}\n", row.requirement, tests));
+        }
+        out
+    }
+
+    /// Renders the matrix as a standalone HTML `<table>`.
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        out.push_str("<table>\n  <tr><th>Requirement</th><th>Text</th><th>Tests</th></tr>\n");
+        for row in &self.rows {
+            let tests = row
+                .tests
+                .iter()
+                .map(|t| escape_html(t))
+                .collect::<Vec<_>>()
+                .join(", ");
+            out.push_str(&format!(
+                "  <tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&row.requirement),
+                escape_html(&row.text),
+                tests
+            ));
+        }
+        out.push_str("</table>\n");
+        out
+    }
+
+    /// Renders the matrix as pretty-printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Renders the matrix as CSV, one row per requirement, with the
+    /// `tests` and `implementations` columns each a `;`-separated list —
+    /// the full requirement/test/implementation linkage an auditor can
+    /// open straight in a spreadsheet.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("requirement,text,tags,kind,tests,implementations,superseded_by\n");
+        for row in &self.rows {
+            out.push_str(&format!(
+                "{},{},{},{:?},{},{},{}\n",
+                escape_csv(&row.requirement),
+                escape_csv(&row.text),
+                escape_csv(&row.tags.join(";")),
+                row.kind,
+                escape_csv(&row.tests.join(";")),
+                escape_csv(&row.implementations.join(";")),
+                escape_csv(&row.superseded_by.join(";")),
+            ));
+        }
+        out
+    }
+
+    /// Breaks this matrix's coverage down by [`RequirementKind`], so a
+    /// report can show "security: 3/5 tested" instead of one aggregate
+    /// percentage that hides which kind of requirement is undertested.
+    /// A kind with no requirements doesn't appear.
+    pub fn by_kind(&self) -> BTreeMap<RequirementKind, KindCoverage> {
+        let mut breakdown: BTreeMap<RequirementKind, KindCoverage> = BTreeMap::new();
+        for row in &self.rows {
+            let entry = breakdown.entry(row.kind).or_default();
+            entry.total += 1;
+            if !row.tests.is_empty() {
+                entry.tested += 1;
+            }
+        }
+        breakdown
+    }
+
+    /// Renders the matrix as a standalone, styled HTML page: one
+    /// `<details>` drill-down per requirement, expandable to its tags,
+    /// covering tests, and implementations. Unlike [`to_html`](Self::to_html),
+    /// which renders a bare `<table>` fragment meant to be embedded in a
+    /// larger page, this is a complete `<html>` document an auditor can
+    /// open directly in a browser.
+    pub fn to_html_report(&self) -> String {
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str("<title>Traceability report</title>\n<style>\n");
+        out.push_str(
+            "body { font-family: sans-serif; margin: 2rem; }\n\
+             details { border: 1px solid #ccc; border-radius: 4px; margin-bottom: 0.5rem; padding: 0.5rem 1rem; }\n\
+             summary { font-weight: bold; cursor: pointer; }\n\
+             .untested { color: #a00; }\n\
+             ul { margin: 0.25rem 0 0.5rem 1.5rem; }\n",
+        );
+        out.push_str("</style>\n</head>\n<body>\n<h1>Traceability report</h1>\n");
+        for row in &self.rows {
+            let status_class = if row.tests.is_empty() { " class=\"untested\"" } else { "" };
+            out.push_str(&format!(
+                "<details>\n  <summary{}>{}: {}</summary>\n",
+                status_class,
+                escape_html(&row.requirement),
+                escape_html(&row.text),
+            ));
+            out.push_str(&render_drilldown_list("Tags", &row.tags));
+            out.push_str(&render_drilldown_list("Tests", &row.tests));
+            out.push_str(&render_drilldown_list("Implementations", &row.implementations));
+            out.push_str(&render_drilldown_list("Superseded by", &row.superseded_by));
+            out.push_str("</details>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    /// Checks `policy`'s per-tag thresholds, and `sign_offs`, against this
+    /// matrix. A row verified by [`VerificationMethod::Test`] (or with no
+    /// verification method recorded at all) falls short when its test
+    /// count is below the highest `min_tests_per_tag` value of any tag it
+    /// carries; a row with no policy-covered tag is unconstrained. A row
+    /// verified some other way falls short when `sign_offs` has no record
+    /// naming it under that same method — the automated-test gate never
+    /// applies to it, since a requirement checked off by inspection or
+    /// analysis has no test to demand. A [`Status::Deprecated`] row is
+    /// never a shortfall either way — a deprecated requirement is on its
+    /// way out, not something CI should keep demanding verification for.
+    /// Returns every shortfall found, so a CI gate can report all of them
+    /// at once instead of one at a time.
+    pub fn enforce(&self, policy: &super::CoveragePolicy, sign_offs: &[SignOff]) -> Result<(), Vec<Violation>> {
+        let violations: Vec<Violation> = self
+            .rows
+            .iter()
+            .filter(|row| row.status != Status::Deprecated)
+            .filter_map(|row| match row.verification_method {
+                Some(method) if method != VerificationMethod::Test => {
+                    let signed = sign_offs
+                        .iter()
+                        .any(|s| s.requirement == row.requirement && s.method == method);
+                    (!signed).then(|| Violation::MissingSignOff {
+                        requirement: row.requirement.clone(),
+                        method,
+                    })
+                }
+                _ => {
+                    let required = row
+                        .tags
+                        .iter()
+                        .filter_map(|tag| policy.min_tests_per_tag.get(tag))
+                        .copied()
+                        .max()
+                        .unwrap_or(0);
+                    (row.tests.len() < required).then(|| Violation::InsufficientTests {
+                        requirement: row.requirement.clone(),
+                        required,
+                        actual: row.tests.len(),
+                    })
+                }
+            })
+            .collect();
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+/// Builds [`CoverageRow`]s one requirement at a time from `requirements`,
+/// e.g. a [`SpecDocument::requirements_from_jsonl_str`] iterator, instead
+/// of [`CoverageMatrix::build`]'s whole-[`SpecDocument`] pass — for a spec
+/// too large to hold as one in-memory tree. `records` is still indexed
+/// into a lookup table up front (a spec large enough to need this path is
+/// large because of its requirement count, not its trace record count).
+/// Unlike `build`, every row's `superseded_by` is left empty: following a
+/// [`Requirement::replaced_by`](crate::spec::Requirement::replaced_by)
+/// chain needs random access across every requirement, which this
+/// streaming path deliberately avoids.
+pub fn rows_streaming<'a>(
+    requirements: impl Iterator<Item = Requirement> + 'a,
+    records: &'a [Record],
+) -> impl Iterator<Item = CoverageRow> + 'a {
+    let mut tests: HashMap<&str, Vec<String>> = HashMap::new();
+    let mut implementations: HashMap<&str, Vec<String>> = HashMap::new();
+    for record in records {
+        let target = match record.kind.as_str() {
+            "covers" => &mut tests,
+            "implements" => &mut implementations,
+            _ => continue,
+        };
+        target
+            .entry(record.requirement.as_str())
+            .or_default()
+            .push(record.function.clone());
+    }
+
+    requirements.map(move |req| {
+        let criteria = req
+            .acceptance_criteria
+            .iter()
+            .map(|c| {
+                let id = criterion_id(&req.id, &c.id);
+                let tested = records.iter().any(|r| r.kind == "covers" && r.requirement == id);
+                CriterionCoverage { id, text: c.text.clone(), tested }
+            })
+            .collect();
+        CoverageRow {
+            tests: tests.get(req.id.as_str()).cloned().unwrap_or_default(),
+            implementations: implementations.get(req.id.as_str()).cloned().unwrap_or_default(),
+            requirement: req.id,
+            text: req.text,
+            tags: req.tags,
+            status: req.status,
+            kind: req.kind,
+            verification_method: req.verification_method,
+            criteria,
+            superseded_by: Vec::new(),
+        }
+    })
+}
+
+/// Coverage of one [`AcceptanceCriterion`](crate::spec::AcceptanceCriterion):
+/// `id` is its full dotted id (e.g. `"REQ-004.a"`), matched against a
+/// `covers` record naming that exact id rather than the parent
+/// requirement's id.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CriterionCoverage {
+    pub id: String,
+    pub text: String,
+    pub tested: bool,
+}
+
+/// Test coverage for one [`RequirementKind`]: how many of that kind's
+/// requirements have at least one covering test, out of how many exist.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct KindCoverage {
+    pub tested: usize,
+    pub total: usize,
+}
+
+/// One way a requirement fell short of [`CoverageMatrix::enforce`]'s
+/// policy.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// A [`VerificationMethod::Test`]-verified (or unverified) requirement
+    /// whose covering test count fell below what its tags require under a
+    /// [`CoveragePolicy`].
+    InsufficientTests { requirement: String, required: usize, actual: usize },
+    /// A requirement verified by a non-[`VerificationMethod::Test`]
+    /// method with no matching [`SignOff`] on record.
+    MissingSignOff { requirement: String, method: VerificationMethod },
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Quotes `s` for a CSV field if it contains a comma, quote, or newline,
+/// doubling any embedded quotes; passes it through unquoted otherwise.
+fn escape_csv(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Renders one `<ul>` of `items` under an `<em>{label}</em>` heading for
+/// [`CoverageMatrix::to_html_report`], or nothing if `items` is empty.
+fn render_drilldown_list(label: &str, items: &[String]) -> String {
+    if items.is_empty() {
+        return String::new();
+    }
+    let mut out = format!("  <em>{label}</em>\n  <ul>\n");
+    for item in items {
+        out.push_str(&format!("    <li>{}</li>\n", escape_html(item)));
+    }
+    out.push_str("  </ul>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    fn doc_with_requirement() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance() returns the current balance".into(),
+            ..Default::default()
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ-005".into(),
+            text: "withdraw() rejects overdrafts".into(),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn joins_requirements_against_covers_records() {
+        let doc = doc_with_requirement();
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_balance".into(),
+            requirement: "REQ-004".into(),
+        }];
+
+        let matrix = CoverageMatrix::build(&doc, &records);
+        assert_eq!(matrix.rows.len(), 2);
+        assert_eq!(matrix.rows[0].tests, vec!["test_balance".to_string()]);
+        assert!(matrix.rows[1].tests.is_empty());
+    }
+
+    #[test]
+    fn joins_requirements_against_implements_records() {
+        let doc = doc_with_requirement();
+        let records = vec![Record {
+            kind: "implements".into(),
+            function: "BankLibrary::balance".into(),
+            requirement: "REQ-004".into(),
+        }];
+
+        let matrix = CoverageMatrix::build(&doc, &records);
+        assert_eq!(matrix.rows[0].implementations, vec!["BankLibrary::balance".to_string()]);
+        assert!(matrix.rows[1].implementations.is_empty());
+    }
+
+    #[test]
+    fn renders_terminal_and_html() {
+        let doc = doc_with_requirement();
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_balance".into(),
+            requirement: "REQ-004".into(),
+        }];
+        let matrix = CoverageMatrix::build(&doc, &records);
+
+        assert!(matrix.to_terminal().contains("REQ-004: test_balance\n"));
+        assert!(matrix.to_terminal().contains("REQ-005: (untested)\n"));
+        assert!(matrix.to_html().contains("<td>REQ-004</td><td>balance() returns the current balance</td><td>test_balance</td>"));
+    }
+
+    #[test]
+    fn renders_json() {
+        let doc = doc_with_requirement();
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let json = matrix.to_json().unwrap();
+        assert!(json.contains("\"requirement\": \"REQ-004\""));
+    }
+
+    #[test]
+    fn renders_csv_with_linkage_columns() {
+        let doc = doc_with_requirement();
+        let records = vec![
+            Record { kind: "covers".into(), function: "test_balance".into(), requirement: "REQ-004".into() },
+            Record { kind: "implements".into(), function: "BankLibrary::balance".into(), requirement: "REQ-004".into() },
+        ];
+        let matrix = CoverageMatrix::build(&doc, &records);
+
+        let csv = matrix.to_csv();
+        assert!(csv.starts_with("requirement,text,tags,kind,tests,implementations,superseded_by\n"));
+        assert!(csv.contains("REQ-004,balance() returns the current balance,,Functional,test_balance,BankLibrary::balance,\n"));
+        assert!(csv.contains("REQ-005,withdraw() rejects overdrafts,,Functional,,,\n"));
+    }
+
+    #[test]
+    fn escapes_commas_in_csv_fields() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance, once settled, never goes negative".into(),
+            ..Default::default()
+        });
+        let matrix = CoverageMatrix::build(&doc, &[]);
+
+        assert!(matrix
+            .to_csv()
+            .contains("\"balance, once settled, never goes negative\""));
+    }
+
+    #[test]
+    fn renders_html_report_with_per_requirement_drilldown() {
+        let doc = doc_with_requirement();
+        let records = vec![
+            Record { kind: "covers".into(), function: "test_balance".into(), requirement: "REQ-004".into() },
+            Record { kind: "implements".into(), function: "BankLibrary::balance".into(), requirement: "REQ-004".into() },
+        ];
+        let matrix = CoverageMatrix::build(&doc, &records);
+
+        let report = matrix.to_html_report();
+        assert!(report.starts_with("<!DOCTYPE html>"));
+        assert!(report.contains("<summary>REQ-004: balance() returns the current balance</summary>"));
+        assert!(report.contains("<li>test_balance</li>"));
+        assert!(report.contains("<li>BankLibrary::balance</li>"));
+        assert!(report.contains("<summary class=\"untested\">REQ-005: withdraw() rejects overdrafts</summary>"));
+    }
+
+    #[test]
+    fn enforce_flags_undertested_mandatory_requirements() {
+        let mut doc = doc_with_requirement();
+        doc.requirements[0].tags = vec!["mandatory".into()];
+
+        let mut policy = super::super::CoveragePolicy::default();
+        policy.min_tests_per_tag.insert("mandatory".into(), 1);
+
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let violations = matrix.enforce(&policy, &[]).unwrap_err();
+        assert_eq!(
+            violations,
+            vec![Violation::InsufficientTests {
+                requirement: "REQ-004".into(),
+                required: 1,
+                actual: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn enforce_passes_when_thresholds_are_met() {
+        let mut doc = doc_with_requirement();
+        doc.requirements[0].tags = vec!["mandatory".into()];
+
+        let mut policy = super::super::CoveragePolicy::default();
+        policy.min_tests_per_tag.insert("mandatory".into(), 1);
+
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_balance".into(),
+            requirement: "REQ-004".into(),
+        }];
+        let matrix = CoverageMatrix::build(&doc, &records);
+        assert_eq!(matrix.enforce(&policy, &[]), Ok(()));
+    }
+
+    #[test]
+    fn rows_streaming_joins_requirements_against_records_without_a_spec_document() {
+        let requirements = vec![
+            Requirement {
+                id: "REQ-004".into(),
+                text: "balance() returns the current balance".into(),
+                ..Default::default()
+            },
+            Requirement {
+                id: "REQ-005".into(),
+                text: "withdraw() rejects overdrafts".into(),
+                ..Default::default()
+            },
+        ];
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_balance".into(),
+            requirement: "REQ-004".into(),
+        }];
+
+        let rows: Vec<CoverageRow> = rows_streaming(requirements.into_iter(), &records).collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].tests, vec!["test_balance".to_string()]);
+        assert!(rows[1].tests.is_empty());
+        assert!(rows[0].superseded_by.is_empty());
+    }
+
+    #[test]
+    fn follows_a_supersession_into_the_superseded_by_column() {
+        let mut doc = doc_with_requirement();
+        doc.requirements[0].status = Status::Deprecated;
+        doc.requirements[0].replaced_by = Some("REQ-005".into());
+
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        assert_eq!(matrix.rows[0].superseded_by, vec!["REQ-005".to_string()]);
+        assert!(matrix.rows[1].superseded_by.is_empty());
+        assert!(matrix.to_csv().contains(
+            "REQ-004,balance() returns the current balance,,Functional,,,REQ-005\n"
+        ));
+        assert!(matrix.to_html_report().contains("<li>REQ-005</li>"));
+    }
+
+    #[test]
+    fn enforce_exempts_deprecated_requirements() {
+        let mut doc = doc_with_requirement();
+        doc.requirements[0].tags = vec!["mandatory".into()];
+        doc.requirements[0].status = Status::Deprecated;
+
+        let mut policy = super::super::CoveragePolicy::default();
+        policy.min_tests_per_tag.insert("mandatory".into(), 1);
+
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        assert_eq!(matrix.enforce(&policy, &[]), Ok(()));
+    }
+
+    #[test]
+    fn enforce_requires_a_sign_off_for_a_non_test_verified_requirement() {
+        let mut doc = doc_with_requirement();
+        doc.requirements[0].verification_method = Some(VerificationMethod::Inspection);
+
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        let violations = matrix.enforce(&super::super::CoveragePolicy::default(), &[]).unwrap_err();
+        assert_eq!(
+            violations,
+            vec![Violation::MissingSignOff {
+                requirement: "REQ-004".into(),
+                method: VerificationMethod::Inspection,
+            }]
+        );
+    }
+
+    #[test]
+    fn enforce_passes_a_non_test_verified_requirement_with_a_matching_sign_off() {
+        let mut doc = doc_with_requirement();
+        doc.requirements[0].verification_method = Some(VerificationMethod::Inspection);
+
+        let sign_offs = vec![SignOff {
+            requirement: "REQ-004".into(),
+            method: VerificationMethod::Inspection,
+            signed_by: "alice".into(),
+            note: "reviewed".into(),
+        }];
+        let matrix = CoverageMatrix::build(&doc, &[]);
+        assert_eq!(matrix.enforce(&super::super::CoveragePolicy::default(), &sign_offs), Ok(()));
+    }
+
+    #[test]
+    fn by_kind_breaks_coverage_down_per_kind() {
+        let mut doc = doc_with_requirement();
+        doc.requirements[0].kind = RequirementKind::Security;
+        doc.requirements[1].kind = RequirementKind::Security;
+
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_balance_rejects_forged_signatures".into(),
+            requirement: "REQ-004".into(),
+        }];
+        let matrix = CoverageMatrix::build(&doc, &records);
+
+        let breakdown = matrix.by_kind();
+        assert_eq!(
+            breakdown.get(&RequirementKind::Security),
+            Some(&KindCoverage { tested: 1, total: 2 })
+        );
+        assert_eq!(breakdown.get(&RequirementKind::Functional), None);
+    }
+
+    #[test]
+    fn by_kind_omits_kinds_with_no_requirements() {
+        let matrix = CoverageMatrix::build(&doc_with_requirement(), &[]);
+        assert!(!matrix.by_kind().contains_key(&RequirementKind::Performance));
+    }
+
+    #[test]
+    fn tracks_acceptance_criteria_coverage_individually() {
+        use crate::spec::AcceptanceCriterion;
+
+        let mut doc = doc_with_requirement();
+        doc.requirements[0].acceptance_criteria = vec![
+            AcceptanceCriterion { id: "a".into(), text: "balance never goes negative".into() },
+            AcceptanceCriterion { id: "b".into(), text: "balance reflects pending deposits".into() },
+        ];
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_balance_never_negative".into(),
+            requirement: "REQ-004.a".into(),
+        }];
+
+        let matrix = CoverageMatrix::build(&doc, &records);
+        let row = matrix.rows.iter().find(|r| r.requirement == "REQ-004").unwrap();
+        assert_eq!(
+            row.criteria,
+            vec![
+                CriterionCoverage { id: "REQ-004.a".into(), text: "balance never goes negative".into(), tested: true },
+                CriterionCoverage {
+                    id: "REQ-004.b".into(),
+                    text: "balance reflects pending deposits".into(),
+                    tested: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn a_covers_record_for_the_parent_requirement_does_not_count_toward_a_criterion() {
+        use crate::spec::AcceptanceCriterion;
+
+        let mut doc = doc_with_requirement();
+        doc.requirements[0].acceptance_criteria =
+            vec![AcceptanceCriterion { id: "a".into(), text: "balance never goes negative".into() }];
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_balance".into(),
+            requirement: "REQ-004".into(),
+        }];
+
+        let matrix = CoverageMatrix::build(&doc, &records);
+        let row = matrix.rows.iter().find(|r| r.requirement == "REQ-004").unwrap();
+        assert!(!row.criteria[0].tested);
+    }
+
+    #[test]
+    fn requirements_without_acceptance_criteria_have_no_criteria_rows() {
+        let matrix = CoverageMatrix::build(&doc_with_requirement(), &[]);
+        assert!(matrix.rows.iter().all(|r| r.criteria.is_empty()));
+    }
+}
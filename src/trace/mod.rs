@@ -0,0 +1,183 @@
+//! Runtime support for the `#[covers]`/`#[implements]` attribute macros in
+//! `libspec-macros`: appends one traceability record per tagged function to
+//! a JSON-lines artifact, replacing the comment-only convention of
+//! `// Test CONST-001` above an assertion. [`coverage_gaps`] turns the
+//! collected records into requirements that have tests but no
+//! implementation, or an implementation but no test.
+
+mod badge;
+mod dashboard;
+mod fuzz_priority;
+mod junit;
+mod matrix;
+mod orphans;
+mod policy;
+mod regression;
+mod scan;
+mod signoff;
+mod snapshot;
+mod test_skeletons;
+
+pub use badge::{badge_svg, percentage, BadgeThresholds};
+pub use dashboard::dashboard_html;
+pub use fuzz_priority::{fuzz_priority, FuzzCoverage};
+pub use junit::to_junit_xml;
+pub use matrix::{rows_streaming, CoverageMatrix, CoverageRow, KindCoverage, Violation};
+pub use orphans::{orphans, Orphan};
+pub use policy::{
+    CheckMode, CoveragePolicy, ExitCategory, ExitCodePolicy, HookConfig, PolicyLoadError, ProfileOverrides,
+    ReportFormat, ReviewPolicy, RiskPolicy,
+};
+pub use regression::{read_coverage_snapshots, record_coverage_snapshot, regressions, CoverageSnapshot, Regression};
+pub use scan::{fingerprint, scan, Mention};
+pub use signoff::{read_sign_offs, record as record_sign_off, record_inspection, SignOff};
+pub use snapshot::{read_snapshots, record_snapshot, Snapshot};
+pub use test_skeletons::generate as generate_test_skeletons;
+
+use std::collections::BTreeSet;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// One line of the trace artifact: `kind` is `"covers"` for a test or
+/// `"implements"` for an implementation function.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub kind: String,
+    pub function: String,
+    pub requirement: String,
+}
+
+/// Where records are appended/read, unless overridden by
+/// `LIBSPEC_TRACE_FILE` (set this in tests that check the artifact, so
+/// parallel test runs don't clobber each other's file).
+fn trace_file_path() -> PathBuf {
+    std::env::var("LIBSPEC_TRACE_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("target/libspec-trace.jsonl"))
+}
+
+/// Appends a record to the trace file. Failures to write are swallowed: a
+/// missing or unwritable trace file shouldn't fail the test/build it's
+/// tracing.
+pub fn record(kind: &str, function: &str, requirement_id: &str) {
+    let path = trace_file_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let record = Record {
+        kind: kind.to_string(),
+        function: function.to_string(),
+        requirement: requirement_id.to_string(),
+    };
+    let Ok(line) = serde_json::to_string(&record) else {
+        return;
+    };
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+/// Reads and parses every record in the trace file, skipping lines that
+/// aren't valid JSON (e.g. a truncated line from a crashed test run).
+pub fn read_records() -> Vec<Record> {
+    let Ok(contents) = std::fs::read_to_string(trace_file_path()) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Requirements with a `covers` record but no `implements` record, and vice
+/// versa.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CoverageGaps {
+    pub tested_not_implemented: Vec<String>,
+    pub implemented_not_tested: Vec<String>,
+}
+
+/// Computes [`CoverageGaps`] from a set of trace records.
+pub fn coverage_gaps(records: &[Record]) -> CoverageGaps {
+    let tested: BTreeSet<&str> = records
+        .iter()
+        .filter(|r| r.kind == "covers")
+        .map(|r| r.requirement.as_str())
+        .collect();
+    let implemented: BTreeSet<&str> = records
+        .iter()
+        .filter(|r| r.kind == "implements")
+        .map(|r| r.requirement.as_str())
+        .collect();
+
+    CoverageGaps {
+        tested_not_implemented: tested
+            .difference(&implemented)
+            .map(|id| id.to_string())
+            .collect(),
+        implemented_not_tested: implemented
+            .difference(&tested)
+            .map(|id| id.to_string())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_trace_file<T>(f: impl FnOnce() -> T) -> T {
+        let path = std::env::temp_dir().join(format!(
+            "libspec-trace-test-{:?}.jsonl",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        std::env::set_var("LIBSPEC_TRACE_FILE", &path);
+        let result = f();
+        std::env::remove_var("LIBSPEC_TRACE_FILE");
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+
+    #[test]
+    fn appends_a_json_line_per_record() {
+        with_trace_file(|| {
+            record("covers", "test_balance", "REQ-004");
+            record("implements", "BankLibrary::balance", "REQ-004");
+
+            let records = read_records();
+            assert_eq!(
+                records,
+                vec![
+                    Record {
+                        kind: "covers".into(),
+                        function: "test_balance".into(),
+                        requirement: "REQ-004".into(),
+                    },
+                    Record {
+                        kind: "implements".into(),
+                        function: "BankLibrary::balance".into(),
+                        requirement: "REQ-004".into(),
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn coverage_gaps_reports_each_direction() {
+        let records = vec![
+            Record { kind: "covers".into(), function: "test_balance".into(), requirement: "REQ-004".into() },
+            Record { kind: "covers".into(), function: "test_withdraw".into(), requirement: "REQ-005".into() },
+            Record { kind: "implements".into(), function: "BankLibrary::withdraw".into(), requirement: "REQ-005".into() },
+            Record { kind: "implements".into(), function: "BankLibrary::deposit".into(), requirement: "REQ-006".into() },
+        ];
+
+        let gaps = coverage_gaps(&records);
+        assert_eq!(gaps.tested_not_implemented, vec!["REQ-004".to_string()]);
+        assert_eq!(gaps.implemented_not_tested, vec!["REQ-006".to_string()]);
+    }
+}
@@ -0,0 +1,143 @@
+//! Orphan-test detection: the reverse of [`coverage_gaps`](super::coverage_gaps).
+//! A `covers`/`implements` trace record names an id a human typed into an
+//! attribute by hand; nothing previously checked that id against the spec,
+//! so a typo like `REQ-04` for `REQ-004` silently recorded coverage for an
+//! id that doesn't exist instead of failing loudly.
+
+use super::Record;
+use crate::spec::SpecDocument;
+
+/// A trace record naming a requirement or constraint id the spec doesn't
+/// declare, with the spec's closest known id as a fix suggestion, if one
+/// is close enough to be worth proposing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Orphan {
+    pub function: String,
+    pub requirement: String,
+    pub suggestion: Option<String>,
+}
+
+/// The largest edit distance a suggestion is still offered at. Anything
+/// further than this is more likely an unrelated id than a typo, so
+/// [`orphans`] leaves `suggestion` as `None` rather than proposing a
+/// misleading fix.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Finds every record in `records` whose `requirement` isn't a requirement
+/// or constraint id declared in `doc`.
+pub fn orphans(doc: &SpecDocument, records: &[Record]) -> Vec<Orphan> {
+    let known: Vec<&str> = doc
+        .requirements
+        .iter()
+        .map(|r| r.id.as_str())
+        .chain(doc.constraints.iter().map(|c| c.code.as_str()))
+        .collect();
+
+    records
+        .iter()
+        .filter(|r| !known.contains(&r.requirement.as_str()))
+        .map(|r| Orphan {
+            function: r.function.clone(),
+            requirement: r.requirement.clone(),
+            suggestion: nearest_id(&r.requirement, &known),
+        })
+        .collect()
+}
+
+/// The known id closest to `id` by Levenshtein distance, or `None` if the
+/// closest one is still further than [`MAX_SUGGESTION_DISTANCE`] away.
+fn nearest_id(id: &str, known: &[&str]) -> Option<String> {
+    known
+        .iter()
+        .map(|candidate| (levenshtein(id, candidate), *candidate))
+        .min_by_key(|(distance, _)| *distance)
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Classic Wagner-Fischer edit distance between two strings, counting
+/// single-character insertions, deletions, and substitutions.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, &ac) in a.iter().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, &bc) in b.iter().enumerate() {
+            let deletion_or_insertion = row[j].min(row[j + 1]) + 1;
+            let substitution = previous + usize::from(ac != bc);
+            previous = row[j + 1];
+            row[j + 1] = deletion_or_insertion.min(substitution);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    fn doc_with_req004() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+        doc
+    }
+
+    #[test]
+    fn known_ids_are_not_orphans() {
+        let doc = doc_with_req004();
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_balance".into(),
+            requirement: "REQ-004".into(),
+        }];
+        assert!(orphans(&doc, &records).is_empty());
+    }
+
+    #[test]
+    fn flags_an_unknown_id_and_suggests_the_closest_typo_fix() {
+        let doc = doc_with_req004();
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_balance".into(),
+            requirement: "REQ-04".into(),
+        }];
+
+        let found = orphans(&doc, &records);
+        assert_eq!(
+            found,
+            vec![Orphan {
+                function: "test_balance".into(),
+                requirement: "REQ-04".into(),
+                suggestion: Some("REQ-004".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn does_not_suggest_an_unrelated_id() {
+        let doc = doc_with_req004();
+        let records = vec![Record {
+            kind: "covers".into(),
+            function: "test_unrelated".into(),
+            requirement: "CONST-999".into(),
+        }];
+
+        let found = orphans(&doc, &records);
+        assert_eq!(found[0].suggestion, None);
+    }
+
+    #[test]
+    fn levenshtein_counts_single_character_edits() {
+        assert_eq!(levenshtein("REQ-04", "REQ-004"), 1);
+        assert_eq!(levenshtein("REQ-004", "REQ-004"), 0);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+}
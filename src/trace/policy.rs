@@ -0,0 +1,524 @@
+//! A [`CoverageMatrix`](super::CoverageMatrix)'s gate configuration: how
+//! many covering tests a requirement carrying a given tag (e.g.
+//! `mandatory`) must have at minimum, configurable in `libspec.toml`
+//! instead of hardcoded, so CI can block merges on untested requirements
+//! without a code change every time the policy shifts. `libspec.toml` also
+//! names the spec file itself (`spec_file`), so `cargo spec` and other
+//! tooling can find it from a single well-known config file instead of
+//! every call site hardcoding a path.
+//!
+//! `libspec.toml` can also declare one or more `[profiles.<name>]` tables
+//! overriding any of the fields above for a named target — e.g. a `ci`
+//! profile raising `min_tests_per_tag` or switching `hooks.lint` from
+//! `warn` to `block` without disturbing the defaults a local `dev` run
+//! uses. [`CoveragePolicy::for_profile`] resolves a named profile against
+//! the base config; tooling picks which profile to resolve (typically via
+//! a `--profile` flag, defaulting to none).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::spec::IdScheme;
+
+/// Minimum number of covering tests a requirement carrying each tag must
+/// have. A requirement with no tag in this map is unconstrained.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoveragePolicy {
+    #[serde(default)]
+    pub min_tests_per_tag: BTreeMap<String, usize>,
+    /// Path to the spec file this policy governs, relative to
+    /// `libspec.toml`'s own directory. Defaults to `spec.toml` when unset.
+    #[serde(default)]
+    pub spec_file: Option<String>,
+    /// Which checks a generated pre-commit hook should run, and whether
+    /// each one blocks the commit or only warns. See `cargo spec
+    /// install-hook`.
+    #[serde(default)]
+    pub hooks: HookConfig,
+    /// Required id scheme for requirement ids (e.g. `REQ-003`), checked by
+    /// `cargo spec check` via
+    /// [`SpecDocument::requirement_id_violations`](crate::spec::SpecDocument::requirement_id_violations).
+    /// Unset means no id scheme is enforced.
+    #[serde(default)]
+    pub id_scheme: Option<IdScheme>,
+    /// Format `cargo spec report` renders to when none is given on the
+    /// command line.
+    #[serde(default)]
+    pub report_format: ReportFormat,
+    /// Per-profile overrides, keyed by profile name (e.g. `"ci"`), applied
+    /// on top of the fields above by [`CoveragePolicy::for_profile`].
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileOverrides>,
+    /// Process exit code for each category of finding `cargo spec` can
+    /// fail on, so a pipeline can tell an advisory finding from a
+    /// blocking one by exit code alone instead of parsing output.
+    #[serde(default)]
+    pub exit_codes: ExitCodePolicy,
+    /// Thread count for the rayon pool behind the `parallel` feature's
+    /// lint/scan/diff passes (see `crate::parallel::run`). Unset runs them
+    /// on rayon's own default (the number of logical CPUs); ignored
+    /// entirely without the `parallel` feature, since there's no pool to
+    /// size.
+    #[serde(default)]
+    pub threads: Option<usize>,
+    /// Which requirement tags need a reviewer approval on record before a
+    /// release report can pass (see [`crate::review::gate`]).
+    #[serde(default)]
+    pub review: ReviewPolicy,
+    /// Minimum test coverage, and whether a formal sign-off is required,
+    /// for a requirement's [`RiskRating`](crate::spec::RiskRating) (see
+    /// [`crate::risk_policy::check`]).
+    #[serde(default)]
+    pub risk: RiskPolicy,
+}
+
+/// Requirement tags that need a reviewer approval — recorded via
+/// `cargo spec review record` — before `cargo spec report` will pass. A
+/// requirement carrying none of these tags is never gated on review.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReviewPolicy {
+    #[serde(default)]
+    pub mandatory_tags: BTreeSet<String>,
+}
+
+/// How much more evidence a risk-rated requirement needs than an
+/// unrated one, per [`crate::spec::RiskLevel`]: `min_tests_at_medium`
+/// and `min_tests_at_high` raise the covering-test floor for a
+/// [`RiskRating::overall`](crate::spec::RiskRating::overall) of at least
+/// that level, and `require_formal_check_at_high` additionally demands a
+/// non-test [`SignOff`](super::SignOff) for the highest-risk
+/// requirements. All default to `0`/`false`, so risk ratings are
+/// advisory until a `[risk]` table in `libspec.toml` opts in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RiskPolicy {
+    #[serde(default)]
+    pub min_tests_at_medium: usize,
+    #[serde(default)]
+    pub min_tests_at_high: usize,
+    #[serde(default)]
+    pub require_formal_check_at_high: bool,
+}
+
+/// A category of finding `cargo spec` can exit non-zero for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCategory {
+    /// A [`crate::lint::LintIssue`] with no other finding alongside it.
+    LintWarning,
+    /// A meta-schema, dangling-reference, graph-cycle, or id-scheme
+    /// finding from `cargo spec check`.
+    ValidationError,
+    /// A [`Violation`](super::Violation) of `min_tests_per_tag` from
+    /// `cargo spec report`.
+    CoverageViolation,
+    /// A [`ReviewGap`](crate::review::ReviewGap) from `cargo spec report`
+    /// or `cargo spec review verify`: a mandatory requirement missing an
+    /// approval, or approved against a spec version that's since changed.
+    ReviewViolation,
+    /// The spec file (or its `libspec.toml`) couldn't be found, read, or
+    /// parsed at all.
+    ParseError,
+}
+
+/// Exit code for each [`ExitCategory`], defaulting to the values the
+/// change request that introduced this asked for: a lint warning alone
+/// doesn't fail the build (`0`), an uncovered mandatory requirement does
+/// but distinctly from a validation error (`2`), and a spec that can't
+/// even be parsed is the most fundamental failure (`3`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExitCodePolicy {
+    #[serde(default = "ExitCodePolicy::default_lint_warning")]
+    pub lint_warning: u8,
+    #[serde(default = "ExitCodePolicy::default_validation_error")]
+    pub validation_error: u8,
+    #[serde(default = "ExitCodePolicy::default_coverage_violation")]
+    pub coverage_violation: u8,
+    #[serde(default = "ExitCodePolicy::default_review_violation")]
+    pub review_violation: u8,
+    #[serde(default = "ExitCodePolicy::default_parse_error")]
+    pub parse_error: u8,
+}
+
+impl ExitCodePolicy {
+    fn default_lint_warning() -> u8 {
+        0
+    }
+
+    fn default_validation_error() -> u8 {
+        1
+    }
+
+    fn default_coverage_violation() -> u8 {
+        2
+    }
+
+    fn default_review_violation() -> u8 {
+        2
+    }
+
+    fn default_parse_error() -> u8 {
+        3
+    }
+
+    /// The exit code configured for `category`.
+    pub fn code(&self, category: ExitCategory) -> u8 {
+        match category {
+            ExitCategory::LintWarning => self.lint_warning,
+            ExitCategory::ValidationError => self.validation_error,
+            ExitCategory::CoverageViolation => self.coverage_violation,
+            ExitCategory::ReviewViolation => self.review_violation,
+            ExitCategory::ParseError => self.parse_error,
+        }
+    }
+}
+
+impl Default for ExitCodePolicy {
+    fn default() -> Self {
+        Self {
+            lint_warning: Self::default_lint_warning(),
+            validation_error: Self::default_validation_error(),
+            coverage_violation: Self::default_coverage_violation(),
+            review_violation: Self::default_review_violation(),
+            parse_error: Self::default_parse_error(),
+        }
+    }
+}
+
+/// Format `cargo spec report` renders a [`CoverageMatrix`](super::CoverageMatrix) to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReportFormat {
+    #[default]
+    Terminal,
+    Html,
+    Json,
+    Csv,
+}
+
+/// A named override applied on top of the base [`CoveragePolicy`] fields
+/// by [`CoveragePolicy::for_profile`]. `min_tests_per_tag` entries overlay
+/// the base map (only the tags listed here change); every other field
+/// replaces the base value outright when set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileOverrides {
+    #[serde(default)]
+    pub min_tests_per_tag: BTreeMap<String, usize>,
+    #[serde(default)]
+    pub hooks: Option<HookConfig>,
+    #[serde(default)]
+    pub id_scheme: Option<IdScheme>,
+    #[serde(default)]
+    pub report_format: Option<ReportFormat>,
+    #[serde(default)]
+    pub exit_codes: Option<ExitCodePolicy>,
+    #[serde(default)]
+    pub threads: Option<usize>,
+    #[serde(default)]
+    pub review: Option<ReviewPolicy>,
+    #[serde(default)]
+    pub risk: Option<RiskPolicy>,
+}
+
+/// Whether a check a pre-commit hook runs blocks the commit on failure,
+/// only prints a warning, or is skipped entirely.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CheckMode {
+    #[default]
+    Block,
+    Warn,
+    Off,
+}
+
+/// Which checks `cargo spec install-hook` wires into the generated
+/// pre-commit hook, and how each one should behave on failure. Defaults
+/// to blocking on both, since a silently-skipped lint or coverage
+/// regression defeats the point of having a hook at all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookConfig {
+    #[serde(default)]
+    pub lint: CheckMode,
+    #[serde(default)]
+    pub coverage: CheckMode,
+}
+
+impl CoveragePolicy {
+    pub fn from_toml_str(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    pub fn load_toml_file(path: &Path) -> Result<Self, PolicyLoadError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| PolicyLoadError::Io {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Self::from_toml_str(&contents).map_err(|e| PolicyLoadError::Parse {
+            path: path.to_path_buf(),
+            source: e,
+        })
+    }
+
+    /// Resolves the effective policy for `profile`, overlaying its
+    /// matching `[profiles.<profile>]` table (if any) onto the base
+    /// config. `None`, or a name with no matching table, returns the base
+    /// config unchanged.
+    pub fn for_profile(&self, profile: Option<&str>) -> CoveragePolicy {
+        let Some(overrides) = profile.and_then(|name| self.profiles.get(name)) else {
+            return self.clone();
+        };
+
+        let mut resolved = self.clone();
+        for (tag, min) in &overrides.min_tests_per_tag {
+            resolved.min_tests_per_tag.insert(tag.clone(), *min);
+        }
+        if let Some(hooks) = overrides.hooks {
+            resolved.hooks = hooks;
+        }
+        if let Some(id_scheme) = &overrides.id_scheme {
+            resolved.id_scheme = Some(id_scheme.clone());
+        }
+        if let Some(report_format) = overrides.report_format {
+            resolved.report_format = report_format;
+        }
+        if let Some(exit_codes) = overrides.exit_codes {
+            resolved.exit_codes = exit_codes;
+        }
+        if let Some(threads) = overrides.threads {
+            resolved.threads = Some(threads);
+        }
+        if let Some(review) = &overrides.review {
+            resolved.review = review.clone();
+        }
+        if let Some(risk) = overrides.risk {
+            resolved.risk = risk;
+        }
+        resolved
+    }
+}
+
+#[derive(Debug)]
+pub enum PolicyLoadError {
+    Io { path: PathBuf, source: std::io::Error },
+    Parse { path: PathBuf, source: toml::de::Error },
+}
+
+impl fmt::Display for PolicyLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyLoadError::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            PolicyLoadError::Parse { path, source } => write!(f, "{}: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for PolicyLoadError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_min_tests_per_tag() {
+        let policy = CoveragePolicy::from_toml_str(
+            "[min_tests_per_tag]\nmandatory = 1\nregression = 2\n",
+        )
+        .unwrap();
+        assert_eq!(policy.min_tests_per_tag.get("mandatory"), Some(&1));
+        assert_eq!(policy.min_tests_per_tag.get("regression"), Some(&2));
+    }
+
+    #[test]
+    fn parses_spec_file() {
+        let policy = CoveragePolicy::from_toml_str("spec_file = \"spec/bank.toml\"\n").unwrap();
+        assert_eq!(policy.spec_file, Some("spec/bank.toml".to_string()));
+    }
+
+    #[test]
+    fn spec_file_defaults_to_none() {
+        let policy = CoveragePolicy::from_toml_str("[min_tests_per_tag]\nmandatory = 1\n").unwrap();
+        assert_eq!(policy.spec_file, None);
+    }
+
+    #[test]
+    fn hooks_default_to_blocking() {
+        let policy = CoveragePolicy::from_toml_str("[min_tests_per_tag]\nmandatory = 1\n").unwrap();
+        assert_eq!(policy.hooks.lint, CheckMode::Block);
+        assert_eq!(policy.hooks.coverage, CheckMode::Block);
+    }
+
+    #[test]
+    fn parses_hook_modes() {
+        let policy =
+            CoveragePolicy::from_toml_str("[hooks]\nlint = \"warn\"\ncoverage = \"off\"\n").unwrap();
+        assert_eq!(policy.hooks.lint, CheckMode::Warn);
+        assert_eq!(policy.hooks.coverage, CheckMode::Off);
+    }
+
+    #[test]
+    fn loads_from_a_file() {
+        let dir = std::env::temp_dir().join(format!("libspec-policy-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("libspec.toml");
+        std::fs::write(&path, "[min_tests_per_tag]\nmandatory = 1\n").unwrap();
+
+        let policy = CoveragePolicy::load_toml_file(&path).unwrap();
+        assert_eq!(policy.min_tests_per_tag.get("mandatory"), Some(&1));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn parses_an_id_scheme() {
+        let policy = CoveragePolicy::from_toml_str("[id_scheme]\nprefix = \"REQ\"\nwidth = 3\n").unwrap();
+        assert_eq!(policy.id_scheme, Some(IdScheme::new("REQ", 3)));
+    }
+
+    #[test]
+    fn report_format_defaults_to_terminal() {
+        let policy = CoveragePolicy::from_toml_str("").unwrap();
+        assert_eq!(policy.report_format, ReportFormat::Terminal);
+    }
+
+    #[test]
+    fn parses_report_format() {
+        let policy = CoveragePolicy::from_toml_str("report_format = \"html\"\n").unwrap();
+        assert_eq!(policy.report_format, ReportFormat::Html);
+    }
+
+    #[test]
+    fn unknown_profile_resolves_to_the_base_policy() {
+        let mut policy = CoveragePolicy::default();
+        policy.min_tests_per_tag.insert("mandatory".to_string(), 1);
+        assert_eq!(policy.for_profile(Some("ci")), policy);
+        assert_eq!(policy.for_profile(None), policy);
+    }
+
+    #[test]
+    fn profile_overlays_min_tests_per_tag_without_dropping_other_tags() {
+        let policy = CoveragePolicy::from_toml_str(
+            "[min_tests_per_tag]\n\
+             mandatory = 1\n\
+             regression = 2\n\
+             \n\
+             [profiles.ci.min_tests_per_tag]\n\
+             mandatory = 3\n",
+        )
+        .unwrap();
+
+        let resolved = policy.for_profile(Some("ci"));
+        assert_eq!(resolved.min_tests_per_tag.get("mandatory"), Some(&3));
+        assert_eq!(resolved.min_tests_per_tag.get("regression"), Some(&2));
+    }
+
+    #[test]
+    fn exit_codes_default_to_the_documented_values() {
+        let policy = CoveragePolicy::from_toml_str("").unwrap();
+        assert_eq!(policy.exit_codes.code(ExitCategory::LintWarning), 0);
+        assert_eq!(policy.exit_codes.code(ExitCategory::ValidationError), 1);
+        assert_eq!(policy.exit_codes.code(ExitCategory::CoverageViolation), 2);
+        assert_eq!(policy.exit_codes.code(ExitCategory::ReviewViolation), 2);
+        assert_eq!(policy.exit_codes.code(ExitCategory::ParseError), 3);
+    }
+
+    #[test]
+    fn parses_partial_exit_codes_leaving_the_rest_at_their_default() {
+        let policy = CoveragePolicy::from_toml_str("[exit_codes]\ncoverage_violation = 5\n").unwrap();
+        assert_eq!(policy.exit_codes.code(ExitCategory::CoverageViolation), 5);
+        assert_eq!(policy.exit_codes.code(ExitCategory::LintWarning), 0);
+    }
+
+    #[test]
+    fn profile_overrides_exit_codes() {
+        let policy = CoveragePolicy::from_toml_str(
+            "[exit_codes]\n\
+             parse_error = 3\n\
+             \n\
+             [profiles.ci.exit_codes]\n\
+             lint_warning = 1\n\
+             validation_error = 1\n\
+             coverage_violation = 1\n\
+             parse_error = 1\n",
+        )
+        .unwrap();
+
+        assert_eq!(policy.exit_codes.code(ExitCategory::LintWarning), 0);
+
+        let resolved = policy.for_profile(Some("ci"));
+        assert_eq!(resolved.exit_codes.code(ExitCategory::LintWarning), 1);
+        assert_eq!(resolved.exit_codes.code(ExitCategory::ParseError), 1);
+    }
+
+    #[test]
+    fn threads_defaults_to_none() {
+        let policy = CoveragePolicy::from_toml_str("[min_tests_per_tag]\nmandatory = 1\n").unwrap();
+        assert_eq!(policy.threads, None);
+    }
+
+    #[test]
+    fn profile_overrides_threads() {
+        let policy = CoveragePolicy::from_toml_str(
+            "threads = 4\n\n[profiles.ci]\nthreads = 8\n",
+        )
+        .unwrap();
+        assert_eq!(policy.threads, Some(4));
+        assert_eq!(policy.for_profile(Some("ci")).threads, Some(8));
+    }
+
+    #[test]
+    fn review_mandatory_tags_default_to_empty() {
+        let policy = CoveragePolicy::from_toml_str("").unwrap();
+        assert!(policy.review.mandatory_tags.is_empty());
+    }
+
+    #[test]
+    fn parses_review_mandatory_tags() {
+        let policy = CoveragePolicy::from_toml_str("[review]\nmandatory_tags = [\"mandatory\", \"safety\"]\n").unwrap();
+        assert_eq!(
+            policy.review.mandatory_tags,
+            BTreeSet::from(["mandatory".to_string(), "safety".to_string()])
+        );
+    }
+
+    #[test]
+    fn profile_overrides_review_policy() {
+        let policy = CoveragePolicy::from_toml_str(
+            "[review]\n\
+             mandatory_tags = [\"mandatory\"]\n\
+             \n\
+             [profiles.ci.review]\n\
+             mandatory_tags = [\"mandatory\", \"safety\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(policy.review.mandatory_tags, BTreeSet::from(["mandatory".to_string()]));
+        let resolved = policy.for_profile(Some("ci"));
+        assert_eq!(
+            resolved.review.mandatory_tags,
+            BTreeSet::from(["mandatory".to_string(), "safety".to_string()])
+        );
+    }
+
+    #[test]
+    fn profile_overrides_hooks_and_id_scheme() {
+        let policy = CoveragePolicy::from_toml_str(
+            "[hooks]\n\
+             lint = \"warn\"\n\
+             \n\
+             [profiles.ci.hooks]\n\
+             lint = \"block\"\n\
+             \n\
+             [profiles.ci.id_scheme]\n\
+             prefix = \"REQ\"\n\
+             width = 3\n",
+        )
+        .unwrap();
+
+        assert_eq!(policy.hooks.lint, CheckMode::Warn);
+
+        let resolved = policy.for_profile(Some("ci"));
+        assert_eq!(resolved.hooks.lint, CheckMode::Block);
+        assert_eq!(resolved.id_scheme, Some(IdScheme::new("REQ", 3)));
+    }
+}
@@ -0,0 +1,203 @@
+//! Per-run coverage snapshots keyed by a caller-supplied label (a commit
+//! hash, a CI run id, anything that identifies when the snapshot was
+//! taken), and a gate that flags any requirement that regressed from
+//! covered to uncovered since a chosen baseline run.
+//! [`super::snapshot::Snapshot`] only tracks the matrix's aggregate
+//! percentage, which can hold steady while one requirement quietly loses
+//! its only test as another gains one — this tracks coverage
+//! per-requirement so that kind of regression doesn't hide in the average.
+
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::CoverageMatrix;
+
+/// One run's per-requirement coverage: whether each requirement in the
+/// spec at the time had at least one covering test.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoverageSnapshot {
+    pub run: String,
+    pub covered: BTreeMap<String, bool>,
+}
+
+impl CoverageSnapshot {
+    /// Builds a snapshot from `matrix`, labelled `run`.
+    pub fn build(matrix: &CoverageMatrix, run: &str) -> Self {
+        let covered = matrix
+            .rows
+            .iter()
+            .map(|row| (row.requirement.clone(), !row.tests.is_empty()))
+            .collect();
+        Self { run: run.to_string(), covered }
+    }
+}
+
+/// Where coverage snapshots are appended/read, unless overridden by
+/// `LIBSPEC_COVERAGE_HISTORY_FILE` (set this in tests, so parallel test
+/// runs don't clobber each other's file).
+fn history_file_path() -> PathBuf {
+    std::env::var("LIBSPEC_COVERAGE_HISTORY_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("target/libspec-coverage-history.jsonl"))
+}
+
+/// Appends `snapshot`. Failures to write are swallowed, the same as
+/// [`super::record_snapshot`]: a missing or unwritable history file
+/// shouldn't fail the build step taking the snapshot.
+pub fn record_coverage_snapshot(snapshot: &CoverageSnapshot) {
+    let path = history_file_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let Ok(line) = serde_json::to_string(snapshot) else {
+        return;
+    };
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+/// Reads and parses every coverage snapshot ever recorded, oldest first.
+pub fn read_coverage_snapshots() -> Vec<CoverageSnapshot> {
+    let Ok(contents) = std::fs::read_to_string(history_file_path()) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// A requirement that was covered under a baseline snapshot but isn't
+/// anymore.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub requirement: String,
+}
+
+/// Compares `current` against `baseline`, one [`Regression`] per
+/// requirement covered under `baseline` that `current` doesn't cover (or
+/// has dropped from the spec entirely). A requirement that's newly added
+/// and uncovered isn't a regression — it never had coverage to lose.
+pub fn regressions(baseline: &CoverageSnapshot, current: &CoverageSnapshot) -> Vec<Regression> {
+    baseline
+        .covered
+        .iter()
+        .filter(|(_, &was_covered)| was_covered)
+        .filter(|(id, _)| !current.covered.get(id.as_str()).copied().unwrap_or(false))
+        .map(|(id, _)| Regression { requirement: id.clone() })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Requirement, SpecDocument};
+    use crate::trace::Record;
+
+    fn with_history_file<T>(f: impl FnOnce() -> T) -> T {
+        let path = std::env::temp_dir().join(format!(
+            "libspec-coverage-history-test-{:?}.jsonl",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        std::env::set_var("LIBSPEC_COVERAGE_HISTORY_FILE", &path);
+        let result = f();
+        std::env::remove_var("LIBSPEC_COVERAGE_HISTORY_FILE");
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+
+    fn doc_with_requirements() -> SpecDocument {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement { id: "REQ-004".into(), text: "balance".into(), ..Default::default() });
+        doc.requirements.push(Requirement { id: "REQ-005".into(), text: "withdraw".into(), ..Default::default() });
+        doc
+    }
+
+    #[test]
+    fn builds_a_snapshot_from_a_coverage_matrix() {
+        let doc = doc_with_requirements();
+        let records = vec![Record { kind: "covers".into(), function: "test_balance".into(), requirement: "REQ-004".into() }];
+        let matrix = CoverageMatrix::build(&doc, &records);
+
+        let snapshot = CoverageSnapshot::build(&matrix, "abc123");
+        assert_eq!(snapshot.run, "abc123");
+        assert_eq!(snapshot.covered.get("REQ-004"), Some(&true));
+        assert_eq!(snapshot.covered.get("REQ-005"), Some(&false));
+    }
+
+    #[test]
+    fn appends_and_reads_back_a_coverage_snapshot() {
+        with_history_file(|| {
+            let doc = doc_with_requirements();
+            let matrix = CoverageMatrix::build(&doc, &[]);
+            let snapshot = CoverageSnapshot::build(&matrix, "abc123");
+
+            record_coverage_snapshot(&snapshot);
+            let snapshots = read_coverage_snapshots();
+            assert_eq!(snapshots, vec![snapshot]);
+        });
+    }
+
+    #[test]
+    fn flags_a_requirement_that_regressed_from_covered_to_uncovered() {
+        let doc = doc_with_requirements();
+        let baseline = CoverageSnapshot::build(
+            &CoverageMatrix::build(
+                &doc,
+                &[Record { kind: "covers".into(), function: "test_balance".into(), requirement: "REQ-004".into() }],
+            ),
+            "baseline",
+        );
+        let current = CoverageSnapshot::build(&CoverageMatrix::build(&doc, &[]), "head");
+
+        assert_eq!(regressions(&baseline, &current), vec![Regression { requirement: "REQ-004".into() }]);
+    }
+
+    #[test]
+    fn a_requirement_that_gains_coverage_is_not_a_regression() {
+        let doc = doc_with_requirements();
+        let baseline = CoverageSnapshot::build(&CoverageMatrix::build(&doc, &[]), "baseline");
+        let current = CoverageSnapshot::build(
+            &CoverageMatrix::build(
+                &doc,
+                &[Record { kind: "covers".into(), function: "test_balance".into(), requirement: "REQ-004".into() }],
+            ),
+            "head",
+        );
+
+        assert!(regressions(&baseline, &current).is_empty());
+    }
+
+    #[test]
+    fn a_requirement_dropped_from_the_spec_still_counts_as_regressed() {
+        let doc = doc_with_requirements();
+        let baseline = CoverageSnapshot::build(
+            &CoverageMatrix::build(
+                &doc,
+                &[Record { kind: "covers".into(), function: "test_balance".into(), requirement: "REQ-004".into() }],
+            ),
+            "baseline",
+        );
+        let mut narrowed = doc.clone();
+        narrowed.requirements.retain(|r| r.id != "REQ-004");
+        let current = CoverageSnapshot::build(&CoverageMatrix::build(&narrowed, &[]), "head");
+
+        assert_eq!(regressions(&baseline, &current), vec![Regression { requirement: "REQ-004".into() }]);
+    }
+
+    #[test]
+    fn no_regressions_between_identical_snapshots() {
+        let doc = doc_with_requirements();
+        let records = vec![Record { kind: "covers".into(), function: "test_balance".into(), requirement: "REQ-004".into() }];
+        let matrix = CoverageMatrix::build(&doc, &records);
+        let snapshot = CoverageSnapshot::build(&matrix, "run");
+
+        assert!(regressions(&snapshot, &snapshot).is_empty());
+    }
+}
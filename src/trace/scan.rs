@@ -0,0 +1,207 @@
+//! Walks a source tree looking for spec ids mentioned in code — the
+//! `"CONST-003: Invalid ID"`-style string literals and `// REQ-004`
+//! comments that, before this module, only a human skimming the tree
+//! could find. Feeds the traceability exporters with mentions the
+//! `#[covers]`/`#[implements]` macros don't capture, since those only see
+//! functions that were explicitly tagged.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::spec::SpecDocument;
+
+/// One occurrence of a spec id found in a source file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mention {
+    pub id: String,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// Walks `root` recursively (skipping `target` and hidden directories) and
+/// returns one [`Mention`] per line of every `.rs` file containing one of
+/// `doc`'s requirement or constraint ids. Best-effort, like
+/// [`method_name`](crate::codegen::method_name): it doesn't parse the
+/// file, so a mention inside a doc comment, a disabled `#[cfg(...)]`
+/// block, or an unrelated identifier that happens to contain an id as a
+/// substring is reported the same as a real traceability link. Same as
+/// [`scan_with_threads`]`(root, doc, None)`.
+pub fn scan(root: &Path, doc: &SpecDocument) -> Vec<Mention> {
+    scan_with_threads(root, doc, None)
+}
+
+/// Same as [`scan`], behind the `parallel` feature run across files on a
+/// rayon pool sized by `threads` (see [`crate::parallel::run`]) —
+/// without it, `threads` is ignored and every file is scanned
+/// sequentially, the same as before this knob existed. Mentions come
+/// back in the same file-then-line order [`scan`] always used,
+/// regardless of which file's scan actually finishes first.
+pub fn scan_with_threads(root: &Path, doc: &SpecDocument, threads: Option<usize>) -> Vec<Mention> {
+    let ids: Vec<&str> = doc
+        .requirements
+        .iter()
+        .map(|r| r.id.as_str())
+        .chain(doc.constraints.iter().map(|c| c.code.as_str()))
+        .collect();
+
+    let mut files = Vec::new();
+    collect_rust_files(root, &mut files);
+
+    #[cfg(feature = "parallel")]
+    {
+        crate::parallel::run(threads, || scan_files_parallel(&files, &ids))
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        let _ = threads;
+        scan_files_sequential(&files, &ids)
+    }
+}
+
+fn mentions_in_file(file: &Path, ids: &[&str]) -> Vec<Mention> {
+    let Ok(contents) = std::fs::read_to_string(file) else {
+        return Vec::new();
+    };
+    let mut mentions = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        for id in ids {
+            if line.contains(id) {
+                mentions.push(Mention {
+                    id: id.to_string(),
+                    file: file.to_path_buf(),
+                    line: i + 1,
+                });
+            }
+        }
+    }
+    mentions
+}
+
+#[cfg(not(feature = "parallel"))]
+fn scan_files_sequential(files: &[PathBuf], ids: &[&str]) -> Vec<Mention> {
+    files.iter().flat_map(|file| mentions_in_file(file, ids)).collect()
+}
+
+#[cfg(feature = "parallel")]
+fn scan_files_parallel(files: &[PathBuf], ids: &[&str]) -> Vec<Mention> {
+    use rayon::prelude::*;
+    files
+        .par_iter()
+        .map(|file| mentions_in_file(file, ids))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// A cheap stand-in for `root`'s content hash: every `.rs` file [`scan`]
+/// would visit, with its path, length, and modification time, hashed
+/// together. Computed from filesystem metadata alone (no file is read),
+/// so a caller can tell whether a cached [`scan`] result is still good
+/// without paying for the read-every-line work `scan` itself does.
+pub fn fingerprint(root: &Path) -> u64 {
+    let mut files = Vec::new();
+    collect_rust_files(root, &mut files);
+    files.sort();
+
+    let mut hasher = DefaultHasher::new();
+    for file in files {
+        file.hash(&mut hasher);
+        if let Ok(metadata) = std::fs::metadata(&file) {
+            metadata.len().hash(&mut hasher);
+            if let Ok(modified) = metadata.modified() {
+                modified.hash(&mut hasher);
+            }
+        }
+    }
+    hasher.finish()
+}
+
+fn collect_rust_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if name == "target" || name.starts_with('.') {
+                continue;
+            }
+            collect_rust_files(&path, out);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            out.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Constraint, Requirement};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("libspec-scan-test-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn finds_a_mention_with_its_line_number() {
+        let dir = temp_dir("mention");
+        std::fs::write(
+            dir.join("lib.rs"),
+            "fn check() {\n    // REQ-004: balance must be non-negative\n    Err(\"CONST-003: Invalid ID\")\n}\n",
+        )
+        .unwrap();
+
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "balance must be non-negative".into(),
+            ..Default::default()
+        });
+        doc.constraints.push(Constraint {
+            code: "CONST-003".into(),
+            text: "Invalid ID".into(),
+            ..Default::default()
+        });
+
+        let mentions = scan(&dir, &doc);
+        assert!(mentions.contains(&Mention {
+            id: "REQ-004".into(),
+            file: dir.join("lib.rs"),
+            line: 2,
+        }));
+        assert!(mentions.contains(&Mention {
+            id: "CONST-003".into(),
+            file: dir.join("lib.rs"),
+            line: 3,
+        }));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn skips_the_target_directory() {
+        let dir = temp_dir("skip-target");
+        let target = dir.join("target");
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(target.join("generated.rs"), "// REQ-004\n").unwrap();
+
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "irrelevant".into(),
+            ..Default::default()
+        });
+
+        assert!(scan(&dir, &doc).is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,130 @@
+//! Sign-off records for requirements verified some way other than an
+//! automated test — inspection, analysis, or demonstration — persisted
+//! over time the same way [`super::record`]'s trace artifact is, so
+//! [`CoverageMatrix::enforce`](super::CoverageMatrix::enforce) has
+//! something to check a [`VerificationMethod::Inspection`]-style
+//! requirement against instead of demanding a test it was never meant to
+//! have.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::spec::VerificationMethod;
+
+/// One requirement checked off by a non-test [`VerificationMethod`]:
+/// `signed_by` names whoever performed the inspection/analysis/
+/// demonstration, and `note` records what they found.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignOff {
+    pub requirement: String,
+    pub method: VerificationMethod,
+    pub signed_by: String,
+    pub note: String,
+}
+
+/// Where sign-offs are appended/read, unless overridden by
+/// `LIBSPEC_SIGNOFF_FILE` (set this in tests, so parallel test runs don't
+/// clobber each other's file).
+fn signoff_file_path() -> PathBuf {
+    std::env::var("LIBSPEC_SIGNOFF_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("target/libspec-signoffs.jsonl"))
+}
+
+/// Appends a sign-off record. Failures to write are swallowed, the same
+/// as [`super::record`]: a missing or unwritable sign-off file shouldn't
+/// fail the review step recording it.
+pub fn record(requirement: &str, method: VerificationMethod, signed_by: &str, note: &str) {
+    let path = signoff_file_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let signoff = SignOff {
+        requirement: requirement.to_string(),
+        method,
+        signed_by: signed_by.to_string(),
+        note: note.to_string(),
+    };
+    let Ok(line) = serde_json::to_string(&signoff) else {
+        return;
+    };
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+/// Records an inspection sign-off — the common case of [`record`] a
+/// reviewer reaches for by name rather than spelling out
+/// [`VerificationMethod::Inspection`] themselves.
+pub fn record_inspection(requirement: &str, signed_by: &str, note: &str) {
+    record(requirement, VerificationMethod::Inspection, signed_by, note);
+}
+
+/// Reads and parses every sign-off ever recorded, oldest first, skipping
+/// lines that aren't valid JSON.
+pub fn read_sign_offs() -> Vec<SignOff> {
+    let Ok(contents) = std::fs::read_to_string(signoff_file_path()) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_signoff_file<T>(f: impl FnOnce() -> T) -> T {
+        let path = std::env::temp_dir().join(format!(
+            "libspec-signoff-test-{:?}.jsonl",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        std::env::set_var("LIBSPEC_SIGNOFF_FILE", &path);
+        let result = f();
+        std::env::remove_var("LIBSPEC_SIGNOFF_FILE");
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+
+    #[test]
+    fn appends_and_reads_back_a_sign_off() {
+        with_signoff_file(|| {
+            record("REQ-004", VerificationMethod::Analysis, "alice", "worked through the proof");
+
+            let sign_offs = read_sign_offs();
+            assert_eq!(
+                sign_offs,
+                vec![SignOff {
+                    requirement: "REQ-004".into(),
+                    method: VerificationMethod::Analysis,
+                    signed_by: "alice".into(),
+                    note: "worked through the proof".into(),
+                }]
+            );
+        });
+    }
+
+    #[test]
+    fn record_inspection_records_the_inspection_method() {
+        with_signoff_file(|| {
+            record_inspection("REQ-005", "bob", "read the diff, looks right");
+
+            let sign_offs = read_sign_offs();
+            assert_eq!(sign_offs[0].method, VerificationMethod::Inspection);
+            assert_eq!(sign_offs[0].signed_by, "bob");
+        });
+    }
+
+    #[test]
+    fn reading_a_missing_file_is_an_empty_list() {
+        with_signoff_file(|| {
+            assert_eq!(read_sign_offs(), Vec::new());
+        });
+    }
+}
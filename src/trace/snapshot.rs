@@ -0,0 +1,105 @@
+//! Coverage percentage snapshots, persisted over time (mirroring
+//! [`super::record`]'s JSON-lines artifact) so the HTML dashboard can chart
+//! a coverage trend instead of just a single point-in-time number.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::{percentage, CoverageMatrix};
+
+/// One point of a coverage trend: `matrix`'s coverage percentage at
+/// `timestamp_unix` seconds since the Unix epoch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub timestamp_unix: u64,
+    pub percentage: f64,
+}
+
+/// Where snapshots are appended/read, unless overridden by
+/// `LIBSPEC_SNAPSHOT_FILE` (set this in tests, so parallel test runs don't
+/// clobber each other's file).
+fn snapshot_file_path() -> PathBuf {
+    std::env::var("LIBSPEC_SNAPSHOT_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("target/libspec-snapshots.jsonl"))
+}
+
+/// Appends `matrix`'s current coverage percentage as a snapshot. Failures
+/// to write are swallowed, the same as [`super::record`]: a missing or
+/// unwritable snapshot file shouldn't fail the build step taking the
+/// snapshot.
+pub fn record_snapshot(matrix: &CoverageMatrix) {
+    let snapshot = Snapshot {
+        timestamp_unix: now_unix(),
+        percentage: percentage(matrix),
+    };
+    let path = snapshot_file_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let Ok(line) = serde_json::to_string(&snapshot) else {
+        return;
+    };
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+/// Reads and parses every snapshot ever recorded, oldest first.
+pub fn read_snapshots() -> Vec<Snapshot> {
+    let Ok(contents) = std::fs::read_to_string(snapshot_file_path()) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{Requirement, SpecDocument};
+
+    fn with_snapshot_file<T>(f: impl FnOnce() -> T) -> T {
+        let path = std::env::temp_dir().join(format!(
+            "libspec-snapshot-test-{:?}.jsonl",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        std::env::set_var("LIBSPEC_SNAPSHOT_FILE", &path);
+        let result = f();
+        std::env::remove_var("LIBSPEC_SNAPSHOT_FILE");
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+
+    #[test]
+    fn appends_and_reads_back_a_snapshot() {
+        with_snapshot_file(|| {
+            let mut doc = SpecDocument::new();
+            doc.requirements.push(Requirement {
+                id: "REQ-1".into(),
+                text: "text".into(),
+                ..Default::default()
+            });
+            let matrix = CoverageMatrix::build(&doc, &[]);
+
+            record_snapshot(&matrix);
+            let snapshots = read_snapshots();
+            assert_eq!(snapshots.len(), 1);
+            assert_eq!(snapshots[0].percentage, 0.0);
+        });
+    }
+}
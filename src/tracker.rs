@@ -0,0 +1,205 @@
+//! Links requirements to external issue trackers via their `tracker` field
+//! (e.g. `tracker = "github:drhodes/libspec#42"`) and [`sync`]s a
+//! document's requirement statuses against where those issues actually
+//! stand — the spec shouldn't say `draft` once the ticket closes, or
+//! `implemented` while it's still open.
+
+use std::fmt;
+
+use crate::spec::{SpecDocument, Status};
+
+/// A parsed `tracker` reference: `{provider}:{slug}#{number}`, e.g.
+/// `github:drhodes/libspec#42`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackerRef {
+    pub provider: String,
+    pub slug: String,
+    pub number: u64,
+}
+
+/// A `tracker` field that isn't `{provider}:{slug}#{number}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackerParseError {
+    pub reference: String,
+}
+
+impl fmt::Display for TrackerParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` isn't a `provider:slug#number` tracker reference",
+            self.reference
+        )
+    }
+}
+
+impl std::error::Error for TrackerParseError {}
+
+/// Parses a `tracker` field into a [`TrackerRef`].
+pub fn parse(reference: &str) -> Result<TrackerRef, TrackerParseError> {
+    let err = || TrackerParseError { reference: reference.to_string() };
+    let (provider, rest) = reference.split_once(':').ok_or_else(err)?;
+    let (slug, number) = rest.rsplit_once('#').ok_or_else(err)?;
+    let number = number.parse().map_err(|_| err())?;
+    if provider.is_empty() || slug.is_empty() {
+        return Err(err());
+    }
+    Ok(TrackerRef { provider: provider.to_string(), slug: slug.to_string(), number })
+}
+
+/// Looks up whether an issue tracked by a [`TrackerRef`] is closed.
+/// Implement this against whatever a project already uses to talk to its
+/// tracker; `sync` doesn't know or care how.
+pub trait IssueStatusSource {
+    /// `None` if the issue's status couldn't be determined (network error,
+    /// unknown provider, issue not found) — `sync` skips such references
+    /// rather than treating "unknown" as a discrepancy.
+    fn is_closed(&self, tracker: &TrackerRef) -> Option<bool>;
+}
+
+/// A requirement whose `tracker`-referenced issue's open/closed state
+/// disagrees with its own `status`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Discrepancy {
+    pub requirement: String,
+    pub tracker: String,
+    pub issue_closed: bool,
+    pub status: Status,
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is {:?} but its tracker issue {} is {}",
+            self.requirement,
+            self.status,
+            self.tracker,
+            if self.issue_closed { "closed" } else { "open" }
+        )
+    }
+}
+
+impl std::error::Error for Discrepancy {}
+
+/// Checks every requirement in `doc` with a `tracker` field against
+/// `source`, reporting a [`Discrepancy`] wherever an issue is closed while
+/// the requirement is still `draft`, or open while the requirement is
+/// marked `implemented`. Requirements with no `tracker`, an unparsable
+/// one, or one `source` has no answer for are silently skipped.
+pub fn sync(doc: &SpecDocument, source: &dyn IssueStatusSource) -> Vec<Discrepancy> {
+    let mut discrepancies = Vec::new();
+    for req in &doc.requirements {
+        let Some(tracker) = &req.tracker else { continue };
+        let Ok(tracker_ref) = parse(tracker) else { continue };
+        let Some(issue_closed) = source.is_closed(&tracker_ref) else { continue };
+
+        let disagrees = (issue_closed && req.status == Status::Draft)
+            || (!issue_closed && req.status == Status::Implemented);
+        if disagrees {
+            discrepancies.push(Discrepancy {
+                requirement: req.id.clone(),
+                tracker: tracker.clone(),
+                issue_closed,
+                status: req.status,
+            });
+        }
+    }
+    discrepancies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    struct FixedSource(bool);
+
+    impl IssueStatusSource for FixedSource {
+        fn is_closed(&self, _tracker: &TrackerRef) -> Option<bool> {
+            Some(self.0)
+        }
+    }
+
+    struct UnknownSource;
+
+    impl IssueStatusSource for UnknownSource {
+        fn is_closed(&self, _tracker: &TrackerRef) -> Option<bool> {
+            None
+        }
+    }
+
+    fn req_with_tracker(status: Status, tracker: &str) -> Requirement {
+        Requirement {
+            id: "REQ-1".into(),
+            text: "text".into(),
+            status,
+            tracker: Some(tracker.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_a_tracker_reference() {
+        assert_eq!(
+            parse("github:drhodes/libspec#42").unwrap(),
+            TrackerRef { provider: "github".into(), slug: "drhodes/libspec".into(), number: 42 }
+        );
+    }
+
+    #[test]
+    fn rejects_a_reference_missing_a_number() {
+        assert!(parse("github:drhodes/libspec").is_err());
+    }
+
+    #[test]
+    fn flags_a_closed_issue_on_a_draft_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req_with_tracker(Status::Draft, "github:drhodes/libspec#42"));
+
+        let discrepancies = sync(&doc, &FixedSource(true));
+        assert_eq!(
+            discrepancies,
+            vec![Discrepancy {
+                requirement: "REQ-1".into(),
+                tracker: "github:drhodes/libspec#42".into(),
+                issue_closed: true,
+                status: Status::Draft,
+            }]
+        );
+    }
+
+    #[test]
+    fn flags_an_open_issue_on_an_implemented_requirement() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req_with_tracker(Status::Implemented, "github:drhodes/libspec#42"));
+
+        let discrepancies = sync(&doc, &FixedSource(false));
+        assert_eq!(discrepancies.len(), 1);
+    }
+
+    #[test]
+    fn approved_requirements_tolerate_either_issue_state() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req_with_tracker(Status::Approved, "github:drhodes/libspec#42"));
+
+        assert!(sync(&doc, &FixedSource(true)).is_empty());
+        assert!(sync(&doc, &FixedSource(false)).is_empty());
+    }
+
+    #[test]
+    fn unknown_issue_status_is_skipped() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(req_with_tracker(Status::Draft, "github:drhodes/libspec#42"));
+
+        assert!(sync(&doc, &UnknownSource).is_empty());
+    }
+
+    #[test]
+    fn requirements_without_a_tracker_are_skipped() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement { id: "REQ-1".into(), text: "text".into(), ..Default::default() });
+
+        assert!(sync(&doc, &FixedSource(true)).is_empty());
+    }
+}
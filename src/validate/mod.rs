@@ -0,0 +1,110 @@
+//! Meta-schema validation: checks a [`SpecDocument`](crate::spec::SpecDocument)'s
+//! own shape is well-formed (non-empty, well-charactered ids) before
+//! anything downstream (codegen, linting, traceability) trusts it.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::spec::SpecDocument;
+
+/// A document field that doesn't satisfy the meta-schema.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "error", rename_all = "kebab-case")]
+pub enum MetaSchemaError {
+    EmptyId { kind: &'static str, index: usize },
+    InvalidIdChars { kind: &'static str, id: String },
+}
+
+impl fmt::Display for MetaSchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetaSchemaError::EmptyId { kind, index } => {
+                write!(f, "{kind} #{index} has an empty id")
+            }
+            MetaSchemaError::InvalidIdChars { kind, id } => {
+                write!(
+                    f,
+                    "{kind} id `{id}` must contain only letters, digits, `-`, and `_`"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MetaSchemaError {}
+
+fn valid_id_chars(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn check_id(kind: &'static str, index: usize, id: &str, errors: &mut Vec<MetaSchemaError>) {
+    if id.trim().is_empty() {
+        errors.push(MetaSchemaError::EmptyId { kind, index });
+    } else if !valid_id_chars(id) {
+        errors.push(MetaSchemaError::InvalidIdChars {
+            kind,
+            id: id.to_string(),
+        });
+    }
+}
+
+/// Validates `doc` against the spec meta-schema, returning every violation
+/// found (rather than stopping at the first).
+pub fn validate(doc: &SpecDocument) -> Result<(), Vec<MetaSchemaError>> {
+    let mut errors = Vec::new();
+    for (i, req) in doc.requirements.iter().enumerate() {
+        check_id("requirement", i, &req.id, &mut errors);
+    }
+    for (i, constraint) in doc.constraints.iter().enumerate() {
+        check_id("constraint", i, &constraint.code, &mut errors);
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Requirement;
+
+    #[test]
+    fn accepts_well_formed_ids() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "REQ-004".into(),
+            text: "ok".into(),
+            ..Default::default()
+        });
+        assert!(validate(&doc).is_ok());
+    }
+
+    #[test]
+    fn rejects_empty_and_invalid_ids() {
+        let mut doc = SpecDocument::new();
+        doc.requirements.push(Requirement {
+            id: "".into(),
+            text: "ok".into(),
+            ..Default::default()
+        });
+        doc.requirements.push(Requirement {
+            id: "REQ 004".into(),
+            text: "ok".into(),
+            ..Default::default()
+        });
+
+        let errors = validate(&doc).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0],
+            MetaSchemaError::EmptyId { index: 0, .. }
+        ));
+        assert!(matches!(errors[1], MetaSchemaError::InvalidIdChars { .. }));
+    }
+}